@@ -1,16 +1,41 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use std::{fmt::Display, io::Result};
+use crate::progress::Event;
+use std::{
+	fmt::Display,
+	io::Result,
+	sync::atomic::{AtomicBool, Ordering},
+};
 #[cfg(test)]
 pub(crate) use tests::MockCli;
 
-pub(crate) mod traits {
+/// Whether output is limited to final results and errors.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether output is limited to final results and errors, suppressing spinners, intros and
+/// other decorative output (e.g. for readable CI logs). Any remaining messages are printed as plain
+/// text, with warnings and errors printed to stderr.
+///
+/// # Arguments
+/// * `quiet` - Whether output should be quiet.
+pub(crate) fn set_quiet(quiet: bool) {
+	QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether output is limited to final results and errors.
+pub(crate) fn is_quiet() -> bool {
+	QUIET.load(Ordering::Relaxed)
+}
+
+pub mod traits {
 	use std::{fmt::Display, io::Result};
 
 	/// A command line interface.
 	pub trait Cli {
 		/// Constructs a new [`Confirm`] prompt.
 		fn confirm(&mut self, prompt: impl Display) -> impl Confirm;
+		/// Prints an error message.
+		fn error(&mut self, text: impl Display) -> Result<()>;
 		/// Prints an info message.
 		fn info(&mut self, text: impl Display) -> Result<()>;
 		/// Constructs a new [`Input`] prompt.
@@ -23,8 +48,14 @@ pub(crate) mod traits {
 		fn outro(&mut self, message: impl Display) -> Result<()>;
 		/// Prints a footer of the prompt sequence with a failure style.
 		fn outro_cancel(&mut self, message: impl Display) -> Result<()>;
+		/// Constructs a new [`Password`] prompt.
+		fn password(&mut self, prompt: impl Display) -> impl Password;
+		/// Prints plain text, such as results to be processed by other tools (e.g. JSON).
+		fn plain(&mut self, text: impl Display) -> Result<()>;
 		/// Constructs a new [`Select`] prompt.
 		fn select<T: Clone + Eq>(&mut self, prompt: impl Display) -> impl Select<T>;
+		/// Constructs a new [`Spinner`].
+		fn spinner(&mut self) -> impl Spinner + Send + 'static;
 		/// Prints a success message.
 		fn success(&mut self, message: impl Display) -> Result<()>;
 		/// Prints a warning message.
@@ -56,6 +87,14 @@ pub(crate) mod traits {
 		) -> Self;
 	}
 
+	/// A password prompt, masking the input.
+	pub trait Password {
+		/// Allows an empty password, which is otherwise not allowed.
+		fn allow_empty(self) -> Self;
+		/// Starts the prompt interaction.
+		fn interact(&mut self) -> Result<String>;
+	}
+
 	/// A multi-select prompt.
 	pub trait MultiSelect<T> {
 		/// Starts the prompt interaction.
@@ -75,18 +114,45 @@ pub(crate) mod traits {
 		/// Adds an item to the selection prompt.
 		fn item(self, value: T, label: impl Display, hint: impl Display) -> Self;
 	}
+
+	/// A spinner, indicating the progress of a long-running task.
+	pub trait Spinner {
+		/// Clears the spinner.
+		fn clear(&self);
+		/// Stops the spinner with an error message.
+		fn error(&self, message: impl Display);
+		/// Sets the message of the spinner.
+		fn set_message(&self, message: impl Display);
+		/// Starts the spinner with a message.
+		fn start(&self, message: impl Display);
+		/// Stops the spinner with a message.
+		fn stop(&self, message: impl Display);
+	}
 }
 
 /// A command line interface using cliclack.
-pub(crate) struct Cli;
+pub struct Cli;
 impl traits::Cli for Cli {
 	/// Constructs a new [`Confirm`] prompt.
 	fn confirm(&mut self, prompt: impl Display) -> impl traits::Confirm {
 		Confirm(cliclack::confirm(prompt))
 	}
 
+	/// Prints an error message.
+	fn error(&mut self, text: impl Display) -> Result<()> {
+		if is_quiet() {
+			eprintln!("{text}");
+			return Ok(());
+		}
+		cliclack::log::error(text)
+	}
+
 	/// Prints an info message.
 	fn info(&mut self, text: impl Display) -> Result<()> {
+		if is_quiet() {
+			println!("{text}");
+			return Ok(());
+		}
 		cliclack::log::info(text)
 	}
 
@@ -97,6 +163,9 @@ impl traits::Cli for Cli {
 
 	/// Prints a header of the prompt sequence.
 	fn intro(&mut self, title: impl Display) -> Result<()> {
+		if is_quiet() {
+			return Ok(());
+		}
 		cliclack::clear_screen()?;
 		cliclack::set_theme(crate::style::Theme);
 		cliclack::intro(format!("{}: {title}", console::style(" Pop CLI ").black().on_magenta()))
@@ -109,26 +178,58 @@ impl traits::Cli for Cli {
 
 	/// Prints a footer of the prompt sequence.
 	fn outro(&mut self, message: impl Display) -> Result<()> {
+		if is_quiet() {
+			println!("{message}");
+			return Ok(());
+		}
 		cliclack::outro(message)
 	}
 
 	/// Prints a footer of the prompt sequence with a failure style.
 	fn outro_cancel(&mut self, message: impl Display) -> Result<()> {
+		if is_quiet() {
+			eprintln!("{message}");
+			return Ok(());
+		}
 		cliclack::outro_cancel(message)
 	}
 
+	/// Constructs a new [`Password`] prompt.
+	fn password(&mut self, prompt: impl Display) -> impl traits::Password {
+		Password(cliclack::password(prompt).mask('▪'))
+	}
+
+	/// Prints plain text to the standard output, regardless of whether output is quiet.
+	fn plain(&mut self, text: impl Display) -> Result<()> {
+		println!("{text}");
+		Ok(())
+	}
+
 	/// Constructs a new [`Select`] prompt.
 	fn select<T: Clone + Eq>(&mut self, prompt: impl Display) -> impl traits::Select<T> {
 		Select::<T>(cliclack::select(prompt))
 	}
 
+	/// Constructs a new [`Spinner`], which only outputs its final message when quiet.
+	fn spinner(&mut self) -> impl traits::Spinner + Send + 'static {
+		Spinner((!is_quiet()).then(cliclack::spinner))
+	}
+
 	/// Prints a success message.
 	fn success(&mut self, message: impl Display) -> Result<()> {
+		if is_quiet() {
+			println!("{message}");
+			return Ok(());
+		}
 		cliclack::log::success(message)
 	}
 
 	/// Prints a warning message.
 	fn warning(&mut self, message: impl Display) -> Result<()> {
+		if is_quiet() {
+			eprintln!("{message}");
+			return Ok(());
+		}
 		cliclack::log::warning(message)
 	}
 }
@@ -180,6 +281,20 @@ impl traits::Input for Input {
 	}
 }
 
+/// A password prompt using cliclack.
+struct Password(cliclack::Password);
+impl traits::Password for Password {
+	/// Allows an empty password, which is otherwise not allowed.
+	fn allow_empty(self) -> Self {
+		Self(self.0.allow_empty())
+	}
+
+	/// Starts the prompt interaction.
+	fn interact(&mut self) -> Result<String> {
+		self.0.interact()
+	}
+}
+
 /// A multi-select prompt using cliclack.
 struct MultiSelect<T: Clone + Eq>(cliclack::MultiSelect<T>);
 
@@ -224,6 +339,59 @@ impl<T: Clone + Eq> traits::Select<T> for Select<T> {
 	}
 }
 
+/// A spinner using cliclack, or only its final message when quiet. Any progress is also emitted
+/// as progress events, when enabled.
+struct Spinner(Option<cliclack::ProgressBar>);
+
+impl traits::Spinner for Spinner {
+	/// Clears the spinner.
+	fn clear(&self) {
+		Event::new("stop").emit();
+		if let Some(spinner) = &self.0 {
+			spinner.clear()
+		}
+	}
+
+	/// Stops the spinner with an error message.
+	fn error(&self, message: impl Display) {
+		Event::new("error").message(&message).emit();
+		match &self.0 {
+			Some(spinner) => spinner.error(message),
+			None => eprintln!("{message}"),
+		}
+	}
+
+	/// Sets the message of the spinner.
+	fn set_message(&self, message: impl Display) {
+		Event::new("update").message(&message).emit();
+		if let Some(spinner) = &self.0 {
+			spinner.set_message(message)
+		}
+	}
+
+	/// Starts the spinner with a message.
+	fn start(&self, message: impl Display) {
+		Event::new("start").message(&message).emit();
+		if let Some(spinner) = &self.0 {
+			spinner.start(message)
+		}
+	}
+
+	/// Stops the spinner with a message.
+	fn stop(&self, message: impl Display) {
+		Event::new("stop").message(&message).emit();
+		match &self.0 {
+			Some(spinner) => spinner.stop(message),
+			None => {
+				let message = message.to_string();
+				if !message.is_empty() {
+					println!("{message}")
+				}
+			},
+		}
+	}
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
 	use super::traits::*;
@@ -233,6 +401,7 @@ pub(crate) mod tests {
 	#[derive(Default)]
 	pub(crate) struct MockCli {
 		confirm_expectation: Vec<(String, bool)>,
+		error_expectations: Vec<String>,
 		info_expectations: Vec<String>,
 		input_expectations: Vec<(String, String)>,
 		intro_expectation: Option<String>,
@@ -240,6 +409,8 @@ pub(crate) mod tests {
 		multiselect_expectation:
 			Option<(String, Option<bool>, bool, Option<Vec<(String, String)>>)>,
 		outro_cancel_expectation: Option<String>,
+		password_expectations: Vec<(String, String)>,
+		plain_expectations: Vec<String>,
 		select_expectation: Vec<(String, Option<bool>, bool, Option<Vec<(String, String)>>, usize)>,
 		success_expectations: Vec<String>,
 		warning_expectations: Vec<String>,
@@ -255,6 +426,11 @@ pub(crate) mod tests {
 			self
 		}
 
+		pub(crate) fn expect_error(mut self, message: impl Display) -> Self {
+			self.error_expectations.push(message.to_string());
+			self
+		}
+
 		pub(crate) fn expect_input(mut self, prompt: impl Display, input: String) -> Self {
 			self.input_expectations.insert(0, (prompt.to_string(), input));
 			self
@@ -291,6 +467,16 @@ pub(crate) mod tests {
 			self
 		}
 
+		pub(crate) fn expect_password(mut self, prompt: impl Display, password: &str) -> Self {
+			self.password_expectations.insert(0, (prompt.to_string(), password.to_string()));
+			self
+		}
+
+		pub(crate) fn expect_plain(mut self, text: impl Display) -> Self {
+			self.plain_expectations.push(text.to_string());
+			self
+		}
+
 		pub(crate) fn expect_select(
 			mut self,
 			prompt: impl Display,
@@ -319,6 +505,12 @@ pub(crate) mod tests {
 			if !self.confirm_expectation.is_empty() {
 				panic!("`{:?}` confirm expectations not satisfied", self.confirm_expectation)
 			}
+			if !self.error_expectations.is_empty() {
+				panic!(
+					"`{}` error log expectations not satisfied",
+					self.error_expectations.join(",")
+				)
+			}
 			if !self.info_expectations.is_empty() {
 				panic!("`{}` info log expectations not satisfied", self.info_expectations.join(","))
 			}
@@ -337,6 +529,15 @@ pub(crate) mod tests {
 			if let Some(expectation) = self.outro_cancel_expectation {
 				panic!("`{expectation}` outro cancel expectation not satisfied")
 			}
+			if !self.password_expectations.is_empty() {
+				panic!("`{:?}` password expectation not satisfied", self.password_expectations)
+			}
+			if !self.plain_expectations.is_empty() {
+				panic!(
+					"`{}` plain output expectations not satisfied",
+					self.plain_expectations.join(",")
+				)
+			}
 			if !self.select_expectation.is_empty() {
 				panic!(
 					"`{}` select prompt expectation not satisfied",
@@ -373,6 +574,12 @@ pub(crate) mod tests {
 			MockConfirm::default()
 		}
 
+		fn error(&mut self, message: impl Display) -> Result<()> {
+			let message = message.to_string();
+			self.error_expectations.retain(|x| *x != message);
+			Ok(())
+		}
+
 		fn info(&mut self, message: impl Display) -> Result<()> {
 			let message = message.to_string();
 			self.info_expectations.retain(|x| *x != message);
@@ -439,6 +646,21 @@ pub(crate) mod tests {
 			Ok(())
 		}
 
+		fn password(&mut self, prompt: impl Display) -> impl Password {
+			let prompt = prompt.to_string();
+			if let Some((expectation, password)) = self.password_expectations.pop() {
+				assert_eq!(expectation, prompt, "prompt does not satisfy expectation");
+				return MockPassword { password };
+			}
+			MockPassword::default()
+		}
+
+		fn plain(&mut self, text: impl Display) -> Result<()> {
+			let text = text.to_string();
+			self.plain_expectations.retain(|x| *x != text);
+			Ok(())
+		}
+
 		fn select<T: Clone + Eq>(&mut self, prompt: impl Display) -> impl Select<T> {
 			let prompt = prompt.to_string();
 			if let Some((expectation, _, collect, items_expectation, item)) =
@@ -457,6 +679,10 @@ pub(crate) mod tests {
 			MockSelect::default()
 		}
 
+		fn spinner(&mut self) -> impl Spinner + Send + 'static {
+			MockSpinner
+		}
+
 		fn success(&mut self, message: impl Display) -> Result<()> {
 			let message = message.to_string();
 			self.success_expectations.retain(|x| *x != message);
@@ -522,6 +748,22 @@ pub(crate) mod tests {
 		}
 	}
 
+	/// Mock password prompt
+	#[derive(Default)]
+	struct MockPassword {
+		password: String,
+	}
+
+	impl Password for MockPassword {
+		fn allow_empty(self) -> Self {
+			self
+		}
+
+		fn interact(&mut self) -> Result<String> {
+			Ok(self.password.clone())
+		}
+	}
+
 	/// Mock multi-select prompt
 	pub(crate) struct MockMultiSelect<T> {
 		required_expectation: Option<bool>,
@@ -570,6 +812,17 @@ pub(crate) mod tests {
 		}
 	}
 
+	/// Mock spinner
+	struct MockSpinner;
+
+	impl Spinner for MockSpinner {
+		fn clear(&self) {}
+		fn error(&self, _message: impl Display) {}
+		fn set_message(&self, _message: impl Display) {}
+		fn start(&self, _message: impl Display) {}
+		fn stop(&self, _message: impl Display) {}
+	}
+
 	/// Mock select prompt
 	pub(crate) struct MockSelect<T> {
 		items_expectation: Option<Vec<(String, String)>>,
@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, prompt_for_account, prompt_for_amount, prompt_for_id, ChainArgs};
+use crate::{cli::traits::*, common::signer::SignerArgs};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{create_asset, registered_asset, set_asset_metadata};
+
+/// Command to create an asset.
+#[derive(Args, Default)]
+pub(crate) struct CreateCommand {
+	/// The identifier of the asset [default: prompted].
+	#[arg(long)]
+	pub(crate) id: Option<u128>,
+	/// The account administering the asset [default: prompted].
+	#[arg(long)]
+	pub(crate) admin: Option<String>,
+	/// The minimum balance of an account holding the asset, in whole tokens, e.g. `0.01`
+	/// [default: prompted].
+	#[arg(long)]
+	pub(crate) min_balance: Option<String>,
+	/// The name of the asset [default: prompted].
+	#[arg(long)]
+	pub(crate) name: Option<String>,
+	/// The symbol of the asset [default: prompted].
+	#[arg(long)]
+	pub(crate) symbol: Option<String>,
+	/// The number of decimals of the asset [default: prompted].
+	#[arg(long)]
+	pub(crate) decimals: Option<u8>,
+	#[command(flatten)]
+	pub(crate) chain: ChainArgs,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl CreateCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Create an asset")?;
+		let (chain, url) = self.chain.configure(cli)?;
+		let client = connect(&url).await?;
+		let id = prompt_for_id(self.id, cli)?;
+		if registered_asset(&client, id).await?.is_some() {
+			cli.outro_cancel(format!("🚫 The asset {id} already exists on {chain}."))?;
+			return Ok(());
+		}
+		let admin = prompt_for_account(
+			self.admin.as_deref(),
+			"Enter the account administering the asset:",
+			cli,
+		)?;
+		let name = match &self.name {
+			Some(name) => name.clone(),
+			None => cli
+				.input("Enter the name of the asset:")
+				.placeholder("e.g. Tether USD")
+				.interact()?,
+		};
+		let symbol = match &self.symbol {
+			Some(symbol) => symbol.clone(),
+			None => cli
+				.input("Enter the symbol of the asset:")
+				.placeholder("e.g. USDt")
+				.interact()?,
+		};
+		let decimals = match self.decimals {
+			Some(decimals) => decimals,
+			None => cli
+				.input("Enter the number of decimals of the asset:")
+				.default_input("10")
+				.validate(|input| match input.parse::<u8>() {
+					Ok(_) => Ok(()),
+					_ => Err("Invalid number of decimals."),
+				})
+				.interact()?
+				.parse()?,
+		};
+		let min_balance = prompt_for_amount(
+			self.min_balance.as_deref(),
+			decimals,
+			"Enter the minimum balance of an account holding the asset:",
+			cli,
+		)?;
+
+		let xt = create_asset(id, &admin, min_balance);
+		if self.signer.submit(&client, &url, xt, "create the asset", cli).await?.is_none() {
+			return Ok(());
+		}
+		let xt = set_asset_metadata(id, &name, &symbol, decimals);
+		if self
+			.signer
+			.submit(&client, &url, xt, "set the metadata of the asset", cli)
+			.await?
+			.is_none()
+		{
+			return Ok(());
+		}
+		cli.outro(format!(
+			"Asset {id} ({symbol}) created on {chain}: mint it with `pop assets mint --id {id}`."
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn create_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"assets",
+			"create",
+			"--id",
+			"7",
+			"--min-balance",
+			"0.01",
+			"--symbol",
+			"POP",
+			"--decimals",
+			"10",
+			"--url",
+			"ws://localhost:9944",
+		])?;
+		let crate::commands::Command::Assets(args) = pop.command else {
+			panic!("expected the assets command");
+		};
+		let super::super::Command::Create(command) = args.command else {
+			panic!("expected the create command");
+		};
+		assert_eq!(command.id, Some(7));
+		assert_eq!(command.min_balance.as_deref(), Some("0.01"));
+		assert_eq!((command.symbol.as_deref(), command.decimals), (Some("POP"), Some(10)));
+		assert!(command.chain.url.is_some());
+		Ok(())
+	}
+}
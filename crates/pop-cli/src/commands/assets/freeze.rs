@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, existing_asset, prompt_for_account, prompt_for_id, ChainArgs};
+use crate::{cli::traits::*, common::signer::SignerArgs};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::freeze_asset;
+
+/// Command to freeze an asset.
+#[derive(Args, Default)]
+pub(crate) struct FreezeCommand {
+	/// The identifier of the asset [default: prompted].
+	#[arg(long)]
+	pub(crate) id: Option<u128>,
+	/// The account for which the asset is frozen [default: prompted, unless `--all`].
+	#[arg(long)]
+	pub(crate) account: Option<String>,
+	/// Freezes the asset for all accounts.
+	#[arg(long, conflicts_with = "account")]
+	pub(crate) all: bool,
+	#[command(flatten)]
+	pub(crate) chain: ChainArgs,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl FreezeCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Freeze an asset")?;
+		let (chain, url) = self.chain.configure(cli)?;
+		let client = connect(&url).await?;
+		let id = prompt_for_id(self.id, cli)?;
+		let asset = existing_asset(&client, id, &chain).await?;
+		let account = match self.all {
+			true => None,
+			false => Some(prompt_for_account(
+				self.account.as_deref(),
+				"Enter the account for which the asset is frozen:",
+				cli,
+			)?),
+		};
+		let xt = freeze_asset(id, account.as_ref());
+		if self.signer.submit(&client, &url, xt, "freeze the asset", cli).await?.is_none() {
+			return Ok(());
+		}
+		match account {
+			Some(account) =>
+				cli.outro(format!("{} frozen for {account} on {chain}.", asset.symbol))?,
+			None => cli.outro(format!("{} frozen for all accounts on {chain}.", asset.symbol))?,
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn freeze_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "assets", "freeze"].iter().chain(args))?;
+			let crate::commands::Command::Assets(args) = pop.command else {
+				panic!("expected the assets command");
+			};
+			let super::super::Command::Freeze(command) = args.command else {
+				panic!("expected the freeze command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		assert!(parse(&["--id", "1", "--all"])?.all);
+		assert_eq!(parse(&["--account", "5GrwvaEF"])?.account.as_deref(), Some("5GrwvaEF"));
+		// The asset is either frozen for an account or for all accounts.
+		assert!(parse(&["--account", "5GrwvaEF", "--all"]).is_err());
+		Ok(())
+	}
+}
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{
+	connect, existing_asset, prompt_for_account, prompt_for_amount, prompt_for_id, ChainArgs,
+};
+use crate::{cli::traits::*, common::signer::SignerArgs};
+use anyhow::Result;
+use clap::Args;
+use pop_common::units::format_units;
+use pop_parachains::mint_asset;
+
+/// Command to mint an amount of an asset.
+#[derive(Args, Default)]
+pub(crate) struct MintCommand {
+	/// The identifier of the asset [default: prompted].
+	#[arg(long)]
+	pub(crate) id: Option<u128>,
+	/// The account receiving the minted amount [default: prompted].
+	#[arg(long)]
+	pub(crate) to: Option<String>,
+	/// The amount to be minted, in whole tokens, e.g. `1.5` [default: prompted].
+	#[arg(long)]
+	pub(crate) amount: Option<String>,
+	#[command(flatten)]
+	pub(crate) chain: ChainArgs,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl MintCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Mint an asset")?;
+		let (chain, url) = self.chain.configure(cli)?;
+		let client = connect(&url).await?;
+		let id = prompt_for_id(self.id, cli)?;
+		let asset = existing_asset(&client, id, &chain).await?;
+		let to =
+			prompt_for_account(self.to.as_deref(), "Enter the account receiving the asset:", cli)?;
+		let amount = prompt_for_amount(
+			self.amount.as_deref(),
+			asset.decimals,
+			"Enter the amount to mint:",
+			cli,
+		)?;
+		if self
+			.signer
+			.submit(&client, &url, mint_asset(id, &to, amount), "mint the asset", cli)
+			.await?
+			.is_none()
+		{
+			return Ok(());
+		}
+		cli.outro(format!(
+			"Minted {} {} to {to} on {chain}.",
+			format_units(amount, asset.decimals),
+			asset.symbol
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+	use pop_parachains::Chain;
+
+	#[test]
+	fn mint_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"assets",
+			"m",
+			"--id",
+			"1984",
+			"--to",
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+			"--amount",
+			"1.5",
+			"--chain",
+			"paseo",
+			"-y",
+		])?;
+		let crate::commands::Command::Assets(args) = pop.command else {
+			panic!("expected the assets command");
+		};
+		let super::super::Command::Mint(command) = args.command else {
+			panic!("expected the mint command");
+		};
+		assert_eq!((command.id, command.amount.as_deref()), (Some(1984), Some("1.5")));
+		assert_eq!(command.chain.chain, Some(Chain::Paseo));
+		assert!(command.signer.skip_confirm);
+		Ok(())
+	}
+}
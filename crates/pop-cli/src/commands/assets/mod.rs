@@ -0,0 +1,269 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::network};
+use anyhow::{anyhow, Result};
+use clap::{
+	builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+	Args, Subcommand,
+};
+use pop_common::{enum_variants, units::parse_units};
+use pop_parachains::{
+	registered_asset, set_up_client, AccountId32, Chain, OnlineClient, RegisteredAsset,
+	SubstrateConfig,
+};
+use std::str::FromStr;
+use strum::VariantArray;
+use url::Url;
+
+pub(crate) mod create;
+pub(crate) mod freeze;
+pub(crate) mod mint;
+pub(crate) mod transfer;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+/// Arguments for managing assets.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct AssetsArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Manage assets via the assets pallet of a chain, such as Asset Hub.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Create an asset, along with its metadata.
+	#[clap(alias = "c")]
+	Create(create::CreateCommand),
+	/// Mint an amount of an asset to an account.
+	#[clap(alias = "m")]
+	Mint(mint::MintCommand),
+	/// Transfer an amount of an asset to an account.
+	#[clap(alias = "t")]
+	Transfer(transfer::TransferCommand),
+	/// Freeze an asset, either for an account or for all accounts.
+	#[clap(alias = "f")]
+	Freeze(freeze::FreezeCommand),
+}
+
+/// Arguments for the chain on which assets are managed.
+#[derive(Args, Clone, Default)]
+pub(crate) struct ChainArgs {
+	/// The relay chain of which the assets are managed on its asset hub, or the asset hub itself.
+	#[arg(long, value_parser = enum_variants!(Chain))]
+	pub(crate) chain: Option<Chain>,
+	/// Websocket endpoint of a node of the chain, such as that of your own chain.
+	#[arg(short, long, value_parser, conflicts_with = "chain")]
+	pub(crate) url: Option<Url>,
+}
+
+impl ChainArgs {
+	/// The name and endpoint of the chain, prompting the user if none specified.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) fn configure(&self, cli: &mut impl Cli) -> Result<(String, Url)> {
+		if let Some(url) = &self.url {
+			let name = Chain::from_url(url.as_str())
+				.map(|c| c.name().to_string())
+				.unwrap_or_else(|| url.to_string());
+			return Ok((name, url.clone()));
+		}
+		let chain = match self.chain {
+			Some(chain) => Some(
+				chain
+					.asset_hub()
+					.ok_or_else(|| anyhow!("No asset hub is known for {}.", chain.name()))?,
+			),
+			None => {
+				let mut prompt = cli.select("Select the chain:");
+				for chain in Chain::VARIANTS.iter().filter(|c| c.asset_hub() == Some(**c)) {
+					prompt = prompt.item(Some(*chain), chain.name(), chain.url());
+				}
+				prompt.item(None, "Local chain", DEFAULT_URL).interact()?
+			},
+		};
+		match chain {
+			Some(chain) => Ok((chain.name().to_string(), Url::parse(chain.url())?)),
+			None => Ok((DEFAULT_URL.to_string(), Url::parse(DEFAULT_URL)?)),
+		}
+	}
+}
+
+/// Connects to the chain.
+///
+/// # Arguments
+/// * `url` - The endpoint of the chain.
+pub(crate) async fn connect(url: &Url) -> Result<OnlineClient<SubstrateConfig>> {
+	network::retry("connecting to the chain", || set_up_client(url.as_str())).await
+}
+
+/// The identifier of an asset, prompting the user if none specified.
+///
+/// # Arguments
+/// * `id` - The identifier of the asset, if specified.
+/// * `cli` - The cli.
+pub(crate) fn prompt_for_id(id: Option<u128>, cli: &mut impl Cli) -> Result<u128> {
+	match id {
+		Some(id) => Ok(id),
+		None => Ok(cli
+			.input("Enter the identifier of the asset:")
+			.placeholder("e.g. 1984")
+			.validate(|input| match input.parse::<u128>() {
+				Ok(_) => Ok(()),
+				_ => Err("Invalid identifier."),
+			})
+			.interact()?
+			.parse()?),
+	}
+}
+
+/// The asset registered with the specified identifier, which must have been created beforehand.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `id` - The identifier of the asset.
+/// * `name` - The name of the chain.
+pub(crate) async fn existing_asset(
+	client: &OnlineClient<SubstrateConfig>,
+	id: u128,
+	name: &str,
+) -> Result<RegisteredAsset> {
+	registered_asset(client, id)
+		.await?
+		.ok_or_else(|| anyhow!("The asset {id} is not registered on {name}."))
+}
+
+/// An account, prompting the user if none specified.
+///
+/// # Arguments
+/// * `account` - The address of the account, if specified.
+/// * `prompt` - The prompt for the account.
+/// * `cli` - The cli.
+pub(crate) fn prompt_for_account(
+	account: Option<&str>,
+	prompt: &str,
+	cli: &mut impl Cli,
+) -> Result<AccountId32> {
+	let account = match account {
+		Some(account) => account.to_string(),
+		None => cli
+			.input(prompt)
+			.placeholder("e.g. 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+			.validate(|input| match AccountId32::from_str(input) {
+				Ok(_) => Ok(()),
+				_ => Err("Invalid address."),
+			})
+			.interact()?,
+	};
+	AccountId32::from_str(&account).map_err(|_| anyhow!("Invalid address: {account}"))
+}
+
+/// An amount of whole tokens in the smallest unit of the asset, prompting the user if none
+/// specified.
+///
+/// # Arguments
+/// * `amount` - The amount of whole tokens, if specified.
+/// * `decimals` - The number of decimals of the asset.
+/// * `prompt` - The prompt for the amount.
+/// * `cli` - The cli.
+pub(crate) fn prompt_for_amount(
+	amount: Option<&str>,
+	decimals: u8,
+	prompt: &str,
+	cli: &mut impl Cli,
+) -> Result<u128> {
+	let amount = match amount {
+		Some(amount) => amount.to_string(),
+		None => cli
+			.input(prompt)
+			.placeholder(&format!(
+				"An amount of whole tokens with up to {decimals} decimals, e.g. 1.5"
+			))
+			.validate(move |input| match parse_units(input, decimals) {
+				Ok(_) => Ok(()),
+				_ => Err("Invalid amount."),
+			})
+			.interact()?,
+	};
+	Ok(parse_units(&amount, decimals)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+
+	const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+	#[test]
+	fn configure_chain_works() -> Result<()> {
+		let mut cli = MockCli::new();
+		let args = |chain| ChainArgs { chain: Some(chain), url: None };
+		assert_eq!(
+			args(Chain::Paseo).configure(&mut cli)?,
+			("Asset Hub (Paseo)".to_string(), Url::parse(Chain::AssetHubPaseo.url())?)
+		);
+		assert_eq!(
+			args(Chain::Ethereum).configure(&mut cli).unwrap_err().to_string(),
+			"No asset hub is known for Ethereum."
+		);
+		let url = Url::parse(DEFAULT_URL)?;
+		assert_eq!(
+			ChainArgs { chain: None, url: Some(url.clone()) }.configure(&mut cli)?,
+			(url.to_string(), url.clone())
+		);
+		cli.verify()?;
+
+		let chains = [
+			Chain::AssetHubPolkadot,
+			Chain::AssetHubKusama,
+			Chain::AssetHubPaseo,
+			Chain::AssetHubWestend,
+		];
+		let mut items: Vec<_> =
+			chains.iter().map(|c| (c.name().to_string(), c.url().to_string())).collect();
+		items.push(("Local chain".into(), DEFAULT_URL.into()));
+		let mut cli =
+			MockCli::new().expect_select("Select the chain:", Some(true), true, Some(items), 4);
+		assert_eq!(ChainArgs::default().configure(&mut cli)?, (url.to_string(), url));
+		cli.verify()
+	}
+
+	#[test]
+	fn prompt_for_id_works() -> Result<()> {
+		let mut cli = MockCli::new();
+		assert_eq!(prompt_for_id(Some(1984), &mut cli)?, 1984);
+		cli.verify()?;
+		let mut cli = MockCli::new().expect_input("Enter the identifier of the asset:", "7".into());
+		assert_eq!(prompt_for_id(None, &mut cli)?, 7);
+		cli.verify()
+	}
+
+	#[test]
+	fn prompt_for_account_works() -> Result<()> {
+		let alice = AccountId32::from_str(ALICE)?;
+		let mut cli = MockCli::new();
+		assert_eq!(prompt_for_account(Some(ALICE), "Account:", &mut cli)?, alice);
+		assert_eq!(
+			prompt_for_account(Some("bob"), "Account:", &mut cli).unwrap_err().to_string(),
+			"Invalid address: bob"
+		);
+		cli.verify()?;
+		let mut cli = MockCli::new().expect_input("Account:", ALICE.into());
+		assert_eq!(prompt_for_account(None, "Account:", &mut cli)?, alice);
+		cli.verify()
+	}
+
+	#[test]
+	fn prompt_for_amount_works() -> Result<()> {
+		let mut cli = MockCli::new();
+		assert_eq!(prompt_for_amount(Some("1.5"), 6, "Amount:", &mut cli)?, 1_500_000);
+		assert!(prompt_for_amount(Some("1.5"), 0, "Amount:", &mut cli).is_err());
+		cli.verify()?;
+		let mut cli = MockCli::new().expect_input("Amount:", "0.01".into());
+		assert_eq!(prompt_for_amount(None, 10, "Amount:", &mut cli)?, 100_000_000);
+		cli.verify()
+	}
+}
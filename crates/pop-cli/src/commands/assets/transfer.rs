@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{
+	connect, existing_asset, prompt_for_account, prompt_for_amount, prompt_for_id, ChainArgs,
+};
+use crate::{cli::traits::*, common::signer::SignerArgs};
+use anyhow::Result;
+use clap::Args;
+use pop_common::units::format_units;
+use pop_parachains::transfer_asset;
+
+/// Command to transfer an amount of an asset.
+#[derive(Args, Default)]
+pub(crate) struct TransferCommand {
+	/// The identifier of the asset [default: prompted].
+	#[arg(long)]
+	pub(crate) id: Option<u128>,
+	/// The account receiving the amount [default: prompted].
+	#[arg(long)]
+	pub(crate) to: Option<String>,
+	/// The amount to be transferred, in whole tokens, e.g. `1.5` [default: prompted].
+	#[arg(long)]
+	pub(crate) amount: Option<String>,
+	#[command(flatten)]
+	pub(crate) chain: ChainArgs,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl TransferCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Transfer an asset")?;
+		let (chain, url) = self.chain.configure(cli)?;
+		let client = connect(&url).await?;
+		let id = prompt_for_id(self.id, cli)?;
+		let asset = existing_asset(&client, id, &chain).await?;
+		let to =
+			prompt_for_account(self.to.as_deref(), "Enter the account receiving the asset:", cli)?;
+		let amount = prompt_for_amount(
+			self.amount.as_deref(),
+			asset.decimals,
+			"Enter the amount to transfer:",
+			cli,
+		)?;
+		let xt = transfer_asset(id, &to, amount);
+		if self
+			.signer
+			.submit(&client, &url, xt, "transfer the asset", cli)
+			.await?
+			.is_none()
+		{
+			return Ok(());
+		}
+		cli.outro(format!(
+			"Transferred {} {} to {to} on {chain}.",
+			format_units(amount, asset.decimals),
+			asset.symbol
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn transfer_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop", "assets", "transfer", "--id", "1984", "--amount", "0.5", "--suri", "//Bob",
+		])?;
+		let crate::commands::Command::Assets(args) = pop.command else {
+			panic!("expected the assets command");
+		};
+		let super::super::Command::Transfer(command) = args.command else {
+			panic!("expected the transfer command");
+		};
+		assert_eq!((command.id, command.amount.as_deref()), (Some(1984), Some("0.5")));
+		assert_eq!(command.to, None);
+		assert_eq!(command.signer.suri.as_deref(), Some("//Bob"));
+		Ok(())
+	}
+}
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use crate::cli;
-use pop_contracts::{build_smart_contract, Verbosity};
+use pop_contracts::{build_smart_contract, is_up_to_date, Verbosity};
 use std::path::PathBuf;
 
 /// Configuration for building a smart contract.
@@ -13,17 +13,18 @@ pub struct BuildContract {
 }
 
 impl BuildContract {
-	/// Executes the command.
-	pub(crate) fn execute(self) -> anyhow::Result<&'static str> {
-		self.build(&mut cli::Cli)
-	}
-
 	/// Builds a smart contract
 	///
 	/// # Arguments
 	/// * `cli` - The CLI implementation to be used.
-	fn build(self, cli: &mut impl cli::traits::Cli) -> anyhow::Result<&'static str> {
+	pub(crate) fn build(self, cli: &mut impl cli::traits::Cli) -> anyhow::Result<&'static str> {
 		cli.intro("Building your contract")?;
+		// Skip the build if nothing has changed since the contract was last built in the same mode.
+		if is_up_to_date(self.path.as_deref(), Some(self.release)) {
+			cli.success("The contract is up to date, so the build was skipped.")?;
+			cli.outro("Build completed successfully!")?;
+			return Ok("contract");
+		}
 		// Build contract.
 		let build_result =
 			build_smart_contract(self.path.as_deref(), self.release, Verbosity::Default)?;
@@ -60,6 +61,9 @@ mod tests {
 
 			cli.verify()?;
 		}
+		// The contract is only rebuilt once its sources change.
+		assert!(is_up_to_date(Some(&path.join(name)), Some(true)));
+		assert!(!is_up_to_date(Some(&path.join(name)), Some(false)));
 
 		Ok(())
 	}
@@ -1,9 +1,6 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::{
-	cli::{self, Cli},
-	common::builds::get_project_path,
-};
+use crate::{cli, common::builds::get_project_path};
 use clap::{Args, Subcommand};
 #[cfg(feature = "contract")]
 use contract::BuildContract;
@@ -23,9 +20,9 @@ pub(crate) mod spec;
 /// Arguments for building a project.
 #[derive(Args)]
 #[command(args_conflicts_with_subcommands = true)]
-pub(crate) struct BuildArgs {
+pub struct BuildArgs {
 	#[command(subcommand)]
-	pub command: Option<Command>,
+	pub(crate) command: Option<Command>,
 	/// Directory path with flag for your project [default: current directory]
 	#[arg(long)]
 	pub(crate) path: Option<PathBuf>,
@@ -54,7 +51,14 @@ pub(crate) enum Command {
 
 impl Command {
 	/// Executes the command.
-	pub(crate) fn execute(args: BuildArgs) -> anyhow::Result<&'static str> {
+	///
+	/// # Arguments
+	/// * `args` - The arguments for building a project.
+	/// * `cli` - The CLI implementation to be used.
+	pub(crate) fn execute(
+		args: BuildArgs,
+		cli: &mut impl cli::traits::Cli,
+	) -> anyhow::Result<&'static str> {
 		// If only contract feature enabled, build as contract
 		let project_path = get_project_path(args.path.clone(), args.path_pos.clone());
 
@@ -65,7 +69,7 @@ impl Command {
 				Some(profile) => profile.into(),
 				None => args.release,
 			};
-			BuildContract { path: project_path, release }.execute()?;
+			BuildContract { path: project_path, release }.build(cli)?;
 			return Ok("contract");
 		}
 
@@ -82,12 +86,12 @@ impl Command {
 				package: args.package,
 				profile,
 			}
-			.execute()?;
+			.build(cli)?;
 			return Ok("parachain");
 		}
 
 		// Otherwise build as a normal Rust project
-		Self::build(args, &mut Cli)
+		Self::build(args, cli)
 	}
 
 	/// Builds a Rust project.
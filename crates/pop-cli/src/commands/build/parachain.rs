@@ -18,16 +18,11 @@ pub struct BuildParachain {
 }
 
 impl BuildParachain {
-	/// Executes the build process.
-	pub(crate) fn execute(self) -> anyhow::Result<&'static str> {
-		self.build(&mut cli::Cli)
-	}
-
 	/// Builds a parachain.
 	///
 	/// # Arguments
 	/// * `cli` - The CLI implementation to be used.
-	fn build(self, cli: &mut impl cli::traits::Cli) -> anyhow::Result<&'static str> {
+	pub(crate) fn build(self, cli: &mut impl cli::traits::Cli) -> anyhow::Result<&'static str> {
 		let project = if self.package.is_some() { "package" } else { "parachain" };
 		cli.intro(format!("Building your {project}"))?;
 
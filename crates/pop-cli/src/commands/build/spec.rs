@@ -1,19 +1,16 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use crate::{
-	cli,
-	cli::{
-		traits::{Cli as _, *},
-		Cli,
-	},
+	cli::{self, traits::*},
+	common::binary::check_binary_and_prompt,
 	style::style,
 };
 use clap::{Args, ValueEnum};
-use cliclack::spinner;
 use pop_common::Profile;
 use pop_parachains::{
-	binary_path, build_parachain, export_wasm_file, generate_genesis_state_file,
-	generate_plain_chain_spec, generate_raw_chain_spec, is_supported, ChainSpec,
+	binary_path, build_omni_runtime, build_parachain, export_wasm_file,
+	generate_genesis_state_file, generate_omni_chain_spec, generate_plain_chain_spec,
+	generate_raw_chain_spec, is_runtime_only, is_supported, omni_node_generator, ChainSpec,
 };
 use std::{
 	env::current_dir,
@@ -173,13 +170,18 @@ pub struct BuildSpecCommand {
 
 impl BuildSpecCommand {
 	/// Executes the build spec command.
-	pub(crate) async fn execute(self) -> anyhow::Result<&'static str> {
-		let mut cli = Cli;
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(
+		self,
+		cli: &mut impl cli::traits::Cli,
+	) -> anyhow::Result<&'static str> {
 		cli.intro("Generate your chain spec")?;
 		// Checks for appchain project in `./`.
 		if is_supported(None)? {
-			let build_spec = self.configure_build_spec(&mut cli).await?;
-			build_spec.build(&mut cli)
+			let build_spec = self.configure_build_spec(cli).await?;
+			build_spec.build(cli).await
 		} else {
 			cli.outro_cancel(
 				"🚫 Can't build a specification for target. Maybe not a chain project ?",
@@ -457,8 +459,9 @@ impl BuildSpec {
 	//
 	// This function generates plain and raw chain spec files based on the provided configuration,
 	// optionally including genesis state and runtime artifacts. If the node binary is missing,
-	// it triggers a build process.
-	fn build(self, cli: &mut impl cli::traits::Cli) -> anyhow::Result<&'static str> {
+	// it triggers a build process. Projects without a node are run via `polkadot-omni-node`, in
+	// which case the chain spec is generated from the runtime.
+	async fn build(self, cli: &mut impl cli::traits::Cli) -> anyhow::Result<&'static str> {
 		cli.intro("Building your chain spec")?;
 		let mut generated_files = vec![];
 		let BuildSpec {
@@ -467,17 +470,44 @@ impl BuildSpec {
 			id,
 			default_bootnode,
 			ref chain,
+			ref relay,
 			genesis_state,
 			genesis_code,
 			..
 		} = self;
-		// Ensure binary is built.
-		let binary_path = ensure_binary_exists(cli, profile)?;
-		let spinner = spinner();
+		let cwd = current_dir().unwrap_or(PathBuf::from("./"));
+		let omni_node = is_runtime_only(&cwd).unwrap_or_default();
+		// Generate the chain spec from the runtime when there is no node, unless an existing
+		// chain spec is provided.
+		let runtime = match omni_node {
+			true if !Path::new(chain).is_file() => {
+				cli.info("The runtime will be built locally.")?;
+				cli.warning("NOTE: this may take some time...")?;
+				Some(build_omni_runtime(&cwd, &cwd.join("runtime"))?)
+			},
+			_ => None,
+		};
+		// Ensure binary is built, or otherwise sourced when using the omni node.
+		let binary_path = if omni_node {
+			check_binary_and_prompt(cli, omni_node_generator(crate::cache()?), false).await?
+		} else {
+			ensure_binary_exists(cli, profile)?
+		};
+		let spinner = cli.spinner();
 		spinner.start("Generating chain specification...");
 
 		// Generate chain spec.
-		generate_plain_chain_spec(&binary_path, output_file, default_bootnode, chain)?;
+		match runtime {
+			Some(runtime) => generate_omni_chain_spec(
+				&binary_path,
+				&runtime,
+				output_file,
+				id,
+				relay.as_ref(),
+				chain,
+			)?,
+			None => generate_plain_chain_spec(&binary_path, output_file, default_bootnode, chain)?,
+		}
 		// Customize spec based on input.
 		self.customize()?;
 		generated_files.push(format!(
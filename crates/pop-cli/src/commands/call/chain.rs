@@ -1,24 +1,75 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use std::path::Path;
+use std::{
+	env::current_dir,
+	fmt::{Display, Formatter},
+	fs,
+	path::{Path, PathBuf},
+	str::FromStr,
+	time::Duration,
+};
 
+use super::xcm;
 use crate::{
-	cli::{self, traits::*},
-	common::wallet::{prompt_to_use_wallet, request_signature},
+	cli::traits::*,
+	common::{
+		balances::warn_of_balance_issues,
+		chains::resolve_chain,
+		explorer::explorer_links,
+		network::{self, TimedOut},
+		secrets::{mask_suri, prompt_suri},
+		wallet::{prompt_to_use_wallet, request_signature},
+	},
 };
 use anyhow::{anyhow, Result};
-use clap::Args;
+use clap::{Args, ValueEnum};
+use pop_common::{
+	address_book::AddressBook,
+	balances::Balances,
+	config::{config_file_path, Config},
+	create_signer,
+	search::search,
+	units::parse_balance,
+};
 use pop_parachains::{
-	construct_extrinsic, construct_sudo_extrinsic, decode_call_data, encode_call_data,
-	find_dispatchable_by_name, find_pallet_by_name, parse_chain_metadata, set_up_client,
-	sign_and_submit_extrinsic, submit_signed_extrinsic, supported_actions, Action, CallData,
-	DynamicPayload, Function, OnlineClient, Pallet, Param, Payload, SubstrateConfig,
+	batch_all, call_fee, clear_cached_metadata, construct_extrinsic, construct_sudo_call_data,
+	construct_sudo_extrinsic, create_payload, decode_call_data, display_extrinsic_events,
+	encode_call_data, find_dispatchable_by_name, find_pallet_by_name, list_pallets, multisig_call,
+	native_transfer, parse_chain_metadata, parse_dispatchable_arguments, parse_json_arguments,
+	parse_storage_items, query_storage, resolve_function, resolve_schedule, schedule,
+	sign_and_submit_extrinsic_until, signed_extrinsic, submit_signed_extrinsic, supported_actions,
+	task_name, AccountFormat, AccountId32, Action, CallBuilder, CallData, CallManifest,
+	CallTemplate, CallTemplates, Connection, DynamicPayload, ExplorerItem, ExtrinsicEvents,
+	ExtrinsicOptions, Function, MetadataV16, Multisig, MultisigCall, NativeToken, OnlineClient,
+	Pallet, Param, Payload, ScheduleAt, SignedPayload, StorageItem, Submitted, SubstrateConfig,
+	ValueKind, WaitFor, ALITH_PRIVATE_KEY,
 };
 use url::Url;
 
 const DEFAULT_URL: &str = "ws://localhost:9944/";
 const DEFAULT_URI: &str = "//Alice";
-const ENCODED_CALL_DATA_MAX_LEN: usize = 500; // Maximum length of encoded call data to display.
+// The development accounts, offered alongside those of the address book.
+const DEV_ACCOUNTS: [&str; 6] = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+// The version of the extrinsics submitted by pop.
+const EXTRINSIC_VERSION: u8 = 4;
+// The pallets via which cross-chain messages are sent.
+const XCM_PALLETS: [&str; 2] = ["PolkadotXcm", "XcmPallet"];
+// The maximum length of encoded call data to display.
+const ENCODED_CALL_DATA_MAX_LEN: usize = 500;
+// The interval at which the connection to the chain is checked while calls are configured.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+// The number of pallets or functions above which they are searched before being selected.
+const SEARCH_THRESHOLD: usize = 15;
+
+/// The formats in which the pallets of a chain can be listed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub(crate) enum ListFormat {
+	/// Pretty-printed JSON.
+	#[default]
+	Json,
+	/// YAML.
+	Yaml,
+}
 
 /// Command to construct and execute extrinsics with configurable pallets, functions, arguments, and
 /// signing options.
@@ -33,14 +84,35 @@ pub struct CallChainCommand {
 	/// The dispatchable function arguments, encoded as strings.
 	#[arg(short, long, num_args = 0..,)]
 	args: Vec<String>,
-	/// Websocket endpoint of a node.
+	/// The dispatchable function arguments as a JSON object keyed by the names of the parameters,
+	/// e.g. '{"dest": "5Grw...", "value": "1000"}', rather than as strings.
+	#[arg(long, conflicts_with_all = ["args", "call", "storage"])]
+	args_json: Option<String>,
+	/// Websocket endpoint of a node, which may be specified multiple times to fail over to the
+	/// next node of the chain whenever one is unreachable.
 	#[arg(short, long, value_parser)]
-	url: Option<Url>,
+	url: Vec<Url>,
+	/// The name of the chain (e.g. `pop`, `paseo` or `local`), as configured within the `[chains]`
+	/// section of the user configuration file or otherwise known to pop, rather than its endpoint.
+	#[arg(long, conflicts_with_all = ["url", "light"])]
+	chain: Option<String>,
+	/// Fetch the metadata of the chain again, rather than reusing that cached by previous calls.
+	#[arg(long, conflicts_with = "light")]
+	refresh_metadata: bool,
+	/// Path to the chain spec of the chain, to connect via an embedded light client rather than
+	/// relying on an endpoint.
+	#[arg(long, conflicts_with_all = ["url", "use-wallet"])]
+	light: Option<PathBuf>,
+	/// Path to the chain spec of the relay chain, when connecting to a parachain via a light
+	/// client.
+	#[arg(long, requires = "light")]
+	relay_chain_spec: Option<PathBuf>,
 	/// Secret key URI for the account signing the extrinsic.
 	///
 	/// e.g.
 	/// - for a dev account "//Alice"
 	/// - with a password "//Alice///SECRET_PASSWORD"
+	/// - for an Ethereum-style account, its private key "0x5fb92d6e..."
 	#[arg(short, long)]
 	suri: Option<String>,
 	/// Use a browser extension wallet to sign the extrinsic.
@@ -58,73 +130,310 @@ pub struct CallChainCommand {
 	/// Authenticates the sudo key and dispatches a function call with `Root` origin.
 	#[arg(short = 'S', long)]
 	sudo: bool,
+	/// Approves the call as a signatory of a multisig account, specified by its signatories and
+	/// threshold (e.g. `"5Grw...,5FHn...,5FLS...;2"`), dispatching it once the threshold is
+	/// reached.
+	#[arg(long, value_parser = parse_multisig, conflicts_with_all = ["call", "use-wallet", "submit_signed", "storage"])]
+	multisig: Option<Multisig>,
+	/// The nonce of the signer, e.g. to submit several extrinsics concurrently [default: the next
+	/// nonce of the account].
+	#[arg(long, conflicts_with_all = ["use-wallet", "create_payload", "submit_signed", "storage"])]
+	nonce: Option<u64>,
+	/// The tip paid to the block author, in the smallest unit of the native token.
+	#[arg(long, conflicts_with_all = ["use-wallet", "create_payload", "submit_signed", "storage"])]
+	tip: Option<u128>,
+	/// The number of blocks for which the extrinsic is valid, rounded to a power of two, or `0`
+	/// for an immortal extrinsic [default: 32].
+	#[arg(long, conflicts_with_all = ["use-wallet", "create_payload", "submit_signed", "storage"])]
+	era: Option<u64>,
+	/// How far the extrinsic is followed once submitted: until accepted by the node (`none`),
+	/// included in the best block (`best`) or finalized (`finalized`) [default: finalized].
+	#[arg(long, value_parser = parse_wait, conflicts_with_all = ["use-wallet", "create_payload", "submit_signed", "storage"])]
+	wait: Option<WaitFor>,
+	/// The maximum time to wait for the extrinsic to be followed as far as specified via
+	/// `--wait`, in seconds.
+	#[arg(long, value_name = "SECS", conflicts_with_all = ["use-wallet", "create_payload", "submit_signed", "storage"])]
+	wait_timeout: Option<u64>,
+	/// Schedules the call via the scheduler pallet rather than dispatching it immediately: at a
+	/// block (e.g. `1200`), after a number of blocks (e.g. `+10`) or at a Unix timestamp in
+	/// seconds (e.g. `@1767225600`).
+	#[arg(long, value_parser = parse_schedule_at, conflicts_with_all = ["call", "submit_signed"])]
+	schedule_at: Option<ScheduleAt>,
+	/// The name of the scheduled task, via which it can be cancelled.
+	#[arg(long, value_parser = parse_schedule_name, requires = "schedule_at")]
+	schedule_name: Option<String>,
+	/// Writes the unsigned extrinsic to the specified file rather than submitting it, so that it
+	/// can be signed offline via `pop sign` and then submitted via `pop submit`.
+	#[arg(
+		long,
+		alias = "export-payload",
+		requires = "signer",
+		conflicts_with_all = ["suri", "use-wallet"]
+	)]
+	create_payload: Option<PathBuf>,
+	/// The address of the account which is to sign the extrinsic offline.
+	#[arg(long, requires = "create_payload")]
+	signer: Option<String>,
+	/// Submits an extrinsic signed offline via `pop sign`, read from the specified file.
+	#[arg(
+		long,
+		conflicts_with_all = ["pallet", "function", "args", "args_json", "call", "suri", "use-wallet", "sudo", "create_payload"]
+	)]
+	submit_signed: Option<PathBuf>,
 	/// Automatically signs and submits the extrinsic without prompting for confirmation.
 	#[arg(short = 'y', long)]
 	skip_confirm: bool,
+	/// Queries a storage item rather than calling a dispatchable function, with any keys of the
+	/// item specified via `--args`.
+	#[arg(
+		long,
+		conflicts_with_all = ["function", "call", "suri", "use-wallet", "sudo", "schedule_at", "create_payload", "submit_signed"]
+	)]
+	storage: bool,
+	/// The storage item to query within the specified pallet.
+	#[arg(long, value_parser = parse_pallet_name, requires = "storage")]
+	item: Option<String>,
+	/// Submits the calls declared within a manifest (e.g. `calls.toml`) rather than a single
+	/// call, reporting the result of each.
+	#[arg(
+		long,
+		conflicts_with_all = ["pallet", "function", "args", "args_json", "call", "use-wallet", "multisig", "schedule_at", "create_payload", "submit_signed", "storage"]
+	)]
+	file: Option<PathBuf>,
+	/// Dispatches the calls of the manifest atomically, within a single `Utility.batch_all` call.
+	#[arg(long, requires = "file")]
+	batch_all: bool,
+	/// Lists the pallets of the chain, along with their dispatchable functions and parameters,
+	/// rather than calling a dispatchable function. Restricted to a single pallet via `--pallet`.
+	#[arg(
+		long,
+		conflicts_with_all = ["function", "args", "args_json", "call", "suri", "use-wallet", "sudo", "multisig", "schedule_at", "create_payload", "submit_signed", "storage", "file"]
+	)]
+	list: bool,
+	/// The format in which the pallets are listed [default: json].
+	#[arg(long, value_enum, requires = "list")]
+	output: Option<ListFormat>,
+	/// Saves the call as a template with the specified name (e.g. `transfer-to-bob`), to be
+	/// re-run via `--template`. Saved within `.pop/calls` should the current directory contain a
+	/// `pop.toml` file, otherwise within the configuration directory of the user.
+	#[arg(
+		long,
+		value_name = "NAME",
+		conflicts_with_all = ["call", "storage", "file", "submit_signed", "list"]
+	)]
+	save_as: Option<String>,
+	/// Runs the call saved as a template with the specified name.
+	#[arg(
+		long,
+		value_name = "NAME",
+		conflicts_with_all = ["pallet", "function", "args", "args_json", "call", "storage", "file", "submit_signed", "list"]
+	)]
+	template: Option<String>,
+	/// Overrides an argument of the template by the name of its parameter, e.g. `--set
+	/// value=2000`.
+	#[arg(long = "set", value_name = "NAME=VALUE", value_parser = parse_override, requires = "template")]
+	overrides: Vec<(String, String)>,
 }
 
 impl CallChainCommand {
+	/// Creates a command to compose a call to a dispatchable function, prompting for its
+	/// arguments.
+	///
+	/// # Arguments
+	/// * `url` - Endpoint of the node.
+	/// * `pallet` - The pallet containing the dispatchable function.
+	/// * `function` - The dispatchable function.
+	pub(crate) fn compose(url: Url, pallet: String, function: String) -> Self {
+		Self {
+			pallet: Some(pallet),
+			function: Some(function),
+			url: vec![url],
+			..Default::default()
+		}
+	}
+
+	/// Creates a command to submit an extrinsic signed offline via `pop sign`.
+	///
+	/// # Arguments
+	/// * `url` - The endpoint of the chain.
+	/// * `signed` - The file containing the signed payload.
+	pub(crate) fn submit(url: Url, signed: PathBuf) -> Self {
+		Self { submit_signed: Some(signed), url: vec![url], ..Default::default() }
+	}
+
 	/// Executes the command.
-	pub(crate) async fn execute(mut self) -> Result<()> {
-		let mut cli = cli::Cli;
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		// Configure the call as per a template, if specified.
+		if let Some(name) = self.template.clone() {
+			self.apply_template(&CallTemplates::open(&current_dir()?)?, &name)?;
+		}
 		// Check if all fields are specified via the command line.
 		let prompt_to_repeat_call = self.requires_user_input();
 		// Configure the chain.
-		let chain = self.configure_chain(&mut cli).await?;
+		let chain = self.configure_chain(cli).await?;
+		// Keep the connection alive while calls are configured, which may take a while.
+		let _keep_alive = chain.connection.keep_alive(KEEP_ALIVE_INTERVAL);
+		// Submit an extrinsic signed offline if provided.
+		if let Some(path) = self.submit_signed.as_ref() {
+			if let Err(e) = self.submit_signed_payload(&chain, path, cli).await {
+				display_message(&e.to_string(), false, cli)?;
+				return network::propagate_timeout(e);
+			}
+			return Ok(());
+		}
+		// List the pallets of the chain rather than calling a dispatchable function.
+		if self.list {
+			return self.list_metadata(&chain, cli);
+		}
+		// Query storage rather than calling a dispatchable function.
+		if self.storage {
+			return self.query_storage(&chain, cli).await;
+		}
+		// Submit the calls declared within a manifest.
+		if let Some(path) = self.file.clone() {
+			if let Err(e) = self.submit_manifest(&chain, &path, cli).await {
+				display_message(&e.to_string(), false, cli)?;
+				return network::propagate_timeout(e);
+			}
+			return Ok(());
+		}
 		// Execute the call if call_data is provided.
 		if let Some(call_data) = self.call_data.as_ref() {
-			if let Err(e) = self
-				.submit_extrinsic_from_call_data(
-					&chain.client,
-					&chain.url,
-					call_data,
-					&mut cli::Cli,
-				)
-				.await
-			{
-				display_message(&e.to_string(), false, &mut cli::Cli)?;
+			if let Err(e) = self.submit_extrinsic_from_call_data(&chain, call_data, cli).await {
+				display_message(&e.to_string(), false, cli)?;
+				return network::propagate_timeout(e);
 			}
 			return Ok(());
 		}
 		loop {
 			// Configure the call based on command line arguments/call UI.
-			let mut call = match self.configure_call(&chain, &mut cli) {
+			let mut call = match self.configure_call(&chain, cli) {
 				Ok(call) => call,
 				Err(e) => {
-					display_message(&e.to_string(), false, &mut cli)?;
+					display_message(&e.to_string(), false, cli)?;
 					break;
 				},
 			};
 			// Display the configured call.
 			cli.info(call.display(&chain))?;
+			// Save the call as a template, so that it can be re-run.
+			if let Some(name) = self.save_as.take() {
+				let templates = CallTemplates::open(&current_dir()?)?;
+				let path = templates.save(&name, &call.template(&chain))?;
+				cli.info(format!(
+					"Saved the call to {}. Re-run it via `pop call chain --template {name}`.",
+					path.display()
+				))?;
+			}
+			// Reconnect if the connection was lost while configuring the call, with the same client
+			// then used to prepare, dry-run and submit the call.
+			chain.connection.ensure_alive().await?;
+			let client = chain.connection.client();
+			// Resolve when a scheduled call is dispatched, e.g. the block by which a timestamp is
+			// reached.
+			if let Some(at) = call.schedule_at {
+				match resolve_schedule(&client, at).await {
+					Ok(at) => call.schedule_at = Some(at),
+					Err(e) => {
+						display_message(&e.to_string(), false, cli)?;
+						break;
+					},
+				}
+			}
 			// Prepare the extrinsic.
-			let xt = match call.prepare_extrinsic(&chain.client, &mut cli) {
+			let xt = match call.prepare_extrinsic(&client, cli) {
 				Ok(payload) => payload,
 				Err(e) => {
-					display_message(&e.to_string(), false, &mut cli)?;
+					display_message(&e.to_string(), false, cli)?;
 					break;
 				},
 			};
 
-			// Sign and submit the extrinsic.
-			let result = if self.use_wallet {
-				let call_data = xt.encode_call_data(&chain.client.metadata())?;
-				submit_extrinsic_with_wallet(&chain.client, &chain.url, call_data, &mut cli).await
+			// Dry-run cross-chain calls, so that their effects on other chains can be seen.
+			let substrate = chain.account_format == AccountFormat::Substrate;
+			if !self.use_wallet && substrate && XCM_PALLETS.contains(&call.function.pallet.as_str())
+			{
+				let origin = call.origin()?;
+				let source = chain
+					.endpoint
+					.url()
+					.and_then(|url| pop_parachains::Chain::from_url(url.as_str()));
+				if let Some(error) =
+					xcm::dry_run(&client, xt.clone(), &origin, source, self.skip_confirm, cli)
+						.await?
+				{
+					display_message(&format!("The call would fail: {error}"), false, cli)?;
+					break;
+				}
+			}
+
+			// Warn should a transfer fail or reap an account, unless signed via a wallet.
+			if let Some((dest, value)) =
+				native_transfer(&xt).filter(|_| !self.use_wallet && substrate)
+			{
+				let sender = call.origin()?;
+				let call_data = xt.encode_call_data(&client.metadata())?;
+				let fees =
+					call_fee(&client, &call_data).await.map(|f| f.total()).unwrap_or_default();
+				if let Ok(balances) = Balances::fetch(&client, &sender, Some(&dest)).await {
+					let token = chain.connection.token_metadata().await?;
+					warn_of_balance_issues(&balances, value, fees, &token, cli)?;
+				}
+			}
+
+			// Approve the call as a signatory of a multisig account.
+			let (xt, approval) = match call.multisig.clone() {
+				Some(multisig) =>
+					match call.approve_multisig(&chain, &client, &multisig, xt, cli).await {
+						Ok(approval) => (approval.xt.clone(), Some((multisig, approval))),
+						Err(e) => {
+							display_message(&e.to_string(), false, cli)?;
+							break;
+						},
+					},
+				None => (xt, None),
+			};
+
+			// Sign and submit the extrinsic, unless it is to be signed offline.
+			let result = if let Some(offline) = &call.offline {
+				let call_data = xt.encode_call_data(&client.metadata())?;
+				write_payload(&client, &chain.endpoint, call_data, offline, cli).await
+			} else if self.use_wallet {
+				let call_data = xt.encode_call_data(&client.metadata())?;
+				submit_extrinsic_with_wallet(
+					&client,
+					&chain.connection,
+					&chain.endpoint,
+					call_data,
+					cli,
+				)
+				.await
 			} else {
-				call.submit_extrinsic(&chain.client, &chain.url, xt, &mut cli).await
+				call.submit_extrinsic(&client, &chain.connection, xt, cli).await
 			};
 
 			if let Err(e) = result {
-				display_message(&e.to_string(), false, &mut cli)?;
-				break;
+				display_message(&e.to_string(), false, cli)?;
+				// A timeout is propagated, so that it results in the corresponding exit code.
+				return network::propagate_timeout(e);
+			}
+			if let Some((multisig, approval)) = approval.filter(|(_, a)| !a.executes) {
+				cli.info(format!(
+					"{} of {} approvals of the call with hash {:?}. The remaining signatories approve it by making the same call with `--multisig \"{multisig}\"`.",
+					approval.approvals, multisig.threshold, approval.call_hash
+				))?;
 			}
 
 			if !prompt_to_repeat_call ||
+				call.offline.is_some() ||
 				!cli.confirm("Do you want to perform another call?")
 					.initial_value(false)
 					.interact()?
 			{
-				display_message("Call complete.", true, &mut cli)?;
+				display_message("Call complete.", true, cli)?;
 				break;
 			}
 			self.reset_for_new_call();
@@ -135,28 +444,81 @@ impl CallChainCommand {
 	// Configures the chain by resolving the URL and fetching its metadata.
 	async fn configure_chain(&self, cli: &mut impl Cli) -> Result<Chain> {
 		cli.intro("Call a chain")?;
-		// Resolve url.
-		let url = match &self.url {
-			Some(url) => url.clone(),
-			None => {
+		if let Some(chain_spec) = &self.light {
+			let read = |path: &Path| {
+				fs::read_to_string(path)
+					.map_err(|e| anyhow!("Failed to read the chain spec {}: {e}", path.display()))
+			};
+			let spec = read(chain_spec)?;
+			let relay_chain_spec = self.relay_chain_spec.as_deref().map(read).transpose()?;
+			cli.info("Connecting via the light client, which may take a while to sync...")?;
+			let connection = Connection::light(spec, relay_chain_spec).await?;
+			let endpoint = Endpoint::Light {
+				chain_spec: chain_spec.clone(),
+				relay_chain_spec: self.relay_chain_spec.clone(),
+			};
+			let mut chain = Chain::new(endpoint, connection);
+			chain.apply_metadata_v16(cli).await?;
+			chain.fetch_token().await;
+			return Ok(chain);
+		}
+		// Resolve the endpoints, along with any properties configured for a named chain.
+		let profile = match &self.chain {
+			Some(name) => {
+				let chains = match config_file_path() {
+					Ok(path) => Config::load(&path)?.chains,
+					Err(_) => Default::default(),
+				};
+				Some((name.clone(), resolve_chain(name, &chains)?))
+			},
+			None => None,
+		};
+		let urls = match (&profile, self.url.is_empty()) {
+			(Some((_, profile)), _) => vec![Url::parse(&profile.url)?],
+			(None, false) => self.url.clone(),
+			(None, true) => {
 				// Prompt for url.
 				let url: String = cli
 					.input("Which chain would you like to interact with?")
 					.default_input(DEFAULT_URL)
 					.interact()?;
-				Url::parse(&url)?
+				vec![Url::parse(&url)?]
 			},
 		};
 
-		// Parse metadata from chain url.
-		let client = set_up_client(url.as_str()).await?;
-		let mut pallets = parse_chain_metadata(&client).map_err(|e| {
-			anyhow!(format!("Unable to fetch the chain metadata: {}", e.to_string()))
-		})?;
-		// Sort by name for display.
-		pallets.sort_by(|a, b| a.name.cmp(&b.name));
-		pallets.iter_mut().for_each(|p| p.functions.sort_by(|a, b| a.name.cmp(&b.name)));
-		Ok(Chain { url, client, pallets })
+		// The metadata is cached, so repeated calls to the same runtime skip downloading it.
+		let cache = crate::cache()?;
+		if self.refresh_metadata {
+			clear_cached_metadata(&cache)?;
+		}
+		let endpoints: Vec<_> = urls.iter().map(Url::as_str).collect();
+		let mut connection = network::retry("connecting to the chain", || {
+			Connection::connect_any(&endpoints, Some(&cache))
+		})
+		.await?;
+		let endpoint = match profile {
+			Some((name, profile)) => {
+				connection = connection.with_token(NativeToken {
+					symbol: profile.token_symbol,
+					decimals: profile.token_decimals,
+				});
+				let token = connection.token_metadata().await?;
+				let ss58_prefix = profile
+					.ss58_prefix
+					.or_else(|| connection.ss58_prefix())
+					.map_or_else(|| "unknown".to_string(), |p| p.to_string());
+				cli.info(format!(
+					"Using `{name}` at {}: {} with {} decimals, SS58 prefix {ss58_prefix}.",
+					profile.url, token.symbol, token.token_decimals
+				))?;
+				Endpoint::Named { name, url: urls[0].clone() }
+			},
+			None => Endpoint::Urls(urls),
+		};
+		let mut chain = Chain::new(endpoint, connection);
+		chain.apply_metadata_v16(cli).await?;
+		chain.fetch_token().await;
+		Ok(chain)
 	}
 
 	// Configure the call based on command line arguments/call UI.
@@ -171,11 +533,13 @@ impl CallChainCommand {
 						self.function = Some(action.function_name().to_string());
 						find_pallet_by_name(&chain.pallets, action.pallet_name())?
 					} else {
-						let mut prompt = cli.select("Select the pallet to call:");
-						for pallet_item in &chain.pallets {
-							prompt = prompt.item(pallet_item, &pallet_item.name, &pallet_item.docs);
-						}
-						prompt.interact()?
+						search_and_select(
+							cli,
+							"pallet",
+							&chain.pallets,
+							|pallet| &pallet.name,
+							|pallet| pallet.docs.clone(),
+						)?
 					}
 				},
 			};
@@ -183,14 +547,21 @@ impl CallChainCommand {
 			// Resolve dispatchable function.
 			let function = match self.function {
 				Some(ref name) => find_dispatchable_by_name(&chain.pallets, &pallet.name, name)?,
-				None => {
-					let mut prompt = cli.select("Select the function to call:");
-					for function in &pallet.functions {
-						prompt = prompt.item(function, &function.name, &function.docs);
-					}
-					prompt.interact()?
-				},
+				None => search_and_select(
+					cli,
+					"function",
+					&pallet.functions,
+					|function| &function.name,
+					|function| match &function.deprecation {
+						Some(deprecation) => format!("{deprecation}. {}", function.docs),
+						None => function.docs.clone(),
+					},
+				)?,
 			};
+			let function = resolve_function(&chain.connection.client(), function)?;
+			if let Some(deprecation) = &function.deprecation {
+				cli.warning(format!("⚠️ {}.{}: {deprecation}", function.pallet, function.name))?;
+			}
 			// Certain dispatchable functions are not supported yet due to complexity.
 			if !function.is_supported {
 				cli.outro_cancel(
@@ -201,10 +572,15 @@ impl CallChainCommand {
 			}
 
 			// Resolve dispatchable function arguments.
-			let args = if self.args.is_empty() {
+			let args = if let Some(json) = &self.args_json {
+				parse_json_arguments(&function.params, json)?
+			} else if self.args.is_empty() {
+				let calls = CallBuilder::new(&chain.connection.client())
+					.with_token(chain.token.clone())
+					.with_address_book(load_address_book(cli)?);
 				let mut args = Vec::new();
 				for param in &function.params {
-					let input = prompt_for_param(cli, param)?;
+					let input = prompt_for_param(cli, param, &calls)?;
 					args.push(input);
 				}
 				args
@@ -212,41 +588,320 @@ impl CallChainCommand {
 				self.expand_file_arguments()?
 			};
 
+			let args = self.override_args(&function, args)?;
+
 			// If chain has sudo prompt the user to confirm if they want to execute the call via
 			// sudo.
 			self.configure_sudo(chain, cli)?;
 
-			let (use_wallet, suri) = self.determine_signing_method(cli)?;
+			// The extrinsic is not signed when its payload is to be signed offline.
+			let offline = self.offline();
+			let (use_wallet, suri) = match offline {
+				Some(_) => (false, String::new()),
+				None => self.determine_signing_method(chain, cli)?,
+			};
 			self.use_wallet = use_wallet;
 
 			return Ok(Call {
-				function: function.clone(),
+				function,
 				args,
 				suri,
 				skip_confirm: self.skip_confirm,
 				sudo: self.sudo,
+				multisig: self.multisig.clone(),
+				options: self.options(),
+				wait_timeout: self.wait_timeout(),
+				schedule_at: self.schedule_at,
+				schedule_name: self.schedule_name.clone(),
 				use_wallet: self.use_wallet,
+				offline,
 			});
 		}
 	}
 
+	// Configures the call as per a template, with any endpoint specified via the command line
+	// taking precedence.
+	fn apply_template(&mut self, templates: &CallTemplates, name: &str) -> Result<()> {
+		let template = templates.load(name)?;
+		self.pallet = Some(template.pallet);
+		self.function = Some(template.function);
+		self.args = template.args;
+		self.sudo |= template.sudo;
+		if let Some(url) = template.url {
+			if self.url.is_empty() && self.chain.is_none() && self.light.is_none() {
+				self.url = vec![Url::parse(&url)?];
+			}
+		}
+		Ok(())
+	}
+
+	// Overrides the arguments of a template by the names of their parameters.
+	fn override_args(&self, function: &Function, mut args: Vec<String>) -> Result<Vec<String>> {
+		for (name, value) in &self.overrides {
+			let arg = function
+				.params
+				.iter()
+				.position(|param| &param.name == name)
+				.and_then(|index| args.get_mut(index))
+				.ok_or_else(|| {
+					anyhow!("{}.{} has no parameter named `{name}`", function.pallet, function.name)
+				})?;
+			*arg = value.clone();
+		}
+		Ok(args)
+	}
+
+	// Lists the pallets of the chain, with their dispatchable functions and parameters, as plain
+	// output.
+	fn list_metadata(&self, chain: &Chain, cli: &mut impl Cli) -> Result<()> {
+		let mut pallets = parse_chain_metadata(&chain.connection.client())?;
+		if let Some(name) = &self.pallet {
+			pallets.retain(|pallet| &pallet.name == name);
+			if pallets.is_empty() {
+				return Err(anyhow!("The pallet `{name}` could not be found."));
+			}
+		}
+		cli.plain(render_pallets(&pallets, self.output.unwrap_or_default())?)?;
+		Ok(())
+	}
+
+	// Queries storage items, prompting for the pallet, item and keys where not specified.
+	async fn query_storage(&mut self, chain: &Chain, cli: &mut impl Cli) -> Result<()> {
+		let prompt_to_repeat_query = self.pallet.is_none() || self.item.is_none();
+		loop {
+			let (item, keys) = match self.configure_storage_query(chain, cli) {
+				Ok(query) => query,
+				Err(e) => {
+					display_message(&e.to_string(), false, cli)?;
+					break;
+				},
+			};
+			chain.connection.ensure_alive().await?;
+			let spinner = cli.spinner();
+			spinner.start(format!("Querying {}.{}...", item.pallet, item.name));
+			let value = query_storage(&chain.connection.client(), &item, keys).await;
+			spinner.clear();
+			match value {
+				Ok(Some(value)) => cli.info(format!("{}.{}: {value}", item.pallet, item.name))?,
+				Ok(None) => cli.warning(format!(
+					"No value is stored for {}.{} with the keys provided.",
+					item.pallet, item.name
+				))?,
+				Err(e) => {
+					display_message(&e.to_string(), false, cli)?;
+					break;
+				},
+			}
+			if !prompt_to_repeat_query ||
+				!cli.confirm("Do you want to perform another query?")
+					.initial_value(false)
+					.interact()?
+			{
+				display_message("Query complete.", true, cli)?;
+				break;
+			}
+			self.reset_for_new_call();
+		}
+		Ok(())
+	}
+
+	// Configures a storage query based on command line arguments/call UI.
+	fn configure_storage_query(
+		&self,
+		chain: &Chain,
+		cli: &mut impl Cli,
+	) -> Result<(StorageItem, Vec<String>)> {
+		let client = chain.connection.client();
+		// Resolve pallet, listing those with storage items.
+		let pallet = match self.pallet {
+			Some(ref pallet_name) => find_pallet_by_name(&chain.pallets, pallet_name)?,
+			None => {
+				let mut prompt = cli.select("Select the pallet to query:");
+				for pallet_item in chain.pallets.iter().filter(|p| {
+					parse_storage_items(&client, &p.name).is_ok_and(|items| !items.is_empty())
+				}) {
+					prompt = prompt.item(pallet_item, &pallet_item.name, &pallet_item.docs);
+				}
+				prompt.interact()?
+			},
+		};
+
+		// Resolve storage item.
+		let items = parse_storage_items(&client, &pallet.name)?;
+		let item = match self.item {
+			Some(ref name) => items
+				.iter()
+				.find(|i| &i.name == name)
+				.ok_or_else(|| anyhow!("Failed to find the storage item {}.{name}", pallet.name))?,
+			None => {
+				let mut prompt = cli.select("Select the storage item to query:");
+				for item in &items {
+					prompt = prompt.item(item, &item.name, &item.docs);
+				}
+				prompt.interact()?
+			},
+		};
+		if !item.is_supported {
+			return Err(anyhow!("The storage item {} is not supported yet.", item.name));
+		}
+
+		// Resolve the keys of a map.
+		let keys = if self.args.is_empty() {
+			let calls = CallBuilder::new(&client).with_address_book(load_address_book(cli)?);
+			let mut keys = Vec::new();
+			for key in &item.keys {
+				keys.push(prompt_for_param(cli, key, &calls)?);
+			}
+			keys
+		} else {
+			self.expand_file_arguments()?
+		};
+		Ok((item.clone(), keys))
+	}
+
+	// Submits the calls declared within a manifest, either individually or atomically as a batch.
+	async fn submit_manifest(
+		&mut self,
+		chain: &Chain,
+		path: &Path,
+		cli: &mut impl Cli,
+	) -> Result<()> {
+		let client = chain.connection.client();
+		let calls = CallManifest::load(path)?.resolve(&client)?;
+		let names: Vec<_> = calls
+			.iter()
+			.enumerate()
+			.map(|(i, (function, _))| format!("  {}. {}.{function}", i + 1, function.pallet))
+			.collect();
+		cli.info(format!("Calls declared in {}:\n{}", path.display(), names.join("\n")))?;
+		// Check that the chain supports the wrapping of the calls.
+		if self.batch_all {
+			find_dispatchable_by_name(&chain.pallets, "Utility", "batch_all")
+				.map_err(|_| anyhow!("The chain does not support batching calls."))?;
+		}
+		if self.sudo && find_dispatchable_by_name(&chain.pallets, "Sudo", "sudo").is_err() {
+			cli.warning("NOTE: sudo is not supported by the chain. Ignoring `--sudo` flag.")?;
+			self.sudo = false;
+		}
+		let (_, suri) = self.determine_signing_method(chain, cli)?;
+		let action = match self.batch_all {
+			true => format!("Do you want to submit the {} calls as a batch?", calls.len()),
+			false => format!("Do you want to submit the {} calls?", calls.len()),
+		};
+		if !self.skip_confirm && !cli.confirm(action).initial_value(true).interact()? {
+			display_message("Calls were not submitted.", false, cli)?;
+			return Ok(());
+		}
+		let sudo = |xt| if self.sudo { construct_sudo_extrinsic(xt) } else { xt };
+
+		// Dispatch the calls atomically, with none dispatched should any fail.
+		if self.batch_all {
+			let xt = sudo(batch_all(calls.into_iter().map(|(_, xt)| xt).collect()));
+			let spinner = cli.spinner();
+			spinner.start(format!(
+				"Signing and submitting the batch and then waiting for it to be {}, please be patient...",
+				following(self.options().wait)
+			));
+			let result = network::once(
+				"submitting the extrinsic",
+				sign_and_submit(
+					&client,
+					&chain.connection,
+					xt,
+					&suri,
+					&self.options(),
+					self.wait_timeout(),
+					|status| spinner.set_message(status),
+				),
+			)
+			.await?;
+			spinner.stop(result);
+			display_message(&format!("All {} calls dispatched.", names.len()), true, cli)?;
+			return Ok(());
+		}
+
+		// Otherwise submit each call in turn, continuing should any fail.
+		let total = calls.len();
+		let mut failed = 0;
+		for (i, (function, xt)) in calls.into_iter().enumerate() {
+			let call = format!("Call {} ({}.{function})", i + 1, function.pallet);
+			// Consecutive nonces are used when the first is specified.
+			let options = ExtrinsicOptions {
+				nonce: self.nonce.map(|nonce| nonce + i as u64),
+				..self.options()
+			};
+			let spinner = cli.spinner();
+			spinner.start(format!("Submitting {call}..."));
+			let result = network::once(
+				"submitting the extrinsic",
+				sign_and_submit(
+					&client,
+					&chain.connection,
+					sudo(xt),
+					&suri,
+					&options,
+					self.wait_timeout(),
+					|status| spinner.set_message(status),
+				),
+			)
+			.await;
+			match result {
+				Ok(result) => spinner.stop(format!("✅ {call} succeeded: {result}")),
+				Err(e) => {
+					failed += 1;
+					spinner.error(format!("🚫 {call} failed: {e}"));
+					// The remaining calls are not submitted once a submission times out.
+					network::propagate_timeout(e)?;
+				},
+			}
+		}
+		match failed {
+			0 => display_message(&format!("All {total} calls succeeded."), true, cli),
+			_ => display_message(&format!("{failed} of {total} calls failed."), false, cli),
+		}
+	}
+
 	// Submits an extrinsic to the chain using the provided encoded call data.
 	async fn submit_extrinsic_from_call_data(
 		&self,
-		client: &OnlineClient<SubstrateConfig>,
-		url: &Url,
+		chain: &Chain,
 		call_data: &str,
 		cli: &mut impl Cli,
 	) -> Result<()> {
-		let (use_wallet, suri) = self.determine_signing_method(cli)?;
+		let client = &chain.connection.client();
+		// Wrap the call in a sudo call, should the chain support it.
+		let call_data = match self.sudo {
+			true => match find_dispatchable_by_name(&chain.pallets, "Sudo", "sudo") {
+				Ok(_) => construct_sudo_call_data(&client.metadata(), call_data)?,
+				Err(_) => {
+					cli.warning(
+						"NOTE: sudo is not supported by the chain. Ignoring `--sudo` flag.",
+					)?;
+					call_data.to_string()
+				},
+			},
+			false => call_data.to_string(),
+		};
+		let call_data_bytes =
+			decode_call_data(&call_data).map_err(|err| anyhow!("{}", format!("{err:?}")))?;
+		if let Some(offline) = self.offline() {
+			write_payload(client, &chain.endpoint, call_data_bytes, &offline, cli).await?;
+			display_message("Call complete.", true, cli)?;
+			return Ok(());
+		}
+		let (use_wallet, suri) = self.determine_signing_method(chain, cli)?;
 
 		// Perform signing steps with wallet integration and return early.
 		if use_wallet {
-			let call_data_bytes =
-				decode_call_data(call_data).map_err(|err| anyhow!("{}", format!("{err:?}")))?;
-			submit_extrinsic_with_wallet(client, url, call_data_bytes, cli)
-				.await
-				.map_err(|err| anyhow!("{}", format!("{err:?}")))?;
+			submit_extrinsic_with_wallet(
+				client,
+				&chain.connection,
+				&chain.endpoint,
+				call_data_bytes,
+				cli,
+			)
+			.await
+			.map_err(|err| anyhow!("{}", format!("{err:?}")))?;
 			display_message("Call complete.", true, cli)?;
 			return Ok(());
 		}
@@ -263,34 +918,99 @@ impl CallChainCommand {
 			)?;
 			return Ok(());
 		}
-		let spinner = cliclack::spinner();
-		spinner.start("Signing and submitting the extrinsic and then waiting for finalization, please be patient...");
-		let call_data_bytes =
-			decode_call_data(call_data).map_err(|err| anyhow!("{}", format!("{err:?}")))?;
-		let result = sign_and_submit_extrinsic(client, url, CallData::new(call_data_bytes), &suri)
-			.await
-			.map_err(|err| anyhow!("{}", format!("{err:?}")))?;
+		let spinner = cli.spinner();
+		spinner.start(format!(
+			"Signing and submitting the extrinsic and then waiting for it to be {}, please be patient...",
+			following(self.options().wait)
+		));
+		let xt = CallData::new(call_data_bytes);
+		let result = network::once(
+			"submitting the extrinsic",
+			sign_and_submit(
+				client,
+				&chain.connection,
+				xt,
+				&suri,
+				&self.options(),
+				self.wait_timeout(),
+				|status| spinner.set_message(status),
+			),
+		)
+		.await?;
 
 		spinner.stop(result);
 		display_message("Call complete.", true, cli)?;
 		Ok(())
 	}
 
+	// Submits an extrinsic signed offline via `pop sign`, once checked against the chain.
+	async fn submit_signed_payload(
+		&self,
+		chain: &Chain,
+		path: &Path,
+		cli: &mut impl Cli,
+	) -> Result<()> {
+		let contents = fs::read_to_string(path)
+			.map_err(|e| anyhow!("Failed to read the signed payload {}: {e}", path.display()))?;
+		let signed: SignedPayload = serde_json::from_str(&contents)
+			.map_err(|e| anyhow!("Invalid signed payload {}: {e}", path.display()))?;
+		let client = chain.connection.client();
+		let extrinsic = signed_extrinsic(&client, &signed)?;
+		cli.info(format!("Encoded call data: {}", signed.payload.call_data))?;
+		cli.info(format!("Signed by: {}", signed.payload.signer))?;
+		if !self.skip_confirm &&
+			!cli.confirm("Do you want to submit the extrinsic?")
+				.initial_value(true)
+				.interact()?
+		{
+			display_message(
+				&format!("Extrinsic signed in {} was not submitted.", path.display()),
+				false,
+				cli,
+			)?;
+			return Ok(());
+		}
+		let spinner = cli.spinner();
+		spinner.start(
+			"Submitting the extrinsic and then waiting for finalization, please be patient...",
+		);
+		let events = network::once("submitting the extrinsic", async {
+			submit_signed_extrinsic(client.clone(), extrinsic, |status| spinner.set_message(status))
+				.await
+				.map_err(|err| anyhow!("{err}"))
+		})
+		.await?;
+		spinner.stop(describe_events(&client, &chain.connection, &events).await?);
+		display_message("Call complete.", true, cli)?;
+		Ok(())
+	}
+
+	// The file to which the payload is written and its signer, if the extrinsic is to be signed
+	// offline.
+	fn offline(&self) -> Option<Offline> {
+		let (path, signer) = self.create_payload.clone().zip(self.signer.clone())?;
+		Some(Offline { path, signer })
+	}
+
 	// Resolve who is signing the extrinsic. If a `suri` was provided via the command line,
-	// skip the prompt.
-	fn determine_signing_method(&self, cli: &mut impl Cli) -> Result<(bool, String)> {
+	// skip the prompt. Browser wallets are only offered for chains with Substrate accounts.
+	fn determine_signing_method(
+		&self,
+		chain: &Chain,
+		cli: &mut impl Cli,
+	) -> Result<(bool, String)> {
 		let mut use_wallet = self.use_wallet;
 		let suri = match self.suri.as_ref() {
 			Some(suri) => suri.clone(),
 			None =>
 				if !self.use_wallet {
-					if prompt_to_use_wallet(cli)? {
+					if chain.account_format == AccountFormat::Substrate &&
+						prompt_to_use_wallet(cli)?
+					{
 						use_wallet = true;
 						DEFAULT_URI.to_string()
 					} else {
-						cli.input("Signer of the extrinsic:")
-							.default_input(DEFAULT_URI)
-							.interact()?
+						prompt_suri("Signer of the extrinsic", chain.default_uri(), cli)?
 					}
 				} else {
 					DEFAULT_URI.to_string()
@@ -323,11 +1043,28 @@ impl CallChainCommand {
 		Ok(())
 	}
 
+	// The nonce, tip and mortality of the extrinsic, and how far it is followed.
+	fn options(&self) -> ExtrinsicOptions {
+		ExtrinsicOptions {
+			nonce: self.nonce,
+			tip: self.tip.unwrap_or_default(),
+			era: self.era,
+			wait: self.wait.unwrap_or_default(),
+		}
+	}
+
+	// The maximum time to wait for the extrinsic to be followed, if limited.
+	fn wait_timeout(&self) -> Option<Duration> {
+		self.wait_timeout.map(Duration::from_secs)
+	}
+
 	// Resets specific fields to default values for a new call.
 	fn reset_for_new_call(&mut self) {
 		self.pallet = None;
 		self.function = None;
+		self.item = None;
 		self.args.clear();
+		self.args_json = None;
 		self.sudo = false;
 		self.use_wallet = false;
 	}
@@ -336,9 +1073,9 @@ impl CallChainCommand {
 	fn requires_user_input(&self) -> bool {
 		self.pallet.is_none() ||
 			self.function.is_none() ||
-			self.args.is_empty() ||
-			self.url.is_none() ||
-			self.suri.is_none()
+			(self.args.is_empty() && self.args_json.is_none()) ||
+			(self.url.is_empty() && self.chain.is_none() && self.light.is_none()) ||
+			(self.suri.is_none() && self.create_payload.is_none())
 	}
 
 	/// Replaces file arguments with their contents, leaving other arguments unchanged.
@@ -357,14 +1094,106 @@ impl CallChainCommand {
 	}
 }
 
-// Represents a chain, including its URL, client connection, and available pallets.
+// Represents a chain, including its endpoint, client connection, and available pallets.
 struct Chain {
-	// Websocket endpoint of the node.
-	url: Url,
-	// The client used to interact with the chain.
-	client: OnlineClient<SubstrateConfig>,
+	// How the chain is connected to.
+	endpoint: Endpoint,
+	// The connection to the chain, shared by all operations.
+	connection: Connection,
 	// A list of pallets available on the chain.
 	pallets: Vec<Pallet>,
+	// The format of the accounts of the chain.
+	account_format: AccountFormat,
+	// The native token of the chain, via which balances may be entered.
+	token: NativeToken,
+}
+
+impl Chain {
+	// Lists the pallets from the chain metadata, with parameters resolved once a function is
+	// selected.
+	fn new(endpoint: Endpoint, connection: Connection) -> Self {
+		let mut pallets = list_pallets(&connection.client());
+		// Sort by name for display.
+		pallets.sort_by(|a, b| a.name.cmp(&b.name));
+		pallets.iter_mut().for_each(|p| p.functions.sort_by(|a, b| a.name.cmp(&b.name)));
+		let account_format = AccountFormat::detect(&connection.client().metadata());
+		Self { endpoint, connection, pallets, account_format, token: NativeToken::default() }
+	}
+
+	// Fetches the symbol and decimals of the native token, unless unavailable.
+	async fn fetch_token(&mut self) {
+		if let Ok(token) = self.connection.token_metadata().await {
+			self.token = NativeToken {
+				symbol: Some(token.symbol),
+				decimals: u8::try_from(token.token_decimals).ok(),
+			};
+		}
+	}
+
+	// The secret URI of the development account offered as the signer by default.
+	fn default_uri(&self) -> &'static str {
+		match self.account_format {
+			AccountFormat::Substrate => DEFAULT_URI,
+			AccountFormat::Ethereum => ALITH_PRIVATE_KEY,
+		}
+	}
+
+	// Adds the features of metadata V16 to the pallets, if provided by the runtime, warning if
+	// the chain no longer accepts the extrinsics submitted by pop.
+	async fn apply_metadata_v16(&mut self, cli: &mut impl Cli) -> Result<()> {
+		// Metadata V16 is optional, so any failure to fetch it is ignored.
+		let Ok(Some(metadata)) = MetadataV16::fetch(&self.connection.client()).await else {
+			return Ok(());
+		};
+		metadata.apply(&mut self.pallets);
+		if !metadata.supports_extrinsic_version(EXTRINSIC_VERSION) {
+			cli.warning(format!(
+				"⚠️ The chain does not accept extrinsics of version {EXTRINSIC_VERSION}, which are submitted by pop."
+			))?;
+		}
+		Ok(())
+	}
+}
+
+// How a chain is connected to, displayed as the corresponding arguments of the command.
+#[derive(Clone, Debug, PartialEq)]
+enum Endpoint {
+	// Via the websocket endpoints of nodes, in order of preference.
+	Urls(Vec<Url>),
+	// Via the endpoint of a named chain.
+	Named { name: String, url: Url },
+	// Via an embedded light client, using the chain specs at the specified paths.
+	Light { chain_spec: PathBuf, relay_chain_spec: Option<PathBuf> },
+}
+
+impl Endpoint {
+	// The preferred websocket endpoint, unless connected via a light client.
+	fn url(&self) -> Option<&Url> {
+		match self {
+			Endpoint::Urls(urls) => urls.first(),
+			Endpoint::Named { url, .. } => Some(url),
+			Endpoint::Light { .. } => None,
+		}
+	}
+}
+
+impl Display for Endpoint {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Endpoint::Urls(urls) => {
+				let args: Vec<_> = urls.iter().map(|url| format!("--url {url}")).collect();
+				write!(f, "{}", args.join(" "))
+			},
+			Endpoint::Named { name, .. } => write!(f, "--chain {name}"),
+			Endpoint::Light { chain_spec, relay_chain_spec } => {
+				write!(f, "--light {}", chain_spec.display())?;
+				if let Some(relay_chain_spec) = relay_chain_spec {
+					write!(f, " --relay-chain-spec {}", relay_chain_spec.display())?;
+				}
+				Ok(())
+			},
+		}
+	}
 }
 
 /// Represents a configured dispatchable function call, including the pallet, function, arguments,
@@ -387,9 +1216,85 @@ struct Call {
 	skip_confirm: bool,
 	/// Whether to dispatch the function call with `Root` origin.
 	sudo: bool,
+	/// The multisig account dispatching the call, if any.
+	multisig: Option<Multisig>,
+	/// The nonce, tip and mortality of the extrinsic, and how far it is followed.
+	options: ExtrinsicOptions,
+	/// The maximum time to wait for the extrinsic to be followed, if limited.
+	wait_timeout: Option<Duration>,
+	/// When the call is scheduled to be dispatched, if it is not dispatched immediately.
+	schedule_at: Option<ScheduleAt>,
+	/// The name of the scheduled task, if any.
+	schedule_name: Option<String>,
+	/// Where the payload is written for the extrinsic to be signed offline, if at all.
+	offline: Option<Offline>,
+}
+
+/// The payload of an extrinsic to be written to a file and signed offline, rather than signing
+/// the extrinsic directly.
+#[derive(Clone, Debug, PartialEq)]
+struct Offline {
+	/// The file to which the payload is written.
+	path: PathBuf,
+	/// The address of the account which is to sign the payload.
+	signer: String,
 }
 
 impl Call {
+	// The template of the call, via which it can be re-run on the same chain.
+	fn template(&self, chain: &Chain) -> CallTemplate {
+		CallTemplate {
+			pallet: self.function.pallet.clone(),
+			function: self.function.name.clone(),
+			args: self.args.clone(),
+			url: chain.endpoint.url().map(Url::to_string),
+			sudo: self.sudo,
+		}
+	}
+
+	// The account signing the extrinsic.
+	fn signatory(&self) -> Result<AccountId32> {
+		Ok(match &self.offline {
+			Some(offline) => parse_signer(&offline.signer)?,
+			None => create_signer(&self.suri)?.public_key().to_account_id(),
+		})
+	}
+
+	// The account from which the call is dispatched.
+	fn origin(&self) -> Result<AccountId32> {
+		match &self.multisig {
+			Some(multisig) => Ok(multisig.account()),
+			None => self.signatory(),
+		}
+	}
+
+	// Wraps the call such that it is approved by the signatory of the multisig account,
+	// describing the approval.
+	async fn approve_multisig(
+		&self,
+		chain: &Chain,
+		client: &OnlineClient<SubstrateConfig>,
+		multisig: &Multisig,
+		xt: DynamicPayload,
+		cli: &mut impl Cli,
+	) -> Result<MultisigCall> {
+		if chain.account_format == AccountFormat::Ethereum {
+			return Err(anyhow!("Multisig accounts are not supported for Ethereum-style accounts."));
+		}
+		find_pallet_by_name(&chain.pallets, "Multisig")
+			.map_err(|_| anyhow!("The chain does not support multisig accounts."))?;
+		let approval = multisig_call(client, multisig, &self.signatory()?, xt).await?;
+		let action = match approval.executes {
+			true => "dispatched",
+			false => "approved",
+		};
+		cli.info(format!(
+			"The call is {action} by the multisig account {} with approval {} of {}.",
+			approval.account, approval.approvals, multisig.threshold
+		))?;
+		Ok(approval)
+	}
+
 	// Prepares the extrinsic.
 	fn prepare_extrinsic(
 		&self,
@@ -402,6 +1307,11 @@ impl Call {
 				return Err(anyhow!("Error: {}", e));
 			},
 		};
+		// If scheduled, wrap the call in a call to the scheduler.
+		let xt = match self.schedule_at {
+			Some(at) => schedule(xt, at, self.schedule_name.as_deref())?,
+			None => xt,
+		};
 		// If sudo is required, wrap the call in a sudo call.
 		let xt = if self.sudo { construct_sudo_extrinsic(xt) } else { xt };
 		let encoded_data = encode_call_data(client, &xt)?;
@@ -416,7 +1326,7 @@ impl Call {
 	async fn submit_extrinsic(
 		&mut self,
 		client: &OnlineClient<SubstrateConfig>,
-		url: &Url,
+		connection: &Connection,
 		tx: DynamicPayload,
 		cli: &mut impl Cli,
 	) -> Result<()> {
@@ -432,11 +1342,24 @@ impl Call {
 			)?;
 			return Ok(());
 		}
-		let spinner = cliclack::spinner();
-		spinner.start("Signing and submitting the extrinsic and then waiting for finalization, please be patient...");
-		let result = sign_and_submit_extrinsic(client, url, tx, &self.suri)
-			.await
-			.map_err(|err| anyhow!("{}", format!("{err:?}")))?;
+		let spinner = cli.spinner();
+		spinner.start(format!(
+			"Signing and submitting the extrinsic and then waiting for it to be {}, please be patient...",
+			following(self.options.wait)
+		));
+		let result = network::once(
+			"submitting the extrinsic",
+			sign_and_submit(
+				client,
+				connection,
+				tx,
+				&self.suri,
+				&self.options,
+				self.wait_timeout,
+				|status| spinner.set_message(status),
+			),
+		)
+		.await?;
 		spinner.stop(result);
 		Ok(())
 	}
@@ -460,39 +1383,163 @@ impl Call {
 				.collect();
 			full_message.push_str(&format!(" --args {}", args.join(" ")));
 		}
-		full_message.push_str(&format!(" --url {}", chain.url));
-		if self.use_wallet {
+		full_message.push_str(&format!(" {}", chain.endpoint));
+		if let Some(Offline { path, signer }) = &self.offline {
+			full_message
+				.push_str(&format!(" --create-payload {} --signer {signer}", path.display()));
+		} else if self.use_wallet {
 			full_message.push_str(" --use-wallet");
 		} else {
-			full_message.push_str(&format!(" --suri {}", self.suri));
+			let suri = match self.suri == chain.default_uri() {
+				true => &self.suri,
+				false => mask_suri(&self.suri),
+			};
+			full_message.push_str(&format!(" --suri {suri}"));
 		}
 		if self.sudo {
 			full_message.push_str(" --sudo");
 		}
+		if let Some(multisig) = &self.multisig {
+			full_message.push_str(&format!(" --multisig \"{multisig}\""));
+		}
+		let ExtrinsicOptions { nonce, tip, era, wait } = &self.options;
+		if let Some(nonce) = nonce {
+			full_message.push_str(&format!(" --nonce {nonce}"));
+		}
+		if *tip > 0 {
+			full_message.push_str(&format!(" --tip {tip}"));
+		}
+		if let Some(era) = era {
+			full_message.push_str(&format!(" --era {era}"));
+		}
+		if *wait != WaitFor::default() {
+			full_message.push_str(&format!(" --wait {}", wait.as_ref()));
+		}
+		if let Some(timeout) = self.wait_timeout {
+			full_message.push_str(&format!(" --wait-timeout {}", timeout.as_secs()));
+		}
+		if let Some(at) = &self.schedule_at {
+			full_message.push_str(&format!(" --schedule-at {at}"));
+		}
+		if let Some(name) = &self.schedule_name {
+			full_message.push_str(&format!(" --schedule-name {name}"));
+		}
 		full_message
 	}
 }
 
+// Writes the payload of an extrinsic to a file, to be signed offline via `pop sign`.
+async fn write_payload(
+	client: &OnlineClient<SubstrateConfig>,
+	endpoint: &Endpoint,
+	call_data: Vec<u8>,
+	offline: &Offline,
+	cli: &mut impl Cli,
+) -> Result<()> {
+	let payload = network::once("creating the payload", async {
+		create_payload(client, call_data, &offline.signer).await
+	})
+	.await?;
+	fs::write(&offline.path, serde_json::to_string_pretty(&payload)?)?;
+	let path = offline.path.display();
+	cli.success(format!("Payload written to {path}, to be signed by {}.", payload.signer))?;
+	cli.info(format!(
+		"Sign it offline via `pop sign {path}`, then submit it via `pop call chain --submit-signed <SIGNED> {endpoint}`."
+	))?;
+	Ok(())
+}
+
+// Parses the address of the account which is to sign an extrinsic offline.
+fn parse_signer(signer: &str) -> Result<AccountId32> {
+	AccountId32::from_str(signer).map_err(|e| anyhow!("Invalid signer {signer}: {e:?}"))
+}
+
+// Signs and submits an extrinsic, following it as far as specified by the options and within any
+// timeout, describing its outcome along with links to it within any explorers of the chain.
+async fn sign_and_submit<Xt: Payload>(
+	client: &OnlineClient<SubstrateConfig>,
+	connection: &Connection,
+	xt: Xt,
+	suri: &str,
+	options: &ExtrinsicOptions,
+	timeout: Option<Duration>,
+	report: impl FnMut(String),
+) -> Result<String> {
+	let submission = sign_and_submit_extrinsic_until(client, xt, suri, options, report);
+	let submitted = match timeout {
+		Some(timeout) => tokio::time::timeout(timeout, submission).await.map_err(|_| {
+			let operation = format!("waiting for the extrinsic to be {}", following(options.wait));
+			TimedOut { operation, timeout }
+		})?,
+		None => submission.await,
+	}
+	.map_err(|err| anyhow!("{err}"))?;
+	match submitted {
+		Submitted::Included(events) => describe_events(client, connection, &events).await,
+		Submitted::Accepted(hash) => {
+			let hash = format!("{hash:?}");
+			let links = extrinsic_links(connection.url().as_deref(), &hash);
+			Ok(format!("Extrinsic Submitted with hash: {hash}{links}"))
+		},
+	}
+}
+
+// Describes how far an extrinsic is followed once submitted, e.g. for messages shown whilst
+// waiting.
+fn following(wait: WaitFor) -> &'static str {
+	match wait {
+		WaitFor::None => "accepted by the node",
+		WaitFor::Best => "included in the best block",
+		WaitFor::Finalized => "finalized",
+	}
+}
+
+// Describes a finalized extrinsic by its decoded events, with links to it on explorers.
+async fn describe_events(
+	client: &OnlineClient<SubstrateConfig>,
+	connection: &Connection,
+	events: &ExtrinsicEvents<SubstrateConfig>,
+) -> Result<String> {
+	let token_metadata = connection.token_metadata().await?;
+	let result = display_extrinsic_events(client, &token_metadata, events)
+		.map_err(|err| anyhow!("{}", format!("{err:?}")))?;
+	let hash = format!("{:?}", events.extrinsic_hash());
+	Ok(format!("{result}{}", extrinsic_links(connection.url().as_deref(), &hash)))
+}
+
+// Links to an extrinsic within the explorers of the chain at the specified endpoint, if any.
+fn extrinsic_links(url: Option<&str>, hash: &str) -> String {
+	url.map(|url| explorer_links(url, &[ExplorerItem::Extrinsic(hash)]))
+		.unwrap_or_default()
+}
+
 // Sign and submit an extrinsic using wallet integration.
 async fn submit_extrinsic_with_wallet(
 	client: &OnlineClient<SubstrateConfig>,
-	url: &Url,
+	connection: &Connection,
+	endpoint: &Endpoint,
 	call_data: Vec<u8>,
 	cli: &mut impl Cli,
 ) -> Result<()> {
-	let maybe_payload = request_signature(call_data, url.to_string()).await?;
+	let url =
+		endpoint.url().ok_or_else(|| {
+			anyhow!("Signing with a wallet requires the endpoint of a node, rather than a light client.")
+		})?;
+	let maybe_payload = request_signature(call_data, url.to_string(), cli).await?;
 	if let Some(payload) = maybe_payload {
 		cli.success("Signed payload received.")?;
-		let spinner = cliclack::spinner();
+		let spinner = cli.spinner();
 		spinner.start(
 			"Submitting the extrinsic and then waiting for finalization, please be patient...",
 		);
 
-		let result = submit_signed_extrinsic(client.clone(), payload)
-			.await
-			.map_err(|err| anyhow!("{}", format!("{err:?}")))?;
-
-		spinner.stop(format!("Extrinsic submitted with hash: {:?}", result));
+		let events = network::once("submitting the extrinsic", async {
+			submit_signed_extrinsic(client.clone(), payload, |status| spinner.set_message(status))
+				.await
+				.map_err(|err| anyhow!("{err}"))
+		})
+		.await?;
+		spinner.stop(describe_events(client, connection, &events).await?);
 	} else {
 		display_message("No signed payload received.", false, cli)?;
 	}
@@ -523,8 +1570,12 @@ fn prompt_predefined_actions(pallets: &[Pallet], cli: &mut impl Cli) -> Result<O
 	Ok(predefined_action.interact()?)
 }
 
-// Prompts the user for the value of a parameter.
-fn prompt_for_param(cli: &mut impl Cli, param: &Param) -> Result<String> {
+// Prompts the user for the value of a parameter, composing any calls via the builder.
+pub(crate) fn prompt_for_param(
+	cli: &mut impl Cli,
+	param: &Param,
+	calls: &CallBuilder,
+) -> Result<String> {
 	if param.is_optional {
 		if !cli
 			.confirm(format!(
@@ -535,30 +1586,187 @@ fn prompt_for_param(cli: &mut impl Cli, param: &Param) -> Result<String> {
 		{
 			return Ok("None()".to_string());
 		}
-		let value = get_param_value(cli, param)?;
+		let value = get_param_value(cli, param, calls)?;
 		Ok(format!("Some({})", value))
 	} else {
-		get_param_value(cli, param)
+		get_param_value(cli, param, calls)
 	}
 }
 
 // Resolves the value of a parameter based on its type.
-fn get_param_value(cli: &mut impl Cli, param: &Param) -> Result<String> {
-	if param.is_sequence {
-		prompt_for_sequence_param(cli, param)
-	} else if param.sub_params.is_empty() {
+fn get_param_value(cli: &mut impl Cli, param: &Param, calls: &CallBuilder) -> Result<String> {
+	let token = calls.token();
+	if param.is_call && param.is_sequence {
+		prompt_for_calls_param(cli, param, calls)
+	} else if param.is_call {
+		prompt_for_call_param(cli, param, calls)
+	} else if param.is_sequence {
+		prompt_for_sequence_param(cli, param, calls)
+	} else if let (true, Some(symbol), Some(decimals)) =
+		(param.is_balance, &token.symbol, token.decimals)
+	{
+		prompt_for_balance_param(cli, param, symbol, decimals)
+	} else if param.is_address {
+		prompt_for_address_param(cli, param, calls)
+	} else if ValueKind::of(&param.type_name) == Some(ValueKind::AccountId32) {
+		match select_account(cli, param, calls)? {
+			Some(address) => Ok(address),
+			None => prompt_for_primitive_param(cli, param),
+		}
+	} else if param.sub_params.is_empty() || ValueKind::of(&param.type_name).is_some() {
+		// Values of known types (e.g. accounts) are entered as a whole, so they can be validated.
 		prompt_for_primitive_param(cli, param)
 	} else if param.is_variant {
-		prompt_for_variant_param(cli, param)
+		prompt_for_variant_param(cli, param, calls)
 	} else if param.is_tuple {
-		prompt_for_tuple_param(cli, param)
+		prompt_for_tuple_param(cli, param, calls)
 	} else {
-		prompt_for_composite_param(cli, param)
+		prompt_for_composite_param(cli, param, calls)
+	}
+}
+
+// Prompt the user to select the pallet or function to call, first searching for it by name where
+// there are too many to scroll through. Matches are listed from best to worst.
+fn search_and_select<'a, T: Eq>(
+	cli: &mut impl Cli,
+	kind: &str,
+	items: &'a [T],
+	name: impl Fn(&T) -> &str,
+	hint: impl Fn(&T) -> String,
+) -> Result<&'a T> {
+	let mut matches: Vec<&T> = items.iter().collect();
+	while items.len() > SEARCH_THRESHOLD {
+		let query = cli
+			.input(format!("Search for the {kind} to call:"))
+			.placeholder("Part of its name, or leave empty to list all")
+			.required(false)
+			.interact()?;
+		matches = search(&query, items, &name);
+		if !matches.is_empty() {
+			break;
+		}
+		cli.warning(format!("No {kind} matches `{query}`, please search again."))?;
+	}
+	let mut prompt = cli.select(format!("Select the {kind} to call:"));
+	for item in matches {
+		prompt = prompt.item(item, name(item), hint(item));
 	}
+	Ok(prompt.interact()?)
 }
 
-// Prompt for the value when it is a sequence.
-fn prompt_for_sequence_param(cli: &mut impl Cli, param: &Param) -> Result<String> {
+// Prompt the user to compose a call, selecting its pallet and function before recursively
+// prompting for its arguments. Output example: `System (remark { remark: (104, 105) })`.
+fn prompt_for_call_param(cli: &mut impl Cli, param: &Param, calls: &CallBuilder) -> Result<String> {
+	loop {
+		let pallet = {
+			let mut select = cli
+				.select(format!("Select the pallet of the call for the parameter: {}", param.name));
+			for pallet in calls.pallets() {
+				select = select.item(pallet, &pallet.name, &pallet.docs);
+			}
+			select.interact()?
+		};
+		let function = {
+			let mut select =
+				cli.select(format!("Select the function of the {} call:", pallet.name));
+			for function in &pallet.functions {
+				select = select.item(function, &function.name, &function.docs);
+			}
+			select.interact()?
+		};
+		let function = calls.resolve(function)?;
+		if !function.is_supported {
+			cli.warning("The selected function is not supported yet. Please choose another one.")?;
+			continue;
+		}
+		let mut args = Vec::new();
+		for param in &function.params {
+			args.push(prompt_for_param(cli, param, calls)?);
+		}
+		return Ok(calls.build(&function, args)?.to_string());
+	}
+}
+
+// Prompt the user to compose a sequence of calls, e.g. for a batch.
+fn prompt_for_calls_param(
+	cli: &mut impl Cli,
+	param: &Param,
+	calls: &CallBuilder,
+) -> Result<String> {
+	let mut values = Vec::new();
+	loop {
+		values.push(prompt_for_call_param(cli, param, calls)?);
+		if !cli
+			.confirm(format!("Do you want to add another call to `{}`?", param.name))
+			.initial_value(false)
+			.interact()?
+		{
+			break;
+		}
+	}
+	Ok(format!("({})", values.join(", ")))
+}
+
+// Prompt the user for the items of a sequence one at a time, each as per the type of the items
+// such that composite items are composed field by field. Sequences of bytes, or whose items cannot
+// be described, are entered as a whole.
+fn prompt_for_sequence_param(
+	cli: &mut impl Cli,
+	param: &Param,
+	calls: &CallBuilder,
+) -> Result<String> {
+	let Some(item) = param.sub_params.first() else {
+		return prompt_for_raw_sequence_param(cli, param);
+	};
+	let mut items: Vec<String> = Vec::new();
+	loop {
+		let action = {
+			let mut select = cli
+				.select(format!(
+					"Add the items of `{}` one at a time ({} so far):",
+					param.name,
+					items.len()
+				))
+				.item(SequenceAction::Add, "Add an item", &item.type_name);
+			if let Some(last) = items.last() {
+				select = select.item(SequenceAction::Remove, "Remove the last item", last);
+			}
+			select
+				.item(SequenceAction::Done, "Done", format!("({})", items.join(", ")))
+				.interact()?
+		};
+		match action {
+			SequenceAction::Add => {
+				let value = prompt_for_param(cli, item, calls)?;
+				// Check the item can be parsed before adding it.
+				match parse_dispatchable_arguments(std::slice::from_ref(item), vec![value.clone()])
+				{
+					Ok(_) => items.push(value),
+					Err(_) => cli.warning(format!(
+						"`{value}` is not a valid {}, so was not added.",
+						item.type_name
+					))?,
+				}
+			},
+			SequenceAction::Remove => {
+				items.pop();
+			},
+			SequenceAction::Done => break,
+		}
+	}
+	Ok(format!("({})", items.join(", ")))
+}
+
+// The actions available whilst entering the items of a sequence.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum SequenceAction {
+	Add,
+	Remove,
+	Done,
+}
+
+// Prompt for the value of a sequence as a whole, or the path to a file containing it.
+fn prompt_for_raw_sequence_param(cli: &mut impl Cli, param: &Param) -> Result<String> {
 	let input_value = cli
 		.input(format!(
 		"The value for `{}` might be too large to enter. You may enter the path to a file instead.",
@@ -573,21 +1781,159 @@ fn prompt_for_sequence_param(cli: &mut impl Cli, param: &Param) -> Result<String
 		return std::fs::read_to_string(&input_value)
 			.map_err(|err| anyhow!("Failed to read file {}", err.to_string()));
 	}
-	Ok(input_value)
+	Ok(input_value)
+}
+
+// Prompt for the value when it is a primitive, re-prompting until the value is valid for types
+// which are known.
+fn prompt_for_primitive_param(cli: &mut impl Cli, param: &Param) -> Result<String> {
+	let input = cli.input(format!("Enter the value for the parameter: {}", param.name));
+	let Some(kind) = ValueKind::of(&param.type_name) else {
+		return Ok(input.placeholder(&format!("Type required: {}", param.type_name)).interact()?);
+	};
+	let range = kind.range().map(|range| format!(" ({range})")).unwrap_or_default();
+	Ok(input
+		.placeholder(&format!("Type required: {}{range}", param.type_name))
+		.validate(move |value| kind.validate(value).map_err(|_| kind.expected()))
+		.interact()?)
+}
+
+// Prompt for a balance, either as an amount of the native token (e.g. `1.5 DOT`) or in its
+// smallest unit, confirming the value in the smallest unit of any amount of the token.
+fn prompt_for_balance_param(
+	cli: &mut impl Cli,
+	param: &Param,
+	symbol: &str,
+	decimals: u8,
+) -> Result<String> {
+	loop {
+		let token = symbol.to_string();
+		let input = cli
+			.input(format!("Enter the value for the parameter: {}", param.name))
+			.placeholder(&format!(
+				"An amount of {symbol} (e.g. 1.5 {symbol}), or of its smallest unit"
+			))
+			.validate(move |input| match parse_balance(input, &token, decimals) {
+				Ok(_) => Ok(()),
+				Err(_) => Err("Invalid amount."),
+			})
+			.interact()?;
+		let balance = parse_balance(&input, symbol, decimals)?;
+		// Values in the smallest unit are used as entered.
+		if input.trim().parse::<u128>().is_ok() ||
+			cli.confirm(format!(
+				"{} is {balance} in the smallest unit of {symbol}. Is this correct?",
+				input.trim()
+			))
+			.initial_value(true)
+			.interact()?
+		{
+			return Ok(balance.to_string());
+		}
+	}
+}
+
+// Prompt for an address as the account of its `Id` variant, either as an SS58 address or a
+// development account (e.g. `//Alice`), or for any of its variants when left empty. Output example:
+// `Id(5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY)`.
+fn prompt_for_address_param(
+	cli: &mut impl Cli,
+	param: &Param,
+	calls: &CallBuilder,
+) -> Result<String> {
+	// Addresses of other accounts (e.g. Ethereum-style) are selected by variant.
+	let is_account = |variant: &Param| {
+		variant.name == "Id" &&
+			variant.sub_params.first().and_then(|account| ValueKind::of(&account.type_name)) ==
+				Some(ValueKind::AccountId32)
+	};
+	if !param.sub_params.iter().any(is_account) {
+		return prompt_for_variant_param(cli, param, calls);
+	}
+	if let Some(address) = select_account(cli, param, calls)? {
+		return Ok(format!("Id({address})"));
+	}
+	let input = cli
+		.input(format!("Enter the value for the parameter: {}", param.name))
+		.placeholder(
+			"An SS58 address or development account (e.g. //Alice), or leave empty to select the kind of address",
+		)
+		.required(false)
+		.validate(|input| match input.trim() {
+			"" => Ok(()),
+			input => resolve_account(input)
+				.map(|_| ())
+				.map_err(|_| "Expected an SS58 address or development account, e.g. //Alice."),
+		})
+		.interact()?;
+	match input.trim() {
+		"" => prompt_for_variant_param(cli, param, calls),
+		input => Ok(format!("Id({})", resolve_account(input)?)),
+	}
 }
 
-// Prompt for the value when it is a primitive.
-fn prompt_for_primitive_param(cli: &mut impl Cli, param: &Param) -> Result<String> {
-	Ok(cli
-		.input(format!("Enter the value for the parameter: {}", param.name))
-		.placeholder(&format!("Type required: {}", param.type_name))
-		.interact()?)
+// Prompt the user to select an account from their address book or the development accounts,
+// returning its address, or `None` should the user opt to enter the account instead.
+fn select_account(
+	cli: &mut impl Cli,
+	param: &Param,
+	calls: &CallBuilder,
+) -> Result<Option<String>> {
+	let mut accounts: Vec<_> = calls
+		.address_book()
+		.accounts()
+		.into_iter()
+		.map(|a| (a.name, a.address))
+		.collect();
+	for name in DEV_ACCOUNTS {
+		let uri = format!("//{name}");
+		let address = create_signer(&uri)?.public_key().to_account_id().to_string();
+		accounts.push((uri, address));
+	}
+	let mut prompt = cli.select(format!("Select the account for the parameter: {}", param.name));
+	for (index, (name, address)) in accounts.iter().enumerate() {
+		prompt = prompt.item(Some(index), name, address);
+	}
+	let selected = prompt.item(None, "Enter an account", "").interact()?;
+	Ok(selected.map(|index| accounts.swap_remove(index).1))
+}
+
+// Loads the address book of the user, warning should it be invalid.
+fn load_address_book(cli: &mut impl Cli) -> Result<AddressBook> {
+	match AddressBook::open() {
+		Ok(address_book) => Ok(address_book),
+		Err(e) => {
+			cli.warning(format!("The address book could not be loaded: {e}"))?;
+			Ok(AddressBook::default())
+		},
+	}
+}
+
+// Resolves an account from its address, or the URI of a development account (e.g. `//Alice`).
+fn resolve_account(input: &str) -> Result<String> {
+	if input.starts_with("//") {
+		return Ok(create_signer(input)?.public_key().to_account_id().to_string());
+	}
+	ValueKind::AccountId32.validate(input)?;
+	Ok(input.to_string())
+}
+
+// Renders the pallets of a chain in the specified format.
+fn render_pallets(pallets: &[Pallet], format: ListFormat) -> Result<String> {
+	Ok(match format {
+		ListFormat::Json => serde_json::to_string_pretty(pallets)?,
+		ListFormat::Yaml => serde_yaml::to_string(pallets)?,
+	})
 }
 
 // Prompt the user to select the value of the variant parameter and recursively prompt for nested
 // fields. Output example: `Id(5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY)` for the `Id`
 // variant.
-fn prompt_for_variant_param(cli: &mut impl Cli, param: &Param) -> Result<String> {
+fn prompt_for_variant_param(
+	cli: &mut impl Cli,
+	param: &Param,
+	calls: &CallBuilder,
+) -> Result<String> {
 	let selected_variant = {
 		let mut select = cli.select(format!("Select the value for the parameter: {}", param.name));
 		for option in &param.sub_params {
@@ -599,7 +1945,7 @@ fn prompt_for_variant_param(cli: &mut impl Cli, param: &Param) -> Result<String>
 	if !selected_variant.sub_params.is_empty() {
 		let mut field_values = Vec::new();
 		for field_arg in &selected_variant.sub_params {
-			let field_value = prompt_for_param(cli, field_arg)?;
+			let field_value = prompt_for_param(cli, field_arg, calls)?;
 			field_values.push(field_value);
 		}
 		Ok(format!("{}({})", selected_variant.name, field_values.join(", ")))
@@ -625,10 +1971,14 @@ fn prompt_for_variant_param(cli: &mut impl Cli, param: &Param) -> Result<String>
 //     ],
 //     is_variant: false
 // }
-fn prompt_for_composite_param(cli: &mut impl Cli, param: &Param) -> Result<String> {
+fn prompt_for_composite_param(
+	cli: &mut impl Cli,
+	param: &Param,
+	calls: &CallBuilder,
+) -> Result<String> {
 	let mut field_values = Vec::new();
 	for field_arg in &param.sub_params {
-		let field_value = prompt_for_param(cli, field_arg)?;
+		let field_value = prompt_for_param(cli, field_arg, calls)?;
 		if param.sub_params.len() == 1 && param.name == param.sub_params[0].name {
 			field_values.push(field_value);
 		} else {
@@ -643,17 +1993,21 @@ fn prompt_for_composite_param(cli: &mut impl Cli, param: &Param) -> Result<Strin
 }
 
 // Recursively prompt the user for the tuple values.
-fn prompt_for_tuple_param(cli: &mut impl Cli, param: &Param) -> Result<String> {
+fn prompt_for_tuple_param(
+	cli: &mut impl Cli,
+	param: &Param,
+	calls: &CallBuilder,
+) -> Result<String> {
 	let mut tuple_values = Vec::new();
 	for tuple_param in param.sub_params.iter() {
-		let tuple_value = prompt_for_param(cli, tuple_param)?;
+		let tuple_value = prompt_for_param(cli, tuple_param, calls)?;
 		tuple_values.push(tuple_value);
 	}
 	Ok(format!("({})", tuple_values.join(", ")))
 }
 
 // Parser to capitalize the first letter of the pallet name.
-fn parse_pallet_name(name: &str) -> Result<String, String> {
+pub(crate) fn parse_pallet_name(name: &str) -> Result<String, String> {
 	let mut chars = name.chars();
 	match chars.next() {
 		Some(c) => Ok(c.to_ascii_uppercase().to_string() + chars.as_str()),
@@ -662,17 +2016,52 @@ fn parse_pallet_name(name: &str) -> Result<String, String> {
 }
 
 // Parser to convert the function name to lowercase.
-fn parse_function_name(name: &str) -> Result<String, String> {
+pub(crate) fn parse_function_name(name: &str) -> Result<String, String> {
 	Ok(name.to_ascii_lowercase())
 }
 
+// Parser for when a call is scheduled to be dispatched.
+fn parse_schedule_at(at: &str) -> Result<ScheduleAt, String> {
+	at.parse().map_err(|_| {
+		"expected a block, e.g. `1200`, a number of blocks, e.g. `+10`, or a Unix timestamp, e.g. `@1767225600`".to_string()
+	})
+}
+
+// Parser for an override of an argument of a template, e.g. `value=2000`.
+fn parse_override(arg: &str) -> Result<(String, String), String> {
+	arg.split_once('=')
+		.filter(|(name, _)| !name.is_empty())
+		.map(|(name, value)| (name.to_string(), value.to_string()))
+		.ok_or_else(|| "expected NAME=VALUE, e.g. `value=2000`".to_string())
+}
+
+// Parser for how far a submitted extrinsic is followed.
+fn parse_wait(wait: &str) -> Result<WaitFor, String> {
+	wait.parse()
+		.map_err(|_| "expected one of `none`, `best` or `finalized`".to_string())
+}
+
+// Parser for the signatories and threshold of a multisig account.
+fn parse_multisig(multisig: &str) -> Result<Multisig, String> {
+	multisig.parse().map_err(|e: pop_parachains::Error| e.to_string())
+}
+
+// Parser for the name of a scheduled task.
+fn parse_schedule_name(name: &str) -> Result<String, String> {
+	task_name(name).map(|_| name.to_string()).map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::{cli::MockCli, common::wallet::USE_WALLET_PROMPT};
+	use clap::Parser;
+	use pop_parachains::set_up_client;
 	use tempfile::tempdir;
 	use url::Url;
 
+	const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
 	const BOB_SURI: &str = "//Bob";
 	const POP_NETWORK_TESTNET_URL: &str = "wss://rpc1.paseo.popnetwork.xyz";
 	const POLKADOT_NETWORK_URL: &str = "wss://polkadot-rpc.publicnode.com";
@@ -686,7 +2075,7 @@ mod tests {
 			POP_NETWORK_TESTNET_URL.into(),
 		);
 		let chain = call_config.configure_chain(&mut cli).await?;
-		assert_eq!(chain.url, Url::parse(POP_NETWORK_TESTNET_URL)?);
+		assert_eq!(chain.endpoint, Endpoint::Urls(vec![Url::parse(POP_NETWORK_TESTNET_URL)?]));
 		cli.verify()
 	}
 
@@ -725,7 +2114,7 @@ mod tests {
 		.expect_confirm(USE_WALLET_PROMPT, true);
 
 		let chain = call_config.configure_chain(&mut cli).await?;
-		assert_eq!(chain.url, Url::parse(POP_NETWORK_TESTNET_URL)?);
+		assert_eq!(chain.endpoint, Endpoint::Urls(vec![Url::parse(POP_NETWORK_TESTNET_URL)?]));
 
 		let call_chain = call_config.configure_call(&chain, &mut cli)?;
 		assert_eq!(call_chain.function.pallet, "System");
@@ -747,7 +2136,7 @@ mod tests {
 			POLKADOT_NETWORK_URL.into(),
 		);
 		let chain = call_config.configure_chain(&mut cli).await?;
-		assert_eq!(chain.url, Url::parse(POLKADOT_NETWORK_URL)?);
+		assert_eq!(chain.endpoint, Endpoint::Urls(vec![Url::parse(POLKADOT_NETWORK_URL)?]));
 		cli.verify()?;
 
 		let mut cli = MockCli::new()
@@ -771,7 +2160,7 @@ mod tests {
 			)
 			.expect_input("Enter the value for the parameter: max_amount", "10000".into())
 			.expect_input("Enter the value for the parameter: para_id", "2000".into())
-			.expect_input("Signer of the extrinsic:", BOB_SURI.into());
+			.expect_password("Signer of the extrinsic [default: //Alice]:", BOB_SURI);
 
 		let call_chain = call_config.configure_call(&chain, &mut cli)?;
 
@@ -781,6 +2170,14 @@ mod tests {
 		assert_eq!(call_chain.suri, "//Bob");
 		assert!(!call_chain.sudo);
 		assert_eq!(call_chain.display(&chain), "pop call chain --pallet OnDemand --function place_order_allow_death --args \"10000\" \"2000\" --url wss://polkadot-rpc.publicnode.com/ --suri //Bob");
+		// Any secret is masked.
+		let call_chain = Call {
+			suri: "bottom drive obey lake curtain smoke basket hold race lonely fit walk".into(),
+			..call_chain
+		};
+		assert!(call_chain.display(&chain).ends_with(" --suri <SURI>"));
+		let call_chain = Call { suri: "secret:savings".into(), ..call_chain };
+		assert!(call_chain.display(&chain).ends_with(" --suri secret:savings"));
 		cli.verify()
 	}
 
@@ -798,6 +2195,12 @@ mod tests {
 			use_wallet: false,
 			skip_confirm: false,
 			sudo: false,
+			multisig: None,
+			options: Default::default(),
+			wait_timeout: None,
+			schedule_at: None,
+			schedule_name: None,
+			offline: None,
 		};
 		let mut cli = MockCli::new();
 		// Error, wrong name of the pallet.
@@ -829,7 +2232,8 @@ mod tests {
 
 	#[tokio::test]
 	async fn user_cancel_submit_extrinsic_works() -> Result<()> {
-		let client = set_up_client(POP_NETWORK_TESTNET_URL).await?;
+		let connection = Connection::connect(POP_NETWORK_TESTNET_URL, None).await?;
+		let client = connection.client();
 		let pallets = parse_chain_metadata(&client)?;
 		let mut call_config = Call {
 			function: find_dispatchable_by_name(&pallets, "System", "remark")?.clone(),
@@ -838,44 +2242,70 @@ mod tests {
 			use_wallet: false,
 			skip_confirm: false,
 			sudo: false,
+			multisig: None,
+			options: Default::default(),
+			wait_timeout: None,
+			schedule_at: None,
+			schedule_name: None,
+			offline: None,
 		};
 		let mut cli = MockCli::new()
 			.expect_confirm("Do you want to submit the extrinsic?", false)
 			.expect_outro_cancel("Extrinsic for `remark` was not submitted.");
 		let xt = call_config.prepare_extrinsic(&client, &mut cli)?;
-		call_config
-			.submit_extrinsic(&client, &Url::parse(POP_NETWORK_TESTNET_URL)?, xt, &mut cli)
-			.await?;
+		call_config.submit_extrinsic(&client, &connection, xt, &mut cli).await?;
 
 		cli.verify()
 	}
 
 	#[tokio::test]
 	async fn user_cancel_submit_extrinsic_from_call_data_works() -> Result<()> {
-		let client = set_up_client(POP_NETWORK_TESTNET_URL).await?;
+		let url = Url::parse(POP_NETWORK_TESTNET_URL)?;
+		let connection = Connection::connect(url.as_str(), None).await?;
+		let chain = Chain::new(Endpoint::Urls(vec![url]), connection);
 		let call_config = CallChainCommand {
 			pallet: None,
 			function: None,
 			args: vec![].to_vec(),
-			url: Some(Url::parse(POP_NETWORK_TESTNET_URL)?),
+			args_json: None,
+			url: vec![Url::parse(POP_NETWORK_TESTNET_URL)?],
+			chain: None,
+			refresh_metadata: false,
+			light: None,
+			relay_chain_spec: None,
 			suri: None,
 			use_wallet: false,
 			skip_confirm: false,
 			call_data: Some("0x00000411".to_string()),
 			sudo: false,
+			multisig: None,
+			nonce: None,
+			tip: None,
+			era: None,
+			wait: None,
+			wait_timeout: None,
+			schedule_at: None,
+			schedule_name: None,
+			create_payload: None,
+			signer: None,
+			submit_signed: None,
+			storage: false,
+			item: None,
+			file: None,
+			batch_all: false,
+			list: false,
+			output: None,
+			save_as: None,
+			template: None,
+			overrides: vec![],
 		};
 		let mut cli = MockCli::new()
 			.expect_confirm(USE_WALLET_PROMPT, false)
-			.expect_input("Signer of the extrinsic:", "//Bob".into())
+			.expect_password("Signer of the extrinsic [default: //Alice]:", "//Bob")
 			.expect_confirm("Do you want to submit the extrinsic?", false)
 			.expect_outro_cancel("Extrinsic with call data 0x00000411 was not submitted.");
 		call_config
-			.submit_extrinsic_from_call_data(
-				&client,
-				&Url::parse(POP_NETWORK_TESTNET_URL)?,
-				"0x00000411",
-				&mut cli,
-			)
+			.submit_extrinsic_from_call_data(&chain, "0x00000411", &mut cli)
 			.await?;
 
 		cli.verify()
@@ -888,12 +2318,37 @@ mod tests {
 			pallet: None,
 			function: None,
 			args: vec![].to_vec(),
-			url: Some(Url::parse(POLKADOT_NETWORK_URL)?),
+			args_json: None,
+			url: vec![Url::parse(POLKADOT_NETWORK_URL)?],
+			chain: None,
+			refresh_metadata: false,
+			light: None,
+			relay_chain_spec: None,
 			suri: Some("//Alice".to_string()),
 			use_wallet: false,
 			skip_confirm: false,
 			call_data: Some("0x00000411".to_string()),
 			sudo: true,
+			multisig: None,
+			nonce: None,
+			tip: None,
+			era: None,
+			wait: None,
+			wait_timeout: None,
+			schedule_at: None,
+			schedule_name: None,
+			create_payload: None,
+			signer: None,
+			submit_signed: None,
+			storage: false,
+			item: None,
+			file: None,
+			batch_all: false,
+			list: false,
+			output: None,
+			save_as: None,
+			template: None,
+			overrides: vec![],
 		};
 		let mut cli = MockCli::new()
 			.expect_intro("Call a chain")
@@ -908,7 +2363,7 @@ mod tests {
 			"Would you like to dispatch this function call with `Root` origin?",
 			true,
 		);
-		call_config.url = Some(Url::parse(POP_NETWORK_TESTNET_URL)?);
+		call_config.url = vec![Url::parse(POP_NETWORK_TESTNET_URL)?];
 		let chain = call_config.configure_chain(&mut cli).await?;
 		call_config.configure_sudo(&chain, &mut cli)?;
 		assert!(call_config.sudo);
@@ -921,12 +2376,37 @@ mod tests {
 			pallet: Some("System".to_string()),
 			function: Some("remark".to_string()),
 			args: vec!["0x11".to_string()].to_vec(),
-			url: Some(Url::parse(POP_NETWORK_TESTNET_URL)?),
+			args_json: None,
+			url: vec![Url::parse(POP_NETWORK_TESTNET_URL)?],
+			chain: None,
+			refresh_metadata: false,
+			light: None,
+			relay_chain_spec: None,
 			use_wallet: true,
 			suri: Some(DEFAULT_URI.to_string()),
 			skip_confirm: false,
 			call_data: None,
 			sudo: true,
+			multisig: None,
+			nonce: None,
+			tip: None,
+			era: None,
+			wait: None,
+			wait_timeout: None,
+			schedule_at: None,
+			schedule_name: None,
+			create_payload: None,
+			signer: None,
+			submit_signed: None,
+			storage: false,
+			item: None,
+			file: None,
+			batch_all: false,
+			list: false,
+			output: None,
+			save_as: None,
+			template: None,
+			overrides: vec![],
 		};
 		call_config.reset_for_new_call();
 		assert_eq!(call_config.pallet, None);
@@ -943,31 +2423,382 @@ mod tests {
 			pallet: Some("System".to_string()),
 			function: Some("remark".to_string()),
 			args: vec!["0x11".to_string()].to_vec(),
-			url: Some(Url::parse(POP_NETWORK_TESTNET_URL)?),
+			args_json: None,
+			url: vec![Url::parse(POP_NETWORK_TESTNET_URL)?],
+			chain: None,
+			refresh_metadata: false,
+			light: None,
+			relay_chain_spec: None,
 			suri: Some(DEFAULT_URI.to_string()),
 			use_wallet: false,
 			skip_confirm: false,
 			call_data: None,
 			sudo: false,
+			multisig: None,
+			nonce: None,
+			tip: None,
+			era: None,
+			wait: None,
+			wait_timeout: None,
+			schedule_at: None,
+			schedule_name: None,
+			create_payload: None,
+			signer: None,
+			submit_signed: None,
+			storage: false,
+			item: None,
+			file: None,
+			batch_all: false,
+			list: false,
+			output: None,
+			save_as: None,
+			template: None,
+			overrides: vec![],
 		};
 		assert!(!call_config.requires_user_input());
+		call_config.url.clear();
+		call_config.chain = Some("pop".to_string());
+		assert!(!call_config.requires_user_input());
+		call_config.chain = None;
+		call_config.light = Some(PathBuf::from("chain-spec.json"));
+		assert!(!call_config.requires_user_input());
+		// The signer is not required when the payload is to be signed offline.
+		call_config.suri = None;
+		assert!(call_config.requires_user_input());
+		call_config.create_payload = Some(PathBuf::from("payload.json"));
+		assert!(!call_config.requires_user_input());
 		call_config.pallet = None;
 		assert!(call_config.requires_user_input());
 		Ok(())
 	}
 
+	#[test]
+	fn args_json_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "call", "chain"].iter().chain(args))?;
+			let crate::commands::Command::Call(args) = pop.command else {
+				panic!("expected the call command");
+			};
+			let super::super::Command::Chain(command) = args.command else {
+				panic!("expected the chain command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let json =
+			r#"{"dest": "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty", "value": "1000"}"#;
+		let command = parse(&[
+			"-p",
+			"Balances",
+			"-f",
+			"transfer_allow_death",
+			"--args-json",
+			json,
+			"-u",
+			POP_NETWORK_TESTNET_URL,
+			"-s",
+			DEFAULT_URI,
+		])?;
+		assert_eq!(command.args_json.as_deref(), Some(json));
+		// The arguments are not prompted for when specified as JSON.
+		assert!(!command.requires_user_input());
+		assert!(parse(&["--args-json", json, "--args", "1000"]).is_err());
+		assert!(parse(&["--args-json", json, "--call", "0x00"]).is_err());
+		assert!(parse(&["--args-json", json, "--storage"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn search_and_select_works() -> Result<()> {
+		let pallet = |name: &str| Pallet { name: name.into(), ..Default::default() };
+		let mut pallets: Vec<_> =
+			(0..SEARCH_THRESHOLD).map(|i| pallet(&format!("Pallet{i}"))).collect();
+		pallets.push(pallet("System"));
+		// Pallets are selected without searching when there are few of them.
+		let mut cli =
+			MockCli::new().expect_select("Select the pallet to call:", Some(true), true, None, 1);
+		let selected =
+			search_and_select(&mut cli, "pallet", &pallets[..2], |p| &p.name, |_| String::new())?;
+		assert_eq!(selected.name, "Pallet1");
+		cli.verify()?;
+		pallets.push(pallet("ParachainSystem"));
+		let mut cli = MockCli::new()
+			.expect_input("Search for the pallet to call:", "xyz".into())
+			.expect_warning("No pallet matches `xyz`, please search again.")
+			.expect_input("Search for the pallet to call:", "sys".into())
+			.expect_select(
+				"Select the pallet to call:",
+				Some(true),
+				true,
+				Some(vec![
+					("System".to_string(), "".to_string()),
+					("ParachainSystem".to_string(), "".to_string()),
+				]),
+				1,
+			);
+		let selected =
+			search_and_select(&mut cli, "pallet", &pallets, |p| &p.name, |_| String::new())?;
+		assert_eq!(selected.name, "ParachainSystem");
+		cli.verify()
+	}
+
+	#[test]
+	fn templates_work() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "call", "chain"].iter().chain(args))?;
+			let crate::commands::Command::Call(args) = pop.command else {
+				panic!("expected the call command");
+			};
+			let super::super::Command::Chain(command) = args.command else {
+				panic!("expected the chain command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["-p", "System", "-f", "remark", "--save-as", "remark"])?;
+		assert_eq!(command.save_as.as_deref(), Some("remark"));
+		let mut command = parse(&["--template", "remark", "--set", "remark=0x22", "-s", BOB_SURI])?;
+		assert_eq!(command.template.as_deref(), Some("remark"));
+		assert_eq!(command.overrides, [("remark".to_string(), "0x22".to_string())]);
+		assert!(parse(&["--template", "remark", "-p", "System"]).is_err());
+		assert!(parse(&["--template", "remark", "--set", "0x22"]).is_err());
+		assert!(parse(&["--set", "remark=0x22"]).is_err());
+
+		// The call is configured as per the template, other than the endpoint if specified.
+		let temp_dir = tempfile::tempdir()?;
+		let templates = CallTemplates::new(vec![temp_dir.path().to_path_buf()]);
+		let template = CallTemplate {
+			pallet: "System".into(),
+			function: "remark".into(),
+			args: vec!["0x11".into()],
+			url: Some(POP_NETWORK_TESTNET_URL.into()),
+			sudo: true,
+		};
+		templates.save("remark", &template)?;
+		command.apply_template(&templates, "remark")?;
+		assert_eq!(command.pallet.as_deref(), Some("System"));
+		assert_eq!(command.function.as_deref(), Some("remark"));
+		assert_eq!(command.args, ["0x11"]);
+		assert_eq!(command.url, [Url::parse(POP_NETWORK_TESTNET_URL)?]);
+		assert!(command.sudo && !command.requires_user_input());
+		let mut local = parse(&["--template", "remark", "-u", DEFAULT_URL])?;
+		local.apply_template(&templates, "remark")?;
+		assert_eq!(local.url, [Url::parse(DEFAULT_URL)?]);
+		assert!(command.apply_template(&templates, "unknown").is_err());
+
+		// Arguments are overridden by the names of their parameters.
+		let function = Function {
+			pallet: "System".into(),
+			name: "remark".into(),
+			params: vec![Param { name: "remark".into(), ..Default::default() }],
+			..Default::default()
+		};
+		assert_eq!(command.override_args(&function, command.args.clone())?, ["0x22"]);
+		command.overrides = vec![("unknown".into(), "0x22".into())];
+		assert!(matches!(
+			command.override_args(&function, command.args.clone()),
+			Err(message) if message.to_string() == "System.remark has no parameter named `unknown`"
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn list_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "call", "chain"].iter().chain(args))?;
+			let crate::commands::Command::Call(args) = pop.command else {
+				panic!("expected the call command");
+			};
+			let super::super::Command::Chain(command) = args.command else {
+				panic!("expected the chain command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["--list", "-u", POP_NETWORK_TESTNET_URL])?;
+		assert!(command.list && command.output.is_none());
+		let command = parse(&["--list", "--output", "yaml", "-p", "Balances"])?;
+		assert_eq!(
+			(command.output, command.pallet.as_deref()),
+			(Some(ListFormat::Yaml), Some("Balances"))
+		);
+		assert!(parse(&["--output", "json"]).is_err());
+		assert!(parse(&["--list", "--output", "toml"]).is_err());
+		assert!(parse(&["--list", "-f", "remark"]).is_err());
+		assert!(parse(&["--list", "--storage"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn render_pallets_works() -> Result<()> {
+		let pallets = [Pallet {
+			name: "System".into(),
+			functions: vec![Function {
+				pallet: "System".into(),
+				name: "remark".into(),
+				params: vec![Param {
+					name: "remark".into(),
+					type_name: "[u8]".into(),
+					is_sequence: true,
+					..Default::default()
+				}],
+				is_supported: true,
+				..Default::default()
+			}],
+			..Default::default()
+		}];
+		let json: serde_json::Value =
+			serde_json::from_str(&render_pallets(&pallets, ListFormat::Json)?)?;
+		assert_eq!(json[0]["name"], "System");
+		assert_eq!(json[0]["functions"][0]["name"], "remark");
+		assert_eq!(json[0]["functions"][0]["params"][0]["type_name"], "[u8]");
+		assert_eq!(json[0]["functions"][0]["params"][0]["is_sequence"], true);
+		let yaml = render_pallets(&pallets, ListFormat::Yaml)?;
+		assert!(yaml.starts_with("- name: System\n"));
+		assert!(yaml.contains("  - pallet: System\n    name: remark\n"));
+		Ok(())
+	}
+
+	#[test]
+	fn light_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "call", "chain"].iter().chain(args))?;
+			let crate::commands::Command::Call(args) = pop.command else {
+				panic!("expected the call command");
+			};
+			let super::super::Command::Chain(command) = args.command else {
+				panic!("expected the chain command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["-u", POP_NETWORK_TESTNET_URL, "--url", POLKADOT_NETWORK_URL])?;
+		assert_eq!(
+			command.url,
+			[Url::parse(POP_NETWORK_TESTNET_URL)?, Url::parse(POLKADOT_NETWORK_URL)?]
+		);
+		assert!(!command.refresh_metadata);
+		assert!(parse(&["-u", POP_NETWORK_TESTNET_URL, "--refresh-metadata"])?.refresh_metadata);
+		let command = parse(&["--light", "para.json", "--relay-chain-spec", "relay.json"])?;
+		assert_eq!(command.light, Some(PathBuf::from("para.json")));
+		assert_eq!(command.relay_chain_spec, Some(PathBuf::from("relay.json")));
+		assert!(parse(&["--light", "para.json", "-u", POP_NETWORK_TESTNET_URL]).is_err());
+		assert!(parse(&["--light", "para.json", "--use-wallet"]).is_err());
+		assert!(parse(&["--relay-chain-spec", "relay.json"]).is_err());
+		assert_eq!(parse(&["--chain", "pop"])?.chain.as_deref(), Some("pop"));
+		assert!(parse(&["--chain", "pop", "-u", POP_NETWORK_TESTNET_URL]).is_err());
+		assert!(parse(&["--chain", "pop", "--light", "para.json"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn offline_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "call", "chain"].iter().chain(args))?;
+			let crate::commands::Command::Call(args) = pop.command else {
+				panic!("expected the call command");
+			};
+			let super::super::Command::Chain(command) = args.command else {
+				panic!("expected the chain command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["--create-payload", "payload.json", "--signer", ALICE])?;
+		assert_eq!(
+			command.offline(),
+			Some(Offline { path: PathBuf::from("payload.json"), signer: ALICE.to_string() })
+		);
+		let command = parse(&["--export-payload", "payload.json", "--signer", ALICE])?;
+		assert_eq!(command.create_payload, Some(PathBuf::from("payload.json")));
+		let command = parse(&["--submit-signed", "payload.signed.json"])?;
+		assert_eq!(command.submit_signed, Some(PathBuf::from("payload.signed.json")));
+		// The signer of a payload is required, with the extrinsic signed offline.
+		assert!(parse(&["--create-payload", "payload.json"]).is_err());
+		assert!(parse(&["--signer", ALICE]).is_err());
+		assert!(parse(&[
+			"--create-payload",
+			"payload.json",
+			"--signer",
+			ALICE,
+			"--suri",
+			"//Alice"
+		])
+		.is_err());
+		assert!(parse(&["--submit-signed", "payload.signed.json", "--pallet", "System"]).is_err());
+		assert!(parse(&["--submit-signed", "payload.signed.json", "--use-wallet"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn parse_signer_works() -> Result<()> {
+		assert_eq!(parse_signer(ALICE)?.to_string(), ALICE);
+		assert!(parse_signer("wrong").is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn endpoint_display_works() -> Result<()> {
+		let url = Url::parse(POP_NETWORK_TESTNET_URL)?;
+		assert_eq!(Endpoint::Urls(vec![url.clone()]).url(), Some(&url));
+		assert_eq!(
+			Endpoint::Urls(vec![url.clone()]).to_string(),
+			"--url wss://rpc1.paseo.popnetwork.xyz/"
+		);
+		// Fallback endpoints are displayed in order of preference.
+		let fallback = Url::parse(POLKADOT_NETWORK_URL)?;
+		let urls = Endpoint::Urls(vec![url.clone(), fallback]);
+		assert_eq!(urls.url(), Some(&url));
+		assert_eq!(
+			urls.to_string(),
+			"--url wss://rpc1.paseo.popnetwork.xyz/ --url wss://polkadot-rpc.publicnode.com/"
+		);
+		let named = Endpoint::Named { name: "pop".into(), url: url.clone() };
+		assert_eq!(named.url(), Some(&url));
+		assert_eq!(named.to_string(), "--chain pop");
+		let light = Endpoint::Light { chain_spec: "para.json".into(), relay_chain_spec: None };
+		assert_eq!(light.url(), None);
+		assert_eq!(light.to_string(), "--light para.json");
+		let light = Endpoint::Light {
+			chain_spec: "para.json".into(),
+			relay_chain_spec: Some("relay.json".into()),
+		};
+		assert_eq!(light.to_string(), "--light para.json --relay-chain-spec relay.json");
+		Ok(())
+	}
+
 	#[test]
 	fn expand_file_arguments_works() -> Result<()> {
 		let mut call_config = CallChainCommand {
 			pallet: Some("Registrar".to_string()),
 			function: Some("register".to_string()),
 			args: vec!["2000".to_string(), "0x1".to_string(), "0x12".to_string()].to_vec(),
-			url: Some(Url::parse(POP_NETWORK_TESTNET_URL)?),
+			args_json: None,
+			url: vec![Url::parse(POP_NETWORK_TESTNET_URL)?],
+			chain: None,
+			refresh_metadata: false,
+			light: None,
+			relay_chain_spec: None,
 			suri: Some(DEFAULT_URI.to_string()),
 			use_wallet: false,
 			call_data: None,
 			skip_confirm: false,
 			sudo: false,
+			multisig: None,
+			nonce: None,
+			tip: None,
+			era: None,
+			wait: None,
+			wait_timeout: None,
+			schedule_at: None,
+			schedule_name: None,
+			create_payload: None,
+			signer: None,
+			submit_signed: None,
+			storage: false,
+			item: None,
+			file: None,
+			batch_all: false,
+			list: false,
+			output: None,
+			save_as: None,
+			template: None,
+			overrides: vec![],
 		};
 		assert_eq!(
 			call_config.expand_file_arguments()?,
@@ -995,6 +2826,15 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn extrinsic_links_works() {
+		assert!(extrinsic_links(Some("wss://rpc.polkadot.io"), "0x12")
+			.contains("https://polkadot.subscan.io/extrinsic/0x12"));
+		assert!(extrinsic_links(Some(DEFAULT_URL), "0x12").is_empty());
+		// No links are available when connected via a light client.
+		assert!(extrinsic_links(None, "0x12").is_empty());
+	}
+
 	#[test]
 	fn display_message_works() -> Result<()> {
 		let mut cli = MockCli::new().expect_outro(&"Call completed successfully!");
@@ -1036,31 +2876,15 @@ mod tests {
 	async fn prompt_for_param_works() -> Result<()> {
 		let client = set_up_client(POP_NETWORK_TESTNET_URL).await?;
 		let pallets = parse_chain_metadata(&client)?;
+		let calls = CallBuilder::new(&client);
+		let account = |name: &str| format!("Select the account for the parameter: {name}");
 		// Using NFT mint dispatchable function to test the majority of sub-functions.
 		let function = find_dispatchable_by_name(&pallets, "Nfts", "mint")?;
 		let mut cli = MockCli::new()
 			.expect_input("Enter the value for the parameter: collection", "0".into())
 			.expect_input("Enter the value for the parameter: item", "0".into())
-			.expect_select(
-				"Select the value for the parameter: mint_to",
-				Some(true),
-				true,
-				Some(
-					[
-						("Id".to_string(), "".to_string()),
-						("Index".to_string(), "".to_string()),
-						("Raw".to_string(), "".to_string()),
-						("Address32".to_string(), "".to_string()),
-						("Address20".to_string(), "".to_string()),
-					]
-					.to_vec(),
-				),
-				0, // "Id" action
-			)
-			.expect_input(
-				"Enter the value for the parameter: Id",
-				"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".into(),
-			)
+			.expect_select(account("mint_to"), Some(true), true, None, DEV_ACCOUNTS.len()) // Enter
+			.expect_input("Enter the value for the parameter: mint_to", "//Bob".into())
 			.expect_confirm(
 				"Do you want to provide a value for the optional parameter: witness_data?",
 				true,
@@ -1078,15 +2902,81 @@ mod tests {
 		// Test all the function params.
 		let mut params: Vec<String> = Vec::new();
 		for param in &function.params {
-			params.push(prompt_for_param(&mut cli, &param)?);
+			params.push(prompt_for_param(&mut cli, param, &calls)?);
 		}
 		assert_eq!(params.len(), 4);
 		assert_eq!(params[0], "0".to_string()); // collection: test primitive
 		assert_eq!(params[1], "0".to_string()); // item: test primitive
-		assert_eq!(params[2], "Id(5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty)".to_string()); // mint_to: test variant
+		assert_eq!(params[2], "Id(5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty)".to_string()); // mint_to: test address
 		assert_eq!(params[3], "Some({owned_item: None(), mint_price: Some(1000)})".to_string()); // witness_data: test composite
 		cli.verify()?;
 
+		// Test the variants of an address, selected when no account is entered.
+		let mut cli = MockCli::new()
+			.expect_select(account("mint_to"), Some(true), true, None, DEV_ACCOUNTS.len()) // Enter
+			.expect_input("Enter the value for the parameter: mint_to", "".into())
+			.expect_select(
+				"Select the value for the parameter: mint_to",
+				Some(true),
+				true,
+				Some(
+					[
+						("Id".to_string(), "".to_string()),
+						("Index".to_string(), "".to_string()),
+						("Raw".to_string(), "".to_string()),
+						("Address32".to_string(), "".to_string()),
+						("Address20".to_string(), "".to_string()),
+					]
+					.to_vec(),
+				),
+				1, // "Index" action
+			)
+			.expect_input("Enter the value for the parameter: Index", "1".into());
+		assert_eq!(prompt_for_param(&mut cli, &function.params[2], &calls)?, "Index(1)");
+		cli.verify()?;
+
+		// Test accounts selected from the address book or the development accounts.
+		let temp_dir = tempfile::tempdir()?;
+		let mut address_book = AddressBook::load(&temp_dir.path().join("address-book.toml"))?;
+		address_book.insert("treasury", ALICE)?;
+		let contacts = calls.clone().with_address_book(address_book);
+		let who =
+			Param { name: "who".into(), type_name: "AccountId32".into(), ..Default::default() };
+		let mut cli = MockCli::new()
+			.expect_select(
+				account("mint_to"),
+				Some(true),
+				true,
+				Some(
+					[
+						("treasury", ALICE),
+						("//Alice", ALICE),
+						("//Bob", "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"),
+						("//Charlie", "5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y"),
+						("//Dave", "5DAAnrj7VHTznn2AWBemMuyBwZWs6FNFjdyVXUeYum3PTXFy"),
+						("//Eve", "5HGjWAeFDfFCWPsjFQdVV2Msvz2XtMktvgocEZcCj68kUMaw"),
+						("//Ferdie", "5CiPPseXPECbkjWCa6MnjNokrgYjMqmKndv2rSnekmSK2DjL"),
+						("Enter an account", ""),
+					]
+					.map(|(name, address)| (name.to_string(), address.to_string()))
+					.to_vec(),
+				),
+				0, // "treasury"
+			)
+			.expect_select(account("who"), Some(true), true, None, 2) // "//Bob"
+			.expect_select(account("who"), Some(true), true, None, DEV_ACCOUNTS.len() + 1) // Enter
+			.expect_input("Enter the value for the parameter: who", ALICE.into());
+		assert_eq!(
+			prompt_for_param(&mut cli, &function.params[2], &contacts)?,
+			format!("Id({ALICE})")
+		);
+		assert_eq!(
+			prompt_for_param(&mut cli, &who, &contacts)?,
+			"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"
+		);
+		assert_eq!(prompt_for_param(&mut cli, &who, &contacts)?, ALICE);
+		cli.verify()?;
+
 		// Using Scheduler set_retry dispatchable function to test the tuple params.
 		let function = find_dispatchable_by_name(&pallets, "Scheduler", "set_retry")?;
 		let mut cli = MockCli::new()
@@ -1104,7 +2994,7 @@ mod tests {
 		// Test all the extrinsic params
 		let mut params: Vec<String> = Vec::new();
 		for param in &function.params {
-			params.push(prompt_for_param(&mut cli, &param)?);
+			params.push(prompt_for_param(&mut cli, param, &calls)?);
 		}
 		assert_eq!(params.len(), 3);
 		assert_eq!(params[0], "(0, 0)".to_string()); // task: test tuples
@@ -1128,10 +3018,103 @@ mod tests {
 		// Test all the function params
 		let mut params: Vec<String> = Vec::new();
 		for param in &function.params {
-			params.push(prompt_for_param(&mut cli, &param)?);
+			params.push(prompt_for_param(&mut cli, param, &calls)?);
 		}
 		assert_eq!(params.len(), 1);
 		assert_eq!(params[0], "testing".to_string()); // remark: test sequence from file
+		cli.verify()?;
+
+		// Test a sequence whose composite items are entered one at a time.
+		let field = |name: &str, type_name: &str| Param {
+			name: name.into(),
+			type_name: type_name.into(),
+			..Default::default()
+		};
+		let param = Param {
+			name: "payouts".into(),
+			type_name: "[Payout]".into(),
+			is_sequence: true,
+			sub_params: vec![Param {
+				name: "payouts".into(),
+				type_name: "Payout".into(),
+				sub_params: vec![field("who", "u32"), field("amount", "u128")],
+				..Default::default()
+			}],
+			..Default::default()
+		};
+		let items =
+			|count: usize| format!("Add the items of `payouts` one at a time ({count} so far):");
+		let mut cli = MockCli::new()
+			.expect_select(items(0), Some(true), true, None, 0) // Add
+			.expect_input("Enter the value for the parameter: who", "1".into())
+			.expect_input("Enter the value for the parameter: amount", "100".into())
+			.expect_select(items(1), Some(true), true, None, 0) // Add
+			.expect_input("Enter the value for the parameter: who", "2".into())
+			.expect_input("Enter the value for the parameter: amount", "{".into())
+			.expect_warning("`{who: 2, amount: {}` is not a valid Payout, so was not added.")
+			.expect_select(items(1), Some(true), true, None, 1) // Remove
+			.expect_select(items(0), Some(true), true, None, 0) // Add
+			.expect_input("Enter the value for the parameter: who", "3".into())
+			.expect_input("Enter the value for the parameter: amount", "300".into())
+			.expect_select(items(1), Some(true), true, None, 0) // Add
+			.expect_input("Enter the value for the parameter: who", "4".into())
+			.expect_input("Enter the value for the parameter: amount", "400".into())
+			.expect_select(items(2), Some(true), true, None, 2); // Done
+		assert_eq!(
+			prompt_for_param(&mut cli, &param, &calls)?,
+			"({who: 3, amount: 300}, {who: 4, amount: 400})"
+		);
+		cli.verify()?;
+
+		// Test balances entered as amounts of the native token, or of its smallest unit.
+		let param = Param {
+			name: "value".into(),
+			type_name: "Compact<u128>".into(),
+			is_balance: true,
+			..Default::default()
+		};
+		let token = NativeToken { symbol: Some("PAS".into()), decimals: Some(10) };
+		let paseo = calls.clone().with_token(token);
+		let mut cli = MockCli::new()
+			.expect_input("Enter the value for the parameter: value", "1.5 pas".into())
+			.expect_confirm(
+				"1.5 pas is 15000000000 in the smallest unit of PAS. Is this correct?",
+				false,
+			)
+			.expect_input("Enter the value for the parameter: value", "0.01 PAS".into())
+			.expect_confirm(
+				"0.01 PAS is 100000000 in the smallest unit of PAS. Is this correct?",
+				true,
+			)
+			.expect_input("Enter the value for the parameter: value", "1000".into());
+		assert_eq!(prompt_for_param(&mut cli, &param, &paseo)?, "100000000");
+		assert_eq!(prompt_for_param(&mut cli, &param, &paseo)?, "1000");
+		cli.verify()?;
+
+		// Using Utility batch dispatchable function to test composing nested calls.
+		let function =
+			resolve_function(&client, find_dispatchable_by_name(&pallets, "Utility", "batch")?)?;
+		let mut cli = MockCli::new()
+			.expect_select(
+				"Select the pallet of the call for the parameter: calls",
+				Some(true),
+				true,
+				None,
+				0, // "System" pallet
+			)
+			.expect_select("Select the function of the System call:", Some(true), true, None, 0) // "remark" function
+			.expect_input(
+				"The value for `remark` might be too large to enter. You may enter the path to a file instead.",
+				"hi".into(),
+			)
+			.expect_confirm("Do you want to add another call to `calls`?", false);
+		let mut params: Vec<String> = Vec::new();
+		for param in &function.params {
+			params.push(prompt_for_param(&mut cli, param, &calls)?);
+		}
+		assert!(params[0].starts_with("(System (remark"));
+		// The composed calls are encoded as the arguments of the batch.
+		construct_extrinsic(&function, params)?.encode_call_data(&client.metadata())?;
 		cli.verify()
 	}
 
@@ -1150,4 +3133,196 @@ mod tests {
 		assert_eq!(parse_function_name("MINT").unwrap(), "mint");
 		Ok(())
 	}
+
+	#[test]
+	fn schedule_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "call", "chain"].iter().chain(args))?;
+			let crate::commands::Command::Call(args) = pop.command else {
+				panic!("expected the call command");
+			};
+			let crate::commands::call::Command::Chain(command) = args.command else {
+				panic!("expected the chain command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["--schedule-at", "+10", "--schedule-name", "upgrade"])?;
+		assert_eq!(command.schedule_at, Some(ScheduleAt::After(10)));
+		assert_eq!(command.schedule_name.as_deref(), Some("upgrade"));
+		assert_eq!(
+			parse(&["--schedule-at", "@1767225600"])?.schedule_at,
+			Some(ScheduleAt::Timestamp(1767225600))
+		);
+		assert!(parse(&["--schedule-at", "tomorrow"]).is_err());
+		assert!(parse(&["--schedule-name", "upgrade"]).is_err());
+		assert!(parse(&["--schedule-at", "1200", "--schedule-name", &"a".repeat(33)]).is_err());
+		assert!(parse(&["--schedule-at", "1200", "--call", "0x00"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn storage_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "call", "chain"].iter().chain(args))?;
+			let crate::commands::Command::Call(args) = pop.command else {
+				panic!("expected the call command");
+			};
+			let crate::commands::call::Command::Chain(command) = args.command else {
+				panic!("expected the chain command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["--storage", "-p", "system", "--item", "account", "-a", ALICE])?;
+		assert!(command.storage);
+		assert_eq!(command.pallet.as_deref(), Some("System"));
+		assert_eq!(command.item.as_deref(), Some("Account"));
+		assert_eq!(command.args, [ALICE]);
+		assert!(parse(&["--item", "Account"]).is_err());
+		assert!(parse(&["--storage", "-f", "remark"]).is_err());
+		assert!(parse(&["--storage", "--sudo"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn multisig_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "call", "chain"].iter().chain(args))?;
+			let crate::commands::Command::Call(args) = pop.command else {
+				panic!("expected the call command");
+			};
+			let crate::commands::call::Command::Chain(command) = args.command else {
+				panic!("expected the chain command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let bob = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+		let multisig = format!("{ALICE},{bob};2");
+		let command = parse(&["-p", "System", "-f", "remark", "--multisig", &multisig])?;
+		assert_eq!(command.multisig, Some(multisig.parse()?));
+		assert!(parse(&["--multisig", ALICE]).is_err());
+		assert!(parse(&["--multisig", &multisig, "--use-wallet"]).is_err());
+		assert!(parse(&["--multisig", &multisig, "--call", "0x00000411"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn extrinsic_options_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "call", "chain"].iter().chain(args))?;
+			let crate::commands::Command::Call(args) = pop.command else {
+				panic!("expected the call command");
+			};
+			let crate::commands::call::Command::Chain(command) = args.command else {
+				panic!("expected the chain command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["-p", "System", "-f", "remark", "--nonce", "7", "--tip", "100"])?;
+		assert_eq!(
+			command.options(),
+			ExtrinsicOptions { nonce: Some(7), tip: 100, ..Default::default() }
+		);
+		assert_eq!(
+			parse(&["--era", "0"])?.options(),
+			ExtrinsicOptions { era: Some(0), ..Default::default() }
+		);
+		let command = parse(&["--wait", "best", "--wait-timeout", "60"])?;
+		assert_eq!(
+			command.options(),
+			ExtrinsicOptions { wait: WaitFor::Best, ..Default::default() }
+		);
+		assert_eq!(command.wait_timeout(), Some(Duration::from_secs(60)));
+		assert_eq!(parse(&["--wait", "none"])?.options().wait, WaitFor::None);
+		assert_eq!(parse(&[])?.options(), ExtrinsicOptions::default());
+		assert_eq!(parse(&[])?.wait_timeout(), None);
+		assert!(parse(&["--wait", "included"]).is_err());
+		assert!(parse(&["--wait", "none", "--use-wallet"]).is_err());
+		assert!(parse(&["--nonce", "7", "--use-wallet"]).is_err());
+		assert!(parse(&["--tip", "100", "--create-payload", "payload.json", "--signer", ALICE])
+			.is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn execute_propagates_timeouts() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"call",
+			"chain",
+			"--call",
+			"0x00000411",
+			"--url",
+			POP_NETWORK_TESTNET_URL,
+			"--suri",
+			BOB_SURI,
+			"--wait-timeout",
+			"0",
+			"--skip-confirm",
+		])?;
+		let crate::commands::Command::Call(args) = pop.command else {
+			panic!("expected the call command");
+		};
+		let crate::commands::call::Command::Chain(command) = args.command else {
+			panic!("expected the chain command");
+		};
+		let message = "Timed out after 0s whilst waiting for the extrinsic to be finalized";
+		let mut cli = MockCli::new().expect_intro("Call a chain").expect_outro_cancel(message);
+		let error = command.execute(&mut cli).await.unwrap_err();
+		// The timeout results in the corresponding exit code.
+		assert!(error.is::<TimedOut>());
+		assert_eq!(crate::exit_code(&error), crate::EXIT_TIMEOUT);
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn execute_lists_pallets() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"call",
+			"chain",
+			"--list",
+			"--output",
+			"json",
+			"--pallet",
+			"System",
+			"--url",
+			POP_NETWORK_TESTNET_URL,
+		])?;
+		let crate::commands::Command::Call(args) = pop.command else {
+			panic!("expected the call command");
+		};
+		let crate::commands::call::Command::Chain(command) = args.command else {
+			panic!("expected the chain command");
+		};
+		let client = set_up_client(POP_NETWORK_TESTNET_URL).await?;
+		let mut pallets = parse_chain_metadata(&client)?;
+		pallets.retain(|pallet| pallet.name == "System");
+		let mut cli = MockCli::new()
+			.expect_intro("Call a chain")
+			.expect_plain(render_pallets(&pallets, ListFormat::Json)?);
+		command.execute(&mut cli).await?;
+		cli.verify()
+	}
+
+	#[test]
+	fn file_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "call", "chain"].iter().chain(args))?;
+			let crate::commands::Command::Call(args) = pop.command else {
+				panic!("expected the call command");
+			};
+			let crate::commands::call::Command::Chain(command) = args.command else {
+				panic!("expected the chain command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["--file", "calls.toml", "--batch-all", "--sudo"])?;
+		assert_eq!(command.file, Some(PathBuf::from("calls.toml")));
+		assert!(command.batch_all && command.sudo);
+		assert!(!parse(&["--file", "calls.toml"])?.batch_all);
+		assert!(parse(&["--batch-all"]).is_err());
+		assert!(parse(&["--file", "calls.toml", "-p", "System"]).is_err());
+		assert!(parse(&["--file", "calls.toml", "--use-wallet"]).is_err());
+		Ok(())
+	}
 }
@@ -1,21 +1,23 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use crate::{
-	cli::{self, traits::*},
+	cli::traits::*,
 	common::{
+		balances::warn_of_balance_issues,
 		builds::get_project_path,
 		contracts::has_contract_been_built,
+		network,
+		secrets::{mask_suri, prompt_suri},
 		wallet::{prompt_to_use_wallet, request_signature},
 	},
 };
 use anyhow::{anyhow, Result};
 use clap::Args;
-use cliclack::spinner;
-use pop_common::{DefaultConfig, Keypair};
+use pop_common::{balances::Balances, call::TokenMetadata, DefaultConfig, Keypair};
 use pop_contracts::{
 	build_smart_contract, call_smart_contract, call_smart_contract_from_signed_payload,
 	dry_run_call, dry_run_gas_estimate_call, get_call_payload, get_message, get_messages,
-	parse_account, set_up_call, CallExec, CallOpts, DefaultEnvironment, Verbosity,
+	parse_account, set_up_call, CallExec, CallOpts, DefaultEnvironment, Error, Verbosity,
 };
 use sp_weights::Weight;
 use std::path::PathBuf;
@@ -82,31 +84,37 @@ pub struct CallContractCommand {
 	/// Recommended for testing and local development only.
 	#[arg(name = "dev", short, long, default_value = "false")]
 	dev_mode: bool,
+	/// Output the value returned by the message as JSON, for calls which are not executed.
+	#[arg(long, conflicts_with_all = ["execute", "dry_run", "use-wallet"])]
+	json: bool,
 }
 impl CallContractCommand {
 	/// Executes the command.
-	pub(crate) async fn execute(mut self) -> Result<()> {
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
 		// Check if message specified via command line argument.
 		let prompt_to_repeat_call = self.message.is_none();
 		// Configure the call based on command line arguments/call UI.
-		if let Err(e) = self.configure(&mut cli::Cli, false).await {
+		if let Err(e) = self.configure(cli, false).await {
 			match e.to_string().as_str() {
 				"Contract not deployed." => {
 					display_message(
 						"Use `pop up contract` to deploy your contract.",
 						true, // Not an error, just a message.
-						&mut cli::Cli,
+						cli,
 					)?;
 				},
 				_ => {
-					display_message(&e.to_string(), false, &mut cli::Cli)?;
+					display_message(&e.to_string(), false, cli)?;
 				},
 			}
 			return Ok(());
 		};
 		// Finally execute the call.
-		if let Err(e) = self.execute_call(&mut cli::Cli, prompt_to_repeat_call).await {
-			display_message(&e.to_string(), false, &mut cli::Cli)?;
+		if let Err(e) = self.execute_call(cli, prompt_to_repeat_call).await {
+			display_message(&e.to_string(), false, cli)?;
 		}
 		Ok(())
 	}
@@ -143,7 +151,7 @@ impl CallContractCommand {
 		if self.use_wallet {
 			full_message.push_str(" --use-wallet");
 		} else {
-			full_message.push_str(&format!(" --suri {}", self.suri));
+			full_message.push_str(&format!(" --suri {}", mask_suri(&self.suri)));
 		}
 		if self.execute {
 			full_message.push_str(" --execute");
@@ -151,6 +159,9 @@ impl CallContractCommand {
 		if self.dry_run {
 			full_message.push_str(" --dry-run");
 		}
+		if self.json {
+			full_message.push_str(" --json");
+		}
 		full_message
 	}
 
@@ -158,8 +169,8 @@ impl CallContractCommand {
 	async fn ensure_contract_built(&self, cli: &mut impl Cli) -> Result<()> {
 		let project_path = get_project_path(self.path.clone(), self.path_pos.clone());
 		// Build the contract in release mode
-		cli.warning("NOTE: contract has not yet been built.")?;
-		let spinner = spinner();
+		cli.warning("NOTE: contract has not yet been built, or has changed since it was.")?;
+		let spinner = cli.spinner();
 		spinner.start("Building contract in RELEASE mode...");
 		let result = match build_smart_contract(project_path.as_deref(), true, Verbosity::Quiet) {
 			Ok(result) => result,
@@ -228,8 +239,8 @@ impl CallContractCommand {
 
 		// Ensure contract is built and check if deployed.
 		if self.is_contract_build_required() {
-			self.ensure_contract_built(&mut cli::Cli).await?;
-			self.confirm_contract_deployment(&mut cli::Cli)?;
+			self.ensure_contract_built(cli).await?;
+			self.confirm_contract_deployment(cli)?;
 		}
 
 		// Parse the contract metadata provided. If there is an error, do not prompt for more.
@@ -340,11 +351,7 @@ impl CallContractCommand {
 			if prompt_to_use_wallet(cli)? {
 				self.use_wallet = true;
 			} else {
-				self.suri = cli
-					.input("Signer calling the contract:")
-					.placeholder("//Alice")
-					.default_input("//Alice")
-					.interact()?;
+				self.suri = prompt_suri("Signer calling the contract", DEFAULT_URI, cli)?;
 			};
 		}
 
@@ -392,7 +399,7 @@ impl CallContractCommand {
 				return Err(anyhow!("Please specify the contract address."));
 			},
 		};
-		let call_exec = match set_up_call(CallOpts {
+		let opts = CallOpts {
 			path: project_path,
 			contract,
 			message,
@@ -403,14 +410,9 @@ impl CallContractCommand {
 			url: self.url.clone(),
 			suri: self.suri.clone(),
 			execute: self.execute,
-		})
-		.await
-		{
-			Ok(call_exec) => call_exec,
-			Err(e) => {
-				return Err(anyhow!(format!("{}", e.to_string())));
-			},
 		};
+		let call_exec =
+			network::retry("connecting to the chain", || set_up_call(opts.clone())).await?;
 
 		// Perform signing steps with wallet integration, skipping secure signing for query-only
 		// operations.
@@ -419,9 +421,9 @@ impl CallContractCommand {
 			return self.finalize_execute_call(cli, prompt_to_repeat_call).await;
 		}
 		if self.dry_run {
-			let spinner = spinner();
+			let spinner = cli.spinner();
 			spinner.start("Doing a dry run to estimate the gas...");
-			match dry_run_gas_estimate_call(&call_exec).await {
+			match estimate_gas(&call_exec).await {
 				Ok(w) => {
 					cli.info(format!("Gas limit: {:?}", w))?;
 					cli.warning("Your call has not been executed.")?;
@@ -435,19 +437,28 @@ impl CallContractCommand {
 		}
 
 		if !self.execute {
-			let spinner = spinner();
+			let spinner = cli.spinner();
 			spinner.start("Calling the contract...");
-			let call_dry_run_result = dry_run_call(&call_exec).await?;
+			let return_value = network::retry("calling the contract", || async {
+				dry_run_call(&call_exec).await.map_err(unless_reverted)
+			})
+			.await?;
 			spinner.stop("");
-			cli.info(format!("Result: {}", call_dry_run_result))?;
+			match self.json {
+				true => cli.plain(return_value.to_json())?,
+				false => cli.info(format!("Result: {}", return_value.value()))?,
+			}
+			if let Some(error) = return_value.error() {
+				cli.warning(format!("The message returned an error: {error}"))?;
+			}
 			cli.warning("Your call has not been executed.")?;
 		} else {
 			let weight_limit = if self.gas_limit.is_some() && self.proof_size.is_some() {
 				Weight::from_parts(self.gas_limit.unwrap(), self.proof_size.unwrap())
 			} else {
-				let spinner = spinner();
+				let spinner = cli.spinner();
 				spinner.start("Doing a dry run to estimate the gas...");
-				match dry_run_gas_estimate_call(&call_exec).await {
+				match estimate_gas(&call_exec).await {
 					Ok(w) => {
 						cli.info(format!("Gas limit: {:?}", w))?;
 						w
@@ -458,12 +469,23 @@ impl CallContractCommand {
 					},
 				}
 			};
-			let spinner = spinner();
+			// Warn should transferring the value fail or reap the account of the signer.
+			if *call_exec.value() > 0 {
+				let signer = call_exec.opts().signer().public_key().to_account_id();
+				if let Ok(balances) = Balances::fetch(call_exec.client(), &signer, None).await {
+					let token = TokenMetadata::query::<DefaultConfig>(&self.url).await?;
+					warn_of_balance_issues(&balances, *call_exec.value(), 0, &token, cli)?;
+				}
+			}
+			let spinner = cli.spinner();
 			spinner.start("Calling the contract...");
 
-			let call_result = call_smart_contract(call_exec, weight_limit, &self.url)
-				.await
-				.map_err(|err| anyhow!("{} {}", "ERROR:", format!("{err:?}")))?;
+			let call_result = network::once("calling the contract", async {
+				call_smart_contract(call_exec, weight_limit, &self.url)
+					.await
+					.map_err(|err| anyhow!("{} {}", "ERROR:", format!("{err:?}")))
+			})
+			.await?;
 
 			cli.info(call_result)?;
 		}
@@ -506,17 +528,19 @@ impl CallContractCommand {
 			anyhow!("An error occurred getting the call data: {}", err.to_string())
 		})?;
 
-		let maybe_payload = request_signature(call_data, self.url.to_string()).await?;
+		let maybe_payload = request_signature(call_data, self.url.to_string(), cli).await?;
 		if let Some(payload) = maybe_payload {
 			cli.success("Signed payload received.")?;
-			let spinner = spinner();
+			let spinner = cli.spinner();
 			spinner
 				.start("Calling the contract and waiting for finalization, please be patient...");
 
-			let call_result =
+			let call_result = network::once("calling the contract", async {
 				call_smart_contract_from_signed_payload(call_exec, payload, &self.url)
 					.await
-					.map_err(|err| anyhow!("{} {}", "ERROR:", format!("{err:?}")))?;
+					.map_err(|err| anyhow!("{} {}", "ERROR:", format!("{err:?}")))
+			})
+			.await?;
 
 			cli.info(call_result)?;
 		} else {
@@ -549,6 +573,25 @@ impl CallContractCommand {
 	}
 }
 
+// Estimates the gas required by a call via a dry run, which is retried should it fail for any
+// reason other than the call reverting.
+async fn estimate_gas(
+	call_exec: &CallExec<DefaultConfig, DefaultEnvironment, Keypair>,
+) -> Result<Weight> {
+	network::retry("estimating the gas", || async {
+		dry_run_gas_estimate_call(call_exec).await.map_err(unless_reverted)
+	})
+	.await
+}
+
+// Marks the failure of a dry run as permanent when the call reverted, so that it is not retried.
+fn unless_reverted(error: Error) -> anyhow::Error {
+	match error {
+		Error::DryRunCallContractError(_) => network::Permanent(error.into()).into(),
+		error => error.into(),
+	}
+}
+
 fn display_message(message: &str, success: bool, cli: &mut impl Cli) -> Result<()> {
 	if success {
 		cli.outro(message)?;
@@ -592,10 +635,46 @@ mod tests {
 			dry_run: false,
 			execute: false,
 			dev_mode: false,
+			json: false,
 		}
-		.execute()
+		.execute(&mut MockCli::new())
 		.await?;
-		Ok(())
+
+		// The return value is output as JSON, to be processed by other tools.
+		let call_exec = set_up_call(CallOpts {
+			path: Some(temp_dir.path().join("testing")),
+			contract: "15XausWjFLBBFLDXUSBRfSfZk25warm4wZRV4ZxhZbfvjrJm".to_string(),
+			message: "get".to_string(),
+			args: vec![],
+			value: "0".to_string(),
+			gas_limit: None,
+			proof_size: None,
+			url: Url::parse("wss://rpc1.paseo.popnetwork.xyz")?,
+			suri: "//Alice".to_string(),
+			execute: false,
+		})
+		.await?;
+		let mut cli = MockCli::new().expect_plain(dry_run_call(&call_exec).await?.to_json());
+		CallContractCommand {
+			path: Some(temp_dir.path().join("testing")),
+			path_pos: None,
+			contract: Some("15XausWjFLBBFLDXUSBRfSfZk25warm4wZRV4ZxhZbfvjrJm".to_string()),
+			message: Some("get".to_string()),
+			args: vec![],
+			value: "0".to_string(),
+			gas_limit: None,
+			proof_size: None,
+			url: Url::parse("wss://rpc1.paseo.popnetwork.xyz")?,
+			suri: "//Alice".to_string(),
+			use_wallet: false,
+			dry_run: false,
+			execute: false,
+			dev_mode: false,
+			json: true,
+		}
+		.execute(&mut cli)
+		.await?;
+		cli.verify()
 	}
 
 	#[tokio::test]
@@ -629,6 +708,7 @@ mod tests {
 			dry_run: true,
 			execute: false,
 			dev_mode: false,
+			json: false,
 		};
 		call_config.configure(&mut cli, false).await?;
 		assert_eq!(call_config.display(), format!(
@@ -667,6 +747,7 @@ mod tests {
 			dry_run: true,
 			execute: false,
 			dev_mode: false,
+			json: false,
 		};
 		call_config.configure(&mut cli, false).await?;
 		assert_eq!(call_config.display(), format!(
@@ -754,6 +835,7 @@ mod tests {
 			dry_run: false,
 			execute: false,
 			dev_mode: false,
+			json: false,
 		};
 		call_config.configure(&mut cli, false).await?;
 		// Test the query. With true, it will prompt for another call.
@@ -817,6 +899,7 @@ mod tests {
 			dry_run: false,
 			execute: false,
 			dev_mode: false,
+			json: false,
 		};
 		call_config.configure(&mut cli, false).await?;
 		assert_eq!(
@@ -901,6 +984,7 @@ mod tests {
 			dry_run: false,
 			execute: false,
 			dev_mode: false,
+			json: false,
 		};
 		call_config.configure(&mut cli, false).await?;
 		assert_eq!(
@@ -965,7 +1049,7 @@ mod tests {
 			.expect_input("Enter the value for the parameter: new_value", "true".into()) // Args for specific_flip
 			.expect_input("Enter the value for the parameter: number", "2".into()) // Args for specific_flip
 			.expect_input("Value to transfer to the call:", "50".into()) // Only if payable
-			.expect_input("Signer calling the contract:", "//Alice".into())
+			.expect_password("Signer calling the contract [default: //Alice]:", "")
 			.expect_info(format!(
 				"pop call contract --path {} --contract 15XausWjFLBBFLDXUSBRfSfZk25warm4wZRV4ZxhZbfvjrJm --message specific_flip --args \"true\", \"2\" --value 50 --url wss://rpc1.paseo.popnetwork.xyz/ --suri //Alice --execute",
 				temp_dir.path().join("testing").display().to_string(),
@@ -986,6 +1070,7 @@ mod tests {
 			dry_run: false,
 			execute: false,
 			dev_mode: true,
+			json: false,
 		};
 		call_config.configure(&mut cli, false).await?;
 		assert_eq!(
@@ -1046,6 +1131,7 @@ mod tests {
 			dry_run: false,
 			execute: false,
 			dev_mode: false,
+			json: false,
 		};
 		let mut cli = MockCli::new();
 		assert!(
@@ -1097,6 +1183,7 @@ mod tests {
 				dry_run: false,
 				execute: false,
 				dev_mode: false,
+				json: false,
 			}.execute_call(&mut cli, false).await,
 			anyhow::Result::Err(message) if message.to_string() == "Please specify the message to call."
 		));
@@ -1117,6 +1204,7 @@ mod tests {
 				dry_run: false,
 				execute: false,
 				dev_mode: false,
+				json: false,
 			}.execute_call(&mut cli, false).await,
 			anyhow::Result::Err(message) if message.to_string() == "Please specify the contract address."
 		));
@@ -1142,6 +1230,7 @@ mod tests {
 			dry_run: false,
 			execute: false,
 			dev_mode: false,
+			json: false,
 		};
 		// Contract is not deployed.
 		let mut cli =
@@ -1174,6 +1263,7 @@ mod tests {
 			dry_run: false,
 			execute: false,
 			dev_mode: false,
+			json: false,
 		};
 		// Contract not build. Build is required.
 		assert!(call_config.is_contract_build_required());
@@ -1198,4 +1288,36 @@ mod tests {
 		display_message("Call failed.", false, &mut cli)?;
 		cli.verify()
 	}
+
+	#[test]
+	// The contract command is the only call command without the `parachain` feature.
+	#[allow(irrefutable_let_patterns)]
+	fn json_args_works() -> Result<()> {
+		use clap::Parser;
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "call", "contract"].iter().chain(args))?;
+			let crate::commands::Command::Call(args) = pop.command else {
+				panic!("expected the call command");
+			};
+			let super::super::Command::Contract(command) = args.command else {
+				panic!("expected the contract command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["--message", "get", "--json"])?;
+		assert!(command.json);
+		assert!(command.display().ends_with(" --json"));
+		// The value returned by the message is only output for calls which are not executed.
+		assert!(parse(&["--message", "flip", "--json", "--execute"]).is_err());
+		assert!(parse(&["--message", "flip", "--json", "--use-wallet"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn unless_reverted_works() {
+		assert!(unless_reverted(Error::DryRunCallContractError("reverted".into()))
+			.is::<network::Permanent>());
+		assert!(!unless_reverted(Error::InstallContractsNode("failed".into()))
+			.is::<network::Permanent>());
+	}
 }
@@ -6,13 +6,15 @@ use clap::{Args, Subcommand};
 pub(crate) mod chain;
 #[cfg(feature = "contract")]
 pub(crate) mod contract;
+#[cfg(feature = "parachain")]
+pub(crate) mod xcm;
 
 /// Arguments for calling a smart contract.
 #[derive(Args)]
 #[command(args_conflicts_with_subcommands = true)]
-pub(crate) struct CallArgs {
+pub struct CallArgs {
 	#[command(subcommand)]
-	pub command: Command,
+	pub(crate) command: Command,
 }
 
 /// Call a chain or a smart contract.
@@ -26,4 +28,8 @@ pub(crate) enum Command {
 	#[cfg(feature = "contract")]
 	#[clap(alias = "c")]
 	Contract(contract::CallContractCommand),
+	/// Send a cross-chain message, e.g. to transfer assets
+	#[cfg(feature = "parachain")]
+	#[clap(alias = "x")]
+	Xcm(xcm::XcmArgs),
 }
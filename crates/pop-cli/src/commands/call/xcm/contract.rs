@@ -0,0 +1,338 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{configure_chains, dry_run, DEFAULT_URI};
+use crate::{
+	cli::traits::*,
+	common::{network, secrets::prompt_suri},
+};
+use anyhow::{anyhow, Result};
+use clap::{
+	builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+	Args,
+};
+use pop_common::{create_signer, enum_variants};
+use pop_contracts::{
+	dry_run_gas_estimate_call, get_call_payload, get_messages, set_up_call, CallOpts,
+};
+use pop_parachains::{
+	encode_call_data, execution_fee, set_up_client, sign_and_submit_extrinsic, AccountId32, Chain,
+	RemoteCall,
+};
+use std::{path::PathBuf, str::FromStr};
+use strum::VariantArray;
+use url::Url;
+
+/// The margin, as a percentage, added to an estimated execution fee to allow for changes in state
+/// before the message is executed.
+const FEE_MARGIN: u128 = 10;
+
+/// Command to call a smart contract deployed on another chain via XCM.
+#[derive(Args, Default)]
+pub(crate) struct ContractCommand {
+	/// Path to the contract build directory or a contract artifact.
+	#[arg(short, long, default_value = "./")]
+	path: PathBuf,
+	/// The chain from which the message is sent.
+	#[arg(long, value_parser = enum_variants!(Chain))]
+	from: Option<Chain>,
+	/// The chain on which the contract is deployed.
+	#[arg(long, value_parser = enum_variants!(Chain))]
+	to: Option<Chain>,
+	/// The address of the contract to call.
+	#[arg(short, long)]
+	contract: String,
+	/// The name of the contract message to call.
+	#[arg(short, long)]
+	message: String,
+	/// The message arguments, encoded as strings.
+	#[arg(short, long, num_args = 0..,)]
+	args: Vec<String>,
+	/// The value to be transferred as part of the call.
+	#[arg(short, long, default_value = "0")]
+	value: String,
+	/// Maximum amount of gas to be used by the call [default: estimated via a dry run].
+	#[arg(name = "gas", short, long)]
+	gas_limit: Option<u64>,
+	/// Maximum proof size of the call [default: estimated via a dry run].
+	#[arg(short = 'P', long)]
+	proof_size: Option<u64>,
+	/// The fee paying for execution on the destination, in the smallest unit of the relay chain
+	/// token [default: estimated via the destination].
+	#[arg(long)]
+	fee: Option<u128>,
+	/// Websocket endpoint of a node of the source chain [default: from the registry].
+	#[arg(short, long, value_parser)]
+	url: Option<Url>,
+	/// Websocket endpoint of a node of the destination chain [default: from the registry].
+	#[arg(long, value_parser)]
+	destination_url: Option<Url>,
+	/// Secret key URI for the account signing the extrinsic.
+	#[arg(short, long)]
+	suri: Option<String>,
+	/// Automatically signs and submits the extrinsic without prompting for confirmation.
+	#[arg(short = 'y', long)]
+	skip_confirm: bool,
+}
+
+impl ContractCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Call a contract on another chain via XCM")?;
+		let (source, destination) = match self.configure(cli) {
+			Ok(chains) => chains,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		};
+		let suri = match &self.suri {
+			Some(suri) => suri.clone(),
+			None => prompt_suri("Signer of the extrinsic", DEFAULT_URI, cli)?,
+		};
+		let origin = create_signer(&suri)?.public_key().to_account_id();
+		let remote = match self.prepare(source, destination, &origin, &suri, cli).await {
+			Ok(remote) => remote,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		};
+
+		let url = match &self.url {
+			Some(url) => url.clone(),
+			None => Url::parse(source.url())?,
+		};
+		let client =
+			network::retry("connecting to the chain", || set_up_client(url.as_str())).await?;
+		let xt = remote.construct();
+		cli.info(format!("Encoded call data: {}", encode_call_data(&client, &xt)?))?;
+		if let Some(error) =
+			dry_run(&client, xt.clone(), &origin, Some(source), self.skip_confirm, cli).await?
+		{
+			cli.outro_cancel(format!("🚫 The call would fail: {error}"))?;
+			return Ok(());
+		}
+
+		if !self.skip_confirm &&
+			!cli.confirm("Do you want to submit the call?").initial_value(true).interact()?
+		{
+			cli.outro_cancel("Call was not submitted.")?;
+			return Ok(());
+		}
+		let spinner = cli.spinner();
+		spinner.start("Signing and submitting the extrinsic and then waiting for finalization, please be patient...");
+		let result = network::once("submitting the extrinsic", async {
+			sign_and_submit_extrinsic(&client, &url, xt, &suri)
+				.await
+				.map_err(|err| anyhow!("{}", format!("{err:?}")))
+		})
+		.await?;
+		spinner.stop(result);
+		cli.outro(format!(
+			"Message sent: the contract is called once the message is executed on {}.",
+			destination.name()
+		))?;
+		Ok(())
+	}
+
+	// Configures the chains based on command line arguments, otherwise prompting the user, and
+	// checks that the message can be called.
+	fn configure(&self, cli: &mut impl Cli) -> Result<(Chain, Chain)> {
+		let (source, destination) = configure_chains(self.from, self.to, "message", cli)?;
+		let messages = get_messages(&self.path).map_err(|e| {
+			anyhow!(
+				"Unable to fetch contract metadata: {}",
+				e.to_string().replace("Anyhow error: ", "")
+			)
+		})?;
+		let message = messages.iter().find(|m| m.label == self.message).ok_or_else(|| {
+			anyhow!("The message {} is not defined by the contract.", self.message)
+		})?;
+		// The result of a call is not returned to the source, so only mutating calls have any
+		// effect.
+		if !message.mutates {
+			return Err(anyhow!(
+				"The message {} does not mutate the state of the contract. Use `pop call contract` to query it.",
+				self.message
+			));
+		}
+		Ok((source, destination))
+	}
+
+	// Prepares the remote call, encoding the call of the contract for the runtime of the
+	// destination and estimating both its weight and the fee for its execution.
+	async fn prepare(
+		&self,
+		source: Chain,
+		destination: Chain,
+		origin: &AccountId32,
+		suri: &str,
+		cli: &mut impl Cli,
+	) -> Result<RemoteCall> {
+		let url = match &self.destination_url {
+			Some(url) => url.clone(),
+			None => Url::parse(destination.url())?,
+		};
+		let spinner = cli.spinner();
+		spinner.start(format!(
+			"Encoding the call of {} on {}...",
+			self.message,
+			destination.name()
+		));
+		// The weight is estimated with the signer as caller, as the account derived from the
+		// origin on the destination may not yet exist.
+		let call_exec = set_up_call(CallOpts {
+			path: Some(self.path.clone()),
+			contract: self.contract.clone(),
+			message: self.message.clone(),
+			args: self.args.clone(),
+			value: self.value.clone(),
+			gas_limit: self.gas_limit,
+			proof_size: self.proof_size,
+			url: url.clone(),
+			suri: suri.to_string(),
+			execute: false,
+		})
+		.await?;
+		let weight = dry_run_gas_estimate_call(&call_exec).await?;
+		let call = get_call_payload(&call_exec, weight)?;
+		let mut remote = RemoteCall {
+			source,
+			destination,
+			origin: origin.clone(),
+			call,
+			weight: (weight.ref_time(), weight.proof_size()),
+			fee: 0,
+		};
+		remote.fee = match self.fee {
+			Some(fee) => fee,
+			None => {
+				spinner.set_message("Estimating the execution fee...");
+				let client =
+					network::retry("connecting to the destination", || set_up_client(url.as_str()))
+						.await?;
+				let fee = execution_fee(&client, remote.message(), remote.fee_location()).await?;
+				fee + fee * FEE_MARGIN / 100
+			},
+		};
+		spinner.clear();
+		cli.info(format!(
+			"Execution on {} is paid with {} of the relay chain token ({}), in its smallest unit.",
+			destination.name(),
+			remote.fee,
+			destination.consensus().symbol()
+		))?;
+		Ok(remote)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use pop_contracts::{mock_build_process, new_environment};
+	use std::env;
+
+	#[test]
+	fn contract_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"call",
+			"xcm",
+			"contract",
+			"--from",
+			"assethub-paseo",
+			"--to",
+			"pop-paseo",
+			"--contract",
+			"15XausWjFLBBFLDXUSBRfSfZk25warm4wZRV4ZxhZbfvjrJm",
+			"--message",
+			"specific_flip",
+			"--args",
+			"true",
+			"2",
+			"--fee",
+			"1000",
+		])?;
+		let crate::commands::Command::Call(args) = pop.command else {
+			panic!("expected the call command");
+		};
+		let super::super::super::Command::Xcm(args) = args.command else {
+			panic!("expected the xcm command");
+		};
+		let super::super::Command::Contract(command) = args.command else {
+			panic!("expected the contract command");
+		};
+		assert_eq!(command.from, Some(Chain::AssetHubPaseo));
+		assert_eq!(command.to, Some(Chain::PopPaseo));
+		assert_eq!(command.message, "specific_flip");
+		assert_eq!(command.args, ["true", "2"]);
+		assert_eq!(command.value, "0");
+		assert_eq!(command.fee, Some(1_000));
+		assert_eq!(command.path, PathBuf::from("./"));
+		// The contract and message are required.
+		assert!(
+			crate::Cli::try_parse_from(["pop", "call", "xcm", "contract", "-m", "flip"]).is_err()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn configure_works() -> Result<()> {
+		let temp_dir = new_environment("testing")?;
+		let mut current_dir = env::current_dir().expect("Failed to get current directory");
+		current_dir.pop();
+		mock_build_process(
+			temp_dir.path().join("testing"),
+			current_dir.join("pop-contracts/tests/files/testing.contract"),
+			current_dir.join("pop-contracts/tests/files/testing.json"),
+		)?;
+		let command = |message: &str| ContractCommand {
+			path: temp_dir.path().join("testing"),
+			from: Some(Chain::AssetHubPaseo),
+			to: Some(Chain::PopPaseo),
+			message: message.into(),
+			..Default::default()
+		};
+		let mut cli = MockCli::new();
+		assert_eq!(command("flip").configure(&mut cli)?, (Chain::AssetHubPaseo, Chain::PopPaseo));
+		assert_eq!(
+			command("get").configure(&mut cli).unwrap_err().to_string(),
+			"The message get does not mutate the state of the contract. Use `pop call contract` to query it."
+		);
+		assert_eq!(
+			command("burn").configure(&mut cli).unwrap_err().to_string(),
+			"The message burn is not defined by the contract."
+		);
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn contract_fails_when_not_mutating() -> Result<()> {
+		let temp_dir = new_environment("testing")?;
+		let mut current_dir = env::current_dir().expect("Failed to get current directory");
+		current_dir.pop();
+		mock_build_process(
+			temp_dir.path().join("testing"),
+			current_dir.join("pop-contracts/tests/files/testing.contract"),
+			current_dir.join("pop-contracts/tests/files/testing.json"),
+		)?;
+		let mut cli = MockCli::new()
+			.expect_intro("Call a contract on another chain via XCM")
+			.expect_outro_cancel("🚫 The message get does not mutate the state of the contract. Use `pop call contract` to query it.");
+		ContractCommand {
+			path: temp_dir.path().join("testing"),
+			from: Some(Chain::AssetHubPaseo),
+			to: Some(Chain::PopPaseo),
+			message: "get".into(),
+			..Default::default()
+		}
+		.execute(&mut cli)
+		.await?;
+		cli.verify()
+	}
+}
@@ -0,0 +1,246 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::network};
+use anyhow::{anyhow, Result};
+use clap::{Args, Subcommand};
+use pop_parachains::{
+	dry_run_call, dry_run_xcm, estimate_fees, instructions, relative_location, resolve_location,
+	set_up_client, AccountId32, Chain, DryRunEffects, DynamicPayload, Fee, HopFees, OnlineClient,
+	SubstrateConfig,
+};
+use strum::VariantArray;
+
+#[cfg(feature = "contract")]
+pub(crate) mod contract;
+pub(crate) mod transfer;
+
+const DEFAULT_URI: &str = "//Alice";
+
+/// Arguments for sending cross-chain messages.
+#[derive(Args)]
+pub(crate) struct XcmArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Send cross-chain messages.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Transfer assets to another chain.
+	#[clap(alias = "t")]
+	Transfer(transfer::TransferCommand),
+	/// Call a smart contract deployed on another chain.
+	#[cfg(feature = "contract")]
+	#[clap(alias = "c")]
+	Contract(contract::ContractCommand),
+}
+
+/// Configures the chains between which a message is sent based on command line arguments,
+/// otherwise prompting the user.
+///
+/// # Arguments
+/// * `from` - The chain from which the message is sent, if specified.
+/// * `to` - The chain to which the message is sent, if specified.
+/// * `subject` - What is being sent, for use within prompts.
+/// * `cli` - The cli.
+pub(crate) fn configure_chains(
+	from: Option<Chain>,
+	to: Option<Chain>,
+	subject: &str,
+	cli: &mut impl Cli,
+) -> Result<(Chain, Chain)> {
+	let source = match from {
+		Some(chain) => chain,
+		None => {
+			let mut prompt =
+				cli.select(format!("Select the chain from which to send the {subject}:"));
+			// Ethereum networks are only reachable via a bridge.
+			for chain in Chain::VARIANTS.iter().filter(|c| !c.is_ethereum()) {
+				prompt = prompt.item(*chain, chain.name(), chain.url());
+			}
+			prompt.interact()?
+		},
+	};
+	let destination = match to {
+		Some(chain) => chain,
+		None => {
+			let siblings = source.siblings();
+			let bridged = source.bridged();
+			if siblings.is_empty() && bridged.is_empty() {
+				return Err(anyhow!("No destinations are known for {}.", source.name()));
+			}
+			let mut prompt =
+				cli.select(format!("Select the chain to which to send the {subject}:"));
+			for chain in siblings {
+				prompt = prompt.item(chain, chain.name(), chain.url());
+			}
+			for chain in bridged {
+				let bridge = source.bridge(&chain).map(|b| b.name()).unwrap_or_default();
+				prompt = prompt.item(chain, chain.name(), format!("via {bridge}"));
+			}
+			prompt.interact()?
+		},
+	};
+	Ok((source, destination))
+}
+
+/// Dry-runs an XCM-related call, displaying the messages it would forward to other chains, which
+/// can then be dry-run on their destinations too. Returns the reason the call would fail, if any.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `xt` - The call to be dry-run.
+/// * `origin` - The account from which the call is dispatched.
+/// * `source` - The chain, if known, which is required to dry-run forwarded messages.
+/// * `skip_confirm` - Whether to skip the prompt to dry-run forwarded messages.
+/// * `cli` - The cli.
+pub(crate) async fn dry_run(
+	client: &OnlineClient<SubstrateConfig>,
+	xt: DynamicPayload,
+	origin: &AccountId32,
+	source: Option<Chain>,
+	skip_confirm: bool,
+	cli: &mut impl Cli,
+) -> Result<Option<String>> {
+	let spinner = cli.spinner();
+	spinner.start("Dry-running the call...");
+	let effects = match dry_run_call(client, xt, origin).await {
+		Ok(effects) => effects,
+		Err(e) => {
+			spinner.clear();
+			cli.warning(format!("NOTE: the call could not be dry-run: {e}"))?;
+			return Ok(None);
+		},
+	};
+	if let Some(error) = effects.error {
+		spinner.error("Dry run failed");
+		return Ok(Some(error));
+	}
+	spinner.stop("Dry run successful");
+
+	for forwarded in effects.forwarded {
+		let destination = source.and_then(|s| resolve_location(&s, &forwarded.destination));
+		let name = match destination {
+			Some(chain) => chain.name().to_string(),
+			None => forwarded.destination.to_string(),
+		};
+		for message in &forwarded.messages {
+			cli.info(format!("Message forwarded to {name}: {}", instructions(message).join(", ")))?;
+		}
+		let (Some(source), Some(destination)) = (source, destination) else { continue };
+		if skip_confirm ||
+			!cli.confirm(format!("Would you like to dry-run the forwarded messages on {name}?"))
+				.initial_value(true)
+				.interact()?
+		{
+			continue;
+		}
+		let client =
+			network::retry("connecting to the destination", || set_up_client(destination.url()))
+				.await?;
+		let origin = relative_location(&destination, &source);
+		for message in forwarded.messages {
+			match dry_run_xcm(&client, origin.clone(), message).await {
+				Ok(DryRunEffects { error: None, .. }) =>
+					cli.success(format!("The message executes successfully on {name}"))?,
+				Ok(DryRunEffects { error: Some(e), .. }) =>
+					cli.warning(format!("The message would fail on {name}: {e}"))?,
+				Err(e) =>
+					cli.warning(format!("NOTE: the message could not be dry-run on {name}: {e}"))?,
+			}
+		}
+	}
+	Ok(None)
+}
+
+/// Estimates the fees charged by each chain along the route of a cross-chain call and displays a
+/// breakdown per hop. Returns the fees, or none if they could not be estimated.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the source.
+/// * `source` - The chain from which the call is dispatched.
+/// * `xt` - The call.
+/// * `origin` - The account from which the call is dispatched.
+/// * `cli` - The cli.
+pub(crate) async fn display_fees(
+	client: &OnlineClient<SubstrateConfig>,
+	source: Chain,
+	xt: DynamicPayload,
+	origin: &AccountId32,
+	cli: &mut impl Cli,
+) -> Result<Vec<HopFees>> {
+	let spinner = cli.spinner();
+	spinner.start("Estimating the fees of each hop...");
+	let hops = estimate_fees(client, source, xt, origin).await;
+	spinner.clear();
+	match hops {
+		Ok(hops) => {
+			cli.info(breakdown(&hops))?;
+			Ok(hops)
+		},
+		Err(e) => {
+			cli.warning(format!("NOTE: the fees could not be estimated: {e}"))?;
+			Ok(vec![])
+		},
+	}
+}
+
+// Formats the fees charged by each hop.
+fn breakdown(hops: &[HopFees]) -> String {
+	let amount = |fee: &Fee, chain: &Chain| match fee.symbol(chain) {
+		Some(symbol) => format!("{} {symbol}", fee.amount),
+		None => format!("{} of {}", fee.amount, fee.asset),
+	};
+	let mut output = "Estimated fees, in the smallest unit of each asset:".to_string();
+	for (i, hop) in hops.iter().enumerate() {
+		let execution = match &hop.execution {
+			Some(fee) => format!("execution {}", amount(fee, &hop.chain)),
+			None => "unpaid execution".to_string(),
+		};
+		output.push_str(&format!("\n  {}. {}: {execution}", i + 1, hop.chain.name()));
+		if !hop.delivery.is_empty() {
+			let delivery: Vec<_> = hop.delivery.iter().map(|f| amount(f, &hop.chain)).collect();
+			output.push_str(&format!(", delivery {}", delivery.join(" + ")));
+		}
+	}
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pop_parachains::Value;
+
+	#[test]
+	fn breakdown_works() {
+		let here = |parents| {
+			Value::named_composite([
+				("parents", Value::u128(parents)),
+				("interior", Value::unnamed_variant("Here", [])),
+			])
+		};
+		let hops = [
+			HopFees {
+				chain: Chain::PopPaseo,
+				execution: Some(Fee { asset: here(0), amount: 1_000 }),
+				delivery: vec![
+					Fee { asset: here(1), amount: 20 },
+					Fee { asset: here(2), amount: 3 },
+				],
+			},
+			HopFees {
+				chain: Chain::Paseo,
+				execution: Some(Fee { asset: here(0), amount: 15 }),
+				delivery: vec![],
+			},
+			HopFees { chain: Chain::AssetHubPaseo, execution: None, delivery: vec![] },
+		];
+		assert_eq!(
+			breakdown(&hops),
+			"Estimated fees, in the smallest unit of each asset:\n  \
+			 1. Pop Network (Paseo): execution 1000 PAS, delivery 20 PAS + 3 of { parents: 2, interior: Here () }\n  \
+			 2. Paseo: execution 15 PAS\n  \
+			 3. Asset Hub (Paseo): unpaid execution"
+		);
+	}
+}
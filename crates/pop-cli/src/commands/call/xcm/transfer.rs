@@ -0,0 +1,481 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{configure_chains, display_fees, dry_run, DEFAULT_URI};
+use crate::{
+	cli::traits::*,
+	common::{network, secrets::prompt_suri},
+};
+use anyhow::{anyhow, Result};
+use clap::{
+	builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+	Args,
+};
+use pop_common::{create_signer, enum_variants};
+use pop_parachains::{
+	encode_call_data, registered_assets, set_up_client, sign_and_submit_extrinsic, Asset,
+	Beneficiary, Chain, RegisteredAsset, Transfer, TransferType,
+};
+use std::str::FromStr;
+use strum::{EnumMessage, VariantArray};
+use url::Url;
+
+/// Command to transfer assets between chains via XCM.
+#[derive(Args, Default)]
+pub(crate) struct TransferCommand {
+	/// The chain from which the assets are sent.
+	#[arg(long, value_parser = enum_variants!(Chain))]
+	from: Option<Chain>,
+	/// The chain to which the assets are sent.
+	#[arg(long, value_parser = enum_variants!(Chain))]
+	to: Option<Chain>,
+	/// The mechanism by which the assets are transferred.
+	#[arg(short = 't', long = "type", value_parser = enum_variants!(TransferType))]
+	transfer_type: Option<TransferType>,
+	/// The asset to be transferred: `relay`, `native` or the identifier or symbol of an asset
+	/// registered on the source.
+	#[arg(short, long)]
+	asset: Option<String>,
+	/// The amount to be transferred, in the smallest unit of the asset.
+	#[arg(long)]
+	amount: Option<u128>,
+	/// The account receiving the assets on the destination, as an SS58 address or, on Ethereum,
+	/// a 20-byte hex address.
+	#[arg(short, long)]
+	beneficiary: Option<String>,
+	/// Websocket endpoint of a node of the source chain [default: from the registry].
+	#[arg(short, long, value_parser)]
+	url: Option<Url>,
+	/// Secret key URI for the account signing the extrinsic.
+	#[arg(short, long)]
+	suri: Option<String>,
+	/// Automatically signs and submits the extrinsic without prompting for confirmation.
+	#[arg(short = 'y', long)]
+	skip_confirm: bool,
+}
+
+impl TransferCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Transfer assets via XCM")?;
+		let (source, destination, transfer_type) = match self.configure_route(cli) {
+			Ok(route) => route,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		};
+		let url = match &self.url {
+			Some(url) => url.clone(),
+			None => Url::parse(source.url())?,
+		};
+		let client =
+			network::retry("connecting to the chain", || set_up_client(url.as_str())).await?;
+		// Any assets registered on the source can also be transferred.
+		let assets = registered_assets(&client).await.unwrap_or_default();
+		let transfer =
+			match self.configure_transfer(source, destination, transfer_type, &assets, cli) {
+				Ok(transfer) => transfer,
+				Err(e) => {
+					cli.outro_cancel(format!("🚫 {e}"))?;
+					return Ok(());
+				},
+			};
+		if let Some(bridge) = transfer.bridge() {
+			cli.info(format!(
+				"The assets are transferred via {}, so are expected to arrive on {} in {}.",
+				bridge.name(),
+				destination.name(),
+				bridge.delivery_time()
+			))?;
+		}
+		let suri = match &self.suri {
+			Some(suri) => suri.clone(),
+			None => prompt_suri("Signer of the extrinsic", DEFAULT_URI, cli)?,
+		};
+		let signer = create_signer(&suri)?;
+		let xt = transfer.construct();
+		cli.info(format!("Encoded call data: {}", encode_call_data(&client, &xt)?))?;
+
+		// Dry-run the transfer before submission, so that failures are caught without fees.
+		let origin = signer.public_key().to_account_id();
+		let source = Some(transfer.source);
+		if let Some(error) =
+			dry_run(&client, xt.clone(), &origin, source, self.skip_confirm, cli).await?
+		{
+			cli.outro_cancel(format!("🚫 The transfer would fail: {error}"))?;
+			return Ok(());
+		}
+		let hops = display_fees(&client, transfer.source, xt.clone(), &origin, cli).await?;
+		if let Some(fees) = transfer.uncovered_fees(&hops) {
+			cli.warning(format!(
+				"NOTE: the amount may not cover the fees of {fees} {} charged along the route to {}.",
+				transfer.source.consensus().symbol(),
+				transfer.destination.name()
+			))?;
+		}
+
+		if !self.skip_confirm &&
+			!cli.confirm("Do you want to submit the transfer?")
+				.initial_value(true)
+				.interact()?
+		{
+			cli.outro_cancel("Transfer was not submitted.")?;
+			return Ok(());
+		}
+		let spinner = cli.spinner();
+		spinner.start("Signing and submitting the extrinsic and then waiting for finalization, please be patient...");
+		let result = network::once("submitting the extrinsic", async {
+			sign_and_submit_extrinsic(&client, &url, xt, &suri)
+				.await
+				.map_err(|err| anyhow!("{}", format!("{err:?}")))
+		})
+		.await?;
+		spinner.stop(result);
+		cli.outro("Transfer complete.")?;
+		Ok(())
+	}
+
+	// Configures the chains and the mechanism of the transfer based on command line arguments,
+	// otherwise prompting the user.
+	fn configure_route(&self, cli: &mut impl Cli) -> Result<(Chain, Chain, TransferType)> {
+		let (source, destination) = configure_chains(self.from, self.to, "assets", cli)?;
+		let supported = TransferType::supported(&source, &destination);
+		let transfer_type = match self.transfer_type {
+			Some(transfer_type) => transfer_type,
+			None => match supported[..] {
+				[] =>
+					return Err(anyhow!(
+						"Assets cannot be transferred from {} to {}.",
+						source.name(),
+						destination.name()
+					)),
+				[transfer_type] => transfer_type,
+				_ => {
+					let mut prompt = cli.select("Select how the assets are transferred:");
+					for transfer_type in supported {
+						prompt = prompt.item(
+							transfer_type,
+							transfer_type.get_message().unwrap_or_default(),
+							transfer_type.get_detailed_message().unwrap_or_default(),
+						);
+					}
+					prompt.interact()?
+				},
+			},
+		};
+		transfer_type.check(&source, &destination)?;
+		Ok((source, destination, transfer_type))
+	}
+
+	// Configures the transfer based on command line arguments, otherwise prompting the user.
+	fn configure_transfer(
+		&self,
+		source: Chain,
+		destination: Chain,
+		transfer_type: TransferType,
+		assets: &[RegisteredAsset],
+		cli: &mut impl Cli,
+	) -> Result<Transfer> {
+		let asset = match &self.asset {
+			Some(asset) => match asset.parse::<Asset>() {
+				Ok(asset) => asset,
+				// Otherwise the identifier or symbol of a registered asset.
+				Err(_) => assets
+					.iter()
+					.find(|a| a.id.to_string() == *asset || a.symbol.eq_ignore_ascii_case(asset))
+					.map(|a| Asset::Registered(a.location.clone()))
+					.ok_or_else(|| {
+						anyhow!("The asset {asset} is not registered on {}.", source.name())
+					})?,
+			},
+			// The native token of a relay chain is the relay chain token.
+			None if source.relay().is_none() => Asset::Relay,
+			None => {
+				let mut prompt = cli.select("Select the asset to transfer:");
+				prompt =
+					prompt.item(Asset::Relay, "Relay chain token", source.consensus().symbol());
+				prompt = prompt.item(
+					Asset::Native,
+					"Native token",
+					format!("The native token of {}", source.name()),
+				);
+				for asset in assets {
+					prompt = prompt.item(
+						Asset::Registered(asset.location.clone()),
+						&asset.symbol,
+						format!("{} ({})", asset.name, asset.id),
+					);
+				}
+				prompt.interact()?
+			},
+		};
+		let amount = match self.amount {
+			Some(amount) => amount,
+			None => cli
+				.input("Enter the amount to transfer:")
+				.placeholder(&format!(
+					"The amount in the smallest unit, where 1 {} is 10^{}",
+					source.consensus().symbol(),
+					source.consensus().decimals()
+				))
+				.validate(|input| match input.parse::<u128>() {
+					Ok(amount) if amount > 0 => Ok(()),
+					_ => Err("Invalid amount."),
+				})
+				.interact()?
+				.parse()?,
+		};
+		let beneficiary = match &self.beneficiary {
+			Some(beneficiary) => beneficiary.clone(),
+			None => {
+				let ethereum = destination.is_ethereum();
+				cli.input(format!(
+					"Enter the account receiving the assets on {}:",
+					destination.name()
+				))
+				.placeholder(match ethereum {
+					true => "e.g. 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+					false => "e.g. 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+				})
+				.validate(move |input| match Beneficiary::from_str(input) {
+					Ok(Beneficiary::AccountKey20(_)) if ethereum => Ok(()),
+					Ok(Beneficiary::AccountId32(_)) if !ethereum => Ok(()),
+					_ => Err("Invalid address."),
+				})
+				.interact()?
+			},
+		};
+		let beneficiary = Beneficiary::from_str(&beneficiary)
+			.map_err(|_| anyhow!("Invalid beneficiary: {beneficiary}"))?;
+		let transfer = Transfer { source, destination, transfer_type, asset, amount, beneficiary };
+		transfer.validate()?;
+		Ok(transfer)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use pop_parachains::{AssetId, Value};
+
+	const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+	#[test]
+	fn transfer_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"call",
+			"xcm",
+			"transfer",
+			"--from",
+			"paseo",
+			"--to",
+			"assethub-paseo",
+			"--type",
+			"teleport",
+			"--amount",
+			"10000000000",
+			"--beneficiary",
+			ALICE,
+		])?;
+		let crate::commands::Command::Call(args) = pop.command else {
+			panic!("expected the call command");
+		};
+		let super::super::super::Command::Xcm(args) = args.command else {
+			panic!("expected the xcm command");
+		};
+		let super::super::Command::Transfer(command) = args.command else {
+			panic!("expected the transfer command");
+		};
+		assert_eq!(command.from, Some(Chain::Paseo));
+		assert_eq!(command.to, Some(Chain::AssetHubPaseo));
+		assert_eq!(command.transfer_type, Some(TransferType::Teleport));
+		assert_eq!(command.amount, Some(10_000_000_000));
+		assert!(crate::Cli::try_parse_from(["pop", "call", "xcm", "transfer", "--from", "mars"])
+			.is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn configure_route_works() -> Result<()> {
+		let destinations = Chain::PopPaseo
+			.siblings()
+			.iter()
+			.map(|c| (c.name().to_string(), c.url().to_string()))
+			.collect();
+		let mut cli = MockCli::new()
+			.expect_select(
+				"Select the chain from which to send the assets:",
+				Some(true),
+				true,
+				None,
+				Chain::VARIANTS.iter().position(|c| *c == Chain::PopPaseo).unwrap(),
+			)
+			.expect_select(
+				"Select the chain to which to send the assets:",
+				Some(true),
+				true,
+				Some(destinations),
+				1,
+			);
+		// Only reserve transfers are supported from a non-system chain.
+		assert_eq!(
+			TransferCommand::default().configure_route(&mut cli)?,
+			(Chain::PopPaseo, Chain::AssetHubPaseo, TransferType::Reserve)
+		);
+		cli.verify()
+	}
+
+	#[test]
+	fn configure_bridged_route_works() -> Result<()> {
+		let mut destinations: Vec<_> = Chain::AssetHubPolkadot
+			.siblings()
+			.iter()
+			.map(|c| (c.name().to_string(), c.url().to_string()))
+			.collect();
+		destinations.extend([
+			("Asset Hub (Kusama)".to_string(), "via the Polkadot <> Kusama bridge".to_string()),
+			("Ethereum".to_string(), "via Snowbridge".to_string()),
+		]);
+		let mut cli = MockCli::new().expect_select(
+			"Select the chain to which to send the assets:",
+			Some(true),
+			true,
+			Some(destinations.clone()),
+			destinations.len() - 1,
+		);
+		// Bridged transfers are via the reserve of the assets.
+		let command = TransferCommand { from: Some(Chain::AssetHubPolkadot), ..Default::default() };
+		assert_eq!(
+			command.configure_route(&mut cli)?,
+			(Chain::AssetHubPolkadot, Chain::Ethereum, TransferType::Reserve)
+		);
+		cli.verify()?;
+
+		let mut cli = MockCli::new();
+		let key = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+		let command = TransferCommand {
+			asset: Some("relay".into()),
+			amount: Some(1),
+			beneficiary: Some(key.into()),
+			..Default::default()
+		};
+		let transfer = command.configure_transfer(
+			Chain::AssetHubPolkadot,
+			Chain::Ethereum,
+			TransferType::Reserve,
+			&[],
+			&mut cli,
+		)?;
+		assert_eq!(transfer.beneficiary, Beneficiary::from_str(key)?);
+		// Ethereum accounts are not valid on Polkadot SDK chains.
+		assert!(command
+			.configure_transfer(
+				Chain::AssetHubPolkadot,
+				Chain::AssetHubKusama,
+				TransferType::Reserve,
+				&[],
+				&mut cli
+			)
+			.is_err());
+		cli.verify()
+	}
+
+	#[test]
+	fn configure_transfer_works() -> Result<()> {
+		let usdt = RegisteredAsset {
+			id: AssetId::Index(1984),
+			name: "Tether USD".into(),
+			symbol: "USDt".into(),
+			decimals: 6,
+			min_balance: 70_000,
+			location: Value::u128(1984),
+		};
+		let mut cli = MockCli::new()
+			.expect_select(
+				"Select the asset to transfer:",
+				Some(true),
+				true,
+				Some(vec![
+					("Relay chain token".into(), "PAS".into()),
+					("Native token".into(), "The native token of Asset Hub (Paseo)".into()),
+					("USDt".into(), "Tether USD (1984)".into()),
+				]),
+				2,
+			)
+			.expect_input("Enter the amount to transfer:", "10000000000".into())
+			.expect_input(
+				"Enter the account receiving the assets on Pop Network (Paseo):",
+				ALICE.into(),
+			);
+		let transfer = TransferCommand::default().configure_transfer(
+			Chain::AssetHubPaseo,
+			Chain::PopPaseo,
+			TransferType::Reserve,
+			std::slice::from_ref(&usdt),
+			&mut cli,
+		)?;
+		assert_eq!(
+			transfer,
+			Transfer {
+				source: Chain::AssetHubPaseo,
+				destination: Chain::PopPaseo,
+				transfer_type: TransferType::Reserve,
+				asset: Asset::Registered(usdt.location.clone()),
+				amount: 10_000_000_000,
+				beneficiary: Beneficiary::from_str(ALICE)?,
+			}
+		);
+		cli.verify()?;
+
+		// Assets can be specified by their symbol.
+		let command = TransferCommand {
+			asset: Some("usdt".into()),
+			amount: Some(1),
+			beneficiary: Some(ALICE.into()),
+			..Default::default()
+		};
+		let mut cli = MockCli::new();
+		let transfer = command.configure_transfer(
+			Chain::AssetHubPaseo,
+			Chain::PopPaseo,
+			TransferType::Reserve,
+			std::slice::from_ref(&usdt),
+			&mut cli,
+		)?;
+		assert_eq!(transfer.asset, Asset::Registered(usdt.location));
+		assert!(command
+			.configure_transfer(
+				Chain::AssetHubPaseo,
+				Chain::PopPaseo,
+				TransferType::Reserve,
+				&[],
+				&mut cli
+			)
+			.is_err());
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn transfer_fails_when_unsupported() -> Result<()> {
+		let mut cli = MockCli::new().expect_intro("Transfer assets via XCM").expect_outro_cancel(
+			"🚫 Unsupported transfer: a teleport is not supported from Paseo to Pop Network (Paseo)",
+		);
+		TransferCommand {
+			from: Some(Chain::Paseo),
+			to: Some(Chain::PopPaseo),
+			transfer_type: Some(TransferType::Teleport),
+			amount: Some(1),
+			beneficiary: Some(ALICE.into()),
+			..Default::default()
+		}
+		.execute(&mut cli)
+		.await?;
+		cli.verify()
+	}
+}
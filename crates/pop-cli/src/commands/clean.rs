@@ -4,13 +4,17 @@ use crate::{cli::traits::*, style::style};
 use anyhow::Result;
 use clap::{Args, Subcommand};
 use std::{
-	fs::{read_dir, remove_file},
-	path::PathBuf,
+	env::temp_dir,
+	fs::{read_dir, remove_dir_all, remove_file},
+	path::{Path, PathBuf},
 };
 
+/// The prefix of the directories in which zombienet stores the data of a network.
+const ZOMBIENET_PREFIX: &str = "zombie-";
+
 #[derive(Args)]
 #[command(args_conflicts_with_subcommands = true)]
-pub(crate) struct CleanArgs {
+pub struct CleanArgs {
 	#[command(subcommand)]
 	pub(crate) command: Command,
 }
@@ -21,6 +25,10 @@ pub(crate) enum Command {
 	/// Remove cached artifacts.
 	#[clap(alias = "c")]
 	Cache(CleanCommandArgs),
+	/// Remove the build artifacts of a project, generated chain specifications and the data of
+	/// local networks.
+	#[clap(alias = "p")]
+	Project(CleanProjectArgs),
 }
 
 #[derive(Args)]
@@ -30,6 +38,19 @@ pub struct CleanCommandArgs {
 	pub(crate) all: bool,
 }
 
+#[derive(Args)]
+pub struct CleanProjectArgs {
+	/// Path to the project [default: current directory].
+	#[arg(short, long)]
+	pub(crate) path: Option<PathBuf>,
+	/// Pass flag to remove all artifacts
+	#[arg(short, long)]
+	pub(crate) all: bool,
+	/// List the artifacts and their sizes, without removing them.
+	#[arg(long)]
+	pub(crate) dry_run: bool,
+}
+
 /// Removes cached artifacts.
 pub(crate) struct CleanCacheCommand<'a, CLI: Cli> {
 	/// The cli to be used.
@@ -67,7 +88,7 @@ impl<'a, CLI: Cli> CleanCacheCommand<'a, CLI> {
 			// Display all artifacts to be deleted and get confirmation
 			let list = style(format!(
 				"\n{}",
-				&contents
+				contents
 					.iter()
 					.map(|(name, _, size)| format!("{} : {}MiB", name, size / 1_048_576))
 					.collect::<Vec<_>>()
@@ -132,6 +153,174 @@ impl<'a, CLI: Cli> CleanCacheCommand<'a, CLI> {
 	}
 }
 
+/// Removes the artifacts generated for a project.
+pub(crate) struct CleanProjectCommand<'a, CLI: Cli> {
+	/// The cli to be used.
+	pub(crate) cli: &'a mut CLI,
+	/// The project whose artifacts are removed.
+	pub(crate) project: PathBuf,
+	/// The directory in which zombienet stores the data of networks.
+	pub(crate) network_dir: PathBuf,
+	/// Whether to clean all artifacts.
+	pub(crate) all: bool,
+	/// Whether to only list the artifacts.
+	pub(crate) dry_run: bool,
+}
+
+impl<'a, CLI: Cli> CleanProjectCommand<'a, CLI> {
+	/// Initializes the command from its arguments.
+	pub(crate) fn new(cli: &'a mut CLI, args: CleanProjectArgs) -> Self {
+		Self {
+			cli,
+			project: args.path.unwrap_or_else(|| PathBuf::from("./")),
+			network_dir: temp_dir(),
+			all: args.all,
+			dry_run: args.dry_run,
+		}
+	}
+
+	/// Executes the command.
+	pub(crate) fn execute(self) -> Result<()> {
+		self.cli.intro("Remove project artifacts")?;
+
+		let artifacts = project_artifacts(&self.project, &self.network_dir)?;
+		if artifacts.is_empty() {
+			self.cli.outro(format!(
+				"ℹ️ No artifacts were found for the project at {}.",
+				self.project.display()
+			))?;
+			return Ok(());
+		}
+		let size: u64 = artifacts.iter().map(|(_, _, size)| size).sum();
+		let list = style(format!(
+			"\n{}",
+			artifacts
+				.iter()
+				.map(|(name, _, size)| format!("{} : {}MiB", name, size / 1_048_576))
+				.collect::<Vec<_>>()
+				.join("; \n")
+		))
+		.to_string();
+
+		if self.dry_run {
+			self.cli
+				.info(format!("The following artifacts would be removed...\n {list} \n"))?;
+			self.cli.outro(format!(
+				"ℹ️ {} artifacts totalling {}MiB would be removed",
+				artifacts.len(),
+				size / 1_048_576
+			))?;
+			return Ok(());
+		}
+
+		let selected: Vec<_> = if self.all {
+			if !self
+				.cli
+				.confirm(format!("Would you like to cleanup all project artifacts...\n {list} \n"))
+				.interact()?
+			{
+				self.cli.outro_cancel("ℹ️ No artifacts removed")?;
+				return Ok(());
+			}
+			artifacts.into_iter().map(|(_, path, _)| path).collect()
+		} else {
+			let selected = {
+				let mut prompt = self
+					.cli
+					.multiselect("Select the artifacts you wish to remove:")
+					.required(false);
+				for (name, path, size) in artifacts {
+					prompt = prompt.item(path, name, format!("{}MiB", size / 1_048_576))
+				}
+				prompt.interact()?
+			};
+			if selected.is_empty() {
+				self.cli.outro("ℹ️ No artifacts removed")?;
+				return Ok(());
+			};
+			let prompt = match selected.len() {
+				1 => "Are you sure you want to remove the selected artifact?".into(),
+				_ => format!(
+					"Are you sure you want to remove the {} selected artifacts?",
+					selected.len()
+				),
+			};
+			if !self.cli.confirm(prompt).interact()? {
+				self.cli.outro("ℹ️ No artifacts removed")?;
+				return Ok(());
+			}
+			selected
+		};
+
+		for path in &selected {
+			// Nested artifacts are already removed along with their parent directory.
+			match path.is_dir() {
+				true => remove_dir_all(path)?,
+				false if path.exists() => remove_file(path)?,
+				false => {},
+			}
+		}
+		self.cli.outro(format!("ℹ️ {} artifacts removed", selected.len()))?;
+		Ok(())
+	}
+}
+
+/// Returns the artifacts generated for a project: the output of contract builds, the build
+/// directory, chain specifications and genesis files generated within the project, and the data
+/// of local networks.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+/// * `network_dir` - The directory in which zombienet stores the data of networks.
+fn project_artifacts(project: &Path, network_dir: &Path) -> Result<Vec<(String, PathBuf, u64)>> {
+	let mut artifacts = Vec::new();
+	// Contract artifacts are listed ahead of the build directory which contains them.
+	for dir in ["target/ink", "target"] {
+		let path = project.join(dir);
+		if path.is_dir() {
+			artifacts.push((dir.to_string(), path.clone(), size(&path)));
+		}
+	}
+	for (name, path, size) in contents(&project.to_path_buf())? {
+		if path.is_file() && is_chain_spec_artifact(&name) {
+			artifacts.push((name, path, size));
+		}
+	}
+	if network_dir.is_dir() {
+		for (name, path, _) in contents(&network_dir.to_path_buf())? {
+			if path.is_dir() && name.starts_with(ZOMBIENET_PREFIX) {
+				artifacts.push((format!("network data: {name}"), path.clone(), size(&path)));
+			}
+		}
+	}
+	Ok(artifacts)
+}
+
+// Whether a file is a chain specification or genesis file, as generated by `pop build spec` and
+// `pop up network`.
+fn is_chain_spec_artifact(name: &str) -> bool {
+	let chain_spec = name.ends_with(".json") &&
+		(name.contains("chain-spec") ||
+			name.contains("chainspec") ||
+			name.ends_with("-raw.json"));
+	let genesis =
+		name.starts_with("para-") && (name.ends_with("-genesis-state") || name.ends_with(".wasm"));
+	chain_spec || genesis
+}
+
+// The total size of the files within a directory.
+fn size(path: &Path) -> u64 {
+	read_dir(path)
+		.into_iter()
+		.flatten()
+		.filter_map(|e| e.ok())
+		.filter_map(|e| {
+			let metadata = e.metadata().ok()?;
+			Some(if metadata.is_dir() { size(&e.path()) } else { metadata.len() })
+		})
+		.sum()
+}
+
 /// Returns the contents of the specified path.
 fn contents(path: &PathBuf) -> Result<Vec<(String, PathBuf, u64)>> {
 	let mut contents: Vec<_> = read_dir(path)?
@@ -154,7 +343,7 @@ fn contents(path: &PathBuf) -> Result<Vec<(String, PathBuf, u64)>> {
 mod tests {
 	use super::*;
 	use crate::cli::MockCli;
-	use std::fs::File;
+	use std::fs::{create_dir_all, write, File};
 
 	#[test]
 	fn clean_cache_has_intro() -> Result<()> {
@@ -366,6 +555,175 @@ mod tests {
 		cli.verify()
 	}
 
+	// Creates a project with build artifacts, generated chain specifications and network data.
+	fn project() -> Result<(tempfile::TempDir, PathBuf, PathBuf)> {
+		let temp = tempfile::tempdir()?;
+		let project = temp.path().join("project");
+		let network_dir = temp.path().join("tmp");
+		create_dir_all(project.join("target/ink"))?;
+		create_dir_all(project.join("target/release"))?;
+		write(project.join("target/ink/flipper.contract"), vec![0; 1_048_576])?;
+		write(project.join("target/release/node"), vec![0; 1_048_576])?;
+		for file in
+			["chain-spec.json", "chain-spec-raw.json", "para-2000-genesis-state", "README.md"]
+		{
+			File::create(project.join(file))?;
+		}
+		create_dir_all(project.join("runtime"))?;
+		create_dir_all(network_dir.join("zombie-5de7b2e4/alice"))?;
+		create_dir_all(network_dir.join("other"))?;
+		Ok((temp, project, network_dir))
+	}
+
+	#[test]
+	fn project_artifacts_works() -> Result<()> {
+		let (_temp, project, network_dir) = project()?;
+		let artifacts = project_artifacts(&project, &network_dir)?;
+		assert_eq!(
+			artifacts,
+			vec![
+				("target/ink".to_string(), project.join("target/ink"), 1_048_576),
+				("target".to_string(), project.join("target"), 2 * 1_048_576),
+				("chain-spec-raw.json".to_string(), project.join("chain-spec-raw.json"), 0),
+				("chain-spec.json".to_string(), project.join("chain-spec.json"), 0),
+				("para-2000-genesis-state".to_string(), project.join("para-2000-genesis-state"), 0),
+				(
+					"network data: zombie-5de7b2e4".to_string(),
+					network_dir.join("zombie-5de7b2e4"),
+					0
+				),
+			]
+		);
+		assert!(project_artifacts(&project.join("runtime"), &project.join("missing"))?.is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn is_chain_spec_artifact_works() {
+		for name in
+			["chain-spec.json", "raw-parachain-chainspec.json", "spec-raw.json", "para-2000.wasm"]
+		{
+			assert!(is_chain_spec_artifact(name), "{name}");
+		}
+		for name in ["package.json", "network.toml", "runtime.wasm", "para-2000.json"] {
+			assert!(!is_chain_spec_artifact(name), "{name}");
+		}
+	}
+
+	#[test]
+	fn clean_project_args_works() -> Result<()> {
+		use clap::Parser;
+		let pop =
+			crate::Cli::try_parse_from(["pop", "clean", "project", "-p", "./chain", "--dry-run"])?;
+		let crate::commands::Command::Clean(args) = pop.command else {
+			panic!("expected the clean command");
+		};
+		let Command::Project(args) = args.command else {
+			panic!("expected the project clean command");
+		};
+		assert_eq!(args.path, Some(PathBuf::from("./chain")));
+		assert!(args.dry_run && !args.all);
+		Ok(())
+	}
+
+	#[test]
+	fn clean_project_handles_no_artifacts() -> Result<()> {
+		let temp = tempfile::tempdir()?;
+		let project = temp.path().to_path_buf();
+		let mut cli = MockCli::new().expect_intro("Remove project artifacts").expect_outro(
+			format!("ℹ️ No artifacts were found for the project at {}.", project.display()),
+		);
+
+		CleanProjectCommand {
+			cli: &mut cli,
+			network_dir: project.join("tmp"),
+			project,
+			all: false,
+			dry_run: false,
+		}
+		.execute()?;
+
+		cli.verify()
+	}
+
+	#[test]
+	fn clean_project_dry_run_removes_nothing() -> Result<()> {
+		let (_temp, project, network_dir) = project()?;
+		let list = style(
+			"\ntarget/ink : 1MiB; \ntarget : 2MiB; \nchain-spec-raw.json : 0MiB; \nchain-spec.json : 0MiB; \npara-2000-genesis-state : 0MiB; \nnetwork data: zombie-5de7b2e4 : 0MiB",
+		)
+		.to_string();
+		let mut cli = MockCli::new()
+			.expect_info(format!("The following artifacts would be removed...\n {list} \n"))
+			.expect_outro("ℹ️ 6 artifacts totalling 3MiB would be removed");
+
+		CleanProjectCommand {
+			cli: &mut cli,
+			project: project.clone(),
+			network_dir: network_dir.clone(),
+			all: false,
+			dry_run: true,
+		}
+		.execute()?;
+
+		assert_eq!(project_artifacts(&project, &network_dir)?.len(), 6);
+		cli.verify()
+	}
+
+	#[test]
+	fn clean_project_removes_selection() -> Result<()> {
+		let (_temp, project, network_dir) = project()?;
+		let mut cli = MockCli::new()
+			.expect_multiselect::<PathBuf>(
+				"Select the artifacts you wish to remove:",
+				Some(false),
+				true,
+				None,
+			)
+			.expect_confirm("Are you sure you want to remove the 6 selected artifacts?", true)
+			.expect_outro("ℹ️ 6 artifacts removed");
+
+		CleanProjectCommand {
+			cli: &mut cli,
+			project: project.clone(),
+			network_dir: network_dir.clone(),
+			all: false,
+			dry_run: false,
+		}
+		.execute()?;
+
+		assert!(project_artifacts(&project, &network_dir)?.is_empty());
+		assert!(project.join("README.md").exists() && network_dir.join("other").exists());
+		cli.verify()
+	}
+
+	#[test]
+	fn clean_project_all_removes_nothing_if_unconfirmed() -> Result<()> {
+		let (_temp, project, network_dir) = project()?;
+		let list = style(
+			"\ntarget/ink : 1MiB; \ntarget : 2MiB; \nchain-spec-raw.json : 0MiB; \nchain-spec.json : 0MiB; \npara-2000-genesis-state : 0MiB; \nnetwork data: zombie-5de7b2e4 : 0MiB",
+		)
+		.to_string();
+		let mut cli = MockCli::new()
+			.expect_confirm(
+				format!("Would you like to cleanup all project artifacts...\n {list} \n"),
+				false,
+			)
+			.expect_outro_cancel("ℹ️ No artifacts removed");
+
+		CleanProjectCommand {
+			cli: &mut cli,
+			project: project.clone(),
+			network_dir: network_dir.clone(),
+			all: true,
+			dry_run: false,
+		}
+		.execute()?;
+
+		assert_eq!(project_artifacts(&project, &network_dir)?.len(), 6);
+		cli.verify()
+	}
+
 	#[test]
 	fn contents_works() -> Result<()> {
 		use std::fs::File;
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, ChainArgs, SignerArgs};
+use crate::cli::traits::*;
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_parachains::{assign, Finality, RegionId, COMPLETE_MASK};
+use sp_core::bytes::from_hex;
+
+/// Command to assign a region of bulk coretime to a parachain.
+#[derive(Args, Default)]
+pub(crate) struct AssignCommand {
+	/// The timeslice from which the region begins.
+	#[arg(long)]
+	pub(crate) begin: u32,
+	/// The index of the core of the region.
+	#[arg(long)]
+	pub(crate) core: u16,
+	/// The parts of the core within the region, as a 10-byte hex mask [default: the complete
+	/// core].
+	#[arg(long)]
+	pub(crate) mask: Option<String>,
+	/// The parachain to which the region is assigned.
+	#[arg(long)]
+	pub(crate) para: u32,
+	/// Assigns the region provisionally, so it may be reassigned but not renewed.
+	#[arg(long)]
+	pub(crate) provisional: bool,
+	#[command(flatten)]
+	pub(crate) chain: ChainArgs,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl AssignCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Assign bulk coretime")?;
+		let region = match self.region() {
+			Ok(region) => region,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		};
+		let (name, url) = self.chain.configure(cli)?;
+		let client = connect(&url).await?;
+		let finality = match self.provisional {
+			true => Finality::Provisional,
+			false => Finality::Final,
+		};
+		if !self
+			.signer
+			.submit(&client, &url, assign(&region, self.para, finality), cli)
+			.await?
+		{
+			return Ok(());
+		}
+		cli.outro(format!(
+			"Region of core {} from timeslice {} assigned to parachain {} on {name}.",
+			region.core, region.begin, self.para
+		))?;
+		Ok(())
+	}
+
+	// The region to be assigned.
+	fn region(&self) -> Result<RegionId> {
+		let mask = match &self.mask {
+			Some(mask) => from_hex(mask)
+				.ok()
+				.and_then(|m| m.try_into().ok())
+				.ok_or_else(|| anyhow!("The mask {mask} is not a valid 10-byte hex value."))?,
+			None => COMPLETE_MASK,
+		};
+		Ok(RegionId { begin: self.begin, core: self.core, mask })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn assign_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"coretime",
+			"assign",
+			"--begin",
+			"1000",
+			"--core",
+			"3",
+			"--para",
+			"4001",
+			"--provisional",
+		])?;
+		let crate::commands::Command::Coretime(args) = pop.command else {
+			panic!("expected the coretime command");
+		};
+		let super::super::Command::Assign(command) = args.command else {
+			panic!("expected the assign command");
+		};
+		assert_eq!((command.begin, command.core, command.para), (1_000, 3, 4001));
+		assert!(command.provisional);
+		// The region and parachain are required.
+		assert!(
+			crate::Cli::try_parse_from(["pop", "coretime", "assign", "--para", "4001"]).is_err()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn region_works() -> Result<()> {
+		let command = |mask: Option<&str>| AssignCommand {
+			begin: 1_000,
+			core: 3,
+			mask: mask.map(String::from),
+			..Default::default()
+		};
+		assert_eq!(command(None).region()?, RegionId { begin: 1_000, core: 3, mask: [0xFF; 10] });
+		assert_eq!(
+			command(Some("0xffffffffff0000000000")).region()?.mask,
+			[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 0]
+		);
+		assert_eq!(
+			command(Some("0xff")).region().unwrap_err().to_string(),
+			"The mask 0xff is not a valid 10-byte hex value."
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	cli::traits::*,
+	common::{network, secrets::prompt_suri},
+};
+use anyhow::{anyhow, Result};
+use clap::{
+	builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+	Args, Subcommand,
+};
+use pop_common::enum_variants;
+use pop_parachains::{
+	encode_call_data, set_up_client, sign_and_submit_extrinsic, Chain, DynamicPayload,
+	OnlineClient, SubstrateConfig,
+};
+use std::str::FromStr;
+use strum::VariantArray;
+use url::Url;
+
+pub(crate) mod assign;
+pub(crate) mod purchase;
+pub(crate) mod renew;
+pub(crate) mod status;
+
+const DEFAULT_URI: &str = "//Alice";
+
+/// Arguments for managing bulk coretime.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct CoretimeArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Manage bulk coretime via the broker pallet of a coretime chain.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Purchase a region of bulk coretime in the current sale.
+	#[clap(alias = "p")]
+	Purchase(purchase::PurchaseCommand),
+	/// Renew the bulk coretime of a core for the next sale.
+	#[clap(alias = "r")]
+	Renew(renew::RenewCommand),
+	/// Assign a region of bulk coretime to a parachain.
+	#[clap(alias = "a")]
+	Assign(assign::AssignCommand),
+	/// Show the core assignments and renewal deadlines of a parachain.
+	#[clap(alias = "s")]
+	Status(status::StatusCommand),
+}
+
+/// Arguments for the coretime chain to be interacted with.
+#[derive(Args, Clone, Default)]
+pub(crate) struct ChainArgs {
+	/// The relay chain of which the coretime is managed, or its coretime chain.
+	#[arg(long, value_parser = enum_variants!(Chain))]
+	pub(crate) chain: Option<Chain>,
+	/// Websocket endpoint of a node of the coretime chain [default: from the registry].
+	#[arg(short, long, value_parser, conflicts_with = "chain")]
+	pub(crate) url: Option<Url>,
+}
+
+impl ChainArgs {
+	/// The name and endpoint of the coretime chain, prompting the user if none specified.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) fn configure(&self, cli: &mut impl Cli) -> Result<(String, Url)> {
+		if let Some(url) = &self.url {
+			let name = Chain::from_url(url.as_str())
+				.map(|c| c.name().to_string())
+				.unwrap_or_else(|| url.to_string());
+			return Ok((name, url.clone()));
+		}
+		let chain = match self.chain {
+			Some(chain) => chain
+				.coretime()
+				.ok_or_else(|| anyhow!("No coretime chain is known for {}.", chain.name()))?,
+			None => {
+				let mut prompt = cli.select("Select the coretime chain:");
+				for chain in Chain::VARIANTS.iter().filter(|c| c.coretime() == Some(**c)) {
+					prompt = prompt.item(*chain, chain.name(), chain.url());
+				}
+				prompt.interact()?
+			},
+		};
+		Ok((chain.name().to_string(), Url::parse(chain.url())?))
+	}
+}
+
+/// Arguments for signing and submitting an extrinsic.
+#[derive(Args, Clone, Default)]
+pub(crate) struct SignerArgs {
+	/// Secret key URI for the account signing the extrinsic.
+	#[arg(short, long)]
+	pub(crate) suri: Option<String>,
+	/// Automatically signs and submits the extrinsic without prompting for confirmation.
+	#[arg(short = 'y', long)]
+	pub(crate) skip_confirm: bool,
+}
+
+impl SignerArgs {
+	/// Signs and submits a call to the coretime chain, after confirming with the user. Returns
+	/// whether the call was submitted.
+	///
+	/// # Arguments
+	/// * `client` - The client used to interact with the coretime chain.
+	/// * `url` - The endpoint of the coretime chain.
+	/// * `xt` - The call to be submitted.
+	/// * `cli` - The cli.
+	pub(crate) async fn submit(
+		&self,
+		client: &OnlineClient<SubstrateConfig>,
+		url: &Url,
+		xt: DynamicPayload,
+		cli: &mut impl Cli,
+	) -> Result<bool> {
+		cli.info(format!("Encoded call data: {}", encode_call_data(client, &xt)?))?;
+		let suri = match &self.suri {
+			Some(suri) => suri.clone(),
+			None => prompt_suri("Signer of the extrinsic", DEFAULT_URI, cli)?,
+		};
+		if !self.skip_confirm &&
+			!cli.confirm("Do you want to submit the call?").initial_value(true).interact()?
+		{
+			cli.outro_cancel("Call was not submitted.")?;
+			return Ok(false);
+		}
+		let spinner = cli.spinner();
+		spinner.start("Signing and submitting the extrinsic and then waiting for finalization, please be patient...");
+		let result = network::once("submitting the extrinsic", async {
+			sign_and_submit_extrinsic(client, url, xt, &suri)
+				.await
+				.map_err(|err| anyhow!("{}", format!("{err:?}")))
+		})
+		.await?;
+		spinner.stop(result);
+		Ok(true)
+	}
+}
+
+/// Connects to the coretime chain.
+///
+/// # Arguments
+/// * `url` - The endpoint of the coretime chain.
+pub(crate) async fn connect(url: &Url) -> Result<OnlineClient<SubstrateConfig>> {
+	network::retry("connecting to the coretime chain", || set_up_client(url.as_str())).await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+
+	#[test]
+	fn configure_chain_works() -> Result<()> {
+		let mut cli = MockCli::new();
+		let args = |chain| ChainArgs { chain: Some(chain), url: None };
+		assert_eq!(
+			args(Chain::Paseo).configure(&mut cli)?,
+			("Coretime (Paseo)".to_string(), Url::parse(Chain::CoretimePaseo.url())?)
+		);
+		assert_eq!(
+			args(Chain::AssetHubKusama).configure(&mut cli)?,
+			("Coretime (Kusama)".to_string(), Url::parse(Chain::CoretimeKusama.url())?)
+		);
+		assert_eq!(
+			args(Chain::Ethereum).configure(&mut cli).unwrap_err().to_string(),
+			"No coretime chain is known for Ethereum."
+		);
+		let url = Url::parse("ws://localhost:9944")?;
+		assert_eq!(
+			ChainArgs { chain: None, url: Some(url.clone()) }.configure(&mut cli)?,
+			(url.to_string(), url)
+		);
+		cli.verify()?;
+
+		let chains = [
+			Chain::CoretimePolkadot,
+			Chain::CoretimeKusama,
+			Chain::CoretimePaseo,
+			Chain::CoretimeWestend,
+		];
+		let items = chains.iter().map(|c| (c.name().to_string(), c.url().to_string())).collect();
+		let mut cli = MockCli::new().expect_select(
+			"Select the coretime chain:",
+			Some(true),
+			true,
+			Some(items),
+			3,
+		);
+		assert_eq!(
+			ChainArgs::default().configure(&mut cli)?,
+			("Coretime (Westend)".to_string(), Url::parse(Chain::CoretimeWestend.url())?)
+		);
+		cli.verify()
+	}
+}
@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, ChainArgs, SignerArgs};
+use crate::cli::traits::*;
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{purchase, sale_price};
+
+/// Command to purchase a region of bulk coretime.
+#[derive(Args, Default)]
+pub(crate) struct PurchaseCommand {
+	/// The maximum price to be paid for the region, in the smallest unit of the token [default:
+	/// prompted, defaulting to the current price].
+	#[arg(long)]
+	pub(crate) price_limit: Option<u128>,
+	#[command(flatten)]
+	pub(crate) chain: ChainArgs,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl PurchaseCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Purchase bulk coretime")?;
+		let (name, url) = self.chain.configure(cli)?;
+		let client = connect(&url).await?;
+		let price_limit = match self.price_limit {
+			Some(limit) => limit,
+			None => {
+				let mut prompt = cli
+					.input("Enter the maximum price to pay for the region:")
+					.placeholder("The price in the smallest unit of the token")
+					.validate(|input| match input.parse::<u128>() {
+						Ok(_) => Ok(()),
+						_ => Err("Invalid price."),
+					});
+				if let Some(price) = sale_price(&client).await.ok().flatten() {
+					prompt = prompt.default_input(&price.to_string());
+				}
+				prompt.interact()?.parse()?
+			},
+		};
+		if !self.signer.submit(&client, &url, purchase(price_limit), cli).await? {
+			return Ok(());
+		}
+		cli.outro(format!(
+			"Region purchased on {name}: assign it to a parachain with `pop coretime assign`."
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+	use pop_parachains::Chain;
+
+	#[test]
+	fn purchase_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"coretime",
+			"purchase",
+			"--price-limit",
+			"1000",
+			"--chain",
+			"paseo",
+			"-y",
+		])?;
+		let crate::commands::Command::Coretime(args) = pop.command else {
+			panic!("expected the coretime command");
+		};
+		let super::super::Command::Purchase(command) = args.command else {
+			panic!("expected the purchase command");
+		};
+		assert_eq!(command.price_limit, Some(1_000));
+		assert_eq!(command.chain.chain, Some(Chain::Paseo));
+		assert!(command.signer.skip_confirm);
+		// The chain and the endpoint are mutually exclusive.
+		assert!(crate::Cli::try_parse_from([
+			"pop",
+			"coretime",
+			"p",
+			"--chain",
+			"paseo",
+			"--url",
+			"ws://localhost:9944"
+		])
+		.is_err());
+		Ok(())
+	}
+}
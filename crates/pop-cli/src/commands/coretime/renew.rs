@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, ChainArgs, SignerArgs};
+use crate::cli::traits::*;
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{coretime_status, renew};
+
+/// Command to renew the bulk coretime of a core.
+#[derive(Args, Default)]
+pub(crate) struct RenewCommand {
+	/// The index of the core to be renewed.
+	#[arg(long, required_unless_present = "para")]
+	pub(crate) core: Option<u16>,
+	/// The parachain of which the earliest renewal is made, instead of specifying the core.
+	#[arg(long, conflicts_with = "core")]
+	pub(crate) para: Option<u32>,
+	#[command(flatten)]
+	pub(crate) chain: ChainArgs,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl RenewCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Renew bulk coretime")?;
+		let (name, url) = self.chain.configure(cli)?;
+		let client = connect(&url).await?;
+		let core = match (self.core, self.para) {
+			(Some(core), _) => core,
+			(None, Some(para_id)) => {
+				let spinner = cli.spinner();
+				spinner.start(format!("Querying the renewals of parachain {para_id}..."));
+				let status = coretime_status(&client, para_id).await?;
+				spinner.clear();
+				let Some(renewal) = status.renewals.first() else {
+					cli.outro_cancel(format!(
+						"🚫 No renewals are available to parachain {para_id} on {name}."
+					))?;
+					return Ok(());
+				};
+				cli.info(format!(
+					"Renewing core {} from timeslice {} for {}, in the smallest unit of the token.",
+					renewal.core, renewal.when, renewal.price
+				))?;
+				renewal.core
+			},
+			(None, None) => unreachable!("either the core or the parachain is required"),
+		};
+		if !self.signer.submit(&client, &url, renew(core), cli).await? {
+			return Ok(());
+		}
+		cli.outro(format!("Core {core} renewed on {name}."))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn renew_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "coretime", "renew"].iter().chain(args))?;
+			let crate::commands::Command::Coretime(args) = pop.command else {
+				panic!("expected the coretime command");
+			};
+			let super::super::Command::Renew(command) = args.command else {
+				panic!("expected the renew command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		assert_eq!(parse(&["--core", "3"])?.core, Some(3));
+		assert_eq!(parse(&["--para", "4001"])?.para, Some(4001));
+		// Either the core or the parachain is required, but not both.
+		assert!(parse(&[]).is_err());
+		assert!(parse(&["--core", "3", "--para", "4001"]).is_err());
+		Ok(())
+	}
+}
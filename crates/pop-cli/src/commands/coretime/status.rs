@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, ChainArgs};
+use crate::cli::traits::*;
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{coretime_status, CoretimeStatus};
+
+/// Command to show the bulk coretime of a parachain.
+#[derive(Args, Default)]
+pub(crate) struct StatusCommand {
+	/// The identifier of the parachain.
+	pub(crate) para: u32,
+	#[command(flatten)]
+	pub(crate) chain: ChainArgs,
+}
+
+impl StatusCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Query bulk coretime")?;
+		let (name, url) = self.chain.configure(cli)?;
+		let client = connect(&url).await?;
+		let spinner = cli.spinner();
+		spinner.start(format!("Querying the coretime of parachain {} on {name}...", self.para));
+		let status = coretime_status(&client, self.para).await;
+		spinner.clear();
+		match status {
+			Ok(status) => cli.info(describe(&name, &status))?,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 The coretime could not be queried: {e}"))?;
+				return Ok(());
+			},
+		}
+		cli.outro("Query complete.")?;
+		Ok(())
+	}
+}
+
+// Describes the bulk coretime of a parachain, with timeslices also converted to relay chain
+// blocks.
+fn describe(name: &str, status: &CoretimeStatus) -> String {
+	let block = |timeslice: u32| timeslice.saturating_mul(status.timeslice_period);
+	let mut output = format!("Coretime of parachain {} on {name}:", status.para_id);
+	let cores = match status.cores.is_empty() {
+		true => "none".to_string(),
+		false => status.cores.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", "),
+	};
+	output.push_str(&format!("\n  Assigned cores: {cores}"));
+	match status.renewals.is_empty() {
+		true => output.push_str("\n  Renewals: none"),
+		false => {
+			output.push_str("\n  Renewals:");
+			for renewal in &status.renewals {
+				output.push_str(&format!(
+					"\n    core {} for {}, renewable until timeslice {} (relay block {})",
+					renewal.core,
+					renewal.price,
+					renewal.when,
+					block(renewal.when)
+				));
+			}
+		},
+	}
+	if let Some(sale) = &status.sale {
+		let price = match status.price {
+			Some(price) => format!("currently {price}"),
+			None => format!("ending at {}", sale.end_price),
+		};
+		output.push_str(&format!(
+			"\n  Sale: {} of {} cores sold, priced {price}, for regions from timeslice {} to {} (relay blocks {} to {})",
+			sale.cores_sold,
+			sale.cores_offered,
+			sale.region_begin,
+			sale.region_end,
+			block(sale.region_begin),
+			block(sale.region_end)
+		));
+	}
+	output.push_str("\n  Prices are in the smallest unit of the token.");
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+	use pop_parachains::{Renewal, Sale};
+
+	#[test]
+	fn status_args_works() -> Result<()> {
+		let pop =
+			crate::Cli::try_parse_from(["pop", "coretime", "status", "4001", "--chain", "paseo"])?;
+		let crate::commands::Command::Coretime(args) = pop.command else {
+			panic!("expected the coretime command");
+		};
+		let super::super::Command::Status(command) = args.command else {
+			panic!("expected the status command");
+		};
+		assert_eq!(command.para, 4001);
+		assert!(crate::Cli::try_parse_from(["pop", "coretime", "status"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn describe_works() {
+		let mut status = CoretimeStatus {
+			para_id: 4001,
+			timeslice_period: 80,
+			sale: Some(Sale {
+				sale_start: 100,
+				leadin_length: 50,
+				end_price: 1_000,
+				region_begin: 20,
+				region_end: 30,
+				cores_offered: 5,
+				cores_sold: 2,
+			}),
+			price: Some(2_000),
+			cores: vec![3, 7],
+			renewals: vec![Renewal { core: 3, when: 20, price: 1_500 }],
+		};
+		assert_eq!(
+			describe("Coretime (Paseo)", &status),
+			"Coretime of parachain 4001 on Coretime (Paseo):\n  \
+			 Assigned cores: 3, 7\n  \
+			 Renewals:\n    \
+			 core 3 for 1500, renewable until timeslice 20 (relay block 1600)\n  \
+			 Sale: 2 of 5 cores sold, priced currently 2000, for regions from timeslice 20 to 30 (relay blocks 1600 to 2400)\n  \
+			 Prices are in the smallest unit of the token."
+		);
+		status.price = None;
+		status.cores.clear();
+		status.renewals.clear();
+		assert_eq!(
+			describe("Coretime (Paseo)", &status),
+			"Coretime of parachain 4001 on Coretime (Paseo):\n  \
+			 Assigned cores: none\n  \
+			 Renewals: none\n  \
+			 Sale: 2 of 5 cores sold, priced ending at 1000, for regions from timeslice 20 to 30 (relay blocks 1600 to 2400)\n  \
+			 Prices are in the smallest unit of the token."
+		);
+	}
+}
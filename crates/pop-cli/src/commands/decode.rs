@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::network};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use pop_parachains::{
+	decode_as_type, decode_call, decode_call_data, load_metadata, resolve_type, set_up_client,
+	Metadata, TypeRef,
+};
+use sp_core::bytes::from_hex;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+/// Arguments for decoding a value, or the call data of a call.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct DecodeArgs {
+	#[command(subcommand)]
+	pub(crate) command: Option<Command>,
+	#[command(flatten)]
+	pub(crate) value: Option<DecodeValueCommand>,
+}
+
+/// Decode call data.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Decode hex-encoded call data, e.g. that of a governance referendum, as the pallet, function
+	/// and arguments of the call.
+	#[clap(alias = "c")]
+	CallData(DecodeCallDataCommand),
+}
+
+/// Decode a SCALE-encoded value as a type of the metadata of a chain.
+#[derive(Args)]
+pub(crate) struct DecodeValueCommand {
+	/// The hex-encoded value to be decoded.
+	pub(crate) value: String,
+	/// The type of the value, by its path, e.g. `pallet_balances::AccountData`, or by its
+	/// identifier within the metadata.
+	#[arg(short, long = "type", value_parser = |s: &str| s.parse::<TypeRef>().map_err(|_| "expected a type path or identifier"))]
+	pub(crate) ty: TypeRef,
+	/// Websocket endpoint of a node of the chain from which the metadata is fetched.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	/// A file containing the SCALE-encoded metadata of the runtime, instead of fetching it from
+	/// a chain.
+	#[arg(short, long, conflicts_with = "url")]
+	pub(crate) metadata: Option<PathBuf>,
+}
+
+impl DecodeValueCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Decode value")?;
+		let Ok(bytes) = from_hex(&self.value) else {
+			cli.outro_cancel(format!("🚫 {} is not a valid hex value.", self.value))?;
+			return Ok(());
+		};
+		let metadata = fetch_metadata(&self.url, self.metadata.as_deref(), cli).await?;
+		let registry = metadata.types();
+		let decoded = resolve_type(registry, &self.ty).and_then(|id| {
+			let path = registry.resolve(id).map(|t| t.path.to_string()).unwrap_or_default();
+			Ok((id, path, decode_as_type(registry, id, &bytes)?))
+		});
+		match decoded {
+			Ok((id, path, value)) => {
+				cli.info(value)?;
+				cli.outro(format!("Decoded as {path} (type {id})."))?;
+			},
+			Err(e) => cli.outro_cancel(format!("🚫 {e}"))?,
+		}
+		Ok(())
+	}
+}
+
+/// Decode hex-encoded call data as a call of a dispatchable function.
+#[derive(Args)]
+pub(crate) struct DecodeCallDataCommand {
+	/// The hex-encoded call data to be decoded, e.g. as output by `pop call chain`.
+	pub(crate) call_data: String,
+	/// Websocket endpoint of a node of the chain from which the metadata is fetched.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	/// A file containing the SCALE-encoded metadata of the runtime, instead of fetching it from
+	/// a chain.
+	#[arg(short, long, conflicts_with = "url")]
+	pub(crate) metadata: Option<PathBuf>,
+}
+
+impl DecodeCallDataCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Decode call data")?;
+		let Ok(bytes) = decode_call_data(&self.call_data) else {
+			cli.outro_cancel(format!("🚫 {} is not valid call data.", self.call_data))?;
+			return Ok(());
+		};
+		let metadata = fetch_metadata(&self.url, self.metadata.as_deref(), cli).await?;
+		match decode_call(&metadata, &bytes) {
+			Ok(call) => {
+				cli.info(call.to_string())?;
+				cli.outro(format!("Decoded as a call of {}.{}.", call.pallet, call.function))?;
+			},
+			Err(e) => cli.outro_cancel(format!("🚫 {e}"))?,
+		}
+		Ok(())
+	}
+}
+
+// Loads the metadata of a runtime from a file when provided, otherwise fetching it from a chain.
+async fn fetch_metadata(url: &Url, path: Option<&Path>, cli: &mut impl Cli) -> Result<Metadata> {
+	if let Some(path) = path {
+		return Ok(load_metadata(path)?);
+	}
+	let spinner = cli.spinner();
+	spinner.start("Fetching the chain metadata...");
+	let client = network::retry("connecting to the chain", || set_up_client(url.as_str())).await?;
+	spinner.clear();
+	Ok(client.metadata())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+
+	#[test]
+	fn decode_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "decode"].iter().chain(args))?;
+			let crate::commands::Command::Decode(args) = pop.command else {
+				panic!("expected the decode command");
+			};
+			assert!(args.command.is_none());
+			Ok::<_, clap::Error>(args.value.expect("expected a value"))
+		};
+		let args = parse(&["0x1234", "--type", "pallet_balances::AccountData"])?;
+		assert_eq!(args.value, "0x1234");
+		assert_eq!(args.ty, TypeRef::Path("pallet_balances::AccountData".into()));
+		assert_eq!((args.url, args.metadata), (Url::parse(DEFAULT_URL)?, None));
+		let args = parse(&["0x1234", "-t", "42", "--metadata", "metadata.scale"])?;
+		assert_eq!(args.ty, TypeRef::Id(42));
+		assert_eq!(args.metadata, Some(PathBuf::from("metadata.scale")));
+		// The type is required, and the metadata is either fetched or loaded.
+		assert!(parse(&["0x1234"]).is_err());
+		assert!(
+			parse(&["0x1234", "-t", "42", "-u", "ws://localhost:8000", "-m", "m.scale"]).is_err()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn decode_call_data_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "decode"].iter().chain(args))?;
+			let crate::commands::Command::Decode(DecodeArgs {
+				command: Some(Command::CallData(command)),
+				..
+			}) = pop.command
+			else {
+				panic!("expected the decode call-data command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["call-data", "0x00000411"])?;
+		assert_eq!(command.call_data, "0x00000411");
+		assert_eq!((command.url, command.metadata), (Url::parse(DEFAULT_URL)?, None));
+		let command = parse(&["c", "0x00000411", "-m", "metadata.scale"])?;
+		assert_eq!(command.metadata, Some(PathBuf::from("metadata.scale")));
+		// The call data is required, without any type.
+		assert!(parse(&["call-data"]).is_err());
+		assert!(parse(&["call-data", "0x00000411", "-t", "42"]).is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn decode_invalid_hex_fails() -> Result<()> {
+		let args = DecodeValueCommand {
+			value: "0xzz".into(),
+			ty: TypeRef::Id(0),
+			url: Url::parse(DEFAULT_URL)?,
+			metadata: None,
+		};
+		let mut cli = MockCli::new()
+			.expect_intro("Decode value")
+			.expect_outro_cancel("🚫 0xzz is not a valid hex value.");
+		args.execute(&mut cli).await?;
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn decode_invalid_call_data_fails() -> Result<()> {
+		let command = DecodeCallDataCommand {
+			call_data: "0xzz".into(),
+			url: Url::parse(DEFAULT_URL)?,
+			metadata: None,
+		};
+		let mut cli = MockCli::new()
+			.expect_intro("Decode call data")
+			.expect_outro_cancel("🚫 0xzz is not valid call data.");
+		command.execute(&mut cli).await?;
+		cli.verify()
+	}
+}
@@ -0,0 +1,336 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use pop_common::{
+	config::{ProjectConfig, PROJECT_CONFIG_FILE_NAME},
+	deploy::{check_deployment, deploy, record_deployment, Deployment, Target},
+};
+use std::{
+	env::current_dir,
+	path::{Path, PathBuf},
+};
+use url::Url;
+
+/// The default name of a deployment.
+const DEFAULT_NAME: &str = "production";
+/// The environment variable from which the key used to authenticate with the API of a provider
+/// is read.
+const API_KEY_ENV: &str = "POP_DEPLOY_API_KEY";
+
+#[derive(Args)]
+pub struct DeployArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Deploy the artifacts of a project to hosted infrastructure.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Copy the artifacts to a host via SSH, optionally running a command on the host afterwards.
+	Ssh(SshCommand),
+	/// Run a node image via Docker, with the artifacts mounted.
+	Docker(DockerCommand),
+	/// Hand the artifacts off to a hosted infrastructure provider via its API.
+	Provider(ProviderCommand),
+	/// Show the current status of the deployments recorded in `pop.toml`.
+	Status(StatusCommand),
+}
+
+/// The arguments common to each deployment.
+#[derive(Args)]
+pub(crate) struct DeploymentArgs {
+	/// The artifacts to be deployed, e.g. a chain spec, runtime or contract bundle.
+	#[arg(required = true)]
+	pub(crate) artifacts: Vec<PathBuf>,
+	/// The name of the deployment, whose endpoint is recorded as the environment of the same name
+	/// in `pop.toml`.
+	#[arg(short, long, default_value = DEFAULT_NAME)]
+	pub(crate) name: String,
+}
+
+#[derive(Args)]
+pub(crate) struct SshCommand {
+	#[command(flatten)]
+	pub(crate) deployment: DeploymentArgs,
+	/// The host, e.g. `user@example.com`.
+	#[arg(long)]
+	pub(crate) host: String,
+	/// The directory on the host to which the artifacts are copied.
+	#[arg(long, default_value = ".")]
+	pub(crate) path: String,
+	/// A command run on the host once the artifacts are copied, e.g. to restart a node.
+	#[arg(name = "cmd", long)]
+	pub(crate) command: Option<String>,
+	/// The endpoint of the deployment, e.g. `wss://rpc.example.com`.
+	#[arg(long)]
+	pub(crate) endpoint: Option<String>,
+}
+
+#[derive(Args)]
+pub(crate) struct DockerCommand {
+	#[command(flatten)]
+	pub(crate) deployment: DeploymentArgs,
+	/// The node image, e.g. `parity/polkadot-parachain`.
+	#[arg(long)]
+	pub(crate) image: String,
+	/// The port on which the RPC server of the node is published.
+	#[arg(long, default_value_t = 9944)]
+	pub(crate) port: u16,
+}
+
+#[derive(Args)]
+pub(crate) struct ProviderCommand {
+	#[command(flatten)]
+	pub(crate) deployment: DeploymentArgs,
+	/// The base URL of the API of the provider.
+	#[arg(long)]
+	pub(crate) api: Url,
+	/// The key used to authenticate with the API of the provider.
+	#[arg(long, env = API_KEY_ENV)]
+	pub(crate) api_key: Option<String>,
+}
+
+#[derive(Args)]
+pub(crate) struct StatusCommand {
+	/// The name of the deployment [default: all deployments].
+	pub(crate) name: Option<String>,
+	/// The key used to authenticate with the API of a provider.
+	#[arg(long, env = API_KEY_ENV)]
+	pub(crate) api_key: Option<String>,
+}
+
+impl SshCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		let SshCommand { deployment, host, path, command, endpoint } = self;
+		let target = Target::Ssh { host, path, command, endpoint };
+		deploy_to(&config_path()?, deployment, target, cli).await
+	}
+}
+
+impl DockerCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		let target = Target::Docker { image: self.image, port: self.port };
+		deploy_to(&config_path()?, self.deployment, target, cli).await
+	}
+}
+
+impl ProviderCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		let target = Target::Provider { api: self.api, api_key: self.api_key };
+		deploy_to(&config_path()?, self.deployment, target, cli).await
+	}
+}
+
+impl StatusCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		self.report(&config_path()?, cli).await
+	}
+
+	/// Checks the status of the deployments recorded within a project configuration file,
+	/// updating their records.
+	///
+	/// # Arguments
+	/// * `path` - The path to the project configuration file.
+	/// * `cli` - The cli.
+	async fn report(self, path: &Path, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Check the status of deployments")?;
+		let config = ProjectConfig::load(path)?;
+		let deployments: Vec<_> = config
+			.deployments
+			.into_iter()
+			.filter(|(name, _)| self.name.iter().all(|n| n == name))
+			.collect();
+		if deployments.is_empty() {
+			cli.outro_cancel(match &self.name {
+				Some(name) => format!("🚫 No deployment named `{name}` is recorded."),
+				None => "🚫 No deployments are recorded.".into(),
+			})?;
+			return Ok(());
+		}
+		let spinner = cli.spinner();
+		spinner.start("Checking the status of deployments...");
+		let mut lines = vec![];
+		for (name, deployment) in deployments {
+			let deployment = match check_deployment(&deployment, self.api_key.as_deref()).await {
+				Ok(checked) => {
+					record_deployment(path, &name, &checked)?;
+					checked
+				},
+				Err(e) => {
+					cli.warning(format!("⚠️ The status of `{name}` could not be checked: {e}"))?;
+					deployment
+				},
+			};
+			lines.push(describe(&name, &deployment));
+		}
+		spinner.clear();
+		cli.info(lines.join("\n"))?;
+		cli.outro("✅ Deployment status checked.")?;
+		Ok(())
+	}
+}
+
+// Deploys artifacts to a target, recording the deployment within the project configuration file.
+async fn deploy_to(
+	path: &Path,
+	args: DeploymentArgs,
+	target: Target,
+	cli: &mut impl Cli,
+) -> Result<()> {
+	let DeploymentArgs { artifacts, name } = args;
+	cli.intro(format!("Deploy `{name}`"))?;
+	let spinner = cli.spinner();
+	spinner.start(format!("Deploying {} artifact(s)...", artifacts.len()));
+	let deployment = match deploy(&name, &target, &artifacts).await {
+		Ok(deployment) => deployment,
+		Err(e) => {
+			spinner.error(format!("🚫 {e}"));
+			cli.outro_cancel("🚫 The deployment failed.")?;
+			return Ok(());
+		},
+	};
+	record_deployment(path, &name, &deployment)?;
+	spinner.stop(describe(&name, &deployment));
+	cli.outro(match deployment.endpoint {
+		Some(_) => format!(
+			"✅ Deployment recorded in {PROJECT_CONFIG_FILE_NAME}. Use `--env {name}` to run commands against it."
+		),
+		None => format!("✅ Deployment recorded in {PROJECT_CONFIG_FILE_NAME}."),
+	})?;
+	Ok(())
+}
+
+// The path to the project configuration file of the current directory.
+fn config_path() -> Result<PathBuf> {
+	Ok(current_dir()?.join(PROJECT_CONFIG_FILE_NAME))
+}
+
+// A description of a deployment.
+fn describe(name: &str, deployment: &Deployment) -> String {
+	let mut description = format!("{name}: {} ({})", deployment.status, deployment.target);
+	if let Some(endpoint) = &deployment.endpoint {
+		description.push_str(&format!(" at {endpoint}"));
+	}
+	description
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use pop_common::deploy::DeploymentStatus;
+	use std::fs;
+
+	fn parse(args: &[&str]) -> Result<Command> {
+		let pop = crate::Cli::try_parse_from(["pop", "deploy"].iter().chain(args))?;
+		let crate::commands::Command::Deploy(args) = pop.command else {
+			panic!("expected the deploy command");
+		};
+		Ok(args.command)
+	}
+
+	#[test]
+	fn deploy_args_work() -> Result<()> {
+		let Command::Docker(command) =
+			parse(&["docker", "chain-spec-raw.json", "--image", "node", "-n", "staging"])?
+		else {
+			panic!("expected the docker command");
+		};
+		assert_eq!(command.deployment.artifacts, vec![PathBuf::from("chain-spec-raw.json")]);
+		assert_eq!(command.deployment.name, "staging");
+		assert_eq!((command.image.as_str(), command.port), ("node", 9944));
+		let Command::Ssh(command) = parse(&[
+			"ssh",
+			"runtime.wasm",
+			"spec.json",
+			"--host",
+			"user@example.com",
+			"--cmd",
+			"systemctl restart node",
+		])?
+		else {
+			panic!("expected the ssh command");
+		};
+		assert_eq!(command.deployment.artifacts.len(), 2);
+		assert_eq!(command.deployment.name, DEFAULT_NAME);
+		assert_eq!((command.host.as_str(), command.path.as_str()), ("user@example.com", "."));
+		assert_eq!(command.command.as_deref(), Some("systemctl restart node"));
+		let Command::Provider(command) =
+			parse(&["provider", "flipper.contract", "--api", "https://api.example.com"])?
+		else {
+			panic!("expected the provider command");
+		};
+		assert_eq!(command.api.as_str(), "https://api.example.com/");
+		let Command::Status(command) = parse(&["status", "staging"])? else {
+			panic!("expected the status command");
+		};
+		assert_eq!(command.name.as_deref(), Some("staging"));
+		// Artifacts are required.
+		assert!(parse(&["docker", "--image", "node"]).is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn deploy_to_fails_with_missing_artifact() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join(PROJECT_CONFIG_FILE_NAME);
+		let args = DeploymentArgs {
+			artifacts: vec![temp_dir.path().join("missing.json")],
+			name: "staging".into(),
+		};
+		let mut cli = MockCli::new()
+			.expect_intro("Deploy `staging`")
+			.expect_outro_cancel("🚫 The deployment failed.");
+		deploy_to(&path, args, Target::Docker { image: "node".into(), port: 9944 }, &mut cli)
+			.await?;
+		assert!(!path.exists());
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn status_reports_recorded_deployments() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join(PROJECT_CONFIG_FILE_NAME);
+		let deployment = Deployment {
+			target: "ssh://user@example.com:.".into(),
+			status: DeploymentStatus::Running,
+			endpoint: Some("wss://rpc.example.com".into()),
+			..Default::default()
+		};
+		record_deployment(&path, "staging", &deployment)?;
+		let mut cli = MockCli::new()
+			.expect_intro("Check the status of deployments")
+			.expect_info("staging: running (ssh://user@example.com:.) at wss://rpc.example.com")
+			.expect_outro("✅ Deployment status checked.");
+		StatusCommand { name: None, api_key: None }.report(&path, &mut cli).await?;
+		cli.verify()?;
+
+		let mut cli = MockCli::new()
+			.expect_intro("Check the status of deployments")
+			.expect_outro_cancel("🚫 No deployment named `production` is recorded.");
+		StatusCommand { name: Some("production".into()), api_key: None }
+			.report(&path, &mut cli)
+			.await?;
+		assert!(fs::read_to_string(&path)?.contains("[deployments.staging]"));
+		cli.verify()
+	}
+}
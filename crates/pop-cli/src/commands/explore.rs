@@ -0,0 +1,443 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	cli::traits::*,
+	commands::call::chain::{prompt_for_param, CallChainCommand},
+	common::network,
+};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_parachains::{
+	parse_chain_metadata, set_up_client, CallBuilder, Function, MetadataV16, Pallet, PalletItem,
+	ViewFunction,
+};
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+/// The sections of a pallet which can be explored.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Section {
+	Calls,
+	Storage,
+	Constants,
+	Events,
+	ViewFunctions,
+	AssociatedTypes,
+}
+
+impl Section {
+	fn name(&self) -> &'static str {
+		match self {
+			Section::Calls => "Calls",
+			Section::Storage => "Storage",
+			Section::Constants => "Constants",
+			Section::Events => "Events",
+			Section::ViewFunctions => "View functions",
+			Section::AssociatedTypes => "Associated types",
+		}
+	}
+}
+
+/// What was selected while exploring, to be acted upon.
+#[derive(Debug, PartialEq)]
+enum Selection {
+	/// A dispatchable function, for which a call is to be composed.
+	Call(Function),
+	/// A view function, which is to be executed.
+	View(ViewFunction),
+}
+
+#[derive(Args)]
+pub struct ExploreArgs {
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+}
+
+impl ExploreArgs {
+	/// Executes the command, browsing the pallets of the chain until exited or a call is to be
+	/// composed.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Explore chain metadata")?;
+		let spinner = cli.spinner();
+		spinner.start("Fetching the chain metadata...");
+		let client =
+			network::retry("connecting to the chain", || set_up_client(self.url.as_str())).await?;
+		let mut pallets = parse_chain_metadata(&client)
+			.map_err(|e| anyhow!("Unable to fetch the chain metadata: {e}"))?;
+		// Metadata V16 is optional, so any failure to fetch it is ignored.
+		let metadata = MetadataV16::fetch(&client).await.ok().flatten();
+		if let Some(metadata) = &metadata {
+			metadata.apply(&mut pallets);
+		}
+		spinner.clear();
+		// Sort by name for display.
+		pallets.sort_by(|a, b| a.name.cmp(&b.name));
+		match explore(&pallets, cli)? {
+			Some(Selection::Call(function)) =>
+				CallChainCommand::compose(self.url, function.pallet, function.name)
+					.execute(cli)
+					.await,
+			Some(Selection::View(function)) => {
+				let metadata = metadata.expect("view functions are only declared by metadata V16");
+				let calls = CallBuilder::new(&client);
+				let mut args = Vec::new();
+				for param in &function.params {
+					args.push(prompt_for_param(cli, param, &calls)?);
+				}
+				let spinner = cli.spinner();
+				spinner.start("Executing the view function...");
+				let output = metadata.execute_view_function(&client, &function, args).await;
+				spinner.clear();
+				cli.info(format!("{}.{}: {}", function.pallet, function.name, output?))?;
+				cli.outro("Exploration complete.")?;
+				Ok(())
+			},
+			None => {
+				cli.outro("Exploration complete.")?;
+				Ok(())
+			},
+		}
+	}
+}
+
+// Browses pallets, returning any dispatchable function selected for composing a call or view
+// function selected for execution.
+fn explore(pallets: &[Pallet], cli: &mut impl Cli) -> Result<Option<Selection>> {
+	loop {
+		let mut prompt = cli.select("Select a pallet to explore:");
+		for pallet in pallets {
+			prompt = prompt.item(Some(pallet), &pallet.name, &pallet.docs);
+		}
+		let Some(pallet) = prompt.item(None, "Exit", "").interact()? else {
+			return Ok(None);
+		};
+		loop {
+			let mut prompt = cli.select(format!("Select what to explore within {}:", pallet.name));
+			for (section, count) in [
+				(Section::Calls, pallet.functions.len()),
+				(Section::Storage, pallet.storage.len()),
+				(Section::Constants, pallet.constants.len()),
+				(Section::Events, pallet.events.len()),
+				(Section::ViewFunctions, pallet.view_functions.len()),
+				(Section::AssociatedTypes, pallet.associated_types.len()),
+			] {
+				let hint = match count {
+					0 => continue,
+					1 => "1 item".to_string(),
+					count => format!("{count} items"),
+				};
+				prompt = prompt.item(Some(section), section.name(), hint);
+			}
+			let Some(section) = prompt.item(None, "Back", "").interact()? else { break };
+			if section == Section::Calls {
+				if let Some(function) = explore_calls(pallet, cli)? {
+					return Ok(Some(Selection::Call(function)));
+				}
+				continue;
+			}
+			if section == Section::ViewFunctions {
+				if let Some(function) = explore_view_functions(pallet, cli)? {
+					return Ok(Some(Selection::View(function)));
+				}
+				continue;
+			}
+			let items = match section {
+				Section::Storage => &pallet.storage,
+				Section::Constants => &pallet.constants,
+				Section::AssociatedTypes => &pallet.associated_types,
+				_ => &pallet.events,
+			};
+			loop {
+				let mut prompt = cli.select(format!(
+					"Select an item of {} to view:",
+					section.name().to_lowercase()
+				));
+				for item in items {
+					prompt = prompt.item(Some(item), &item.name, &item.type_name);
+				}
+				let Some(item) = prompt.item(None, "Back", "").interact()? else { break };
+				cli.info(describe_item(pallet, item))?;
+			}
+		}
+	}
+}
+
+// Browses the dispatchable functions of a pallet, returning any selected for composing a call.
+fn explore_calls(pallet: &Pallet, cli: &mut impl Cli) -> Result<Option<Function>> {
+	loop {
+		let mut prompt = cli.select("Select a function to view:");
+		for function in &pallet.functions {
+			prompt = prompt.item(Some(function), &function.name, &function.docs);
+		}
+		let Some(function) = prompt.item(None, "Back", "").interact()? else {
+			return Ok(None);
+		};
+		cli.info(describe_function(function))?;
+		if function.is_supported &&
+			cli.confirm(format!(
+				"Would you like to compose a call to {}.{}?",
+				function.pallet, function.name
+			))
+			.initial_value(false)
+			.interact()?
+		{
+			return Ok(Some(function.clone()));
+		}
+	}
+}
+
+// Browses the view functions of a pallet, returning any selected for execution.
+fn explore_view_functions(pallet: &Pallet, cli: &mut impl Cli) -> Result<Option<ViewFunction>> {
+	loop {
+		let mut prompt = cli.select("Select a view function to view:");
+		for function in &pallet.view_functions {
+			prompt = prompt.item(Some(function), &function.name, &function.output);
+		}
+		let Some(function) = prompt.item(None, "Back", "").interact()? else {
+			return Ok(None);
+		};
+		cli.info(describe_view_function(function))?;
+		if function.is_supported &&
+			cli.confirm(format!(
+				"Would you like to execute {}.{}?",
+				function.pallet, function.name
+			))
+			.initial_value(false)
+			.interact()?
+		{
+			return Ok(Some(function.clone()));
+		}
+	}
+}
+
+// Describes a dispatchable function, along with its parameters.
+fn describe_function(function: &Function) -> String {
+	let mut output = format!("{}.{}", function.pallet, function.name);
+	if let Some(deprecation) = &function.deprecation {
+		output.push_str(&format!("\n  ⚠️ {deprecation}"));
+	}
+	if !function.docs.is_empty() {
+		output.push_str(&format!("\n  {}", function.docs));
+	}
+	for param in &function.params {
+		let type_name = match param.is_optional {
+			true => format!("Option<{}>", param.type_name),
+			false => param.type_name.clone(),
+		};
+		output.push_str(&format!("\n  {}: {type_name}", param.name));
+	}
+	output
+}
+
+// Describes a view function, along with its parameters and output.
+fn describe_view_function(function: &ViewFunction) -> String {
+	let params: Vec<_> =
+		function.params.iter().map(|p| format!("{}: {}", p.name, p.type_name)).collect();
+	let mut output = format!(
+		"{}.{}({}) -> {}",
+		function.pallet,
+		function.name,
+		params.join(", "),
+		function.output
+	);
+	if let Some(deprecation) = &function.deprecation {
+		output.push_str(&format!("\n  ⚠️ {deprecation}"));
+	}
+	if !function.docs.is_empty() {
+		output.push_str(&format!("\n  {}", function.docs));
+	}
+	output
+}
+
+// Describes a storage item, constant, event or associated type, along with its type.
+fn describe_item(pallet: &Pallet, item: &PalletItem) -> String {
+	let mut output = format!("{}.{}", pallet.name, item.name);
+	if !item.type_name.is_empty() {
+		output.push_str(&format!(": {}", item.type_name));
+	}
+	if !item.docs.is_empty() {
+		output.push_str(&format!("\n  {}", item.docs));
+	}
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use pop_parachains::Param;
+
+	fn pallets() -> Vec<Pallet> {
+		vec![Pallet {
+			name: "System".into(),
+			docs: "".into(),
+			functions: vec![Function {
+				pallet: "System".into(),
+				name: "remark".into(),
+				docs: "Make some on-chain remark.".into(),
+				params: vec![Param {
+					name: "remark".into(),
+					type_name: "[u8]".into(),
+					..Default::default()
+				}],
+				is_supported: true,
+				..Default::default()
+			}],
+			storage: vec![PalletItem {
+				name: "Number".into(),
+				docs: "The current block number being processed.".into(),
+				type_name: "u32".into(),
+			}],
+			events: vec![PalletItem {
+				name: "Remarked".into(),
+				docs: "On on-chain remark happened.".into(),
+				type_name: "{ sender: T::AccountId, hash: T::Hash }".into(),
+			}],
+			..Default::default()
+		}]
+	}
+
+	#[test]
+	fn explore_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from(["pop", "explore"])?;
+		let crate::commands::Command::Explore(args) = pop.command else {
+			panic!("expected the explore command");
+		};
+		assert_eq!(args.url, Url::parse(DEFAULT_URL)?);
+		Ok(())
+	}
+
+	#[test]
+	fn explore_items_works() -> Result<()> {
+		let pallets = pallets();
+		let mut cli = MockCli::new()
+			.expect_select(
+				"Select a pallet to explore:",
+				Some(true),
+				true,
+				Some(vec![("System".into(), "".into()), ("Exit".into(), "".into())]),
+				0,
+			)
+			.expect_select(
+				"Select what to explore within System:",
+				Some(true),
+				true,
+				Some(vec![
+					("Calls".into(), "1 item".into()),
+					("Storage".into(), "1 item".into()),
+					("Events".into(), "1 item".into()),
+					("Back".into(), "".into()),
+				]),
+				1,
+			)
+			.expect_select("Select an item of storage to view:", Some(true), true, None, 0)
+			.expect_info(
+				"System.Number: u32\n  The current block number being processed.".to_string(),
+			)
+			.expect_select("Select an item of storage to view:", Some(true), true, None, 1)
+			.expect_select("Select what to explore within System:", Some(true), true, None, 3)
+			.expect_select("Select a pallet to explore:", Some(true), true, None, 1);
+		assert_eq!(explore(&pallets, &mut cli)?, None);
+		cli.verify()
+	}
+
+	#[test]
+	fn explore_calls_works() -> Result<()> {
+		let pallets = pallets();
+		let mut cli = MockCli::new()
+			.expect_select("Select a pallet to explore:", Some(true), true, None, 0)
+			.expect_select("Select what to explore within System:", Some(true), true, None, 0)
+			.expect_select(
+				"Select a function to view:",
+				Some(true),
+				true,
+				Some(vec![
+					("remark".into(), "Make some on-chain remark.".into()),
+					("Back".into(), "".into()),
+				]),
+				0,
+			)
+			.expect_info("System.remark\n  Make some on-chain remark.\n  remark: [u8]".to_string())
+			.expect_confirm("Would you like to compose a call to System.remark?", true);
+		assert_eq!(
+			explore(&pallets, &mut cli)?,
+			Some(Selection::Call(pallets[0].functions[0].clone()))
+		);
+		cli.verify()
+	}
+
+	#[test]
+	fn explore_view_functions_works() -> Result<()> {
+		let mut pallets = pallets();
+		pallets[0].view_functions = vec![ViewFunction {
+			pallet: "System".into(),
+			name: "account_nonce".into(),
+			docs: "The nonce of an account.".into(),
+			params: vec![Param {
+				name: "account".into(),
+				type_name: "AccountId32".into(),
+				..Default::default()
+			}],
+			output: "u32".into(),
+			deprecation: Some("Deprecated: Use `AccountNonceApi`".into()),
+			is_supported: true,
+		}];
+		pallets[0].associated_types = vec![PalletItem {
+			name: "Nonce".into(),
+			type_name: "u32".into(),
+			..Default::default()
+		}];
+		let mut cli = MockCli::new()
+			.expect_select("Select a pallet to explore:", Some(true), true, None, 0)
+			.expect_select(
+				"Select what to explore within System:",
+				Some(true),
+				true,
+				Some(vec![
+					("Calls".into(), "1 item".into()),
+					("Storage".into(), "1 item".into()),
+					("Events".into(), "1 item".into()),
+					("View functions".into(), "1 item".into()),
+					("Associated types".into(), "1 item".into()),
+					("Back".into(), "".into()),
+				]),
+				3,
+			)
+			.expect_select(
+				"Select a view function to view:",
+				Some(true),
+				true,
+				Some(vec![("account_nonce".into(), "u32".into()), ("Back".into(), "".into())]),
+				0,
+			)
+			.expect_info(
+				"System.account_nonce(account: AccountId32) -> u32\n  ⚠️ Deprecated: Use \
+				 `AccountNonceApi`\n  The nonce of an account."
+					.to_string(),
+			)
+			.expect_confirm("Would you like to execute System.account_nonce?", true);
+		assert_eq!(
+			explore(&pallets, &mut cli)?,
+			Some(Selection::View(pallets[0].view_functions[0].clone()))
+		);
+		cli.verify()
+	}
+
+	#[test]
+	fn describe_item_works() {
+		let pallets = pallets();
+		assert_eq!(
+			describe_item(&pallets[0], &pallets[0].events[0]),
+			"System.Remarked: { sender: T::AccountId, hash: T::Hash }\n  \
+			 On on-chain remark happened."
+		);
+		let item = PalletItem { name: "Version".into(), ..Default::default() };
+		assert_eq!(describe_item(&pallets[0], &item), "System.Version");
+	}
+}
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, DEFAULT_URL};
+use crate::{cli::traits::*, common::signer::SignerArgs};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::place_decision_deposit;
+use url::Url;
+
+/// Command to place the decision deposit of a referendum.
+#[derive(Args)]
+pub(crate) struct DepositCommand {
+	/// The index of the referendum.
+	pub(crate) index: u32,
+	/// Websocket endpoint of a node.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl DepositCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Place a decision deposit")?;
+		let client = connect(&self.url).await?;
+		let index = self.index;
+		let xt = place_decision_deposit(index);
+		if self
+			.signer
+			.submit(&client, &self.url, xt, "place the decision deposit", cli)
+			.await?
+			.is_none()
+		{
+			return Ok(());
+		}
+		cli.outro(format!(
+			"Decision deposit of referendum {index} placed: vote on it with `pop governance vote {index}`."
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn deposit_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from(["pop", "governance", "d", "3", "--suri", "//Bob"])?;
+		let crate::commands::Command::Governance(args) = pop.command else {
+			panic!("expected the governance command");
+		};
+		let super::super::Command::Deposit(command) = args.command else {
+			panic!("expected the deposit command");
+		};
+		assert_eq!(command.index, 3);
+		assert_eq!(command.url, Url::parse(DEFAULT_URL)?);
+		assert_eq!(command.signer.suri.as_deref(), Some("//Bob"));
+		// The index of the referendum is required.
+		assert!(crate::Cli::try_parse_from(["pop", "governance", "deposit"]).is_err());
+		Ok(())
+	}
+}
@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	cli::traits::*,
+	commands::call::chain::{parse_function_name, parse_pallet_name, prompt_for_param},
+	common::network,
+};
+use anyhow::{anyhow, Result};
+use clap::{Args, Subcommand};
+use pop_parachains::{
+	construct_extrinsic, decode_call_data, find_dispatchable_by_name, find_pallet_by_name,
+	parse_chain_metadata, resolve_function, set_up_client, CallBuilder, OnlineClient, Payload,
+	SubstrateConfig,
+};
+use url::Url;
+
+pub(crate) mod deposit;
+pub(crate) mod preimage;
+pub(crate) mod submit;
+pub(crate) mod vote;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+/// Arguments for participating in the governance of a chain.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct GovernanceArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Propose and decide referenda via the referenda and conviction voting pallets of a chain.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Note the preimage of a proposal, so that a referendum can refer to it.
+	#[clap(alias = "p")]
+	Preimage(preimage::PreimageCommand),
+	/// Submit a referendum for a proposal on a track, noting its preimage if required.
+	#[clap(alias = "s")]
+	Submit(submit::SubmitCommand),
+	/// Place the decision deposit of a referendum, so that it can start being decided.
+	#[clap(alias = "d")]
+	Deposit(deposit::DepositCommand),
+	/// Vote on a referendum.
+	#[clap(alias = "v")]
+	Vote(vote::VoteCommand),
+}
+
+/// Arguments for the proposal of a referendum, either as its call data or composed from a
+/// dispatchable function and its arguments.
+#[derive(Args, Clone, Default)]
+pub(crate) struct ProposalArgs {
+	/// The pallet containing the dispatchable function of the proposal.
+	#[arg(short, long, value_parser = parse_pallet_name)]
+	pub(crate) pallet: Option<String>,
+	/// The dispatchable function of the proposal.
+	#[arg(short, long, value_parser = parse_function_name, requires = "pallet")]
+	pub(crate) function: Option<String>,
+	/// The arguments of the dispatchable function, encoded as strings.
+	#[arg(short, long, num_args = 0.., requires = "function")]
+	pub(crate) args: Vec<String>,
+	/// SCALE encoded bytes representing the call data of the proposal.
+	#[arg(name = "call", short, long, conflicts_with_all = ["pallet", "function", "args"])]
+	pub(crate) call_data: Option<String>,
+}
+
+impl ProposalArgs {
+	/// The call data of the proposal, prompting the user to compose the call if not specified.
+	///
+	/// # Arguments
+	/// * `client` - The client used to interact with the chain.
+	/// * `cli` - The cli.
+	pub(crate) fn call_data(
+		&self,
+		client: &OnlineClient<SubstrateConfig>,
+		cli: &mut impl Cli,
+	) -> Result<Vec<u8>> {
+		if let Some(call_data) = &self.call_data {
+			return Ok(decode_call_data(call_data)?);
+		}
+		let pallets = parse_chain_metadata(client)?;
+		let pallet = match &self.pallet {
+			Some(name) => find_pallet_by_name(&pallets, name)?,
+			None => {
+				let mut prompt = cli.select("Select the pallet of the proposal:");
+				for pallet in &pallets {
+					prompt = prompt.item(pallet, &pallet.name, &pallet.docs);
+				}
+				prompt.interact()?
+			},
+		};
+		let function = match &self.function {
+			Some(name) => find_dispatchable_by_name(&pallets, &pallet.name, name)?,
+			None => {
+				let mut prompt = cli.select("Select the function of the proposal:");
+				for function in &pallet.functions {
+					prompt = prompt.item(function, &function.name, &function.docs);
+				}
+				prompt.interact()?
+			},
+		};
+		let function = resolve_function(client, function)?;
+		if !function.is_supported {
+			return Err(anyhow!("The function {} is not supported yet.", function.name));
+		}
+		let args = match self.args.is_empty() {
+			true => {
+				let calls = CallBuilder::new(client);
+				function
+					.params
+					.iter()
+					.map(|param| prompt_for_param(cli, param, &calls))
+					.collect::<Result<_>>()?
+			},
+			false => self.args.clone(),
+		};
+		let xt = construct_extrinsic(&function, args)?;
+		Ok(xt.encode_call_data(&client.metadata())?)
+	}
+}
+
+/// Connects to the chain.
+///
+/// # Arguments
+/// * `url` - The endpoint of the chain.
+pub(crate) async fn connect(url: &Url) -> Result<OnlineClient<SubstrateConfig>> {
+	network::retry("connecting to the chain", || set_up_client(url.as_str())).await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn proposal_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop =
+				crate::Cli::try_parse_from(["pop", "governance", "preimage"].iter().chain(args))?;
+			let crate::commands::Command::Governance(args) = pop.command else {
+				panic!("expected the governance command");
+			};
+			let Command::Preimage(command) = args.command else {
+				panic!("expected the preimage command");
+			};
+			Ok::<_, clap::Error>(command.proposal)
+		};
+		let proposal = parse(&["--pallet", "system", "--function", "Remark", "--args", "0x11"])?;
+		assert_eq!(proposal.pallet.as_deref(), Some("System"));
+		assert_eq!(proposal.function.as_deref(), Some("remark"));
+		assert_eq!(proposal.args, vec!["0x11".to_string()]);
+		assert_eq!(parse(&["--call", "0x00000411"])?.call_data.as_deref(), Some("0x00000411"));
+		// The call data conflicts with composing the call.
+		assert!(parse(&["--call", "0x00000411", "--pallet", "System"]).is_err());
+		// The function requires its pallet.
+		assert!(parse(&["--function", "remark"]).is_err());
+		Ok(())
+	}
+}
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, ProposalArgs, DEFAULT_URL};
+use crate::{cli::traits::*, common::signer::SignerArgs};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{is_preimage_noted, note_preimage, preimage_hash};
+use url::Url;
+
+/// Command to note the preimage of a proposal.
+#[derive(Args)]
+pub(crate) struct PreimageCommand {
+	#[command(flatten)]
+	pub(crate) proposal: ProposalArgs,
+	/// Websocket endpoint of a node.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl PreimageCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Note a preimage")?;
+		let client = connect(&self.url).await?;
+		let call_data = self.proposal.call_data(&client, cli)?;
+		let hash = preimage_hash(&call_data);
+		if is_preimage_noted(&client, hash).await? {
+			cli.outro(format!("Preimage {hash:?} is already noted."))?;
+			return Ok(());
+		}
+		let xt = note_preimage(&call_data);
+		if self
+			.signer
+			.submit(&client, &self.url, xt, "note the preimage", cli)
+			.await?
+			.is_none()
+		{
+			return Ok(());
+		}
+		cli.outro(format!(
+			"Preimage {hash:?} noted ({} bytes): submit it as a referendum with `pop governance submit`.",
+			call_data.len()
+		))?;
+		Ok(())
+	}
+}
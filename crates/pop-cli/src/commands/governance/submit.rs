@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, ProposalArgs, DEFAULT_URL};
+use crate::{cli::traits::*, common::signer::SignerArgs};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_parachains::{
+	is_preimage_noted, note_preimage, place_decision_deposit, preimage_hash, submit_referendum,
+	submitted_referendum, tracks, Track,
+};
+use url::Url;
+
+/// Command to submit a referendum.
+#[derive(Args)]
+pub(crate) struct SubmitCommand {
+	#[command(flatten)]
+	pub(crate) proposal: ProposalArgs,
+	/// The name of the track on which the referendum is decided, e.g. `root` [default: prompted].
+	#[arg(short, long)]
+	pub(crate) track: Option<String>,
+	/// The number of blocks after approval at which the proposal is enacted.
+	#[arg(long, default_value = "10")]
+	pub(crate) after: u32,
+	/// Also places the decision deposit of the referendum, so that it can start being decided.
+	#[arg(short, long)]
+	pub(crate) deposit: bool,
+	/// Websocket endpoint of a node.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl SubmitCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Submit a referendum")?;
+		let client = connect(&self.url).await?;
+		let tracks = tracks(&client)?;
+		if tracks.is_empty() {
+			cli.outro_cancel("🚫 No referenda tracks were found on the chain.")?;
+			return Ok(());
+		}
+		let track = select_track(&tracks, self.track.as_deref(), cli)?;
+		let call_data = self.proposal.call_data(&client, cli)?;
+		let (hash, len) = (preimage_hash(&call_data), call_data.len() as u32);
+
+		// A referendum refers to its proposal by the hash of its preimage, which must be noted.
+		if !is_preimage_noted(&client, hash).await? {
+			cli.info(format!("The preimage {hash:?} of the proposal is not noted yet."))?;
+			let xt = note_preimage(&call_data);
+			if self
+				.signer
+				.submit(&client, &self.url, xt, "note the preimage", cli)
+				.await?
+				.is_none()
+			{
+				return Ok(());
+			}
+		}
+		let xt = submit_referendum(track, hash, len, self.after);
+		let Some(events) =
+			self.signer.submit(&client, &self.url, xt, "submit the referendum", cli).await?
+		else {
+			return Ok(());
+		};
+		let index = submitted_referendum(&events)?.ok_or_else(|| {
+			anyhow!("The referendum was submitted, but its index was not found in the events.")
+		})?;
+		cli.info(format!("Referendum {index} submitted on the {} track.", track.name))?;
+
+		if !self.deposit {
+			cli.outro(format!(
+				"Place its decision deposit with `pop governance deposit {index}`, so that it can start being decided."
+			))?;
+			return Ok(());
+		}
+		let xt = place_decision_deposit(index);
+		if self
+			.signer
+			.submit(&client, &self.url, xt, "place the decision deposit", cli)
+			.await?
+			.is_none()
+		{
+			return Ok(());
+		}
+		cli.outro(format!("Vote on referendum {index} with `pop governance vote {index}`."))?;
+		Ok(())
+	}
+}
+
+// Selects a track by its name, prompting the user if none specified.
+fn select_track<'a>(
+	tracks: &'a [Track],
+	name: Option<&str>,
+	cli: &mut impl Cli,
+) -> Result<&'a Track> {
+	match name {
+		Some(name) => tracks.iter().find(|t| t.name == name).ok_or_else(|| {
+			let names: Vec<_> = tracks.iter().map(|t| t.name.as_str()).collect();
+			anyhow!("The track {name} was not found, expected one of: {}.", names.join(", "))
+		}),
+		None => {
+			let mut prompt = cli.select("Select the track of the referendum:");
+			for track in tracks {
+				let hint = format!("decision deposit: {}", track.decision_deposit);
+				prompt = prompt.item(track, &track.name, hint);
+			}
+			Ok(prompt.interact()?)
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+
+	#[test]
+	fn submit_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"governance",
+			"submit",
+			"--call",
+			"0x00000411",
+			"--track",
+			"root",
+			"--deposit",
+			"-y",
+		])?;
+		let crate::commands::Command::Governance(args) = pop.command else {
+			panic!("expected the governance command");
+		};
+		let super::super::Command::Submit(command) = args.command else {
+			panic!("expected the submit command");
+		};
+		assert_eq!(command.track.as_deref(), Some("root"));
+		assert_eq!(command.after, 10);
+		assert!(command.deposit && command.signer.skip_confirm);
+		Ok(())
+	}
+
+	#[test]
+	fn select_track_works() -> Result<()> {
+		let tracks = [
+			Track { id: 0, name: "root".into(), decision_deposit: 100 },
+			Track { id: 1, name: "whitelisted_caller".into(), decision_deposit: 10 },
+		];
+		let mut cli = MockCli::new();
+		assert_eq!(select_track(&tracks, Some("root"), &mut cli)?, &tracks[0]);
+		assert_eq!(
+			select_track(&tracks, Some("treasurer"), &mut cli).unwrap_err().to_string(),
+			"The track treasurer was not found, expected one of: root, whitelisted_caller."
+		);
+		cli.verify()?;
+
+		let items = tracks
+			.iter()
+			.map(|t| (t.name.clone(), format!("decision deposit: {}", t.decision_deposit)))
+			.collect();
+		let mut cli = MockCli::new().expect_select(
+			"Select the track of the referendum:",
+			Some(true),
+			true,
+			Some(items),
+			1,
+		);
+		assert_eq!(select_track(&tracks, None, &mut cli)?, &tracks[1]);
+		cli.verify()
+	}
+}
@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, DEFAULT_URL};
+use crate::{cli::traits::*, common::signer::SignerArgs};
+use anyhow::Result;
+use clap::{
+	builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+	Args,
+};
+use pop_common::enum_variants;
+use pop_parachains::{vote, Conviction, Vote};
+use std::str::FromStr;
+use strum::VariantArray;
+use url::Url;
+
+/// Command to vote on a referendum.
+#[derive(Args)]
+pub(crate) struct VoteCommand {
+	/// The index of the referendum.
+	pub(crate) index: u32,
+	/// Votes in favour of the referendum.
+	#[arg(long, conflicts_with = "nay")]
+	pub(crate) aye: bool,
+	/// Votes against the referendum.
+	#[arg(long)]
+	pub(crate) nay: bool,
+	/// The conviction of the vote [default: prompted].
+	#[arg(long, value_parser = enum_variants!(Conviction))]
+	pub(crate) conviction: Option<Conviction>,
+	/// The balance to vote with, in the smallest unit of the token [default: prompted].
+	#[arg(short, long)]
+	pub(crate) balance: Option<u128>,
+	/// Websocket endpoint of a node.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl VoteCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Vote on a referendum")?;
+		let choice = self.configure(cli)?;
+		let client = connect(&self.url).await?;
+		let index = self.index;
+		if self
+			.signer
+			.submit(&client, &self.url, vote(index, &choice), "vote", cli)
+			.await?
+			.is_none()
+		{
+			return Ok(());
+		}
+		let aye = if choice.aye { "aye" } else { "nay" };
+		cli.outro(format!("Voted {aye} on referendum {index}."))?;
+		Ok(())
+	}
+
+	// Configures the vote, prompting the user for anything not specified.
+	fn configure(&self, cli: &mut impl Cli) -> Result<Vote> {
+		let aye = match (self.aye, self.nay) {
+			(true, _) => true,
+			(_, true) => false,
+			_ => cli
+				.confirm(format!("Do you vote in favour of referendum {}?", self.index))
+				.initial_value(true)
+				.interact()?,
+		};
+		let conviction = match self.conviction {
+			Some(conviction) => conviction,
+			None => {
+				let mut prompt = cli.select("Select the conviction of the vote:");
+				for (i, conviction) in Conviction::VARIANTS.iter().enumerate() {
+					prompt = prompt.item(*conviction, conviction.as_ref(), describe(i));
+				}
+				prompt.interact()?
+			},
+		};
+		let balance = match self.balance {
+			Some(balance) => balance,
+			None => cli
+				.input("Enter the balance to vote with:")
+				.placeholder("The balance in the smallest unit of the token")
+				.validate(|input| match input.parse::<u128>() {
+					Ok(_) => Ok(()),
+					_ => Err("Invalid balance."),
+				})
+				.interact()?
+				.parse()?,
+		};
+		Ok(Vote { aye, conviction, balance })
+	}
+}
+
+// Describes the conviction at the specified index, being how many times the balance is counted
+// and for how many enactment periods it is locked.
+fn describe(index: usize) -> String {
+	match index {
+		0 => "0.1x votes, not locked".to_string(),
+		n => format!("{n}x votes, locked for {} enactment periods", 1 << (n - 1)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+
+	fn parse(args: &[&str]) -> Result<VoteCommand, clap::Error> {
+		let pop = crate::Cli::try_parse_from(["pop", "governance", "vote"].iter().chain(args))?;
+		let crate::commands::Command::Governance(args) = pop.command else {
+			panic!("expected the governance command");
+		};
+		let super::super::Command::Vote(command) = args.command else {
+			panic!("expected the vote command");
+		};
+		Ok(command)
+	}
+
+	#[test]
+	fn vote_args_works() -> Result<()> {
+		let command = parse(&["2", "--nay", "--conviction", "locked3x", "--balance", "1000"])?;
+		assert_eq!(command.index, 2);
+		assert_eq!(
+			command.configure(&mut MockCli::new())?,
+			Vote { aye: false, conviction: Conviction::Locked3x, balance: 1_000 }
+		);
+		// A vote is either in favour of or against the referendum.
+		assert!(parse(&["2", "--aye", "--nay"]).is_err());
+		assert!(parse(&["2", "--conviction", "locked7x"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn configure_prompts_for_vote() -> Result<()> {
+		let items = Conviction::VARIANTS
+			.iter()
+			.enumerate()
+			.map(|(i, c)| (c.as_ref().to_string(), describe(i)))
+			.collect();
+		let mut cli = MockCli::new()
+			.expect_confirm("Do you vote in favour of referendum 2?", true)
+			.expect_select("Select the conviction of the vote:", Some(true), true, Some(items), 1)
+			.expect_input("Enter the balance to vote with:", "500".into());
+		assert_eq!(
+			parse(&["2"])?.configure(&mut cli)?,
+			Vote { aye: true, conviction: Conviction::Locked1x, balance: 500 }
+		);
+		cli.verify()
+	}
+
+	#[test]
+	fn describe_works() {
+		assert_eq!(describe(0), "0.1x votes, not locked");
+		assert_eq!(describe(1), "1x votes, locked for 1 enactment periods");
+		assert_eq!(describe(6), "6x votes, locked for 32 enactment periods");
+	}
+}
@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::network};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{account_history, AccountActivity, AccountId32, Connection};
+use sp_core::bytes::from_hex;
+use std::str::FromStr;
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+/// The number of blocks scanned by default.
+const DEFAULT_BLOCKS: u32 = 100;
+
+/// An encoded account, of either 20 or 32 bytes.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Account(pub(crate) Vec<u8>);
+
+/// Command to show the history of an account.
+#[derive(Args)]
+pub(crate) struct InspectAccountCommand {
+	/// The account, as an SS58 address or a hex-encoded 20 or 32-byte account.
+	#[arg(value_parser = parse_account)]
+	pub(crate) account: Account,
+	/// The number of the first block to be scanned [default: 100 blocks before the last block].
+	#[arg(long)]
+	pub(crate) from: Option<u32>,
+	/// The number of the last block to be scanned [default: the latest block].
+	#[arg(long)]
+	pub(crate) to: Option<u32>,
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+}
+
+impl InspectAccountCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Inspect account")?;
+		let spinner = cli.spinner();
+		spinner.start("Connecting to the chain...");
+		let connection = network::retry("connecting to the chain", || {
+			Connection::connect(self.url.as_str(), None)
+		})
+		.await?;
+		let client = connection.client();
+		let to = match self.to {
+			Some(to) => to,
+			None => client.blocks().at_latest().await?.number(),
+		};
+		let from = self.from.unwrap_or(to.saturating_sub(DEFAULT_BLOCKS - 1));
+		if from > to {
+			spinner.clear();
+			cli.outro_cancel(format!("🚫 Block #{from} is after block #{to}."))?;
+			return Ok(());
+		}
+		spinner.start(format!("Scanning blocks #{from} to #{to}..."));
+		let history = account_history(&connection, &self.account.0, from..=to).await;
+		spinner.clear();
+		let history = match history {
+			Ok(history) => history,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 The blocks could not be scanned: {e}"))?;
+				return Ok(());
+			},
+		};
+		if history.is_empty() {
+			cli.outro(format!("No activity found within blocks #{from} to #{to}."))?;
+			return Ok(());
+		}
+		cli.info(describe(&history))?;
+		cli.outro(format!("Activity found in {} of the blocks #{from} to #{to}.", history.len()))?;
+		Ok(())
+	}
+}
+
+// Parses an account from an SS58 address or its hex encoding.
+fn parse_account(s: &str) -> Result<Account, &'static str> {
+	if let Ok(account) = AccountId32::from_str(s) {
+		return Ok(Account(account.0.to_vec()));
+	}
+	from_hex(s)
+		.ok()
+		.filter(|a| a.len() == 20 || a.len() == 32)
+		.map(Account)
+		.ok_or("expected an SS58 address or a hex-encoded 20 or 32-byte account")
+}
+
+// Describes the activity of an account, block by block.
+fn describe(history: &[AccountActivity]) -> String {
+	let mut output = Vec::new();
+	for activity in history {
+		output.push(format!("Block #{} ({:?})", activity.number, activity.hash));
+		for extrinsic in &activity.extrinsics {
+			let mut line =
+				format!("  #{} {}.{}", extrinsic.index, extrinsic.pallet, extrinsic.call);
+			if let Some(fee) = extrinsic.fee {
+				line.push_str(&format!(", fee {fee}"));
+			}
+			output.push(line);
+		}
+		for event in &activity.events {
+			match event.extrinsic {
+				Some(index) => output.push(format!("  ↳ #{index} {}", event.event)),
+				None => output.push(format!("  ↳ {}", event.event)),
+			}
+		}
+	}
+	output.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+	use pop_parachains::{BlockEvent, DecodedEvent, DecodedExtrinsic, H256};
+
+	#[test]
+	fn inspect_account_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "inspect", "account"].iter().chain(args))?;
+			let crate::commands::Command::Inspect(args) = pop.command else {
+				panic!("expected the inspect command");
+			};
+			let super::super::Command::Account(command) = args.command else {
+				panic!("expected the account command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+		let command = parse(&[alice])?;
+		assert_eq!(command.account.0, AccountId32::from_str(alice)?.0.to_vec());
+		assert_eq!((command.from, command.to), (None, None));
+		assert_eq!(command.url, Url::parse(DEFAULT_URL)?);
+		let command = parse(&[&format!("0x{}", "ab".repeat(20)), "--from", "10", "--to", "20"])?;
+		assert_eq!(command.account.0, vec![0xab; 20]);
+		assert_eq!((command.from, command.to), (Some(10), Some(20)));
+		assert!(parse(&["0x1234"]).is_err());
+		assert!(parse(&[]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn describe_works() {
+		let transfer = DecodedEvent {
+			pallet: "Balances".into(),
+			name: "Transfer".into(),
+			fields: vec![("amount".into(), "100".into())],
+		};
+		let history = vec![AccountActivity {
+			number: 42,
+			hash: H256::repeat_byte(1),
+			extrinsics: vec![DecodedExtrinsic {
+				index: 1,
+				hash: H256::zero(),
+				pallet: "Balances".into(),
+				call: "transfer_keep_alive".into(),
+				args: vec![],
+				signer: Some("5Alice".into()),
+				fee: Some(123),
+				events: vec![transfer.clone()],
+			}],
+			events: vec![
+				BlockEvent {
+					number: 42,
+					hash: H256::repeat_byte(1),
+					extrinsic: Some(1),
+					event: transfer.clone(),
+				},
+				BlockEvent {
+					number: 42,
+					hash: H256::repeat_byte(1),
+					extrinsic: None,
+					event: transfer,
+				},
+			],
+		}];
+		assert_eq!(
+			describe(&history),
+			format!(
+				"Block #42 (0x{})\n  \
+				 #1 Balances.transfer_keep_alive, fee 123\n  \
+				 ↳ #1 Balances.Transfer {{ amount: 100 }}\n  \
+				 ↳ Balances.Transfer {{ amount: 100 }}",
+				"01".repeat(32)
+			)
+		);
+	}
+}
@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::resolve_block;
+use crate::{cli::traits::*, common::network};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_parachains::{inspect_block, BlockDetails, BlockId, Connection};
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+/// Command to inspect a block of a chain.
+#[derive(Args)]
+pub(crate) struct InspectBlockCommand {
+	/// The number or hash of the block [default: the latest block].
+	#[arg(value_parser = |s: &str| s.parse::<BlockId>().map_err(|_| "expected a block number or hash"))]
+	pub(crate) block: Option<BlockId>,
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+}
+
+impl InspectBlockCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Inspect block")?;
+		let spinner = cli.spinner();
+		spinner.start("Decoding the block...");
+		let connection = network::retry("connecting to the chain", || {
+			Connection::connect(self.url.as_str(), None)
+		})
+		.await?;
+		let client = connection.client();
+		let Some(hash) = resolve_block(&connection, self.block).await? else {
+			spinner.clear();
+			let block = self.block.map(|b| b.to_string()).unwrap_or_default();
+			cli.outro_cancel(format!("🚫 Block {block} does not exist."))?;
+			return Ok(());
+		};
+		let block = inspect_block(&client, hash)
+			.await
+			.map_err(|e| anyhow!("The block could not be decoded: {e}"));
+		spinner.clear();
+		match block {
+			Ok(block) => cli.info(describe(&block))?,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		}
+		cli.outro("Inspection complete.")?;
+		Ok(())
+	}
+}
+
+// Describes a block, along with its extrinsics and events.
+fn describe(block: &BlockDetails) -> String {
+	let mut output = format!(
+		"Block #{} ({:?})\n  Parent: {:?}\n  State root: {:?}\n  Extrinsics root: {:?}",
+		block.number, block.hash, block.parent_hash, block.state_root, block.extrinsics_root
+	);
+	output.push_str("\nExtrinsics:");
+	for extrinsic in &block.extrinsics {
+		output
+			.push_str(&format!("\n  #{} {}.{}", extrinsic.index, extrinsic.pallet, extrinsic.call));
+		match &extrinsic.signer {
+			Some(signer) => output.push_str(&format!(", signed by {signer}")),
+			None => output.push_str(" (unsigned)"),
+		}
+		if let Some(fee) = extrinsic.fee {
+			output.push_str(&format!(", fee {fee}"));
+		}
+		for (name, value) in &extrinsic.args {
+			output.push_str(&format!("\n      {name}: {value}"));
+		}
+		for event in &extrinsic.events {
+			output.push_str(&format!("\n    ↳ {event}"));
+		}
+	}
+	if !block.events.is_empty() {
+		output.push_str("\nBlock events:");
+		for event in &block.events {
+			output.push_str(&format!("\n  {event}"));
+		}
+	}
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+	use pop_parachains::{DecodedEvent, DecodedExtrinsic, H256};
+
+	fn event(pallet: &str, name: &str, fields: &[(&str, &str)]) -> DecodedEvent {
+		DecodedEvent {
+			pallet: pallet.into(),
+			name: name.into(),
+			fields: fields.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect(),
+		}
+	}
+
+	#[test]
+	fn inspect_block_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "inspect", "block"].iter().chain(args))?;
+			let crate::commands::Command::Inspect(args) = pop.command else {
+				panic!("expected the inspect command");
+			};
+			let super::super::Command::Block(command) = args.command else {
+				panic!("expected the block command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&[])?;
+		assert_eq!((command.block, command.url), (None, Url::parse(DEFAULT_URL)?));
+		let command = parse(&["42", "--url", "ws://localhost:8000"])?;
+		assert_eq!(command.block, Some(BlockId::Number(42)));
+		assert_eq!(command.url, Url::parse("ws://localhost:8000")?);
+		let hash = format!("0x{}", "01".repeat(32));
+		assert_eq!(parse(&[&hash])?.block, Some(BlockId::Hash(H256::repeat_byte(1))));
+		assert!(parse(&["latest"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn describe_works() {
+		let success = event("System", "ExtrinsicSuccess", &[("dispatch_info", "{ weight: 1 }")]);
+		let block = BlockDetails {
+			number: 42,
+			hash: H256::repeat_byte(1),
+			parent_hash: H256::repeat_byte(2),
+			state_root: H256::repeat_byte(3),
+			extrinsics_root: H256::repeat_byte(4),
+			extrinsics: vec![
+				DecodedExtrinsic {
+					index: 0,
+					hash: H256::zero(),
+					pallet: "Timestamp".into(),
+					call: "set".into(),
+					args: vec![("now".into(), "1000".into())],
+					signer: None,
+					fee: None,
+					events: vec![success.clone()],
+				},
+				DecodedExtrinsic {
+					index: 1,
+					hash: H256::zero(),
+					pallet: "Balances".into(),
+					call: "transfer_keep_alive".into(),
+					args: vec![("dest".into(), "Id (0x02)".into()), ("value".into(), "100".into())],
+					signer: Some("5Alice".into()),
+					fee: Some(123),
+					events: vec![
+						event("Balances", "Transfer", &[("amount", "100")]),
+						success.clone(),
+					],
+				},
+			],
+			events: vec![event("Session", "NewSession", &[])],
+		};
+		let hash = |byte: &str| format!("0x{}", byte.repeat(32));
+		assert_eq!(
+			describe(&block),
+			format!(
+				"Block #42 ({})\n  Parent: {}\n  State root: {}\n  Extrinsics root: {}\n\
+				 Extrinsics:\n  \
+				 #0 Timestamp.set (unsigned)\n      \
+				 now: 1000\n    \
+				 ↳ System.ExtrinsicSuccess {{ dispatch_info: {{ weight: 1 }} }}\n  \
+				 #1 Balances.transfer_keep_alive, signed by 5Alice, fee 123\n      \
+				 dest: Id (0x02)\n      \
+				 value: 100\n    \
+				 ↳ Balances.Transfer {{ amount: 100 }}\n    \
+				 ↳ System.ExtrinsicSuccess {{ dispatch_info: {{ weight: 1 }} }}\n\
+				 Block events:\n  \
+				 Session.NewSession",
+				hash("01"),
+				hash("02"),
+				hash("03"),
+				hash("04")
+			)
+		);
+	}
+}
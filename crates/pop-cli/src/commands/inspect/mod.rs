@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use pop_parachains::{block_hash, BlockId, Connection, H256};
+
+pub(crate) mod account;
+pub(crate) mod block;
+pub(crate) mod state_diff;
+pub(crate) mod storage;
+
+/// Arguments for inspecting a chain.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct InspectArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Inspect a chain.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Show a block, along with its decoded extrinsics and events.
+	#[clap(alias = "b")]
+	Block(block::InspectBlockCommand),
+	/// Dump the storage of a pallet, or of one of its items, at a block as JSON or CSV.
+	#[clap(alias = "s")]
+	Storage(storage::InspectStorageCommand),
+	/// Show the storage entries inserted, updated and removed between two blocks.
+	#[clap(alias = "d")]
+	StateDiff(state_diff::InspectStateDiffCommand),
+	/// Show the extrinsics signed by an account and the events referring to it within a range of
+	/// blocks.
+	#[clap(alias = "a")]
+	Account(account::InspectAccountCommand),
+}
+
+/// Resolves the hash of a block, defaulting to the latest block. Returns `None` if no block with
+/// the specified number exists.
+///
+/// # Arguments
+/// * `connection` - The connection to a node of the chain.
+/// * `block` - The number or hash of the block.
+pub(crate) async fn resolve_block(
+	connection: &Connection,
+	block: Option<BlockId>,
+) -> Result<Option<H256>> {
+	Ok(match block {
+		None => Some(connection.client().blocks().at_latest().await?.hash()),
+		Some(BlockId::Hash(hash)) => Some(hash),
+		Some(BlockId::Number(number)) => block_hash(connection, number).await?,
+	})
+}
@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::resolve_block;
+use crate::{cli::traits::*, common::network};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{state_diff, BlockId, Connection, StorageChange, StorageEntry};
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+/// Command to compare the storage of a chain between two blocks.
+#[derive(Args)]
+pub(crate) struct InspectStateDiffCommand {
+	/// The number or hash of the block from which changes are compared.
+	#[arg(long, value_parser = parse_block)]
+	pub(crate) from: BlockId,
+	/// The number or hash of the block to which changes are compared [default: the latest block].
+	#[arg(long, value_parser = parse_block)]
+	pub(crate) to: Option<BlockId>,
+	/// The pallets to be compared, e.g. `Balances,System` [default: all pallets].
+	#[arg(short, long = "pallet", value_delimiter = ',')]
+	pub(crate) pallets: Vec<String>,
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+}
+
+impl InspectStateDiffCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Inspect state diff")?;
+		let spinner = cli.spinner();
+		spinner.start("Comparing the storage of the blocks...");
+		let connection = network::retry("connecting to the chain", || {
+			Connection::connect(self.url.as_str(), None)
+		})
+		.await?;
+		let client = connection.client();
+		let mut hashes = Vec::new();
+		for block in [Some(self.from), self.to] {
+			let Some(hash) = resolve_block(&connection, block).await? else {
+				spinner.clear();
+				let block = block.map(|b| b.to_string()).unwrap_or_default();
+				cli.outro_cancel(format!("🚫 Block {block} does not exist."))?;
+				return Ok(());
+			};
+			hashes.push(hash);
+		}
+		let changes = state_diff(&client, hashes[0], hashes[1], &self.pallets).await;
+		spinner.clear();
+		let changes = match changes {
+			Ok(changes) => changes,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 The storage could not be compared: {e}"))?;
+				return Ok(());
+			},
+		};
+		cli.info(format!("Comparing block {:?} to block {:?}.", hashes[0], hashes[1]))?;
+		if changes.is_empty() {
+			cli.outro("No storage changes found.")?;
+			return Ok(());
+		}
+		cli.info(describe(&changes))?;
+		cli.outro(summarize(&changes))?;
+		Ok(())
+	}
+}
+
+fn parse_block(s: &str) -> Result<BlockId, &'static str> {
+	s.parse().map_err(|_| "expected a block number or hash")
+}
+
+// Describes storage changes, one per line.
+fn describe(changes: &[StorageChange]) -> String {
+	changes
+		.iter()
+		.map(|change| match change {
+			StorageChange::Inserted(entry) => format!("+ {}: {}", label(entry), entry.value),
+			StorageChange::Updated { from, to } =>
+				format!("~ {}: {} → {}", label(to), from.value, to.value),
+			StorageChange::Removed(entry) => format!("- {}: {}", label(entry), entry.value),
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+// Labels a storage entry by its item and decoded keys, or its raw key when the keys are hashed.
+fn label(entry: &StorageEntry) -> String {
+	let item = format!("{}.{}", entry.pallet, entry.item);
+	match entry.keys.as_slice() {
+		[] => item,
+		keys if keys.iter().all(|k| k == "()") => format!("{item} [{}]", entry.key),
+		keys => format!("{item}({})", keys.join(", ")),
+	}
+}
+
+// Summarizes the number of each kind of storage changes.
+fn summarize(changes: &[StorageChange]) -> String {
+	let count = |f: fn(&StorageChange) -> bool| changes.iter().filter(|c| f(c)).count();
+	format!(
+		"{} inserted, {} updated and {} removed.",
+		count(|c| matches!(c, StorageChange::Inserted(_))),
+		count(|c| matches!(c, StorageChange::Updated { .. })),
+		count(|c| matches!(c, StorageChange::Removed(_))),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+	use pop_parachains::H256;
+
+	fn entry(item: &str, keys: &[&str], value: &str) -> StorageEntry {
+		StorageEntry {
+			pallet: "Balances".into(),
+			item: item.into(),
+			key: "0x1234".into(),
+			keys: keys.iter().map(|k| k.to_string()).collect(),
+			value: value.into(),
+		}
+	}
+
+	#[test]
+	fn inspect_state_diff_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop =
+				crate::Cli::try_parse_from(["pop", "inspect", "state-diff"].iter().chain(args))?;
+			let crate::commands::Command::Inspect(args) = pop.command else {
+				panic!("expected the inspect command");
+			};
+			let super::super::Command::StateDiff(command) = args.command else {
+				panic!("expected the state diff command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["--from", "42"])?;
+		assert_eq!((command.from, command.to), (BlockId::Number(42), None));
+		assert!(command.pallets.is_empty());
+		assert_eq!(command.url, Url::parse(DEFAULT_URL)?);
+		let hash = format!("0x{}", "01".repeat(32));
+		let command = parse(&["--from", "42", "--to", &hash, "--pallet", "Balances,System"])?;
+		assert_eq!(command.to, Some(BlockId::Hash(H256::repeat_byte(1))));
+		assert_eq!(command.pallets, vec!["Balances".to_string(), "System".to_string()]);
+		// The block from which changes are compared is required.
+		assert!(parse(&[]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn describe_works() {
+		let changes = vec![
+			StorageChange::Inserted(entry("Locks", &["5Alice"], "[]")),
+			StorageChange::Updated {
+				from: entry("TotalIssuance", &[], "100"),
+				to: entry("TotalIssuance", &[], "200"),
+			},
+			StorageChange::Removed(entry("Holds", &["()"], "{ amount: 1 }")),
+		];
+		assert_eq!(
+			describe(&changes),
+			"+ Balances.Locks(5Alice): []\n\
+			 ~ Balances.TotalIssuance: 100 → 200\n\
+			 - Balances.Holds [0x1234]: { amount: 1 }"
+		);
+		assert_eq!(summarize(&changes), "1 inserted, 1 updated and 1 removed.");
+	}
+}
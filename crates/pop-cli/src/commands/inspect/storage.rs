@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::resolve_block;
+use crate::{cli::traits::*, common::network};
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use pop_parachains::{dump_storage, BlockId, Connection, StorageEntry};
+use std::path::PathBuf;
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+/// The formats in which storage can be dumped.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub(crate) enum Format {
+	/// An array of JSON objects.
+	#[default]
+	Json,
+	/// Comma-separated values, with a header row.
+	Csv,
+}
+
+/// Command to dump the storage of a pallet.
+#[derive(Args)]
+pub(crate) struct InspectStorageCommand {
+	/// The pallet of which the storage is dumped, e.g. `Balances`.
+	pub(crate) pallet: String,
+	/// The storage item of the pallet to be dumped, e.g. `Account` [default: all items].
+	pub(crate) item: Option<String>,
+	/// The number or hash of the block [default: the latest block].
+	#[arg(short, long, value_parser = |s: &str| s.parse::<BlockId>().map_err(|_| "expected a block number or hash"))]
+	pub(crate) block: Option<BlockId>,
+	/// The format of the dump.
+	#[arg(short, long, value_enum, default_value_t)]
+	pub(crate) format: Format,
+	/// The file to which the dump is written [default: the standard output].
+	#[arg(short, long)]
+	pub(crate) output: Option<PathBuf>,
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+}
+
+impl InspectStorageCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Inspect storage")?;
+		let name = match &self.item {
+			Some(item) => format!("{}.{item}", self.pallet),
+			None => self.pallet.clone(),
+		};
+		let spinner = cli.spinner();
+		spinner.start(format!("Dumping the storage of {name}..."));
+		let connection = network::retry("connecting to the chain", || {
+			Connection::connect(self.url.as_str(), None)
+		})
+		.await?;
+		let client = connection.client();
+		let Some(hash) = resolve_block(&connection, self.block).await? else {
+			spinner.clear();
+			let block = self.block.map(|b| b.to_string()).unwrap_or_default();
+			cli.outro_cancel(format!("🚫 Block {block} does not exist."))?;
+			return Ok(());
+		};
+		let entries = dump_storage(&client, hash, &self.pallet, self.item.as_deref()).await;
+		spinner.clear();
+		let entries = match entries {
+			Ok(entries) => entries,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 The storage could not be dumped: {e}"))?;
+				return Ok(());
+			},
+		};
+		let dump = render(&entries, self.format)?;
+		match &self.output {
+			Some(path) => {
+				std::fs::write(path, dump)?;
+				cli.outro(format!(
+					"{} entries of {name} at block {hash:?} written to {}.",
+					entries.len(),
+					path.display()
+				))?;
+			},
+			None => {
+				cli.plain(dump)?;
+				cli.outro(format!(
+					"{} entries of {name} dumped at block {hash:?}.",
+					entries.len()
+				))?;
+			},
+		}
+		Ok(())
+	}
+}
+
+// Renders storage entries in the specified format.
+fn render(entries: &[StorageEntry], format: Format) -> Result<String> {
+	Ok(match format {
+		Format::Json => serde_json::to_string_pretty(entries)?,
+		Format::Csv => {
+			let mut output = String::from("pallet,item,key,keys,value");
+			for entry in entries {
+				let fields =
+					[&entry.pallet, &entry.item, &entry.key, &entry.keys.join(", "), &entry.value];
+				output.push('\n');
+				output.push_str(&fields.map(|f| escape(f)).join(","));
+			}
+			output
+		},
+	})
+}
+
+// Quotes a CSV field if it contains any separators, quotes or line breaks.
+fn escape(field: &str) -> String {
+	match field.contains([',', '"', '\n', '\r']) {
+		true => format!("\"{}\"", field.replace('"', "\"\"")),
+		false => field.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+
+	fn entries() -> Vec<StorageEntry> {
+		vec![
+			StorageEntry {
+				pallet: "Balances".into(),
+				item: "TotalIssuance".into(),
+				key: "0x01".into(),
+				keys: vec![],
+				value: "1000".into(),
+			},
+			StorageEntry {
+				pallet: "Balances".into(),
+				item: "Locks".into(),
+				key: "0x02".into(),
+				keys: vec!["0x03".into(), "\"vesting\"".into()],
+				value: "{ amount: 10 }".into(),
+			},
+		]
+	}
+
+	#[test]
+	fn inspect_storage_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "inspect", "storage"].iter().chain(args))?;
+			let crate::commands::Command::Inspect(args) = pop.command else {
+				panic!("expected the inspect command");
+			};
+			let super::super::Command::Storage(command) = args.command else {
+				panic!("expected the storage command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["Balances"])?;
+		assert_eq!((command.pallet.as_str(), command.item), ("Balances", None));
+		assert_eq!((command.block, command.format, command.output), (None, Format::Json, None));
+		assert_eq!(command.url, Url::parse(DEFAULT_URL)?);
+		let command =
+			parse(&["Balances", "Account", "--block", "42", "-f", "csv", "-o", "dump.csv"])?;
+		assert_eq!(command.item.as_deref(), Some("Account"));
+		assert_eq!(command.block, Some(BlockId::Number(42)));
+		assert_eq!(command.format, Format::Csv);
+		assert_eq!(command.output, Some(PathBuf::from("dump.csv")));
+		// The pallet is required.
+		assert!(parse(&[]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn render_works() -> Result<()> {
+		assert_eq!(render(&[], Format::Json)?, "[]");
+		assert_eq!(
+			serde_json::from_str::<serde_json::Value>(&render(&entries(), Format::Json)?)?[1],
+			serde_json::json!({
+				"pallet": "Balances",
+				"item": "Locks",
+				"key": "0x02",
+				"keys": ["0x03", "\"vesting\""],
+				"value": "{ amount: 10 }",
+			})
+		);
+		assert_eq!(
+			render(&entries(), Format::Csv)?,
+			"pallet,item,key,keys,value\n\
+			 Balances,TotalIssuance,0x01,,1000\n\
+			 Balances,Locks,0x02,\"0x03, \"\"vesting\"\"\",{ amount: 10 }"
+		);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn execute_writes_dump_to_standard_output() -> Result<()> {
+		let url = Url::parse("wss://rpc1.paseo.popnetwork.xyz")?;
+		let connection = Connection::connect(url.as_str(), None).await?;
+		let hash = resolve_block(&connection, None).await?.expect("latest block exists");
+		let entries = dump_storage(&connection.client(), hash, "Timestamp", Some("Now")).await?;
+		let mut cli = MockCli::new()
+			.expect_intro("Inspect storage")
+			.expect_plain(render(&entries, Format::Json)?)
+			.expect_outro(format!("1 entries of Timestamp.Now dumped at block {hash:?}."));
+		InspectStorageCommand {
+			pallet: "Timestamp".into(),
+			item: Some("Now".into()),
+			block: Some(BlockId::Hash(hash)),
+			format: Format::Json,
+			output: None,
+			url,
+		}
+		.execute(&mut cli)
+		.await?;
+		cli.verify()
+	}
+}
@@ -1,21 +1,27 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::style::{style, Theme};
+use crate::cli::traits::*;
 use anyhow::Context;
 use clap::Args;
-use cliclack::{clear_screen, confirm, intro, log, outro, set_theme};
 use duct::cmd;
 use os_info::Type;
+use std::fmt;
 use strum::Display;
 use tokio::fs;
 use Dependencies::*;
 
-#[derive(Display)]
+#[derive(Debug, Display, PartialEq)]
 pub enum Dependencies {
+	#[strum(serialize = "bash")]
+	Bash,
+	#[strum(serialize = "build-base")]
+	BuildBase,
 	#[strum(serialize = "build-essential")]
 	BuildEssential,
 	#[strum(serialize = "clang")]
 	Clang,
+	#[strum(serialize = "clang-dev")]
+	ClangDev,
 	#[strum(serialize = "clang-devel")]
 	ClangDevel,
 	#[strum(serialize = "cmake")]
@@ -32,10 +38,16 @@ pub enum Dependencies {
 	LibClang,
 	#[strum(serialize = "libssl-dev")]
 	Libssl,
+	#[strum(serialize = "linux-headers")]
+	LinuxHeaders,
+	#[strum(serialize = "llvm")]
+	Llvm,
 	#[strum(serialize = "make")]
 	Make,
 	#[strum(serialize = "openssl")]
 	Openssl,
+	#[strum(serialize = "openssl-dev")]
+	OpensslDev,
 	#[strum(serialize = "openssl-devel")]
 	OpenSslDevel,
 	#[strum(serialize = "pkg-config")]
@@ -44,14 +56,35 @@ pub enum Dependencies {
 	Protobuf,
 	#[strum(serialize = "protobuf-compiler")]
 	ProtobufCompiler,
+	#[strum(serialize = "protobuf-dev")]
+	ProtobufDev,
+	#[strum(serialize = "protoc")]
+	Protoc,
 	#[strum(serialize = "rustup")]
 	Rustup,
+	#[strum(serialize = "rustup.install")]
+	RustupInstall,
+	#[strum(serialize = "Git.Git")]
+	WingetGit,
+	#[strum(serialize = "Kitware.CMake")]
+	WingetCmake,
+	#[strum(serialize = "LLVM.LLVM")]
+	WingetLlvm,
+	#[strum(serialize = "Google.Protobuf")]
+	WingetProtobuf,
+	#[strum(serialize = "Rustlang.Rustup")]
+	WingetRustup,
 }
 
+/// A package to be installed, along with the binary by which it is detected as already
+/// installed. Packages without a binary, such as libraries, are detected by querying the package
+/// manager instead.
+type Package = (Dependencies, Option<&'static str>);
+
 /// Arguments for installing.
 #[derive(Args)]
 #[command(args_conflicts_with_subcommands = true)]
-pub(crate) struct InstallArgs {
+pub struct InstallArgs {
 	/// Automatically install all dependencies required without prompting for confirmation.
 	#[clap(short = 'y', long)]
 	skip_confirm: bool,
@@ -62,213 +95,408 @@ pub(crate) struct Command;
 
 impl Command {
 	/// Executes the command.
-	pub(crate) async fn execute(self, args: InstallArgs) -> anyhow::Result<()> {
-		clear_screen()?;
-		set_theme(Theme);
-		intro(format!(
-			"{}: Install dependencies for development",
-			style(" Pop CLI ").black().on_magenta()
-		))?;
+	///
+	/// # Arguments
+	/// * `args` - The arguments for installing.
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, args: InstallArgs, cli: &mut impl Cli) -> anyhow::Result<()> {
+		cli.intro("Install dependencies for development")?;
 		if cfg!(target_os = "macos") {
-			log::info("ℹ️ Mac OS (Darwin) detected.")?;
-			install_mac(args.skip_confirm).await?;
+			cli.info("ℹ️ Mac OS (Darwin) detected.")?;
+			install_mac(args.skip_confirm, cli).await?;
+		} else if cfg!(target_os = "windows") {
+			cli.info("ℹ️ Windows detected.")?;
+			if !install_windows(args.skip_confirm, cli).await? {
+				return not_supported_message(cli);
+			}
 		} else if cfg!(target_os = "linux") {
 			match os_info::get().os_type() {
-				Type::Arch => {
-					log::info("ℹ️ Arch Linux detected.")?;
-					install_arch(args.skip_confirm).await?;
+				Type::Alpine => {
+					cli.info("ℹ️ Alpine Linux detected.")?;
+					install_alpine(args.skip_confirm, cli).await?;
+				},
+				Type::Arch | Type::EndeavourOS | Type::Manjaro => {
+					cli.info("ℹ️ Arch Linux detected.")?;
+					install_arch(args.skip_confirm, cli).await?;
 				},
-				Type::Debian => {
-					log::info("ℹ️ Debian Linux detected.")?;
-					install_debian(args.skip_confirm).await?;
+				Type::Debian | Type::Raspbian => {
+					cli.info("ℹ️ Debian Linux detected.")?;
+					install_debian(args.skip_confirm, cli).await?;
 				},
-				Type::Redhat => {
-					log::info("ℹ️ Redhat Linux detected.")?;
-					install_redhat(args.skip_confirm).await?;
+				Type::NixOS => {
+					cli.info("ℹ️ NixOS detected.")?;
+					install_nixos(args.skip_confirm, cli).await?;
 				},
-				Type::Ubuntu => {
-					log::info("ℹ️ Ubuntu detected.")?;
-					install_ubuntu(args.skip_confirm).await?;
+				Type::Redhat | Type::CentOS | Type::Fedora => {
+					cli.info("ℹ️ Redhat Linux detected.")?;
+					install_redhat(args.skip_confirm, cli).await?;
 				},
-				_ => return not_supported_message(),
+				Type::Ubuntu | Type::Mint | Type::Pop => {
+					cli.info("ℹ️ Ubuntu detected.")?;
+					install_ubuntu(args.skip_confirm, cli).await?;
+				},
+				_ => return not_supported_message(cli),
 			}
 		} else {
-			return not_supported_message();
+			return not_supported_message(cli);
 		}
-		install_rustup().await?;
-		outro("✅ Installation complete.")?;
+		install_rustup(cli).await?;
+		cli.outro("✅ Installation complete.")?;
 		Ok(())
 	}
 }
 
-async fn install_mac(skip_confirm: bool) -> anyhow::Result<()> {
-	log::info("More information about the packages to be installed here: https://docs.substrate.io/install/macos/")?;
-	if !skip_confirm {
-		prompt_for_confirmation(&format!(
-			"{}, {}, {}, {} and {}",
-			Dependencies::Homebrew,
-			Dependencies::Protobuf,
-			Dependencies::Openssl,
-			Dependencies::Rustup,
-			Dependencies::Cmake,
-		))?
-	}
-	install_homebrew().await?;
+async fn install_mac(skip_confirm: bool, cli: &mut impl Cli) -> anyhow::Result<()> {
+	cli.info("More information about the packages to be installed here: https://docs.substrate.io/install/macos/")?;
+	let packages = missing(
+		[(Protobuf, Some("protoc")), (Openssl, None), (Cmake, Some("cmake"))],
+		is_installed,
+		is_package_installed("brew", &["list", "--versions"]),
+	);
+	let homebrew = (!is_installed("brew")).then_some(Homebrew);
+	confirm_installation(
+		skip_confirm,
+		&list(homebrew.iter().chain(&packages).chain(rustup())),
+		cli,
+	)?;
+	install_homebrew(cli).await?;
 	cmd("brew", vec!["update"]).run()?;
-	cmd("brew", vec!["install", &Protobuf.to_string(), &Openssl.to_string(), &Cmake.to_string()])
-		.run()?;
+	if !packages.is_empty() {
+		cmd("brew", args(["install"], &packages)).run()?;
+	}
 
 	Ok(())
 }
 
-async fn install_arch(skip_confirm: bool) -> anyhow::Result<()> {
-	log::info("More information about the packages to be installed here: https://docs.substrate.io/install/linux/")?;
-	if !skip_confirm {
-		prompt_for_confirmation(&format!(
-			"{}, {}, {}, {}, {} and {}",
-			Curl, Git, Clang, Make, Openssl, Rustup,
-		))?
+async fn install_windows(skip_confirm: bool, cli: &mut impl Cli) -> anyhow::Result<bool> {
+	cli.info("More information about the packages to be installed here: https://docs.substrate.io/install/windows/")?;
+	if is_installed("winget") {
+		let packages = missing(
+			[
+				(WingetGit, Some("git")),
+				(WingetCmake, Some("cmake")),
+				(WingetLlvm, Some("clang")),
+				(WingetProtobuf, Some("protoc")),
+				(WingetRustup, Some("rustup")),
+			],
+			is_installed,
+			|_| false,
+		);
+		confirm_installation(skip_confirm, &list(&packages), cli)?;
+		for package in packages {
+			let id = package.to_string();
+			cmd(
+				"winget",
+				vec![
+					"install",
+					"--id",
+					&id,
+					"--exact",
+					"--silent",
+					"--accept-package-agreements",
+					"--accept-source-agreements",
+				],
+			)
+			.run()?;
+		}
+	} else if is_installed("choco") {
+		let packages = missing(
+			[
+				(Git, Some("git")),
+				(Cmake, Some("cmake")),
+				(Llvm, Some("clang")),
+				(Protoc, Some("protoc")),
+				(RustupInstall, Some("rustup")),
+			],
+			is_installed,
+			|_| false,
+		);
+		confirm_installation(skip_confirm, &list(&packages), cli)?;
+		if !packages.is_empty() {
+			cmd("choco", args(["install", "-y"], &packages)).run()?;
+		}
+	} else {
+		cli.warning("⚠️ Neither winget nor Chocolatey was found to install the packages with.")?;
+		return Ok(false);
 	}
-	cmd(
-		"pacman",
-		vec![
-			"-Syu",
-			"--needed",
-			"--noconfirm",
-			&Curl.to_string(),
-			&Git.to_string(),
-			&Clang.to_string(),
-			&Make.to_string(),
-			&Openssl.to_string(),
+	Ok(true)
+}
+
+async fn install_alpine(skip_confirm: bool, cli: &mut impl Cli) -> anyhow::Result<()> {
+	cli.info("More information about the packages to be installed here: https://docs.substrate.io/install/linux/")?;
+	let packages = missing(
+		[
+			(Bash, Some("bash")),
+			(BuildBase, Some("make")),
+			(Clang, Some("clang")),
+			(ClangDev, None),
+			(Cmake, Some("cmake")),
+			(Curl, Some("curl")),
+			(Git, Some("git")),
+			(LinuxHeaders, None),
+			(OpensslDev, None),
+			(ProtobufDev, Some("protoc")),
 		],
-	)
-	.run()?;
+		is_installed,
+		is_package_installed("apk", &["info", "--installed"]),
+	);
+	confirm_installation(skip_confirm, &list(packages.iter().chain(rustup())), cli)?;
+	if !packages.is_empty() {
+		cmd("apk", args(["add"], &packages)).run()?;
+	}
 
 	Ok(())
 }
 
-async fn install_ubuntu(skip_confirm: bool) -> anyhow::Result<()> {
-	log::info("More information about the packages to be installed here: https://docs.substrate.io/install/linux/")?;
-	if !skip_confirm {
-		prompt_for_confirmation(&format!(
-			"{}, {}, {}, {}, {} and {}",
-			Git, Clang, Curl, Libssl, ProtobufCompiler, Rustup,
-		))?
-	}
-	cmd(
-		"apt",
-		vec![
-			"install",
-			"--assume-yes",
-			&Git.to_string(),
-			&Clang.to_string(),
-			&Curl.to_string(),
-			&Libssl.to_string(),
-			&ProtobufCompiler.to_string(),
+async fn install_arch(skip_confirm: bool, cli: &mut impl Cli) -> anyhow::Result<()> {
+	cli.info("More information about the packages to be installed here: https://docs.substrate.io/install/linux/")?;
+	let packages = missing(
+		[
+			(Curl, Some("curl")),
+			(Git, Some("git")),
+			(Clang, Some("clang")),
+			(Make, Some("make")),
+			(Openssl, None),
 		],
-	)
-	.run()?;
+		is_installed,
+		is_package_installed("pacman", &["--query"]),
+	);
+	confirm_installation(skip_confirm, &list(packages.iter().chain(rustup())), cli)?;
+	cmd("pacman", args(["-Syu", "--needed", "--noconfirm"], &packages)).run()?;
 
 	Ok(())
 }
 
-async fn install_debian(skip_confirm: bool) -> anyhow::Result<()> {
-	log::info("More information about the packages to be installed here: https://docs.substrate.io/install/linux/")?;
-	if !skip_confirm {
-		prompt_for_confirmation(&format!(
-			"{}, {}, {}, {}, {}, {}, {}, {}, {} and {}",
-			Cmake,
-			PkgConfig,
-			Libssl,
-			Git,
-			Gcc,
-			BuildEssential,
-			ProtobufCompiler,
-			Clang,
-			LibClang,
-			Rustup,
-		))?
+async fn install_nixos(skip_confirm: bool, cli: &mut impl Cli) -> anyhow::Result<()> {
+	cli.info("More information about the packages to be installed here: https://docs.substrate.io/install/linux/")?;
+	cli.info("ℹ️ Alternatively, declare these packages within the development shell of a flake, entered via `nix develop`, to keep them out of your profile.")?;
+	let packages = missing(
+		[
+			(Clang, Some("clang")),
+			(Cmake, Some("cmake")),
+			(Git, Some("git")),
+			(Openssl, None),
+			(PkgConfig, Some("pkg-config")),
+			(Protobuf, Some("protoc")),
+			(Rustup, Some("rustup")),
+		],
+		is_installed,
+		in_nix_profile(&cmd("nix", vec!["profile", "list"]).read().unwrap_or_default()),
+	);
+	confirm_installation(skip_confirm, &list(&packages), cli)?;
+	if !packages.is_empty() {
+		cmd("nix", args(["profile", "install"], &nix_attributes(&packages))).run()?;
 	}
-	cmd(
-		"apt",
-		vec![
-			"install",
-			"-y",
-			&Cmake.to_string(),
-			&PkgConfig.to_string(),
-			&Libssl.to_string(),
-			&Git.to_string(),
-			&Gcc.to_string(),
-			&BuildEssential.to_string(),
-			&ProtobufCompiler.to_string(),
-			&Clang.to_string(),
-			&LibClang.to_string(),
+
+	Ok(())
+}
+
+async fn install_ubuntu(skip_confirm: bool, cli: &mut impl Cli) -> anyhow::Result<()> {
+	cli.info("More information about the packages to be installed here: https://docs.substrate.io/install/linux/")?;
+	let packages = missing(
+		[
+			(Git, Some("git")),
+			(Clang, Some("clang")),
+			(Curl, Some("curl")),
+			(Libssl, None),
+			(ProtobufCompiler, Some("protoc")),
 		],
-	)
-	.run()?;
+		is_installed,
+		is_package_installed("dpkg", &["--status"]),
+	);
+	confirm_installation(skip_confirm, &list(packages.iter().chain(rustup())), cli)?;
+	if !packages.is_empty() {
+		cmd("apt", args(["install", "--assume-yes"], &packages)).run()?;
+	}
 
 	Ok(())
 }
 
-async fn install_redhat(skip_confirm: bool) -> anyhow::Result<()> {
-	log::info("More information about the packages to be installed here: https://docs.substrate.io/install/linux/")?;
-	if !skip_confirm {
-		prompt_for_confirmation(&format!(
-			"{}, {}, {}, {}, {}, {}, {} and {}",
-			Cmake, OpenSslDevel, Git, Protobuf, ProtobufCompiler, Clang, ClangDevel, Rustup,
-		))?
+async fn install_debian(skip_confirm: bool, cli: &mut impl Cli) -> anyhow::Result<()> {
+	cli.info("More information about the packages to be installed here: https://docs.substrate.io/install/linux/")?;
+	let packages = missing(
+		[
+			(Cmake, Some("cmake")),
+			(PkgConfig, Some("pkg-config")),
+			(Libssl, None),
+			(Git, Some("git")),
+			(Gcc, Some("gcc")),
+			(BuildEssential, None),
+			(ProtobufCompiler, Some("protoc")),
+			(Clang, Some("clang")),
+			(LibClang, None),
+		],
+		is_installed,
+		is_package_installed("dpkg", &["--status"]),
+	);
+	confirm_installation(skip_confirm, &list(packages.iter().chain(rustup())), cli)?;
+	if !packages.is_empty() {
+		cmd("apt", args(["install", "-y"], &packages)).run()?;
 	}
+
+	Ok(())
+}
+
+async fn install_redhat(skip_confirm: bool, cli: &mut impl Cli) -> anyhow::Result<()> {
+	cli.info("More information about the packages to be installed here: https://docs.substrate.io/install/linux/")?;
+	let packages = missing(
+		[
+			(Cmake, Some("cmake")),
+			(OpenSslDevel, None),
+			(Git, Some("git")),
+			(Protobuf, None),
+			(ProtobufCompiler, Some("protoc")),
+			(Clang, Some("clang")),
+			(ClangDevel, None),
+		],
+		is_installed,
+		is_package_installed("rpm", &["--query"]),
+	);
+	confirm_installation(skip_confirm, &list(packages.iter().chain(rustup())), cli)?;
 	cmd("yum", vec!["update", "-y"]).run()?;
 	cmd("yum", vec!["groupinstall", "-y", "'Development Tool"]).run()?;
-	cmd(
-		"yum",
-		vec![
-			"install",
-			"-y",
-			&Cmake.to_string(),
-			&OpenSslDevel.to_string(),
-			&Git.to_string(),
-			&Protobuf.to_string(),
-			&ProtobufCompiler.to_string(),
-			&Clang.to_string(),
-			&ClangDevel.to_string(),
-		],
-	)
-	.run()?;
+	if !packages.is_empty() {
+		cmd("yum", args(["install", "-y"], &packages)).run()?;
+	}
 
 	Ok(())
 }
 
-fn prompt_for_confirmation(message: &str) -> anyhow::Result<()> {
-	if !confirm(format!(
-		"📦 Do you want to proceed with the installation of the following packages: {} ?",
-		message
-	))
-	.initial_value(true)
-	.interact()?
+// The packages which are not yet installed, according to their binaries or otherwise the
+// package manager.
+fn missing<const N: usize>(
+	packages: [Package; N],
+	is_installed: impl Fn(&str) -> bool,
+	is_package_installed: impl Fn(&Dependencies) -> bool,
+) -> Vec<Dependencies> {
+	packages
+		.into_iter()
+		.filter(|(dependency, binary)| match binary {
+			Some(binary) => !is_installed(binary),
+			None => !is_package_installed(dependency),
+		})
+		.map(|(dependency, _)| dependency)
+		.collect()
+}
+
+// Whether a package is installed, according to a query of the package manager which succeeds
+// only for installed packages.
+fn is_package_installed(
+	program: &'static str,
+	query: &'static [&'static str],
+) -> impl Fn(&Dependencies) -> bool {
+	move |package| {
+		cmd(program, args(query.iter().copied(), &[package]))
+			.stdout_null()
+			.stderr_null()
+			.run()
+			.is_ok()
+	}
+}
+
+// Whether a package is installed within the nix profile, according to its listing.
+fn in_nix_profile(profile: &str) -> impl Fn(&Dependencies) -> bool + '_ {
+	move |package| {
+		let suffix = format!(".{package}");
+		profile.split_whitespace().any(|word| word.ends_with(&suffix))
+	}
+}
+
+// The attributes by which packages are installed from nixpkgs, e.g. `nixpkgs#git`.
+fn nix_attributes(packages: &[Dependencies]) -> Vec<String> {
+	packages.iter().map(|p| format!("nixpkgs#{p}")).collect()
+}
+
+// Rustup, if not yet installed, as it is installed separately from the other packages.
+fn rustup() -> Option<&'static Dependencies> {
+	(!is_installed("rustup")).then_some(&Rustup)
+}
+
+// Whether the binary is available on the path.
+fn is_installed(binary: &str) -> bool {
+	cmd(which(), vec![binary]).stdout_null().stderr_null().run().is_ok()
+}
+
+// The command which locates a binary on the path.
+fn which() -> &'static str {
+	if cfg!(target_os = "windows") {
+		"where"
+	} else {
+		"which"
+	}
+}
+
+// Lists the packages within a message, e.g. `git, clang and rustup`.
+fn list<T: fmt::Display>(packages: impl IntoIterator<Item = T>) -> String {
+	let packages: Vec<_> = packages.into_iter().map(|p| p.to_string()).collect();
+	match packages.split_last() {
+		Some((last, [])) => last.clone(),
+		Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+		None => String::new(),
+	}
+}
+
+// The arguments of a package manager, followed by the packages to be installed.
+fn args<T: fmt::Display>(
+	args: impl IntoIterator<Item = &'static str>,
+	packages: &[T],
+) -> Vec<String> {
+	args.into_iter()
+		.map(String::from)
+		.chain(packages.iter().map(|p| p.to_string()))
+		.collect()
+}
+
+// Prompts for confirmation of the installation of the packages, unless skipped or there is
+// nothing to install.
+fn confirm_installation(
+	skip_confirm: bool,
+	packages: &str,
+	cli: &mut impl Cli,
+) -> anyhow::Result<()> {
+	if skip_confirm || packages.is_empty() {
+		return Ok(());
+	}
+	prompt_for_confirmation(packages, cli)
+}
+
+fn prompt_for_confirmation(message: &str, cli: &mut impl Cli) -> anyhow::Result<()> {
+	if !cli
+		.confirm(format!(
+			"📦 Do you want to proceed with the installation of the following packages: {} ?",
+			message
+		))
+		.initial_value(true)
+		.interact()?
 	{
 		return Err(anyhow::anyhow!("🚫 You have cancelled the installation process."));
 	}
 	Ok(())
 }
 
-fn not_supported_message() -> anyhow::Result<()> {
-	log::error("This OS is not supported at present")?;
-	log::warning("⚠️ Please refer to https://docs.substrate.io/install/ for setup information.")?;
+fn not_supported_message(cli: &mut impl Cli) -> anyhow::Result<()> {
+	cli.error("This OS is not supported at present")?;
+	cli.warning("⚠️ Please refer to https://docs.substrate.io/install/ for setup information.")?;
 	Ok(())
 }
 
-async fn install_rustup() -> anyhow::Result<()> {
-	match cmd("which", vec!["rustup"]).read() {
+async fn install_rustup(cli: &mut impl Cli) -> anyhow::Result<()> {
+	match cmd(which(), vec!["rustup"]).read() {
 		Ok(output) => {
-			log::info(format!("ℹ️ rustup installed already at {}.", output))?;
+			cli.info(format!("ℹ️ rustup installed already at {}.", output))?;
 			cmd("rustup", vec!["update"]).run()?;
 		},
+		// Rustup is installed via the package manager on Windows, but only available on the path
+		// of a new terminal.
+		Err(_) if cfg!(target_os = "windows") => {
+			cli.warning("⚠️ Please open a new terminal for rustup to be available, then run `pop install` again.")?;
+			return Ok(());
+		},
 		Err(_) => {
-			let spinner = cliclack::spinner();
+			let spinner = cli.spinner();
 			spinner.start("Installing rustup ...");
 			run_external_script("https://sh.rustup.rs").await?;
-			outro("rustup installed!")?;
+			cli.outro("rustup installed!")?;
 			cmd("source", vec!["~/.cargo/env"]).run()?;
 		},
 	}
@@ -296,9 +524,9 @@ async fn install_rustup() -> anyhow::Result<()> {
 	Ok(())
 }
 
-async fn install_homebrew() -> anyhow::Result<()> {
-	match cmd("which", vec!["brew"]).read() {
-		Ok(output) => log::info(format!("ℹ️ Homebrew installed already at {}.", output))?,
+async fn install_homebrew(cli: &mut impl Cli) -> anyhow::Result<()> {
+	match cmd(which(), vec!["brew"]).read() {
+		Ok(output) => cli.info(format!("ℹ️ Homebrew installed already at {}.", output))?,
 		Err(_) =>
 			run_external_script(
 				"https://raw.githubusercontent.com/Homebrew/install/master/install.sh",
@@ -325,3 +553,118 @@ async fn run_external_script(script_url: &str) -> anyhow::Result<()> {
 	temp.close()?;
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+
+	#[test]
+	fn prompt_for_confirmation_works() -> anyhow::Result<()> {
+		let mut cli = MockCli::new().expect_confirm(
+			"📦 Do you want to proceed with the installation of the following packages: git and rustup ?",
+			true,
+		);
+		prompt_for_confirmation("git and rustup", &mut cli)?;
+		cli.verify()?;
+
+		let mut cli = MockCli::new().expect_confirm(
+			"📦 Do you want to proceed with the installation of the following packages: git and rustup ?",
+			false,
+		);
+		assert!(matches!(
+			prompt_for_confirmation("git and rustup", &mut cli),
+			Err(e) if e.to_string() == "🚫 You have cancelled the installation process."
+		));
+		cli.verify()
+	}
+
+	#[test]
+	fn confirm_installation_works() -> anyhow::Result<()> {
+		// Confirmation is not prompted for when skipped or there is nothing to install.
+		let mut cli = MockCli::new();
+		confirm_installation(true, "git and rustup", &mut cli)?;
+		confirm_installation(false, "", &mut cli)?;
+		cli.verify()?;
+
+		let mut cli = MockCli::new().expect_confirm(
+			"📦 Do you want to proceed with the installation of the following packages: git ?",
+			true,
+		);
+		confirm_installation(false, "git", &mut cli)?;
+		cli.verify()
+	}
+
+	#[test]
+	fn missing_works() {
+		let packages = [(Git, Some("git")), (Libssl, None), (ProtobufCompiler, Some("protoc"))];
+		assert_eq!(
+			missing(packages, |binary| binary == "git", |_| false),
+			vec![Libssl, ProtobufCompiler]
+		);
+		// Packages without a binary are skipped once installed.
+		let packages = [(Git, Some("git")), (Libssl, None), (ProtobufCompiler, Some("protoc"))];
+		assert_eq!(
+			missing(packages, |binary| binary == "git", |package| package == &Libssl),
+			vec![ProtobufCompiler]
+		);
+		let packages = [(Cmake, Some("cmake")), (Clang, Some("clang"))];
+		assert!(missing(packages, |_| true, |_| false).is_empty());
+	}
+
+	#[test]
+	fn in_nix_profile_works() {
+		let profile = concat!(
+			"Name:               openssl\n",
+			"Flake attribute:    legacyPackages.x86_64-linux.openssl\n",
+			"Original flake URL: flake:nixpkgs\n\n",
+			"Name:               git\n",
+			"Flake attribute:    legacyPackages.x86_64-linux.git\n",
+		);
+		let installed = in_nix_profile(profile);
+		assert!(installed(&Openssl) && installed(&Git));
+		assert!(!installed(&Clang) && !installed(&PkgConfig));
+		assert!(!in_nix_profile("")(&Openssl));
+	}
+
+	#[test]
+	fn nix_attributes_works() {
+		assert_eq!(
+			nix_attributes(&[Git, Llvm, Openssl, PkgConfig, Protobuf]),
+			vec![
+				"nixpkgs#git",
+				"nixpkgs#llvm",
+				"nixpkgs#openssl",
+				"nixpkgs#pkg-config",
+				"nixpkgs#protobuf"
+			]
+		);
+	}
+
+	#[test]
+	fn list_works() {
+		assert_eq!(list([Git, Clang, Rustup]), "git, clang and rustup");
+		assert_eq!(list([Protoc]), "protoc");
+		assert_eq!(list(Vec::<Dependencies>::new()), "");
+		assert_eq!(list([WingetGit, WingetLlvm]), "Git.Git and LLVM.LLVM");
+	}
+
+	#[test]
+	fn args_works() {
+		assert_eq!(
+			args(["install", "-y"], &[Cmake, Protoc]),
+			vec!["install", "-y", "cmake", "protoc"]
+		);
+	}
+
+	#[test]
+	fn not_supported_message_works() -> anyhow::Result<()> {
+		let mut cli = MockCli::new()
+			.expect_error("This OS is not supported at present")
+			.expect_warning(
+				"⚠️ Please refer to https://docs.substrate.io/install/ for setup information.",
+			);
+		not_supported_message(&mut cli)?;
+		cli.verify()
+	}
+}
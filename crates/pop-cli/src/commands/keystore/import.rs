@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_common::keystore::{Keystore, KEYSTORE_PREFIX};
+use std::{fs, path::PathBuf};
+
+/// Imports an account into the keystore.
+#[derive(Args)]
+pub(crate) struct ImportCommand {
+	/// Path to the JSON file of the exported account.
+	pub(crate) path: PathBuf,
+	/// The name of the account within the keystore [default: the name with which it was
+	/// exported].
+	#[arg(short, long)]
+	pub(crate) name: Option<String>,
+}
+
+impl ImportCommand {
+	/// Executes the command, re-encrypting the exported account with a new password before it is
+	/// stored locally.
+	///
+	/// # Arguments
+	/// * `keystore` - The keystore into which the account is imported.
+	/// * `cli` - The cli.
+	pub(crate) fn execute(self, keystore: &Keystore, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Import an account")?;
+		let json = fs::read_to_string(&self.path)
+			.map_err(|e| anyhow!("Failed to read the account {}: {e}", self.path.display()))?;
+		let password = cli.password("Password of the exported account:").interact()?;
+		let keystore_password =
+			cli.password("Password with which to encrypt the account:").interact()?;
+		if cli.password("Confirm the password:").interact()? != keystore_password {
+			cli.outro_cancel("🚫 The passwords do not match.")?;
+			return Ok(());
+		}
+		let spinner = cli.spinner();
+		spinner.start("Decrypting and re-encrypting the account...");
+		let account = keystore.import(&json, &password, &keystore_password, self.name.as_deref());
+		spinner.clear();
+		let account = account?;
+		cli.info(format!("Address: {}", account.address))?;
+		cli.outro(format!(
+			"✅ Imported `{name}`, to be signed with via `--suri {KEYSTORE_PREFIX}{name}`.",
+			name = account.name
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{cli::MockCli, common::keystore::tests::ALICE};
+	use clap::Parser;
+
+	#[test]
+	fn import_command_works() -> Result<()> {
+		let pop =
+			crate::Cli::try_parse_from(["pop", "keystore", "import", "alice.json", "-n", "dev"])?;
+		let crate::commands::Command::Keystore(args) = pop.command else {
+			panic!("expected the keystore command");
+		};
+		let super::super::Command::Import(command) = args.command else {
+			panic!("expected the import command");
+		};
+		assert_eq!(command.path, PathBuf::from("alice.json"));
+		assert_eq!(command.name.as_deref(), Some("dev"));
+		Ok(())
+	}
+
+	#[test]
+	fn import_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("alice.json");
+		fs::write(&path, ALICE)?;
+		let keystore = Keystore::new(&temp_dir.path().join("keystore"));
+		let mut cli = MockCli::new()
+			.expect_intro("Import an account")
+			.expect_password("Password of the exported account:", "whoisalice")
+			.expect_password("Password with which to encrypt the account:", "secret")
+			.expect_password("Confirm the password:", "secret")
+			.expect_info("Address: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+			.expect_outro("✅ Imported `Alice`, to be signed with via `--suri keystore:Alice`.");
+		ImportCommand { path, name: None }.execute(&keystore, &mut cli)?;
+		assert!(keystore.contains("Alice"));
+		cli.verify()
+	}
+
+	#[test]
+	fn import_cancelled_when_passwords_differ() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("alice.json");
+		fs::write(&path, ALICE)?;
+		let keystore = Keystore::new(&temp_dir.path().join("keystore"));
+		let mut cli = MockCli::new()
+			.expect_password("Password of the exported account:", "whoisalice")
+			.expect_password("Password with which to encrypt the account:", "secret")
+			.expect_password("Confirm the password:", "typo")
+			.expect_outro_cancel("🚫 The passwords do not match.");
+		ImportCommand { path, name: None }.execute(&keystore, &mut cli)?;
+		assert!(keystore.accounts()?.is_empty());
+		cli.verify()
+	}
+}
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::Result;
+use clap::Args;
+use pop_common::{
+	keystore::{Keystore, KEYSTORE_PREFIX},
+	secrets::{Secrets, SECRET_PREFIX},
+};
+
+/// Lists the accounts of the keystore and the stored secrets.
+#[derive(Args)]
+pub(crate) struct ListCommand;
+
+impl ListCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `keystore` - The keystore.
+	/// * `secrets` - The stored secrets.
+	/// * `cli` - The cli.
+	pub(crate) fn execute(
+		self,
+		keystore: &Keystore,
+		secrets: &Secrets,
+		cli: &mut impl Cli,
+	) -> Result<()> {
+		cli.intro("List the accounts of the keystore")?;
+		let accounts = keystore.accounts()?;
+		let names = secrets.names()?;
+		if accounts.is_empty() && names.is_empty() {
+			cli.outro(
+				"ℹ️ The keystore is empty. Import an account via `pop keystore import <PATH>`.",
+			)?;
+			return Ok(());
+		}
+		for account in accounts {
+			cli.info(format!("{}: {}", account.name, account.address))?;
+		}
+		for name in names {
+			cli.info(format!("{SECRET_PREFIX}{name}"))?;
+		}
+		cli.outro(format!(
+			"Sign with an account via `--suri {KEYSTORE_PREFIX}<NAME>` or a stored secret via `--suri {SECRET_PREFIX}<NAME>`."
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{cli::MockCli, common::keystore::tests::ALICE};
+
+	#[test]
+	fn list_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let keystore = Keystore::new(&temp_dir.path().join("keystore"));
+		let secrets = Secrets::new(&temp_dir.path().join("secrets.json"));
+		let mut cli = MockCli::new().expect_outro(
+			"ℹ️ The keystore is empty. Import an account via `pop keystore import <PATH>`.",
+		);
+		ListCommand.execute(&keystore, &secrets, &mut cli)?;
+		cli.verify()?;
+
+		keystore.import(ALICE, "whoisalice", "secret", Some("dev"))?;
+		let key = secrets.create("master")?;
+		secrets.store(&key, "bob", "//Bob")?;
+		let mut cli = MockCli::new()
+			.expect_intro("List the accounts of the keystore")
+			.expect_info("dev: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+			.expect_info("secret:bob")
+			.expect_outro("Sign with an account via `--suri keystore:<NAME>` or a stored secret via `--suri secret:<NAME>`.");
+		ListCommand.execute(&keystore, &secrets, &mut cli)?;
+		cli.verify()
+	}
+}
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use clap::{Args, Subcommand};
+
+pub(crate) mod import;
+pub(crate) mod list;
+pub(crate) mod unlock;
+
+/// Arguments for managing the accounts of the keystore.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct KeystoreArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Manage the accounts of the keystore.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Import an account exported as JSON by polkadot.js or a compatible wallet, e.g. Talisman.
+	#[clap(alias = "i")]
+	Import(import::ImportCommand),
+	/// List the accounts of the keystore and the stored secrets.
+	#[clap(alias = "l")]
+	List(list::ListCommand),
+	/// Unlock the stored secrets for the current shell session, for up to 12 hours, via
+	/// `eval "$(pop keystore unlock)"`.
+	#[clap(alias = "u")]
+	Unlock(unlock::UnlockCommand),
+}
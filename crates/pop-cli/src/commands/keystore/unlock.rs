@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::secrets::session};
+use anyhow::Result;
+use clap::Args;
+use pop_common::secrets::{Secrets, SESSION_VARIABLE};
+
+/// Unlocks the stored secrets for the current shell session.
+#[derive(Args)]
+pub(crate) struct UnlockCommand;
+
+impl UnlockCommand {
+	/// Executes the command, printing the command which exports the session, to be evaluated by
+	/// the shell via `eval "$(pop keystore unlock)"`.
+	///
+	/// # Arguments
+	/// * `secrets` - The stored secrets.
+	/// * `cli` - The cli.
+	pub(crate) fn execute(self, secrets: &Secrets, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Unlock the stored secrets")?;
+		if !secrets.exists() {
+			cli.outro_cancel("🚫 No secrets have been stored.")?;
+			return Ok(());
+		}
+		let key = session(secrets, cli)?;
+		cli.plain(export(&secrets.start_session(&key)?))?;
+		cli.outro(
+			"✅ Unlocked the stored secrets, if evaluated via `eval \"$(pop keystore unlock)\"`.",
+		)?;
+		Ok(())
+	}
+}
+
+// The shell command exporting the session.
+fn export(token: &str) -> String {
+	format!("export {SESSION_VARIABLE}='{token}'")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+
+	#[test]
+	fn unlock_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let secrets = Secrets::new(&temp_dir.path().join("secrets.json"));
+		let mut cli = MockCli::new()
+			.expect_intro("Unlock the stored secrets")
+			.expect_outro_cancel("🚫 No secrets have been stored.");
+		UnlockCommand.execute(&secrets, &mut cli)?;
+		cli.verify()?;
+
+		secrets.create("master")?;
+		let mut cli = MockCli::new().expect_password("Master password:", "master").expect_outro(
+			"✅ Unlocked the stored secrets, if evaluated via `eval \"$(pop keystore unlock)\"`.",
+		);
+		UnlockCommand.execute(&secrets, &mut cli)?;
+		cli.verify()
+	}
+
+	#[test]
+	fn export_works() {
+		assert_eq!(export("dG9rZW4="), "export POP_SESSION='dG9rZW4='");
+	}
+}
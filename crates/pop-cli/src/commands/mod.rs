@@ -1,21 +1,60 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::{cache, cli::Cli, common::builds::get_project_path};
+#[cfg(feature = "contract")]
+use crate::common::builds::get_project_path;
+use crate::{cache, cli::traits::Cli};
 use clap::Subcommand;
+#[cfg(feature = "parachain")]
 use pop_common::templates::Template;
+use pop_common::{keystore::Keystore, secrets::Secrets};
 use serde_json::{json, Value};
 
-pub(crate) mod build;
-pub(crate) mod call;
-pub(crate) mod clean;
-pub(crate) mod install;
-pub(crate) mod new;
-pub(crate) mod test;
-pub(crate) mod up;
+#[cfg(feature = "parachain")]
+pub mod assets;
+pub mod build;
+pub mod call;
+pub mod clean;
+#[cfg(feature = "parachain")]
+pub mod coretime;
+#[cfg(feature = "parachain")]
+pub mod decode;
+pub mod deploy;
+#[cfg(feature = "parachain")]
+pub mod explore;
+#[cfg(feature = "parachain")]
+pub mod governance;
+#[cfg(feature = "parachain")]
+pub mod inspect;
+pub mod install;
+pub mod keystore;
+pub mod new;
+#[cfg(feature = "parachain")]
+pub mod nfts;
+#[cfg(feature = "parachain")]
+pub mod query;
+#[cfg(feature = "parachain")]
+pub mod release;
+pub mod serve;
+#[cfg(feature = "parachain")]
+pub mod sign;
+#[cfg(feature = "parachain")]
+pub mod snapshot;
+#[cfg(feature = "parachain")]
+pub mod submit;
+pub mod test;
+#[cfg(feature = "parachain")]
+pub mod transfer;
+pub mod up;
+pub mod upgrade;
+#[cfg(feature = "parachain")]
+pub mod wait;
+#[cfg(feature = "parachain")]
+pub mod watch;
 
+/// The commands of `pop`.
 #[derive(Subcommand)]
 #[command(subcommand_required = true)]
-pub(crate) enum Command {
+pub enum Command {
 	/// Set up the environment for development by installing required packages.
 	#[clap(alias = "i")]
 	Install(install::InstallArgs),
@@ -33,13 +72,73 @@ pub(crate) enum Command {
 	#[clap(alias = "u", about = about_up())]
 	#[cfg(any(feature = "parachain", feature = "contract"))]
 	Up(up::UpArgs),
-	/// Test a smart contract.
-	#[clap(alias = "t")]
-	#[cfg(feature = "contract")]
+	#[clap(alias = "t", about = about_test())]
+	#[cfg(any(feature = "parachain", feature = "contract"))]
 	Test(test::TestArgs),
+	/// Deploy the artifacts of a project to hosted infrastructure, and track its deployments.
+	Deploy(deploy::DeployArgs),
 	/// Remove generated/cached artifacts.
 	#[clap(alias = "C")]
 	Clean(clean::CleanArgs),
+	/// Upgrade the polkadot-sdk and ink! dependencies of a project to a release.
+	Upgrade(upgrade::UpgradeArgs),
+	/// Prepare a release of a runtime, ready to be proposed for its upgrade.
+	#[cfg(feature = "parachain")]
+	Release(release::ReleaseArgs),
+	/// Query the state of a chain, e.g. its registered assets or a parachain.
+	#[clap(alias = "q")]
+	#[cfg(feature = "parachain")]
+	Query(query::QueryArgs),
+	/// Inspect a chain, e.g. a block with its decoded extrinsics and events.
+	#[cfg(feature = "parachain")]
+	Inspect(inspect::InspectArgs),
+	/// Decode a SCALE-encoded value as a type of the metadata of a chain, or call data as a call.
+	#[clap(alias = "d")]
+	#[cfg(feature = "parachain")]
+	Decode(decode::DecodeArgs),
+	/// Browse the pallets of a chain, e.g. their calls, storage, constants and events.
+	#[clap(alias = "e")]
+	#[cfg(feature = "parachain")]
+	Explore(explore::ExploreArgs),
+	/// Transfer an amount of the native token of a chain to an account.
+	#[cfg(feature = "parachain")]
+	Transfer(transfer::TransferCommand),
+	/// Create, mint, transfer and freeze assets, e.g. on Asset Hub.
+	#[clap(alias = "a")]
+	#[cfg(feature = "parachain")]
+	Assets(assets::AssetsArgs),
+	/// Create collections of NFTs, and mint, describe and transfer their items.
+	#[cfg(feature = "parachain")]
+	Nfts(nfts::NftsArgs),
+	/// Purchase, renew and assign bulk coretime, or show that of a parachain.
+	#[clap(alias = "ct")]
+	#[cfg(feature = "parachain")]
+	Coretime(coretime::CoretimeArgs),
+	/// Note preimages, submit referenda and vote on them.
+	#[clap(alias = "gov")]
+	#[cfg(feature = "parachain")]
+	Governance(governance::GovernanceArgs),
+	/// Block until a condition holds on a chain, e.g. a node being reachable.
+	#[cfg(feature = "parachain")]
+	Wait(wait::WaitArgs),
+	/// Watch a chain, e.g. streaming its events or monitoring its health.
+	#[cfg(feature = "parachain")]
+	Watch(watch::WatchArgs),
+	/// Download the state of a live chain into a snapshot, e.g. for executing blocks against.
+	#[cfg(feature = "parachain")]
+	Snapshot(snapshot::SnapshotArgs),
+	/// Sign the payload of an extrinsic offline, e.g. on a machine without network access.
+	#[cfg(feature = "parachain")]
+	Sign(sign::SignArgs),
+	/// Submit an extrinsic signed offline via `pop sign`.
+	#[cfg(feature = "parachain")]
+	Submit(submit::SubmitArgs),
+	/// Import and list the accounts of the keystore, with which extrinsics are signed, and unlock
+	/// stored secrets.
+	#[clap(alias = "ks")]
+	Keystore(keystore::KeystoreArgs),
+	/// Serve pop via a local JSON-RPC interface, e.g. for IDE integration.
+	Serve(serve::ServeArgs),
 }
 
 /// Help message for the build command.
@@ -62,16 +161,29 @@ fn about_up() -> &'static str {
 	return "Deploy a smart contract.";
 }
 
+/// Help message for the test command.
+fn about_test() -> &'static str {
+	#[cfg(all(feature = "parachain", feature = "contract"))]
+	return "Test a smart contract or parachain.";
+	#[cfg(all(feature = "parachain", not(feature = "contract")))]
+	return "Test a parachain.";
+	#[cfg(all(feature = "contract", not(feature = "parachain")))]
+	return "Test a smart contract.";
+}
+
 impl Command {
-	/// Executes the command.
-	pub(crate) async fn execute(self) -> anyhow::Result<Value> {
+	/// Executes the command, returning any data observed for telemetry.
+	///
+	/// # Arguments
+	/// * `cli` - The cli, via which all interaction with the user occurs.
+	pub async fn execute(self, cli: &mut impl Cli) -> anyhow::Result<Value> {
 		match self {
 			#[cfg(any(feature = "parachain", feature = "contract"))]
-			Self::Install(args) => install::Command.execute(args).await.map(|_| Value::Null),
+			Self::Install(args) => install::Command.execute(args, cli).await.map(|_| Value::Null),
 			#[cfg(any(feature = "parachain", feature = "contract"))]
 			Self::New(args) => match args.command {
 				#[cfg(feature = "parachain")]
-				new::Command::Parachain(cmd) => match cmd.execute().await {
+				new::Command::Parachain(cmd) => match cmd.execute(cli).await {
 					Ok(template) => {
 						// telemetry should never cause a panic or early exit
 						Ok(
@@ -84,65 +196,204 @@ impl Command {
 				new::Command::Pallet(cmd) => {
 					// When more contract selections are added the tel data will likely need to go
 					// deeper in the stack
-					cmd.execute().await.map(|_| json!("template"))
+					cmd.execute(cli).await.map(|_| json!("template"))
+				},
+				#[cfg(feature = "parachain")]
+				new::Command::Fuzz(cmd) => cmd.execute(cli).await.map(|_| json!("fuzz")),
+				#[cfg(feature = "parachain")]
+				new::Command::XcmSimulator(cmd) => cmd.execute(cli).await.map(|_| json!("xcm-simulator")),
+				#[cfg(feature = "parachain")]
+				new::Command::Test(args) => match args.command {
+					new::test::Command::Network(cmd) =>
+						cmd.execute(cli).await.map(|_| json!("network-tests")),
+					new::test::Command::Runtime(cmd) =>
+						cmd.execute(cli).await.map(|_| json!("runtime-tests")),
 				},
+				new::Command::Ci(cmd) => cmd.execute(cli).await.map(|_| json!("ci")),
 				#[cfg(feature = "contract")]
 				new::Command::Contract(cmd) => {
 					// When more contract selections are added, the tel data will likely need to go
 					// deeper in the stack
-					cmd.execute().await.map(|_| json!("default"))
+					cmd.execute(cli).await.map(|_| json!("default"))
 				},
 			},
 			#[cfg(any(feature = "parachain", feature = "contract"))]
 			Self::Build(args) => match args.command {
-				None => build::Command::execute(args).map(|t| json!(t)),
+				None => build::Command::execute(args, cli).map(|t| json!(t)),
 				Some(cmd) => match cmd {
 					#[cfg(feature = "parachain")]
-					build::Command::Spec(cmd) => cmd.execute().await.map(|_| Value::Null),
+					build::Command::Spec(cmd) => cmd.execute(cli).await.map(|_| Value::Null),
 				},
 			},
 			#[cfg(any(feature = "parachain", feature = "contract"))]
 			Self::Call(args) => match args.command {
 				#[cfg(feature = "parachain")]
-				call::Command::Chain(cmd) => cmd.execute().await.map(|_| Value::Null),
+				call::Command::Chain(cmd) => cmd.execute(cli).await.map(|_| Value::Null),
 				#[cfg(feature = "contract")]
-				call::Command::Contract(cmd) => cmd.execute().await.map(|_| Value::Null),
+				call::Command::Contract(cmd) => cmd.execute(cli).await.map(|_| Value::Null),
+				#[cfg(feature = "parachain")]
+				call::Command::Xcm(args) => match args.command {
+					call::xcm::Command::Transfer(cmd) =>
+						cmd.execute(cli).await.map(|_| json!("xcm-transfer")),
+					#[cfg(feature = "contract")]
+					call::xcm::Command::Contract(cmd) => cmd.execute(cli).await.map(|_| json!("xcm-contract")),
+				},
 			},
 			#[cfg(any(feature = "parachain", feature = "contract"))]
 			Self::Up(args) => match args.command {
-				None => up::Command::execute(args).await.map(|t| json!(t)),
+				None => up::Command::execute(args, cli).await.map(|t| json!(t)),
 				Some(cmd) => match cmd {
 					#[cfg(feature = "parachain")]
 					up::Command::Network(mut cmd) => {
 						cmd.valid = true;
-						cmd.execute().await.map(|_| Value::Null)
+						cmd.execute(cli).await.map(|_| Value::Null)
 					},
 					// TODO: Deprecated, will be removed in v0.8.0.
 					#[cfg(feature = "parachain")]
-					up::Command::Parachain(cmd) => cmd.execute().await.map(|_| Value::Null),
+					up::Command::Parachain(cmd) => cmd.execute(cli).await.map(|_| Value::Null),
 					// TODO: Deprecated, will be removed in v0.8.0.
 					#[cfg(feature = "contract")]
 					up::Command::Contract(mut cmd) => {
 						cmd.path = get_project_path(args.path, args.path_pos);
-						cmd.execute().await.map(|_| Value::Null)
+						cmd.execute(cli).await.map(|_| Value::Null)
 					},
 				},
 			},
-			#[cfg(feature = "contract")]
+			#[cfg(any(feature = "parachain", feature = "contract"))]
 			Self::Test(args) => match args.command {
-				test::Command::Contract(cmd) => match cmd.execute().await {
-					Ok(feature) => Ok(json!(feature)),
-					Err(e) => Err(e),
+				None => args.matrix.execute(cli).map(|t| json!(t)),
+				Some(cmd) => match cmd {
+					#[cfg(feature = "contract")]
+					test::Command::Contract(cmd) => match cmd.execute(cli).await {
+						Ok(feature) => Ok(json!(feature)),
+						Err(e) => Err(e),
+					},
+					#[cfg(feature = "parachain")]
+					test::Command::Parachain(cmd) => cmd.execute(cli).await.map(|t| json!(t)),
+					#[cfg(feature = "parachain")]
+					test::Command::Network(cmd) => cmd.execute(cli).await.map(|t| json!(t)),
+					#[cfg(feature = "parachain")]
+					test::Command::Fuzz(cmd) => cmd.execute(cli).await.map(|t| json!(t)),
+					#[cfg(feature = "parachain")]
+					test::Command::ExecuteBlock(cmd) => cmd.execute(cli).await.map(|t| json!(t)),
+					#[cfg(feature = "parachain")]
+					test::Command::FollowChain(cmd) => cmd.execute(cli).await.map(|t| json!(t)),
+					#[cfg(feature = "benchmark")]
+					test::Command::Weights(cmd) => cmd.execute(cli).await.map(|t| json!(t)),
 				},
 			},
+			Self::Deploy(args) => match args.command {
+				deploy::Command::Ssh(cmd) => cmd.execute(cli).await.map(|_| json!("ssh")),
+				deploy::Command::Docker(cmd) => cmd.execute(cli).await.map(|_| json!("docker")),
+				deploy::Command::Provider(cmd) => cmd.execute(cli).await.map(|_| json!("provider")),
+				deploy::Command::Status(cmd) => cmd.execute(cli).await.map(|_| json!("status")),
+			},
 			Self::Clean(args) => match args.command {
 				clean::Command::Cache(cmd_args) => {
 					// Initialize command and execute
-					clean::CleanCacheCommand { cli: &mut Cli, cache: cache()?, all: cmd_args.all }
+					clean::CleanCacheCommand { cli, cache: cache()?, all: cmd_args.all }
 						.execute()
 						.map(|_| Value::Null)
 				},
+				clean::Command::Project(cmd_args) =>
+					clean::CleanProjectCommand::new(cli, cmd_args).execute().map(|_| Value::Null),
+			},
+			Self::Upgrade(args) => match args.command {
+				upgrade::Command::Project(cmd) => cmd.execute(cli).await.map(|_| json!("project")),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Release(args) => match args.command {
+				release::Command::Runtime(cmd) => cmd.execute(cli).await.map(|_| json!("runtime")),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Query(args) => match args.command {
+				query::Command::Assets(cmd) => cmd.execute(cli).await.map(|_| json!("assets")),
+				query::Command::Parachain(cmd) =>
+					cmd.execute(cli).await.map(|_| json!("parachain")),
+				query::Command::Chain(cmd) => cmd.execute(cli).await.map(|_| json!("chain")),
+				query::Command::Fees(cmd) => cmd.execute(cli).await.map(|_| json!("fees")),
+				query::Command::Scheduled(cmd) =>
+					cmd.execute(cli).await.map(|_| json!("scheduled")),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Inspect(args) => match args.command {
+				inspect::Command::Block(cmd) => cmd.execute(cli).await.map(|_| json!("block")),
+				inspect::Command::Storage(cmd) => cmd.execute(cli).await.map(|_| json!("storage")),
+				inspect::Command::StateDiff(cmd) =>
+					cmd.execute(cli).await.map(|_| json!("state-diff")),
+				inspect::Command::Account(cmd) => cmd.execute(cli).await.map(|_| json!("account")),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Decode(args) => match (args.command, args.value) {
+				(Some(decode::Command::CallData(cmd)), _) =>
+					cmd.execute(cli).await.map(|_| json!("call-data")),
+				(None, Some(cmd)) => cmd.execute(cli).await.map(|_| Value::Null),
+				(None, None) => unreachable!("a value is required without a subcommand"),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Explore(args) => args.execute(cli).await.map(|_| Value::Null),
+			#[cfg(feature = "parachain")]
+			Self::Transfer(cmd) => cmd.execute(cli).await.map(|_| Value::Null),
+			#[cfg(feature = "parachain")]
+			Self::Assets(args) => match args.command {
+				assets::Command::Create(cmd) => cmd.execute(cli).await.map(|_| json!("create")),
+				assets::Command::Mint(cmd) => cmd.execute(cli).await.map(|_| json!("mint")),
+				assets::Command::Transfer(cmd) => cmd.execute(cli).await.map(|_| json!("transfer")),
+				assets::Command::Freeze(cmd) => cmd.execute(cli).await.map(|_| json!("freeze")),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Nfts(args) => match args.command {
+				nfts::Command::Create(cmd) => cmd.execute(cli).await.map(|_| json!("create")),
+				nfts::Command::Mint(cmd) => cmd.execute(cli).await.map(|_| json!("mint")),
+				nfts::Command::Metadata(cmd) => cmd.execute(cli).await.map(|_| json!("metadata")),
+				nfts::Command::Transfer(cmd) => cmd.execute(cli).await.map(|_| json!("transfer")),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Coretime(args) => match args.command {
+				coretime::Command::Purchase(cmd) =>
+					cmd.execute(cli).await.map(|_| json!("purchase")),
+				coretime::Command::Renew(cmd) => cmd.execute(cli).await.map(|_| json!("renew")),
+				coretime::Command::Assign(cmd) => cmd.execute(cli).await.map(|_| json!("assign")),
+				coretime::Command::Status(cmd) => cmd.execute(cli).await.map(|_| json!("status")),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Governance(args) => match args.command {
+				governance::Command::Preimage(cmd) =>
+					cmd.execute(cli).await.map(|_| json!("preimage")),
+				governance::Command::Submit(cmd) => cmd.execute(cli).await.map(|_| json!("submit")),
+				governance::Command::Deposit(cmd) =>
+					cmd.execute(cli).await.map(|_| json!("deposit")),
+				governance::Command::Vote(cmd) => cmd.execute(cli).await.map(|_| json!("vote")),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Wait(args) => {
+				let timeout = crate::common::network::settings().timeout;
+				wait::WaitCommand { cli, args, timeout }.execute().await.map(|_| Value::Null)
+			},
+			#[cfg(feature = "parachain")]
+			Self::Watch(args) => match args.command {
+				watch::Command::Events(cmd) => cmd.execute(cli).await.map(|_| json!("events")),
+				watch::Command::Health(cmd) => cmd.execute(cli).await.map(|_| json!("health")),
+			},
+			#[cfg(feature = "parachain")]
+			Self::Snapshot(args) => args.execute(cli).await.map(|_| Value::Null),
+			#[cfg(feature = "parachain")]
+			Self::Sign(args) => args.execute(cli).map(|_| Value::Null),
+			#[cfg(feature = "parachain")]
+			Self::Submit(args) => args.execute(cli).await.map(|_| Value::Null),
+			Self::Keystore(args) => {
+				let keystore = Keystore::open()?;
+				let secrets = Secrets::open()?;
+				match args.command {
+					keystore::Command::Import(cmd) =>
+						cmd.execute(&keystore, cli).map(|_| json!("import")),
+					keystore::Command::List(cmd) =>
+						cmd.execute(&keystore, &secrets, cli).map(|_| json!("list")),
+					keystore::Command::Unlock(cmd) =>
+						cmd.execute(&secrets, cli).map(|_| json!("unlock")),
+				}
 			},
+			Self::Serve(args) => serve::Command.execute(args, cli).await.map(|_| Value::Null),
 		}
 	}
 }
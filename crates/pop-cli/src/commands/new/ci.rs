@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use clap::{
+	builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+	Args,
+};
+use pop_common::{
+	ci::{generate_pipeline, Provider},
+	enum_variants,
+};
+use std::{path::PathBuf, str::FromStr};
+use strum::VariantArray;
+
+#[derive(Args)]
+pub struct NewCiCommand {
+	/// Path to the project.
+	#[arg(long, default_value = "./")]
+	pub(crate) path: PathBuf,
+	/// The CI provider for which the pipeline is generated.
+	#[arg(
+		short,
+		long,
+		default_value = Provider::GitHub.as_ref(),
+		value_parser = enum_variants!(Provider)
+	)]
+	pub(crate) provider: Provider,
+}
+
+impl NewCiCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> anyhow::Result<()> {
+		cli.intro("Generate a CI pipeline")?;
+		let file = self.path.join(self.provider.path());
+		if file.exists() &&
+			!cli.confirm(format!(
+				"\"{}\" already exists. Would you like to overwrite it?",
+				file.display()
+			))
+			.interact()?
+		{
+			cli.outro_cancel(format!(
+				"Cannot generate the CI pipeline until \"{}\" is removed.",
+				file.display()
+			))?;
+			return Ok(());
+		}
+
+		let file = match generate_pipeline(&self.path, self.provider) {
+			Ok(file) => file,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 Could not generate the CI pipeline: {e}"))?;
+				return Err(e.into());
+			},
+		};
+		cli.outro(format!(
+			"Commit \"{}\" to build and test your project with `pop` on every change! 🚀",
+			file.display()
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use std::fs;
+
+	#[test]
+	fn new_ci_args_works() -> anyhow::Result<()> {
+		for (args, expected) in [
+			(vec!["pop", "new", "ci"], Provider::GitHub),
+			(vec!["pop", "new", "ci", "--provider", "gitlab"], Provider::GitLab),
+		] {
+			let pop = crate::Cli::try_parse_from(args)?;
+			let crate::commands::Command::New(args) = pop.command else {
+				panic!("expected the new command");
+			};
+			let super::super::Command::Ci(command) = args.command else {
+				panic!("expected the ci command");
+			};
+			assert_eq!(command.path, PathBuf::from("./"));
+			assert_eq!(command.provider, expected);
+		}
+		assert!(crate::Cli::try_parse_from(["pop", "new", "ci", "-p", "jenkins"]).is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn new_ci_works() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		fs::write(
+			temp_dir.path().join("Cargo.toml"),
+			"[package]\nname = \"flipper\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nink = \"5\"\n",
+		)?;
+		fs::create_dir(temp_dir.path().join("src"))?;
+		fs::write(temp_dir.path().join("src/lib.rs"), "")?;
+		let file = temp_dir.path().join(".gitlab-ci.yml");
+		let mut cli = MockCli::new().expect_intro("Generate a CI pipeline").expect_outro(format!(
+			"Commit \"{}\" to build and test your project with `pop` on every change! 🚀",
+			file.display()
+		));
+		NewCiCommand { path: temp_dir.path().to_path_buf(), provider: Provider::GitLab }
+			.execute(&mut cli)
+			.await?;
+		assert!(fs::read_to_string(&file)?.contains("    - pop test contract\n"));
+		cli.verify()?;
+
+		let mut cli = MockCli::new()
+			.expect_intro("Generate a CI pipeline")
+			.expect_confirm(
+				format!("\"{}\" already exists. Would you like to overwrite it?", file.display()),
+				false,
+			)
+			.expect_outro_cancel(format!(
+				"Cannot generate the CI pipeline until \"{}\" is removed.",
+				file.display()
+			));
+		NewCiCommand { path: temp_dir.path().to_path_buf(), provider: Provider::GitLab }
+			.execute(&mut cli)
+			.await?;
+		cli.verify()
+	}
+}
@@ -1,9 +1,6 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::cli::{
-	traits::{Cli as _, Confirm as _},
-	Cli,
-};
+use crate::cli::{self, traits::*};
 use pop_common::manifest::{add_crate_to_workspace, find_workspace_toml};
 
 use anyhow::Result;
@@ -11,7 +8,6 @@ use clap::{
 	builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
 	Args,
 };
-use cliclack::input;
 use console::style;
 use pop_common::{
 	enum_variants, get_project_name_from_path,
@@ -44,10 +40,13 @@ pub struct NewContractCommand {
 
 impl NewContractCommand {
 	/// Executes the command.
-	pub(crate) async fn execute(self) -> Result<()> {
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
 		// If the user doesn't provide a name, guide them in generating a contract.
 		let contract_config = if self.name.is_none() {
-			guide_user_to_generate_contract().await?
+			guide_user_to_generate_contract(cli).await?
 		} else {
 			self.clone()
 		};
@@ -61,7 +60,7 @@ impl NewContractCommand {
 
 		// Validate contract name.
 		if let Err(e) = is_valid_contract_name(name) {
-			Cli.outro_cancel(e)?;
+			cli.outro_cancel(e)?;
 			return Ok(());
 		}
 
@@ -72,7 +71,7 @@ impl NewContractCommand {
 		};
 
 		is_template_supported(contract_type, &template)?;
-		generate_contract_from_template(name, path, &template)?;
+		generate_contract_from_template(name, path, &template, cli)?;
 
 		// If the contract is part of a workspace, add it to that workspace
 		if let Some(workspace_toml) = find_workspace_toml(path) {
@@ -95,29 +94,32 @@ fn is_template_supported(contract_type: &ContractType, template: &Contract) -> R
 }
 
 /// Guide the user to generate a contract from available templates.
-async fn guide_user_to_generate_contract() -> Result<NewContractCommand> {
-	Cli.intro("Generate a contract")?;
+async fn guide_user_to_generate_contract(cli: &mut impl Cli) -> Result<NewContractCommand> {
+	cli.intro("Generate a contract")?;
 
-	let mut contract_type_prompt = cliclack::select("Select a template type: ".to_string());
-	for (i, contract_type) in ContractType::types().iter().enumerate() {
-		if i == 0 {
-			contract_type_prompt = contract_type_prompt.initial_value(contract_type);
+	let contract_type = {
+		let mut contract_type_prompt = cli.select("Select a template type: ".to_string());
+		for (i, contract_type) in ContractType::types().iter().enumerate() {
+			if i == 0 {
+				contract_type_prompt = contract_type_prompt.initial_value(contract_type);
+			}
+			contract_type_prompt = contract_type_prompt.item(
+				contract_type,
+				contract_type.name(),
+				format!(
+					"{} {} available option(s)",
+					contract_type.description(),
+					contract_type.templates().len(),
+				),
+			);
 		}
-		contract_type_prompt = contract_type_prompt.item(
-			contract_type,
-			contract_type.name(),
-			format!(
-				"{} {} available option(s)",
-				contract_type.description(),
-				contract_type.templates().len(),
-			),
-		);
-	}
-	let contract_type = contract_type_prompt.interact()?;
-	let template = display_select_options(contract_type)?;
+		contract_type_prompt.interact()?
+	};
+	let template = display_select_options(contract_type, cli)?;
 
 	// Prompt for location.
-	let name: String = input("Where should your project be created?")
+	let name: String = cli
+		.input("Where should your project be created?")
 		.placeholder("./my_contract")
 		.default_input("./my_contract")
 		.interact()?;
@@ -129,8 +131,11 @@ async fn guide_user_to_generate_contract() -> Result<NewContractCommand> {
 	})
 }
 
-fn display_select_options(contract_type: &ContractType) -> Result<&Contract> {
-	let mut prompt = cliclack::select("Select the contract:".to_string());
+fn display_select_options<'a>(
+	contract_type: &'a ContractType,
+	cli: &mut impl Cli,
+) -> Result<&'a Contract> {
+	let mut prompt = cli.select("Select the contract:".to_string());
 	for (i, template) in contract_type.templates().into_iter().enumerate() {
 		if i == 0 {
 			prompt = prompt.initial_value(template);
@@ -144,24 +149,27 @@ fn generate_contract_from_template(
 	name: &str,
 	path: &Path,
 	template: &Contract,
+	cli: &mut impl Cli,
 ) -> anyhow::Result<()> {
-	Cli.intro(format!("Generating \"{}\" using {}!", name, template.name(),))?;
+	cli.intro(format!("Generating \"{}\" using {}!", name, template.name(),))?;
 
-	let contract_path = check_destination_path(path)?;
+	let contract_path = check_destination_path(path, cli)?;
 	fs::create_dir_all(contract_path.as_path())?;
-	let spinner = cliclack::spinner();
+	let spinner = cli.spinner();
 	spinner.start("Generating contract...");
 	create_smart_contract(name, contract_path.as_path(), template)?;
 	spinner.clear();
 	// Replace spinner with success.
-	console::Term::stderr().clear_last_lines(2)?;
-	Cli.success("Generation complete")?;
+	if !cli::is_quiet() {
+		console::Term::stderr().clear_last_lines(2)?;
+	}
+	cli.success("Generation complete")?;
 
 	// warn about audit status and licensing
 	let repository = template.repository_url().ok().map(|url|
 		style(format!("\nPlease consult the source repository at {url} to assess production suitability and licensing restrictions.")).dim()
 	);
-	Cli.warning(format!("NOTE: the resulting contract is not guaranteed to be audited or reviewed for security vulnerabilities.{}",
+	cli.warning(format!("NOTE: the resulting contract is not guaranteed to be audited or reviewed for security vulnerabilities.{}",
 					repository.unwrap_or_else(|| style("".to_string()))))?;
 
 	// add next steps
@@ -174,25 +182,25 @@ fn generate_contract_from_template(
 		.iter()
 		.map(|s| style(format!("{} {s}", console::Emoji("●", ">"))).dim().to_string())
 		.collect();
-	Cli.success(format!("Next Steps:\n{}", next_steps.join("\n")))?;
+	cli.success(format!("Next Steps:\n{}", next_steps.join("\n")))?;
 
-	Cli.outro(format!(
+	cli.outro(format!(
 		"Need help? Learn more at {}\n",
 		style("https://learn.onpop.io").magenta().underlined()
 	))?;
 	Ok(())
 }
 
-fn check_destination_path(contract_path: &Path) -> anyhow::Result<PathBuf> {
+fn check_destination_path(contract_path: &Path, cli: &mut impl Cli) -> anyhow::Result<PathBuf> {
 	if contract_path.exists() {
-		if !Cli
+		if !cli
 			.confirm(format!(
 				"\"{}\" directory already exists. Would you like to remove it?",
 				contract_path.display()
 			))
 			.interact()?
 		{
-			Cli.outro_cancel(format!(
+			cli.outro_cancel(format!(
 				"Cannot generate contract until \"{}\" directory is removed.",
 				contract_path.display()
 			))?;
@@ -209,6 +217,7 @@ fn check_destination_path(contract_path: &Path) -> anyhow::Result<PathBuf> {
 #[cfg(test)]
 mod tests {
 	use crate::{
+		cli::MockCli,
 		commands::new::{Command::Contract, NewArgs},
 		Cli,
 		Command::New,
@@ -227,7 +236,7 @@ mod tests {
 			panic!("unable to parse command")
 		};
 		// Execute
-		command.execute().await?;
+		command.execute(&mut MockCli::new()).await?;
 		Ok(())
 	}
 
@@ -242,7 +251,7 @@ mod tests {
 			panic!("unable to parse command")
 		};
 		// Execute
-		command.execute().await?;
+		command.execute(&mut MockCli::new()).await?;
 		Ok(())
 	}
 }
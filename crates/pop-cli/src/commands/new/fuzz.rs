@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use clap::Args;
+use pop_parachains::{FuzzHarness, FUZZ_TARGET};
+use std::{fs, path::PathBuf};
+
+#[derive(Args)]
+pub struct NewFuzzCommand {
+	/// Path to the pallet [default: current directory].
+	#[arg(short, long)]
+	pub(crate) path: Option<PathBuf>,
+}
+
+impl NewFuzzCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> anyhow::Result<()> {
+		cli.intro("Generate a fuzzing harness")?;
+		let pallet = self.path.unwrap_or_else(|| PathBuf::from("./"));
+		let path = pallet.join("fuzz");
+		if path.exists() {
+			if !cli
+				.confirm(format!(
+					"\"{}\" directory already exists. Would you like to remove it?",
+					path.display()
+				))
+				.interact()?
+			{
+				cli.outro_cancel(format!(
+					"Cannot generate fuzzing harness until \"{}\" directory is removed.",
+					path.display()
+				))?;
+				return Ok(());
+			}
+			fs::remove_dir_all(&path)?;
+		}
+
+		let spinner = cli.spinner();
+		spinner.start("Generating fuzzing harness...");
+		let harness = match FuzzHarness::create(&pallet) {
+			Ok(harness) => harness,
+			Err(e) => {
+				spinner.error(format!("🚫 Could not generate the fuzzing harness: {e}"));
+				return Err(e.into());
+			},
+		};
+		spinner.stop(format!(
+			"Generated the `{FUZZ_TARGET}` fuzz target at \"{}\"",
+			harness.path().display()
+		));
+		cli.warning("NOTE: the harness includes its own mock runtime, which must be kept in sync with the configuration of the pallet.")?;
+		cli.outro("Run `pop test fuzz` to start fuzzing! 🐛")?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+
+	fn pallet(dir: &std::path::Path) -> anyhow::Result<PathBuf> {
+		fs::write(
+			dir.join("Cargo.toml"),
+			"[package]\nname = \"pallet-fuzzed\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+		)?;
+		Ok(dir.to_path_buf())
+	}
+
+	#[tokio::test]
+	async fn new_fuzz_works() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let pallet = pallet(temp_dir.path())?;
+		let mut cli = MockCli::new()
+			.expect_intro("Generate a fuzzing harness")
+			.expect_warning("NOTE: the harness includes its own mock runtime, which must be kept in sync with the configuration of the pallet.")
+			.expect_outro("Run `pop test fuzz` to start fuzzing! 🐛");
+		NewFuzzCommand { path: Some(pallet.clone()) }.execute(&mut cli).await?;
+		assert!(pallet.join("fuzz/fuzz_targets/extrinsics.rs").exists());
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn new_fuzz_keeps_existing_harness() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let pallet = pallet(temp_dir.path())?;
+		let path = pallet.join("fuzz");
+		fs::create_dir(&path)?;
+		let mut cli = MockCli::new()
+			.expect_intro("Generate a fuzzing harness")
+			.expect_confirm(
+				format!(
+					"\"{}\" directory already exists. Would you like to remove it?",
+					path.display()
+				),
+				false,
+			)
+			.expect_outro_cancel(format!(
+				"Cannot generate fuzzing harness until \"{}\" directory is removed.",
+				path.display()
+			));
+		NewFuzzCommand { path: Some(pallet) }.execute(&mut cli).await?;
+		assert!(!path.join("Cargo.toml").exists());
+		cli.verify()
+	}
+}
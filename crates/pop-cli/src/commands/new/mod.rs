@@ -2,12 +2,19 @@
 
 use clap::{Args, Subcommand};
 
+pub mod ci;
 #[cfg(feature = "contract")]
 pub mod contract;
 #[cfg(feature = "parachain")]
+pub mod fuzz;
+#[cfg(feature = "parachain")]
 pub mod pallet;
 #[cfg(feature = "parachain")]
 pub mod parachain;
+#[cfg(feature = "parachain")]
+pub mod test;
+#[cfg(feature = "parachain")]
+pub mod xcm_simulator;
 
 #[macro_export]
 macro_rules! enum_variants {
@@ -41,6 +48,20 @@ pub enum Command {
 	#[cfg(feature = "parachain")]
 	#[clap(alias = "P")]
 	Pallet(pallet::NewPalletCommand),
+	/// Generate a fuzzing harness for a pallet
+	#[cfg(feature = "parachain")]
+	#[clap(alias = "f")]
+	Fuzz(fuzz::NewFuzzCommand),
+	/// Generate XCM simulator tests for a parachain
+	#[cfg(feature = "parachain")]
+	#[clap(alias = "x")]
+	XcmSimulator(xcm_simulator::NewXcmSimulatorCommand),
+	/// Generate tests for a parachain, e.g. zombienet-sdk tests of its network
+	#[cfg(feature = "parachain")]
+	#[clap(alias = "t")]
+	Test(test::NewTestArgs),
+	/// Generate a CI pipeline which builds and tests the project
+	Ci(ci::NewCiCommand),
 	/// Generate a new smart contract
 	#[cfg(feature = "contract")]
 	#[clap(alias = "c")]
@@ -1,12 +1,8 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::{
-	cli::{traits::Cli as _, Cli},
-	multiselect_pick,
-};
+use crate::{cli::traits::*, multiselect_pick};
 
 use clap::{Args, Subcommand};
-use cliclack::{confirm, input, multiselect, outro, outro_cancel};
 use pop_common::{add_crate_to_workspace, find_workspace_toml, prefix_with_current_dir_if_needed};
 use pop_parachains::{
 	create_pallet_template, TemplatePalletConfig, TemplatePalletConfigCommonTypes,
@@ -74,8 +70,11 @@ pub struct AdvancedMode {
 
 impl NewPalletCommand {
 	/// Executes the command.
-	pub(crate) async fn execute(self) -> anyhow::Result<()> {
-		Cli.intro("Generate a pallet")?;
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> anyhow::Result<()> {
+		cli.intro("Generate a pallet")?;
 
 		let mut pallet_default_config = false;
 		let mut pallet_common_types = Vec::new();
@@ -90,12 +89,13 @@ impl NewPalletCommand {
 					advanced_mode_args.default_config ||
 					advanced_mode_args.custom_origin)
 			{
-				Cli.info("Generate the pallet's config trait.")?;
+				cli.info("Generate the pallet's config trait.")?;
 
-				pallet_common_types = multiselect_pick!(TemplatePalletConfigCommonTypes, "Are you interested in adding one of these types and their usual configuration to your pallet?");
-				Cli.info("Generate the pallet's storage.")?;
+				pallet_common_types = multiselect_pick!(cli, TemplatePalletConfigCommonTypes, "Are you interested in adding one of these types and their usual configuration to your pallet?");
+				cli.info("Generate the pallet's storage.")?;
 
 				pallet_storage = multiselect_pick!(
+					cli,
 					TemplatePalletStorageTypes,
 					"Are you interested in adding some of those storage items to your pallet?"
 				);
@@ -103,12 +103,14 @@ impl NewPalletCommand {
 				// If there's no common types, default_config is excluded from the multiselect
 				let boolean_options = if pallet_common_types.is_empty() {
 					multiselect_pick!(
+                        cli,
                         TemplatePalletOptions,
                         "Are you interested in adding one of these types and their usual configuration to your pallet?",
                         [TemplatePalletOptions::DefaultConfig]
 				    )
 				} else {
 					multiselect_pick!(
+                        cli,
                         TemplatePalletOptions,
                         "Are you interested in adding one of these types and their usual configuration to your pallet?"
                     )
@@ -136,7 +138,8 @@ impl NewPalletCommand {
 		let pallet_path = if let Some(path) = self.name {
 			PathBuf::from(path)
 		} else {
-			let path: String = input("Where should your project be created?")
+			let path: String = cli
+				.input("Where should your project be created?")
 				.placeholder("./template")
 				.default_input("./template")
 				.interact()?;
@@ -152,13 +155,14 @@ impl NewPalletCommand {
 		let workspace_toml = find_workspace_toml(&pallet_path);
 
 		if pallet_path.exists() {
-			if !confirm(format!(
-				"\"{}\" directory already exists. Would you like to remove it?",
-				pallet_path.display()
-			))
-			.interact()?
+			if !cli
+				.confirm(format!(
+					"\"{}\" directory already exists. Would you like to remove it?",
+					pallet_path.display()
+				))
+				.interact()?
 			{
-				outro_cancel(format!(
+				cli.outro_cancel(format!(
 					"Cannot generate pallet until \"{}\" directory is removed.",
 					pallet_path.display()
 				))?;
@@ -166,7 +170,7 @@ impl NewPalletCommand {
 			}
 			fs::remove_dir_all(pallet_path.clone())?;
 		}
-		let spinner = cliclack::spinner();
+		let spinner = cli.spinner();
 		spinner.start("Generating pallet...");
 		create_pallet_template(
 			pallet_path.clone(),
@@ -196,7 +200,7 @@ impl NewPalletCommand {
 			.output()?;
 
 		spinner.stop("Generation complete");
-		outro(format!(
+		cli.outro(format!(
 			"cd into \"{}\" and enjoy hacking! 🚀",
 			pallet_path
 				.to_str()
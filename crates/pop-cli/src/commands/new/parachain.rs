@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use crate::{
-	cli::{traits::Cli as _, Cli},
+	cli::{self, traits::*},
 	style::style,
 };
 use anyhow::Result;
@@ -9,15 +9,10 @@ use clap::{
 	builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
 	Args,
 };
-use cliclack::{
-	confirm, input,
-	log::{self, success, warning},
-	outro, outro_cancel,
-};
 use pop_common::{
 	enum_variants, enum_variants_without_deprecated,
 	templates::{Template, Type},
-	Git, GitHub, Release,
+	Git, GitHub, Release, Status,
 };
 use pop_parachains::{
 	instantiate_template_dir, is_initial_endowment_valid, Config, Parachain, Provider,
@@ -72,10 +67,13 @@ pub struct NewParachainCommand {
 
 impl NewParachainCommand {
 	/// Executes the command.
-	pub(crate) async fn execute(self) -> Result<Parachain> {
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<Parachain> {
 		// If user doesn't select the name guide them to generate a parachain.
 		let parachain_config = if self.name.is_none() {
-			guide_user_to_generate_parachain(self.verify).await?
+			guide_user_to_generate_parachain(self.verify, cli).await?
 		} else {
 			self.clone()
 		};
@@ -90,12 +88,13 @@ impl NewParachainCommand {
 			None => provider.default_template().expect("parachain templates have defaults; qed."), /* Each provider has a template by default */
 		};
 
-		is_template_supported(provider, &template)?;
+		is_template_supported(provider, &template, cli)?;
 		let config = get_customization_value(
 			&template,
 			parachain_config.symbol.clone(),
 			parachain_config.decimals,
 			parachain_config.initial_endowment.clone(),
+			cli,
 		)?;
 
 		let tag_version = parachain_config.release_tag.clone();
@@ -107,6 +106,7 @@ impl NewParachainCommand {
 			tag_version,
 			config,
 			self.verify,
+			cli,
 		)
 		.await?;
 		Ok(template)
@@ -114,31 +114,37 @@ impl NewParachainCommand {
 }
 
 /// Guide the user to generate a parachain from available templates.
-async fn guide_user_to_generate_parachain(verify: bool) -> Result<NewParachainCommand> {
-	Cli.intro("Generate a parachain")?;
+async fn guide_user_to_generate_parachain(
+	verify: bool,
+	cli: &mut impl Cli,
+) -> Result<NewParachainCommand> {
+	cli.intro("Generate a parachain")?;
 
 	// Prompt for template selection.
-	let mut prompt = cliclack::select("Select a template provider: ".to_string());
-	for (i, provider) in Provider::types().iter().enumerate() {
-		if i == 0 {
-			prompt = prompt.initial_value(provider);
+	let provider = {
+		let mut prompt = cli.select("Select a template provider: ".to_string());
+		for (i, provider) in Provider::types().iter().enumerate() {
+			if i == 0 {
+				prompt = prompt.initial_value(provider);
+			}
+			prompt = prompt.item(
+				provider,
+				provider.name(),
+				format!(
+					"{} {} available option(s)",
+					provider.description(),
+					provider.templates().len()
+				),
+			);
 		}
-		prompt = prompt.item(
-			provider,
-			provider.name(),
-			format!(
-				"{} {} available option(s)",
-				provider.description(),
-				provider.templates().len()
-			),
-		);
-	}
-	let provider = prompt.interact()?;
-	let template = display_select_options(provider)?;
-	let release_name = choose_release(template, verify).await?;
+		prompt.interact()?
+	};
+	let template = display_select_options(provider, cli)?;
+	let release_name = choose_release(template, verify, cli).await?;
 
 	// Prompt for location.
-	let name: String = input("Where should your project be created?")
+	let name: String = cli
+		.input("Where should your project be created?")
 		.placeholder("./my-parachain")
 		.default_input("./my-parachain")
 		.interact()?;
@@ -150,7 +156,7 @@ async fn guide_user_to_generate_parachain(verify: bool) -> Result<NewParachainCo
 		initial_endowment: "1u64 << 60".to_string(),
 	};
 	if Provider::Pop.provides(template) {
-		customizable_options = prompt_customizable_options()?;
+		customizable_options = prompt_customizable_options(cli)?;
 	}
 
 	Ok(NewParachainCommand {
@@ -172,27 +178,36 @@ async fn generate_parachain_from_template(
 	tag_version: Option<String>,
 	config: Config,
 	verify: bool,
+	cli: &mut impl Cli,
 ) -> Result<()> {
-	Cli.intro(format!(
+	cli.intro(format!(
 		"Generating \"{name_template}\" using {} from {}!",
 		template.name(),
 		provider.name()
 	))?;
 
-	let destination_path = check_destination_path(name_template)?;
+	let destination_path = check_destination_path(name_template, cli)?;
 
-	let spinner = cliclack::spinner();
+	let spinner = cli.spinner();
 	spinner.start("Generating parachain...");
-	let tag = instantiate_template_dir(template, destination_path, tag_version, config)?;
+	let tag = instantiate_template_dir(
+		template,
+		destination_path,
+		tag_version,
+		config,
+		&SpinnerStatus(&spinner),
+	)?;
 	if let Err(err) = Git::git_init(destination_path, "initialized parachain") {
 		if err.class() == git2::ErrorClass::Config && err.code() == git2::ErrorCode::NotFound {
-			outro_cancel("git signature could not be found. Please configure your git config with your name and email")?;
+			cli.outro_cancel("git signature could not be found. Please configure your git config with your name and email")?;
 		}
 	}
 	spinner.clear();
 
 	// Replace spinner with success.
-	console::Term::stderr().clear_last_lines(2)?;
+	if !cli::is_quiet() {
+		console::Term::stderr().clear_last_lines(2)?;
+	}
 	let mut verify_note = "".to_string();
 	if verify && tag.is_some() {
 		let url = url::Url::parse(template.repository_url()?).expect("valid repository url");
@@ -200,7 +215,7 @@ async fn generate_parachain_from_template(
 		let commit = repo.get_commit_sha_from_release(&tag.clone().unwrap()).await;
 		verify_note = format!(" ✅ Fetched the latest release of the template along with its license based on the commit SHA for the release ({}).", commit.unwrap_or_default());
 	}
-	success(format!(
+	cli.success(format!(
 		"Generation complete{}",
 		tag.map(|t| format!("\n{}", style(format!("Version: {t} {}", verify_note)).dim()))
 			.unwrap_or_default()
@@ -208,7 +223,7 @@ async fn generate_parachain_from_template(
 
 	if !template.is_audited() {
 		// warn about audit status and licensing
-		warning(format!("NOTE: the resulting parachain is not guaranteed to be audited or reviewed for security vulnerabilities.\n{}",
+		cli.warning(format!("NOTE: the resulting parachain is not guaranteed to be audited or reviewed for security vulnerabilities.\n{}",
 						style(format!("Please consult the source repository at {} to assess production suitability and licensing restrictions.", template.repository_url()?))
 							.dim()))?;
 	}
@@ -227,9 +242,9 @@ async fn generate_parachain_from_template(
 		.iter()
 		.map(|s| style(format!("{} {s}", console::Emoji("●", ">"))).dim().to_string())
 		.collect();
-	success(format!("Next Steps:\n{}", next_steps.join("\n")))?;
+	cli.success(format!("Next Steps:\n{}", next_steps.join("\n")))?;
 
-	outro(format!(
+	cli.outro(format!(
 		"Need help? Learn more at {}\n",
 		style("https://learn.onpop.io").magenta().underlined()
 	))?;
@@ -237,7 +252,11 @@ async fn generate_parachain_from_template(
 }
 
 /// Determines whether the specified template is supported by the provider.
-fn is_template_supported(provider: &Provider, template: &Parachain) -> Result<()> {
+fn is_template_supported(
+	provider: &Provider,
+	template: &Parachain,
+	cli: &mut impl Cli,
+) -> Result<()> {
 	if !provider.provides(template) {
 		return Err(anyhow::anyhow!(format!(
 			"The provider \"{:?}\" doesn't support the {:?} template.",
@@ -245,13 +264,16 @@ fn is_template_supported(provider: &Provider, template: &Parachain) -> Result<()
 		)));
 	};
 	if template.is_deprecated() {
-		warning(format!("NOTE: this template is deprecated.{}", template.deprecated_message()))?;
+		cli.warning(format!(
+			"NOTE: this template is deprecated.{}",
+			template.deprecated_message()
+		))?;
 	}
 	Ok(())
 }
 
-fn display_select_options(provider: &Provider) -> Result<&Parachain> {
-	let mut prompt = cliclack::select("Select the type of parachain:".to_string());
+fn display_select_options<'a>(provider: &'a Provider, cli: &mut impl Cli) -> Result<&'a Parachain> {
+	let mut prompt = cli.select("Select the type of parachain:".to_string());
 	for (i, template) in provider.templates().into_iter().enumerate() {
 		if i == 0 {
 			prompt = prompt.initial_value(template);
@@ -266,11 +288,12 @@ fn get_customization_value(
 	symbol: Option<String>,
 	decimals: Option<u8>,
 	initial_endowment: Option<String>,
+	cli: &mut impl Cli,
 ) -> Result<Config> {
 	if !matches!(template, Parachain::Standard) &&
 		(symbol.is_some() || decimals.is_some() || initial_endowment.is_some())
 	{
-		log::warning("Customization options are not available for this template")?;
+		cli.warning("Customization options are not available for this template")?;
 		sleep(Duration::from_secs(3))
 	}
 	Ok(Config {
@@ -280,16 +303,17 @@ fn get_customization_value(
 	})
 }
 
-fn check_destination_path(name_template: &String) -> Result<&Path> {
+fn check_destination_path<'a>(name_template: &'a String, cli: &mut impl Cli) -> Result<&'a Path> {
 	let destination_path = Path::new(name_template);
 	if destination_path.exists() {
-		if !confirm(format!(
-			"\"{}\" directory already exists. Would you like to remove it?",
-			destination_path.display()
-		))
-		.interact()?
+		if !cli
+			.confirm(format!(
+				"\"{}\" directory already exists. Would you like to remove it?",
+				destination_path.display()
+			))
+			.interact()?
 		{
-			outro_cancel(format!(
+			cli.outro_cancel(format!(
 				"Cannot generate parachain until \"{}\" directory is removed.",
 				destination_path.display()
 			))?;
@@ -307,7 +331,11 @@ fn check_destination_path(name_template: &String) -> Result<&Path> {
 /// Otherwise, the default release is used.
 ///
 /// return: `Option<String>` - The release name selected by the user or None if no releases found.
-async fn choose_release(template: &Parachain, verify: bool) -> Result<Option<String>> {
+async fn choose_release(
+	template: &Parachain,
+	verify: bool,
+	cli: &mut impl Cli,
+) -> Result<Option<String>> {
 	let url = url::Url::parse(template.repository_url()?).expect("valid repository url");
 	let repo = GitHub::parse(url.as_str())?;
 
@@ -316,7 +344,7 @@ async fn choose_release(template: &Parachain, verify: bool) -> Result<Option<Str
 	} else {
 		template.license().unwrap().to_string() // unwrap is safe as it is checked above
 	};
-	log::info(format!("Template {}: {}", style("License").bold(), license))?;
+	cli.info(format!("Template {}: {}", style("License").bold(), license))?;
 
 	// Get only the latest 3 releases that are supported by the template (default is all)
 	let latest_3_releases: Vec<Release> = get_latest_3_releases(&repo, verify)
@@ -327,7 +355,7 @@ async fn choose_release(template: &Parachain, verify: bool) -> Result<Option<Str
 
 	let mut release_name = None;
 	if !latest_3_releases.is_empty() {
-		release_name = Some(display_release_versions_to_user(latest_3_releases)?);
+		release_name = Some(display_release_versions_to_user(latest_3_releases, cli)?);
 	} else {
 		// If supported_versions exists and no other releases are found,
 		// then the default branch is not supported and an error is returned
@@ -336,7 +364,7 @@ async fn choose_release(template: &Parachain, verify: bool) -> Result<Option<Str
 				"No supported versions found for this template. Please open an issue here: https://github.com/r0gue-io/pop-cli/issues "
 			))?;
 
-		warning("No releases found for this template. Will use the default branch")?;
+		cli.warning("No releases found for this template. Will use the default branch")?;
 	}
 
 	Ok(release_name)
@@ -355,8 +383,8 @@ async fn get_latest_3_releases(repo: &GitHub, verify: bool) -> Result<Vec<Releas
 	Ok(latest_3_releases)
 }
 
-fn display_release_versions_to_user(releases: Vec<Release>) -> Result<String> {
-	let mut prompt = cliclack::select("Select a specific release:".to_string());
+fn display_release_versions_to_user(releases: Vec<Release>, cli: &mut impl Cli) -> Result<String> {
+	let mut prompt = cli.select("Select a specific release:".to_string());
 	for (i, release) in releases.iter().enumerate() {
 		if i == 0 {
 			prompt = prompt.initial_value(&release.tag_name);
@@ -373,30 +401,34 @@ fn display_release_versions_to_user(releases: Vec<Release>) -> Result<String> {
 	Ok(prompt.interact()?.to_string())
 }
 
-fn prompt_customizable_options() -> Result<Config> {
-	let symbol: String = input("What is the symbol of your parachain token?")
+fn prompt_customizable_options(cli: &mut impl Cli) -> Result<Config> {
+	let symbol: String = cli
+		.input("What is the symbol of your parachain token?")
 		.placeholder("UNIT")
 		.default_input("UNIT")
 		.interact()?;
 
-	let decimals_input: String = input("How many token decimals?")
+	let decimals_input: String = cli
+		.input("How many token decimals?")
 		.placeholder("12")
 		.default_input("12")
 		.interact()?;
 	let decimals: u8 = decimals_input.parse::<u8>().expect("input has to be a number");
 
-	let mut initial_endowment: String = input("And the initial endowment for dev accounts?")
+	let mut initial_endowment: String = cli
+		.input("And the initial endowment for dev accounts?")
 		.placeholder("1u64 << 60")
 		.default_input("1u64 << 60")
 		.interact()?;
 	if !is_initial_endowment_valid(&initial_endowment) {
-		outro_cancel("⚠️ The specified initial endowment is not valid")?;
+		cli.outro_cancel("⚠️ The specified initial endowment is not valid")?;
 		// Prompt the user if they want to use the one by default
-		if !confirm(format!("📦 Would you like to use the default {}?", DEFAULT_INITIAL_ENDOWMENT))
+		if !cli
+			.confirm(format!("📦 Would you like to use the default {}?", DEFAULT_INITIAL_ENDOWMENT))
 			.initial_value(true)
 			.interact()?
 		{
-			outro_cancel(
+			cli.outro_cancel(
 				"🚫 Cannot create a parachain with an incorrect initial endowment value.",
 			)?;
 			return Err(anyhow::anyhow!("incorrect initial endowment value"));
@@ -406,11 +438,25 @@ fn prompt_customizable_options() -> Result<Config> {
 	Ok(Config { symbol, decimals, initial_endowment })
 }
 
+// Shows the progress of fetching a template via a spinner.
+struct SpinnerStatus<'a, S>(&'a S);
+
+impl<S: Spinner> Status for SpinnerStatus<'_, S> {
+	fn update(&self, status: &str) {
+		self.0.set_message(status.trim());
+	}
+
+	fn progress(&self, phase: &str, pct: u8) {
+		self.0.set_message(format!("Generating parachain ({phase}: {pct}%)..."));
+	}
+}
+
 #[cfg(test)]
 mod tests {
 
 	use super::*;
 	use crate::{
+		cli::MockCli,
 		commands::new::{Command::Parachain as ParachainCommand, NewArgs},
 		Cli,
 		Command::New,
@@ -434,7 +480,7 @@ mod tests {
 		};
 		// Execute
 		let name = command.name.clone().unwrap();
-		command.execute().await?;
+		command.execute(&mut MockCli::new()).await?;
 		// check for git_init
 		let repo = Repository::open(Path::new(&name))?;
 		let reflog = repo.reflog("HEAD")?;
@@ -456,7 +502,7 @@ mod tests {
 			initial_endowment: Some("1u64 << 60".to_string()),
 			verify: false,
 		};
-		command.execute().await?;
+		command.execute(&mut MockCli::new()).await?;
 
 		// check for git_init
 		let repo = Repository::open(Path::new(&name))?;
@@ -468,13 +514,28 @@ mod tests {
 
 	#[test]
 	fn test_is_template_supported() -> Result<()> {
-		is_template_supported(&Provider::Pop, &Parachain::Standard)?;
-		assert!(is_template_supported(&Provider::Pop, &Parachain::ParityContracts).is_err());
-		assert!(is_template_supported(&Provider::Pop, &Parachain::ParityGeneric).is_err());
+		is_template_supported(&Provider::Pop, &Parachain::Standard, &mut MockCli::new())?;
+		assert!(is_template_supported(
+			&Provider::Pop,
+			&Parachain::ParityContracts,
+			&mut MockCli::new()
+		)
+		.is_err());
+		assert!(is_template_supported(
+			&Provider::Pop,
+			&Parachain::ParityGeneric,
+			&mut MockCli::new()
+		)
+		.is_err());
 
-		assert!(is_template_supported(&Provider::Parity, &Parachain::Standard).is_err());
-		is_template_supported(&Provider::Parity, &Parachain::ParityContracts)?;
-		is_template_supported(&Provider::Parity, &Parachain::ParityGeneric)
+		assert!(is_template_supported(
+			&Provider::Parity,
+			&Parachain::Standard,
+			&mut MockCli::new()
+		)
+		.is_err());
+		is_template_supported(&Provider::Parity, &Parachain::ParityContracts, &mut MockCli::new())?;
+		is_template_supported(&Provider::Parity, &Parachain::ParityGeneric, &mut MockCli::new())
 	}
 
 	#[test]
@@ -484,6 +545,7 @@ mod tests {
 			Some("DOT".to_string()),
 			Some(6),
 			Some("10000".to_string()),
+			&mut MockCli::new(),
 		)?;
 		assert_eq!(
 			config,
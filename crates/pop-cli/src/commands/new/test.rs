@@ -0,0 +1,254 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use clap::{Args, Subcommand};
+use pop_common::{add_crate_to_workspace, find_workspace_toml, prefix_with_current_dir_if_needed};
+use pop_parachains::{create_network_tests, create_runtime_tests, RuntimeTestsConfig};
+use std::{fs, path::PathBuf};
+
+/// Arguments for generating tests.
+#[derive(Args)]
+pub struct NewTestArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Generate tests for a project.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Generate zombienet-sdk tests for the network of a project, runnable via `pop test
+	/// network`
+	#[clap(alias = "n")]
+	Network(NewNetworkTestsCommand),
+	/// Generate unit tests for the runtime of a project, which execute blocks against a mock
+	/// relay chain
+	#[clap(alias = "r")]
+	Runtime(NewRuntimeTestsCommand),
+}
+
+#[derive(Args)]
+pub struct NewNetworkTestsCommand {
+	/// Path at which the tests will be created.
+	#[arg(default_value = "network-tests")]
+	pub(crate) path: PathBuf,
+	/// The Zombienet network configuration file of the project.
+	#[arg(short, long, default_value = "network.toml")]
+	pub(crate) file: PathBuf,
+}
+
+impl NewNetworkTestsCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> anyhow::Result<()> {
+		cli.intro("Generate network tests")?;
+		if !self.file.exists() {
+			cli.outro_cancel(format!(
+				"🚫 The network configuration file could not be found at {}.",
+				self.file.display()
+			))?;
+			return Ok(());
+		}
+
+		let path = prefix_with_current_dir_if_needed(self.path);
+		if path.exists() {
+			if !cli
+				.confirm(format!(
+					"\"{}\" directory already exists. Would you like to remove it?",
+					path.display()
+				))
+				.interact()?
+			{
+				cli.outro_cancel(format!(
+					"Cannot generate network tests until \"{}\" directory is removed.",
+					path.display()
+				))?;
+				return Ok(());
+			}
+			fs::remove_dir_all(&path)?;
+		}
+
+		let spinner = cli.spinner();
+		spinner.start("Generating network tests...");
+		if let Err(e) = create_network_tests(&path, &self.file) {
+			spinner.error(format!("🚫 Could not generate the network tests: {e}"));
+			return Err(e.into());
+		}
+		// If the tests have been created inside a workspace, add them to that workspace
+		if let Some(workspace_toml) = find_workspace_toml(&path) {
+			add_crate_to_workspace(&workspace_toml, &path)?;
+		}
+		spinner.stop("Generation complete");
+		cli.outro("Run `pop test network` to launch the network and run the tests! 🚀")?;
+		Ok(())
+	}
+}
+
+#[derive(Args)]
+pub struct NewRuntimeTestsCommand {
+	/// Path at which the tests will be created.
+	#[arg(default_value = "runtime-tests")]
+	pub(crate) path: PathBuf,
+	/// Path to the runtime of the project.
+	#[arg(short, long, default_value = "runtime")]
+	pub(crate) runtime: PathBuf,
+	/// The identifier of the parachain.
+	#[arg(long, default_value_t = 2000)]
+	pub(crate) para_id: u32,
+	/// The duration of a slot of the parachain (i.e. its block time), in milliseconds.
+	#[arg(long, default_value_t = 6000)]
+	pub(crate) slot_duration: u64,
+}
+
+impl NewRuntimeTestsCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> anyhow::Result<()> {
+		cli.intro("Generate runtime tests")?;
+		let path = prefix_with_current_dir_if_needed(self.path);
+		if path.exists() {
+			if !cli
+				.confirm(format!(
+					"\"{}\" directory already exists. Would you like to remove it?",
+					path.display()
+				))
+				.interact()?
+			{
+				cli.outro_cancel(format!(
+					"Cannot generate runtime tests until \"{}\" directory is removed.",
+					path.display()
+				))?;
+				return Ok(());
+			}
+			fs::remove_dir_all(&path)?;
+		}
+
+		let spinner = cli.spinner();
+		spinner.start("Generating runtime tests...");
+		let config = RuntimeTestsConfig {
+			runtime: self.runtime,
+			para_id: self.para_id,
+			slot_duration: self.slot_duration,
+		};
+		if let Err(e) = create_runtime_tests(&path, config) {
+			spinner.error(format!("🚫 Could not generate the runtime tests: {e}"));
+			return Err(e.into());
+		}
+		// If the tests have been created inside a workspace, add them to that workspace
+		if let Some(workspace_toml) = find_workspace_toml(&path) {
+			add_crate_to_workspace(&workspace_toml, &path)?;
+		}
+		spinner.stop("Generation complete");
+		cli.outro(format!(
+			"cd into \"{}\" and run `cargo test` to test your runtime against a mock relay chain! 🚀",
+			path.display()
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+
+	#[test]
+	fn new_network_tests_args_works() -> anyhow::Result<()> {
+		let pop =
+			crate::Cli::try_parse_from(["pop", "new", "test", "network", "-f", "zombie.toml"])?;
+		let crate::commands::Command::New(args) = pop.command else {
+			panic!("expected the new command");
+		};
+		let super::super::Command::Test(NewTestArgs { command: Command::Network(command) }) =
+			args.command
+		else {
+			panic!("expected the new test network command");
+		};
+		assert_eq!(command.path, PathBuf::from("network-tests"));
+		assert_eq!(command.file, PathBuf::from("zombie.toml"));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn new_network_tests_works() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let file = temp_dir.path().join("network.toml");
+		fs::write(&file, "[relaychain]\nchain = \"paseo-local\"\n\n[[parachains]]\nid = 2000\n")?;
+		let path = temp_dir.path().join("network-tests");
+		let mut cli = MockCli::new()
+			.expect_intro("Generate network tests")
+			.expect_outro("Run `pop test network` to launch the network and run the tests! 🚀");
+		NewNetworkTestsCommand { path: path.clone(), file }.execute(&mut cli).await?;
+		assert!(path.join("tests/network.rs").exists());
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn new_network_tests_requires_network_config() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let file = temp_dir.path().join("network.toml");
+		let mut cli =
+			MockCli::new()
+				.expect_intro("Generate network tests")
+				.expect_outro_cancel(format!(
+					"🚫 The network configuration file could not be found at {}.",
+					file.display()
+				));
+		NewNetworkTestsCommand { path: temp_dir.path().join("network-tests"), file }
+			.execute(&mut cli)
+			.await?;
+		cli.verify()
+	}
+
+	#[test]
+	fn new_runtime_tests_args_works() -> anyhow::Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"new",
+			"test",
+			"runtime",
+			"--runtime",
+			"runtimes/devnet",
+			"--slot-duration",
+			"12000",
+		])?;
+		let crate::commands::Command::New(args) = pop.command else {
+			panic!("expected the new command");
+		};
+		let super::super::Command::Test(NewTestArgs { command: Command::Runtime(command) }) =
+			args.command
+		else {
+			panic!("expected the new test runtime command");
+		};
+		assert_eq!(command.path, PathBuf::from("runtime-tests"));
+		assert_eq!(command.runtime, PathBuf::from("runtimes/devnet"));
+		assert_eq!(command.para_id, 2000);
+		assert_eq!(command.slot_duration, 12000);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn new_runtime_tests_works() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let runtime = temp_dir.path().join("runtime");
+		fs::create_dir(&runtime)?;
+		fs::write(
+			runtime.join("Cargo.toml"),
+			"[package]\nname = \"my-runtime\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+		)?;
+		let path = temp_dir.path().join("runtime-tests");
+		let mut cli = MockCli::new().expect_intro("Generate runtime tests").expect_outro(format!(
+			"cd into \"{}\" and run `cargo test` to test your runtime against a mock relay chain! 🚀",
+			path.display()
+		));
+		NewRuntimeTestsCommand { path: path.clone(), runtime, para_id: 2000, slot_duration: 6000 }
+			.execute(&mut cli)
+			.await?;
+		assert!(path.join("src/mock_relay.rs").exists());
+		cli.verify()
+	}
+}
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::anyhow;
+use clap::Args;
+use pop_common::{add_crate_to_workspace, find_workspace_toml, prefix_with_current_dir_if_needed};
+use pop_parachains::{create_xcm_simulator_tests, XcmSimulatorConfig};
+use std::{fs, path::PathBuf};
+
+#[derive(Args)]
+pub struct NewXcmSimulatorCommand {
+	/// Path at which the tests will be created.
+	#[arg(default_value = "xcm-tests")]
+	pub(crate) path: PathBuf,
+	/// Path to the runtime of the project.
+	#[arg(short, long, default_value = "runtime")]
+	pub(crate) runtime: PathBuf,
+	/// Path to the XCM configuration within the runtime.
+	#[arg(short, long, default_value = "configs::xcm_config::XcmConfig")]
+	pub(crate) xcm_config: String,
+	/// The identifiers of the two parachains, separated by a comma.
+	#[arg(long, value_delimiter = ',', default_values_t = [2000, 2001])]
+	pub(crate) para_ids: Vec<u32>,
+}
+
+impl NewXcmSimulatorCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> anyhow::Result<()> {
+		cli.intro("Generate XCM simulator tests")?;
+		let [para_a, para_b] = self.para_ids[..] else {
+			return Err(anyhow!("exactly two parachain identifiers are required"));
+		};
+		if para_a == para_b {
+			cli.outro_cancel("🚫 The parachain identifiers must be unique.")?;
+			return Ok(());
+		}
+
+		let path = prefix_with_current_dir_if_needed(self.path);
+		if path.exists() {
+			if !cli
+				.confirm(format!(
+					"\"{}\" directory already exists. Would you like to remove it?",
+					path.display()
+				))
+				.interact()?
+			{
+				cli.outro_cancel(format!(
+					"Cannot generate XCM simulator tests until \"{}\" directory is removed.",
+					path.display()
+				))?;
+				return Ok(());
+			}
+			fs::remove_dir_all(&path)?;
+		}
+
+		let spinner = cli.spinner();
+		spinner.start("Generating XCM simulator tests...");
+		let config = XcmSimulatorConfig {
+			runtime: self.runtime,
+			xcm_config: self.xcm_config,
+			para_ids: (para_a, para_b),
+		};
+		if let Err(e) = create_xcm_simulator_tests(&path, config) {
+			spinner.error(format!("🚫 Could not generate the XCM simulator tests: {e}"));
+			return Err(e.into());
+		}
+		// If the tests have been created inside a workspace, add them to that workspace
+		if let Some(workspace_toml) = find_workspace_toml(&path) {
+			add_crate_to_workspace(&workspace_toml, &path)?;
+		}
+		spinner.stop("Generation complete");
+		cli.outro(format!(
+			"cd into \"{}\" and run `cargo test` to test your cross-chain messages! 🚀",
+			path.display()
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+
+	#[test]
+	fn new_xcm_simulator_args_works() -> anyhow::Result<()> {
+		let pop = crate::Cli::try_parse_from(["pop", "new", "xcm-simulator"])?;
+		let crate::commands::Command::New(args) = pop.command else {
+			panic!("expected the new command");
+		};
+		let super::super::Command::XcmSimulator(command) = args.command else {
+			panic!("expected the xcm-simulator command");
+		};
+		assert_eq!(command.path, PathBuf::from("xcm-tests"));
+		assert_eq!(command.runtime, PathBuf::from("runtime"));
+		assert_eq!(command.xcm_config, "configs::xcm_config::XcmConfig");
+		assert_eq!(command.para_ids, vec![2000, 2001]);
+
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"new",
+			"xcm-simulator",
+			"tests/xcm",
+			"--runtime",
+			"runtimes/devnet",
+			"--para-ids",
+			"1000,1001",
+		])?;
+		let crate::commands::Command::New(args) = pop.command else {
+			panic!("expected the new command");
+		};
+		let super::super::Command::XcmSimulator(command) = args.command else {
+			panic!("expected the xcm-simulator command");
+		};
+		assert_eq!(command.path, PathBuf::from("tests/xcm"));
+		assert_eq!(command.runtime, PathBuf::from("runtimes/devnet"));
+		assert_eq!(command.para_ids, vec![1000, 1001]);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn new_xcm_simulator_works() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let runtime = temp_dir.path().join("runtime");
+		fs::create_dir(&runtime)?;
+		fs::write(
+			runtime.join("Cargo.toml"),
+			"[package]\nname = \"my-runtime\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+		)?;
+		let path = temp_dir.path().join("xcm-tests");
+		let mut cli =
+			MockCli::new()
+				.expect_intro("Generate XCM simulator tests")
+				.expect_outro(format!(
+					"cd into \"{}\" and run `cargo test` to test your cross-chain messages! 🚀",
+					path.display()
+				));
+		NewXcmSimulatorCommand {
+			path: path.clone(),
+			runtime,
+			xcm_config: "configs::xcm_config::XcmConfig".into(),
+			para_ids: vec![2000, 2001],
+		}
+		.execute(&mut cli)
+		.await?;
+		assert!(path.join("src/relay_chain.rs").exists());
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn new_xcm_simulator_requires_unique_para_ids() -> anyhow::Result<()> {
+		let mut cli = MockCli::new()
+			.expect_intro("Generate XCM simulator tests")
+			.expect_outro_cancel("🚫 The parachain identifiers must be unique.");
+		NewXcmSimulatorCommand {
+			path: PathBuf::from("xcm-tests"),
+			runtime: PathBuf::from("runtime"),
+			xcm_config: "configs::xcm_config::XcmConfig".into(),
+			para_ids: vec![2000, 2000],
+		}
+		.execute(&mut cli)
+		.await?;
+		cli.verify()
+	}
+}
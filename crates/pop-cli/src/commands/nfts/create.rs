@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, MetadataArgs, DEFAULT_URL};
+use crate::{cli::traits::*, commands::assets::prompt_for_account, common::signer::SignerArgs};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_parachains::{create_collection, created_collection};
+use url::Url;
+
+/// Command to create a collection.
+#[derive(Args)]
+pub(crate) struct CreateCommand {
+	/// The account administering the collection [default: prompted].
+	#[arg(long)]
+	pub(crate) admin: Option<String>,
+	/// The maximum number of items in the collection [default: unlimited].
+	#[arg(long)]
+	pub(crate) max_supply: Option<u32>,
+	#[command(flatten)]
+	pub(crate) metadata: MetadataArgs,
+	/// Websocket endpoint of a node.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl CreateCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Create a collection")?;
+		let client = connect(&self.url).await?;
+		let admin = prompt_for_account(
+			self.admin.as_deref(),
+			"Enter the account administering the collection:",
+			cli,
+		)?;
+		let xt = create_collection(&admin, self.max_supply);
+		let Some(events) =
+			self.signer.submit(&client, &self.url, xt, "create the collection", cli).await?
+		else {
+			return Ok(());
+		};
+		let collection = created_collection(&events)?.ok_or_else(|| {
+			anyhow!("The collection was created, but its identifier was not found in the events.")
+		})?;
+		cli.info(format!("Collection {collection} created."))?;
+		if !self
+			.metadata
+			.submit(&client, &self.url, collection, None, &mut self.signer, cli)
+			.await?
+		{
+			return Ok(());
+		}
+		cli.outro(format!(
+			"Mint items of collection {collection} with `pop nfts mint --collection {collection}`."
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn create_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"nfts",
+			"create",
+			"--max-supply",
+			"100",
+			"--metadata",
+			"ipfs://collection",
+			"--attributes",
+			"attributes.json",
+		])?;
+		let crate::commands::Command::Nfts(args) = pop.command else {
+			panic!("expected the nfts command");
+		};
+		let super::super::Command::Create(command) = args.command else {
+			panic!("expected the create command");
+		};
+		assert_eq!((command.admin, command.max_supply), (None, Some(100)));
+		assert_eq!(command.metadata.metadata.as_deref(), Some("ipfs://collection"));
+		assert_eq!(command.metadata.attributes.as_deref(), Some("attributes.json"));
+		assert_eq!(command.url, Url::parse(DEFAULT_URL)?);
+		Ok(())
+	}
+}
@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, prompt_for_id, MetadataArgs, DEFAULT_URL};
+use crate::{cli::traits::*, common::signer::SignerArgs};
+use anyhow::Result;
+use clap::Args;
+use url::Url;
+
+/// Command to set the metadata and attributes of an item or collection.
+#[derive(Args)]
+pub(crate) struct MetadataCommand {
+	/// The identifier of the collection [default: prompted].
+	#[arg(long)]
+	pub(crate) collection: Option<u32>,
+	/// The identifier of the item, if not setting those of the collection itself.
+	#[arg(long)]
+	pub(crate) item: Option<u32>,
+	#[command(flatten)]
+	pub(crate) metadata: MetadataArgs,
+	/// Websocket endpoint of a node.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl MetadataCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Set the metadata of an item")?;
+		let collection =
+			prompt_for_id(self.collection, "Enter the identifier of the collection:", cli)?;
+		if self.metadata.is_empty() {
+			let metadata = cli
+				.input("Enter the metadata:")
+				.placeholder("e.g. ipfs://... or the path to a file containing the metadata")
+				.interact()?;
+			self.metadata.metadata = Some(metadata);
+		}
+		let client = connect(&self.url).await?;
+		if !self
+			.metadata
+			.submit(&client, &self.url, collection, self.item, &mut self.signer, cli)
+			.await?
+		{
+			return Ok(());
+		}
+		match self.item {
+			Some(item) =>
+				cli.outro(format!("Metadata of item {item} of collection {collection} set."))?,
+			None => cli.outro(format!("Metadata of collection {collection} set."))?,
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn metadata_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"nfts",
+			"metadata",
+			"--collection",
+			"0",
+			"--attributes",
+			"attributes.json",
+		])?;
+		let crate::commands::Command::Nfts(args) = pop.command else {
+			panic!("expected the nfts command");
+		};
+		let super::super::Command::Metadata(command) = args.command else {
+			panic!("expected the metadata command");
+		};
+		assert_eq!((command.collection, command.item), (Some(0), None));
+		assert_eq!(command.metadata.metadata, None);
+		assert_eq!(command.metadata.attributes.as_deref(), Some("attributes.json"));
+		Ok(())
+	}
+}
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, prompt_for_id, MetadataArgs, DEFAULT_URL};
+use crate::{cli::traits::*, commands::assets::prompt_for_account, common::signer::SignerArgs};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::mint_item;
+use url::Url;
+
+/// Command to mint an item of a collection.
+#[derive(Args)]
+pub(crate) struct MintCommand {
+	/// The identifier of the collection [default: prompted].
+	#[arg(long)]
+	pub(crate) collection: Option<u32>,
+	/// The identifier of the item [default: prompted].
+	#[arg(long)]
+	pub(crate) item: Option<u32>,
+	/// The account receiving the item [default: prompted].
+	#[arg(long)]
+	pub(crate) to: Option<String>,
+	#[command(flatten)]
+	pub(crate) metadata: MetadataArgs,
+	/// Websocket endpoint of a node.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl MintCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Mint an item")?;
+		let client = connect(&self.url).await?;
+		let collection =
+			prompt_for_id(self.collection, "Enter the identifier of the collection:", cli)?;
+		let item = prompt_for_id(self.item, "Enter the identifier of the item:", cli)?;
+		let to =
+			prompt_for_account(self.to.as_deref(), "Enter the account receiving the item:", cli)?;
+		let xt = mint_item(collection, item, &to);
+		if self
+			.signer
+			.submit(&client, &self.url, xt, "mint the item", cli)
+			.await?
+			.is_none()
+		{
+			return Ok(());
+		}
+		if !self
+			.metadata
+			.submit(&client, &self.url, collection, Some(item), &mut self.signer, cli)
+			.await?
+		{
+			return Ok(());
+		}
+		cli.outro(format!("Item {item} of collection {collection} minted to {to}."))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn mint_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"nfts",
+			"m",
+			"--collection",
+			"0",
+			"--item",
+			"1",
+			"--to",
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+			"-y",
+		])?;
+		let crate::commands::Command::Nfts(args) = pop.command else {
+			panic!("expected the nfts command");
+		};
+		let super::super::Command::Mint(command) = args.command else {
+			panic!("expected the mint command");
+		};
+		assert_eq!((command.collection, command.item), (Some(0), Some(1)));
+		assert!(command.metadata.is_empty());
+		assert!(command.signer.skip_confirm);
+		Ok(())
+	}
+}
@@ -0,0 +1,190 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	cli::traits::*,
+	common::{network, signer::SignerArgs},
+};
+use anyhow::{anyhow, Result};
+use clap::{Args, Subcommand};
+use pop_parachains::{
+	parse_attributes, set_nft_attribute, set_nft_metadata, set_up_client, OnlineClient,
+	SubstrateConfig,
+};
+use std::{fs, path::Path};
+use url::Url;
+
+pub(crate) mod create;
+pub(crate) mod metadata;
+pub(crate) mod mint;
+pub(crate) mod transfer;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+/// Arguments for managing NFTs.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct NftsArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Manage collections and their items via the NFTs pallet of a chain.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Create a collection, optionally along with its metadata and attributes.
+	#[clap(alias = "c")]
+	Create(create::CreateCommand),
+	/// Mint an item of a collection, optionally along with its metadata and attributes.
+	#[clap(alias = "m")]
+	Mint(mint::MintCommand),
+	/// Set the metadata and attributes of an item, or of a collection itself.
+	#[clap(alias = "md")]
+	Metadata(metadata::MetadataCommand),
+	/// Transfer an item of a collection to an account.
+	#[clap(alias = "t")]
+	Transfer(transfer::TransferCommand),
+}
+
+/// Arguments for the metadata and attributes of an item or collection.
+#[derive(Args, Clone, Default)]
+pub(crate) struct MetadataArgs {
+	/// The metadata, e.g. a link to a JSON file describing it, or the path to a file containing
+	/// the metadata.
+	#[arg(long)]
+	pub(crate) metadata: Option<String>,
+	/// The path to a JSON file containing the attributes, e.g. `{"color": "red"}`.
+	#[arg(long)]
+	pub(crate) attributes: Option<String>,
+}
+
+impl MetadataArgs {
+	/// Whether any metadata or attributes were specified.
+	pub(crate) fn is_empty(&self) -> bool {
+		self.metadata.is_none() && self.attributes.is_none()
+	}
+
+	/// Signs and submits the calls setting the specified metadata and attributes. Returns whether
+	/// all the calls were submitted.
+	///
+	/// # Arguments
+	/// * `client` - The client used to interact with the chain.
+	/// * `url` - The endpoint of the chain.
+	/// * `collection` - The identifier of the collection.
+	/// * `item` - The identifier of the item, if any.
+	/// * `signer` - The signer of the calls.
+	/// * `cli` - The cli.
+	pub(crate) async fn submit(
+		&self,
+		client: &OnlineClient<SubstrateConfig>,
+		url: &Url,
+		collection: u32,
+		item: Option<u32>,
+		signer: &mut SignerArgs,
+		cli: &mut impl Cli,
+	) -> Result<bool> {
+		if let Some(metadata) = &self.metadata {
+			let xt = set_nft_metadata(collection, item, &read_metadata(metadata)?);
+			if signer.submit(client, url, xt, "set the metadata", cli).await?.is_none() {
+				return Ok(false);
+			}
+		}
+		for (key, value) in self.attributes()? {
+			let xt = set_nft_attribute(collection, item, &key, &value);
+			if signer
+				.submit(client, url, xt, &format!("set the {key} attribute"), cli)
+				.await?
+				.is_none()
+			{
+				return Ok(false);
+			}
+		}
+		Ok(true)
+	}
+
+	// The attributes read from the specified file, if any.
+	fn attributes(&self) -> Result<Vec<(String, String)>> {
+		let Some(path) = &self.attributes else { return Ok(vec![]) };
+		let contents = fs::read_to_string(path)
+			.map_err(|e| anyhow!("Failed to read the attributes {path}: {e}"))?;
+		parse_attributes(&contents).map_err(|e| anyhow!("Invalid attributes {path}: {e}"))
+	}
+}
+
+/// Connects to the chain.
+///
+/// # Arguments
+/// * `url` - The endpoint of the chain.
+pub(crate) async fn connect(url: &Url) -> Result<OnlineClient<SubstrateConfig>> {
+	network::retry("connecting to the chain", || set_up_client(url.as_str())).await
+}
+
+/// The identifier of a collection or item, prompting the user if none specified.
+///
+/// # Arguments
+/// * `id` - The identifier, if specified.
+/// * `prompt` - The prompt for the identifier.
+/// * `cli` - The cli.
+pub(crate) fn prompt_for_id(id: Option<u32>, prompt: &str, cli: &mut impl Cli) -> Result<u32> {
+	match id {
+		Some(id) => Ok(id),
+		None => Ok(cli
+			.input(prompt)
+			.validate(|input| match input.parse::<u32>() {
+				Ok(_) => Ok(()),
+				_ => Err("Invalid identifier."),
+			})
+			.interact()?
+			.parse()?),
+	}
+}
+
+// The metadata, read from a file if it is the path to one.
+fn read_metadata(metadata: &str) -> Result<Vec<u8>> {
+	match Path::new(metadata).is_file() {
+		true =>
+			fs::read(metadata).map_err(|e| anyhow!("Failed to read the metadata {metadata}: {e}")),
+		false => Ok(metadata.as_bytes().to_vec()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+
+	#[test]
+	fn read_metadata_works() -> Result<()> {
+		assert_eq!(read_metadata("ipfs://item")?, b"ipfs://item");
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("metadata.json");
+		fs::write(&path, r#"{"name": "Pop"}"#)?;
+		assert_eq!(read_metadata(path.to_str().unwrap())?, br#"{"name": "Pop"}"#);
+		Ok(())
+	}
+
+	#[test]
+	fn attributes_works() -> Result<()> {
+		assert!(MetadataArgs::default().is_empty());
+		assert!(MetadataArgs::default().attributes()?.is_empty());
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("attributes.json");
+		fs::write(&path, r#"{"color": "red", "size": 3}"#)?;
+		let args = MetadataArgs { metadata: None, attributes: Some(path.display().to_string()) };
+		assert!(!args.is_empty());
+		assert_eq!(
+			args.attributes()?,
+			vec![("color".into(), "red".into()), ("size".into(), "3".into())]
+		);
+		fs::write(&path, "[]")?;
+		assert!(args.attributes().unwrap_err().to_string().starts_with("Invalid attributes"));
+		Ok(())
+	}
+
+	#[test]
+	fn prompt_for_id_works() -> Result<()> {
+		let mut cli = MockCli::new().expect_input("Enter the collection:", "3".into());
+		assert_eq!(prompt_for_id(None, "Enter the collection:", &mut cli)?, 3);
+		assert_eq!(prompt_for_id(Some(4), "Enter the collection:", &mut cli)?, 4);
+		cli.verify()
+	}
+}
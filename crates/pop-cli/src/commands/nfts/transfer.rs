@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{connect, prompt_for_id, DEFAULT_URL};
+use crate::{cli::traits::*, commands::assets::prompt_for_account, common::signer::SignerArgs};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::transfer_item;
+use url::Url;
+
+/// Command to transfer an item of a collection.
+#[derive(Args)]
+pub(crate) struct TransferCommand {
+	/// The identifier of the collection [default: prompted].
+	#[arg(long)]
+	pub(crate) collection: Option<u32>,
+	/// The identifier of the item [default: prompted].
+	#[arg(long)]
+	pub(crate) item: Option<u32>,
+	/// The account receiving the item [default: prompted].
+	#[arg(long)]
+	pub(crate) to: Option<String>,
+	/// Websocket endpoint of a node.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl TransferCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Transfer an item")?;
+		let client = connect(&self.url).await?;
+		let collection =
+			prompt_for_id(self.collection, "Enter the identifier of the collection:", cli)?;
+		let item = prompt_for_id(self.item, "Enter the identifier of the item:", cli)?;
+		let to =
+			prompt_for_account(self.to.as_deref(), "Enter the account receiving the item:", cli)?;
+		let xt = transfer_item(collection, item, &to);
+		if self
+			.signer
+			.submit(&client, &self.url, xt, "transfer the item", cli)
+			.await?
+			.is_none()
+		{
+			return Ok(());
+		}
+		cli.outro(format!("Item {item} of collection {collection} transferred to {to}."))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn transfer_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"nfts",
+			"transfer",
+			"--collection",
+			"0",
+			"--item",
+			"1",
+			"--url",
+			"ws://127.0.0.1:9944",
+		])?;
+		let crate::commands::Command::Nfts(args) = pop.command else {
+			panic!("expected the nfts command");
+		};
+		let super::super::Command::Transfer(command) = args.command else {
+			panic!("expected the transfer command");
+		};
+		assert_eq!((command.collection, command.item, command.to), (Some(0), Some(1), None));
+		assert_eq!(command.url, Url::parse("ws://127.0.0.1:9944")?);
+		Ok(())
+	}
+}
@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::network};
+use anyhow::Result;
+use clap::{
+	builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+	Args,
+};
+use pop_common::enum_variants;
+use pop_parachains::{registered_assets, set_up_client, AssetId, Chain, RegisteredAsset};
+use std::str::FromStr;
+use strum::VariantArray;
+use url::Url;
+
+/// Command to list the assets registered on chains.
+#[derive(Args, Default)]
+pub(crate) struct QueryAssetsCommand {
+	/// The chains to be queried, separated by commas.
+	#[arg(long = "chain", value_delimiter = ',', value_parser = enum_variants!(Chain))]
+	pub(crate) chains: Vec<Chain>,
+	/// Websocket endpoint of a node of a chain to be queried instead.
+	#[arg(short, long, value_parser, conflicts_with = "chains")]
+	pub(crate) url: Option<Url>,
+}
+
+impl QueryAssetsCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Query registered assets")?;
+		let endpoints = self.endpoints(cli)?;
+		let spinner = cli.spinner();
+		for (name, url) in endpoints {
+			spinner.start(format!("Querying the assets registered on {name}..."));
+			let client =
+				network::retry("connecting to the chain", || set_up_client(url.as_str())).await?;
+			let assets = registered_assets(&client).await;
+			spinner.clear();
+			match assets {
+				Ok(assets) if assets.is_empty() =>
+					cli.warning(format!("No assets are registered on {name}."))?,
+				Ok(assets) =>
+					cli.info(format!("Assets registered on {name}:\n{}", table(&assets)))?,
+				Err(e) => cli.warning(format!("The assets of {name} could not be queried: {e}"))?,
+			}
+		}
+		cli.outro("Query complete.")?;
+		Ok(())
+	}
+
+	// The names and endpoints of the chains to be queried, prompting the user if none specified.
+	fn endpoints(&self, cli: &mut impl Cli) -> Result<Vec<(String, Url)>> {
+		if let Some(url) = &self.url {
+			let name = Chain::from_url(url.as_str())
+				.map(|c| c.name().to_string())
+				.unwrap_or_else(|| url.to_string());
+			return Ok(vec![(name, url.clone())]);
+		}
+		let chains = match self.chains.is_empty() {
+			true => {
+				let mut prompt = cli.select("Select the chain to be queried:");
+				// Assets are only registered on Polkadot SDK chains.
+				for chain in Chain::VARIANTS.iter().filter(|c| !c.is_ethereum()) {
+					prompt = prompt.item(*chain, chain.name(), chain.url());
+				}
+				vec![prompt.interact()?]
+			},
+			false => self.chains.clone(),
+		};
+		chains
+			.into_iter()
+			.map(|c| Ok((c.name().to_string(), Url::parse(c.url())?)))
+			.collect()
+	}
+}
+
+// Formats the assets as a table, with any foreign assets listed separately by location.
+fn table(assets: &[RegisteredAsset]) -> String {
+	let (local, foreign): (Vec<_>, Vec<_>) =
+		assets.iter().partition(|a| matches!(a.id, AssetId::Index(_)));
+	let mut rows = vec![[
+		"ID".to_string(),
+		"SYMBOL".into(),
+		"DECIMALS".into(),
+		"MIN BALANCE".into(),
+		"NAME".into(),
+	]];
+	rows.extend(local.iter().map(|a| {
+		[
+			a.id.to_string(),
+			a.symbol.clone(),
+			a.decimals.to_string(),
+			a.min_balance.to_string(),
+			a.name.clone(),
+		]
+	}));
+	let widths: Vec<usize> = (0..4)
+		.map(|i| rows.iter().map(|r| r[i].len()).max().unwrap_or_default())
+		.collect();
+	let mut output = rows
+		.iter()
+		.map(|row| {
+			let mut line = String::new();
+			for (i, width) in widths.iter().enumerate() {
+				line.push_str(&format!("{:<width$}  ", row[i]));
+			}
+			line.push_str(&row[4]);
+			line.trim_end().to_string()
+		})
+		.collect::<Vec<_>>()
+		.join("\n");
+	if !foreign.is_empty() {
+		output.push_str("\nForeign assets:");
+		for asset in foreign {
+			let symbol = match asset.symbol.is_empty() {
+				true => "?",
+				false => &asset.symbol,
+			};
+			output.push_str(&format!(
+				"\n  {symbol} (decimals: {}, min balance: {}): {}",
+				asset.decimals, asset.min_balance, asset.location
+			));
+		}
+	}
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use pop_parachains::Value;
+
+	fn asset(id: u128, symbol: &str, decimals: u8, min_balance: u128) -> RegisteredAsset {
+		RegisteredAsset {
+			id: AssetId::Index(id),
+			name: format!("{symbol} token"),
+			symbol: symbol.into(),
+			decimals,
+			min_balance,
+			location: Value::u128(id),
+		}
+	}
+
+	#[test]
+	fn query_assets_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"query",
+			"assets",
+			"--chain",
+			"assethub-paseo,pop-paseo",
+		])?;
+		let crate::commands::Command::Query(args) = pop.command else {
+			panic!("expected the query command");
+		};
+		let super::super::Command::Assets(command) = args.command else {
+			panic!("expected the assets command");
+		};
+		assert_eq!(command.chains, vec![Chain::AssetHubPaseo, Chain::PopPaseo]);
+		assert!(
+			crate::Cli::try_parse_from(["pop", "query", "assets", "--chain", "moonbeam"]).is_err()
+		);
+		assert!(crate::Cli::try_parse_from([
+			"pop",
+			"q",
+			"a",
+			"--chain",
+			"paseo",
+			"--url",
+			"ws://localhost:9944"
+		])
+		.is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn endpoints_works() -> Result<()> {
+		let mut cli = MockCli::new();
+		let command = QueryAssetsCommand {
+			chains: vec![Chain::AssetHubPaseo, Chain::PopPaseo],
+			..Default::default()
+		};
+		assert_eq!(
+			command.endpoints(&mut cli)?,
+			vec![
+				("Asset Hub (Paseo)".to_string(), Url::parse(Chain::AssetHubPaseo.url())?),
+				("Pop Network (Paseo)".to_string(), Url::parse(Chain::PopPaseo.url())?),
+			]
+		);
+
+		let url = Url::parse("ws://localhost:9944")?;
+		let command = QueryAssetsCommand { url: Some(url.clone()), ..Default::default() };
+		assert_eq!(command.endpoints(&mut cli)?, vec![(url.to_string(), url)]);
+		cli.verify()?;
+
+		let items = Chain::VARIANTS
+			.iter()
+			.filter(|c| !c.is_ethereum())
+			.map(|c| (c.name().to_string(), c.url().to_string()))
+			.collect();
+		let mut cli = MockCli::new().expect_select(
+			"Select the chain to be queried:",
+			Some(true),
+			true,
+			Some(items),
+			6,
+		);
+		assert_eq!(
+			QueryAssetsCommand::default().endpoints(&mut cli)?,
+			vec![("Asset Hub (Paseo)".to_string(), Url::parse(Chain::AssetHubPaseo.url())?)]
+		);
+		cli.verify()
+	}
+
+	#[test]
+	fn table_works() {
+		let mut foreign = asset(0, "", 0, 1);
+		foreign.id = AssetId::Location(Value::u128(0));
+		let assets = [asset(1984, "USDt", 6, 70_000), asset(7, "WETH", 18, 1), foreign];
+		assert_eq!(
+			table(&assets),
+			"ID    SYMBOL  DECIMALS  MIN BALANCE  NAME\n\
+			 1984  USDt    6         70000        USDt token\n\
+			 7     WETH    18        1            WETH token\n\
+			 Foreign assets:\n  \
+			 ? (decimals: 0, min balance: 1): 0"
+		);
+	}
+}
@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::block_time;
+use crate::{cli::traits::*, common::network};
+use anyhow::Result;
+use clap::Args;
+use pop_common::units::format_units;
+use pop_parachains::{block_times, chain_info, ChainInfo, Connection};
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+/// The number of recent blocks from which block times are measured.
+const BLOCKS: usize = 10;
+
+/// Command to summarize a chain.
+#[derive(Args)]
+pub(crate) struct QueryChainCommand {
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+}
+
+impl QueryChainCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Query chain")?;
+		let spinner = cli.spinner();
+		spinner.start(format!("Querying the chain at {}...", self.url));
+		let connection = network::retry("connecting to the chain", || {
+			Connection::connect(self.url.as_str(), None)
+		})
+		.await?;
+		let client = connection.client();
+		let info = match chain_info(&connection).await {
+			Ok(info) => info,
+			Err(e) => {
+				spinner.clear();
+				cli.outro_cancel(format!("🚫 The chain could not be queried: {e}"))?;
+				return Ok(());
+			},
+		};
+		spinner.set_message("Measuring the block times...");
+		let times = block_times(&client, BLOCKS).await.unwrap_or_default();
+		spinner.clear();
+		cli.info(describe(&info, &times))?;
+		cli.outro("Query complete.")?;
+		Ok(())
+	}
+}
+
+// Describes a chain and its runtime.
+fn describe(info: &ChainInfo, times: &[u64]) -> String {
+	let unknown = || "unknown".to_string();
+	let name = |name: &Option<String>, version: String| match name {
+		Some(name) => format!("{name} v{version}"),
+		None => format!("v{version}"),
+	};
+	let mut output = format!("{}:", info.name);
+	output.push_str(&format!(
+		"\n  Runtime: {}, transaction version {}",
+		name(&info.spec_name, info.spec_version.to_string()),
+		info.transaction_version
+	));
+	if let Some(version) = info.impl_version {
+		output.push_str(&format!(
+			"\n  Implementation: {}",
+			name(&info.impl_name, version.to_string())
+		));
+	}
+	let token = match (&info.token_symbol, info.token_decimals) {
+		(Some(symbol), Some(decimals)) => format!("{symbol}, {decimals} decimals"),
+		(Some(symbol), None) => symbol.clone(),
+		(None, Some(decimals)) => format!("{decimals} decimals"),
+		(None, None) => unknown(),
+	};
+	output.push_str(&format!("\n  Token: {token}"));
+	output.push_str(&format!(
+		"\n  SS58 prefix: {}",
+		info.ss58_prefix.map(|p| p.to_string()).unwrap_or_else(unknown)
+	));
+	if let Some(deposit) = info.existential_deposit {
+		let mut line = format!("\n  Existential deposit: {deposit}");
+		match (info.token_decimals, &info.token_symbol) {
+			(Some(decimals), Some(symbol)) =>
+				line.push_str(&format!(" ({} {symbol})", format_units(deposit, decimals))),
+			(Some(decimals), None) =>
+				line.push_str(&format!(" ({})", format_units(deposit, decimals))),
+			(None, _) => {},
+		}
+		output.push_str(&line);
+	}
+	if let Some(block_time) = block_time(times) {
+		output.push_str(&format!("\n  Block time: {block_time}"));
+	}
+	let (number, hash) = info.finalized;
+	output.push_str(&format!("\n  Last finalized block: #{number} ({hash:?})"));
+	if !info.runtime_apis.is_empty() {
+		output.push_str(&format!("\n  Runtime APIs: {}", info.runtime_apis.join(", ")));
+	}
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+	use pop_parachains::H256;
+
+	#[test]
+	fn query_chain_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "query", "chain"].iter().chain(args))?;
+			let crate::commands::Command::Query(args) = pop.command else {
+				panic!("expected the query command");
+			};
+			let super::super::Command::Chain(command) = args.command else {
+				panic!("expected the chain command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		assert_eq!(parse(&[])?.url, Url::parse(DEFAULT_URL)?);
+		assert_eq!(parse(&["-u", "ws://localhost:8000"])?.url, Url::parse("ws://localhost:8000")?);
+		Ok(())
+	}
+
+	#[test]
+	fn describe_works() {
+		let info = ChainInfo {
+			name: "Paseo Testnet".into(),
+			spec_name: Some("paseo".into()),
+			spec_version: 1_004_001,
+			impl_name: Some("parity-paseo".into()),
+			impl_version: Some(0),
+			transaction_version: 26,
+			token_symbol: Some("PAS".into()),
+			token_decimals: Some(10),
+			ss58_prefix: Some(0),
+			existential_deposit: Some(10_000_000_000),
+			finalized: (42, H256::repeat_byte(1)),
+			runtime_apis: vec!["Core".into(), "Metadata".into()],
+		};
+		assert_eq!(
+			describe(&info, &[6_000, 6_000]),
+			format!(
+				"Paseo Testnet:\n  \
+				 Runtime: paseo v1004001, transaction version 26\n  \
+				 Implementation: parity-paseo v0\n  \
+				 Token: PAS, 10 decimals\n  \
+				 SS58 prefix: 0\n  \
+				 Existential deposit: 10000000000 (1 PAS)\n  \
+				 Block time: 6.0s, averaging 6.0s over the last 2 blocks\n  \
+				 Last finalized block: #42 (0x{})\n  \
+				 Runtime APIs: Core, Metadata",
+				"01".repeat(32)
+			)
+		);
+		let info = ChainInfo { name: "Dev".into(), ..Default::default() };
+		assert_eq!(
+			describe(&info, &[]),
+			format!(
+				"Dev:\n  \
+				 Runtime: v0, transaction version 0\n  \
+				 Token: unknown\n  \
+				 SS58 prefix: unknown\n  \
+				 Last finalized block: #0 (0x{})",
+				"00".repeat(32)
+			)
+		);
+	}
+}
@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::network};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{
+	call_fee, decode_call_data, fee_multipliers, set_up_client, transfer_call_data, AccountId32,
+	FeeDetails,
+};
+use std::str::FromStr;
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+/// The account of Alice, to which the standard transfer is made.
+const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+/// The precision of a fee multiplier, as a fixed-point number.
+const MULTIPLIER_PRECISION: f64 = 1_000_000_000_000_000_000.0;
+
+/// Command to estimate the fee of a call and show the recent fee multipliers of a chain.
+#[derive(Args)]
+pub(crate) struct QueryFeesCommand {
+	/// The SCALE-encoded call data to estimate the fee of [default: a transfer of the native
+	/// token].
+	#[arg(short, long)]
+	pub(crate) call: Option<String>,
+	/// The amount of the standard transfer, in the smallest unit of the token.
+	#[arg(short, long, default_value = "1000000000000", conflicts_with = "call")]
+	pub(crate) amount: u128,
+	/// The number of recent blocks over which the fee multiplier is shown.
+	#[arg(short, long, default_value = "10")]
+	pub(crate) blocks: usize,
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+}
+
+impl QueryFeesCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Query fees")?;
+		let spinner = cli.spinner();
+		spinner.start(format!("Querying the chain at {}...", self.url));
+		let client =
+			network::retry("connecting to the chain", || set_up_client(self.url.as_str())).await?;
+		let (label, call_data) = match &self.call {
+			Some(call) => ("the call".to_string(), decode_call_data(call)),
+			None => (
+				format!("a transfer of {}", self.amount),
+				transfer_call_data(&client, &AccountId32::from_str(ALICE)?, self.amount),
+			),
+		};
+		let fee = match call_data {
+			Ok(call_data) => call_fee(&client, &call_data).await,
+			Err(e) => Err(e),
+		};
+		let fee = match fee {
+			Ok(fee) => fee,
+			Err(e) => {
+				spinner.clear();
+				cli.outro_cancel(format!("🚫 The fee of {label} could not be estimated: {e}"))?;
+				return Ok(());
+			},
+		};
+		spinner.set_message("Fetching the recent fee multipliers...");
+		let multipliers = fee_multipliers(&client, self.blocks).await.unwrap_or_default();
+		spinner.clear();
+		cli.info(describe(&label, &fee, &multipliers))?;
+		cli.outro("Query complete.")?;
+		Ok(())
+	}
+}
+
+// Describes the estimated fee of a call, along with the recent fee multipliers.
+fn describe(label: &str, fee: &FeeDetails, multipliers: &[(u32, u128)]) -> String {
+	let mut output = format!(
+		"Estimated fee for {label}: {} (base {}, length {}, weight {})",
+		fee.total(),
+		fee.base_fee,
+		fee.len_fee,
+		fee.adjusted_weight_fee
+	);
+	let multiplier = |m: u128| m as f64 / MULTIPLIER_PRECISION;
+	if let (Some((_, latest)), Some((_, oldest))) = (multipliers.first(), multipliers.last()) {
+		output.push_str(&format!(
+			"\nFee multiplier over the last {} blocks: {:+.2}%",
+			multipliers.len(),
+			(multiplier(*latest) / multiplier(*oldest) - 1.0) * 100.0
+		));
+		for (number, m) in multipliers {
+			output.push_str(&format!("\n  #{number}: {:.6}", multiplier(*m)));
+		}
+	}
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn query_fees_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "query", "fees"].iter().chain(args))?;
+			let crate::commands::Command::Query(args) = pop.command else {
+				panic!("expected the query command");
+			};
+			let super::super::Command::Fees(command) = args.command else {
+				panic!("expected the fees command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&[])?;
+		assert_eq!((command.call, command.amount), (None, 1_000_000_000_000));
+		assert_eq!(command.blocks, 10);
+		assert_eq!(command.url, Url::parse(DEFAULT_URL)?);
+		let command = parse(&["-c", "0x0000", "-b", "5"])?;
+		assert_eq!((command.call.as_deref(), command.blocks), (Some("0x0000"), 5));
+		assert!(parse(&["-c", "0x0000", "-a", "1"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn describe_works() {
+		let fee = FeeDetails { base_fee: 100, len_fee: 20, adjusted_weight_fee: 3 };
+		let unit = MULTIPLIER_PRECISION as u128;
+		assert_eq!(
+			describe("a transfer of 1", &fee, &[(12, unit + unit / 40), (11, unit)]),
+			"Estimated fee for a transfer of 1: 123 (base 100, length 20, weight 3)\n\
+			 Fee multiplier over the last 2 blocks: +2.50%\n  \
+			 #12: 1.025000\n  \
+			 #11: 1.000000"
+		);
+		assert_eq!(
+			describe("the call", &fee, &[]),
+			"Estimated fee for the call: 123 (base 100, length 20, weight 3)"
+		);
+	}
+}
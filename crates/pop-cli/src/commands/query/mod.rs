@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use clap::{Args, Subcommand};
+
+pub(crate) mod assets;
+pub(crate) mod chain;
+pub(crate) mod fees;
+pub(crate) mod parachain;
+pub(crate) mod scheduled;
+
+/// Arguments for querying the state of a chain.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct QueryArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Query the state of a chain.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// List the assets registered on one or more chains
+	#[clap(alias = "a")]
+	Assets(assets::QueryAssetsCommand),
+	/// Show the state of a parachain on its relay chains, e.g. its head and cores
+	#[clap(alias = "p")]
+	Parachain(parachain::QueryParachainCommand),
+	/// Show a summary of a chain, e.g. its runtime version, token and runtime APIs
+	#[clap(alias = "c")]
+	Chain(chain::QueryChainCommand),
+	/// Estimate the fee of a call, along with the recent fee multipliers of a chain
+	#[clap(alias = "f")]
+	Fees(fees::QueryFeesCommand),
+	/// List the tasks scheduled via the scheduler pallet of a chain, optionally cancelling one
+	#[clap(alias = "s")]
+	Scheduled(scheduled::QueryScheduledCommand),
+}
+
+/// Describes the latest block time of a chain, along with the average over the measured blocks.
+///
+/// # Arguments
+/// * `times` - The intervals between the latest blocks, in milliseconds and starting with the most
+///   recent.
+pub(crate) fn block_time(times: &[u64]) -> Option<String> {
+	let latest = times.first()?;
+	let average = times.iter().sum::<u64>() / times.len() as u64;
+	let seconds = |ms: u64| ms as f64 / 1_000.0;
+	Some(format!(
+		"{:.1}s, averaging {:.1}s over the last {} blocks",
+		seconds(*latest),
+		seconds(average),
+		times.len()
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn block_time_works() {
+		assert_eq!(block_time(&[]), None);
+		assert_eq!(
+			block_time(&[6_000, 12_000, 6_000]).as_deref(),
+			Some("6.0s, averaging 8.0s over the last 3 blocks")
+		);
+	}
+}
@@ -0,0 +1,250 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::block_time;
+use crate::{cli::traits::*, common::network};
+use anyhow::{anyhow, Result};
+use clap::{
+	builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+	Args,
+};
+use pop_common::enum_variants;
+use pop_parachains::{block_times, parachain_info, set_up_client, Chain, ParachainInfo};
+use std::str::FromStr;
+use strum::VariantArray;
+
+/// The number of recent blocks from which block times are measured.
+const BLOCKS: usize = 10;
+
+/// Command to query the state of a parachain on relay chains.
+#[derive(Args, Default)]
+pub(crate) struct QueryParachainCommand {
+	/// The identifier of the parachain, or its name in the registry, e.g. `pop-paseo`.
+	pub(crate) parachain: String,
+	/// The relay chains to be queried, separated by commas [default: all known relay chains].
+	#[arg(long = "relay", value_delimiter = ',', value_parser = enum_variants!(Chain))]
+	pub(crate) relays: Vec<Chain>,
+}
+
+impl QueryParachainCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Query parachain")?;
+		let (para_id, relays) = match self.targets() {
+			Ok(targets) => targets,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 {e}"))?;
+				return Ok(());
+			},
+		};
+		let spinner = cli.spinner();
+		for relay in relays {
+			spinner.start(format!("Querying parachain {para_id} on {}...", relay.name()));
+			let client =
+				network::retry("connecting to the relay chain", || set_up_client(relay.url()))
+					.await?;
+			let info = match parachain_info(&client, para_id).await {
+				Ok(info) if info.lifecycle.is_some() => info,
+				Ok(_) => {
+					spinner.clear();
+					cli.info(format!(
+						"Parachain {para_id} is not registered on {}.",
+						relay.name()
+					))?;
+					continue;
+				},
+				Err(e) => {
+					spinner.clear();
+					cli.warning(format!(
+						"Parachain {para_id} could not be queried on {}: {e}",
+						relay.name()
+					))?;
+					continue;
+				},
+			};
+			// Block times are measured on the parachain itself, so require a known endpoint.
+			let chain = Chain::VARIANTS
+				.iter()
+				.find(|c| c.relay() == Some(relay) && c.para_id() == Some(para_id))
+				.copied();
+			let times = match chain {
+				Some(chain) => {
+					spinner
+						.set_message(format!("Measuring the block times of {}...", chain.name()));
+					match set_up_client(chain.url()).await {
+						Ok(client) => block_times(&client, BLOCKS).await.unwrap_or_default(),
+						Err(_) => vec![],
+					}
+				},
+				None => vec![],
+			};
+			spinner.clear();
+			cli.info(describe(&relay, &info, chain.as_ref(), &times))?;
+		}
+		cli.outro("Query complete.")?;
+		Ok(())
+	}
+
+	// The identifier of the parachain and the relay chains on which it is queried.
+	fn targets(&self) -> Result<(u32, Vec<Chain>)> {
+		if let Some(chain) = self.relays.iter().find(|c| c.relay().is_some() || c.is_ethereum()) {
+			return Err(anyhow!("{} is not a relay chain.", chain.name()));
+		}
+		if let Ok(para_id) = self.parachain.parse::<u32>() {
+			let relays = match self.relays.is_empty() {
+				true => Chain::VARIANTS
+					.iter()
+					.filter(|c| c.relay().is_none() && !c.is_ethereum())
+					.copied()
+					.collect(),
+				false => self.relays.clone(),
+			};
+			return Ok((para_id, relays));
+		}
+		let chain = Chain::from_str(&self.parachain)
+			.map_err(|_| anyhow!("{} is not a known parachain.", self.parachain))?;
+		let (Some(para_id), Some(relay)) = (chain.para_id(), chain.relay()) else {
+			return Err(anyhow!("{} is not a parachain.", chain.name()));
+		};
+		if !self.relays.is_empty() && !self.relays.contains(&relay) {
+			return Err(anyhow!("{} is a parachain of {}.", chain.name(), relay.name()));
+		}
+		Ok((para_id, vec![relay]))
+	}
+}
+
+// Describes the state of a parachain on a relay chain.
+fn describe(relay: &Chain, info: &ParachainInfo, chain: Option<&Chain>, times: &[u64]) -> String {
+	let para_id = info.para_id;
+	let mut output = match chain {
+		Some(chain) => format!("{} (parachain {para_id}) on {}:", chain.name(), relay.name()),
+		None => format!("Parachain {para_id} on {}:", relay.name()),
+	};
+	if let Some(lifecycle) = &info.lifecycle {
+		output.push_str(&format!("\n  Status: {lifecycle}"));
+	}
+	if let Some((number, hash)) = &info.head {
+		output.push_str(&format!("\n  Head: #{number} ({hash:?})"));
+	}
+	if let Some(hash) = &info.code_hash {
+		output.push_str(&format!("\n  Code hash: {hash:?}"));
+	}
+	let cores = match info.cores.is_empty() {
+		true => "none".to_string(),
+		false => info
+			.cores
+			.iter()
+			.map(|c| format!("{} ({})", c.index, c.assignment))
+			.collect::<Vec<_>>()
+			.join(", "),
+	};
+	output.push_str(&format!("\n  Cores: {cores}"));
+	if let Some(block_time) = block_time(times) {
+		output.push_str(&format!("\n  Block time: {block_time}"));
+	}
+	if let Some(chain) = chain {
+		output.push_str(&format!("\n  RPC endpoints: {}", chain.url()));
+	}
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+	use pop_parachains::{Core, CoreAssignment, H256};
+
+	#[test]
+	fn query_parachain_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"query",
+			"parachain",
+			"4001",
+			"--relay",
+			"paseo,westend",
+		])?;
+		let crate::commands::Command::Query(args) = pop.command else {
+			panic!("expected the query command");
+		};
+		let super::super::Command::Parachain(command) = args.command else {
+			panic!("expected the parachain command");
+		};
+		assert_eq!(command.parachain, "4001");
+		assert_eq!(command.relays, vec![Chain::Paseo, Chain::Westend]);
+		// The parachain is required.
+		assert!(crate::Cli::try_parse_from(["pop", "q", "p"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn targets_works() -> Result<()> {
+		let command = |parachain: &str, relays: Vec<Chain>| QueryParachainCommand {
+			parachain: parachain.into(),
+			relays,
+		};
+		assert_eq!(
+			command("1000", vec![]).targets()?,
+			(1000, vec![Chain::Polkadot, Chain::Kusama, Chain::Paseo, Chain::Westend])
+		);
+		assert_eq!(command("2000", vec![Chain::Paseo]).targets()?, (2000, vec![Chain::Paseo]));
+		assert_eq!(command("pop-paseo", vec![]).targets()?, (4001, vec![Chain::Paseo]));
+		assert_eq!(
+			command("pop-paseo", vec![Chain::Kusama]).targets().unwrap_err().to_string(),
+			"Pop Network (Paseo) is a parachain of Paseo."
+		);
+		assert_eq!(
+			command("1000", vec![Chain::AssetHubPaseo]).targets().unwrap_err().to_string(),
+			"Asset Hub (Paseo) is not a relay chain."
+		);
+		assert_eq!(
+			command("paseo", vec![]).targets().unwrap_err().to_string(),
+			"Paseo is not a parachain."
+		);
+		assert_eq!(
+			command("moonbeam", vec![]).targets().unwrap_err().to_string(),
+			"moonbeam is not a known parachain."
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn describe_works() {
+		let info = ParachainInfo {
+			para_id: 4001,
+			lifecycle: Some("Parachain".into()),
+			head: Some((1_234, H256::repeat_byte(1))),
+			code_hash: Some(H256::repeat_byte(2)),
+			cores: vec![
+				Core { index: 3, assignment: CoreAssignment::Bulk },
+				Core { index: 7, assignment: CoreAssignment::OnDemand },
+			],
+		};
+		assert_eq!(
+			describe(&Chain::Paseo, &info, Some(&Chain::PopPaseo), &[6_000, 12_000, 6_000]),
+			format!(
+				"Pop Network (Paseo) (parachain 4001) on Paseo:\n  \
+				 Status: Parachain\n  \
+				 Head: #1234 (0x{})\n  \
+				 Code hash: 0x{}\n  \
+				 Cores: 3 (bulk), 7 (on-demand)\n  \
+				 Block time: 6.0s, averaging 8.0s over the last 3 blocks\n  \
+				 RPC endpoints: {}",
+				"01".repeat(32),
+				"02".repeat(32),
+				Chain::PopPaseo.url()
+			)
+		);
+		let info = ParachainInfo {
+			para_id: 3000,
+			lifecycle: Some("Onboarding".into()),
+			..Default::default()
+		};
+		assert_eq!(
+			describe(&Chain::Kusama, &info, None, &[]),
+			"Parachain 3000 on Kusama:\n  Status: Onboarding\n  Cores: none"
+		);
+	}
+}
@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	cli::traits::*,
+	common::{network, signer::SignerArgs},
+};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{
+	cancel_scheduled, construct_sudo_extrinsic, scheduled_tasks, set_up_client, ScheduledTask,
+	ScheduledTaskId,
+};
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+/// Command to list the tasks scheduled on a chain, optionally cancelling one of them.
+#[derive(Args)]
+pub(crate) struct QueryScheduledCommand {
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	/// Cancel a scheduled task, by its block and index (e.g. `1200:0`) or by its name.
+	#[arg(long, value_parser = parse_task_id)]
+	pub(crate) cancel: Option<ScheduledTaskId>,
+	/// Authenticates the sudo key and cancels the task with `Root` origin.
+	#[arg(short = 'S', long, requires = "cancel")]
+	pub(crate) sudo: bool,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl QueryScheduledCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Query scheduled tasks")?;
+		let spinner = cli.spinner();
+		spinner.start(format!("Querying the tasks scheduled on {}...", self.url));
+		let client =
+			network::retry("connecting to the chain", || set_up_client(self.url.as_str())).await?;
+		let tasks = scheduled_tasks(&client).await;
+		spinner.clear();
+		let tasks = match tasks {
+			Ok(tasks) => tasks,
+			Err(e) => {
+				cli.outro_cancel(format!("🚫 The scheduled tasks could not be queried: {e}"))?;
+				return Ok(());
+			},
+		};
+		match tasks.is_empty() {
+			true => cli.warning("No tasks are scheduled on the chain.")?,
+			false => cli.info(format!("Scheduled tasks:\n{}", table(&tasks)))?,
+		}
+
+		let Some(id) = &self.cancel else {
+			cli.outro("Query complete.")?;
+			return Ok(());
+		};
+		if !tasks.iter().any(|t| t.id() == *id) {
+			cli.warning(format!("⚠️ No task {id} is scheduled, so cancelling it may fail."))?;
+		}
+		let xt = cancel_scheduled(id)?;
+		let xt = if self.sudo { construct_sudo_extrinsic(xt) } else { xt };
+		let action = format!("cancel the task {id}");
+		if self.signer.submit(&client, &self.url, xt, &action, cli).await?.is_none() {
+			return Ok(());
+		}
+		cli.outro(format!("Cancelled the task {id}."))?;
+		Ok(())
+	}
+}
+
+// Parser for the identifier of a scheduled task.
+fn parse_task_id(id: &str) -> Result<ScheduledTaskId, String> {
+	id.parse().map_err(|e: pop_parachains::Error| e.to_string())
+}
+
+// Formats the scheduled tasks as a table.
+fn table(tasks: &[ScheduledTask]) -> String {
+	let mut rows = vec![format!(
+		"{:<12} {:<32} {:<8} {:<12} CALL",
+		"BLOCK:INDEX", "NAME", "PRIORITY", "PERIODIC"
+	)];
+	for task in tasks {
+		let periodic = task
+			.periodic
+			.map(|(period, count)| format!("{count}x/{period}"))
+			.unwrap_or_else(|| "-".into());
+		rows.push(format!(
+			"{:<12} {:<32} {:<8} {:<12} {}",
+			format!("{}:{}", task.block, task.index),
+			task.name.as_deref().unwrap_or("-"),
+			task.priority,
+			periodic,
+			task.call
+		));
+	}
+	rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn query_scheduled_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "query", "scheduled"].iter().chain(args))?;
+			let crate::commands::Command::Query(args) = pop.command else {
+				panic!("expected the query command");
+			};
+			let super::super::Command::Scheduled(command) = args.command else {
+				panic!("expected the scheduled command");
+			};
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&[])?;
+		assert_eq!(command.url, Url::parse(DEFAULT_URL)?);
+		assert_eq!(command.cancel, None);
+		let command = parse(&["--cancel", "1200:0", "--sudo", "--suri", "//Bob"])?;
+		assert_eq!(command.cancel, Some(ScheduledTaskId::Index { block: 1200, index: 0 }));
+		assert!(command.sudo);
+		assert_eq!(command.signer.suri.as_deref(), Some("//Bob"));
+		assert_eq!(
+			parse(&["--cancel", "upgrade"])?.cancel,
+			Some(ScheduledTaskId::Named("upgrade".into()))
+		);
+		assert!(parse(&["--sudo"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn table_works() {
+		let tasks = [
+			ScheduledTask {
+				block: 1200,
+				index: 0,
+				name: Some("upgrade".into()),
+				priority: 127,
+				call: "System.remark".into(),
+				periodic: Some((10, 3)),
+			},
+			ScheduledTask {
+				block: 1300,
+				index: 1,
+				call: "preimage 0x01".into(),
+				..Default::default()
+			},
+		];
+		let table = table(&tasks);
+		let rows: Vec<_> =
+			table.lines().map(|l| l.split_whitespace().collect::<Vec<_>>()).collect();
+		assert_eq!(rows[0], ["BLOCK:INDEX", "NAME", "PRIORITY", "PERIODIC", "CALL"]);
+		assert_eq!(rows[1], ["1200:0", "upgrade", "127", "3x/10", "System.remark"]);
+		assert_eq!(rows[2], ["1300:1", "-", "0", "-", "preimage", "0x01"]);
+	}
+}
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::{anyhow, Result};
+use clap::{Args, Subcommand};
+use pop_parachains::{
+	build_release_runtime, bump_spec_version, migrations, previous_release, weight_changes,
+	RuntimeDigest, RuntimeRelease,
+};
+use std::path::PathBuf;
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct ReleaseArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Prepare a release of a project.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Prepare a release of a runtime: bump its version, build it deterministically and bundle
+	/// the artifacts required to propose its upgrade.
+	#[clap(alias = "r")]
+	Runtime(ReleaseRuntimeCommand),
+}
+
+#[derive(Args)]
+pub(crate) struct ReleaseRuntimeCommand {
+	/// Path to the project [default: current directory].
+	#[arg(short, long)]
+	path: Option<PathBuf>,
+	/// Path to the runtime crate [default: `runtime` within the project].
+	#[arg(short, long)]
+	runtime: Option<PathBuf>,
+	/// The new `spec_version` of the runtime [default: the next version].
+	#[arg(long, conflicts_with = "skip_bump")]
+	spec_version: Option<u32>,
+	/// Release the runtime with the `spec_version` already declared.
+	#[arg(long)]
+	skip_bump: bool,
+	/// Build the runtime with the local toolchain, rather than deterministically via srtool.
+	#[arg(long)]
+	local: bool,
+	/// The git reference of the previous release, against which weights are compared [default:
+	/// the latest tag].
+	#[arg(long)]
+	since: Option<String>,
+	/// Directory to which the artifacts are written [default: `release` within the project].
+	#[arg(short, long)]
+	output: Option<PathBuf>,
+}
+
+impl ReleaseRuntimeCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Release runtime")?;
+		let project = self.path.unwrap_or_else(|| PathBuf::from("./"));
+		let runtime = self.runtime.unwrap_or_else(|| project.join("runtime"));
+		if !runtime.join("Cargo.toml").exists() {
+			cli.outro_cancel(format!("🚫 No runtime could be found at {}.", runtime.display()))?;
+			return Err(anyhow!("no runtime found at {}", runtime.display()));
+		}
+
+		let previous_version = match self.skip_bump {
+			true => None,
+			false => {
+				let (previous, next) = bump_spec_version(&runtime, self.spec_version)?;
+				cli.info(format!("Bumped `spec_version` from {previous} to {next}."))?;
+				Some(previous)
+			},
+		};
+
+		let spinner = cli.spinner();
+		spinner.start(match self.local {
+			true => "Building the runtime with the local toolchain...".to_string(),
+			false => "Building the runtime deterministically via srtool...".to_string(),
+		});
+		let wasm = build_release_runtime(&project, &runtime, !self.local)
+			.inspect_err(|e| spinner.error(format!("🚫 The runtime could not be built: {e}")))?;
+		spinner.clear();
+		let digest = RuntimeDigest::from_file(&wasm)?;
+		if let Some(previous) = previous_version.filter(|v| *v >= digest.version.spec_version) {
+			cli.warning(format!(
+				"⚠️ The built runtime declares `spec_version` {}, which is not newer than {previous}.",
+				digest.version.spec_version
+			))?;
+		}
+		if self.local {
+			cli.warning("⚠️ The runtime was built locally, so its hash cannot be reproduced.")?;
+		}
+
+		let since = self.since.or_else(|| previous_release(&project));
+		let weights = match &since {
+			Some(since) => weight_changes(&project, since).unwrap_or_else(|e| {
+				let _ = cli.warning(format!("⚠️ Weights could not be compared to {since}: {e}"));
+				Vec::new()
+			}),
+			None => {
+				cli.warning("⚠️ No previous release was found, so weights are not compared.")?;
+				Vec::new()
+			},
+		};
+		let migrations = migrations(&runtime).unwrap_or_else(|e| {
+			let _ = cli.warning(format!("⚠️ The migrations could not be determined: {e}"));
+			Vec::new()
+		});
+
+		let release = RuntimeRelease {
+			digest,
+			previous_version,
+			deterministic: !self.local,
+			since,
+			weights,
+			migrations,
+		};
+		let output = self.output.unwrap_or_else(|| project.join("release"));
+		let artifacts = release.write(&wasm, &output)?;
+		cli.info(format!(
+			"Artifacts:\n{}",
+			artifacts
+				.iter()
+				.map(|a| format!("  {}", a.display()))
+				.collect::<Vec<_>>()
+				.join("\n")
+		))?;
+		cli.outro(format!(
+			"✅ Released {} with the code hash {}",
+			release.digest.version,
+			release.digest.code_hash()
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+
+	#[test]
+	fn release_runtime_args_works() -> Result<()> {
+		let parse = |args: &[&str]| -> Result<ReleaseRuntimeCommand> {
+			let pop = crate::Cli::try_parse_from(["pop", "release", "runtime"].iter().chain(args))?;
+			let crate::commands::Command::Release(args) = pop.command else {
+				panic!("expected the release command");
+			};
+			let Command::Runtime(command) = args.command;
+			Ok(command)
+		};
+		let command = parse(&[
+			"-p",
+			"./parachain",
+			"--spec-version",
+			"1001",
+			"--local",
+			"--since",
+			"v1.0.0",
+			"-o",
+			"./out",
+		])?;
+		assert_eq!(command.path, Some(PathBuf::from("./parachain")));
+		assert_eq!(command.spec_version, Some(1001));
+		assert!(command.local && !command.skip_bump);
+		assert_eq!(command.since.as_deref(), Some("v1.0.0"));
+		assert_eq!(command.output, Some(PathBuf::from("./out")));
+		let command = parse(&["--skip-bump", "-r", "./runtimes/pop"])?;
+		assert!(command.skip_bump && !command.local);
+		assert_eq!(command.runtime, Some(PathBuf::from("./runtimes/pop")));
+		assert!(parse(&["--skip-bump", "--spec-version", "1001"]).is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn release_runtime_without_runtime_fails() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let mut cli = MockCli::new().expect_intro("Release runtime").expect_outro_cancel(format!(
+			"🚫 No runtime could be found at {}.",
+			temp_dir.path().join("runtime").display()
+		));
+		let command = ReleaseRuntimeCommand {
+			path: Some(temp_dir.path().to_path_buf()),
+			runtime: None,
+			spec_version: None,
+			skip_bump: false,
+			local: true,
+			since: None,
+			output: None,
+		};
+		assert!(command.execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+}
@@ -0,0 +1,326 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, progress::Event};
+use std::{
+	fmt::Display,
+	io::{Error, ErrorKind, Result},
+};
+
+/// A non-interactive command line interface, used to execute commands on behalf of a client of
+/// `pop serve`. Any output is emitted as a progress event and captured, whilst any prompt is
+/// resolved using its initial/default value.
+#[derive(Default)]
+pub(crate) struct ServeCli {
+	/// The output captured whilst executing a command.
+	pub(crate) output: Vec<String>,
+}
+
+impl ServeCli {
+	/// Emits and captures a message.
+	fn log(&mut self, phase: &str, message: impl Display) -> Result<()> {
+		let message = message.to_string();
+		Event::new(phase).message(&message).emit();
+		self.output.push(message);
+		Ok(())
+	}
+}
+
+impl Cli for ServeCli {
+	fn confirm(&mut self, prompt: impl Display) -> impl Confirm {
+		ServeConfirm { prompt: prompt.to_string(), initial_value: false }
+	}
+
+	fn error(&mut self, text: impl Display) -> Result<()> {
+		self.log("error", text)
+	}
+
+	fn info(&mut self, text: impl Display) -> Result<()> {
+		self.log("info", text)
+	}
+
+	fn input(&mut self, prompt: impl Display) -> impl Input {
+		ServeInput { prompt: prompt.to_string(), default: None, validate: None }
+	}
+
+	fn intro(&mut self, title: impl Display) -> Result<()> {
+		self.log("intro", title)
+	}
+
+	fn multiselect<T: Clone + Eq>(&mut self, prompt: impl Display) -> impl MultiSelect<T> {
+		ServeMultiSelect { prompt: prompt.to_string(), required: true, items: vec![] }
+	}
+
+	fn outro(&mut self, message: impl Display) -> Result<()> {
+		self.log("outro", message)
+	}
+
+	fn outro_cancel(&mut self, message: impl Display) -> Result<()> {
+		self.log("outro_cancel", message)
+	}
+
+	fn password(&mut self, prompt: impl Display) -> impl Password {
+		ServePassword { prompt: prompt.to_string(), allow_empty: false }
+	}
+
+	fn plain(&mut self, text: impl Display) -> Result<()> {
+		self.log("output", text)
+	}
+
+	fn select<T: Clone + Eq>(&mut self, prompt: impl Display) -> impl Select<T> {
+		ServeSelect { prompt: prompt.to_string(), initial_value: None, first: None }
+	}
+
+	fn spinner(&mut self) -> impl Spinner + Send + 'static {
+		ServeSpinner
+	}
+
+	fn success(&mut self, message: impl Display) -> Result<()> {
+		self.log("success", message)
+	}
+
+	fn warning(&mut self, message: impl Display) -> Result<()> {
+		self.log("warning", message)
+	}
+}
+
+/// Emits the prompt as a progress event, noting that it was resolved without any interaction.
+fn resolved(prompt: &str) {
+	Event::new("prompt").message(prompt).emit()
+}
+
+/// Returns an error for a prompt which cannot be resolved without any interaction.
+fn unresolvable(prompt: &str) -> Error {
+	Error::other(format!(
+		"interactive input is required to resolve the prompt `{prompt}`, consider specifying the corresponding argument"
+	))
+}
+
+/// A confirmation prompt, resolved using its initial value.
+struct ServeConfirm {
+	prompt: String,
+	initial_value: bool,
+}
+
+impl Confirm for ServeConfirm {
+	fn initial_value(mut self, initial_value: bool) -> Self {
+		self.initial_value = initial_value;
+		self
+	}
+
+	fn interact(&mut self) -> Result<bool> {
+		resolved(&self.prompt);
+		Ok(self.initial_value)
+	}
+}
+
+/// A validator of text input.
+type Validator = Box<dyn Fn(&String) -> std::result::Result<(), &'static str>>;
+
+/// A text input prompt, resolved using its default input.
+struct ServeInput {
+	prompt: String,
+	default: Option<String>,
+	validate: Option<Validator>,
+}
+
+impl Input for ServeInput {
+	fn default_input(mut self, value: &str) -> Self {
+		self.default = Some(value.to_string());
+		self
+	}
+
+	fn interact(&mut self) -> Result<String> {
+		let value = self.default.clone().ok_or_else(|| unresolvable(&self.prompt))?;
+		if let Some(validate) = &self.validate {
+			validate(&value).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+		}
+		resolved(&self.prompt);
+		Ok(value)
+	}
+
+	fn placeholder(self, _value: &str) -> Self {
+		self
+	}
+
+	fn required(self, _required: bool) -> Self {
+		self
+	}
+
+	fn validate(
+		mut self,
+		validator: impl Fn(&String) -> std::result::Result<(), &'static str> + 'static,
+	) -> Self {
+		self.validate = Some(Box::new(validator));
+		self
+	}
+}
+
+/// A password prompt, resolved as empty when allowed, otherwise unresolvable without any
+/// interaction.
+struct ServePassword {
+	prompt: String,
+	allow_empty: bool,
+}
+
+impl Password for ServePassword {
+	fn allow_empty(self) -> Self {
+		Self { allow_empty: true, ..self }
+	}
+
+	fn interact(&mut self) -> Result<String> {
+		match self.allow_empty {
+			true => Ok(String::new()),
+			false => Err(unresolvable(&self.prompt)),
+		}
+	}
+}
+
+/// A multi-select prompt, resolved with no items selected when selection is optional.
+struct ServeMultiSelect<T> {
+	prompt: String,
+	required: bool,
+	items: Vec<T>,
+}
+
+impl<T: Clone + Eq> MultiSelect<T> for ServeMultiSelect<T> {
+	fn interact(&mut self) -> Result<Vec<T>> {
+		if self.required && !self.items.is_empty() {
+			return Err(unresolvable(&self.prompt));
+		}
+		resolved(&self.prompt);
+		Ok(vec![])
+	}
+
+	fn item(mut self, value: T, _label: impl Display, _hint: impl Display) -> Self {
+		self.items.push(value);
+		self
+	}
+
+	fn required(mut self, required: bool) -> Self {
+		self.required = required;
+		self
+	}
+}
+
+/// A select prompt, resolved using its initial value, otherwise the first item.
+struct ServeSelect<T> {
+	prompt: String,
+	initial_value: Option<T>,
+	first: Option<T>,
+}
+
+impl<T: Clone + Eq> Select<T> for ServeSelect<T> {
+	fn initial_value(mut self, initial_value: T) -> Self {
+		self.initial_value = Some(initial_value);
+		self
+	}
+
+	fn interact(&mut self) -> Result<T> {
+		let value = self
+			.initial_value
+			.clone()
+			.or_else(|| self.first.clone())
+			.ok_or_else(|| unresolvable(&self.prompt))?;
+		resolved(&self.prompt);
+		Ok(value)
+	}
+
+	fn item(mut self, value: T, _label: impl Display, _hint: impl Display) -> Self {
+		if self.first.is_none() {
+			self.first = Some(value);
+		}
+		self
+	}
+}
+
+/// A spinner, emitting its progress as events.
+struct ServeSpinner;
+
+impl Spinner for ServeSpinner {
+	fn clear(&self) {
+		Event::new("stop").emit()
+	}
+
+	fn error(&self, message: impl Display) {
+		Event::new("error").message(message).emit()
+	}
+
+	fn set_message(&self, message: impl Display) {
+		Event::new("update").message(message).emit()
+	}
+
+	fn start(&self, message: impl Display) {
+		Event::new("start").message(message).emit()
+	}
+
+	fn stop(&self, message: impl Display) {
+		Event::new("stop").message(message).emit()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn output_is_captured() -> Result<()> {
+		let mut cli = ServeCli::default();
+		cli.intro("Generate a contract")?;
+		cli.warning("NOTE: the resulting contract is not audited")?;
+		cli.plain("{\"name\":\"flipper\"}")?;
+		cli.outro("Done")?;
+		assert_eq!(
+			cli.output,
+			vec![
+				"Generate a contract",
+				"NOTE: the resulting contract is not audited",
+				"{\"name\":\"flipper\"}",
+				"Done"
+			]
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn prompts_resolve_to_defaults() -> Result<()> {
+		let mut cli = ServeCli::default();
+		assert!(!cli.confirm("Remove the directory?").interact()?);
+		assert!(cli.confirm("Source binaries?").initial_value(true).interact()?);
+		assert_eq!(
+			cli.input("Location?").default_input("./my-parachain").interact()?,
+			"./my-parachain"
+		);
+		assert_eq!(cli.select("Template?").item(1, "One", "").item(2, "Two", "").interact()?, 1);
+		assert_eq!(
+			cli.select("Template?")
+				.initial_value(2)
+				.item(1, "One", "")
+				.item(2, "Two", "")
+				.interact()?,
+			2
+		);
+		assert!(cli
+			.multiselect("Storage?")
+			.required(false)
+			.item(1, "One", "")
+			.interact()?
+			.is_empty());
+		assert_eq!(cli.password("Signer?").allow_empty().interact()?, "");
+		Ok(())
+	}
+
+	#[test]
+	fn prompts_without_defaults_fail() {
+		let mut cli = ServeCli::default();
+		assert!(cli.input("Which chain?").interact().is_err());
+		assert!(cli
+			.input("Which chain?")
+			.default_input("")
+			.validate(|_| Err("invalid"))
+			.interact()
+			.is_err());
+		assert!(cli.select::<u8>("Pallet?").interact().is_err());
+		assert!(cli.multiselect("Storage?").item(1, "One", "").interact().is_err());
+		assert!(cli.password("Master password:").interact().is_err());
+	}
+}
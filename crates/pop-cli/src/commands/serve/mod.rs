@@ -0,0 +1,482 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	cli::{set_quiet, traits::Cli},
+	common::interrupt::Handled,
+	progress,
+};
+use axum::{
+	extract::{rejection::JsonRejection, Request as HttpRequest, State},
+	http::{
+		header::{AUTHORIZATION, HOST, ORIGIN},
+		StatusCode,
+	},
+	middleware::{self, Next},
+	response::{
+		sse::{self, KeepAlive, Sse},
+		IntoResponse,
+	},
+	routing::{get, post},
+	Json, Router,
+};
+use clap::{Args, CommandFactory, Parser};
+use cli::ServeCli;
+use futures::{stream, Stream};
+use pop_common::secrets::random_token;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+	convert::Infallible,
+	net::{IpAddr, SocketAddr},
+	sync::Arc,
+};
+use tokio::{net::TcpListener, runtime::Handle, sync::broadcast::error::RecvError};
+
+mod cli;
+
+/// The default port on which to listen.
+const DEFAULT_PORT: u16 = 9191;
+/// The JSON-RPC version supported.
+const JSONRPC_VERSION: &str = "2.0";
+
+/// Arguments for serving pop via a local JSON-RPC interface.
+#[derive(Args)]
+pub struct ServeArgs {
+	/// The local port on which to listen.
+	#[arg(short, long, default_value_t = DEFAULT_PORT)]
+	pub(crate) port: u16,
+}
+
+/// Serves pop via a local JSON-RPC interface, allowing tools such as IDE extensions to drive pop
+/// without invoking a new process per action.
+///
+/// Requests are posted to `/`, with `templates` listing the available templates and every other
+/// method corresponding to a pop command (e.g. `new`, `build`, `up` or `call`), whose arguments
+/// are provided as `params` (e.g. `["parachain", "my-parachain", "-t", "assets"]`). Commands are
+/// executed non-interactively, with prompts resolving to their defaults. Progress is streamed as
+/// server-sent events via `/events`.
+///
+/// Requests must be authorised via the bearer token printed at startup, which is generated anew
+/// each time pop is served. Requests from browsers (i.e. with an `Origin` header) or to a host
+/// other than a loopback address are rejected, as are requests posted without a JSON content type,
+/// so that web pages cannot drive pop.
+pub(crate) struct Command;
+
+impl Command {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `args` - The arguments for serving pop.
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, args: ServeArgs, cli: &mut impl Cli) -> anyhow::Result<()> {
+		cli.intro("Serve pop via JSON-RPC")?;
+		let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], args.port))).await?;
+		let address = listener.local_addr()?;
+		let token = random_token();
+		cli.success(format!(
+			"Listening for requests at http://{address}, with progress events streamed via http://{address}/events - ctrl-c to terminate"
+		))?;
+		cli.info(format!("Authorise requests via the header `Authorization: Bearer {token}`"))?;
+		// Output of any served commands is only available via the interface.
+		set_quiet(true);
+		let _interrupts = Handled::new();
+		axum::serve(listener, router(token))
+			.with_graceful_shutdown(async {
+				let _ = tokio::signal::ctrl_c().await;
+			})
+			.await?;
+		set_quiet(false);
+		cli.outro("Server terminated")?;
+		Ok(())
+	}
+}
+
+/// The routes of the interface, with every request authorised via the specified token.
+///
+/// # Arguments
+/// * `token` - The bearer token with which requests are authorised.
+fn router(token: String) -> Router {
+	Router::new()
+		.route("/", post(rpc))
+		.route("/events", get(events))
+		.layer(middleware::from_fn_with_state(Arc::<str>::from(token), authorise))
+}
+
+/// Rejects any request from a browser, to a host other than a loopback address or without the
+/// bearer token, guarding against cross-site requests and DNS rebinding.
+async fn authorise(
+	State(token): State<Arc<str>>,
+	request: HttpRequest,
+	next: Next,
+) -> axum::response::Response {
+	let headers = request.headers();
+	let host = headers.get(HOST).and_then(|h| h.to_str().ok());
+	if headers.contains_key(ORIGIN) || !host.is_some_and(is_loopback) {
+		return StatusCode::FORBIDDEN.into_response();
+	}
+	let authorization = headers.get(AUTHORIZATION).and_then(|h| h.to_str().ok());
+	if authorization.and_then(|a| a.strip_prefix("Bearer ")) != Some(&*token) {
+		return StatusCode::UNAUTHORIZED.into_response();
+	}
+	next.run(request).await
+}
+
+/// Whether a host, optionally including a port, is a loopback address.
+///
+/// # Arguments
+/// * `host` - The value of a `Host` header, e.g. `127.0.0.1:9191`.
+fn is_loopback(host: &str) -> bool {
+	let name = match host.rsplit_once(':') {
+		Some((name, port)) if !name.ends_with(':') && port.parse::<u16>().is_ok() => name,
+		_ => host,
+	};
+	name.eq_ignore_ascii_case("localhost") ||
+		name.trim_start_matches('[')
+			.trim_end_matches(']')
+			.parse::<IpAddr>()
+			.is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Handles a JSON-RPC request, which must be posted with a JSON content type.
+async fn rpc(
+	request: Result<Json<Request>, JsonRejection>,
+) -> Result<Json<Response>, JsonRejection> {
+	let request = match request {
+		Ok(Json(request)) => request,
+		Err(e @ JsonRejection::MissingJsonContentType(_)) => return Err(e),
+		Err(JsonRejection::JsonSyntaxError(e)) =>
+			return Ok(Json(Response::error(Value::Null, RpcError::parse(e.body_text())))),
+		Err(e) =>
+			return Ok(Json(Response::error(Value::Null, RpcError::invalid_request(e.body_text())))),
+	};
+	let id = request.id.clone();
+	Ok(Json(match handle(request).await {
+		Ok(result) => Response::result(id, result),
+		Err(e) => Response::error(id, e),
+	}))
+}
+
+/// Streams any progress events as server-sent events.
+async fn events() -> Sse<impl Stream<Item = Result<sse::Event, Infallible>>> {
+	let stream = stream::unfold(progress::subscribe(), |mut receiver| async move {
+		loop {
+			match receiver.recv().await {
+				Ok(line) => return Some((Ok(sse::Event::default().data(line)), receiver)),
+				// Skip any events missed by a slow client.
+				Err(RecvError::Lagged(_)) => continue,
+				Err(RecvError::Closed) => return None,
+			}
+		}
+	});
+	Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Handles a request, by either listing the available templates or executing a command.
+async fn handle(request: Request) -> Result<Value, RpcError> {
+	if request.jsonrpc != JSONRPC_VERSION {
+		return Err(RpcError::invalid_request(format!(
+			"unsupported JSON-RPC version: {}",
+			request.jsonrpc
+		)));
+	}
+	if request.method == "templates" {
+		return Ok(templates());
+	}
+	if request.method == "serve" ||
+		!crate::Cli::command().get_subcommands().any(|c| c.get_name() == request.method)
+	{
+		return Err(RpcError::method_not_found(&request.method));
+	}
+	let params: Vec<String> = match request.params {
+		None => vec![],
+		Some(params) => serde_json::from_value(params).map_err(|_| {
+			RpcError::invalid_params("params must be an array of command arguments".into())
+		})?,
+	};
+	let args = ["pop".to_string(), request.method].into_iter().chain(params);
+	let pop = crate::Cli::try_parse_from(args)
+		.map_err(|e| RpcError::invalid_params(e.render().to_string()))?;
+	execute(pop.command).await
+}
+
+/// Executes a command non-interactively, returning the data observed for telemetry along with any
+/// output.
+///
+/// # Arguments
+/// * `command` - The command to be executed.
+async fn execute(command: super::Command) -> Result<Value, RpcError> {
+	// Commands are executed on a dedicated thread, as they may block.
+	let handle = Handle::current();
+	let (result, output) = tokio::task::spawn_blocking(move || {
+		handle.block_on(async move {
+			let mut cli = ServeCli::default();
+			let result = command.execute(&mut cli).await;
+			(result, cli.output)
+		})
+	})
+	.await
+	.map_err(|e| RpcError::execution(e.to_string(), vec![]))?;
+	match result {
+		Ok(data) => Ok(json!({ "data": data, "output": output })),
+		Err(e) => Err(RpcError::execution(e.to_string(), output)),
+	}
+}
+
+/// Lists the available templates, by type.
+fn templates() -> Value {
+	#[allow(unused_mut)]
+	let mut templates = serde_json::Map::new();
+	#[cfg(feature = "parachain")]
+	templates.insert("parachain".into(), templates_of::<pop_parachains::Provider, _>());
+	#[cfg(feature = "contract")]
+	templates.insert("contract".into(), templates_of::<pop_contracts::ContractType, _>());
+	Value::Object(templates)
+}
+
+/// Lists the templates of each variant of a template type.
+#[cfg(any(feature = "parachain", feature = "contract"))]
+fn templates_of<T, U>() -> Value
+where
+	T: pop_common::templates::Type<U> + AsRef<str>,
+	U: pop_common::templates::Template + AsRef<str>,
+{
+	T::types()
+		.iter()
+		.flat_map(|t| {
+			t.templates().into_iter().map(move |template| {
+				json!({
+					"type": t.as_ref(),
+					"name": template.as_ref(),
+					"title": template.name(),
+					"description": template.description(),
+				})
+			})
+		})
+		.collect()
+}
+
+/// A JSON-RPC request.
+#[derive(Deserialize)]
+struct Request {
+	jsonrpc: String,
+	#[serde(default)]
+	id: Value,
+	method: String,
+	params: Option<Value>,
+}
+
+/// A JSON-RPC response.
+#[derive(Debug, PartialEq, Serialize)]
+struct Response {
+	jsonrpc: &'static str,
+	id: Value,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	result: Option<Value>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<RpcError>,
+}
+
+impl Response {
+	fn result(id: Value, result: Value) -> Self {
+		Self { jsonrpc: JSONRPC_VERSION, id, result: Some(result), error: None }
+	}
+
+	fn error(id: Value, error: RpcError) -> Self {
+		Self { jsonrpc: JSONRPC_VERSION, id, result: None, error: Some(error) }
+	}
+}
+
+/// A JSON-RPC error.
+#[derive(Debug, PartialEq, Serialize)]
+struct RpcError {
+	code: i32,
+	message: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	data: Option<Value>,
+}
+
+impl RpcError {
+	fn new(code: i32, message: String) -> Self {
+		Self { code, message, data: None }
+	}
+
+	fn parse(message: String) -> Self {
+		Self::new(-32700, format!("Parse error: {message}"))
+	}
+
+	fn invalid_request(message: String) -> Self {
+		Self::new(-32600, message)
+	}
+
+	fn method_not_found(method: &str) -> Self {
+		Self::new(-32601, format!("Method not found: {method}"))
+	}
+
+	fn invalid_params(message: String) -> Self {
+		Self::new(-32602, message)
+	}
+
+	fn execution(message: String, output: Vec<String>) -> Self {
+		Self { data: Some(json!({ "output": output })), ..Self::new(-32000, message) }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use axum::http::header::CONTENT_TYPE;
+
+	async fn request(body: &str) -> Response {
+		rpc(Ok(Json(serde_json::from_str(body).unwrap()))).await.unwrap().0
+	}
+
+	// Serves the interface on an available port, returning its address and token.
+	async fn serve() -> anyhow::Result<(SocketAddr, String)> {
+		let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await?;
+		let address = listener.local_addr()?;
+		let token = random_token();
+		let router = router(token.clone());
+		tokio::spawn(async move { axum::serve(listener, router).await });
+		Ok((address, token))
+	}
+
+	#[tokio::test]
+	async fn requests_are_authorised() -> anyhow::Result<()> {
+		let (address, token) = serve().await?;
+		let client = reqwest::Client::new();
+		let url = format!("http://{address}/");
+		let body = r#"{"jsonrpc":"2.0","id":1,"method":"templates"}"#;
+		let post = |token: &str| {
+			client
+				.post(&url)
+				.bearer_auth(token)
+				.header(CONTENT_TYPE, "application/json")
+				.body(body)
+		};
+		assert_eq!(post(&token).send().await?.status(), StatusCode::OK);
+		assert_eq!(post("invalid").send().await?.status(), StatusCode::UNAUTHORIZED);
+		assert_eq!(
+			client
+				.post(&url)
+				.header(CONTENT_TYPE, "application/json")
+				.body(body)
+				.send()
+				.await?
+				.status(),
+			StatusCode::UNAUTHORIZED
+		);
+		// A request from a web page.
+		assert_eq!(
+			post(&token).header(ORIGIN, "https://example.com").send().await?.status(),
+			StatusCode::FORBIDDEN
+		);
+		// A request via a domain rebound to the loopback address.
+		assert_eq!(
+			post(&token)
+				.header(HOST, format!("example.com:{}", address.port()))
+				.send()
+				.await?
+				.status(),
+			StatusCode::FORBIDDEN
+		);
+		// A simple request, which browsers send cross-origin without a preflight check.
+		assert_eq!(
+			client
+				.post(&url)
+				.bearer_auth(&token)
+				.header(CONTENT_TYPE, "text/plain")
+				.body(body)
+				.send()
+				.await?
+				.status(),
+			StatusCode::UNSUPPORTED_MEDIA_TYPE
+		);
+		assert_eq!(
+			client.get(format!("http://{address}/events")).send().await?.status(),
+			StatusCode::UNAUTHORIZED
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn is_loopback_works() {
+		for host in ["localhost", "localhost:9191", "127.0.0.1:9191", "[::1]:9191", "[::1]"] {
+			assert!(is_loopback(host), "{host} is a loopback address");
+		}
+		for host in ["example.com", "example.com:9191", "192.168.1.1:9191", "localhost.example.com"]
+		{
+			assert!(!is_loopback(host), "{host} is not a loopback address");
+		}
+	}
+
+	#[tokio::test]
+	async fn rpc_rejects_invalid_requests() -> anyhow::Result<()> {
+		let (address, token) = serve().await?;
+		let response: Value = reqwest::Client::new()
+			.post(format!("http://{address}/"))
+			.bearer_auth(&token)
+			.header(CONTENT_TYPE, "application/json")
+			.body("{")
+			.send()
+			.await?
+			.json()
+			.await?;
+		assert_eq!(response["id"], Value::Null);
+		assert_eq!(response["error"]["code"], -32700);
+
+		let response = request(r#"{"jsonrpc":"1.0","id":1,"method":"templates"}"#).await;
+		assert_eq!(response.error.unwrap().code, -32600);
+
+		for method in ["serve", "unknown"] {
+			let response =
+				request(&format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{method}"}}"#)).await;
+			assert_eq!(
+				response.error.unwrap(),
+				RpcError::method_not_found(method),
+				"{method} is not a method"
+			);
+		}
+
+		let response =
+			request(r#"{"jsonrpc":"2.0","id":1,"method":"new","params":{"name":"test"}}"#).await;
+		assert_eq!(response.error.unwrap().code, -32602);
+
+		let response =
+			request(r#"{"jsonrpc":"2.0","id":1,"method":"new","params":["unknown"]}"#).await;
+		assert_eq!(response.error.unwrap().code, -32602);
+		Ok(())
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn rpc_executes_commands() {
+		// Any prompt is resolved without interaction, so nothing is removed.
+		let response =
+			request(r#"{"jsonrpc":"2.0","id":"clean","method":"clean","params":["cache"]}"#).await;
+		assert_eq!(response.id, json!("clean"));
+		let output = match (response.result, response.error) {
+			(Some(result), None) => result["output"].clone(),
+			(None, Some(error)) => error.data.unwrap()["output"].clone(),
+			_ => panic!("expected either a result or an error"),
+		};
+		assert_eq!(output[0], "Remove cached artifacts");
+	}
+
+	#[tokio::test]
+	async fn templates_works() {
+		let response = request(r#"{"jsonrpc":"2.0","id":1,"method":"templates"}"#).await;
+		assert_eq!(response, Response::result(json!(1), templates()));
+		let templates = response.result.unwrap();
+		#[cfg(feature = "parachain")]
+		assert!(templates["parachain"]
+			.as_array()
+			.unwrap()
+			.iter()
+			.any(|t| t["type"] == "pop" && t["name"] == "standard" && t["title"] == "Standard"));
+		#[cfg(feature = "contract")]
+		assert!(templates["contract"]
+			.as_array()
+			.unwrap()
+			.iter()
+			.any(|t| t["type"] == "erc" && t["name"] == "erc20"));
+	}
+}
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_parachains::{sign_payload, UnsignedPayload};
+use std::{fs, path::PathBuf};
+
+#[derive(Args)]
+pub struct SignArgs {
+	/// Path to the payload created via `pop call chain --create-payload`.
+	pub(crate) payload: PathBuf,
+	/// Secret key URI for the account signing the payload.
+	///
+	/// e.g.
+	/// - for a dev account "//Alice"
+	/// - with a password "//Alice///SECRET_PASSWORD"
+	#[arg(short, long)]
+	pub(crate) suri: Option<String>,
+	/// Path to which the signed payload is written [default: the path of the payload, with a
+	/// `.signed.json` extension].
+	#[arg(short, long)]
+	pub(crate) output: Option<PathBuf>,
+	/// Automatically signs the payload without prompting for confirmation.
+	#[arg(short = 'y', long)]
+	pub(crate) skip_confirm: bool,
+}
+
+impl SignArgs {
+	/// Executes the command, signing a payload without requiring a connection to the chain, such
+	/// as on a machine which is kept offline.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Sign a payload offline")?;
+		let contents = fs::read_to_string(&self.payload)
+			.map_err(|e| anyhow!("Failed to read the payload {}: {e}", self.payload.display()))?;
+		let payload: UnsignedPayload = serde_json::from_str(&contents)
+			.map_err(|e| anyhow!("Invalid payload {}: {e}", self.payload.display()))?;
+		cli.info(format!(
+			"Chain: {}\nSigner: {}\nNonce: {}\nCall data: {}",
+			payload.genesis_hash, payload.signer, payload.nonce, payload.call_data
+		))?;
+		if !self.skip_confirm &&
+			!cli.confirm("Do you want to sign the payload?").initial_value(true).interact()?
+		{
+			cli.outro_cancel("🚫 Signing cancelled.")?;
+			return Ok(());
+		}
+		let suri = match self.suri {
+			Some(suri) => suri,
+			None => cli.password(format!("Secret key URI of {}:", payload.signer)).interact()?,
+		};
+		let signed = sign_payload(payload, &suri)?;
+		let output = self.output.unwrap_or_else(|| self.payload.with_extension("signed.json"));
+		fs::write(&output, serde_json::to_string_pretty(&signed)?)?;
+		cli.info(format!("Submit it via `pop submit {} --url <URL>`.", output.display()))?;
+		cli.outro(format!("✅ Signed payload written to {}", output.display()))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use pop_parachains::SignedPayload;
+
+	fn payload() -> UnsignedPayload {
+		UnsignedPayload {
+			genesis_hash: format!("0x{}", "01".repeat(32)),
+			spec_version: 1_000,
+			transaction_version: 1,
+			signer: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".into(),
+			nonce: 0,
+			call_data: "0x00000411".into(),
+			extra: "0x0000".into(),
+			additional: "0x0000".into(),
+			signer_payload: "0x0000041100000000".into(),
+		}
+	}
+
+	#[test]
+	fn sign_args_works() -> Result<()> {
+		let pop =
+			crate::Cli::try_parse_from(["pop", "sign", "payload.json", "--suri", "//Alice", "-y"])?;
+		let crate::commands::Command::Sign(args) = pop.command else {
+			panic!("expected the sign command");
+		};
+		assert_eq!(args.payload, PathBuf::from("payload.json"));
+		assert_eq!(args.suri.as_deref(), Some("//Alice"));
+		assert_eq!((args.output, args.skip_confirm), (None, true));
+		Ok(())
+	}
+
+	#[test]
+	fn sign_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("payload.json");
+		fs::write(&path, serde_json::to_string(&payload())?)?;
+		let output = temp_dir.path().join("payload.signed.json");
+		let mut cli = MockCli::new()
+			.expect_intro("Sign a payload offline")
+			.expect_info(format!(
+				"Chain: 0x{}\nSigner: {}\nNonce: 0\nCall data: 0x00000411",
+				"01".repeat(32),
+				payload().signer
+			))
+			.expect_confirm("Do you want to sign the payload?", true)
+			.expect_password(format!("Secret key URI of {}:", payload().signer), "//Alice")
+			.expect_info(format!("Submit it via `pop submit {} --url <URL>`.", output.display()))
+			.expect_outro(format!("✅ Signed payload written to {}", output.display()));
+		SignArgs { payload: path, suri: None, output: None, skip_confirm: false }
+			.execute(&mut cli)?;
+		let signed: SignedPayload = serde_json::from_str(&fs::read_to_string(&output)?)?;
+		assert_eq!(signed.payload, payload());
+		cli.verify()
+	}
+
+	#[test]
+	fn sign_fails_with_tampered_payload() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("payload.json");
+		let tampered = UnsignedPayload { signer_payload: "0x0000041200000000".into(), ..payload() };
+		fs::write(&path, serde_json::to_string(&tampered)?)?;
+		let mut cli = MockCli::new().expect_intro("Sign a payload offline").expect_info(format!(
+			"Chain: 0x{}\nSigner: {}\nNonce: 0\nCall data: 0x00000411",
+			"01".repeat(32),
+			payload().signer
+		));
+		let error = SignArgs {
+			payload: path,
+			suri: Some("//Alice".into()),
+			output: None,
+			skip_confirm: true,
+		}
+		.execute(&mut cli)
+		.unwrap_err();
+		assert!(error.to_string().contains("does not match the call data"));
+		assert!(!temp_dir.path().join("payload.signed.json").exists());
+		cli.verify()
+	}
+
+	#[test]
+	fn sign_cancelled_when_not_confirmed() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("payload.json");
+		fs::write(&path, serde_json::to_string(&payload())?)?;
+		let mut cli = MockCli::new()
+			.expect_intro("Sign a payload offline")
+			.expect_confirm("Do you want to sign the payload?", false)
+			.expect_outro_cancel("🚫 Signing cancelled.");
+		SignArgs { payload: path, suri: Some("//Alice".into()), output: None, skip_confirm: false }
+			.execute(&mut cli)?;
+		assert!(!temp_dir.path().join("payload.signed.json").exists());
+		cli.verify()
+	}
+}
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	cli::traits::*,
+	common::{network, try_runtime::check_try_runtime_and_prompt},
+};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{create_snapshot, create_snapshot_args, finalized_block};
+use std::{
+	fs,
+	path::PathBuf,
+	time::{Duration, Instant},
+};
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+#[derive(Args)]
+pub struct SnapshotArgs {
+	/// Path to which the snapshot is written.
+	#[arg(default_value = "chain.snap")]
+	pub(crate) path: PathBuf,
+	/// Websocket endpoint of the node from which the state is downloaded.
+	#[arg(short, long, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	/// The pallets whose storage is included, separated by commas [default: all storage].
+	#[arg(long, value_delimiter = ',')]
+	pub(crate) pallets: Vec<String>,
+	/// The hash of the block at which the state is downloaded [default: the latest finalized
+	/// block].
+	#[arg(long)]
+	pub(crate) at: Option<String>,
+	/// Automatically source the try-runtime binary and overwrite any existing snapshot without
+	/// prompting for confirmation.
+	#[clap(short = 'y', long)]
+	pub(crate) skip_confirm: bool,
+}
+
+impl SnapshotArgs {
+	/// Executes the command, downloading the state of a live chain into a snapshot which can be
+	/// used by `pop test execute-block --snapshot`.
+	///
+	/// A failed or timed out download is retried (per `--retries`) against the same block,
+	/// restarting the download from scratch, with the snapshot only being written once complete.
+	/// Progress is reported as the time elapsed, as `try-runtime` does not report how much of the
+	/// state has been downloaded.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Create a snapshot of chain state")?;
+		if self.path.exists() &&
+			!self.skip_confirm &&
+			!cli.confirm(format!(
+				"A snapshot already exists at {}. Would you like to overwrite it?",
+				self.path.display()
+			))
+			.interact()?
+		{
+			cli.outro_cancel("🚫 Snapshot cancelled.")?;
+			return Ok(());
+		}
+		let binary = check_try_runtime_and_prompt(cli, &crate::cache()?, self.skip_confirm).await?;
+
+		// Pin the block, so that any retried download is of the same state.
+		let url = self.url.as_str();
+		let at = match self.at {
+			Some(at) => at,
+			None => {
+				let hash = network::retry(&format!("connecting to {url}"), || finalized_block(url))
+					.await?;
+				// The debug representation is the full (rather than abbreviated) hash.
+				format!("{hash:?}")
+			},
+		};
+		let storage = match self.pallets.is_empty() {
+			true => "all storage".to_string(),
+			false => format!("the storage of {}", self.pallets.join(", ")),
+		};
+		let message = format!("Downloading {storage} at block {at}...");
+
+		let partial = PathBuf::from(format!("{}.part", self.path.display()));
+		let args = create_snapshot_args(url, &at, &self.pallets, &partial);
+		let spinner = cli.spinner();
+		spinner.start(&message);
+		let started = Instant::now();
+		let download =
+			network::retry("downloading the snapshot", || create_snapshot(&binary, &args));
+		tokio::pin!(download);
+		let mut ticks = tokio::time::interval(Duration::from_secs(1));
+		let downloaded = loop {
+			tokio::select! {
+				result = &mut download => break result,
+				_ = ticks.tick() => spinner
+					.set_message(format!("{message} {}s elapsed", started.elapsed().as_secs())),
+			}
+		};
+		spinner.clear();
+		if let Err(e) = downloaded {
+			let _ = fs::remove_file(&partial);
+			cli.outro_cancel(format!("🚫 Could not create the snapshot: {e}"))?;
+			return Err(e);
+		}
+		fs::rename(&partial, &self.path)?;
+		cli.info(format!(
+			"Execute blocks against it via `pop test execute-block --snapshot {}`.",
+			self.path.display()
+		))?;
+		cli.outro(format!("✅ Snapshot written to {}", self.path.display()))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+
+	#[test]
+	fn snapshot_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"snapshot",
+			"--url",
+			"wss://rpc.example.com",
+			"--pallets",
+			"Balances,Assets",
+			"out.snap",
+		])?;
+		let crate::commands::Command::Snapshot(args) = pop.command else {
+			panic!("expected the snapshot command");
+		};
+		assert_eq!(args.path, PathBuf::from("out.snap"));
+		assert_eq!(args.url.as_str(), "wss://rpc.example.com/");
+		assert_eq!(args.pallets, vec!["Balances", "Assets"]);
+		assert_eq!((args.at, args.skip_confirm), (None, false));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn snapshot_cancelled_when_not_overwriting() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("out.snap");
+		fs::write(&path, [0u8])?;
+		let mut cli = MockCli::new()
+			.expect_intro("Create a snapshot of chain state")
+			.expect_confirm(
+				format!(
+					"A snapshot already exists at {}. Would you like to overwrite it?",
+					path.display()
+				),
+				false,
+			)
+			.expect_outro_cancel("🚫 Snapshot cancelled.");
+		SnapshotArgs {
+			path: path.clone(),
+			url: Url::parse(DEFAULT_URL)?,
+			pallets: vec![],
+			at: None,
+			skip_confirm: false,
+		}
+		.execute(&mut cli)
+		.await?;
+		assert_eq!(fs::read(&path)?, vec![0u8]);
+		cli.verify()
+	}
+}
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, commands::call::chain::CallChainCommand};
+use anyhow::Result;
+use clap::Args;
+use std::path::PathBuf;
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+#[derive(Args)]
+pub struct SubmitArgs {
+	/// Path to the payload signed via `pop sign`.
+	pub(crate) signed: PathBuf,
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+}
+
+impl SubmitArgs {
+	/// Executes the command, broadcasting an extrinsic signed offline once checked against the
+	/// chain.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		CallChainCommand::submit(self.url, self.signed).execute(cli).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn submit_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from(["pop", "submit", "payload.signed.json"])?;
+		let crate::commands::Command::Submit(args) = pop.command else {
+			panic!("expected the submit command");
+		};
+		assert_eq!(args.signed, PathBuf::from("payload.signed.json"));
+		assert_eq!(args.url, Url::parse(DEFAULT_URL)?);
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"submit",
+			"payload.signed.json",
+			"--url",
+			"wss://rpc1.paseo.popnetwork.xyz",
+		])?;
+		let crate::commands::Command::Submit(args) = pop.command else {
+			panic!("expected the submit command");
+		};
+		assert_eq!(args.url, Url::parse("wss://rpc1.paseo.popnetwork.xyz")?);
+		Ok(())
+	}
+}
@@ -1,12 +1,12 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::{
-	cli::{traits::Cli as _, Cli},
-	common::contracts::check_contracts_node_and_prompt,
-};
+use super::{record_run, write_coverage, write_report, CoverageArgs, SeedArgs};
+use crate::{cli::traits::Cli, common::contracts::check_contracts_node_and_prompt};
+use anyhow::anyhow;
 use clap::Args;
-use cliclack::{clear_screen, log::warning, outro};
-use pop_contracts::{test_e2e_smart_contract, test_smart_contract};
+use pop_contracts::{
+	has_sandbox_tests, test_e2e_smart_contract, test_sandbox_smart_contract, test_smart_contract,
+};
 use std::path::PathBuf;
 
 #[derive(Args)]
@@ -16,43 +16,138 @@ pub(crate) struct TestContractCommand {
 	/// Run end-to-end tests
 	#[arg(short, long)]
 	e2e: bool,
+	/// Run sandboxed tests, executing the contract within a runtime embedded in the tests (via
+	/// drink!) rather than on a node.
+	#[arg(long, conflicts_with = "e2e")]
+	sandbox: bool,
 	#[arg(short, long, help = "Path to the contracts node to run e2e tests [default: none]")]
 	node: Option<PathBuf>,
 	/// Automatically source the needed binary required without prompting for confirmation.
 	#[clap(short = 'y', long)]
 	skip_confirm: bool,
+	/// Write a JUnit XML report of the test results to the specified file.
+	#[arg(long)]
+	report: Option<PathBuf>,
+	#[command(flatten)]
+	coverage: CoverageArgs,
+	#[command(flatten)]
+	seed: SeedArgs,
 }
 
 impl TestContractCommand {
 	/// Executes the command.
-	pub(crate) async fn execute(mut self) -> anyhow::Result<&'static str> {
-		clear_screen()?;
-
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> anyhow::Result<&'static str> {
+		let project = self.path.clone().unwrap_or_else(|| PathBuf::from("./"));
 		if self.e2e {
-			Cli.intro("Starting end-to-end tests")?;
+			cli.intro("Starting end-to-end tests")?;
+			let run = self.seed.resolve(&project, "e2e", cli)?;
+			if self.coverage.coverage.is_some() {
+				cli.warning("Coverage is only collected by unit tests: end-to-end tests execute the contract as Wasm on a node, where it cannot be instrumented.")?;
+			}
 
 			self.node = match check_contracts_node_and_prompt(
-				&mut Cli,
+				cli,
 				&crate::cache()?,
+				None,
 				self.skip_confirm,
 			)
 			.await
 			{
 				Ok(binary_path) => Some(binary_path),
 				Err(_) => {
-					warning("🚫 substrate-contracts-node is necessary to run e2e tests. Will try to run tests anyway...")?;
+					cli.warning("🚫 substrate-contracts-node is necessary to run e2e tests. Will try to run tests anyway...")?;
 					Some(PathBuf::new())
 				},
 			};
 
-			test_e2e_smart_contract(self.path.as_deref(), self.node.as_deref())?;
-			outro("End-to-end testing complete")?;
+			let report = test_e2e_smart_contract(self.path.as_deref(), self.node.as_deref(), &run)?;
+			write_report(&report, self.report.as_deref(), "e2e", cli)?;
+			record_run(&run, &report, &project, "e2e", cli)?;
+			if !report.success {
+				cli.outro_cancel("🚫 End-to-end tests failed")?;
+				return Err(anyhow!("end-to-end tests failed"));
+			}
+			cli.outro("End-to-end testing complete")?;
 			Ok("e2e")
+		} else if self.sandbox {
+			cli.intro("Starting sandboxed tests")?;
+			if !has_sandbox_tests(self.path.as_deref())? {
+				cli.outro_cancel("🚫 Sandboxed tests require `drink` as a dev-dependency of the contract. See https://github.com/inkdevhub/drink.")?;
+				return Err(anyhow!("sandbox dependencies not found"));
+			}
+			let run = self.seed.resolve(&project, "sandbox", cli)?;
+			let coverage = self.coverage.resolve(cli)?;
+			let report =
+				test_sandbox_smart_contract(self.path.as_deref(), &run, coverage.as_ref())?;
+			write_report(&report, self.report.as_deref(), "sandbox", cli)?;
+			record_run(&run, &report, &project, "sandbox", cli)?;
+			write_coverage(coverage.as_ref(), &project, cli)?;
+			if !report.success {
+				cli.outro_cancel("🚫 Sandboxed tests failed")?;
+				return Err(anyhow!("sandboxed tests failed"));
+			}
+			cli.outro("Sandboxed testing complete")?;
+			Ok("sandbox")
 		} else {
-			Cli.intro("Starting unit tests")?;
-			test_smart_contract(self.path.as_deref())?;
-			outro("Unit testing complete")?;
+			cli.intro("Starting unit tests")?;
+			let run = self.seed.resolve(&project, "unit", cli)?;
+			let coverage = self.coverage.resolve(cli)?;
+			let report = test_smart_contract(self.path.as_deref(), &run, coverage.as_ref())?;
+			write_report(&report, self.report.as_deref(), "unit", cli)?;
+			record_run(&run, &report, &project, "unit", cli)?;
+			write_coverage(coverage.as_ref(), &project, cli)?;
+			if !report.success {
+				cli.outro_cancel("🚫 Unit tests failed")?;
+				return Err(anyhow!("unit tests failed"));
+			}
+			cli.outro("Unit testing complete")?;
 			Ok("unit")
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use std::fs;
+
+	#[test]
+	fn sandbox_conflicts_with_e2e() {
+		assert!(crate::Cli::try_parse_from(["pop", "test", "contract", "--sandbox"]).is_ok());
+		assert!(
+			crate::Cli::try_parse_from(["pop", "test", "contract", "--sandbox", "--e2e"]).is_err()
+		);
+	}
+
+	#[tokio::test]
+	async fn sandbox_requires_drink() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		fs::write(
+			temp_dir.path().join("Cargo.toml"),
+			"[package]\nname = \"flipper\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+		)?;
+		let mut cli = MockCli::new().expect_intro("Starting sandboxed tests").expect_outro_cancel(
+			"🚫 Sandboxed tests require `drink` as a dev-dependency of the contract. See https://github.com/inkdevhub/drink.",
+		);
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"test",
+			"contract",
+			"--sandbox",
+			"--path",
+			temp_dir.path().to_str().unwrap(),
+		])?;
+		let crate::commands::Command::Test(args) = pop.command else {
+			panic!("expected the test command");
+		};
+		let Some(super::super::Command::Contract(command)) = args.command else {
+			panic!("expected the contract command");
+		};
+		assert!(command.execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+}
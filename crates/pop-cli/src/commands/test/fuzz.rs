@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::{anyhow, Result};
+use clap::Args;
+use duct::cmd;
+use pop_common::test_report::TestRun;
+use pop_parachains::{FuzzHarness, FuzzOptions, FUZZ_TARGET};
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub(crate) struct TestFuzzCommand {
+	/// Path to the pallet [default: current directory].
+	#[arg(short, long)]
+	path: Option<PathBuf>,
+	/// The fuzz target to be run.
+	#[arg(short, long, default_value = FUZZ_TARGET)]
+	target: String,
+	/// The maximum duration of the run, in seconds [default: unlimited].
+	#[arg(long, value_name = "SECS")]
+	max_time: Option<u64>,
+	/// The maximum number of individual runs [default: unlimited].
+	#[arg(long)]
+	runs: Option<u64>,
+	/// The number of fuzzing jobs to run in parallel.
+	#[arg(short, long)]
+	jobs: Option<u16>,
+	/// The seed of the fuzzer, allowing a run to be repeated [default: random].
+	#[arg(long)]
+	seed: Option<u64>,
+	/// Reproduce a failure by running the target with the specified input, e.g. a crash.
+	#[arg(long, conflicts_with = "minimize")]
+	reproduce: Option<PathBuf>,
+	/// Minimize the corpus of the target, removing inputs which do not increase coverage.
+	#[arg(long)]
+	minimize: bool,
+}
+
+impl TestFuzzCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<&'static str> {
+		cli.intro("Starting fuzz tests")?;
+		let pallet = self.path.clone().unwrap_or_else(|| PathBuf::from("./"));
+		let harness = match FuzzHarness::new(&pallet) {
+			Ok(harness) => harness,
+			Err(e) => {
+				cli.outro_cancel(format!(
+					"🚫 {e}. Run `pop new fuzz` to generate a fuzzing harness for the pallet."
+				))?;
+				return Err(e.into());
+			},
+		};
+		let targets = harness.targets()?;
+		if !targets.contains(&self.target) {
+			cli.outro_cancel(format!(
+				"🚫 The fuzz target `{}` could not be found. Available targets: {}",
+				self.target,
+				targets.join(", ")
+			))?;
+			return Err(anyhow!("fuzz target not found: {}", self.target));
+		}
+		if !cargo_fuzz_installed() {
+			cli.outro_cancel(
+				"🚫 cargo-fuzz and a nightly toolchain are required. Install them via `rustup toolchain install nightly && cargo install cargo-fuzz`.",
+			)?;
+			return Err(anyhow!("cargo-fuzz is not installed"));
+		}
+
+		if let Some(input) = &self.reproduce {
+			cli.info(format!("Reproducing {}...", input.display()))?;
+			if !harness.reproduce(&self.target, input)? {
+				cli.outro_cancel("🚫 The input still causes a failure")?;
+				return Err(anyhow!("failure reproduced: {}", input.display()));
+			}
+			cli.outro("✅ The input no longer causes a failure")?;
+			return Ok("fuzz");
+		}
+		if self.minimize {
+			cli.info(format!(
+				"Minimizing the corpus at {}...",
+				harness.corpus(&self.target).display()
+			))?;
+			if !harness.minimize(&self.target)? {
+				cli.outro_cancel("🚫 The corpus could not be minimized")?;
+				return Err(anyhow!("corpus minimization failed"));
+			}
+			cli.outro("✅ Corpus minimized")?;
+			return Ok("fuzz");
+		}
+
+		let known = harness.crashes(&self.target)?;
+		let seed = TestRun::new(self.seed).seed;
+		cli.info(format!("Using seed {seed}. Reproduce via `--seed {seed}`."))?;
+		let options = FuzzOptions {
+			max_total_time: self.max_time,
+			runs: self.runs,
+			jobs: self.jobs,
+			seed: Some(seed),
+		};
+		let success = harness.run(&self.target, &options)?;
+		let crashes: Vec<_> = harness
+			.crashes(&self.target)?
+			.into_iter()
+			.filter(|c| !known.contains(c))
+			.collect();
+		if !crashes.is_empty() {
+			cli.warning(format!(
+				"Failing inputs:\n{}\nReproduce a failure via `pop test fuzz --reproduce <input>`.",
+				crashes.iter().map(|c| c.display().to_string()).collect::<Vec<_>>().join("\n")
+			))?;
+			cli.outro_cancel(format!("🚫 Fuzzing found {} failing input(s)", crashes.len()))?;
+			return Err(anyhow!("fuzzing found {} failing input(s)", crashes.len()));
+		}
+		if !success {
+			cli.outro_cancel("🚫 Fuzzing failed")?;
+			return Err(anyhow!("fuzzing failed"));
+		}
+		cli.outro("✅ Fuzzing complete, no failures found")?;
+		Ok("fuzz")
+	}
+}
+
+/// Whether cargo-fuzz is available via a nightly toolchain.
+fn cargo_fuzz_installed() -> bool {
+	cmd("cargo", ["+nightly", "fuzz", "--version"])
+		.stdout_null()
+		.stderr_null()
+		.unchecked()
+		.run()
+		.is_ok_and(|o| o.status.success())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use std::fs;
+
+	fn command(path: PathBuf) -> TestFuzzCommand {
+		TestFuzzCommand {
+			path: Some(path),
+			target: FUZZ_TARGET.into(),
+			max_time: None,
+			runs: None,
+			jobs: None,
+			seed: None,
+			reproduce: None,
+			minimize: false,
+		}
+	}
+
+	#[test]
+	fn test_fuzz_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"test",
+			"fuzz",
+			"-p",
+			"./my-pallet",
+			"--max-time",
+			"60",
+			"-j",
+			"4",
+			"--seed",
+			"42",
+		])?;
+		let crate::commands::Command::Test(args) = pop.command else {
+			panic!("expected the test command");
+		};
+		let Some(super::super::Command::Fuzz(command)) = args.command else {
+			panic!("expected the fuzz test command");
+		};
+		assert_eq!(command.path, Some(PathBuf::from("./my-pallet")));
+		assert_eq!(command.target, FUZZ_TARGET);
+		assert_eq!((command.max_time, command.runs, command.jobs), (Some(60), None, Some(4)));
+		assert_eq!(command.seed, Some(42));
+		assert!(crate::Cli::try_parse_from([
+			"pop",
+			"test",
+			"fuzz",
+			"--reproduce",
+			"crash-1",
+			"--minimize"
+		])
+		.is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn execute_fails_without_harness() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let mut cli = MockCli::new().expect_intro("Starting fuzz tests").expect_outro_cancel(
+			format!(
+				"🚫 Missing fuzzing harness at: {}. Run `pop new fuzz` to generate a fuzzing harness for the pallet.",
+				temp_dir.path().join("fuzz").display()
+			),
+		);
+		assert!(command(temp_dir.path().to_path_buf()).execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn execute_fails_with_unknown_target() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		fs::write(
+			temp_dir.path().join("Cargo.toml"),
+			"[package]\nname = \"pallet-fuzzed\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+		)?;
+		FuzzHarness::create(temp_dir.path())?;
+		let mut cli = MockCli::new().expect_intro("Starting fuzz tests").expect_outro_cancel(
+			"🚫 The fuzz target `transfers` could not be found. Available targets: extrinsics",
+		);
+		let mut command = command(temp_dir.path().to_path_buf());
+		command.target = "transfers".into();
+		assert!(command.execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+}
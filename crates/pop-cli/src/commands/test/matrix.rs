@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::write_report;
+use crate::cli::traits::*;
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_common::{
+	test_report::{cargo_test, declared_features, matrix, DEFAULT_FEATURES},
+	TestReport,
+};
+use std::path::PathBuf;
+
+/// Arguments for running the tests of a project across a matrix of feature sets and toolchains.
+#[derive(Args)]
+pub(crate) struct MatrixArgs {
+	/// Run the tests of the project across a matrix of feature sets and toolchains, aggregating
+	/// the results.
+	#[arg(long, required = true)]
+	pub(crate) matrix: bool,
+	/// Path to the project [default: current directory].
+	#[arg(long, requires = "matrix")]
+	pub(crate) path: Option<PathBuf>,
+	/// The feature sets to test, where `default` denotes the default features. Feature sets not
+	/// declared by the project are skipped.
+	#[arg(
+		long,
+		value_delimiter = ',',
+		default_values = [DEFAULT_FEATURES, "runtime-benchmarks", "try-runtime"],
+		requires = "matrix"
+	)]
+	pub(crate) features: Vec<String>,
+	/// The toolchains to test with, e.g. `stable,nightly`.
+	#[arg(long, value_delimiter = ',', default_value = "stable", requires = "matrix")]
+	pub(crate) toolchains: Vec<String>,
+	/// Write a JUnit XML report of the aggregated test results to the specified file.
+	#[arg(long, requires = "matrix")]
+	pub(crate) report: Option<PathBuf>,
+	/// Any arguments to be passed to `cargo test`, specified after `--`.
+	#[arg(last = true)]
+	pub(crate) args: Vec<String>,
+}
+
+impl MatrixArgs {
+	/// Executes the command, running the tests once for each entry of the matrix.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) fn execute(self, cli: &mut impl Cli) -> Result<&'static str> {
+		cli.intro("Testing across feature sets and toolchains")?;
+		let path = self.path.unwrap_or_else(|| PathBuf::from("./"));
+		let declared = declared_features(&path)?;
+		let mut features = vec![];
+		for set in self.features {
+			if set != DEFAULT_FEATURES && !declared.contains(&set) {
+				cli.warning(format!(
+					"Skipping the `{set}` feature set, which is not declared by the project."
+				))?;
+				continue;
+			}
+			features.push(set);
+		}
+		if features.is_empty() {
+			cli.outro_cancel("🚫 None of the feature sets are declared by the project.")?;
+			return Err(anyhow!("no feature sets to test"));
+		}
+
+		let mut reports = vec![];
+		for entry in matrix(&features, &self.toolchains) {
+			cli.info(format!("Running tests with {}...", entry.label()))?;
+			let report = cargo_test(&path, &entry.args(&self.args), &entry.envs(), None)?;
+			reports.push((entry.label(), report));
+		}
+		for (label, report) in &reports {
+			summarize(label, report, cli)?;
+		}
+
+		let report = TestReport::merge(&reports);
+		write_report(&report, self.report.as_deref(), "matrix", cli)?;
+		let failed = reports.iter().filter(|(_, r)| !r.success).count();
+		if failed > 0 {
+			cli.outro_cancel(format!("🚫 Tests failed for {failed} of {} entries", reports.len()))?;
+			return Err(anyhow!("matrix tests failed"));
+		}
+		cli.outro(format!("✅ Tests passed for all {} entries", reports.len()))?;
+		Ok("matrix")
+	}
+}
+
+/// Reports the outcome of the tests of a matrix entry.
+fn summarize(label: &str, report: &TestReport, cli: &mut impl Cli) -> Result<()> {
+	let passed = report.cases.len() - report.failures() - report.ignored();
+	if report.success {
+		cli.success(format!("{label}: {passed} passed, {} ignored", report.ignored()))?;
+	} else if report.cases.is_empty() {
+		cli.error(format!("{label}: the tests could not be run"))?;
+	} else {
+		cli.error(format!("{label}: {passed} passed, {} failed", report.failures()))?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use duct::cmd;
+	use std::fs;
+
+	#[test]
+	fn matrix_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from(["pop", "test", "--matrix"])?;
+		let crate::commands::Command::Test(args) = pop.command else {
+			panic!("expected the test command");
+		};
+		assert!(args.command.is_none());
+		assert_eq!(args.matrix.features, vec!["default", "runtime-benchmarks", "try-runtime"]);
+		assert_eq!(args.matrix.toolchains, vec!["stable"]);
+
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"test",
+			"--matrix",
+			"--features",
+			"default,try-runtime",
+			"--toolchains",
+			"stable,nightly",
+			"--",
+			"--offline",
+		])?;
+		let crate::commands::Command::Test(args) = pop.command else {
+			panic!("expected the test command");
+		};
+		assert_eq!(args.matrix.features, vec!["default", "try-runtime"]);
+		assert_eq!(args.matrix.toolchains, vec!["stable", "nightly"]);
+		assert_eq!(args.matrix.args, vec!["--offline"]);
+
+		assert!(crate::Cli::try_parse_from(["pop", "test"]).is_err());
+		assert!(crate::Cli::try_parse_from(["pop", "test", "--features", "try-runtime"]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn summarize_works() -> Result<()> {
+		let mut cli = MockCli::new()
+			.expect_success("default@stable: 0 passed, 0 ignored")
+			.expect_error("try-runtime@stable: the tests could not be run");
+		summarize("default@stable", &TestReport { cases: vec![], success: true }, &mut cli)?;
+		summarize("try-runtime@stable", &TestReport::default(), &mut cli)?;
+		cli.verify()
+	}
+
+	#[test]
+	fn execute_skips_undeclared_feature_sets() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		cmd("cargo", ["init", "--lib", "--vcs", "none", "--name", "matrix"])
+			.dir(temp_dir.path())
+			.stderr_null()
+			.run()?;
+		let manifest = temp_dir.path().join("Cargo.toml");
+		let contents = fs::read_to_string(&manifest)?;
+		fs::write(&manifest, format!("{contents}\n[features]\ntry-runtime = []\n"))?;
+		fs::write(
+			temp_dir.path().join("src/lib.rs"),
+			"#[cfg(not(feature = \"try-runtime\"))]\n#[test]\nfn passes() {}\n",
+		)?;
+		let mut cli = MockCli::new()
+			.expect_intro("Testing across feature sets and toolchains")
+			.expect_warning(
+				"Skipping the `runtime-benchmarks` feature set, which is not declared by the project.",
+			)
+			.expect_info("Running tests with default@stable...")
+			.expect_info("Running tests with try-runtime@stable...")
+			.expect_success("default@stable: 1 passed, 0 ignored")
+			.expect_success("try-runtime@stable: 0 passed, 0 ignored")
+			.expect_outro("✅ Tests passed for all 2 entries");
+		MatrixArgs {
+			matrix: true,
+			path: Some(temp_dir.path().to_path_buf()),
+			features: vec!["default".into(), "runtime-benchmarks".into(), "try-runtime".into()],
+			toolchains: vec!["stable".into()],
+			report: None,
+			args: vec!["--offline".into()],
+		}
+		.execute(&mut cli)?;
+		cli.verify()
+	}
+}
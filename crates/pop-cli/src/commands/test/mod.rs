@@ -1,23 +1,285 @@
 // SPDX-License-Identifier: GPL-3.0
 
+use crate::cli::traits::Cli;
+use anyhow::anyhow;
 use clap::{Args, Subcommand};
+use pop_common::{
+	test_report::{llvm_cov_installed, Coverage, CoverageFormat, TestRun},
+	TestReport,
+};
+use std::path::{Path, PathBuf};
 
 #[cfg(feature = "contract")]
 pub mod contract;
+#[cfg(feature = "parachain")]
+pub mod fuzz;
+pub mod matrix;
+#[cfg(feature = "parachain")]
+pub mod network;
+#[cfg(feature = "parachain")]
+pub mod parachain;
+#[cfg(feature = "parachain")]
+pub mod try_runtime;
+#[cfg(feature = "benchmark")]
+pub mod weights;
 
 /// Arguments for testing.
 #[derive(Args)]
-#[command(args_conflicts_with_subcommands = true)]
-pub(crate) struct TestArgs {
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct TestArgs {
 	#[command(subcommand)]
-	pub command: Command,
+	pub(crate) command: Option<Command>,
+	#[command(flatten)]
+	pub(crate) matrix: matrix::MatrixArgs,
 }
 
-/// Test a smart contract.
+/// Test a smart contract or parachain.
 #[derive(Subcommand)]
 pub(crate) enum Command {
 	/// Test a smart contract
 	#[cfg(feature = "contract")]
 	#[clap(alias = "c")]
 	Contract(contract::TestContractCommand),
+	/// Test a parachain, by running its integration tests against its local network
+	#[cfg(feature = "parachain")]
+	#[clap(alias = "p")]
+	Parachain(parachain::TestParachainCommand),
+	/// Run the network tests generated via `pop new test network`
+	#[cfg(feature = "parachain")]
+	#[clap(alias = "n")]
+	Network(network::TestNetworkCommand),
+	/// Fuzz the extrinsics of a pallet, using the harness generated via `pop new fuzz`
+	#[cfg(feature = "parachain")]
+	#[clap(alias = "f")]
+	Fuzz(fuzz::TestFuzzCommand),
+	/// Replay a block against the locally built runtime via try-runtime, using the state of a
+	/// live chain or a local snapshot
+	#[cfg(feature = "parachain")]
+	#[clap(alias = "eb")]
+	ExecuteBlock(try_runtime::TestExecuteBlockCommand),
+	/// Replay each newly finalized block of a live chain against the locally built runtime via
+	/// try-runtime
+	#[cfg(feature = "parachain")]
+	#[clap(alias = "fc")]
+	FollowChain(try_runtime::TestFollowChainCommand),
+	/// Assert that extrinsics stay within the weights declared in a configuration file, via a
+	/// quick benchmark of each
+	#[cfg(feature = "benchmark")]
+	#[clap(alias = "w")]
+	Weights(weights::TestWeightsCommand),
+}
+
+/// Arguments for seeding a test run, or replaying the last failing run.
+#[derive(Args)]
+pub(crate) struct SeedArgs {
+	/// The seed provided to the tests via `POP_TEST_SEED`, from which any random data should be
+	/// derived [default: random].
+	#[arg(long)]
+	pub(crate) seed: Option<u64>,
+	/// Re-run only the tests which failed during the last run, using the same seed.
+	#[arg(long, conflicts_with = "seed")]
+	pub(crate) rerun_failed: bool,
+}
+
+impl SeedArgs {
+	/// The test run to be performed, reporting the seed used.
+	///
+	/// # Arguments
+	/// * `project` - The path to the project.
+	/// * `suite` - The name of the test suite.
+	/// * `cli` - The cli.
+	pub(crate) fn resolve(
+		&self,
+		project: &Path,
+		suite: &str,
+		cli: &mut impl Cli,
+	) -> anyhow::Result<TestRun> {
+		if !self.rerun_failed {
+			let run = TestRun::new(self.seed);
+			cli.info(format!("Using seed {}. Reproduce via `--seed {}`.", run.seed, run.seed))?;
+			return Ok(run);
+		}
+		match TestRun::load(project, suite)? {
+			Some(run) => {
+				cli.info(format!(
+					"Re-running {} failed test(s) with seed {}: {}",
+					run.tests.len(),
+					run.seed,
+					run.tests.join(", ")
+				))?;
+				Ok(run)
+			},
+			None => {
+				cli.outro_cancel(format!("🚫 No failing {suite} test run has been recorded."))?;
+				Err(anyhow!("no failing {suite} test run recorded"))
+			},
+		}
+	}
+}
+
+/// Records the outcome of a test run, so that any failures can be replayed via
+/// `--rerun-failed`.
+///
+/// # Arguments
+/// * `run` - The test run.
+/// * `report` - The report of the test run.
+/// * `project` - The path to the project.
+/// * `suite` - The name of the test suite.
+/// * `cli` - The cli.
+pub(crate) fn record_run(
+	run: &TestRun,
+	report: &TestReport,
+	project: &Path,
+	suite: &str,
+	cli: &mut impl Cli,
+) -> anyhow::Result<()> {
+	run.record(project, suite, report)?;
+	if report.failures() > 0 {
+		cli.info(format!("Re-run the failed tests with seed {} via `--rerun-failed`.", run.seed))?;
+	}
+	Ok(())
+}
+
+/// Arguments for collecting coverage whilst testing.
+#[derive(Args)]
+pub(crate) struct CoverageArgs {
+	/// Collect coverage via `cargo llvm-cov`, reported in the specified format: `lcov` or
+	/// `html`.
+	#[arg(long, value_name = "FORMAT")]
+	pub(crate) coverage: Option<CoverageFormat>,
+	/// The location of the coverage report, relative to the project [default: within
+	/// `target/coverage`].
+	#[arg(long, requires = "coverage")]
+	pub(crate) coverage_output: Option<PathBuf>,
+}
+
+impl CoverageArgs {
+	/// The coverage to be collected, if any, failing should `cargo llvm-cov` not be installed.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) fn resolve(&self, cli: &mut impl Cli) -> anyhow::Result<Option<Coverage>> {
+		let Some(format) = self.coverage else {
+			return Ok(None);
+		};
+		if !llvm_cov_installed() {
+			cli.outro_cancel(
+				"🚫 cargo-llvm-cov is required to collect coverage. Install it via `cargo install cargo-llvm-cov`.",
+			)?;
+			return Err(anyhow!("cargo-llvm-cov is not installed"));
+		}
+		Ok(Some(Coverage { format, output: self.coverage_output.clone() }))
+	}
+}
+
+/// Reports the location of the coverage report of a test run, if coverage was collected.
+///
+/// # Arguments
+/// * `coverage` - The coverage collected, if any.
+/// * `project` - The path to the project.
+/// * `cli` - The cli.
+pub(crate) fn write_coverage(
+	coverage: Option<&Coverage>,
+	project: &Path,
+	cli: &mut impl Cli,
+) -> anyhow::Result<()> {
+	if let Some(coverage) = coverage {
+		cli.info(format!(
+			"Coverage report written to {}",
+			project.join(coverage.report()).display()
+		))?;
+	}
+	Ok(())
+}
+
+/// Writes the report of a test run as JUnit XML, if a report file was specified.
+///
+/// # Arguments
+/// * `report` - The report of the test run.
+/// * `path` - The file to which the report should be written, if any.
+/// * `suite` - The name of the test suite.
+/// * `cli` - The cli.
+pub(crate) fn write_report(
+	report: &TestReport,
+	path: Option<&Path>,
+	suite: &str,
+	cli: &mut impl Cli,
+) -> anyhow::Result<()> {
+	if let Some(path) = path {
+		report.write_junit(path, suite)?;
+		cli.info(format!("JUnit report written to {}", path.display()))?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use pop_common::test_report::{TestCase, TestOutcome};
+	use std::fs;
+
+	#[test]
+	fn write_report_works() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("junit.xml");
+		let report = TestReport {
+			cases: vec![TestCase { name: "tests::it_works".into(), outcome: TestOutcome::Passed }],
+			success: true,
+		};
+		let mut cli = MockCli::new();
+		write_report(&report, None, "unit", &mut cli)?;
+		assert!(!path.exists());
+		let mut cli =
+			MockCli::new().expect_info(format!("JUnit report written to {}", path.display()));
+		write_report(&report, Some(&path), "unit", &mut cli)?;
+		assert_eq!(fs::read_to_string(&path)?, report.to_junit("unit"));
+		cli.verify()
+	}
+
+	#[test]
+	fn write_coverage_works() -> anyhow::Result<()> {
+		let mut cli = MockCli::new();
+		write_coverage(None, Path::new("./"), &mut cli)?;
+		cli.verify()?;
+		let coverage = Coverage { format: CoverageFormat::Html, output: None };
+		let mut cli = MockCli::new()
+			.expect_info("Coverage report written to ./my-project/target/coverage/html/index.html");
+		write_coverage(Some(&coverage), Path::new("./my-project"), &mut cli)?;
+		cli.verify()
+	}
+
+	#[test]
+	fn seed_args_works() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let args = SeedArgs { seed: Some(42), rerun_failed: false };
+		let mut cli = MockCli::new().expect_info("Using seed 42. Reproduce via `--seed 42`.");
+		assert_eq!(args.resolve(temp_dir.path(), "unit", &mut cli)?, TestRun::new(Some(42)));
+		cli.verify()?;
+
+		let args = SeedArgs { seed: None, rerun_failed: true };
+		let mut cli =
+			MockCli::new().expect_outro_cancel("🚫 No failing unit test run has been recorded.");
+		assert!(args.resolve(temp_dir.path(), "unit", &mut cli).is_err());
+		cli.verify()?;
+
+		let report = TestReport {
+			cases: vec![TestCase {
+				name: "tests::flaky".into(),
+				outcome: TestOutcome::Failed(None),
+			}],
+			success: false,
+		};
+		let mut cli = MockCli::new()
+			.expect_info("Re-run the failed tests with seed 42 via `--rerun-failed`.");
+		record_run(&TestRun::new(Some(42)), &report, temp_dir.path(), "unit", &mut cli)?;
+		cli.verify()?;
+		let mut cli =
+			MockCli::new().expect_info("Re-running 1 failed test(s) with seed 42: tests::flaky");
+		assert_eq!(
+			args.resolve(temp_dir.path(), "unit", &mut cli)?,
+			TestRun { seed: 42, tests: vec!["tests::flaky".into()] }
+		);
+		cli.verify()
+	}
 }
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::write_report;
+use crate::{cli::traits::*, commands::up::network::ZombienetCommand};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_common::test_report::cargo_test;
+use pop_parachains::Zombienet;
+use std::path::PathBuf;
+
+/// The environment variable via which the network configuration is provided to the tests.
+const NETWORK_CONFIG_ENV: &str = "POP_NETWORK_CONFIG";
+
+#[derive(Args)]
+pub(crate) struct TestNetworkCommand {
+	/// Path to the parachain project [default: current directory].
+	#[arg(short, long)]
+	path: Option<PathBuf>,
+	/// The network tests generated via `pop new test network`, relative to the project.
+	#[arg(long, default_value = "network-tests")]
+	tests: PathBuf,
+	/// The Zombienet network configuration file to be used, relative to the project.
+	#[arg(short, long, default_value = "network.toml")]
+	file: PathBuf,
+	/// Automatically source all needed binaries required without prompting for confirmation.
+	#[clap(short = 'y', long)]
+	skip_confirm: bool,
+	/// Write a JUnit XML report of the test results to the specified file.
+	#[arg(long)]
+	report: Option<PathBuf>,
+	/// Any arguments to be passed to `cargo test`, specified after `--` (e.g. `-- --ignored`).
+	#[arg(last = true)]
+	args: Vec<String>,
+}
+
+impl TestNetworkCommand {
+	/// Executes the command, sourcing the binaries required by the network and then running the
+	/// network tests, which launch the network themselves.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<&'static str> {
+		cli.intro("Starting network tests")?;
+		let path = self.path.unwrap_or_else(|| PathBuf::from("./"));
+		let tests = path.join(&self.tests);
+		if !tests.join("Cargo.toml").exists() {
+			cli.outro_cancel(format!(
+				"🚫 The network tests could not be found at {}. Run `pop new test network` to generate them.",
+				tests.display()
+			))?;
+			return Err(anyhow!("network tests not found: {}", tests.display()));
+		}
+		let file = path.join(&self.file);
+		if !file.exists() {
+			cli.outro_cancel(format!(
+				"🚫 The network configuration file could not be found at {}.",
+				file.display()
+			))?;
+			return Err(anyhow!("network configuration file not found: {}", file.display()));
+		}
+
+		// Source any missing/stale binaries required by the network.
+		let cache = crate::cache()?;
+		let file = file.to_str().ok_or(anyhow!("invalid network configuration file path"))?;
+		let mut zombienet = Zombienet::new(&cache, file, None, None, None, None, None).await?;
+		if ZombienetCommand::source_binaries(&mut zombienet, &cache, false, self.skip_confirm, cli)
+			.await?
+		{
+			return Err(anyhow!("the binaries required to launch the network are unavailable"));
+		}
+		let config = zombienet.prepare()?;
+
+		let envs = [(NETWORK_CONFIG_ENV.to_string(), config.path().display().to_string())];
+		let report = cargo_test(&tests, &self.args, &envs, None)?;
+		write_report(&report, self.report.as_deref(), "network", cli)?;
+		if !report.success {
+			cli.outro_cancel("🚫 Network tests failed")?;
+			return Err(anyhow!("network tests failed"));
+		}
+		cli.outro("✅ Network tests passed")?;
+		Ok("network")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use std::fs;
+
+	fn command(path: PathBuf) -> TestNetworkCommand {
+		TestNetworkCommand {
+			path: Some(path),
+			tests: PathBuf::from("network-tests"),
+			file: PathBuf::from("network.toml"),
+			skip_confirm: true,
+			report: None,
+			args: vec![],
+		}
+	}
+
+	#[test]
+	fn test_network_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"test",
+			"network",
+			"-p",
+			"./my-parachain",
+			"--",
+			"--ignored",
+		])?;
+		let crate::commands::Command::Test(args) = pop.command else {
+			panic!("expected the test command");
+		};
+		let Some(super::super::Command::Network(command)) = args.command else {
+			panic!("expected the network test command");
+		};
+		assert_eq!(command.path, Some(PathBuf::from("./my-parachain")));
+		assert_eq!(command.tests, PathBuf::from("network-tests"));
+		assert_eq!(command.file, PathBuf::from("network.toml"));
+		assert_eq!(command.args, vec!["--ignored"]);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn execute_fails_without_tests() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let mut cli =
+			MockCli::new()
+				.expect_intro("Starting network tests")
+				.expect_outro_cancel(format!(
+				"🚫 The network tests could not be found at {}. Run `pop new test network` to generate them.",
+				temp_dir.path().join("network-tests").display()
+			));
+		assert!(command(temp_dir.path().to_path_buf()).execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn execute_fails_without_network_config() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let tests = temp_dir.path().join("network-tests");
+		fs::create_dir(&tests)?;
+		fs::write(tests.join("Cargo.toml"), "")?;
+		let mut cli =
+			MockCli::new()
+				.expect_intro("Starting network tests")
+				.expect_outro_cancel(format!(
+					"🚫 The network configuration file could not be found at {}.",
+					temp_dir.path().join("network.toml").display()
+				));
+		assert!(command(temp_dir.path().to_path_buf()).execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+}
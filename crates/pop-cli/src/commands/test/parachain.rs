@@ -0,0 +1,343 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{record_run, write_coverage, write_report, CoverageArgs, SeedArgs};
+use crate::{
+	cli::traits::*,
+	commands::up::network::ZombienetCommand,
+	common::{
+		interrupt::{unless_interrupted, Handled},
+		network::{self, TimedOut},
+	},
+};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_common::test_report::{cargo_test, Coverage, TestReport, TestRun};
+use pop_parachains::{wait_for, wait_for_client, Condition, Zombienet};
+use std::{
+	path::{Path, PathBuf},
+	time::{Duration, Instant},
+};
+
+/// The default network configuration file, relative to the project.
+const DEFAULT_NETWORK_CONFIG: &str = "network.toml";
+/// The default maximum time to wait for block production, unless otherwise specified via
+/// `--timeout`.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+/// The interval between attempts to connect to the relay chain.
+const RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Args)]
+pub(crate) struct TestParachainCommand {
+	/// Path to the parachain project [default: current directory].
+	#[arg(short, long)]
+	path: Option<PathBuf>,
+	/// The Zombienet network configuration file to be used, relative to the project.
+	#[arg(short, long, default_value = DEFAULT_NETWORK_CONFIG)]
+	file: PathBuf,
+	/// Automatically source all needed binaries required without prompting for confirmation.
+	#[clap(short = 'y', long)]
+	skip_confirm: bool,
+	/// Write a JUnit XML report of the test results to the specified file.
+	#[arg(long)]
+	report: Option<PathBuf>,
+	#[command(flatten)]
+	coverage: CoverageArgs,
+	#[command(flatten)]
+	seed: SeedArgs,
+	/// Any arguments to be passed to `cargo test`, specified after `--` (e.g. `-- --test
+	/// integration`).
+	#[arg(last = true)]
+	args: Vec<String>,
+}
+
+impl TestParachainCommand {
+	/// Executes the command, launching the network of the project, waiting for block production
+	/// and then running the integration tests of the project against it.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<&'static str> {
+		cli.intro("Starting parachain integration tests")?;
+		let started = Instant::now();
+		let path = self.path.unwrap_or_else(|| PathBuf::from("./"));
+		let file = path.join(&self.file);
+		if !file.exists() {
+			cli.outro_cancel(format!(
+				"🚫 The network configuration file could not be found at {}.",
+				file.display()
+			))?;
+			return Err(anyhow!("network configuration file not found: {}", file.display()));
+		}
+		let run = self.seed.resolve(&path, "parachain", cli)?;
+		let coverage = self.coverage.resolve(cli)?;
+
+		// Source any missing/stale binaries required by the network.
+		let cache = crate::cache()?;
+		let file = file.to_str().ok_or(anyhow!("invalid network configuration file path"))?;
+		let mut zombienet = Zombienet::new(&cache, file, None, None, None, None, None).await?;
+		if ZombienetCommand::source_binaries(&mut zombienet, &cache, false, self.skip_confirm, cli)
+			.await?
+		{
+			return Err(anyhow!("the binaries required to launch the network are unavailable"));
+		}
+
+		// Interrupts are handled from here on, so that any launched nodes are terminated.
+		let _interrupts = Handled::new();
+		let spinner = cli.spinner();
+		spinner.start("🚀 Launching local network...");
+		let network = match unless_interrupted(zombienet.spawn()).await {
+			Some(Ok(network)) => network,
+			Some(Err(e)) => {
+				spinner.error(format!("🚫 Could not launch local network: {e}"));
+				return Err(e.into());
+			},
+			None => {
+				spinner.error("🚫 Launch of the network was cancelled.");
+				return Err(anyhow!("interrupted"));
+			},
+		};
+		let mut relay_nodes = network.relaychain().nodes();
+		relay_nodes.sort_by_key(|n| n.name());
+		let relay_chain = relay_nodes[0].ws_uri().to_string();
+		let mut parachains: Vec<_> = network
+			.parachains()
+			.iter()
+			.filter_map(|p| {
+				let mut collators = p.collators();
+				collators.sort_by_key(|n| n.name());
+				collators.first().map(|c| (p.para_id(), c.ws_uri().to_string()))
+			})
+			.collect();
+		parachains.sort();
+
+		// Wait for each parachain to produce blocks, then run the tests.
+		spinner.set_message("⏳ Waiting for block production...");
+		let timeout = network::settings().timeout.unwrap_or(DEFAULT_TIMEOUT);
+		let para_ids: Vec<_> = parachains.iter().map(|(id, _)| *id).collect();
+		let waited = unless_interrupted(wait_for_blocks(&relay_chain, &para_ids, timeout)).await;
+		let result = match waited.unwrap_or_else(|| Err(anyhow!("interrupted"))) {
+			Ok(()) => {
+				let endpoints = endpoints(&relay_chain, &parachains);
+				spinner.stop(format!(
+					"✅ Network producing blocks:\n{}",
+					endpoints
+						.iter()
+						.map(|(k, v)| format!("{k}={v}"))
+						.collect::<Vec<_>>()
+						.join("\n")
+				));
+				run_tests(&path, &run, &self.args, &endpoints, coverage.as_ref())
+			},
+			Err(e) => {
+				spinner.error(format!("🚫 {e}"));
+				Err(e)
+			},
+		};
+
+		// Tear down the network, regardless of the outcome.
+		let spinner = cli.spinner();
+		spinner.start("Tearing down the network...");
+		match network.destroy().await {
+			Ok(()) => spinner.stop("Network torn down."),
+			Err(e) => spinner.error(format!("Could not tear down the network: {e}")),
+		}
+
+		let elapsed = started.elapsed().as_secs_f64();
+		match result {
+			Ok(report) => {
+				write_report(&report, self.report.as_deref(), "parachain", cli)?;
+				record_run(&run, &report, &path, "parachain", cli)?;
+				write_coverage(coverage.as_ref(), &path, cli)?;
+				if report.success {
+					cli.outro(format!("✅ Integration tests passed in {elapsed:.1}s"))?;
+					return Ok("parachain");
+				}
+				cli.outro_cancel(format!("🚫 Integration tests failed in {elapsed:.1}s"))?;
+				Err(anyhow!("integration tests failed"))
+			},
+			Err(e) => {
+				cli.outro_cancel("🚫 Integration tests could not be run")?;
+				Err(e)
+			},
+		}
+	}
+}
+
+/// Waits until each parachain is producing blocks, as observed via the relay chain.
+///
+/// # Arguments
+/// * `relay_chain` - Endpoint of the relay chain.
+/// * `para_ids` - The identifiers of the parachains.
+/// * `timeout` - The maximum time to wait.
+async fn wait_for_blocks(relay_chain: &str, para_ids: &[u32], timeout: Duration) -> Result<()> {
+	let wait = async {
+		let client = wait_for_client(relay_chain, RETRY_INTERVAL).await;
+		for id in para_ids {
+			wait_for(&client, &Condition::Producing(*id)).await?;
+		}
+		Ok::<_, anyhow::Error>(())
+	};
+	match tokio::time::timeout(timeout, wait).await {
+		Ok(result) => result,
+		Err(_) =>
+			Err(TimedOut { operation: "waiting for block production".into(), timeout }.into()),
+	}
+}
+
+/// The environment variables via which the endpoints of the network are provided to the tests:
+/// `POP_RELAY_CHAIN_URL`, `POP_PARACHAIN_<ID>_URL` for each parachain and, when the network
+/// consists of a single parachain, `POP_PARACHAIN_URL`.
+///
+/// # Arguments
+/// * `relay_chain` - Endpoint of the relay chain.
+/// * `parachains` - The identifier and endpoint of each parachain.
+fn endpoints(relay_chain: &str, parachains: &[(u32, String)]) -> Vec<(String, String)> {
+	let mut endpoints = vec![("POP_RELAY_CHAIN_URL".to_string(), relay_chain.to_string())];
+	if let [(_, url)] = parachains {
+		endpoints.push(("POP_PARACHAIN_URL".to_string(), url.clone()));
+	}
+	endpoints.extend(
+		parachains
+			.iter()
+			.map(|(id, url)| (format!("POP_PARACHAIN_{id}_URL"), url.clone())),
+	);
+	endpoints
+}
+
+/// Runs the tests of the project, returning a report of the results.
+///
+/// # Arguments
+/// * `path` - The path to the project.
+/// * `run` - The seed and tests of the run.
+/// * `args` - Any arguments to be passed to `cargo test`.
+/// * `endpoints` - The environment variables providing the endpoints of the network.
+/// * `coverage` - Whether coverage should be collected, and how it should be reported.
+fn run_tests(
+	path: &Path,
+	run: &TestRun,
+	args: &[String],
+	endpoints: &[(String, String)],
+	coverage: Option<&Coverage>,
+) -> Result<TestReport> {
+	let envs: Vec<_> = endpoints.iter().cloned().chain(run.envs()).collect();
+	Ok(cargo_test(path, &run.args(args), &envs, coverage)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use duct::cmd;
+	use pop_common::test_report::CoverageFormat;
+
+	#[test]
+	fn test_parachain_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"test",
+			"parachain",
+			"-p",
+			"./my-parachain",
+			"--report",
+			"junit.xml",
+			"--coverage",
+			"lcov",
+			"--",
+			"--test",
+			"integration",
+		])?;
+		let crate::commands::Command::Test(args) = pop.command else {
+			panic!("expected the test command");
+		};
+		let Some(super::super::Command::Parachain(command)) = args.command else {
+			panic!("expected the parachain test command");
+		};
+		assert_eq!(command.path, Some(PathBuf::from("./my-parachain")));
+		assert_eq!(command.file, PathBuf::from(DEFAULT_NETWORK_CONFIG));
+		assert_eq!(command.report, Some(PathBuf::from("junit.xml")));
+		assert_eq!(command.coverage.coverage, Some(CoverageFormat::Lcov));
+		assert_eq!(command.coverage.coverage_output, None);
+		assert_eq!(command.args, vec!["--test", "integration"]);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn execute_fails_without_network_config() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let file = temp_dir.path().join(DEFAULT_NETWORK_CONFIG);
+		let mut cli = MockCli::new()
+			.expect_intro("Starting parachain integration tests")
+			.expect_outro_cancel(format!(
+				"🚫 The network configuration file could not be found at {}.",
+				file.display()
+			));
+		let command = TestParachainCommand {
+			path: Some(temp_dir.path().to_path_buf()),
+			file: PathBuf::from(DEFAULT_NETWORK_CONFIG),
+			skip_confirm: true,
+			report: None,
+			coverage: CoverageArgs { coverage: None, coverage_output: None },
+			seed: SeedArgs { seed: None, rerun_failed: false },
+			args: vec![],
+		};
+		assert!(command.execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+
+	#[test]
+	fn endpoints_works() {
+		let relay_chain = "ws://127.0.0.1:9944";
+		assert_eq!(
+			endpoints(relay_chain, &[(2000, "ws://127.0.0.1:9945".into())]),
+			vec![
+				("POP_RELAY_CHAIN_URL".to_string(), relay_chain.to_string()),
+				("POP_PARACHAIN_URL".to_string(), "ws://127.0.0.1:9945".to_string()),
+				("POP_PARACHAIN_2000_URL".to_string(), "ws://127.0.0.1:9945".to_string()),
+			]
+		);
+		assert_eq!(
+			endpoints(
+				relay_chain,
+				&[(1000, "ws://127.0.0.1:9945".into()), (2000, "ws://127.0.0.1:9946".into())]
+			),
+			vec![
+				("POP_RELAY_CHAIN_URL".to_string(), relay_chain.to_string()),
+				("POP_PARACHAIN_1000_URL".to_string(), "ws://127.0.0.1:9945".to_string()),
+				("POP_PARACHAIN_2000_URL".to_string(), "ws://127.0.0.1:9946".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn run_tests_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		cmd("cargo", ["init", "--lib", "--vcs", "none", "--name", "integration"])
+			.dir(temp_dir.path())
+			.stderr_null()
+			.run()?;
+		std::fs::write(
+			temp_dir.path().join("src/lib.rs"),
+			r#"#[test]
+fn endpoint_provided() {
+	assert_eq!(std::env::var("POP_PARACHAIN_URL").unwrap(), "ws://127.0.0.1:9945");
+}
+#[test]
+fn seed_provided() {
+	assert_eq!(std::env::var("POP_TEST_SEED").unwrap(), "42");
+}"#,
+		)?;
+		let endpoints = [("POP_PARACHAIN_URL".to_string(), "ws://127.0.0.1:9945".to_string())];
+		let run = TestRun::new(Some(42));
+		assert!(run_tests(temp_dir.path(), &run, &["--offline".into()], &endpoints, None)?.success);
+		let report = run_tests(temp_dir.path(), &run, &["--offline".into()], &[], None)?;
+		assert!(!report.success);
+		assert_eq!(report.failures(), 1);
+		// Only the specified tests are run.
+		let run = TestRun { seed: 42, tests: vec!["seed_provided".into()] };
+		let report = run_tests(temp_dir.path(), &run, &["--offline".into()], &[], None)?;
+		assert!(report.success);
+		assert_eq!(report.cases.len(), 1);
+		Ok(())
+	}
+}
@@ -0,0 +1,261 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::try_runtime::check_try_runtime_and_prompt};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_parachains::{
+	build_try_runtime, execute_block_args, follow_chain_args, run_try_runtime, State,
+	TryStateSelect,
+};
+use std::path::{Path, PathBuf};
+
+/// The default endpoint of the node providing the blocks and state.
+const DEFAULT_URI: &str = "ws://127.0.0.1:9944";
+
+/// Arguments shared by the commands replaying blocks against a runtime via `try-runtime`.
+#[derive(Args)]
+pub(crate) struct TryRuntimeArgs {
+	/// Path to the parachain project [default: current directory].
+	#[arg(short, long)]
+	path: Option<PathBuf>,
+	/// The runtime to be tested, built with the `try-runtime` feature [default: built from
+	/// the `runtime` of the project].
+	#[arg(long, value_name = "WASM")]
+	runtime: Option<PathBuf>,
+	/// The checks to be performed after executing each block: `all`, `none` or a
+	/// comma-separated list of pallets.
+	#[arg(long, default_value = "all")]
+	try_state: TryStateSelect,
+	/// Automatically source the try-runtime binary without prompting for confirmation.
+	#[clap(short = 'y', long)]
+	skip_confirm: bool,
+}
+
+impl TryRuntimeArgs {
+	/// Sources the `try-runtime` binary and resolves the runtime to be tested, building it if
+	/// not specified.
+	async fn prepare(&self, cli: &mut impl Cli) -> Result<(PathBuf, PathBuf)> {
+		let project = self.path.clone().unwrap_or_else(|| PathBuf::from("./"));
+		let runtime = match &self.runtime {
+			Some(runtime) if !runtime.exists() => {
+				cli.outro_cancel(format!(
+					"🚫 The runtime could not be found at {}.",
+					runtime.display()
+				))?;
+				return Err(anyhow!("runtime not found: {}", runtime.display()));
+			},
+			Some(runtime) => runtime.clone(),
+			None => build(&project, cli)?,
+		};
+		let binary = check_try_runtime_and_prompt(cli, &crate::cache()?, self.skip_confirm).await?;
+		Ok((binary, runtime))
+	}
+}
+
+#[derive(Args)]
+pub(crate) struct TestExecuteBlockCommand {
+	#[command(flatten)]
+	args: TryRuntimeArgs,
+	/// The endpoint of the node from which the block (and any live state) is fetched.
+	#[arg(short, long, default_value = DEFAULT_URI)]
+	uri: String,
+	/// The hash of the block to be executed [default: the latest finalized block].
+	#[arg(long, conflicts_with = "snapshot")]
+	at: Option<String>,
+	/// Use the state within a local snapshot, rather than that of the live chain.
+	#[arg(short, long)]
+	snapshot: Option<PathBuf>,
+}
+
+impl TestExecuteBlockCommand {
+	/// Executes the command, replaying a block against the runtime to verify that the resulting
+	/// state root matches that of the block.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<&'static str> {
+		cli.intro("Executing block against the runtime")?;
+		let state = match self.snapshot {
+			Some(path) if !path.exists() => {
+				cli.outro_cancel(format!(
+					"🚫 The snapshot could not be found at {}.",
+					path.display()
+				))?;
+				return Err(anyhow!("snapshot not found: {}", path.display()));
+			},
+			Some(path) => State::Snapshot { path },
+			None => State::Live { at: self.at },
+		};
+		let (binary, runtime) = self.args.prepare(cli).await?;
+		let args = execute_block_args(&runtime, &self.uri, &state, &self.args.try_state);
+		if !run_try_runtime(&binary, &args)? {
+			cli.outro_cancel(
+				"🚫 The block could not be executed successfully against the runtime",
+			)?;
+			return Err(anyhow!("block execution failed"));
+		}
+		cli.outro("✅ Block executed successfully, with no divergence found")?;
+		Ok("execute-block")
+	}
+}
+
+#[derive(Args)]
+pub(crate) struct TestFollowChainCommand {
+	#[command(flatten)]
+	args: TryRuntimeArgs,
+	/// The endpoint of the node to be followed.
+	#[arg(short, long, default_value = DEFAULT_URI)]
+	uri: String,
+}
+
+impl TestFollowChainCommand {
+	/// Executes the command, replaying each newly finalized block of a chain against the runtime
+	/// until a divergence is found or the command is interrupted.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<&'static str> {
+		cli.intro("Following chain with the runtime")?;
+		let (binary, runtime) = self.args.prepare(cli).await?;
+		cli.info(format!("Executing each finalized block from {}...", self.uri))?;
+		let args = follow_chain_args(&runtime, &self.uri, &self.args.try_state);
+		if !run_try_runtime(&binary, &args)? {
+			cli.outro_cancel("🚫 A block could not be executed successfully against the runtime")?;
+			return Err(anyhow!("block execution failed"));
+		}
+		cli.outro("✅ Stopped following the chain")?;
+		Ok("follow-chain")
+	}
+}
+
+/// Builds the runtime of the project with the `try-runtime` feature enabled.
+fn build(project: &Path, cli: &mut impl Cli) -> Result<PathBuf> {
+	let spinner = cli.spinner();
+	spinner.start("Building the runtime with the `try-runtime` feature...");
+	match build_try_runtime(project, &project.join("runtime")) {
+		Ok(runtime) => {
+			spinner.stop(format!("Runtime built: {}", runtime.display()));
+			Ok(runtime)
+		},
+		Err(e) => {
+			spinner.error(format!("🚫 Could not build the runtime: {e}"));
+			Err(e.into())
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+
+	#[test]
+	fn test_execute_block_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"test",
+			"execute-block",
+			"--runtime",
+			"runtime.wasm",
+			"--try-state",
+			"System,Balances",
+			"--at",
+			"0x1234",
+		])?;
+		let crate::commands::Command::Test(args) = pop.command else {
+			panic!("expected the test command");
+		};
+		let Some(super::super::Command::ExecuteBlock(command)) = args.command else {
+			panic!("expected the execute-block test command");
+		};
+		assert_eq!(command.uri, DEFAULT_URI);
+		assert_eq!(command.at.as_deref(), Some("0x1234"));
+		assert_eq!(command.args.runtime, Some(PathBuf::from("runtime.wasm")));
+		assert_eq!(
+			command.args.try_state,
+			TryStateSelect::Only(vec!["System".into(), "Balances".into()])
+		);
+		assert!(crate::Cli::try_parse_from([
+			"pop",
+			"test",
+			"execute-block",
+			"--at",
+			"0x1234",
+			"--snapshot",
+			"chain.snap"
+		])
+		.is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_follow_chain_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"test",
+			"follow-chain",
+			"-u",
+			"wss://rpc.example.com",
+			"-y",
+		])?;
+		let crate::commands::Command::Test(args) = pop.command else {
+			panic!("expected the test command");
+		};
+		let Some(super::super::Command::FollowChain(command)) = args.command else {
+			panic!("expected the follow-chain test command");
+		};
+		assert_eq!(command.uri, "wss://rpc.example.com");
+		assert_eq!(command.args.try_state, TryStateSelect::All);
+		assert!(command.args.skip_confirm);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn execute_block_fails_without_snapshot() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let snapshot = temp_dir.path().join("chain.snap");
+		let mut cli = MockCli::new()
+			.expect_intro("Executing block against the runtime")
+			.expect_outro_cancel(format!(
+				"🚫 The snapshot could not be found at {}.",
+				snapshot.display()
+			));
+		let command = TestExecuteBlockCommand {
+			args: TryRuntimeArgs {
+				path: None,
+				runtime: None,
+				try_state: TryStateSelect::All,
+				skip_confirm: false,
+			},
+			uri: DEFAULT_URI.into(),
+			at: None,
+			snapshot: Some(snapshot),
+		};
+		assert!(command.execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn follow_chain_fails_without_runtime() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let runtime = temp_dir.path().join("runtime.wasm");
+		let mut cli = MockCli::new()
+			.expect_intro("Following chain with the runtime")
+			.expect_outro_cancel(format!(
+				"🚫 The runtime could not be found at {}.",
+				runtime.display()
+			));
+		let command = TestFollowChainCommand {
+			args: TryRuntimeArgs {
+				path: None,
+				runtime: Some(runtime),
+				try_state: TryStateSelect::All,
+				skip_confirm: false,
+			},
+			uri: DEFAULT_URI.into(),
+		};
+		assert!(command.execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+}
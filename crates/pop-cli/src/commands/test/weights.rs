@@ -0,0 +1,281 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::write_report;
+use crate::{
+	cli::traits::*,
+	common::binary::{check_prefetched_binary_and_prompt, Prefetch},
+};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_common::{
+	test_report::{TestCase, TestOutcome},
+	TestReport,
+};
+use pop_parachains::{
+	assert_weight, build_benchmark_runtime, omni_bencher_generator, ExecutionOptions, HeapPages,
+	WeightLimit, WeightLimits,
+};
+use std::path::{Path, PathBuf};
+
+/// The strategies with which wasmtime can instantiate the runtime.
+const WASM_INSTANTIATION_STRATEGIES: [&str; 4] =
+	["pooling-copy-on-write", "recreate-instance-copy-on-write", "pooling", "recreate-instance"];
+
+#[derive(Args)]
+pub(crate) struct TestWeightsCommand {
+	/// Path to the parachain project [default: current directory].
+	#[arg(short, long)]
+	path: Option<PathBuf>,
+	/// The file declaring the maximum weight of each extrinsic, relative to the project.
+	#[arg(short, long, default_value = "weights.toml")]
+	file: PathBuf,
+	/// The runtime to be benchmarked, built with the `runtime-benchmarks` feature [default:
+	/// built from the `runtime` of the project].
+	#[arg(long, value_name = "WASM")]
+	runtime: Option<PathBuf>,
+	/// The strategy with which wasmtime instantiates the runtime [default: that of
+	/// frame-omni-bencher].
+	#[arg(long, value_name = "STRATEGY", value_parser = WASM_INSTANTIATION_STRATEGIES)]
+	wasm_instantiation_strategy: Option<String>,
+	/// The number of heap pages with which the runtime is executed, or `auto` to probe increasing
+	/// heap sizes until each benchmark fits.
+	#[arg(
+		long,
+		value_name = "PAGES",
+		value_parser = |s: &str| s.parse::<HeapPages>().map_err(|_| "expected a number of pages or `auto`")
+	)]
+	heap_pages: Option<HeapPages>,
+	/// Automatically source the frame-omni-bencher binary without prompting for confirmation.
+	#[clap(short = 'y', long)]
+	skip_confirm: bool,
+	/// Write a JUnit XML report of the assertions to the specified file.
+	#[arg(long)]
+	report: Option<PathBuf>,
+}
+
+impl TestWeightsCommand {
+	/// Executes the command, running a quick benchmark of each declared extrinsic and asserting
+	/// that its measured weight stays within the declared maximum.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<&'static str> {
+		cli.intro("Asserting extrinsic weights")?;
+		let project = self.path.unwrap_or_else(|| PathBuf::from("./"));
+		let file = project.join(&self.file);
+		if !file.exists() {
+			cli.outro_cancel(format!(
+				"🚫 The weights configuration file could not be found at {}.",
+				file.display()
+			))?;
+			return Err(anyhow!("weights configuration file not found: {}", file.display()));
+		}
+		let limits = WeightLimits::load(&file)?;
+		// Source the benchmarking binary in the background while the runtime is built.
+		let prefetch = Prefetch::start(omni_bencher_generator(crate::cache()?));
+		let runtime = match self.runtime {
+			Some(runtime) if !runtime.exists() => {
+				cli.outro_cancel(format!(
+					"🚫 The runtime could not be found at {}.",
+					runtime.display()
+				))?;
+				return Err(anyhow!("runtime not found: {}", runtime.display()));
+			},
+			Some(runtime) => runtime,
+			None => build(&project, cli)?,
+		};
+		let binary = check_prefetched_binary_and_prompt(cli, prefetch, self.skip_confirm).await?;
+
+		let options = ExecutionOptions {
+			wasm_instantiation_strategy: self.wasm_instantiation_strategy,
+			heap_pages: self.heap_pages,
+		};
+		let mut report = TestReport { cases: vec![], success: true };
+		for limit in &limits.extrinsics {
+			let case = benchmark(&binary, &runtime, limit, &options, cli)?;
+			report.success &= case.outcome == TestOutcome::Passed;
+			report.cases.push(case);
+		}
+		write_report(&report, self.report.as_deref(), "weights", cli)?;
+		let failures = report.failures();
+		if failures > 0 {
+			cli.outro_cancel(format!(
+				"🚫 {failures} of {} extrinsics exceeded their declared weight",
+				report.cases.len()
+			))?;
+			return Err(anyhow!("weight assertions failed"));
+		}
+		cli.outro(format!(
+			"✅ All {} extrinsics are within their declared weight",
+			report.cases.len()
+		))?;
+		Ok("weights")
+	}
+}
+
+/// Benchmarks an extrinsic, reporting whether it is within its declared weight.
+fn benchmark(
+	binary: &Path,
+	runtime: &Path,
+	limit: &WeightLimit,
+	options: &ExecutionOptions,
+	cli: &mut impl Cli,
+) -> Result<TestCase> {
+	let name = format!("{}::{}", limit.pallet, limit.extrinsic);
+	let spinner = cli.spinner();
+	spinner.start(format!("Benchmarking {name}..."));
+	let result = assert_weight(binary, runtime, limit, options);
+	spinner.clear();
+	if let Some(pages) = result.as_ref().ok().and_then(|a| a.heap_pages) {
+		cli.info(format!("{name}: fits within {pages} heap pages"))?;
+	}
+	let outcome = match result {
+		Ok(assertion) if assertion.passed => {
+			cli.success(&assertion)?;
+			TestOutcome::Passed
+		},
+		Ok(assertion) => {
+			cli.error(&assertion)?;
+			TestOutcome::Failed(Some(format!("exceeded the declared weight: {assertion}")))
+		},
+		Err(e) => {
+			cli.error(format!("{name}: could not be benchmarked: {e}"))?;
+			TestOutcome::Failed(Some(e.to_string()))
+		},
+	};
+	Ok(TestCase { name, outcome })
+}
+
+/// Builds the runtime of the project with the `runtime-benchmarks` feature enabled.
+fn build(project: &Path, cli: &mut impl Cli) -> Result<PathBuf> {
+	let spinner = cli.spinner();
+	spinner.start("Building the runtime with the `runtime-benchmarks` feature...");
+	match build_benchmark_runtime(project, &project.join("runtime")) {
+		Ok(runtime) => {
+			spinner.stop(format!("Runtime built: {}", runtime.display()));
+			Ok(runtime)
+		},
+		Err(e) => {
+			spinner.error(format!("🚫 Could not build the runtime: {e}"));
+			Err(e.into())
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use std::fs;
+
+	#[test]
+	fn test_weights_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"test",
+			"weights",
+			"--runtime",
+			"runtime.wasm",
+			"--report",
+			"weights.xml",
+			"--wasm-instantiation-strategy",
+			"recreate-instance",
+			"--heap-pages",
+			"auto",
+		])?;
+		let crate::commands::Command::Test(args) = pop.command else {
+			panic!("expected the test command");
+		};
+		let Some(super::super::Command::Weights(command)) = args.command else {
+			panic!("expected the weights test command");
+		};
+		assert_eq!(command.path, None);
+		assert_eq!(command.file, PathBuf::from("weights.toml"));
+		assert_eq!(command.runtime, Some(PathBuf::from("runtime.wasm")));
+		assert_eq!(command.report, Some(PathBuf::from("weights.xml")));
+		assert_eq!(command.wasm_instantiation_strategy, Some("recreate-instance".into()));
+		assert_eq!(command.heap_pages, Some(HeapPages::Auto));
+		assert!(
+			crate::Cli::try_parse_from(["pop", "test", "weights", "--heap-pages", "many"]).is_err()
+		);
+		assert!(crate::Cli::try_parse_from([
+			"pop",
+			"test",
+			"weights",
+			"--wasm-instantiation-strategy",
+			"legacy"
+		])
+		.is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn execute_fails_without_config() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let mut cli = MockCli::new()
+			.expect_intro("Asserting extrinsic weights")
+			.expect_outro_cancel(format!(
+				"🚫 The weights configuration file could not be found at {}.",
+				temp_dir.path().join("weights.toml").display()
+			));
+		let command = TestWeightsCommand {
+			path: Some(temp_dir.path().to_path_buf()),
+			file: PathBuf::from("weights.toml"),
+			runtime: None,
+			wasm_instantiation_strategy: None,
+			heap_pages: None,
+			skip_confirm: true,
+			report: None,
+		};
+		assert!(command.execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn execute_fails_without_runtime() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		fs::write(
+			temp_dir.path().join("weights.toml"),
+			"[[extrinsic]]\npallet = \"pallet_balances\"\nextrinsic = \"transfer_allow_death\"\n",
+		)?;
+		let runtime = temp_dir.path().join("runtime.wasm");
+		let mut cli =
+			MockCli::new().expect_intro("Asserting extrinsic weights").expect_outro_cancel(
+				format!("🚫 The runtime could not be found at {}.", runtime.display()),
+			);
+		let command = TestWeightsCommand {
+			path: Some(temp_dir.path().to_path_buf()),
+			file: PathBuf::from("weights.toml"),
+			runtime: Some(runtime),
+			wasm_instantiation_strategy: None,
+			heap_pages: None,
+			skip_confirm: true,
+			report: None,
+		};
+		assert!(command.execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+
+	#[test]
+	fn benchmark_reports_failures() -> Result<()> {
+		let limit = WeightLimit {
+			pallet: "pallet_balances".into(),
+			extrinsic: "transfer_allow_death".into(),
+			..Default::default()
+		};
+		let mut cli = MockCli::new().expect_error(
+			"pallet_balances::transfer_allow_death: could not be benchmarked: IO error: No such file or directory (os error 2)",
+		);
+		let case = benchmark(
+			Path::new("./missing"),
+			Path::new("runtime.wasm"),
+			&limit,
+			&ExecutionOptions::default(),
+			&mut cli,
+		)?;
+		assert_eq!(case.name, "pallet_balances::transfer_allow_death");
+		assert!(matches!(case.outcome, TestOutcome::Failed(Some(_))));
+		cli.verify()
+	}
+}
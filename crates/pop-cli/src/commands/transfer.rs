@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	cli::traits::*,
+	commands::assets::{prompt_for_account, prompt_for_amount},
+	common::{balances::warn_of_balance_issues, network, signer::SignerArgs},
+};
+use anyhow::Result;
+use clap::Args;
+use pop_common::{balances::Balances, create_signer, units::format_units};
+use pop_parachains::{call_fee, transfer_call, Connection, Payload};
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+
+/// Command to transfer an amount of the native token of a chain to an account.
+#[derive(Args)]
+pub struct TransferCommand {
+	/// The account receiving the amount [default: prompted].
+	pub(crate) to: Option<String>,
+	/// The amount to be transferred, in whole tokens, e.g. `1.5` [default: prompted].
+	pub(crate) amount: Option<String>,
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	/// Allow the transfer to reap the account of the signer, should its balance fall below the
+	/// existential deposit.
+	#[arg(long)]
+	pub(crate) allow_death: bool,
+	#[command(flatten)]
+	pub(crate) signer: SignerArgs,
+}
+
+impl TransferCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Transfer tokens")?;
+		let connection = network::retry("connecting to the chain", || {
+			Connection::connect(self.url.as_str(), None)
+		})
+		.await?;
+		let client = connection.client();
+		let token = connection.token_metadata().await?;
+		let decimals = token.token_decimals as u8;
+		let to =
+			prompt_for_account(self.to.as_deref(), "Enter the account receiving the tokens:", cli)?;
+		let amount = prompt_for_amount(
+			self.amount.as_deref(),
+			decimals,
+			"Enter the amount to transfer:",
+			cli,
+		)?;
+		let xt = transfer_call(&to, amount, !self.allow_death);
+
+		// Show the fee, and warn should the transfer fail or reap an account.
+		let sender = create_signer(&self.signer.suri(cli)?)?.public_key().to_account_id();
+		let call_data = xt.encode_call_data(&client.metadata())?;
+		let fees = match call_fee(&client, &call_data).await {
+			Ok(fees) => {
+				cli.info(format!(
+					"Estimated fee: {} {}",
+					format_units(fees.total(), decimals),
+					token.symbol
+				))?;
+				fees.total()
+			},
+			Err(e) => {
+				cli.warning(format!("⚠️ The fee could not be estimated: {e}"))?;
+				0
+			},
+		};
+		if let Ok(balances) = Balances::fetch(&client, &sender, Some(&to)).await {
+			warn_of_balance_issues(&balances, amount, fees, &token, cli)?;
+		}
+
+		let action =
+			format!("transfer {} {} to {to}", format_units(amount, decimals), token.symbol);
+		if self.signer.submit(&client, &self.url, xt, &action, cli).await?.is_none() {
+			return Ok(());
+		}
+		cli.outro(format!(
+			"Transferred {} {} to {to}.",
+			format_units(amount, decimals),
+			token.symbol
+		))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[test]
+	fn transfer_args_works() -> Result<()> {
+		let parse = |args: &[&str]| -> Result<TransferCommand> {
+			let pop = crate::Cli::try_parse_from(["pop", "transfer"].iter().chain(args))?;
+			let crate::commands::Command::Transfer(command) = pop.command else {
+				panic!("expected the transfer command");
+			};
+			Ok(command)
+		};
+		let command = parse(&[
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+			"1.5",
+			"--url",
+			"wss://rpc.example.com",
+			"--suri",
+			"//Bob",
+			"--allow-death",
+			"-y",
+		])?;
+		assert_eq!(command.to.as_deref(), Some("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"));
+		assert_eq!(command.amount.as_deref(), Some("1.5"));
+		assert_eq!(command.url, Url::parse("wss://rpc.example.com")?);
+		assert_eq!(command.signer.suri.as_deref(), Some("//Bob"));
+		assert!(command.allow_death && command.signer.skip_confirm);
+		// The recipient and amount are prompted for when not specified.
+		let command = parse(&[])?;
+		assert_eq!((command.to, command.amount), (None, None));
+		assert_eq!(command.url, Url::parse(DEFAULT_URL)?);
+		assert!(!command.allow_death);
+		Ok(())
+	}
+}
@@ -1,26 +1,36 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use crate::{
-	cli::{traits::Cli as _, Cli},
+	cli::traits::{Cli, Confirm, Spinner},
 	common::{
+		balances::warn_of_balance_issues,
+		binary::Prefetch,
 		contracts::{check_contracts_node_and_prompt, has_contract_been_built, terminate_node},
+		network,
 		wallet::request_signature,
 	},
 	style::style,
 };
 use clap::Args;
-use cliclack::{confirm, log, log::error, spinner, ProgressBar};
 use console::{Emoji, Style};
+use pop_common::{
+	balances::Balances,
+	call::TokenMetadata,
+	config::{config_file_path, Config, Verifier},
+	rollback::Rollback,
+	DefaultConfig,
+};
 use pop_contracts::{
-	build_smart_contract, dry_run_gas_estimate_instantiate, dry_run_upload,
-	get_code_hash_from_event, get_contract_code, get_instantiate_payload, get_upload_payload,
-	instantiate_contract_signed, instantiate_smart_contract, is_chain_alive, parse_hex_bytes,
-	run_contracts_node, set_up_deployment, set_up_upload, upload_contract_signed,
-	upload_smart_contract, UpOpts, Verbosity,
+	build_smart_contract, contracts_node_generator, dry_run_gas_estimate_instantiate,
+	dry_run_upload, get_code_hash_from_event, get_contract_bundle, get_contract_code,
+	get_instantiate_payload, get_upload_payload, instantiate_contract_signed,
+	instantiate_smart_contract, is_chain_alive, parse_hex_bytes, run_contracts_node,
+	set_up_deployment, set_up_upload, upload_contract_signed, upload_smart_contract,
+	upload_verification_package, UpOpts, Verbosity,
 };
 use sp_core::Bytes;
 use sp_weights::Weight;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tempfile::NamedTempFile;
 use url::Url;
 
@@ -87,6 +97,15 @@ pub struct UpContractCommand {
 	/// confirmation.
 	#[clap(short = 'y', long)]
 	pub(crate) skip_confirm: bool,
+	/// Upload the verification package of the contract to a contract verification service once
+	/// deployed, so that the contract is shown as verified in explorers. The service and its API
+	/// key are configured within the `[verifier]` section of the user configuration file.
+	#[clap(long)]
+	pub(crate) verify: bool,
+	/// The base URL of the API of the contract verification service, overriding any configured
+	/// service.
+	#[clap(long, requires = "verify")]
+	pub(crate) verifier: Option<Url>,
 	// Deprecation flag, used to specify whether the deprecation warning is shown.
 	#[clap(skip)]
 	pub(crate) valid: bool,
@@ -94,22 +113,35 @@ pub struct UpContractCommand {
 
 impl UpContractCommand {
 	/// Executes the command.
-	pub(crate) async fn execute(mut self) -> anyhow::Result<()> {
-		Cli.intro("Deploy a smart contract")?;
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(mut self, cli: &mut impl Cli) -> anyhow::Result<()> {
+		cli.intro("Deploy a smart contract")?;
 		// Show warning if specified as deprecated.
 		if !self.valid {
-			Cli.warning("DEPRECATION: Please use `pop up` (or simply `pop u`) in future...")?;
+			cli.warning("DEPRECATION: Please use `pop up` (or simply `pop u`) in future...")?;
 		}
 		// Check if build exists in the specified "Contract build directory"
-		if !has_contract_been_built(self.path.as_deref()) {
+		let built = has_contract_been_built(self.path.as_deref());
+		// Source a local node in the background while the contract is built, should one be needed.
+		let mut prefetch = None;
+		if !built &&
+			self.url.as_str() == DEFAULT_URL &&
+			!is_chain_alive(self.url.clone()).await.unwrap_or_default()
+		{
+			prefetch =
+				contracts_node_generator(crate::cache()?, None).await.ok().map(Prefetch::start);
+		}
+		if !built {
 			// Build the contract in release mode
-			Cli.warning("NOTE: contract has not yet been built.")?;
-			let spinner = spinner();
+			cli.warning("NOTE: contract has not yet been built, or has changed since it was.")?;
+			let spinner = cli.spinner();
 			spinner.start("Building contract in RELEASE mode...");
 			let result = match build_smart_contract(self.path.as_deref(), true, Verbosity::Quiet) {
 				Ok(result) => result,
 				Err(e) => {
-					Cli.outro_cancel(format!("🚫 An error occurred building your contract: {e}\nUse `pop build` to retry with build output."))?;
+					cli.outro_cancel(format!("🚫 An error occurred building your contract: {e}\nUse `pop build` to retry with build output."))?;
 					return Ok(());
 				},
 			};
@@ -119,106 +151,115 @@ impl UpContractCommand {
 			));
 		}
 
-		// Check if specified chain is accessible
-		let process = if !is_chain_alive(self.url.clone()).await? {
-			if !self.skip_confirm {
-				let chain = if self.url.as_str() == DEFAULT_URL {
-					"No endpoint was specified.".into()
-				} else {
-					format!("The specified endpoint of {} is inaccessible.", self.url)
-				};
+		// Check if specified chain is accessible, with any local node launched terminated if
+		// cancelled.
+		let mut rollback = Vec::new();
+		let process =
+			if !network::once("connecting to the chain", is_chain_alive(self.url.clone())).await? {
+				if !self.skip_confirm {
+					let chain = if self.url.as_str() == DEFAULT_URL {
+						"No endpoint was specified.".into()
+					} else {
+						format!("The specified endpoint of {} is inaccessible.", self.url)
+					};
 
-				if !confirm(format!(
+					if !cli
+						.confirm(format!(
 					"{chain} Would you like to start a local node in the background for testing?",
 				))
-				.initial_value(true)
-				.interact()?
-				{
-					Cli.outro_cancel(
-						"🚫 You need to specify an accessible endpoint to deploy the contract.",
-					)?;
-					return Ok(());
+						.initial_value(true)
+						.interact()?
+					{
+						cli.outro_cancel(
+							"🚫 You need to specify an accessible endpoint to deploy the contract.",
+						)?;
+						return Ok(());
+					}
 				}
-			}
 
-			// Update url to that of the launched node
-			self.url = Url::parse(DEFAULT_URL).expect("default url is valid");
+				// Update url to that of the launched node
+				self.url = Url::parse(DEFAULT_URL).expect("default url is valid");
 
-			let log = NamedTempFile::new()?;
+				let log = NamedTempFile::new()?;
 
-			// uses the cache location
-			let binary_path = match check_contracts_node_and_prompt(
-				&mut Cli,
-				&crate::cache()?,
-				self.skip_confirm,
-			)
-			.await
-			{
-				Ok(binary_path) => binary_path,
-				Err(_) => {
-					Cli.outro_cancel(
-						"🚫 You need to specify an accessible endpoint to deploy the contract.",
-					)?;
-					return Ok(());
-				},
-			};
+				// uses the cache location
+				let binary_path = match check_contracts_node_and_prompt(
+					cli,
+					&crate::cache()?,
+					prefetch.take(),
+					self.skip_confirm,
+				)
+				.await
+				{
+					Ok(binary_path) => binary_path,
+					Err(_) => {
+						cli.outro_cancel(
+							"🚫 You need to specify an accessible endpoint to deploy the contract.",
+						)?;
+						return Ok(());
+					},
+				};
 
-			let spinner = spinner();
-			spinner.start("Starting local node...");
+				let spinner = cli.spinner();
+				spinner.start("Starting local node...");
 
-			let process =
-				run_contracts_node(binary_path, Some(log.as_file()), DEFAULT_PORT).await?;
-			let bar = Style::new().magenta().dim().apply_to(Emoji("│", "|"));
-			spinner.stop(format!(
-				"Local node started successfully:{}",
-				style(format!(
-					"
+				let process =
+					run_contracts_node(binary_path, Some(log.as_file()), DEFAULT_PORT).await?;
+				rollback.extend([Rollback::terminate(process.id()), Rollback::remove(log.path())]);
+				let bar = Style::new().magenta().dim().apply_to(Emoji("│", "|"));
+				spinner.stop(format!(
+					"Local node started successfully:{}",
+					style(format!(
+						"
 {bar}  {}
 {bar}  {}",
-					style(format!(
-						"portal: https://polkadot.js.org/apps/?rpc={}#/explorer",
-						self.url
+						style(format!(
+							"portal: https://polkadot.js.org/apps/?rpc={}#/explorer",
+							self.url
+						))
+						.dim(),
+						style(format!("logs: tail -f {}", log.path().display())).dim(),
 					))
-					.dim(),
-					style(format!("logs: tail -f {}", log.path().display())).dim(),
-				))
-				.dim()
-			));
-			Some((process, log))
-		} else {
-			None
-		};
+					.dim()
+				));
+				Some((process, log))
+			} else {
+				None
+			};
 
 		// Run steps for signing with wallet integration. Returns early.
 		if self.use_wallet {
 			let (call_data, hash) = match self.get_contract_data().await {
 				Ok(data) => data,
 				Err(e) => {
-					error(format!("An error occurred getting the call data: {e}"))?;
-					terminate_node(&mut Cli, process)?;
-					Cli.outro_cancel(FAILED)?;
+					cli.error(format!("An error occurred getting the call data: {e}"))?;
+					terminate_node(cli, process)?;
+					cli.outro_cancel(FAILED)?;
 					return Ok(());
 				},
 			};
 
-			let maybe_payload = request_signature(call_data, self.url.to_string()).await?;
+			let maybe_payload = request_signature(call_data, self.url.to_string(), cli).await?;
 			if let Some(payload) = maybe_payload {
-				log::success("Signed payload received.")?;
-				let spinner = spinner();
+				cli.success("Signed payload received.")?;
+				let spinner = cli.spinner();
 				spinner.start(
 					"Uploading the contract and waiting for finalization, please be patient...",
 				);
 
 				if self.upload_only {
-					let upload_result = match upload_contract_signed(self.url.as_str(), payload)
-						.await
+					let upload_result = match network::once(
+						"uploading the contract",
+						upload_contract_signed(self.url.as_str(), payload),
+					)
+					.await
 					{
 						Err(e) => {
 							spinner
 								.error(format!("An error occurred uploading your contract: {e}"));
-							terminate_node(&mut Cli, process)?;
-							Cli.outro_cancel(FAILED)?;
-							return Ok(());
+							terminate_node(cli, process)?;
+							cli.outro_cancel(FAILED)?;
+							return network::propagate_timeout(e);
 						},
 						Ok(result) => result,
 					};
@@ -226,6 +267,7 @@ impl UpContractCommand {
 					match get_code_hash_from_event(&upload_result, hash) {
 						Ok(r) => {
 							spinner.stop(format!("Contract uploaded: The code hash is {:?}", r));
+							self.verify_contract(&format!("{:?}", r), cli).await?;
 						},
 						Err(e) => {
 							spinner
@@ -233,71 +275,82 @@ impl UpContractCommand {
 						},
 					};
 				} else {
-					let contract_info =
-						match instantiate_contract_signed(self.url.as_str(), payload).await {
-							Err(e) => {
-								spinner.error(format!(
-									"An error occurred uploading your contract: {e}"
-								));
-								terminate_node(&mut Cli, process)?;
-								Cli.outro_cancel(FAILED)?;
-								return Ok(());
-							},
-							Ok(result) => result,
-						};
+					let contract_info = match network::once(
+						"instantiating the contract",
+						instantiate_contract_signed(self.url.as_str(), payload),
+					)
+					.await
+					{
+						Err(e) => {
+							spinner
+								.error(format!("An error occurred uploading your contract: {e}"));
+							terminate_node(cli, process)?;
+							cli.outro_cancel(FAILED)?;
+							return network::propagate_timeout(e);
+						},
+						Ok(result) => result,
+					};
 
 					let hash = contract_info.code_hash.map(|code_hash| format!("{:?}", code_hash));
 					display_contract_info(
 						&spinner,
 						contract_info.contract_address.to_string(),
-						hash,
+						hash.clone(),
+						&self.url,
 					);
+					if let Some(hash) = hash {
+						self.verify_contract(&hash, cli).await?;
+					}
 				};
 
 				if self.upload_only {
-					log::warning("NOTE: The contract has not been instantiated.")?;
+					cli.warning("NOTE: The contract has not been instantiated.")?;
 				}
 			} else {
-				Cli.outro_cancel("Signed payload doesn't exist.")?;
-				terminate_node(&mut Cli, process)?;
+				cli.outro_cancel("Signed payload doesn't exist.")?;
+				terminate_node(cli, process)?;
 				return Ok(());
 			}
 
-			terminate_node(&mut Cli, process)?;
-			Cli.outro(COMPLETE)?;
+			terminate_node(cli, process)?;
+			cli.outro(COMPLETE)?;
 			return Ok(());
 		}
 
 		// Check for upload only.
 		if self.upload_only {
-			let result = self.upload_contract().await;
-			terminate_node(&mut Cli, process)?;
+			let result = self.upload_contract(cli).await;
+			terminate_node(cli, process)?;
 			match result {
 				Ok(_) => {
-					Cli.outro(COMPLETE)?;
+					cli.outro(COMPLETE)?;
 				},
 				Err(_) => {
-					Cli.outro_cancel(FAILED)?;
+					cli.outro_cancel(FAILED)?;
 				},
 			}
 			return Ok(());
 		}
 
 		// Otherwise instantiate.
-		let instantiate_exec = match set_up_deployment(self.clone().into()).await {
+		let instantiate_exec = match network::retry("connecting to the chain", || {
+			set_up_deployment(self.clone().into())
+		})
+		.await
+		{
 			Ok(i) => i,
 			Err(e) => {
-				error(format!("An error occurred instantiating the contract: {e}"))?;
-				terminate_node(&mut Cli, process)?;
-				Cli.outro_cancel(FAILED)?;
-				return Ok(());
+				cli.error(format!("An error occurred instantiating the contract: {e}"))?;
+				terminate_node(cli, process)?;
+				cli.outro_cancel(FAILED)?;
+				return network::propagate_timeout(e);
 			},
 		};
 
 		let weight_limit = if self.gas_limit.is_some() && self.proof_size.is_some() {
 			Weight::from_parts(self.gas_limit.unwrap(), self.proof_size.unwrap())
 		} else {
-			let spinner = spinner();
+			let spinner = cli.spinner();
 			spinner.start("Doing a dry run to estimate the gas...");
 			match dry_run_gas_estimate_instantiate(&instantiate_exec).await {
 				Ok(w) => {
@@ -306,8 +359,8 @@ impl UpContractCommand {
 				},
 				Err(e) => {
 					spinner.error(format!("{e}"));
-					terminate_node(&mut Cli, process)?;
-					Cli.outro_cancel(FAILED)?;
+					terminate_node(cli, process)?;
+					cli.outro_cancel(FAILED)?;
 					return Ok(());
 				},
 			}
@@ -315,25 +368,46 @@ impl UpContractCommand {
 
 		// Finally upload and instantiate.
 		if !self.dry_run {
-			let spinner = spinner();
+			// Warn should transferring the value fail or reap the account of the signer.
+			let value = instantiate_exec.args().value();
+			if value > 0 {
+				let signer = instantiate_exec.opts().signer().public_key().to_account_id();
+				if let Ok(balances) =
+					Balances::fetch(instantiate_exec.client(), &signer, None).await
+				{
+					let token = TokenMetadata::query::<DefaultConfig>(&self.url).await?;
+					warn_of_balance_issues(&balances, value, 0, &token, cli)?;
+				}
+			}
+			let spinner = cli.spinner();
 			spinner.start("Uploading and instantiating the contract...");
-			let contract_info = instantiate_smart_contract(instantiate_exec, weight_limit).await?;
+			let contract_info = network::once(
+				"instantiating the contract",
+				instantiate_smart_contract(instantiate_exec, weight_limit),
+			)
+			.await?;
 			display_contract_info(
 				&spinner,
 				contract_info.address.to_string(),
-				contract_info.code_hash,
+				contract_info.code_hash.clone(),
+				&self.url,
 			);
+			if let Some(hash) = contract_info.code_hash {
+				self.verify_contract(&hash, cli).await?;
+			}
 
-			terminate_node(&mut Cli, process)?;
-			Cli.outro(COMPLETE)?;
+			terminate_node(cli, process)?;
+			cli.outro(COMPLETE)?;
 		}
 
 		Ok(())
 	}
 
 	/// Uploads the contract without instantiating it.
-	async fn upload_contract(self) -> anyhow::Result<()> {
-		let upload_exec = set_up_upload(self.clone().into()).await?;
+	async fn upload_contract(self, cli: &mut impl Cli) -> anyhow::Result<()> {
+		let upload_exec =
+			network::retry("connecting to the chain", || set_up_upload(self.clone().into()))
+				.await?;
 		if self.dry_run {
 			match dry_run_upload(&upload_exec).await {
 				Ok(upload_result) => {
@@ -343,29 +417,56 @@ impl UpContractCommand {
 						.iter()
 						.map(|s| style(format!("{} {s}", Emoji("●", ">"))).dim().to_string())
 						.collect();
-					Cli.success(format!("Dry run successful!\n{}", result.join("\n")))?;
+					cli.success(format!("Dry run successful!\n{}", result.join("\n")))?;
 				},
 				Err(_) => {
-					Cli.outro_cancel(FAILED)?;
+					cli.outro_cancel(FAILED)?;
 					return Ok(());
 				},
 			};
 		} else {
-			let spinner = spinner();
+			let spinner = cli.spinner();
 			spinner.start("Uploading your contract...");
-			let code_hash = match upload_smart_contract(&upload_exec).await {
-				Ok(r) => r,
-				Err(e) => {
-					spinner.error(format!("An error occurred uploading your contract: {e}"));
-					return Err(e.into());
-				},
-			};
+			let code_hash =
+				match network::once("uploading the contract", upload_smart_contract(&upload_exec))
+					.await
+				{
+					Ok(r) => r,
+					Err(e) => {
+						spinner.error(format!("An error occurred uploading your contract: {e}"));
+						return Err(e);
+					},
+				};
 			spinner.stop(format!("Contract uploaded: The code hash is {:?}", code_hash));
-			log::warning("NOTE: The contract has not been instantiated.")?;
+			self.verify_contract(&format!("{:?}", code_hash), cli).await?;
+			cli.warning("NOTE: The contract has not been instantiated.")?;
 		}
 		Ok(())
 	}
 
+	/// Uploads the verification package of the deployed contract, if requested, using the
+	/// verification service configured within the user configuration file unless otherwise
+	/// specified.
+	async fn verify_contract(&self, code_hash: &str, cli: &mut impl Cli) -> anyhow::Result<()> {
+		if !self.verify {
+			return Ok(());
+		}
+		// The configuration directory may not be determinable, in which case nothing is configured.
+		let config = match config_file_path() {
+			Ok(path) => Config::load(&path)?.verifier,
+			Err(_) => Verifier::default(),
+		};
+		verify_contract(
+			self.path.as_deref(),
+			&self.url,
+			code_hash,
+			self.verifier.as_ref(),
+			&config,
+			cli,
+		)
+		.await
+	}
+
 	// get the call data and contract code hash
 	async fn get_contract_data(&self) -> anyhow::Result<(Vec<u8>, [u8; 32])> {
 		let contract_code = get_contract_code(self.path.as_ref())?;
@@ -374,7 +475,10 @@ impl UpContractCommand {
 			let call_data = get_upload_payload(contract_code, self.url.as_str()).await?;
 			Ok((call_data, hash))
 		} else {
-			let instantiate_exec = set_up_deployment(self.clone().into()).await?;
+			let instantiate_exec = network::retry("connecting to the chain", || {
+				set_up_deployment(self.clone().into())
+			})
+			.await?;
 
 			let weight_limit = if self.gas_limit.is_some() && self.proof_size.is_some() {
 				Weight::from_parts(self.gas_limit.unwrap(), self.proof_size.unwrap())
@@ -404,9 +508,66 @@ impl From<UpContractCommand> for UpOpts {
 	}
 }
 
-fn display_contract_info(spinner: &ProgressBar, address: String, code_hash: Option<String>) {
+// Uploads the verification package of a deployed contract to a contract verification service.
+// As the contract has already been deployed, any failure is reported as a warning.
+async fn verify_contract(
+	path: Option<&Path>,
+	network: &Url,
+	code_hash: &str,
+	api: Option<&Url>,
+	config: &Verifier,
+	cli: &mut impl Cli,
+) -> anyhow::Result<()> {
+	let api = match api.cloned().or_else(|| config.url.as_ref().and_then(|u| u.parse().ok())) {
+		Some(api) => api,
+		None => {
+			cli.warning("NOTE: The contract was not verified as no verification service is configured. Use `--verifier` or specify the `url` of the `[verifier]` section of the pop configuration file.")?;
+			return Ok(());
+		},
+	};
+	let spinner = cli.spinner();
+	spinner.start("Uploading the verification package of the contract...");
+	let result = match get_contract_bundle(path) {
+		Ok(bundle) =>
+			upload_verification_package(
+				&api,
+				config.api_key.as_deref(),
+				network.as_str(),
+				code_hash,
+				&bundle,
+			)
+			.await,
+		Err(e) => Err(e),
+	};
+	match result {
+		Ok(verification) => {
+			let mut message = format!(
+				"Verification package uploaded: {}",
+				verification.status.as_deref().unwrap_or("submitted")
+			);
+			if let Some(url) = verification.url {
+				message
+					.push_str(&format!("\n{}", style(format!("{} {url}", Emoji("●", ">"))).dim()));
+			}
+			spinner.stop(message);
+		},
+		Err(e) => {
+			spinner.clear();
+			cli.warning(format!("⚠️ The contract could not be verified: {e}"))?;
+		},
+	}
+	Ok(())
+}
+
+fn display_contract_info(
+	spinner: &impl Spinner,
+	address: String,
+	code_hash: Option<String>,
+	url: &Url,
+) {
+	let links = contract_links(url, &address);
 	spinner.stop(format!(
-		"Contract deployed and instantiated:\n{}",
+		"Contract deployed and instantiated:\n{}{links}",
 		style(format!(
 			"{}\n{}",
 			style(format!("{} The contract address is {:?}", console::Emoji("●", ">"), address))
@@ -425,9 +586,23 @@ fn display_contract_info(spinner: &ProgressBar, address: String, code_hash: Opti
 	));
 }
 
+// Links to a deployed contract within the explorers of the chain, should it be known.
+#[cfg(feature = "parachain")]
+fn contract_links(url: &Url, address: &str) -> String {
+	use crate::common::explorer::explorer_links;
+	explorer_links(url.as_str(), &[pop_parachains::ExplorerItem::Account(address)])
+}
+
+#[cfg(not(feature = "parachain"))]
+fn contract_links(_url: &Url, _address: &str) -> String {
+	String::new()
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::cli::MockCli;
+	use cliclack::log::error;
 	use pop_common::{find_free_port, set_executable_permission};
 	use pop_contracts::{contracts_node_generator, mock_build_process, new_environment};
 	use std::{
@@ -455,6 +630,8 @@ mod tests {
 			upload_only: false,
 			skip_confirm: false,
 			use_wallet: false,
+			verify: false,
+			verifier: None,
 			valid: true,
 		}
 	}
@@ -502,6 +679,35 @@ mod tests {
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn verify_contract_warns_without_verifier() -> anyhow::Result<()> {
+		let mut cli = MockCli::new().expect_warning("NOTE: The contract was not verified as no verification service is configured. Use `--verifier` or specify the `url` of the `[verifier]` section of the pop configuration file.");
+		let network = Url::parse(DEFAULT_URL)?;
+		verify_contract(None, &network, "0x1234", None, &Verifier::default(), &mut cli).await?;
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn verify_contract_warns_without_bundle() -> anyhow::Result<()> {
+		let temp_dir = new_environment("testing")?;
+		let mut cli = MockCli::new().expect_warning(format!(
+			"⚠️ The contract could not be verified: Failed to verify the contract: the contract bundle {} was not found",
+			temp_dir.path().join("testing/target/ink/testing.contract").display()
+		));
+		let config =
+			Verifier { url: Some("https://verify.example.com".into()), ..Default::default() };
+		verify_contract(
+			Some(&temp_dir.path().join("testing")),
+			&Url::parse(DEFAULT_URL)?,
+			"0x1234",
+			None,
+			&config,
+			&mut cli,
+		)
+		.await?;
+		cli.verify()
+	}
+
 	#[tokio::test]
 	async fn get_upload_and_instantiate_call_data_works() -> anyhow::Result<()> {
 		let (contracts_node_process, port, temp_dir) = start_test_environment().await?;
@@ -532,6 +738,8 @@ mod tests {
 			upload_only: true,
 			skip_confirm: true,
 			use_wallet: true,
+			verify: false,
+			verifier: None,
 			valid: true,
 		};
 
@@ -584,6 +792,8 @@ mod tests {
 			upload_only: false,
 			skip_confirm: true,
 			use_wallet: true,
+			verify: false,
+			verifier: None,
 			valid: true,
 		};
 
@@ -1,21 +1,20 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::{
-	cli::{self, Cli},
-	common::builds::get_project_path,
-};
+use crate::cli;
+#[cfg(feature = "contract")]
+use crate::common::builds::get_project_path;
 use clap::{Args, Subcommand};
 use std::path::PathBuf;
 
 #[cfg(feature = "contract")]
 mod contract;
 #[cfg(feature = "parachain")]
-mod network;
+pub(crate) mod network;
 
 /// Arguments for launching or deploying a project.
 #[derive(Args, Clone)]
 #[command(args_conflicts_with_subcommands = true)]
-pub(crate) struct UpArgs {
+pub struct UpArgs {
 	/// Path to the project directory.
 	// TODO: Introduce the short option in v0.8.0 once deprecated parachain command is removed.
 	#[arg(long, global = true)]
@@ -51,32 +50,34 @@ pub(crate) enum Command {
 }
 
 impl Command {
-	/// Executes the command.
-	pub(crate) async fn execute(args: UpArgs) -> anyhow::Result<&'static str> {
-		Self::execute_project_deployment(args, &mut Cli).await
-	}
-
 	/// Identifies the project type and executes the appropriate deployment process.
-	async fn execute_project_deployment(
+	///
+	/// # Arguments
+	/// * `args` - The arguments for launching or deploying a project.
+	/// * `cli` - The cli.
+	#[cfg_attr(not(feature = "contract"), allow(unused_variables))]
+	pub(crate) async fn execute(
 		args: UpArgs,
 		cli: &mut impl cli::traits::Cli,
 	) -> anyhow::Result<&'static str> {
-		let project_path = get_project_path(args.path.clone(), args.path_pos.clone());
 		// If only contract feature enabled, deploy a contract
 		#[cfg(feature = "contract")]
-		if pop_contracts::is_supported(project_path.as_deref())? {
-			let mut cmd = args.contract;
-			cmd.path = project_path;
-			cmd.valid = true; // To handle deprecated command, remove in v0.8.0.
-			cmd.execute().await?;
-			return Ok("contract");
+		{
+			let project_path = get_project_path(args.path.clone(), args.path_pos.clone());
+			if pop_contracts::is_supported(project_path.as_deref())? {
+				let mut cmd = args.contract;
+				cmd.path = project_path;
+				cmd.valid = true; // To handle deprecated command, remove in v0.8.0.
+				cmd.execute(cli).await?;
+				return Ok("contract");
+			}
 		}
 		cli.warning("No contract detected. Ensure you are in a valid project directory.")?;
 		Ok("")
 	}
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "contract"))]
 mod tests {
 	use super::{contract::UpContractCommand, *};
 
@@ -104,6 +105,8 @@ mod tests {
 				dry_run: true,
 				upload_only: true,
 				skip_confirm: false,
+				verify: false,
+				verifier: None,
 				valid: false,
 			},
 			command: None,
@@ -122,7 +125,7 @@ mod tests {
 		)?;
 		let args = create_up_args(temp_dir.path().join("testing"))?;
 		let mut cli = MockCli::new();
-		assert_eq!(Command::execute_project_deployment(args, &mut cli).await?, "contract");
+		assert_eq!(Command::execute(args, &mut cli).await?, "contract");
 		cli.verify()
 	}
 
@@ -137,7 +140,7 @@ mod tests {
 		cmd("cargo", ["new", name, "--bin"]).dir(&path).run()?;
 		let mut cli = MockCli::new()
 			.expect_warning("No contract detected. Ensure you are in a valid project directory.");
-		assert_eq!(Command::execute_project_deployment(args, &mut cli).await?, "");
+		assert_eq!(Command::execute(args, &mut cli).await?, "");
 		cli.verify()
 	}
 }
@@ -1,16 +1,31 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::style::{style, Theme};
-use clap::Args;
-use cliclack::{
-	clear_screen, confirm, intro, log, multi_progress, outro, outro_cancel, set_theme, spinner,
-	ProgressBar, Theme as _, ThemeState,
+use crate::{
+	cli::{
+		self,
+		traits::{Cli, Confirm, Spinner},
+	},
+	common::{
+		interrupt::{unless_interrupted, Handled},
+		network,
+	},
+	progress::{Event, Reporter},
+	style::style,
 };
-use console::{Emoji, Style, Term};
+use clap::Args;
+use console::{Emoji, Style};
 use duct::cmd;
-use pop_common::Status;
-use pop_parachains::{clear_dmpq, Error, IndexSet, NetworkNode, RelayChain, Zombienet};
-use std::{path::Path, time::Duration};
+use pop_common::{Profile, Status};
+use pop_parachains::{
+	build_omni_runtime, clear_dmpq, generate_omni_chain_spec, is_runtime_only, omni_node_generator,
+	Error, IndexSet, Monitoring, NetworkNode, RelayChain, RuntimeVersion, ScrapeTarget, Telemetry,
+	Zombienet,
+};
+use std::{
+	env::current_dir,
+	path::{Path, PathBuf},
+	time::Duration,
+};
 use tokio::time::sleep;
 
 #[derive(Args, Clone)]
@@ -48,6 +63,24 @@ pub(crate) struct ZombienetCommand {
 	/// Automatically source all needed binaries required without prompting for confirmation.
 	#[clap(short = 'y', long)]
 	skip_confirm: bool,
+	/// Monitor the nodes of the network via Prometheus and Grafana, run via Docker, with a
+	/// dashboard provisioned for Substrate nodes.
+	#[arg(long)]
+	with_monitoring: bool,
+	/// The telemetry endpoint to which the nodes of the network submit telemetry, optionally
+	/// followed by the verbosity (e.g. "wss://telemetry.polkadot.io/submit/ 0"). Overrides any
+	/// `telemetry_url` specified within the `settings` of the network configuration file.
+	#[arg(long, conflicts_with = "with_telemetry")]
+	telemetry_url: Option<String>,
+	/// Run a local telemetry server and UI via Docker, to which the nodes of the network submit
+	/// telemetry.
+	#[arg(long)]
+	with_telemetry: bool,
+	/// A locally built runtime to replace that of the relay chain (`relay`) or of a parachain
+	/// (its identifier) within the generated chain specification, e.g. "relay=./runtime.wasm" or
+	/// "2000=./runtime.compact.compressed.wasm".
+	#[arg(long, value_name = "CHAIN=PATH", value_parser = parse_runtime_wasm)]
+	runtime_wasm: Vec<(Option<u32>, PathBuf)>,
 	// Deprecation flag, used to specify whether the deprecation warning is shown.
 	#[clap(skip)]
 	pub(crate) valid: bool,
@@ -55,14 +88,15 @@ pub(crate) struct ZombienetCommand {
 
 impl ZombienetCommand {
 	/// Executes the command.
-	pub(crate) async fn execute(self) -> anyhow::Result<()> {
-		clear_screen()?;
-		intro(format!("{}: Launch a local network", style(" Pop CLI ").black().on_magenta()))?;
-		set_theme(Theme);
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> anyhow::Result<()> {
+		cli.intro("Launch a local network")?;
 
 		// Show warning if specified as deprecated.
 		if !self.valid {
-			log::warning(
+			cli.warning(
 				"DEPRECATION: Please use `pop up network` (or simply `pop up n`) in future...",
 			)?;
 		}
@@ -84,11 +118,13 @@ impl ZombienetCommand {
 			Err(e) =>
 				return match e {
 					Error::Config(message) => {
-						outro_cancel(format!("🚫 A configuration error occurred: `{message}`"))?;
+						cli.outro_cancel(format!(
+							"🚫 A configuration error occurred: `{message}`"
+						))?;
 						Ok(())
 					},
 					Error::MissingBinary(name) => {
-						outro_cancel(format!("🚫 The `{name}` binary is specified in the network configuration file, but cannot be resolved to a source. Are you missing a `--parachain` argument?"))?;
+						cli.outro_cancel(format!("🚫 The `{name}` binary is specified in the network configuration file, but cannot be resolved to a source. Are you missing a `--parachain` argument?"))?;
 						Ok(())
 					},
 					_ => Err(e.into()),
@@ -96,14 +132,56 @@ impl ZombienetCommand {
 		};
 
 		// Source any missing/stale binaries
-		if Self::source_binaries(&mut zombienet, &cache, self.verbose, self.skip_confirm).await? {
+		if Self::source_binaries(&mut zombienet, &cache, self.verbose, self.skip_confirm, cli)
+			.await?
+		{
+			return Ok(());
+		}
+
+		// Generate chain specs for any parachains launched via the omni node
+		if Self::generate_chain_specs(&mut zombienet, &current_dir()?, &cache, cli)? {
+			return Ok(());
+		}
+
+		// Replace any runtimes with those specified, once validated
+		if Self::set_runtimes(&mut zombienet, &self.runtime_wasm, cli)? {
 			return Ok(());
 		}
 
-		// Finally spawn network and wait for signal to terminate
-		let progress = spinner();
+		// Point the nodes at a telemetry endpoint, starting a local telemetry server if requested.
+		if let Some(url) = &self.telemetry_url {
+			zombienet.set_telemetry_url(url);
+		}
+		let mut telemetry = None;
+		if self.with_telemetry {
+			let spinner = cli.spinner();
+			spinner.start("Starting the telemetry server...");
+			let started =
+				Telemetry::create(&cache.join("telemetry")).and_then(|t| t.start().map(|_| t));
+			spinner.clear();
+			match started {
+				Ok(t) => {
+					zombienet.set_telemetry_url(&t.submit_url());
+					telemetry = Some(t);
+				},
+				Err(e) =>
+					cli.warning(format!("⚠️ The telemetry server could not be started: {e}"))?,
+			}
+		}
+
+		// Finally spawn network and wait for signal to terminate, handling interrupts from here on
+		// so that any launched nodes are terminated.
+		let _interrupts = Handled::new();
+		let progress = cli.spinner();
 		progress.start("🚀 Launching local network...");
-		match zombienet.spawn().await {
+		let Some(spawned) = unless_interrupted(zombienet.spawn()).await else {
+			progress.error("🚫 Launch of the network was cancelled.");
+			if let Some(telemetry) = telemetry {
+				telemetry.stop()?;
+			}
+			return Ok(());
+		};
+		match spawned {
 			Ok(network) => {
 				let mut result =
 					"🚀 Network launched successfully - ctrl-c to terminate".to_string();
@@ -152,21 +230,70 @@ impl ZombienetCommand {
 					}
 				}
 
+				// Add telemetry info
+				if let Some(telemetry) = &telemetry {
+					result.push_str(&format!("\n{bar}  📡 telemetry: {}", telemetry.ui_url()));
+				} else if let Some(url) = zombienet.telemetry_url() {
+					result.push_str(&format!("\n{bar}  📡 telemetry: {url}"));
+				}
+
+				// Scrape the metrics of all nodes into the monitoring stack, if requested.
+				let (mut monitoring, mut monitoring_error) = (None, None);
+				if self.with_monitoring {
+					progress.set_message("Starting the monitoring stack...");
+					let mut targets = Vec::new();
+					for node in network.relaychain().nodes() {
+						targets.extend(ScrapeTarget::from_args(
+							node.name(),
+							network.relaychain().chain(),
+							&node.args(),
+						));
+					}
+					for parachain in network.parachains() {
+						let chain = parachain
+							.chain_id()
+							.map_or(parachain.para_id().to_string(), |c| c.to_string());
+						for node in parachain.collators() {
+							targets.extend(ScrapeTarget::from_args(
+								node.name(),
+								&chain,
+								&node.args(),
+							));
+						}
+					}
+					match Monitoring::create(&Path::new(base_dir).join("monitoring"), &targets)
+						.and_then(|m| m.start().map(|_| m))
+					{
+						Ok(m) => {
+							result.push_str(&format!(
+								"\n{bar}  📊 monitoring:\n{bar}         dashboard: {}\n{bar}         prometheus: {}",
+								m.dashboard_url(),
+								m.prometheus_url()
+							));
+							monitoring = Some(m);
+						},
+						Err(e) => monitoring_error = Some(e),
+					}
+				}
+
 				if let Some(command) = &self.command {
 					run_custom_command(&progress, command).await?;
 				}
 
 				progress.stop(result);
+				if let Some(e) = monitoring_error {
+					cli.warning(format!("⚠️ The monitoring stack could not be started: {e}"))?;
+				}
 
 				// Check for any specified channels
 				if zombienet.hrmp_channels() {
 					let relay_chain = zombienet.relay_chain();
 					match RelayChain::from(relay_chain) {
 						None => {
-							log::error(format!("🚫 Using `{relay_chain}` with HRMP channels is currently unsupported. Please use `paseo-local` or `westend-local`."))?;
+							cli.error(format!("🚫 Using `{relay_chain}` with HRMP channels is currently unsupported. Please use `paseo-local` or `westend-local`."))?;
 						},
 						Some(_) => {
-							let progress = spinner();
+							let progress = cli.spinner();
 							progress.start("Connecting to relay chain to prepare channels...");
 							// Allow relay node time to start
 							sleep(Duration::from_secs(10)).await;
@@ -187,21 +314,33 @@ impl ZombienetCommand {
 				}
 
 				tokio::signal::ctrl_c().await?;
-				outro("Done")?;
+				if let Some(monitoring) = monitoring {
+					monitoring.stop()?;
+				}
+				if let Some(telemetry) = telemetry {
+					telemetry.stop()?;
+				}
+				cli.outro("Done")?;
 			},
 			Err(e) => {
-				outro_cancel(format!("🚫 Could not launch local network: {e}"))?;
+				if let Some(telemetry) = telemetry {
+					telemetry.stop()?;
+				}
+				cli.outro_cancel(format!("🚫 Could not launch local network: {e}"))?;
 			},
 		}
 
 		Ok(())
 	}
 
-	async fn source_binaries(
+	/// Sources any missing or stale binaries, returning whether the launch of the network has been
+	/// cancelled.
+	pub(crate) async fn source_binaries(
 		zombienet: &mut Zombienet,
 		cache: &Path,
 		verbose: bool,
 		skip_confirm: bool,
+		cli: &mut impl Cli,
 	) -> anyhow::Result<bool> {
 		// Check for any missing or stale binaries
 		let binaries: Vec<_> = zombienet.binaries().filter(|b| !b.exists() || b.stale()).collect();
@@ -221,7 +360,7 @@ impl ZombienetCommand {
 			))
 			.dim()
 			.to_string();
-			log::warning(format!("⚠️ The following binaries required to launch the network cannot be found locally:\n   {list}"))?;
+			cli.warning(format!("⚠️ The following binaries required to launch the network cannot be found locally:\n   {list}"))?;
 
 			// Prompt for automatic sourcing of binaries
 			let list = style(format!(
@@ -241,12 +380,12 @@ impl ZombienetCommand {
 			.dim()
 			.to_string();
 			if !skip_confirm &&
-				!confirm(format!(
+				!cli.confirm(format!(
 				"📦 Would you like to source them automatically now? It may take some time...\n   {list}"))
-				.initial_value(true)
-				.interact()?
+					.initial_value(true)
+					.interact()?
 			{
-				outro_cancel(
+				cli.outro_cancel(
 					"🚫 Cannot launch the specified network until all required binaries are available.",
 				)?;
 				return Ok(true);
@@ -276,11 +415,11 @@ impl ZombienetCommand {
 			))
 			.dim()
 			.to_string();
-			log::warning(format!(
+			cli.warning(format!(
 				"ℹ️ The following binaries have newer versions available:\n   {list}"
 			))?;
 			if !skip_confirm {
-				latest = confirm(
+				latest = cli.confirm(
 					"📦 Would you like to source them automatically now? It may take some time..."
 						.to_string(),
 				)
@@ -307,7 +446,7 @@ impl ZombienetCommand {
 		}
 
 		if binaries.iter().any(|b| !b.local()) {
-			log::info(format!(
+			cli.info(format!(
 				"ℹ️ Binaries will be cached at {}",
 				&cache.to_str().expect("expected local cache is invalid")
 			))?;
@@ -315,57 +454,171 @@ impl ZombienetCommand {
 
 		// Source binaries
 		let release = true;
-		match verbose {
-			true => {
-				let reporter = VerboseReporter;
-				for binary in binaries {
-					log::info(format!("📦 Sourcing {}...", binary.name()))?;
-					Term::stderr().clear_last_lines(1)?;
-					if let Err(e) = binary.source(release, &reporter, verbose).await {
-						reporter.update(&format!("Sourcing failed: {e}"));
-						outro_cancel(
-							"🚫 Cannot launch the network until all required binaries are available.",
-						)?;
-						return Ok(true);
+		for binary in binaries {
+			let name = binary.name();
+			let result = match verbose || cli::is_quiet() {
+				// Only report progress as events, with any build output streamed when verbose.
+				true => {
+					if verbose {
+						cli.info(format!("📦 Sourcing {name}..."))?;
 					}
-				}
-				reporter.update("");
-			},
-			false => {
-				let multi = multi_progress("📦 Sourcing binaries...".to_string());
-				let queue: Vec<_> = binaries
-					.into_iter()
-					.map(|binary| {
-						let progress = multi.add(cliclack::spinner());
-						progress.start(format!("{}: waiting...", binary.name()));
-						(binary, progress)
+					let reporter = Reporter::new(name);
+					network::retry(&format!("sourcing {name}"), || {
+						binary.source(release, &reporter, verbose)
+					})
+					.await
+				},
+				false => {
+					let reporter = ProgressReporter(format!("{name}: "), cli.spinner());
+					reporter.1.start(format!("📦 {name}: sourcing..."));
+					let result = network::retry(&format!("sourcing {name}"), || {
+						binary.source(release, &reporter, verbose)
 					})
-					.collect();
-				let mut error = false;
-				for (binary, progress) in queue {
-					let prefix = format!("{}: ", binary.name());
-					let progress_reporter = ProgressReporter(prefix, progress);
-					if let Err(e) = binary.source(release, &progress_reporter, verbose).await {
-						progress_reporter.1.error(format!("🚫 {}: {e}", binary.name()));
-						error = true;
+					.await;
+					match &result {
+						Ok(_) => reporter.1.stop(format!("✅  {name}")),
+						Err(e) => reporter.1.error(format!("🚫 {name}: {e}")),
 					}
-					progress_reporter.1.stop(format!("✅  {}", binary.name()));
-				}
-				multi.stop();
-				if error {
-					outro_cancel(
-						"🚫 Cannot launch the network until all required binaries are available.",
-					)?;
-					return Ok(true);
-				}
-			},
-		};
+					result
+				},
+			};
+			if let Err(e) = result {
+				cli.outro_cancel(format!(
+					"🚫 {name}: {e}\nCannot launch the network until all required binaries are available."
+				))?;
+				return network::propagate_timeout(e).map(|_| true);
+			}
+		}
+
+		Ok(false)
+	}
+
+	/// Generates the chain specifications of any parachains launched via `polkadot-omni-node`
+	/// from the runtime of the project, returning whether the launch of the network has been
+	/// cancelled.
+	///
+	/// # Arguments
+	/// * `zombienet` - The network to be launched.
+	/// * `project` - The path to the project.
+	/// * `cache` - The location used for caching binaries.
+	/// * `cli` - The cli.
+	fn generate_chain_specs(
+		zombienet: &mut Zombienet,
+		project: &Path,
+		cache: &Path,
+		cli: &mut impl Cli,
+	) -> anyhow::Result<bool> {
+		let parachains = zombienet.omni_node_parachains();
+		if parachains.is_empty() {
+			return Ok(false);
+		}
+		if !is_runtime_only(project).unwrap_or_default() {
+			cli.outro_cancel("🚫 A chain specification is required to launch `polkadot-omni-node`. Specify a `chain_spec_path` in the network configuration file, or launch the network from a project without a node so that one is generated from its runtime.")?;
+			return Ok(true);
+		}
+		let spinner = cli.spinner();
+		spinner.start("Building the runtime...");
+		let runtime = build_omni_runtime(project, &project.join("runtime"))?;
+		let binary = omni_node_generator(cache.to_path_buf()).path();
+		for id in parachains {
+			spinner.set_message(format!("Generating the chain specification of {id}..."));
+			let chain_spec = chain_spec_path(project, id);
+			generate_omni_chain_spec(
+				&binary,
+				&runtime,
+				&chain_spec,
+				id,
+				zombienet.relay_chain(),
+				"dev",
+			)?;
+			zombienet.set_chain_spec(id, &chain_spec)?;
+		}
+		spinner.stop("Chain specifications generated from the runtime.");
+		Ok(false)
+	}
 
+	/// Replaces the runtimes of chains within the network with locally built runtimes, once
+	/// validated as being compatible, returning whether the launch of the network has been
+	/// cancelled.
+	///
+	/// # Arguments
+	/// * `zombienet` - The network to be launched.
+	/// * `runtimes` - The runtimes, keyed by parachain identifier or `None` for the relay chain.
+	/// * `cli` - The cli.
+	fn set_runtimes(
+		zombienet: &mut Zombienet,
+		runtimes: &[(Option<u32>, PathBuf)],
+		cli: &mut impl Cli,
+	) -> anyhow::Result<bool> {
+		for (id, path) in runtimes {
+			let chain = id.map_or_else(
+				|| zombienet.relay_chain().to_string(),
+				|id| format!("parachain {id}"),
+			);
+			let version = match RuntimeVersion::from_file(path) {
+				Ok(version) => version,
+				Err(e) => {
+					cli.outro_cancel(format!(
+						"🚫 The runtime at {} is invalid: {e}",
+						path.display()
+					))?;
+					return Ok(true);
+				},
+			};
+			let spinner = cli.spinner();
+			spinner.start(format!("Determining the current runtime of {chain}..."));
+			let current = zombienet.runtime_version(*id);
+			spinner.clear();
+			match current {
+				Ok(Some(current)) if !version.is_compatible_with(&current) => {
+					cli.outro_cancel(format!(
+						"🚫 The runtime at {} ({version}) is incompatible with the runtime of {chain} ({current}).",
+						path.display()
+					))?;
+					return Ok(true);
+				},
+				Ok(Some(current)) => cli.info(format!(
+					"The runtime of {chain} ({current}) is replaced by {} ({version}).",
+					path.display()
+				))?,
+				_ => cli.warning(format!(
+					"⚠️ The current runtime of {chain} could not be determined, so {} ({version}) is used without validation.",
+					path.display()
+				))?,
+			}
+			if let Err(Error::Config(message)) = zombienet.set_runtime(*id, path) {
+				cli.outro_cancel(format!("🚫 A configuration error occurred: `{message}`"))?;
+				return Ok(true);
+			}
+		}
 		Ok(false)
 	}
 }
 
-async fn run_custom_command(spinner: &ProgressBar, command: &str) -> Result<(), anyhow::Error> {
+/// Parses a runtime to replace that of a chain within the network, specified as `CHAIN=PATH`.
+fn parse_runtime_wasm(value: &str) -> Result<(Option<u32>, PathBuf), String> {
+	let (chain, path) = value
+		.split_once('=')
+		.ok_or_else(|| format!("expected `CHAIN=PATH`, found `{value}`"))?;
+	let id = match chain {
+		"relay" => None,
+		id =>
+			Some(id.parse().map_err(|_| {
+				format!("expected `relay` or a parachain identifier, found `{chain}`")
+			})?),
+	};
+	Ok((id, PathBuf::from(path)))
+}
+
+/// The path of the chain specification generated for a parachain launched via
+/// `polkadot-omni-node`.
+fn chain_spec_path(project: &Path, id: u32) -> PathBuf {
+	Profile::Release
+		.target_directory(project)
+		.join(format!("para-{id}-chain-spec.json"))
+}
+
+async fn run_custom_command(spinner: &impl Spinner, command: &str) -> Result<(), anyhow::Error> {
 	spinner.set_message(format!("Spinning up network & running command: {}", command));
 	sleep(Duration::from_secs(15)).await;
 
@@ -381,31 +634,17 @@ async fn run_custom_command(spinner: &ProgressBar, command: &str) -> Result<(),
 	Ok(())
 }
 
-/// Reports any observed status updates to a progress bar.
-struct ProgressReporter(String, ProgressBar);
+/// Reports any observed status updates to a spinner.
+struct ProgressReporter<S: Spinner>(String, S);
 
-impl Status for ProgressReporter {
+impl<S: Spinner> Status for ProgressReporter<S> {
 	fn update(&self, status: &str) {
 		self.1
-			.start(format!("{}{}", self.0, status.replace("   Compiling", "Compiling")))
+			.set_message(format!("{}{}", self.0, status.replace("   Compiling", "Compiling")))
 	}
-}
-
-/// Reports any observed status updates as indented messages.
-#[derive(Copy, Clone)]
-struct VerboseReporter;
 
-impl Status for VerboseReporter {
-	fn update(&self, status: &str) {
-		const S_BAR: Emoji = Emoji("│", "|");
-		let message = format!(
-			"{bar}  {status}",
-			bar = Theme.bar_color(&ThemeState::Submit).apply_to(S_BAR),
-			status = style(status).dim()
-		);
-		if let Err(e) = Term::stderr().write_line(&message) {
-			println!("An error occurred logging the status message of '{status}': {e}")
-		}
+	fn progress(&self, phase: &str, pct: u8) {
+		Event::new(phase).pct(pct).message(self.0.trim_end_matches(": ")).emit()
 	}
 }
 
@@ -413,10 +652,11 @@ impl Status for VerboseReporter {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use cli::MockCli;
 
 	#[tokio::test]
 	async fn test_run_custom_command() -> Result<(), anyhow::Error> {
-		let spinner = ProgressBar::new(1);
+		let spinner = MockCli::new().spinner();
 
 		// Define the command to be executed
 		let command = "echo 2 + 2";
@@ -426,4 +666,47 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn chain_spec_path_works() {
+		assert_eq!(
+			chain_spec_path(Path::new("./chain"), 2000),
+			PathBuf::from("./chain/target/release/para-2000-chain-spec.json")
+		);
+	}
+
+	#[test]
+	fn zombienet_args_work() -> anyhow::Result<()> {
+		use clap::Parser;
+		let parse = |args: &[&str]| -> anyhow::Result<ZombienetCommand> {
+			let pop = crate::Cli::try_parse_from(
+				["pop", "up", "network", "-f", "network.toml"].iter().chain(args),
+			)?;
+			let crate::commands::Command::Up(crate::commands::up::UpArgs {
+				command: Some(crate::commands::up::Command::Network(command)),
+				..
+			}) = pop.command
+			else {
+				panic!("expected the network command");
+			};
+			Ok(command)
+		};
+		let command = parse(&["--telemetry-url", "wss://telemetry.polkadot.io/submit/ 0"])?;
+		assert_eq!(command.telemetry_url.as_deref(), Some("wss://telemetry.polkadot.io/submit/ 0"));
+		assert!(!command.with_telemetry);
+		assert!(parse(&["--with-telemetry"])?.with_telemetry);
+		let command =
+			parse(&["--runtime-wasm", "relay=./relay.wasm", "--runtime-wasm", "2000=pop.wasm"])?;
+		assert_eq!(
+			command.runtime_wasm,
+			vec![(None, PathBuf::from("./relay.wasm")), (Some(2000), PathBuf::from("pop.wasm"))]
+		);
+		assert!(parse(&["--runtime-wasm", "./relay.wasm"]).is_err());
+		assert!(parse(&["--runtime-wasm", "asset-hub=./relay.wasm"]).is_err());
+		// A local telemetry server cannot be used along with another endpoint.
+		assert!(
+			parse(&["--with-telemetry", "--telemetry-url", "ws://127.0.0.1:8001/submit"]).is_err()
+		);
+		Ok(())
+	}
 }
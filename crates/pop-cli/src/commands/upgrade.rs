@@ -0,0 +1,342 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::network};
+use anyhow::{anyhow, Result};
+use clap::{Args, Subcommand};
+use pop_common::upgrade::{
+	check, ink_versions, manifests, release_tag, sdk_versions, Breakage, Upgrade,
+	UpgradedDependency, POLKADOT_SDK,
+};
+use std::{
+	collections::BTreeMap,
+	fs::read_to_string,
+	path::{Path, PathBuf},
+};
+
+/// The maximum number of breakages listed for each kind.
+const MAX_LISTED_BREAKAGES: usize = 10;
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct UpgradeArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Upgrade the dependencies of a project.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Upgrade the polkadot-sdk and ink! dependencies of a project to a release.
+	#[clap(alias = "p")]
+	Project(UpgradeProjectCommand),
+}
+
+#[derive(Args)]
+pub(crate) struct UpgradeProjectCommand {
+	/// Path to the project [default: current directory].
+	#[arg(short, long)]
+	path: Option<PathBuf>,
+	/// The stable release of polkadot-sdk to upgrade to, e.g. `stable2412`.
+	#[arg(short, long, value_parser = |s: &str| release_tag(s).ok_or("expected a stable release, e.g. `stable2412`"))]
+	release: Option<String>,
+	/// The version of ink! to upgrade to, e.g. `5.1.1`.
+	#[arg(long, value_name = "VERSION", value_parser = |s: &str| is_version(s).then(|| s.to_string()).ok_or("expected a version, e.g. `5.1.1`"))]
+	ink: Option<String>,
+	/// Skip checking that the project builds once upgraded.
+	#[arg(long)]
+	skip_check: bool,
+}
+
+impl UpgradeProjectCommand {
+	/// Executes the command.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Upgrade project dependencies")?;
+		let project = self.path.unwrap_or_else(|| PathBuf::from("./"));
+		let manifests = manifests(&project);
+		if manifests.is_empty() {
+			cli.outro_cancel(format!("🚫 No manifest could be found at {}.", project.display()))?;
+			return Err(anyhow!("no manifest found at {}", project.display()));
+		}
+
+		// Prompt for the releases to upgrade to, based on the dependencies of the project.
+		let (mut release, mut ink) = (self.release, self.ink);
+		if release.is_none() && ink.is_none() {
+			let (uses_sdk, uses_ink) = dependencies(&manifests);
+			if !uses_sdk && !uses_ink {
+				cli.outro_cancel("🚫 The project does not depend on polkadot-sdk or ink!.")?;
+				return Ok(());
+			}
+			if uses_sdk {
+				let input = cli
+					.input("Which stable release of polkadot-sdk would you like to upgrade to?")
+					.placeholder("stable2412")
+					.validate(|input| match release_tag(input) {
+						Some(_) => Ok(()),
+						None => Err("Invalid release."),
+					})
+					.interact()?;
+				release = release_tag(&input);
+			}
+			if uses_ink {
+				ink = Some(
+					cli.input("Which version of ink! would you like to upgrade to?")
+						.placeholder("5.1.1")
+						.validate(|input| match is_version(input) {
+							true => Ok(()),
+							false => Err("Invalid version."),
+						})
+						.interact()?,
+				);
+			}
+		}
+
+		let mut upgrade = Upgrade::default();
+		if let Some(tag) = &release {
+			let spinner = cli.spinner();
+			spinner.start(format!("Fetching the crates of {tag}..."));
+			upgrade.versions =
+				network::retry("fetching the release", || sdk_versions(tag)).await.inspect_err(
+					|e| spinner.error(format!("🚫 Could not fetch the crates of {tag}: {e}")),
+				)?;
+			spinner.clear();
+			upgrade.git = Some((POLKADOT_SDK.to_string(), tag.clone()));
+		}
+		if let Some(version) = &ink {
+			upgrade.versions.extend(ink_versions(version));
+		}
+
+		let mut upgraded = Vec::new();
+		for manifest in &manifests {
+			upgraded.extend(upgrade.apply(manifest)?);
+		}
+		if upgraded.is_empty() {
+			cli.outro("ℹ️ The dependencies of the project are already up to date.")?;
+			return Ok(());
+		}
+		cli.info(summarize_upgrades(&upgraded, &project))?;
+
+		if !self.skip_check {
+			let spinner = cli.spinner();
+			spinner.start("Checking the project with the upgraded dependencies...");
+			let breakages = check(&project)?;
+			spinner.clear();
+			if !breakages.is_empty() {
+				cli.warning(summarize_breakages(&breakages))?;
+				cli.outro(format!(
+					"⚠️ {} dependencies upgraded, with {} errors to be resolved",
+					upgraded.len(),
+					breakages.len()
+				))?;
+				return Ok(());
+			}
+			cli.success("The project builds with the upgraded dependencies.")?;
+		}
+		cli.outro(format!("✅ {} dependencies upgraded", upgraded.len()))?;
+		Ok(())
+	}
+}
+
+/// Whether a version is valid, e.g. `5.1.1` or `6.0.0-alpha`.
+fn is_version(version: &str) -> bool {
+	let version = version.split_once('-').map_or(version, |(version, _)| version);
+	let parts: Vec<_> = version.split('.').collect();
+	parts.len() == 3 && parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Whether the manifests declare dependencies on polkadot-sdk and ink! respectively.
+fn dependencies(manifests: &[PathBuf]) -> (bool, bool) {
+	const SDK_PREFIXES: [&str; 5] = ["frame-", "pallet-", "sp-", "sc-", "cumulus-"];
+	// The names of the dependencies declared, either as keys or as tables.
+	let names: Vec<String> = manifests
+		.iter()
+		.filter_map(|m| read_to_string(m).ok())
+		.flat_map(|manifest| {
+			manifest
+				.lines()
+				.filter_map(|line| {
+					let line = line.trim();
+					match line.strip_prefix('[') {
+						Some(table) => table
+							.trim_end_matches(']')
+							.split_once("dependencies.")
+							.map(|(_, name)| name.to_string()),
+						None => line.split_once('=').map(|(name, _)| name.trim().to_string()),
+					}
+				})
+				.collect::<Vec<_>>()
+		})
+		.collect();
+	let uses_sdk = names
+		.iter()
+		.any(|n| n == "polkadot-sdk" || SDK_PREFIXES.iter().any(|p| n.starts_with(p)));
+	let uses_ink = names.iter().any(|n| n == "ink" || n.starts_with("ink_"));
+	(uses_sdk, uses_ink)
+}
+
+/// Lists the upgraded dependencies by manifest.
+fn summarize_upgrades(upgraded: &[UpgradedDependency], project: &Path) -> String {
+	let mut manifests: BTreeMap<_, Vec<_>> = BTreeMap::new();
+	for dependency in upgraded {
+		let manifest = dependency.manifest.strip_prefix(project).unwrap_or(&dependency.manifest);
+		manifests.entry(manifest).or_default().push(format!("  {dependency}"));
+	}
+	manifests
+		.into_iter()
+		.map(|(manifest, dependencies)| {
+			format!("{}:\n{}", manifest.display(), dependencies.join("\n"))
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+/// Lists the breakages detected by kind, up to a maximum of each kind.
+fn summarize_breakages(breakages: &[Breakage]) -> String {
+	let mut kinds: BTreeMap<_, Vec<_>> = BTreeMap::new();
+	for breakage in breakages {
+		kinds.entry(breakage.kind).or_default().push(breakage);
+	}
+	let mut summary = vec![format!(
+		"The upgrade introduced {} errors, which may be API breakages:",
+		breakages.len()
+	)];
+	for (kind, breakages) in kinds {
+		summary.push(format!("{kind} ({}):", breakages.len()));
+		summary.extend(breakages.iter().take(MAX_LISTED_BREAKAGES).map(|b| format!("  {b}")));
+		if breakages.len() > MAX_LISTED_BREAKAGES {
+			summary.push(format!("  ...and {} more", breakages.len() - MAX_LISTED_BREAKAGES));
+		}
+	}
+	summary.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use pop_common::upgrade::BreakageKind;
+	use std::fs::{create_dir_all, write};
+	use tempfile::tempdir;
+
+	#[test]
+	fn upgrade_project_args_works() -> Result<()> {
+		let parse = |args: &[&str]| {
+			let pop = crate::Cli::try_parse_from(["pop", "upgrade", "project"].iter().chain(args))?;
+			let crate::commands::Command::Upgrade(args) = pop.command else {
+				panic!("expected the upgrade command");
+			};
+			let Command::Project(command) = args.command;
+			Ok::<_, clap::Error>(command)
+		};
+		let command = parse(&["-p", "./chain", "--release", "stable2412-1", "--ink", "5.1.1"])?;
+		assert_eq!(command.path, Some(PathBuf::from("./chain")));
+		assert_eq!(command.release, Some("polkadot-stable2412-1".into()));
+		assert_eq!(command.ink, Some("5.1.1".into()));
+		assert!(!command.skip_check);
+		assert!(parse(&["--release", "v1.14.0"]).is_err());
+		assert!(parse(&["--ink", "5.1"]).is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn execute_upgrades_ink() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let project = temp_dir.path();
+		write(
+			project.join("Cargo.toml"),
+			"[dependencies]\nink = { version = \"5.0.0\", default-features = false }\n\n[dev-dependencies]\nink_e2e = \"5.0.0\"\n",
+		)?;
+		let mut cli = MockCli::new()
+			.expect_intro("Upgrade project dependencies")
+			.expect_input("Which version of ink! would you like to upgrade to?", "5.1.1".into())
+			.expect_info("Cargo.toml:\n  ink: 5.0.0 -> 5.1.1\n  ink_e2e: 5.0.0 -> 5.1.1")
+			.expect_outro("✅ 2 dependencies upgraded");
+		let command = UpgradeProjectCommand {
+			path: Some(project.to_path_buf()),
+			release: None,
+			ink: None,
+			skip_check: true,
+		};
+		command.execute(&mut cli).await?;
+		assert!(read_to_string(project.join("Cargo.toml"))?.contains("ink_e2e = \"5.1.1\""));
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn execute_handles_up_to_date_project() -> Result<()> {
+		let temp_dir = tempdir()?;
+		write(temp_dir.path().join("Cargo.toml"), "[dependencies]\nink = \"5.1.1\"\n")?;
+		let mut cli = MockCli::new()
+			.expect_intro("Upgrade project dependencies")
+			.expect_outro("ℹ️ The dependencies of the project are already up to date.");
+		let command = UpgradeProjectCommand {
+			path: Some(temp_dir.path().to_path_buf()),
+			release: None,
+			ink: Some("5.1.1".into()),
+			skip_check: false,
+		};
+		command.execute(&mut cli).await?;
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn execute_fails_without_manifest() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let mut cli =
+			MockCli::new().expect_intro("Upgrade project dependencies").expect_outro_cancel(
+				format!("🚫 No manifest could be found at {}.", temp_dir.path().display()),
+			);
+		let command = UpgradeProjectCommand {
+			path: Some(temp_dir.path().to_path_buf()),
+			release: None,
+			ink: None,
+			skip_check: true,
+		};
+		assert!(command.execute(&mut cli).await.is_err());
+		cli.verify()
+	}
+
+	#[test]
+	fn dependencies_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let (runtime, contract, other) = (
+			temp_dir.path().join("runtime/Cargo.toml"),
+			temp_dir.path().join("contract/Cargo.toml"),
+			temp_dir.path().join("other/Cargo.toml"),
+		);
+		for manifest in [&runtime, &contract, &other] {
+			create_dir_all(manifest.parent().unwrap())?;
+		}
+		write(&runtime, "[dependencies.frame-support]\nversion = \"38.0.0\"\n")?;
+		write(&contract, "[dependencies]\nink = { version = \"5.1.1\" }\n")?;
+		write(&other, "[dependencies]\nserde = \"1.0\"\ninkwell = \"0.5\"\n")?;
+		assert_eq!(dependencies(std::slice::from_ref(&runtime)), (true, false));
+		assert_eq!(dependencies(std::slice::from_ref(&contract)), (false, true));
+		assert_eq!(dependencies(std::slice::from_ref(&other)), (false, false));
+		assert_eq!(dependencies(&[runtime, contract, other]), (true, true));
+		Ok(())
+	}
+
+	#[test]
+	fn summarize_breakages_works() {
+		let breakage = |kind, code: &str, line| Breakage {
+			kind,
+			code: Some(code.into()),
+			message: format!("error {line}"),
+			location: Some(format!("runtime/src/lib.rs:{line}")),
+		};
+		let mut breakages: Vec<_> =
+			(0..12).map(|l| breakage(BreakageKind::MissingItem, "E0412", l)).collect();
+		breakages.push(breakage(BreakageKind::ChangedTrait, "E0046", 99));
+		let summary = summarize_breakages(&breakages);
+		assert!(summary.starts_with(
+			"The upgrade introduced 13 errors, which may be API breakages:\nremoved or renamed item (12):\n  [E0412] error 0 (runtime/src/lib.rs:0)"
+		));
+		assert!(summary.ends_with(
+			"  ...and 2 more\nchanged trait (1):\n  [E0046] error 99 (runtime/src/lib.rs:99)"
+		));
+	}
+}
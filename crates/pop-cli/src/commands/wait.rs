@@ -0,0 +1,219 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::network::TimedOut};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use pop_parachains::{wait_for, wait_for_client, Condition, ObservedBlock, H256};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+/// The default maximum time to wait, unless otherwise specified via `--timeout`.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+/// The interval between attempts to connect to an unreachable node.
+const RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Args)]
+pub struct WaitArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+	/// Websocket endpoint of the node.
+	#[arg(short, long, global = true, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+	/// Output the result as JSON.
+	#[arg(long, global = true)]
+	pub(crate) json: bool,
+}
+
+/// Block until a condition holds on a chain.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Wait until the node is reachable.
+	Reachable,
+	/// Wait until the chain has progressed to at least the specified block number.
+	Block {
+		/// The block number.
+		number: u64,
+	},
+	/// Wait until an extrinsic has been included within a finalized block.
+	Tx {
+		/// The hash of the extrinsic.
+		hash: H256,
+	},
+	/// Wait until a parachain is producing blocks, with `--url` being an endpoint of the relay
+	/// chain.
+	Para {
+		/// The identifier of the parachain.
+		id: u32,
+	},
+}
+
+impl From<Command> for Condition {
+	fn from(command: Command) -> Self {
+		match command {
+			Command::Reachable => Condition::Reachable,
+			Command::Block { number } => Condition::Block(number),
+			Command::Tx { hash } => Condition::Finalized(hash),
+			Command::Para { id } => Condition::Producing(id),
+		}
+	}
+}
+
+/// Waits until a condition holds on a chain.
+pub(crate) struct WaitCommand<'a, CLI: Cli> {
+	/// The cli to be used.
+	pub(crate) cli: &'a mut CLI,
+	/// The arguments of the command.
+	pub(crate) args: WaitArgs,
+	/// The maximum time to wait, otherwise the default.
+	pub(crate) timeout: Option<Duration>,
+}
+
+impl<'a, CLI: Cli> WaitCommand<'a, CLI> {
+	/// Executes the command, failing if the condition does not hold within the timeout.
+	pub(crate) async fn execute(self) -> Result<()> {
+		let WaitArgs { command, url, json } = self.args;
+		let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+		let condition = Condition::from(command);
+		self.cli.intro("Wait for chain readiness")?;
+		let spinner = self.cli.spinner();
+		spinner.start(format!("Waiting for {condition} at {url}..."));
+
+		let started = Instant::now();
+		let result = tokio::time::timeout(timeout, async {
+			let client = wait_for_client(url.as_str(), RETRY_INTERVAL).await;
+			wait_for(&client, &condition).await
+		})
+		.await;
+		let elapsed = started.elapsed();
+		spinner.clear();
+
+		let observed = match result {
+			Ok(observed) => Some(observed?),
+			Err(_) => None,
+		};
+		if json {
+			self.cli.plain(outcome(&condition, &url, observed.as_ref(), elapsed))?;
+		}
+		match observed {
+			Some(block) => {
+				let at = block.map(|b| format!(" at block #{}", b.number)).unwrap_or_default();
+				self.cli.success(format!("Observed {condition}{at}."))?;
+				self.cli.outro(format!("Waited {:.1}s", elapsed.as_secs_f64()))?;
+				Ok(())
+			},
+			None =>
+				Err(TimedOut { operation: format!("waiting for {condition} at {url}"), timeout }
+					.into()),
+		}
+	}
+}
+
+/// The outcome of waiting, as a JSON result.
+///
+/// # Arguments
+/// * `condition` - The condition waited for.
+/// * `url` - Endpoint of the node.
+/// * `observed` - Whether the condition was observed to hold, along with any block at which it
+///   held.
+/// * `elapsed` - The time spent waiting.
+fn outcome(
+	condition: &Condition,
+	url: &Url,
+	observed: Option<&Option<ObservedBlock>>,
+	elapsed: Duration,
+) -> Value {
+	let block = observed
+		.and_then(|b| b.as_ref())
+		.map(|b| json!({ "number": b.number, "hash": format!("{:?}", b.hash) }));
+	json!({
+		"condition": condition.to_string(),
+		"url": url.as_str(),
+		"satisfied": observed.is_some(),
+		"block": block,
+		"elapsed_ms": elapsed.as_millis() as u64,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use std::net::TcpListener;
+
+	#[test]
+	fn wait_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"wait",
+			"block",
+			"10",
+			"--url",
+			"ws://127.0.0.1:9955",
+			"--json",
+		])?;
+		let crate::commands::Command::Wait(args) = pop.command else {
+			panic!("expected the wait command");
+		};
+		assert_eq!(args.url, Url::parse("ws://127.0.0.1:9955")?);
+		assert!(args.json);
+		assert_eq!(Condition::from(args.command), Condition::Block(10));
+
+		let hash = format!("0x{}", "01".repeat(32));
+		let pop = crate::Cli::try_parse_from(["pop", "wait", "tx", &hash])?;
+		let crate::commands::Command::Wait(args) = pop.command else {
+			panic!("expected the wait command");
+		};
+		assert_eq!(args.url, Url::parse(DEFAULT_URL)?);
+		assert!(!args.json);
+		assert_eq!(Condition::from(args.command), Condition::Finalized(H256::repeat_byte(1)));
+		assert!(crate::Cli::try_parse_from(["pop", "wait", "para", "not-an-id"]).is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn wait_times_out() -> Result<()> {
+		// Reserve a local port with nothing listening on it.
+		let port = TcpListener::bind("127.0.0.1:0")?.local_addr()?.port();
+		let url = Url::parse(&format!("ws://127.0.0.1:{port}"))?;
+		let mut cli = MockCli::new().expect_intro("Wait for chain readiness");
+		let args = WaitArgs { command: Command::Reachable, url: url.clone(), json: false };
+		let timeout = Some(Duration::ZERO);
+		let error = WaitCommand { cli: &mut cli, args, timeout }.execute().await.unwrap_err();
+		assert!(error.is::<TimedOut>());
+		assert_eq!(
+			error.to_string(),
+			format!("Timed out after 0s whilst waiting for node reachable at {url}")
+		);
+		cli.verify()
+	}
+
+	#[test]
+	fn outcome_works() -> Result<()> {
+		let url = Url::parse(DEFAULT_URL)?;
+		let block = ObservedBlock { number: 10, hash: H256::zero() };
+		assert_eq!(
+			outcome(&Condition::Block(10), &url, Some(&Some(block)), Duration::from_millis(1500)),
+			json!({
+				"condition": "chain at block #10",
+				"url": DEFAULT_URL,
+				"satisfied": true,
+				"block": { "number": 10, "hash": format!("0x{}", "00".repeat(32)) },
+				"elapsed_ms": 1500,
+			})
+		);
+		assert_eq!(
+			outcome(&Condition::Reachable, &url, None, Duration::from_secs(5)),
+			json!({
+				"condition": "node reachable",
+				"url": DEFAULT_URL,
+				"satisfied": false,
+				"block": null,
+				"elapsed_ms": 5000,
+			})
+		);
+		Ok(())
+	}
+}
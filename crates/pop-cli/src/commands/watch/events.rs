@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::network};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{block_events, set_up_client, BlockEvent, EventFilter, H256};
+use serde_json::{json, Map, Value};
+use std::time::Duration;
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+/// The interval before reconnecting once a subscription ends.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Command to stream the events of a chain.
+#[derive(Args)]
+pub(crate) struct WatchEventsCommand {
+	/// The pallet of the events to be streamed, e.g. `Balances` [default: any pallet].
+	#[arg(short, long)]
+	pub(crate) pallet: Option<String>,
+	/// The name of the events to be streamed, e.g. `Transfer` [default: any event].
+	#[arg(short, long)]
+	pub(crate) event: Option<String>,
+	/// Stream the events of finalized blocks, rather than of new best blocks.
+	#[arg(long)]
+	pub(crate) finalized: bool,
+	/// Output each event as a line of JSON.
+	#[arg(long)]
+	pub(crate) json: bool,
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+}
+
+impl WatchEventsCommand {
+	/// Executes the command, streaming events until interrupted and reconnecting whenever the
+	/// subscription ends.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Watch events")?;
+		let filter = EventFilter { pallet: self.pallet.clone(), event: self.event.clone() };
+		let blocks = if self.finalized { "finalized" } else { "new" };
+		cli.info(format!(
+			"Streaming {} from {blocks} blocks at {}, press Ctrl+C to stop...",
+			describe(&filter),
+			self.url
+		))?;
+		// The last block observed, as blocks may be observed again after reconnecting.
+		let mut last: Option<(u32, H256)> = None;
+		loop {
+			let client =
+				network::retry("connecting to the chain", || set_up_client(self.url.as_str()))
+					.await?;
+			let subscription = match self.finalized {
+				true => client.blocks().subscribe_finalized().await,
+				false => client.blocks().subscribe_best().await,
+			};
+			match subscription {
+				Ok(mut subscription) =>
+					while let Some(block) = subscription.next().await {
+						let block = match block {
+							Ok(block) => block,
+							Err(e) => {
+								cli.warning(format!("The subscription failed: {e}"))?;
+								break;
+							},
+						};
+						let observed = last.is_some_and(|(number, hash)| {
+							hash == block.hash() || (self.finalized && block.number() <= number)
+						});
+						if observed {
+							continue;
+						}
+						last = Some((block.number(), block.hash()));
+						match block_events(&block, &filter).await {
+							Ok(events) =>
+								for event in events {
+									self.output(&event, cli)?;
+								},
+							Err(e) => cli.warning(format!(
+								"The events of block #{} could not be decoded: {e}",
+								block.number()
+							))?,
+						}
+					},
+				Err(e) => cli.warning(format!("The subscription failed: {e}"))?,
+			}
+			cli.warning("Reconnecting to the chain...")?;
+			tokio::time::sleep(RECONNECT_INTERVAL).await;
+		}
+	}
+
+	// Outputs an event, either on a single line or as a line of JSON.
+	fn output(&self, event: &BlockEvent, cli: &mut impl Cli) -> Result<()> {
+		match self.json {
+			true => cli.plain(json_line(event))?,
+			false => cli.info(line(event))?,
+		}
+		Ok(())
+	}
+}
+
+// Describes the events selected by a filter.
+fn describe(filter: &EventFilter) -> String {
+	match (&filter.pallet, &filter.event) {
+		(None, None) => "all events".to_string(),
+		(Some(pallet), None) => format!("the events of {pallet}"),
+		(None, Some(event)) => format!("{event} events"),
+		(Some(pallet), Some(event)) => format!("{pallet}.{event} events"),
+	}
+}
+
+// Formats an event on a single line, prefixed by its block and any extrinsic which emitted it.
+fn line(event: &BlockEvent) -> String {
+	match event.extrinsic {
+		Some(index) => format!("#{}-{index} {}", event.number, event.event),
+		None => format!("#{} {}", event.number, event.event),
+	}
+}
+
+// Formats an event as a line of JSON.
+fn json_line(event: &BlockEvent) -> Value {
+	let fields: Map<String, Value> =
+		event.event.fields.iter().map(|(n, v)| (n.clone(), json!(v))).collect();
+	json!({
+		"block": event.number,
+		"hash": format!("{:?}", event.hash),
+		"extrinsic": event.extrinsic,
+		"pallet": event.event.pallet,
+		"event": event.event.name,
+		"fields": fields,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use clap::Parser;
+	use pop_parachains::DecodedEvent;
+
+	fn event(extrinsic: Option<u32>) -> BlockEvent {
+		BlockEvent {
+			number: 42,
+			hash: H256::repeat_byte(1),
+			extrinsic,
+			event: DecodedEvent {
+				pallet: "Balances".into(),
+				name: "Transfer".into(),
+				fields: vec![("amount".into(), "100".into())],
+			},
+		}
+	}
+
+	#[test]
+	fn output_works() -> Result<()> {
+		let mut command = WatchEventsCommand {
+			pallet: None,
+			event: None,
+			finalized: false,
+			json: false,
+			url: Url::parse(DEFAULT_URL)?,
+		};
+		let mut cli = MockCli::new().expect_info(line(&event(Some(2))));
+		command.output(&event(Some(2)), &mut cli)?;
+		cli.verify()?;
+
+		command.json = true;
+		let mut cli = MockCli::new().expect_plain(json_line(&event(Some(2))));
+		command.output(&event(Some(2)), &mut cli)?;
+		cli.verify()
+	}
+
+	#[test]
+	fn watch_events_args_works() -> Result<()> {
+		let pop = crate::Cli::try_parse_from([
+			"pop",
+			"watch",
+			"events",
+			"--pallet",
+			"Balances",
+			"--event",
+			"Transfer",
+			"--finalized",
+			"--json",
+		])?;
+		let crate::commands::Command::Watch(args) = pop.command else {
+			panic!("expected the watch command");
+		};
+		let super::super::Command::Events(command) = args.command else {
+			panic!("expected the events command");
+		};
+		assert_eq!(command.pallet.as_deref(), Some("Balances"));
+		assert_eq!(command.event.as_deref(), Some("Transfer"));
+		assert!(command.finalized && command.json);
+		assert_eq!(command.url, Url::parse(DEFAULT_URL)?);
+		Ok(())
+	}
+
+	#[test]
+	fn describe_works() {
+		let filter = |pallet: Option<&str>, event: Option<&str>| EventFilter {
+			pallet: pallet.map(String::from),
+			event: event.map(String::from),
+		};
+		assert_eq!(describe(&filter(None, None)), "all events");
+		assert_eq!(describe(&filter(Some("Balances"), None)), "the events of Balances");
+		assert_eq!(describe(&filter(None, Some("Transfer"))), "Transfer events");
+		assert_eq!(
+			describe(&filter(Some("Balances"), Some("Transfer"))),
+			"Balances.Transfer events"
+		);
+	}
+
+	#[test]
+	fn line_works() {
+		assert_eq!(line(&event(Some(2))), "#42-2 Balances.Transfer { amount: 100 }");
+		assert_eq!(line(&event(None)), "#42 Balances.Transfer { amount: 100 }");
+		assert_eq!(
+			json_line(&event(Some(2))),
+			json!({
+				"block": 42,
+				"hash": format!("0x{}", "01".repeat(32)),
+				"extrinsic": 2,
+				"pallet": "Balances",
+				"event": "Transfer",
+				"fields": { "amount": "100" },
+			})
+		);
+	}
+}
@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::network};
+use anyhow::Result;
+use clap::Args;
+use pop_parachains::{ChainHealth, HealthMonitor};
+use std::{
+	collections::VecDeque,
+	time::{Duration, Instant},
+};
+use url::Url;
+
+const DEFAULT_URL: &str = "ws://localhost:9944/";
+/// The number of recent samples over which the block production rate is measured.
+const SAMPLES: usize = 10;
+
+/// Command to monitor the health of a chain.
+#[derive(Args)]
+pub(crate) struct WatchHealthCommand {
+	/// The interval between samples, in seconds.
+	#[arg(short, long, default_value = "6")]
+	pub(crate) interval: u64,
+	/// Alert when finality lags behind the best block by more than this number of blocks.
+	#[arg(long, default_value = "10")]
+	pub(crate) max_lag: u32,
+	/// Alert when no block has been produced for this number of seconds.
+	#[arg(long, default_value = "30")]
+	pub(crate) max_block_time: u64,
+	/// Alert when the node has fewer peers, unless it is not expected to have any.
+	#[arg(long, default_value = "1")]
+	pub(crate) min_peers: usize,
+	/// Alert when more parachain blocks than this are yet to be included by the relay chain.
+	#[arg(long, default_value = "3")]
+	pub(crate) max_unincluded: usize,
+	/// Websocket endpoint of a node of the chain.
+	#[arg(short, long, value_parser, default_value = DEFAULT_URL)]
+	pub(crate) url: Url,
+}
+
+impl WatchHealthCommand {
+	/// Executes the command, sampling the health of the chain until interrupted and reconnecting
+	/// whenever a sample fails.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) async fn execute(self, cli: &mut impl Cli) -> Result<()> {
+		cli.intro("Watch health")?;
+		cli.info(format!(
+			"Monitoring the health of the chain at {} every {}s, press Ctrl+C to stop...",
+			self.url, self.interval
+		))?;
+		let interval = Duration::from_secs(self.interval.max(1));
+		// The recent best block numbers, by when they were observed.
+		let mut samples = VecDeque::with_capacity(SAMPLES);
+		// When the best block last advanced, along with its number.
+		let mut produced: Option<(Instant, u32)> = None;
+		loop {
+			let monitor = network::retry("connecting to the chain", || {
+				HealthMonitor::connect(self.url.as_str())
+			})
+			.await?;
+			loop {
+				let health = match monitor.sample().await {
+					Ok(health) => health,
+					Err(e) => {
+						cli.warning(format!("The chain could not be sampled: {e}"))?;
+						break;
+					},
+				};
+				let now = Instant::now();
+				if samples.len() == SAMPLES {
+					samples.pop_front();
+				}
+				samples.push_back((now, health.best));
+				if !matches!(produced, Some((_, best)) if health.best <= best) {
+					produced = Some((now, health.best));
+				}
+				let rate = rate(&samples);
+				cli.info(describe(&health, rate))?;
+				let stalled = produced.map(|(at, _)| now.duration_since(at)).unwrap_or_default();
+				for alert in self.alerts(&health, stalled) {
+					cli.warning(format!("⚠️ {alert}"))?;
+				}
+				tokio::time::sleep(interval).await;
+			}
+			cli.warning("Reconnecting to the chain...")?;
+			tokio::time::sleep(interval).await;
+		}
+	}
+
+	// The thresholds breached by a sample, given how long since a block was last produced.
+	fn alerts(&self, health: &ChainHealth, stalled: Duration) -> Vec<String> {
+		let mut alerts = Vec::new();
+		if health.lag() > self.max_lag {
+			alerts.push(format!(
+				"Finality is lagging by {} blocks, exceeding {}.",
+				health.lag(),
+				self.max_lag
+			));
+		}
+		if stalled.as_secs() >= self.max_block_time {
+			alerts.push(format!("No block has been produced for {}s.", stalled.as_secs()));
+		}
+		if health.should_have_peers && health.peers < self.min_peers {
+			alerts.push(format!("The node has {} peers, below {}.", health.peers, self.min_peers));
+		}
+		if let Some(segment) = health.unincluded_segment.filter(|s| *s > self.max_unincluded) {
+			alerts.push(format!(
+				"{segment} blocks are yet to be included by the relay chain, exceeding {}.",
+				self.max_unincluded
+			));
+		}
+		alerts
+	}
+}
+
+// The rate of block production in blocks per minute, as measured across samples of the best block.
+fn rate(samples: &VecDeque<(Instant, u32)>) -> Option<f64> {
+	let ((first, from), (last, to)) = (samples.front()?, samples.back()?);
+	let elapsed = last.duration_since(*first).as_secs_f64();
+	(elapsed > 0.0).then(|| to.saturating_sub(*from) as f64 * 60.0 / elapsed)
+}
+
+// Describes a sample of the health of a chain on a single line.
+fn describe(health: &ChainHealth, rate: Option<f64>) -> String {
+	let mut output =
+		format!("Best #{}, finalized #{} (lag {})", health.best, health.finalized, health.lag());
+	if let Some(rate) = rate {
+		output.push_str(&format!(", {rate:.1} blocks/min"));
+	}
+	output.push_str(&format!(", {} peers", health.peers));
+	if health.is_syncing {
+		output.push_str(", syncing");
+	}
+	if let Some(segment) = health.unincluded_segment {
+		output.push_str(&format!(", {segment} unincluded"));
+	}
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	fn parse(args: &[&str]) -> Result<WatchHealthCommand> {
+		let pop = crate::Cli::try_parse_from(["pop", "watch", "health"].iter().chain(args))?;
+		let crate::commands::Command::Watch(args) = pop.command else {
+			panic!("expected the watch command");
+		};
+		let super::super::Command::Health(command) = args.command else {
+			panic!("expected the health command");
+		};
+		Ok(command)
+	}
+
+	#[test]
+	fn watch_health_args_works() -> Result<()> {
+		let command = parse(&[])?;
+		assert_eq!(command.interval, 6);
+		assert_eq!((command.max_lag, command.max_block_time), (10, 30));
+		assert_eq!((command.min_peers, command.max_unincluded), (1, 3));
+		assert_eq!(command.url, Url::parse(DEFAULT_URL)?);
+		let command = parse(&["-i", "12", "--max-lag", "3", "-u", "ws://localhost:8000"])?;
+		assert_eq!((command.interval, command.max_lag), (12, 3));
+		assert_eq!(command.url, Url::parse("ws://localhost:8000")?);
+		Ok(())
+	}
+
+	#[test]
+	fn alerts_works() -> Result<()> {
+		let command = parse(&[])?;
+		let healthy = ChainHealth {
+			best: 12,
+			finalized: 10,
+			peers: 3,
+			should_have_peers: true,
+			is_syncing: false,
+			unincluded_segment: Some(1),
+		};
+		assert!(command.alerts(&healthy, Duration::from_secs(6)).is_empty());
+		let unhealthy = ChainHealth { best: 30, peers: 0, unincluded_segment: Some(4), ..healthy };
+		assert_eq!(
+			command.alerts(&unhealthy, Duration::from_secs(30)),
+			vec![
+				"Finality is lagging by 20 blocks, exceeding 10.",
+				"No block has been produced for 30s.",
+				"The node has 0 peers, below 1.",
+				"4 blocks are yet to be included by the relay chain, exceeding 3.",
+			]
+		);
+		// Local chains are not expected to have peers.
+		let local = ChainHealth { peers: 0, should_have_peers: false, ..healthy };
+		assert!(command.alerts(&local, Duration::ZERO).is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn rate_works() {
+		let now = Instant::now();
+		let mut samples = VecDeque::from([(now, 10)]);
+		assert_eq!(rate(&samples), None);
+		samples.push_back((now + Duration::from_secs(60), 20));
+		assert_eq!(rate(&samples), Some(10.0));
+	}
+
+	#[test]
+	fn describe_works() {
+		let health = ChainHealth {
+			best: 12,
+			finalized: 10,
+			peers: 3,
+			is_syncing: true,
+			unincluded_segment: Some(1),
+			..Default::default()
+		};
+		assert_eq!(
+			describe(&health, Some(10.0)),
+			"Best #12, finalized #10 (lag 2), 10.0 blocks/min, 3 peers, syncing, 1 unincluded"
+		);
+		let health = ChainHealth { best: 1, finalized: 1, ..Default::default() };
+		assert_eq!(describe(&health, None), "Best #1, finalized #1 (lag 0), 0 peers");
+	}
+}
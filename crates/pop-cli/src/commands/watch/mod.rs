@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use clap::{Args, Subcommand};
+
+pub(crate) mod events;
+pub(crate) mod health;
+
+/// Arguments for watching a chain.
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct WatchArgs {
+	#[command(subcommand)]
+	pub(crate) command: Command,
+}
+
+/// Watch a chain.
+#[derive(Subcommand)]
+pub(crate) enum Command {
+	/// Stream the decoded events of new blocks, optionally filtered by pallet and name.
+	#[clap(alias = "e")]
+	Events(events::WatchEventsCommand),
+	/// Monitor the health of a chain, alerting when thresholds are breached.
+	#[clap(alias = "h")]
+	Health(health::WatchHealthCommand),
+}
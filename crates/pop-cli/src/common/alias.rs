@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use anyhow::{anyhow, Result};
+use pop_common::config::Alias;
+use std::collections::{BTreeMap, HashSet};
+
+/// Expands any user-defined alias used as the command, before the arguments are parsed.
+///
+/// Built-in commands (and their aliases) always take precedence over user-defined aliases. An
+/// alias may expand to another alias, provided it does not recursively refer to itself.
+///
+/// # Arguments
+/// * `args` - The command line arguments, including the binary name.
+/// * `aliases` - The user-defined aliases.
+/// * `commands` - The names (and aliases) of the built-in commands.
+/// * `options` - The global options which take a separate value (e.g. `--timeout 30`).
+pub fn expand_aliases(
+	mut args: Vec<String>,
+	aliases: &BTreeMap<String, Alias>,
+	commands: &[String],
+	options: &[String],
+) -> Result<Vec<String>> {
+	let Some(position) = command_position(&args, options) else {
+		return Ok(args);
+	};
+	let mut expanded = HashSet::new();
+	while !commands.contains(&args[position]) {
+		let Some(alias) = aliases.get(&args[position]) else {
+			break;
+		};
+		if !expanded.insert(args[position].clone()) {
+			return Err(anyhow!("The alias `{}` is recursive", args[position]));
+		}
+		let alias_args = alias.args();
+		if alias_args.is_empty() {
+			return Err(anyhow!("The alias `{}` is empty", args[position]));
+		}
+		args.splice(position..=position, alias_args);
+	}
+	Ok(args)
+}
+
+// The position of the command, being the first argument which is neither an option nor the value
+// of an option.
+fn command_position(args: &[String], options: &[String]) -> Option<usize> {
+	let mut position = 1;
+	while let Some(arg) = args.get(position) {
+		if !arg.starts_with('-') {
+			return Some(position);
+		}
+		// Skip the value of the option, unless specified via `--option=value`.
+		if options.contains(arg) {
+			position += 1;
+		}
+		position += 1;
+	}
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn args(args: &str) -> Vec<String> {
+		args.split_whitespace().map(String::from).collect()
+	}
+
+	fn aliases() -> BTreeMap<String, Alias> {
+		BTreeMap::from([
+			("b".to_string(), Alias::Command("build --release".to_string())),
+			("deploy-dev".to_string(), Alias::Args(args("up --url ws://localhost:9944 -y"))),
+			("rb".to_string(), Alias::Command("b --package runtime".to_string())),
+			("loop".to_string(), Alias::Command("loop --release".to_string())),
+			("empty".to_string(), Alias::Args(vec![])),
+			("up".to_string(), Alias::Command("build".to_string())),
+		])
+	}
+
+	fn commands() -> Vec<String> {
+		args("build b up u call c")
+	}
+
+	fn options() -> Vec<String> {
+		args("--timeout --retries --retry-delay --env")
+	}
+
+	#[test]
+	fn expand_aliases_works() -> Result<()> {
+		let commands = args("build up call c");
+		assert_eq!(
+			expand_aliases(args("pop b --path ./runtime"), &aliases(), &commands, &options())?,
+			args("pop build --release --path ./runtime")
+		);
+		assert_eq!(
+			expand_aliases(args("pop deploy-dev"), &aliases(), &commands, &options())?,
+			args("pop up --url ws://localhost:9944 -y")
+		);
+		// Aliases can refer to other aliases.
+		assert_eq!(
+			expand_aliases(args("pop rb"), &aliases(), &commands, &options())?,
+			args("pop build --release --package runtime")
+		);
+		// Only the command is expanded.
+		assert_eq!(
+			expand_aliases(args("pop call b"), &aliases(), &commands, &options())?,
+			args("pop call b")
+		);
+		// Options preceding the command are preserved.
+		assert_eq!(
+			expand_aliases(args("pop --help b"), &aliases(), &commands, &options())?,
+			args("pop --help build --release")
+		);
+		// Including the values of global options.
+		assert_eq!(
+			expand_aliases(
+				args("pop --timeout 30 --retries=3 --env dev -q deploy-dev"),
+				&aliases(),
+				&commands,
+				&options()
+			)?,
+			args("pop --timeout 30 --retries=3 --env dev -q up --url ws://localhost:9944 -y")
+		);
+		assert_eq!(
+			expand_aliases(args("pop --retry-delay 2"), &aliases(), &commands, &options())?,
+			args("pop --retry-delay 2")
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn expand_aliases_ignores_builtin_commands() -> Result<()> {
+		for command in ["pop b", "pop up", "pop unknown", "pop", "pop --version"] {
+			assert_eq!(
+				expand_aliases(args(command), &aliases(), &commands(), &options())?,
+				args(command)
+			);
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn expand_aliases_fails_when_invalid() {
+		assert!(matches!(
+			expand_aliases(args("pop loop"), &aliases(), &commands(), &options()),
+			Err(e) if e.to_string() == "The alias `loop` is recursive"
+		));
+		assert!(matches!(
+			expand_aliases(args("pop empty"), &aliases(), &commands(), &options()),
+			Err(e) if e.to_string() == "The alias `empty` is empty"
+		));
+	}
+}
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::Cli;
+use anyhow::Result;
+use pop_common::{balances::Balances, call::TokenMetadata};
+
+/// Warns of any issue with the balances involved in an operation transferring value which would
+/// cause the operation to fail or an account to be reaped.
+///
+/// # Arguments
+/// * `balances` - The balances involved in the operation.
+/// * `value` - The value transferred.
+/// * `fees` - The fees paid by the sender.
+/// * `token` - The native token of the chain.
+/// * `cli` - The cli.
+pub(crate) fn warn_of_balance_issues(
+	balances: &Balances,
+	value: u128,
+	fees: u128,
+	token: &TokenMetadata,
+	cli: &mut impl Cli,
+) -> Result<()> {
+	for issue in balances.check(value, fees) {
+		let description = issue.describe(token.token_decimals as u8, &token.symbol);
+		cli.warning(match issue.is_failure() {
+			true => format!("⚠️ The operation would fail: {description}"),
+			false => format!("⚠️ {description}"),
+		})?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+
+	#[test]
+	fn warn_of_balance_issues_works() -> Result<()> {
+		let token = TokenMetadata { token_decimals: 10, symbol: "DOT".into() };
+		let balances = Balances {
+			free: 10u128.pow(10),
+			destination: Some(0),
+			existential_deposit: 10u128.pow(10),
+		};
+		let mut cli = MockCli::new()
+			.expect_warning("⚠️ The operation would fail: The free balance of the signer (1 DOT) does not cover the 2 DOT required.");
+		warn_of_balance_issues(&balances, 2 * 10u128.pow(10), 0, &token, &mut cli)?;
+		cli.verify()?;
+		let mut cli = MockCli::new()
+			.expect_warning("⚠️ The signer would be left with 0.9 DOT, less than the existential deposit of 1 DOT, and its account reaped.")
+			.expect_warning("⚠️ The operation would fail: The destination account does not exist and 0.1 DOT is less than the existential deposit of 1 DOT required to create it.");
+		warn_of_balance_issues(&balances, 10u128.pow(9), 0, &token, &mut cli)?;
+		cli.verify()?;
+		// Nothing is warned of when the balances suffice.
+		let balances = Balances { free: 10 * 10u128.pow(10), ..balances };
+		let mut cli = MockCli::new();
+		warn_of_balance_issues(&balances, 2 * 10u128.pow(10), 0, &token, &mut cli)?;
+		cli.verify()
+	}
+}
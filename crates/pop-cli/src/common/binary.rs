@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use pop_common::sourcing::{Binary, Error};
+use std::fs;
+use tokio::task::JoinHandle;
+#[cfg(feature = "parachain")]
+use {
+	crate::{cli::traits::*, common::network, progress::Reporter},
+	anyhow::anyhow,
+	std::path::PathBuf,
+};
+
+/// A binary being sourced in the background, such as while the user answers prompts, so that it
+/// is ready sooner once required. Any incomplete sourcing is cancelled when dropped.
+pub struct Prefetch {
+	binary: Binary,
+	task: Option<JoinHandle<Result<(), Error>>>,
+}
+
+impl Prefetch {
+	/// Starts sourcing a binary in the background, unless it already exists.
+	///
+	/// # Arguments
+	/// * `binary`: The binary.
+	#[cfg(any(feature = "contract", feature = "benchmark"))]
+	pub fn start(binary: Binary) -> Self {
+		let task = (!binary.exists()).then(|| {
+			let binary = binary.clone();
+			tokio::spawn(async move { binary.source(false, &(), false).await })
+		});
+		Self { binary, task }
+	}
+
+	/// The binary being sourced.
+	#[cfg(feature = "contract")]
+	pub fn binary(&self) -> &Binary {
+		&self.binary
+	}
+
+	/// Waits for the binary to be sourced, returning whether it was sourced successfully.
+	pub async fn finish(mut self) -> bool {
+		match self.task.take() {
+			Some(task) => matches!(task.await, Ok(Ok(()))),
+			None => self.binary.exists(),
+		}
+	}
+}
+
+impl Drop for Prefetch {
+	fn drop(&mut self) {
+		let Some(task) = self.task.take() else { return };
+		if !task.is_finished() {
+			task.abort();
+			// The binary did not exist when sourcing started, so anything now at its path is
+			// incomplete.
+			let _ = fs::remove_file(self.binary.path());
+		}
+	}
+}
+
+/// Checks the status of a binary, sourcing it if necessary.
+///
+/// # Arguments
+/// * `cli`: Command line interface.
+/// * `binary`: The binary.
+/// * `skip_confirm`: A boolean indicating whether to skip confirmation prompts.
+#[cfg(feature = "parachain")]
+pub async fn check_binary_and_prompt(
+	cli: &mut impl Cli,
+	binary: Binary,
+	skip_confirm: bool,
+) -> anyhow::Result<PathBuf> {
+	check_prefetched_binary_and_prompt(cli, Prefetch { binary, task: None }, skip_confirm).await
+}
+
+/// Checks the status of a binary which may be being sourced in the background, waiting for it to
+/// be sourced if necessary.
+///
+/// # Arguments
+/// * `cli`: Command line interface.
+/// * `prefetch`: The binary, along with any sourcing of it in the background.
+/// * `skip_confirm`: A boolean indicating whether to skip confirmation prompts.
+#[cfg(feature = "parachain")]
+pub async fn check_prefetched_binary_and_prompt(
+	cli: &mut impl Cli,
+	prefetch: Prefetch,
+	skip_confirm: bool,
+) -> anyhow::Result<PathBuf> {
+	let binary = prefetch.binary.clone();
+	let name = binary.name().to_string();
+	// A binary being prefetched was not found when sourcing started, so confirmation is still
+	// required to use it.
+	if prefetch.task.is_some() || !binary.exists() {
+		cli.warning(format!("⚠️ The {name} binary is not found."))?;
+		let source = if !skip_confirm {
+			cli.confirm("📦 Would you like to source it automatically now?")
+				.initial_value(true)
+				.interact()?
+		} else {
+			true
+		};
+		if !source {
+			return Err(anyhow!("the {name} binary is required"));
+		}
+		let spinner = cli.spinner();
+		spinner.start(format!("📦 Sourcing {name}..."));
+		// Fall back to sourcing the binary again if sourcing it in the background failed.
+		if !prefetch.finish().await {
+			let reporter = Reporter::new(&name);
+			network::retry(&format!("sourcing {name}"), || binary.source(false, &reporter, true))
+				.await?;
+		}
+		spinner.stop(format!(
+			"✅ {name} successfully sourced. Cached at: {}",
+			binary.path().display()
+		));
+	}
+	Ok(binary.path())
+}
+
+#[cfg(all(test, feature = "benchmark", feature = "contract"))]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use std::fs::File;
+
+	#[tokio::test]
+	async fn prefetch_skips_existing_binary() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("node");
+		File::create(&path)?;
+		let binary = Binary::Local { name: "node".into(), path: path.clone(), manifest: None };
+		let prefetch = Prefetch::start(binary.clone());
+		assert!(prefetch.task.is_none());
+		assert_eq!(prefetch.binary(), &binary);
+		// No prompts are required for an existing binary.
+		let mut cli = MockCli::new();
+		assert_eq!(check_prefetched_binary_and_prompt(&mut cli, prefetch, false).await?, path);
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn prefetch_requires_confirmation() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("node");
+		let binary = Binary::Local { name: "node".into(), path, manifest: None };
+		let prefetch = Prefetch::start(binary);
+		assert!(prefetch.task.is_some());
+		let mut cli = MockCli::new()
+			.expect_warning("⚠️ The node binary is not found.")
+			.expect_confirm("📦 Would you like to source it automatically now?", false);
+		assert!(check_prefetched_binary_and_prompt(&mut cli, prefetch, false).await.is_err());
+		cli.verify()
+	}
+}
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use anyhow::{anyhow, Result};
+use pop_common::config::ChainProfile;
+use pop_parachains::Chain;
+use std::collections::BTreeMap;
+use strum::VariantArray;
+
+/// The name of the local development chain, reachable at the default endpoint of a node.
+const LOCAL: &str = "local";
+/// The endpoint of a local development node.
+const LOCAL_URL: &str = "ws://localhost:9944";
+/// The name of Pop Network, resolving to its deployment on Paseo.
+const POP: &str = "pop";
+
+/// Resolves a named chain, preferring any chain of the same name within the `[chains]` section of
+/// the user configuration file over the maintained defaults: `local`, `pop` and the chains known
+/// to the registry (e.g. `paseo`).
+///
+/// # Arguments
+/// * `name` - The name of the chain.
+/// * `chains` - The chains configured by the user, keyed by name.
+pub(crate) fn resolve_chain(
+	name: &str,
+	chains: &BTreeMap<String, ChainProfile>,
+) -> Result<ChainProfile> {
+	if let Some(profile) = chains.get(name) {
+		return Ok(profile.clone());
+	}
+	if name == LOCAL {
+		return Ok(ChainProfile { url: LOCAL_URL.into(), ..Default::default() });
+	}
+	let chain = match name {
+		POP => Chain::PopPaseo,
+		name => name.parse::<Chain>().map_err(|_| {
+			let known: Vec<_> = chains
+				.keys()
+				.map(String::as_str)
+				.chain([LOCAL, POP])
+				.chain(Chain::VARIANTS.iter().map(AsRef::<str>::as_ref))
+				.collect();
+			anyhow!("Unknown chain `{name}`, expected one of: {}", known.join(", "))
+		})?,
+	};
+	Ok(ChainProfile {
+		url: chain.url().into(),
+		token_symbol: Some(chain.symbol().into()),
+		token_decimals: Some(chain.decimals()),
+		ss58_prefix: None,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn resolve_chain_works() -> Result<()> {
+		let mut chains = BTreeMap::new();
+		assert_eq!(resolve_chain("local", &chains)?.url, LOCAL_URL);
+		let pop = resolve_chain("pop", &chains)?;
+		assert_eq!(pop.url, "wss://rpc1.paseo.popnetwork.xyz");
+		assert_eq!((pop.token_symbol.as_deref(), pop.token_decimals), (Some("PAS"), Some(10)));
+		assert_eq!(resolve_chain("paseo", &chains)?.url, Chain::Paseo.url());
+		assert!(matches!(
+			resolve_chain("unknown", &chains),
+			Err(message) if message.to_string().contains("expected one of: local, pop, polkadot")
+		));
+		// Configured chains take precedence over the defaults.
+		let local = ChainProfile { url: "ws://127.0.0.1:8000".into(), ..Default::default() };
+		chains.insert("local".to_string(), local.clone());
+		assert_eq!(resolve_chain("local", &chains)?, local);
+		Ok(())
+	}
+}
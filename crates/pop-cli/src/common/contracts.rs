@@ -1,9 +1,12 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::cli::traits::*;
-use cliclack::spinner;
+use crate::{
+	cli::traits::*,
+	common::{binary::Prefetch, network},
+	progress::Reporter,
+};
 use pop_common::{manifest::from_path, sourcing::set_executable_permission};
-use pop_contracts::contracts_node_generator;
+use pop_contracts::{contracts_node_generator, has_fingerprint, is_up_to_date};
 use std::{
 	path::{Path, PathBuf},
 	process::{Child, Command},
@@ -16,13 +19,18 @@ use tempfile::NamedTempFile;
 /// # Arguments
 /// * `cli`: Command line interface.
 /// * `cache_path`: The cache directory path.
+/// * `prefetch`: Any sourcing of the binary already started in the background.
 /// * `skip_confirm`: A boolean indicating whether to skip confirmation prompts.
 pub async fn check_contracts_node_and_prompt(
 	cli: &mut impl Cli,
 	cache_path: &Path,
+	mut prefetch: Option<Prefetch>,
 	skip_confirm: bool,
 ) -> anyhow::Result<PathBuf> {
-	let mut binary = contracts_node_generator(PathBuf::from(cache_path), None).await?;
+	let mut binary = match &prefetch {
+		Some(prefetch) => prefetch.binary().clone(),
+		None => contracts_node_generator(PathBuf::from(cache_path), None).await?,
+	};
 	let mut node_path = binary.path();
 	if !binary.exists() {
 		cli.warning("⚠️ The substrate-contracts-node binary is not found.")?;
@@ -34,10 +42,21 @@ pub async fn check_contracts_node_and_prompt(
 			true
 		};
 		if latest {
-			let spinner = spinner();
+			let spinner = cli.spinner();
 			spinner.start("📦 Sourcing substrate-contracts-node...");
 
-			binary.source(false, &(), true).await?;
+			// Fall back to sourcing the binary again if sourcing it in the background failed.
+			let prefetched = match prefetch.take() {
+				Some(prefetch) => prefetch.finish().await,
+				None => false,
+			};
+			if !prefetched {
+				let reporter = Reporter::new(binary.name());
+				network::retry(&format!("sourcing {}", binary.name()), || {
+					binary.source(false, &reporter, true)
+				})
+				.await?;
+			}
 
 			spinner.stop(format!(
 				"✅ substrate-contracts-node successfully sourced. Cached at: {}",
@@ -64,11 +83,15 @@ pub async fn check_contracts_node_and_prompt(
 			true
 		};
 		if latest {
-			let spinner = spinner();
+			let spinner = cli.spinner();
 			spinner.start("📦 Sourcing substrate-contracts-node...");
 
 			binary = contracts_node_generator(crate::cache()?, binary.latest()).await?;
-			binary.source(false, &(), true).await?;
+			let reporter = Reporter::new(binary.name());
+			network::retry(&format!("sourcing {}", binary.name()), || {
+				binary.source(false, &reporter, true)
+			})
+			.await?;
 			set_executable_permission(binary.path())?;
 
 			spinner.stop(format!(
@@ -113,7 +136,7 @@ pub fn terminate_node(
 }
 
 /// Checks if a contract has been built by verifying the existence of the build directory and the
-/// <name>.contract file.
+/// <name>.contract file, and that its sources have not changed since if it was built by pop.
 ///
 /// # Arguments
 /// * `path` - An optional path to the project directory. If no path is provided, the current
@@ -123,10 +146,12 @@ pub fn has_contract_been_built(path: Option<&Path>) -> bool {
 	let Ok(manifest) = from_path(Some(project_path)) else {
 		return false;
 	};
-	manifest
+	let built = manifest
 		.package
 		.map(|p| project_path.join(format!("target/ink/{}.contract", p.name())).exists())
-		.unwrap_or_default()
+		.unwrap_or_default();
+	// Artifacts built by other tools have no fingerprint, so are assumed to be up to date.
+	built && (!has_fingerprint(Some(project_path)) || is_up_to_date(Some(project_path), None))
 }
 
 #[cfg(test)]
@@ -168,7 +193,8 @@ mod tests {
 			.expect_confirm("📦 Would you like to source it automatically now?", true)
 			.expect_warning("⚠️ The substrate-contracts-node binary is not found.");
 
-		let node_path = check_contracts_node_and_prompt(&mut cli, cache_path.path(), false).await?;
+		let node_path =
+			check_contracts_node_and_prompt(&mut cli, cache_path.path(), None, false).await?;
 		// Binary path is at least equal to the cache path + "substrate-contracts-node".
 		assert!(node_path
 			.to_str()
@@ -183,7 +209,8 @@ mod tests {
 		let mut cli =
 			MockCli::new().expect_warning("⚠️ The substrate-contracts-node binary is not found.");
 
-		let node_path = check_contracts_node_and_prompt(&mut cli, cache_path.path(), true).await?;
+		let node_path =
+			check_contracts_node_and_prompt(&mut cli, cache_path.path(), None, true).await?;
 		// Binary path is at least equal to the cache path + "substrate-contracts-node".
 		assert!(node_path
 			.to_str()
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use clap::{Arg, Command};
+use pop_common::config::Environment;
+use std::collections::BTreeMap;
+
+/// Resolves the arguments of a command against an environment, before the arguments are parsed.
+///
+/// Any argument of the command for which the environment defines a value (e.g. `--url`) is added
+/// when not otherwise specified, and any signer and contract aliases are resolved to their secret
+/// key URI and address respectively.
+///
+/// # Arguments
+/// * `args` - The command line arguments, including the binary name.
+/// * `environment` - The environment.
+/// * `command` - The built-in commands, with any global arguments propagated to subcommands.
+pub fn apply_environment(
+	mut args: Vec<String>,
+	environment: &Environment,
+	command: &Command,
+) -> Vec<String> {
+	// Determine the (sub)command being executed.
+	let mut command = command;
+	for arg in args.iter().skip(1).take_while(|a| *a != "--") {
+		if let Some(subcommand) = command
+			.get_subcommands()
+			.find(|c| c.get_name() == arg || c.get_all_aliases().any(|a| a == arg))
+		{
+			command = subcommand;
+		}
+	}
+	let find = |long: &str| command.get_arguments().find(|a| a.get_long() == Some(long));
+
+	// Resolve aliases to their values.
+	for (long, aliases) in [("suri", &environment.signers), ("contract", &environment.contracts)] {
+		if let Some(arg) = find(long) {
+			resolve_aliases(&mut args, arg, aliases);
+		}
+	}
+
+	// Add any values which are not otherwise specified.
+	let para_id = environment.para_id.map(|id| id.to_string());
+	for (long, value) in [
+		("url", environment.url.as_ref()),
+		("suri", environment.suri.as_ref()),
+		("para", para_id.as_ref()),
+		("para-id", para_id.as_ref()),
+	] {
+		let (Some(arg), Some(value)) = (find(long), value) else {
+			continue;
+		};
+		if is_specified(&args, arg) ||
+			command.get_arg_conflicts_with(arg).iter().any(|a| is_specified(&args, a))
+		{
+			continue;
+		}
+		let end = args.iter().position(|a| a == "--").unwrap_or(args.len());
+		args.splice(end..end, [format!("--{long}"), value.clone()]);
+	}
+	args
+}
+
+// Replaces any value of an argument which is an alias with the value it resolves to.
+fn resolve_aliases(args: &mut [String], arg: &Arg, aliases: &BTreeMap<String, String>) {
+	let end = args.iter().position(|a| a == "--").unwrap_or(args.len());
+	for i in 1..end {
+		if let Some(long) = arg.get_long() {
+			let prefix = format!("--{long}=");
+			if let Some(value) = args[i].strip_prefix(&prefix).and_then(|v| aliases.get(v)) {
+				args[i] = format!("{prefix}{value}");
+				continue;
+			}
+		}
+		if i + 1 < end && is_flag(&args[i], arg) {
+			if let Some(value) = aliases.get(&args[i + 1]) {
+				args[i + 1] = value.clone();
+			}
+		}
+	}
+}
+
+// Whether an argument is specified, via either its long or short name.
+fn is_specified(args: &[String], arg: &Arg) -> bool {
+	args.iter().take_while(|a| *a != "--").any(|a| {
+		is_flag(a, arg) || arg.get_long().is_some_and(|l| a.starts_with(&format!("--{l}=")))
+	})
+}
+
+// Whether a command line argument is the flag of an argument.
+fn is_flag(value: &str, arg: &Arg) -> bool {
+	arg.get_long().is_some_and(|l| value == format!("--{l}")) ||
+		arg.get_short().is_some_and(|s| value == format!("-{s}"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn args(args: &str) -> Vec<String> {
+		args.split_whitespace().map(String::from).collect()
+	}
+
+	fn command() -> Command {
+		let mut command = Command::new("pop")
+			.arg(Arg::new("env").long("env").global(true))
+			.subcommand(
+				Command::new("call").alias("c").subcommand(
+					Command::new("contract")
+						.arg(Arg::new("contract").short('c').long("contract"))
+						.arg(Arg::new("url").short('u').long("url"))
+						.arg(Arg::new("suri").short('s').long("suri"))
+						.arg(Arg::new("args").long("args").num_args(0..)),
+				),
+			)
+			.subcommand(
+				Command::new("coretime")
+					.arg(Arg::new("chain").long("chain"))
+					.arg(Arg::new("url").short('u').long("url").conflicts_with("chain"))
+					.arg(Arg::new("para").long("para")),
+			);
+		command.build();
+		command
+	}
+
+	fn environment() -> Environment {
+		Environment {
+			url: Some("wss://rpc.example.com".into()),
+			suri: Some("//Alice".into()),
+			para_id: Some(2000),
+			signers: BTreeMap::from([("deployer".into(), "//Bob".into())]),
+			contracts: BTreeMap::from([("flipper".into(), "5Flipper".into())]),
+		}
+	}
+
+	#[test]
+	fn adds_values_not_specified() {
+		assert_eq!(
+			apply_environment(args("pop --env dev call contract"), &environment(), &command()),
+			args("pop --env dev call contract --url wss://rpc.example.com --suri //Alice")
+		);
+		// Values are added before any trailing arguments.
+		assert_eq!(
+			apply_environment(args("pop c contract -- --args 1"), &environment(), &command()),
+			args("pop c contract --url wss://rpc.example.com --suri //Alice -- --args 1")
+		);
+		assert_eq!(
+			apply_environment(args("pop coretime"), &environment(), &command()),
+			args("pop coretime --url wss://rpc.example.com --para 2000")
+		);
+	}
+
+	#[test]
+	fn retains_specified_values() {
+		assert_eq!(
+			apply_environment(
+				args("pop call contract -u ws://localhost:9944 --suri=//Charlie"),
+				&environment(),
+				&command()
+			),
+			args("pop call contract -u ws://localhost:9944 --suri=//Charlie")
+		);
+		// Values are not added when a conflicting argument is specified.
+		assert_eq!(
+			apply_environment(
+				args("pop coretime --chain paseo --para 1000"),
+				&environment(),
+				&command()
+			),
+			args("pop coretime --chain paseo --para 1000")
+		);
+		// Values are not added to commands which do not define the argument.
+		assert_eq!(
+			apply_environment(args("pop call"), &environment(), &command()),
+			args("pop call")
+		);
+	}
+
+	#[test]
+	fn resolves_aliases() {
+		assert_eq!(
+			apply_environment(
+				args("pop call contract -c flipper --suri=deployer --url ws://localhost:9944"),
+				&environment(),
+				&command()
+			),
+			args("pop call contract -c 5Flipper --suri=//Bob --url ws://localhost:9944")
+		);
+		assert_eq!(
+			apply_environment(
+				args("pop call contract --contract 5Other -s deployer --url ws://localhost:9944"),
+				&environment(),
+				&command()
+			),
+			args("pop call contract --contract 5Other -s //Bob --url ws://localhost:9944")
+		);
+	}
+}
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::style::style;
+use console::Emoji;
+use pop_parachains::{Chain, ExplorerItem};
+
+/// Formats links to items within the block explorers of the chain at the specified endpoint, to
+/// be appended to the output of a command. Nothing is returned unless the endpoint is that of a
+/// chain known to the registry.
+///
+/// # Arguments
+/// * `url` - The endpoint of the chain.
+/// * `items` - The items to be linked to.
+pub(crate) fn explorer_links(url: &str, items: &[ExplorerItem]) -> String {
+	let Some(chain) = Chain::from_url(url) else {
+		return String::new();
+	};
+	items
+		.iter()
+		.flat_map(|item| chain.explorer_links(*item))
+		.map(|link| format!("\n{}", style(format!("{} {link}", Emoji("●", ">"))).dim()))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn explorer_links_works() {
+		let links = explorer_links(
+			"wss://rpc.polkadot.io/",
+			&[ExplorerItem::Block("0x12"), ExplorerItem::Extrinsic("0x34")],
+		);
+		let links: Vec<_> = links.lines().skip(1).collect();
+		assert_eq!(links.len(), 3);
+		assert!(links[0].contains("https://polkadot.subscan.io/block/0x12"));
+		assert!(links[1].contains(
+			"https://polkadot.js.org/apps/?rpc=wss://rpc.polkadot.io#/explorer/query/0x12"
+		));
+		assert!(links[2].contains("https://polkadot.subscan.io/extrinsic/0x34"));
+		// Links are only provided for known chains.
+		assert!(explorer_links("ws://localhost:9944/", &[ExplorerItem::Block("0x12")]).is_empty());
+	}
+}
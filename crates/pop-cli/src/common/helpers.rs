@@ -2,6 +2,7 @@
 
 /// A macro to facilitate the select multiple variant of an enum and store them inside a `Vec`.
 /// # Arguments
+/// * `$cli`: The cli used to prompt the user, implementing the `Cli` trait.
 /// * `$enum`: The enum type to be iterated over for the selection. This enum must implement
 ///   `IntoEnumIterator` and `EnumMessage` traits from the `strum` crate. Each variant is
 ///   responsible of its own messages.
@@ -28,8 +29,13 @@
 /// ```rust
 /// use strum::{IntoEnumIterator, EnumMessage};
 /// use strum_macros::{EnumIter, EnumMessage as EnumMessageDerive};
-/// use cliclack::{multiselect};
-/// use pop_common::multiselect_pick;
+/// use pop_cli::{
+///     cli::{
+///         traits::{Cli as _, MultiSelect as _},
+///         Cli,
+///     },
+///     multiselect_pick,
+/// };
 ///
 /// #[derive(Debug, EnumIter, EnumMessageDerive, Copy, Clone)]
 /// enum FieldlessEnum {
@@ -42,7 +48,8 @@
 /// }
 ///
 /// fn test_function() -> Result<(),std::io::Error>{
-///     let vec = multiselect_pick!(FieldlessEnum, "Hello, world!");
+///     let mut cli = Cli;
+///     let vec = multiselect_pick!(cli, FieldlessEnum, "Hello, world!");
 ///     Ok(())
 /// }
 /// ```
@@ -52,7 +59,7 @@
 /// This macro requires the following imports to function correctly:
 ///
 /// ```rust
-/// use cliclack::{multiselect};
+/// use pop_cli::cli::traits::*;
 /// use strum::{EnumMessage, IntoEnumIterator};
 /// ```
 ///
@@ -60,18 +67,19 @@
 /// Otherwise the compilation will fail.
 #[macro_export]
 macro_rules! multiselect_pick {
-	($enum: ty, $prompt_message: expr $(, $excluded_variants: expr)?) => {{
+	($cli: expr, $enum: ty, $prompt_message: expr $(, $excluded_variants: expr)?) => {{
         // Ensure the enum is 1-byte long. This is needed cause fieldless enums with > 256 elements
 		// will lead to unexpected behavior as the conversion to u8 for them isn't detected as wrong
 		// at compile time. Enums containing variants with fields will be catched at compile time.
 		// Weird but possible.
 		assert_eq!(std::mem::size_of::<$enum>(), 1);
-		let mut prompt = multiselect(format!(
-			"{} {}",
-			$prompt_message,
-			"Pick an option by pressing the spacebar. Press enter when you're done!"
-		))
-		.required(false);
+		let mut prompt = $cli
+			.multiselect(format!(
+				"{} {}",
+				$prompt_message,
+				"Pick an option by pressing the spacebar. Press enter when you're done!"
+			))
+			.required(false);
 
 		for variant in <$enum>::iter() {
             $(if $excluded_variants.contains(&variant){continue; })?
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli, cli::traits::Cli, EXIT_INTERRUPTED};
+use anyhow::Result;
+use pop_common::rollback::roll_back;
+#[cfg(feature = "parachain")]
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The number of operations currently handling interrupts themselves.
+static HANDLED: AtomicUsize = AtomicUsize::new(0);
+
+/// Indicates that interrupts are handled by the current command while held, such as to tear down
+/// a network gracefully, rather than by rolling back and exiting.
+pub(crate) struct Handled(());
+
+impl Handled {
+	pub(crate) fn new() -> Self {
+		HANDLED.fetch_add(1, Ordering::SeqCst);
+		Self(())
+	}
+}
+
+impl Drop for Handled {
+	fn drop(&mut self) {
+		HANDLED.fetch_sub(1, Ordering::SeqCst);
+	}
+}
+
+/// Handles interrupts in the background for the remainder of the process, by rolling back any
+/// incomplete operations and then exiting, unless they are currently [`Handled`] by the command.
+pub(crate) fn handle() {
+	tokio::spawn(async {
+		while tokio::signal::ctrl_c().await.is_ok() {
+			if HANDLED.load(Ordering::SeqCst) > 0 {
+				continue;
+			}
+			let _ = report(&mut cli::Cli, &roll_back());
+			std::process::exit(EXIT_INTERRUPTED);
+		}
+	});
+}
+
+/// Awaits a future, unless interrupted beforehand, in which case the future is dropped and `None`
+/// is returned.
+///
+/// # Arguments
+/// * `future` - The future to be awaited.
+#[cfg(feature = "parachain")]
+pub(crate) async fn unless_interrupted<F: Future>(future: F) -> Option<F::Output> {
+	tokio::select! {
+		output = future => Some(output),
+		_ = tokio::signal::ctrl_c() => None,
+	}
+}
+
+// Reports what was rolled back as a result of an interrupt.
+fn report(cli: &mut impl Cli, rolled_back: &[String]) -> Result<()> {
+	for description in rolled_back {
+		cli.warning(format!("↩️ Rolled back: {description}"))?;
+	}
+	cli.outro_cancel("🚫 Cancelled.")?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+
+	#[test]
+	fn report_works() -> Result<()> {
+		let mut cli = MockCli::new()
+			.expect_warning("↩️ Rolled back: removed /tmp/partial")
+			.expect_warning("↩️ Rolled back: terminated process 1")
+			.expect_outro_cancel("🚫 Cancelled.");
+		report(&mut cli, &["removed /tmp/partial".into(), "terminated process 1".into()])?;
+		cli.verify()
+	}
+
+	#[tokio::test]
+	#[cfg(feature = "parachain")]
+	async fn unless_interrupted_works() {
+		assert_eq!(unless_interrupted(async { 1 }).await, Some(1));
+	}
+}
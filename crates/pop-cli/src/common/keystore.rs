@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::cli::traits::*;
+use anyhow::Result;
+use pop_common::keystore::{is_unlocked, keystore_account, Keystore};
+
+/// Unlocks any keystore accounts referred to by the command line arguments, e.g. via
+/// `--suri keystore:savings`, prompting for their passwords.
+///
+/// # Arguments
+/// * `keystore` - The keystore.
+/// * `args` - The command line arguments.
+/// * `cli` - The cli.
+pub(crate) fn unlock_accounts(
+	keystore: &Keystore,
+	args: &[String],
+	cli: &mut impl Cli,
+) -> Result<()> {
+	for arg in args.iter().take_while(|a| *a != "--") {
+		// Values may be specified via `--suri=keystore:savings`.
+		let value = arg.split_once('=').map_or(arg.as_str(), |(_, value)| value);
+		unlock(keystore, value, cli)?;
+	}
+	Ok(())
+}
+
+/// Unlocks the keystore account referred to by a secret URI, if not already unlocked, prompting
+/// for its password.
+///
+/// # Arguments
+/// * `keystore` - The keystore.
+/// * `suri` - The secret URI, e.g. `keystore:savings`.
+/// * `cli` - The cli.
+pub(crate) fn unlock(keystore: &Keystore, suri: &str, cli: &mut impl Cli) -> Result<()> {
+	let Some(name) = keystore_account(suri) else {
+		return Ok(());
+	};
+	if is_unlocked(name) {
+		return Ok(());
+	}
+	let password = cli.password(format!("Password of {suri}:")).interact()?;
+	keystore.unlock(name, &password)?;
+	Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use pop_common::create_signer;
+
+	// The account of Alice, as exported by polkadot.js with the password `whoisalice`.
+	pub(crate) const ALICE: &str = r#"{
+		"encoded": "DumgApKCTqoCty1OZW/8WS+sgo6RdpHhCwAkA2IoDBMAgAAAAQAAAAgAAAB6IG/q24EeVf0JqWqcBd5m2tKq5BlyY84IQ8oamLn9DZe9Ouhgunr7i36J1XxUnTI801axqL/ym1gil0U8440Qvj0lFVKwGuxq38zuifgoj0B3Yru0CI6QKEvQPU5xxj4MpyxdSxP+2PnTzYao0HDH0fulaGvlAYXfqtU89xrx2/z9z7IjSwS3oDFPXRQ9kAdDebtyCVreZ9Otw9v3",
+		"encoding": { "content": ["pkcs8", "sr25519"], "type": ["scrypt", "xsalsa20-poly1305"], "version": "3" },
+		"address": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+		"meta": { "genesisHash": "", "name": "Alice", "whenCreated": 1718265838755 }
+	}"#;
+
+	#[test]
+	fn unlock_accounts_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let keystore = Keystore::new(temp_dir.path());
+		keystore.import(ALICE, "whoisalice", "secret", Some("unlocked"))?;
+		let args: Vec<_> = ["pop", "call", "chain", "--suri=keystore:unlocked", "--", "keystore:x"]
+			.map(String::from)
+			.into();
+		let mut cli = MockCli::new().expect_password("Password of keystore:unlocked:", "secret");
+		unlock_accounts(&keystore, &args, &mut cli)?;
+		cli.verify()?;
+		assert_eq!(
+			create_signer("keystore:unlocked")?.public_key().to_account_id(),
+			create_signer("//Alice")?.public_key().to_account_id()
+		);
+		// Unlocked accounts are not prompted for again.
+		let mut cli = MockCli::new();
+		unlock(&keystore, "keystore:unlocked", &mut cli)?;
+		unlock(&keystore, "//Alice", &mut cli)?;
+		cli.verify()
+	}
+}
@@ -1,7 +1,23 @@
 // SPDX-License-Identifier: GPL-3.0
 
+pub mod alias;
+pub mod balances;
+pub mod binary;
 pub mod builds;
+#[cfg(feature = "parachain")]
+pub mod chains;
 #[cfg(feature = "contract")]
 pub mod contracts;
+pub mod env;
+#[cfg(feature = "parachain")]
+pub mod explorer;
 pub mod helpers;
+pub mod interrupt;
+pub mod keystore;
+pub mod network;
+pub mod secrets;
+#[cfg(feature = "parachain")]
+pub mod signer;
+#[cfg(feature = "parachain")]
+pub mod try_runtime;
 pub mod wallet;
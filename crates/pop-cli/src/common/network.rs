@@ -0,0 +1,287 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use anyhow::Result;
+use std::{fmt, future::Future, sync::RwLock, time::Duration};
+
+/// The settings applied to network operations.
+static SETTINGS: RwLock<Settings> = RwLock::new(DEFAULT_SETTINGS);
+/// The settings applied unless otherwise specified, matching the defaults of the command line.
+const DEFAULT_SETTINGS: Settings = Settings { timeout: None, retries: 0, retry_delay: RETRY_DELAY };
+
+/// The default delay before retrying a failed network operation.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+/// The maximum delay between attempts of a failed network operation, however many times it has
+/// been retried.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Settings applied to network operations, such as RPC connections, extrinsic submission and
+/// binary downloads.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Settings {
+	/// The maximum duration of each attempt of an operation, if any.
+	pub(crate) timeout: Option<Duration>,
+	/// The number of times a failed operation is retried, if it can safely be retried.
+	pub(crate) retries: u8,
+	/// The delay before the first retry of a failed operation, which doubles with each
+	/// subsequent retry.
+	pub(crate) retry_delay: Duration,
+}
+
+impl Default for Settings {
+	fn default() -> Self {
+		DEFAULT_SETTINGS
+	}
+}
+
+/// Configures the settings applied to any subsequent network operations.
+///
+/// # Arguments
+/// * `settings` - The settings to be applied.
+pub(crate) fn configure(settings: Settings) {
+	store(&SETTINGS, settings)
+}
+
+/// The settings currently applied to network operations.
+pub(crate) fn settings() -> Settings {
+	load(&SETTINGS)
+}
+
+// Stores the settings within the specified lock.
+fn store(lock: &RwLock<Settings>, settings: Settings) {
+	if let Ok(mut current) = lock.write() {
+		*current = settings;
+	}
+}
+
+// Loads the settings from the specified lock.
+fn load(lock: &RwLock<Settings>) -> Settings {
+	lock.read().map(|s| *s).unwrap_or_default()
+}
+
+/// An error indicating that an operation did not complete within its timeout.
+#[derive(Debug, PartialEq)]
+pub struct TimedOut {
+	/// A description of the operation.
+	pub(crate) operation: String,
+	/// The timeout of the operation.
+	pub(crate) timeout: Duration,
+}
+
+impl fmt::Display for TimedOut {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Timed out after {}s whilst {}", self.timeout.as_secs(), self.operation)
+	}
+}
+
+impl std::error::Error for TimedOut {}
+
+/// An error which is not transient (e.g. a call reverting), so that the failed operation is not
+/// retried. The underlying error is returned once the operation fails.
+#[derive(Debug)]
+pub(crate) struct Permanent(pub(crate) anyhow::Error);
+
+impl fmt::Display for Permanent {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl std::error::Error for Permanent {}
+
+/// Propagates the error should it be the result of an operation timing out, ensuring the
+/// corresponding exit code, otherwise treating the failure as having been handled.
+///
+/// # Arguments
+/// * `error` - The error resulting from the operation.
+pub(crate) fn propagate_timeout(error: anyhow::Error) -> Result<()> {
+	match error.is::<TimedOut>() {
+		true => Err(error),
+		false => Ok(()),
+	}
+}
+
+/// Performs a network operation which cannot safely be retried (e.g. submitting an extrinsic),
+/// failing should it not complete within the configured timeout.
+///
+/// # Arguments
+/// * `operation` - A description of the operation, e.g. "submitting the extrinsic".
+/// * `future` - The operation.
+pub(crate) async fn once<T, E: Into<anyhow::Error>>(
+	operation: &str,
+	future: impl Future<Output = Result<T, E>>,
+) -> Result<T> {
+	settings().attempt(operation, future).await
+}
+
+/// Performs a network operation which can safely be retried (e.g. connecting to a node or
+/// downloading a binary), retrying any failure up to the configured number of retries with an
+/// exponential backoff, with each attempt subject to the configured timeout. Any [`Permanent`]
+/// failure is not retried.
+///
+/// # Arguments
+/// * `operation` - A description of the operation, e.g. "connecting to the node".
+/// * `f` - A function providing each attempt of the operation.
+pub(crate) async fn retry<T, E: Into<anyhow::Error>, F: Future<Output = Result<T, E>>>(
+	operation: &str,
+	f: impl FnMut() -> F,
+) -> Result<T> {
+	settings().retry(operation, f).await
+}
+
+impl Settings {
+	async fn attempt<T, E: Into<anyhow::Error>>(
+		&self,
+		operation: &str,
+		future: impl Future<Output = Result<T, E>>,
+	) -> Result<T> {
+		let Some(timeout) = self.timeout else {
+			return future.await.map_err(Into::into);
+		};
+		match tokio::time::timeout(timeout, future).await {
+			Ok(result) => result.map_err(Into::into),
+			Err(_) => Err(TimedOut { operation: operation.to_string(), timeout }.into()),
+		}
+	}
+
+	async fn retry<T, E: Into<anyhow::Error>, F: Future<Output = Result<T, E>>>(
+		&self,
+		operation: &str,
+		mut f: impl FnMut() -> F,
+	) -> Result<T> {
+		let mut attempt = 0;
+		loop {
+			match self.attempt(operation, f()).await {
+				Ok(result) => return Ok(result),
+				Err(e) if attempt < self.retries && !e.is::<Permanent>() => {
+					tokio::time::sleep(self.delay(attempt)).await;
+					attempt += 1;
+				},
+				Err(e) => return Err(e.downcast::<Permanent>().map(|e| e.0).unwrap_or_else(|e| e)),
+			}
+		}
+	}
+
+	// The delay before the specified retry of a failed operation, starting from zero.
+	fn delay(&self, retry: u8) -> Duration {
+		self.retry_delay
+			.saturating_mul(2u32.saturating_pow(retry.into()))
+			.min(MAX_RETRY_DELAY)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::anyhow;
+	use std::sync::atomic::{AtomicU8, Ordering};
+
+	#[tokio::test]
+	async fn attempt_times_out() {
+		let settings = Settings { timeout: Some(Duration::from_millis(10)), ..Default::default() };
+		let error = settings
+			.attempt("connecting to the node", async {
+				tokio::time::sleep(Duration::from_secs(1)).await;
+				Ok::<_, anyhow::Error>(())
+			})
+			.await
+			.unwrap_err();
+		assert_eq!(
+			error.downcast_ref::<TimedOut>(),
+			Some(&TimedOut {
+				operation: "connecting to the node".into(),
+				timeout: Duration::from_millis(10)
+			})
+		);
+		assert_eq!(error.to_string(), "Timed out after 0s whilst connecting to the node");
+	}
+
+	#[tokio::test]
+	async fn attempt_without_timeout_works() -> Result<()> {
+		assert_eq!(
+			Settings::default().attempt("test", async { Ok::<_, anyhow::Error>(1) }).await?,
+			1
+		);
+		assert!(Settings::default()
+			.attempt("test", async { Err::<(), _>(anyhow!("failed")) })
+			.await
+			.is_err());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn retry_works() -> Result<()> {
+		let attempts = AtomicU8::new(0);
+		let operation = || async {
+			match attempts.fetch_add(1, Ordering::Relaxed) {
+				n if n < 2 => Err(anyhow!("failed")),
+				n => Ok(n),
+			}
+		};
+		assert!(Settings::default().retry("test", operation).await.is_err());
+		let settings = Settings { retries: 1, retry_delay: Duration::ZERO, ..Default::default() };
+		assert_eq!(settings.retry("test", operation).await?, 2);
+		assert_eq!(attempts.load(Ordering::Relaxed), 3);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn retry_skips_permanent_failures() {
+		let attempts = AtomicU8::new(0);
+		let operation = || async {
+			attempts.fetch_add(1, Ordering::Relaxed);
+			Err::<(), _>(Permanent(anyhow!("reverted")))
+		};
+		let settings = Settings { retries: 3, ..Default::default() };
+		let error = settings.retry("test", operation).await.unwrap_err();
+		assert!(!error.is::<Permanent>());
+		assert_eq!(error.to_string(), "reverted");
+		assert_eq!(attempts.load(Ordering::Relaxed), 1);
+	}
+
+	#[test]
+	fn delay_backs_off_exponentially() {
+		let settings = Settings { retry_delay: Duration::from_secs(1), ..Default::default() };
+		assert_eq!(settings.delay(0), Duration::from_secs(1));
+		assert_eq!(settings.delay(1), Duration::from_secs(2));
+		assert_eq!(settings.delay(3), Duration::from_secs(8));
+		// The delay is capped.
+		assert_eq!(settings.delay(5), MAX_RETRY_DELAY);
+		assert_eq!(settings.delay(u8::MAX), MAX_RETRY_DELAY);
+	}
+
+	#[test]
+	fn propagate_timeout_works() {
+		let timed_out = TimedOut { operation: "test".into(), timeout: Duration::from_secs(1) };
+		assert!(propagate_timeout(timed_out.into()).is_err());
+		assert!(propagate_timeout(anyhow!("failed")).is_ok());
+	}
+
+	#[test]
+	fn configure_works() {
+		// A separate lock is used, so that the settings of other tests are unaffected.
+		let lock = RwLock::new(DEFAULT_SETTINGS);
+		assert_eq!(load(&lock).retry_delay, RETRY_DELAY);
+		let settings = Settings {
+			timeout: Some(Duration::from_secs(30)),
+			retries: 3,
+			retry_delay: Duration::from_secs(2),
+		};
+		store(&lock, settings);
+		assert_eq!(load(&lock), settings);
+	}
+
+	#[test]
+	fn default_settings_match_command_line() -> Result<()> {
+		use clap::Parser;
+		let pop = crate::Cli::try_parse_from(["pop", "clean", "cache"])?;
+		assert_eq!(
+			Settings::default(),
+			Settings {
+				timeout: pop.timeout.map(Duration::from_secs),
+				retries: pop.retries,
+				retry_delay: Duration::from_secs(pop.retry_delay),
+			}
+		);
+		Ok(())
+	}
+}
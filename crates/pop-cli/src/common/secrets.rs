@@ -0,0 +1,247 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::keystore};
+use anyhow::{anyhow, Result};
+use pop_common::{
+	keystore::{keystore_account, Keystore},
+	secrets::{is_loaded, secret_name, Secrets, SessionKey, SECRET_PREFIX, SESSION_VARIABLE},
+};
+
+/// The placeholder displayed in place of a secret URI.
+const MASKED_SURI: &str = "<SURI>";
+
+/// Prompts for the secret URI of a signer without echoing it, using the specified default should
+/// none be entered, and prepares it for signing via [`prepare_suri`]. Returns the secret URI with
+/// which to sign.
+///
+/// # Arguments
+/// * `prompt` - The prompt, e.g. "Signer of the extrinsic".
+/// * `default` - The secret URI of the development account used by default.
+/// * `cli` - The cli.
+pub(crate) fn prompt_suri(prompt: &str, default: &str, cli: &mut impl Cli) -> Result<String> {
+	let suri = cli
+		.password(format!("{prompt} [default: {default}]:"))
+		.allow_empty()
+		.interact()?;
+	match suri.trim() {
+		"" => Ok(default.to_string()),
+		suri => prepare_suri(suri, cli),
+	}
+}
+
+/// Prepares a secret URI entered interactively for signing: unlocking the keystore account or
+/// loading the stored secret it refers to, or otherwise offering to store it. Returns the secret
+/// URI with which to sign, which refers to the stored secret once stored.
+///
+/// # Arguments
+/// * `suri` - The secret URI.
+/// * `cli` - The cli.
+pub(crate) fn prepare_suri(suri: &str, cli: &mut impl Cli) -> Result<String> {
+	if keystore_account(suri).is_some() {
+		keystore::unlock(&Keystore::open()?, suri, cli)?;
+		return Ok(suri.to_string());
+	}
+	// Development accounts, e.g. `//Alice`, are not secret.
+	if suri.starts_with("//") {
+		return Ok(suri.to_string());
+	}
+	let secrets = Secrets::open()?;
+	if secret_name(suri).is_some() {
+		load(&secrets, suri, cli)?;
+		return Ok(suri.to_string());
+	}
+	offer_to_store(&secrets, suri, cli)
+}
+
+/// A secret URI as displayed (e.g. within a command reproducing a call), masking it unless it is
+/// a development account (e.g. `//Alice`) or refers to a stored secret or keystore account.
+///
+/// # Arguments
+/// * `suri` - The secret URI.
+pub(crate) fn mask_suri(suri: &str) -> &str {
+	let development = suri.starts_with("//") && !suri.contains("///");
+	match development || secret_name(suri).is_some() || keystore_account(suri).is_some() {
+		true => suri,
+		false => MASKED_SURI,
+	}
+}
+
+/// Loads any stored secrets referred to by the command line arguments, e.g. via
+/// `--suri secret:savings`, prompting for the master password unless a session is unlocked.
+///
+/// # Arguments
+/// * `secrets` - The stored secrets.
+/// * `args` - The command line arguments.
+/// * `cli` - The cli.
+pub(crate) fn load_secrets(secrets: &Secrets, args: &[String], cli: &mut impl Cli) -> Result<()> {
+	let names: Vec<_> = args
+		.iter()
+		.take_while(|a| *a != "--")
+		// Values may be specified via `--suri=secret:savings`.
+		.map(|a| a.split_once('=').map_or(a.as_str(), |(_, value)| value))
+		.filter_map(secret_name)
+		.filter(|name| !is_loaded(name))
+		.collect();
+	if names.is_empty() {
+		return Ok(());
+	}
+	let key = session(secrets, cli)?;
+	for name in names {
+		secrets.load(&key, name)?;
+	}
+	Ok(())
+}
+
+/// Loads the stored secret referred to by a secret URI, if not already loaded.
+///
+/// # Arguments
+/// * `secrets` - The stored secrets.
+/// * `suri` - The secret URI, e.g. `secret:savings`.
+/// * `cli` - The cli.
+pub(crate) fn load(secrets: &Secrets, suri: &str, cli: &mut impl Cli) -> Result<()> {
+	match secret_name(suri) {
+		Some(name) if !is_loaded(name) => Ok(secrets.load(&session(secrets, cli)?, name)?),
+		_ => Ok(()),
+	}
+}
+
+/// Offers to store a secret URI, encrypted with the master password, so that it does not need to
+/// be entered again. Returns the secret URI with which to sign: a reference to the stored secret
+/// (e.g. `secret:savings`) if stored, otherwise the secret URI itself.
+///
+/// # Arguments
+/// * `secrets` - The stored secrets.
+/// * `suri` - The secret URI.
+/// * `cli` - The cli.
+pub(crate) fn offer_to_store(secrets: &Secrets, suri: &str, cli: &mut impl Cli) -> Result<String> {
+	if !cli
+		.confirm("Would you like to store the secret URI, encrypted, to sign with it again?")
+		.initial_value(false)
+		.interact()?
+	{
+		return Ok(suri.to_string());
+	}
+	let name = cli.input("Name of the secret:").interact()?;
+	let key = session(secrets, cli)?;
+	secrets.store(&key, &name, suri)?;
+	secrets.load(&key, &name)?;
+	cli.info(format!(
+		"Stored the secret URI, to be signed with via `--suri {SECRET_PREFIX}{name}`. Unlock the stored secrets once per shell session via `eval \"$(pop keystore unlock)\"`."
+	))?;
+	Ok(format!("{SECRET_PREFIX}{name}"))
+}
+
+/// The key of the stored secrets: resumed from the session of the shell if unlocked, otherwise
+/// derived from the master password, which is set if no secrets have been stored.
+///
+/// # Arguments
+/// * `secrets` - The stored secrets.
+/// * `cli` - The cli.
+pub(crate) fn session(secrets: &Secrets, cli: &mut impl Cli) -> Result<SessionKey> {
+	if !secrets.exists() {
+		let password = cli.password("Master password with which to encrypt secrets:").interact()?;
+		if cli.password("Confirm the master password:").interact()? != password {
+			return Err(anyhow!("The master passwords do not match"));
+		}
+		return Ok(secrets.create(&password)?);
+	}
+	if let Ok(token) = std::env::var(SESSION_VARIABLE) {
+		match secrets.resume(&token) {
+			Ok(key) => return Ok(key),
+			Err(e) => cli.warning(format!("{e}"))?,
+		}
+	}
+	let password = cli.password("Master password:").interact()?;
+	Ok(secrets.unlock(&password)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use pop_common::create_signer;
+
+	#[test]
+	fn offer_to_store_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let secrets = Secrets::new(&temp_dir.path().join("secrets.json"));
+		let message = "Would you like to store the secret URI, encrypted, to sign with it again?";
+		let mut cli = MockCli::new().expect_confirm(message, false);
+		assert_eq!(offer_to_store(&secrets, "//Dave", &mut cli)?, "//Dave");
+		cli.verify()?;
+		assert!(!secrets.exists());
+
+		let mut cli = MockCli::new()
+			.expect_confirm(message, true)
+			.expect_input("Name of the secret:", "dave".into())
+			.expect_password("Master password with which to encrypt secrets:", "master")
+			.expect_password("Confirm the master password:", "master")
+			.expect_info("Stored the secret URI, to be signed with via `--suri secret:dave`. Unlock the stored secrets once per shell session via `eval \"$(pop keystore unlock)\"`.");
+		// The stored secret is loaded, so that it can be signed with via its reference.
+		assert_eq!(offer_to_store(&secrets, "//Dave", &mut cli)?, "secret:dave");
+		cli.verify()?;
+		assert_eq!(secrets.names()?, vec!["dave"]);
+		assert!(is_loaded("dave"));
+		assert_eq!(
+			create_signer("secret:dave")?.public_key().to_account_id(),
+			create_signer("//Dave")?.public_key().to_account_id()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn load_secrets_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let secrets = Secrets::new(&temp_dir.path().join("secrets.json"));
+		secrets.store(&secrets.create("master")?, "eve", "//Eve")?;
+		let mut cli = MockCli::new().expect_password("Master password:", "master");
+		let args: Vec<_> = ["pop", "call", "chain", "--suri=secret:eve"].map(String::from).into();
+		load_secrets(&secrets, &args, &mut cli)?;
+		cli.verify()?;
+		assert_eq!(
+			create_signer("secret:eve")?.public_key().to_account_id(),
+			create_signer("//Eve")?.public_key().to_account_id()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn prompt_suri_works() -> Result<()> {
+		let prompt = "Signer of the extrinsic [default: //Alice]:";
+		let mut cli = MockCli::new().expect_password(prompt, "");
+		assert_eq!(prompt_suri("Signer of the extrinsic", "//Alice", &mut cli)?, "//Alice");
+		cli.verify()?;
+		let mut cli = MockCli::new().expect_password(prompt, "//Bob");
+		assert_eq!(prompt_suri("Signer of the extrinsic", "//Alice", &mut cli)?, "//Bob");
+		cli.verify()
+	}
+
+	#[test]
+	fn mask_suri_works() {
+		for suri in ["//Alice", "secret:savings", "keystore:savings"] {
+			assert_eq!(mask_suri(suri), suri);
+		}
+		for suri in [
+			"//Alice///password",
+			"bottom drive obey lake curtain smoke basket hold race lonely fit walk",
+			"0xe5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a",
+		] {
+			assert_eq!(mask_suri(suri), MASKED_SURI);
+		}
+	}
+
+	#[test]
+	fn session_fails_when_passwords_differ() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let secrets = Secrets::new(&temp_dir.path().join("secrets.json"));
+		let mut cli = MockCli::new()
+			.expect_password("Master password with which to encrypt secrets:", "master")
+			.expect_password("Confirm the master password:", "typo");
+		assert!(matches!(
+			session(&secrets, &mut cli),
+			Err(message) if message.to_string() == "The master passwords do not match"
+		));
+		assert!(!secrets.exists());
+		cli.verify()
+	}
+}
@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	cli::traits::*,
+	common::{explorer::explorer_links, network, secrets::prompt_suri},
+};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use pop_parachains::{
+	encode_call_data, sign_and_submit_extrinsic_progress, DynamicPayload, ExplorerItem,
+	ExtrinsicEvents, OnlineClient, SubstrateConfig,
+};
+use url::Url;
+
+const DEFAULT_URI: &str = "//Alice";
+
+/// Arguments for signing and submitting extrinsics.
+#[derive(Args, Clone, Default)]
+pub(crate) struct SignerArgs {
+	/// Secret key URI for the account signing the extrinsics, `keystore:<name>` for an account of
+	/// the keystore or `secret:<name>` for a stored secret.
+	#[arg(short, long)]
+	pub(crate) suri: Option<String>,
+	/// Automatically signs and submits the extrinsics without prompting for confirmation.
+	#[arg(short = 'y', long)]
+	pub(crate) skip_confirm: bool,
+}
+
+impl SignerArgs {
+	/// The secret key URI of the signer, prompting the user if none specified. The signer is only
+	/// prompted for once, as several calls may be submitted.
+	///
+	/// # Arguments
+	/// * `cli` - The cli.
+	pub(crate) fn suri(&mut self, cli: &mut impl Cli) -> Result<String> {
+		if let Some(suri) = &self.suri {
+			return Ok(suri.clone());
+		}
+		let suri = prompt_suri("Signer of the extrinsic", DEFAULT_URI, cli)?;
+		self.suri = Some(suri.clone());
+		Ok(suri)
+	}
+
+	/// Signs and submits a call, after confirming with the user. Returns the events of the
+	/// extrinsic once finalized, or `None` if it was not submitted.
+	///
+	/// # Arguments
+	/// * `client` - The client used to interact with the chain.
+	/// * `url` - The endpoint of the chain, used to link to the extrinsic within any explorers.
+	/// * `xt` - The call to be submitted.
+	/// * `action` - A description of what the call does, e.g. `vote`.
+	/// * `cli` - The cli.
+	pub(crate) async fn submit(
+		&mut self,
+		client: &OnlineClient<SubstrateConfig>,
+		url: &Url,
+		xt: DynamicPayload,
+		action: &str,
+		cli: &mut impl Cli,
+	) -> Result<Option<ExtrinsicEvents<SubstrateConfig>>> {
+		cli.info(format!("Encoded call data: {}", encode_call_data(client, &xt)?))?;
+		let suri = self.suri(cli)?;
+		if !self.skip_confirm &&
+			!cli.confirm(format!("Do you want to {action}?"))
+				.initial_value(true)
+				.interact()?
+		{
+			cli.outro_cancel("Call was not submitted.")?;
+			return Ok(None);
+		}
+		let spinner = cli.spinner();
+		spinner.start("Signing and submitting the extrinsic and then waiting for finalization, please be patient...");
+		let events = network::once("submitting the extrinsic", async {
+			sign_and_submit_extrinsic_progress(client, xt, &suri, &Default::default(), |status| {
+				spinner.set_message(status)
+			})
+			.await
+			.map_err(|err| anyhow!("{err}"))
+		})
+		.await?;
+		let hash = format!("{:?}", events.extrinsic_hash());
+		spinner.stop(format!(
+			"Extrinsic submitted with hash: {hash}{}",
+			explorer_links(url.as_str(), &[ExplorerItem::Extrinsic(&hash)])
+		));
+		Ok(Some(events))
+	}
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{cli::traits::*, common::binary::check_binary_and_prompt};
+use pop_parachains::try_runtime_generator;
+use std::path::{Path, PathBuf};
+
+/// Checks the status of the `try-runtime` binary, sourcing it if necessary.
+///
+/// # Arguments
+/// * `cli`: Command line interface.
+/// * `cache_path`: The cache directory path.
+/// * `skip_confirm`: A boolean indicating whether to skip confirmation prompts.
+pub async fn check_try_runtime_and_prompt(
+	cli: &mut impl Cli,
+	cache_path: &Path,
+	skip_confirm: bool,
+) -> anyhow::Result<PathBuf> {
+	check_binary_and_prompt(cli, try_runtime_generator(PathBuf::from(cache_path)), skip_confirm)
+		.await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::MockCli;
+	use std::fs;
+
+	#[tokio::test]
+	async fn check_try_runtime_and_prompt_works() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let binary = temp_dir.path().join("try-runtime");
+		fs::write(&binary, "")?;
+		let mut cli = MockCli::new();
+		assert_eq!(check_try_runtime_and_prompt(&mut cli, temp_dir.path(), false).await?, binary);
+		cli.verify()
+	}
+
+	#[tokio::test]
+	async fn check_try_runtime_and_prompt_fails_when_declined() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let mut cli = MockCli::new()
+			.expect_warning("⚠️ The try-runtime binary is not found.")
+			.expect_confirm("📦 Would you like to source it automatically now?", false);
+		assert!(check_try_runtime_and_prompt(&mut cli, temp_dir.path(), false).await.is_err());
+		cli.verify()
+	}
+}
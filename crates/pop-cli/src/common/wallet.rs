@@ -1,10 +1,9 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use crate::{
-	cli::traits::Cli,
+	cli::traits::*,
 	wallet_integration::{FrontendFromString, TransactionData, WalletIntegrationManager},
 };
-use cliclack::{log, spinner};
 
 /// The prompt to ask the user if they want to use the wallet for signing.
 pub const USE_WALLET_PROMPT: &str = "Do you want to use your browser wallet to sign the extrinsic? (Selecting 'No' will prompt you to manually enter the secret key URI for signing, e.g., '//Alice')";
@@ -14,18 +13,23 @@ pub const USE_WALLET_PROMPT: &str = "Do you want to use your browser wallet to s
 /// # Arguments
 /// * `call_data` - The call data to be signed.
 /// * `url` - Chain rpc.
+/// * `cli` - The CLI instance.
 /// # Returns
 /// * The signed payload, if it exists.
-pub async fn request_signature(call_data: Vec<u8>, rpc: String) -> anyhow::Result<Option<String>> {
+pub async fn request_signature(
+	call_data: Vec<u8>,
+	rpc: String,
+	cli: &mut impl Cli,
+) -> anyhow::Result<Option<String>> {
 	let ui = FrontendFromString::new(include_str!("../assets/index.html").to_string());
 
 	let transaction_data = TransactionData::new(rpc, call_data);
 	// Starts server with port 9090.
 	let mut wallet = WalletIntegrationManager::new(ui, transaction_data, Some(9090));
 	let url = format!("http://{}", &wallet.server_url);
-	log::step(format!("Wallet signing portal started at {url}."))?;
+	cli.info(format!("Wallet signing portal started at {url}."))?;
 
-	let spinner = spinner();
+	let spinner = cli.spinner();
 	spinner.start(format!("Opening browser to {url}"));
 	if let Err(e) = open::that(url) {
 		spinner.error(format!("Failed to launch browser. Please open link manually. {e}"));
@@ -35,7 +39,7 @@ pub async fn request_signature(call_data: Vec<u8>, rpc: String) -> anyhow::Resul
 	loop {
 		// Display error, if any.
 		if let Some(error) = wallet.take_error().await {
-			log::error(format!("Signing portal error: {error}"))?;
+			cli.error(format!("Signing portal error: {error}"))?;
 		}
 
 		let state = wallet.state.lock().await;
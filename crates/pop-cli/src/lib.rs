@@ -0,0 +1,292 @@
+// SPDX-License-Identifier: GPL-3.0
+
+//! Pop CLI as a library, allowing other tools to embed the functionality of `pop`
+//! programmatically.
+//!
+//! The command orchestration (e.g. generating, building, deploying and calling) is exposed via
+//! [`Cli`], which is parsed from command line arguments, and [`commands::Command`]. All
+//! interaction with the user occurs via the [`cli::traits::Cli`] trait, allowing an alternative to
+//! the terminal-based [`cli::Cli`] to be provided (e.g. an IDE integration).
+//!
+//! ```no_run
+//! use clap::Parser;
+//!
+//! # async fn build() -> anyhow::Result<()> {
+//! let pop = pop_cli::Cli::try_parse_from(["pop", "build", "--release"])?;
+//! pop.execute(&mut pop_cli::cli::Cli).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(not(any(feature = "contract", feature = "parachain")))]
+compile_error!("feature \"contract\" or feature \"parachain\" must be enabled");
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use commands::*;
+use common::network::{self, TimedOut};
+use pop_common::{
+	config::{config_file_path, Config, ProjectConfig, PROJECT_CONFIG_FILE_NAME},
+	keystore::{Keystore, KEYSTORE_PREFIX},
+	secrets::{Secrets, SECRET_PREFIX},
+};
+use serde_json::Value;
+use std::{env::current_dir, fs::create_dir_all, iter::once, path::PathBuf, time::Duration};
+
+/// The command line interface, via which all interaction with the user occurs.
+pub mod cli;
+/// The commands, orchestrating the functionality of `pop`.
+#[cfg(any(feature = "parachain", feature = "contract"))]
+pub mod commands;
+mod common;
+mod progress;
+mod style;
+mod wallet_integration;
+
+/// The exit code when a command fails.
+pub const EXIT_FAILURE: i32 = 1;
+/// The exit code when a network operation times out, distinct from any other failure.
+pub const EXIT_TIMEOUT: i32 = 124;
+/// The exit code when a command is interrupted (e.g. via ctrl-c).
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+/// The command line arguments of `pop`.
+#[derive(Parser)]
+#[command(author, version, about, styles=style::get_styles())]
+pub struct Cli {
+	#[command(subcommand)]
+	pub command: Command,
+	/// Suppress spinners, intros and other decorative output, printing only results and errors.
+	#[arg(short, long, global = true)]
+	pub quiet: bool,
+	/// Emit structured progress events as JSON lines, to the specified file or otherwise stderr.
+	#[arg(long, global = true, value_name = "FILE", num_args = 0..=1, require_equals = true)]
+	pub progress_json: Option<Option<PathBuf>>,
+	/// The maximum time to wait for network operations, such as RPC connections, extrinsic
+	/// submission and binary downloads, in seconds.
+	#[arg(long, global = true, value_name = "SECS")]
+	pub timeout: Option<u64>,
+	/// The number of times failed RPC connections and binary downloads are retried.
+	#[arg(long, global = true, default_value_t = 0)]
+	pub retries: u8,
+	/// The delay before the first retry of a failed network operation, in seconds, which doubles
+	/// with each subsequent retry.
+	#[arg(long, global = true, value_name = "SECS", default_value_t = 1)]
+	pub retry_delay: u64,
+	/// The environment, as defined within the `[env]` section of `pop.toml`, against which
+	/// endpoints, signers, contract addresses and parachain identifiers are resolved.
+	#[arg(long, global = true, value_name = "NAME")]
+	pub env: Option<String>,
+}
+
+impl Cli {
+	/// Executes the command, returning any data observed for telemetry.
+	///
+	/// # Arguments
+	/// * `cli` - The command line interface used for any interaction with the user.
+	pub async fn execute(self, cli: &mut impl cli::traits::Cli) -> Result<Value> {
+		cli::set_quiet(self.quiet);
+		network::configure(network::Settings {
+			timeout: self.timeout.map(Duration::from_secs),
+			retries: self.retries,
+			retry_delay: Duration::from_secs(self.retry_delay),
+		});
+		if let Some(path) = &self.progress_json {
+			progress::enable(path.as_deref())?;
+		}
+		self.command.execute(cli).await
+	}
+}
+
+/// Determines the exit code for an error, allowing automation to distinguish a timeout from any
+/// other failure.
+///
+/// # Arguments
+/// * `error` - The error resulting from executing a command.
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+	match error.chain().any(|e| e.is::<TimedOut>()) {
+		true => EXIT_TIMEOUT,
+		false => EXIT_FAILURE,
+	}
+}
+
+/// Handles interrupts (e.g. via ctrl-c) for the remainder of the process, by rolling back any
+/// incomplete operations, such as partial downloads and spawned processes, before exiting with
+/// [`EXIT_INTERRUPTED`]. Must be called from within a Tokio runtime.
+pub fn handle_interrupts() {
+	common::interrupt::handle();
+}
+
+/// Determines the cache to be used.
+fn cache() -> Result<PathBuf> {
+	let cache_path = dirs::cache_dir()
+		.ok_or(anyhow!("the cache directory could not be determined"))?
+		.join("pop");
+	// Creates pop dir if needed
+	create_dir_all(cache_path.as_path())?;
+	Ok(cache_path)
+}
+
+/// Expands any user-defined command aliases, as specified within the `[alias]` section of the
+/// user configuration file.
+///
+/// # Arguments
+/// * `args` - The command line arguments, including the binary name.
+pub fn expand_aliases(args: Vec<String>) -> Result<Vec<String>> {
+	use clap::CommandFactory;
+	// The configuration directory may not be determinable, in which case there are no aliases.
+	let Ok(path) = config_file_path() else {
+		return Ok(args);
+	};
+	let config = Config::load(&path)?;
+	if config.alias.is_empty() {
+		return Ok(args);
+	}
+	let mut command = Cli::command();
+	command.build();
+	let commands: Vec<String> = command
+		.get_subcommands()
+		.flat_map(|c| once(c.get_name()).chain(c.get_all_aliases()))
+		.chain(once("help"))
+		.map(String::from)
+		.collect();
+	// The global options whose values may precede the command, e.g. `--timeout 30`.
+	let options: Vec<String> = command
+		.get_arguments()
+		.filter(|a| a.get_action().takes_values() && !a.is_require_equals_set())
+		.flat_map(|a| {
+			a.get_long()
+				.map(|long| format!("--{long}"))
+				.into_iter()
+				.chain(a.get_short().map(|short| format!("-{short}")))
+		})
+		.collect();
+	common::alias::expand_aliases(args, &config.alias, &commands, &options)
+}
+
+/// Resolves the command line arguments against the environment specified via `--env`, as
+/// defined within the project configuration file (`pop.toml`) of the current directory.
+///
+/// # Arguments
+/// * `args` - The command line arguments, including the binary name.
+pub fn apply_environment(args: Vec<String>) -> Result<Vec<String>> {
+	use clap::CommandFactory;
+	let Some(name) = environment_name(&args) else {
+		return Ok(args);
+	};
+	let path = current_dir()?.join(PROJECT_CONFIG_FILE_NAME);
+	if !path.exists() {
+		return Err(anyhow!(
+			"The `{name}` environment requires a {PROJECT_CONFIG_FILE_NAME} file in the current directory"
+		));
+	}
+	let config = ProjectConfig::load(&path)?;
+	let mut command = Cli::command();
+	command.build();
+	Ok(common::env::apply_environment(args, config.environment(&name)?, &command))
+}
+
+/// Unlocks any keystore accounts or stored secrets with which the command signs, as referred to
+/// via `keystore:<name>` or `secret:<name>`, prompting for their passwords.
+///
+/// # Arguments
+/// * `args` - The command line arguments, including the binary name.
+/// * `cli` - The cli.
+pub fn unlock_signers(args: &[String], cli: &mut impl cli::traits::Cli) -> Result<()> {
+	if args.iter().any(|a| a.contains(KEYSTORE_PREFIX)) {
+		common::keystore::unlock_accounts(&Keystore::open()?, args, cli)?;
+	}
+	if args.iter().any(|a| a.contains(SECRET_PREFIX)) {
+		common::secrets::load_secrets(&Secrets::open()?, args, cli)?;
+	}
+	Ok(())
+}
+
+// The name of the environment specified via `--env`, if any.
+fn environment_name(args: &[String]) -> Option<String> {
+	let mut args = args.iter().take_while(|a| *a != "--");
+	while let Some(arg) = args.next() {
+		if arg == "--env" {
+			return args.next().cloned();
+		}
+		if let Some(name) = arg.strip_prefix("--env=") {
+			return Some(name.to_string());
+		}
+	}
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn verify_cli() {
+		// https://docs.rs/clap/latest/clap/_derive/_tutorial/chapter_4/index.html
+		use clap::CommandFactory;
+		Cli::command().debug_assert()
+	}
+
+	#[test]
+	fn quiet_flag_is_global() {
+		for args in [vec!["pop", "-q", "clean", "cache"], vec!["pop", "clean", "cache", "--quiet"]]
+		{
+			assert!(Cli::parse_from(args).quiet);
+		}
+		assert!(!Cli::parse_from(["pop", "clean", "cache"]).quiet);
+	}
+
+	#[test]
+	fn progress_json_flag_works() {
+		assert_eq!(Cli::parse_from(["pop", "clean", "cache"]).progress_json, None);
+		assert_eq!(
+			Cli::parse_from(["pop", "--progress-json", "clean", "cache"]).progress_json,
+			Some(None)
+		);
+		assert_eq!(
+			Cli::parse_from(["pop", "clean", "cache", "--progress-json=events.log"]).progress_json,
+			Some(Some(PathBuf::from("events.log")))
+		);
+	}
+
+	#[test]
+	fn network_flags_work() {
+		let pop = Cli::parse_from(["pop", "clean", "cache"]);
+		assert_eq!((pop.timeout, pop.retries), (None, 0));
+		let pop = Cli::parse_from(["pop", "--timeout", "30", "clean", "cache", "--retries", "3"]);
+		assert_eq!((pop.timeout, pop.retries), (Some(30), 3));
+		assert_eq!(pop.retry_delay, 1);
+		let pop = Cli::parse_from(["pop", "clean", "cache", "--retry-delay", "5"]);
+		assert_eq!(pop.retry_delay, 5);
+	}
+
+	#[test]
+	fn environment_name_works() {
+		let args = |args: &str| args.split_whitespace().map(String::from).collect::<Vec<_>>();
+		assert_eq!(environment_name(&args("pop call chain")), None);
+		assert_eq!(environment_name(&args("pop --env dev call chain")).as_deref(), Some("dev"));
+		assert_eq!(
+			environment_name(&args("pop call chain --env=staging")).as_deref(),
+			Some("staging")
+		);
+		assert_eq!(environment_name(&args("pop call chain -- --env dev")), None);
+		assert_eq!(
+			Cli::parse_from(["pop", "clean", "cache", "--env", "dev"]).env.as_deref(),
+			Some("dev")
+		);
+	}
+
+	#[test]
+	fn exit_code_works() {
+		let timed_out = TimedOut { operation: "test".into(), timeout: Duration::from_secs(1) };
+		assert_eq!(exit_code(&anyhow::Error::from(timed_out).context("failed")), EXIT_TIMEOUT);
+		assert_eq!(exit_code(&anyhow!("failed")), EXIT_FAILURE);
+	}
+
+	#[test]
+	fn test_cache() -> Result<(), Box<dyn std::error::Error>> {
+		let path = cache()?;
+		assert_eq!(path.file_name().unwrap().to_str().unwrap().to_string(), "pop");
+		Ok(())
+	}
+}
@@ -1,39 +1,34 @@
 // SPDX-License-Identifier: GPL-3.0
 
-#[cfg(not(any(feature = "contract", feature = "parachain")))]
-compile_error!("feature \"contract\" or feature \"parachain\" must be enabled");
-
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use clap::Parser;
-use commands::*;
-use serde_json::json;
-use std::{fs::create_dir_all, path::PathBuf};
+use pop_cli::{
+	apply_environment, cli, exit_code, expand_aliases, handle_interrupts, unlock_signers, Cli,
+};
 #[cfg(feature = "telemetry")]
 use {
-	pop_telemetry::{config_file_path, record_cli_command, record_cli_used, Telemetry},
-	std::env::args,
+	pop_telemetry::{record_cli_command, record_cli_used, Telemetry},
+	serde_json::json,
 };
 
-mod cli;
-#[cfg(any(feature = "parachain", feature = "contract"))]
-mod commands;
-mod common;
-mod style;
-mod wallet_integration;
-
 #[tokio::main]
 async fn main() -> Result<()> {
 	#[cfg(feature = "telemetry")]
 	let maybe_tel = init().unwrap_or(None);
 
-	let cli = Cli::parse();
-	let res = cli.command.execute().await;
+	handle_interrupts();
+	let args = apply_environment(expand_aliases(std::env::args().collect())?)?;
+	let cli = Cli::parse_from(&args);
+	let res = match unlock_signers(&args, &mut cli::Cli) {
+		Ok(()) => cli.execute(&mut cli::Cli).await,
+		Err(e) => Err(e),
+	};
 
 	#[cfg(feature = "telemetry")]
 	if let Some(tel) = maybe_tel.clone() {
 		// `args` is guaranteed to have at least 3 elements as clap will display help message if not
 		// set.
-		let (command, subcommand) = parse_args(args().collect());
+		let (command, subcommand) = parse_args(args);
 
 		if let Ok(sub_data) = &res {
 			// Best effort to send on first try, no action if failure.
@@ -48,32 +43,19 @@ async fn main() -> Result<()> {
 		}
 	}
 
-	// map result from Result<Value> to Result<()>
-	res.map(|_| ())
-}
-
-#[derive(Parser)]
-#[command(author, version, about, styles=style::get_styles())]
-pub struct Cli {
-	#[command(subcommand)]
-	command: Command,
-}
-
-/// Determines the cache to be used.
-fn cache() -> Result<PathBuf> {
-	let cache_path = dirs::cache_dir()
-		.ok_or(anyhow!("the cache directory could not be determined"))?
-		.join("pop");
-	// Creates pop dir if needed
-	create_dir_all(cache_path.as_path())?;
-	Ok(cache_path)
+	// Exit with a code distinguishing timeouts from any other failure.
+	if let Err(e) = res {
+		eprintln!("Error: {e:?}");
+		std::process::exit(exit_code(&e));
+	}
+	Ok(())
 }
 
 /// Initializes telemetry.
 #[cfg(feature = "telemetry")]
 fn init() -> Result<Option<Telemetry>> {
 	env_logger::init();
-	let maybe_config_path = config_file_path();
+	let maybe_config_path = pop_telemetry::config_file_path();
 
 	let maybe_tel = maybe_config_path.ok().map(|path| Telemetry::new(&path));
 
@@ -90,6 +72,25 @@ fn init() -> Result<Option<Telemetry>> {
 /// Parses command line arguments.
 #[cfg(feature = "telemetry")]
 fn parse_args(args: Vec<String>) -> (String, String) {
+	// ignore any global flags, which may be specified anywhere
+	let mut args = args.into_iter();
+	let mut filtered = vec![];
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"-q" | "--quiet" => {},
+			// Skip the value of the flag.
+			"--timeout" | "--retries" | "--retry-delay" | "--env" => {
+				args.next();
+			},
+			a if a.starts_with("--progress-json") ||
+				a.starts_with("--timeout=") ||
+				a.starts_with("--retries=") ||
+				a.starts_with("--retry-delay=") ||
+				a.starts_with("--env=") => {},
+			_ => filtered.push(arg),
+		}
+	}
+	let args = filtered;
 	// command is always present as clap will print help if not set
 	let command = args.get(1).expect("expected command missing").to_string();
 	// subcommand may not exist
@@ -97,33 +98,29 @@ fn parse_args(args: Vec<String>) -> (String, String) {
 	(command.clone(), subcommand.clone())
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "telemetry"))]
 mod tests {
 	use super::*;
 
-	#[test]
-	fn verify_cli() {
-		// https://docs.rs/clap/latest/clap/_derive/_tutorial/chapter_4/index.html
-		use clap::CommandFactory;
-		Cli::command().debug_assert()
-	}
-
-	#[test]
-	fn test_cache() -> Result<(), Box<dyn std::error::Error>> {
-		let path = cache()?;
-		assert_eq!(path.file_name().unwrap().to_str().unwrap().to_string(), "pop");
-		Ok(())
-	}
-
 	#[test]
 	fn parse_args_works() {
 		for args in vec![
 			vec!["pop", "install"],
 			vec!["pop", "new", "parachain"],
 			vec!["pop", "new", "parachain", "extra"],
+			vec!["pop", "--quiet", "new", "parachain"],
+			vec!["pop", "new", "-q", "parachain"],
+			vec!["pop", "--progress-json=events.log", "new", "parachain"],
+			vec!["pop", "--timeout", "30", "new", "--retries=3", "parachain"],
+			vec!["pop", "--retry-delay", "2", "new", "parachain"],
+			vec!["pop", "--env=dev", "new", "parachain"],
 		] {
 			// map args<&str> to args<String>
 			let (command, subcommand) = parse_args(args.iter().map(|s| s.to_string()).collect());
+			let args: Vec<_> = args
+				.into_iter()
+				.filter(|a| !a.starts_with('-') && a.parse::<u64>().is_err())
+				.collect();
 			assert_eq!(command, args[1]);
 			if args.len() > 2 {
 				assert_eq!(subcommand, args[2]);
@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use pop_common::Status;
+use serde::Serialize;
+use std::{
+	fmt::Display,
+	fs::OpenOptions,
+	io::{stderr, Result, Write},
+	path::Path,
+	sync::{Mutex, OnceLock},
+};
+use tokio::sync::broadcast;
+
+/// The destination of any progress events, once enabled.
+static SINK: OnceLock<Mutex<Box<dyn Write + Send>>> = OnceLock::new();
+
+/// The channel via which any progress events are broadcast to subscribers, once subscribed.
+static CHANNEL: OnceLock<broadcast::Sender<String>> = OnceLock::new();
+
+/// The capacity of the channel, after which the oldest events are dropped for lagging subscribers.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Enables the emission of structured progress events, written as JSON lines to the specified
+/// file (e.g. a named pipe) or otherwise to stderr.
+///
+/// # Arguments
+/// * `path` - An optional path to the file to which events are appended.
+pub(crate) fn enable(path: Option<&Path>) -> Result<()> {
+	let sink: Box<dyn Write + Send> = match path {
+		Some(path) => Box::new(OpenOptions::new().create(true).append(true).open(path)?),
+		None => Box::new(stderr()),
+	};
+	// Events can only be enabled once, with any subsequent attempt ignored.
+	let _ = SINK.set(Mutex::new(sink));
+	Ok(())
+}
+
+/// Subscribes to progress events, with each event received as a JSON line (e.g. to stream events to
+/// clients of `pop serve`).
+pub(crate) fn subscribe() -> broadcast::Receiver<String> {
+	CHANNEL.get_or_init(|| broadcast::channel(CHANNEL_CAPACITY).0).subscribe()
+}
+
+/// A structured progress event, allowing tools embedding pop to render native progress.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub(crate) struct Event {
+	/// The phase of the operation, e.g. "download".
+	phase: String,
+	/// The progress of the phase, as a percentage.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pct: Option<u8>,
+	/// A message describing the progress.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	message: Option<String>,
+}
+
+impl Event {
+	/// Creates a new event for the specified phase.
+	///
+	/// # Arguments
+	/// * `phase` - The phase of the operation.
+	pub(crate) fn new(phase: impl Into<String>) -> Self {
+		Self { phase: phase.into(), ..Default::default() }
+	}
+
+	/// Sets the progress of the phase, as a percentage.
+	pub(crate) fn pct(mut self, pct: u8) -> Self {
+		self.pct = Some(pct.min(100));
+		self
+	}
+
+	/// Sets the message describing the progress.
+	pub(crate) fn message(mut self, message: impl Display) -> Self {
+		self.message = Some(message.to_string());
+		self
+	}
+
+	/// Emits the event as a JSON line, provided events have been enabled or subscribed to.
+	pub(crate) fn emit(&self) {
+		let (sink, channel) = (SINK.get(), CHANNEL.get());
+		if sink.is_none() && channel.is_none() {
+			return;
+		}
+		let Ok(line) = serde_json::to_string(self) else {
+			return;
+		};
+		// Progress events are best effort, so any failure is ignored.
+		if let Some(sink) = sink {
+			if let Ok(mut sink) = sink.lock() {
+				let _ = writeln!(sink, "{line}").and_then(|_| sink.flush());
+			}
+		}
+		if let Some(channel) = channel {
+			let _ = channel.send(line);
+		}
+	}
+}
+
+/// Reports any observed status updates while sourcing a binary as progress events.
+pub(crate) struct Reporter(String);
+
+impl Reporter {
+	/// Creates a new reporter for the specified binary.
+	///
+	/// # Arguments
+	/// * `name` - The name of the binary being sourced.
+	pub(crate) fn new(name: impl Into<String>) -> Self {
+		Self(name.into())
+	}
+}
+
+impl Status for Reporter {
+	fn update(&self, status: &str) {
+		Event::new("status").message(format!("{}: {}", self.0, status.trim())).emit()
+	}
+
+	fn progress(&self, phase: &str, pct: u8) {
+		Event::new(phase).pct(pct).message(&self.0).emit()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+
+	#[test]
+	fn event_serializes_as_json_line() -> Result<()> {
+		assert_eq!(
+			serde_json::to_string(&Event::new("download").pct(40))?,
+			r#"{"phase":"download","pct":40}"#
+		);
+		assert_eq!(
+			serde_json::to_string(&Event::new("start").message("Generating contract..."))?,
+			r#"{"phase":"start","message":"Generating contract..."}"#
+		);
+		assert_eq!(Event::new("download").pct(140).pct, Some(100));
+		Ok(())
+	}
+
+	#[test]
+	fn subscribe_receives_events() -> Result<()> {
+		let mut receiver = subscribe();
+		Event::new("subscribe_receives_events").pct(10).emit();
+		// Other tests may emit events concurrently.
+		loop {
+			let line = receiver.try_recv()?;
+			if line.contains("subscribe_receives_events") {
+				assert_eq!(line, r#"{"phase":"subscribe_receives_events","pct":10}"#);
+				return Ok(());
+			}
+		}
+	}
+}
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{config::config_dir, Error};
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::BTreeMap,
+	fs,
+	path::{Path, PathBuf},
+	str::FromStr,
+};
+use subxt::{ext::subxt_core::utils::AccountId20, utils::AccountId32};
+
+/// The name of the address book, located within the pop configuration directory.
+const ADDRESS_BOOK_FILE_NAME: &str = "address-book.toml";
+
+/// A named account within the address book.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Contact {
+	/// The name of the account.
+	pub name: String,
+	/// The address of the account, either as SS58 or, for Ethereum-style accounts, as hex.
+	pub address: String,
+}
+
+/// An address book of named accounts, which are offered whenever an account is to be entered.
+///
+/// Stored as `address-book.toml` within the pop configuration directory, for example:
+/// ```toml
+/// [accounts]
+/// treasury = "5EYCAe5ijiYfyeZ2JJCGq56LmPyNRAKzpG4QkoQkkQNB5e6Z"
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct AddressBook {
+	/// The addresses of the accounts, keyed by name.
+	accounts: BTreeMap<String, String>,
+	/// The file from which the address book was loaded, and to which it is saved.
+	#[serde(skip)]
+	path: PathBuf,
+}
+
+impl AddressBook {
+	/// Loads the address book from the specified file, returning an empty address book if the
+	/// file does not exist.
+	///
+	/// # Arguments
+	/// * `path` - The path to the address book.
+	pub fn load(path: &Path) -> Result<Self, Error> {
+		let mut book = match path.exists() {
+			true => {
+				let contents = fs::read_to_string(path)?;
+				let book: Self = toml::from_str(&contents).map_err(|e| {
+					Error::AddressBook(format!("unable to parse {}: {e}", path.display()))
+				})?;
+				if let Some((name, address)) =
+					book.accounts.iter().find(|(_, address)| !is_valid_address(address))
+				{
+					return Err(Error::AddressBook(format!(
+						"the address of `{name}` is invalid: {address}"
+					)));
+				}
+				book
+			},
+			false => Self::default(),
+		};
+		book.path = path.to_path_buf();
+		Ok(book)
+	}
+
+	/// Loads the address book within the configuration directory of the user.
+	pub fn open() -> Result<Self, Error> {
+		Self::load(&config_dir()?.join(ADDRESS_BOOK_FILE_NAME))
+	}
+
+	/// The accounts within the address book, ordered by name.
+	pub fn accounts(&self) -> Vec<Contact> {
+		self.accounts
+			.iter()
+			.map(|(name, address)| Contact { name: name.clone(), address: address.clone() })
+			.collect()
+	}
+
+	/// The address of the account with the specified name, if any.
+	///
+	/// # Arguments
+	/// * `name` - The name of the account.
+	pub fn get(&self, name: &str) -> Option<&str> {
+		self.accounts.get(name).map(String::as_str)
+	}
+
+	/// Adds an account to the address book, replacing any account of the same name.
+	///
+	/// # Arguments
+	/// * `name` - The name of the account.
+	/// * `address` - The address of the account.
+	pub fn insert(&mut self, name: &str, address: &str) -> Result<(), Error> {
+		if name.trim().is_empty() {
+			return Err(Error::AddressBook("the name of an account cannot be empty".into()));
+		}
+		if !is_valid_address(address) {
+			return Err(Error::AddressBook(format!("invalid address: {address}")));
+		}
+		self.accounts.insert(name.to_string(), address.to_string());
+		Ok(())
+	}
+
+	/// Removes an account from the address book, returning its address if it existed.
+	///
+	/// # Arguments
+	/// * `name` - The name of the account.
+	pub fn remove(&mut self, name: &str) -> Option<String> {
+		self.accounts.remove(name)
+	}
+
+	/// Saves the address book to the file from which it was loaded.
+	pub fn save(&self) -> Result<(), Error> {
+		if let Some(parent) = self.path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		let contents = toml::to_string(self).map_err(|e| Error::AddressBook(e.to_string()))?;
+		fs::write(&self.path, contents)?;
+		Ok(())
+	}
+}
+
+// Whether an address is either an SS58 address or the hex address of an Ethereum-style account.
+fn is_valid_address(address: &str) -> bool {
+	AccountId32::from_str(address).is_ok() || AccountId20::from_str(address).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+
+	const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+	const ALITH: &str = "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac";
+
+	#[test]
+	fn address_book_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("pop").join(ADDRESS_BOOK_FILE_NAME);
+		let mut book = AddressBook::load(&path)?;
+		assert!(book.accounts().is_empty());
+		book.insert("treasury", ALICE)?;
+		book.insert("alith", ALITH)?;
+		assert!(matches!(book.insert("invalid", "0x1234"), Err(Error::AddressBook(..))));
+		assert!(matches!(book.insert(" ", ALICE), Err(Error::AddressBook(..))));
+		book.save()?;
+		// Accounts are persisted and ordered by name.
+		let mut book = AddressBook::load(&path)?;
+		assert_eq!(
+			book.accounts(),
+			[
+				Contact { name: "alith".into(), address: ALITH.into() },
+				Contact { name: "treasury".into(), address: ALICE.into() },
+			]
+		);
+		assert_eq!(book.get("treasury"), Some(ALICE));
+		assert_eq!(book.remove("alith").as_deref(), Some(ALITH));
+		assert_eq!(book.get("alith"), None);
+		Ok(())
+	}
+
+	#[test]
+	fn load_rejects_invalid_addresses() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join(ADDRESS_BOOK_FILE_NAME);
+		fs::write(&path, "[accounts]\ntreasury = \"invalid\"\n")?;
+		assert!(matches!(
+			AddressBook::load(&path),
+			Err(Error::AddressBook(message)) if message.contains("`treasury` is invalid")
+		));
+		fs::write(&path, "accounts = 1")?;
+		assert!(matches!(AddressBook::load(&path), Err(Error::AddressBook(..))));
+		Ok(())
+	}
+}
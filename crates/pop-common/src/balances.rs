@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{units::format_units, Error};
+use subxt::{
+	dynamic::{self, Value},
+	ext::scale_value::At,
+	utils::AccountId32,
+	Config, OnlineClient,
+};
+
+/// An issue with an operation transferring value, which would cause the operation to fail or an
+/// account to be reaped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BalanceIssue {
+	/// The free balance of the sender does not cover the value transferred and the fees.
+	InsufficientBalance {
+		/// The free balance of the sender.
+		free: u128,
+		/// The value transferred along with any fees.
+		required: u128,
+	},
+	/// The sender would be left with less than the existential deposit, reaping its account.
+	ReapsSender {
+		/// The free balance remaining once the value and fees are paid.
+		remaining: u128,
+		/// The minimum balance required for an account to exist.
+		existential_deposit: u128,
+	},
+	/// The destination account does not exist and the value transferred is less than the
+	/// existential deposit required to create it.
+	BelowExistentialDeposit {
+		/// The value transferred.
+		value: u128,
+		/// The minimum balance required for an account to exist.
+		existential_deposit: u128,
+	},
+}
+
+impl BalanceIssue {
+	/// Whether the issue causes the operation to fail, rather than an account to be reaped.
+	pub fn is_failure(&self) -> bool {
+		!matches!(self, BalanceIssue::ReapsSender { .. })
+	}
+
+	/// Describes the issue, with amounts formatted as whole tokens.
+	///
+	/// # Arguments
+	/// * `decimals` - The number of decimals of the token.
+	/// * `symbol` - The symbol of the token.
+	pub fn describe(&self, decimals: u8, symbol: &str) -> String {
+		let amount = |amount: &u128| format!("{} {symbol}", format_units(*amount, decimals));
+		match self {
+			BalanceIssue::InsufficientBalance { free, required } => format!(
+				"The free balance of the signer ({}) does not cover the {} required.",
+				amount(free),
+				amount(required)
+			),
+			BalanceIssue::ReapsSender { remaining, existential_deposit } => format!(
+				"The signer would be left with {}, less than the existential deposit of {}, and its account reaped.",
+				amount(remaining),
+				amount(existential_deposit)
+			),
+			BalanceIssue::BelowExistentialDeposit { value, existential_deposit } => format!(
+				"The destination account does not exist and {} is less than the existential deposit of {} required to create it.",
+				amount(value),
+				amount(existential_deposit)
+			),
+		}
+	}
+}
+
+/// The balances relevant to an operation transferring value.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Balances {
+	/// The free balance of the sender.
+	pub free: u128,
+	/// The free balance of the destination, if the value is transferred to an account.
+	pub destination: Option<u128>,
+	/// The minimum balance required for an account to exist.
+	pub existential_deposit: u128,
+}
+
+impl Balances {
+	/// Fetches the balances of the accounts involved in an operation transferring value.
+	///
+	/// # Arguments
+	/// * `client` - The client used to interact with the chain.
+	/// * `sender` - The account from which the value is transferred.
+	/// * `destination` - The account to which the value is transferred, if any.
+	pub async fn fetch<C: Config>(
+		client: &OnlineClient<C>,
+		sender: &AccountId32,
+		destination: Option<&AccountId32>,
+	) -> Result<Self, Error> {
+		let destination = match destination {
+			Some(destination) => Some(free_balance(client, destination).await?),
+			None => None,
+		};
+		Ok(Self {
+			free: free_balance(client, sender).await?,
+			destination,
+			existential_deposit: existential_deposit(client),
+		})
+	}
+
+	/// Checks whether transferring the value, along with the fees, would cause the operation to
+	/// fail or an account to be reaped.
+	///
+	/// # Arguments
+	/// * `value` - The value transferred.
+	/// * `fees` - The fees paid by the sender.
+	pub fn check(&self, value: u128, fees: u128) -> Vec<BalanceIssue> {
+		let mut issues = Vec::new();
+		let required = value.saturating_add(fees);
+		match self.free.checked_sub(required) {
+			None => issues.push(BalanceIssue::InsufficientBalance { free: self.free, required }),
+			Some(remaining) if remaining < self.existential_deposit =>
+				issues.push(BalanceIssue::ReapsSender {
+					remaining,
+					existential_deposit: self.existential_deposit,
+				}),
+			Some(_) => {},
+		}
+		if self.destination == Some(0) && value < self.existential_deposit {
+			issues.push(BalanceIssue::BelowExistentialDeposit {
+				value,
+				existential_deposit: self.existential_deposit,
+			});
+		}
+		issues
+	}
+}
+
+/// Fetches the free balance of an account, which is zero if the account does not exist.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `account` - The account.
+pub async fn free_balance<C: Config>(
+	client: &OnlineClient<C>,
+	account: &AccountId32,
+) -> Result<u128, Error> {
+	let query = dynamic::storage("System", "Account", vec![Value::from_bytes(account.0)]);
+	let Some(info) = client.storage().at_latest().await?.fetch(&query).await? else {
+		return Ok(0);
+	};
+	let info = info.to_value().map_err(subxt::Error::from)?;
+	Ok(info
+		.at("data")
+		.and_then(|d| d.at("free"))
+		.and_then(|f| f.as_u128())
+		.unwrap_or_default())
+}
+
+/// The minimum balance required for an account to exist, which is zero if the chain does not
+/// define one.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+pub fn existential_deposit<C: Config>(client: &OnlineClient<C>) -> u128 {
+	client
+		.constants()
+		.at(&dynamic::constant("Balances", "ExistentialDeposit"))
+		.ok()
+		.and_then(|c| c.to_value().ok())
+		.and_then(|v| v.as_u128())
+		.unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn check_works() {
+		let balances = Balances { free: 100, destination: Some(50), existential_deposit: 10 };
+		assert!(balances.check(80, 5).is_empty());
+		assert_eq!(
+			balances.check(90, 5),
+			vec![BalanceIssue::ReapsSender { remaining: 5, existential_deposit: 10 }]
+		);
+		assert_eq!(
+			balances.check(100, 5),
+			vec![BalanceIssue::InsufficientBalance { free: 100, required: 105 }]
+		);
+		// A new destination account requires the existential deposit.
+		let balances = Balances { destination: Some(0), ..balances };
+		assert_eq!(
+			balances.check(5, 1),
+			vec![BalanceIssue::BelowExistentialDeposit { value: 5, existential_deposit: 10 }]
+		);
+		assert!(balances.check(10, 1).is_empty());
+		// The destination is not checked when value is not transferred to an account.
+		let balances = Balances { destination: None, ..balances };
+		assert!(balances.check(5, 1).is_empty());
+	}
+
+	#[test]
+	fn describe_works() {
+		let issue = BalanceIssue::InsufficientBalance {
+			free: 15_000_000_000,
+			required: 2 * 10u128.pow(10),
+		};
+		assert!(issue.is_failure());
+		assert_eq!(
+			issue.describe(10, "DOT"),
+			"The free balance of the signer (1.5 DOT) does not cover the 2 DOT required."
+		);
+		let issue = BalanceIssue::ReapsSender { remaining: 1, existential_deposit: 10u128.pow(10) };
+		assert!(!issue.is_failure());
+		assert_eq!(
+			issue.describe(10, "DOT"),
+			"The signer would be left with 0.0000000001 DOT, less than the existential deposit of 1 DOT, and its account reaped."
+		);
+		let issue = BalanceIssue::BelowExistentialDeposit {
+			value: 5 * 10u128.pow(9),
+			existential_deposit: 10u128.pow(10),
+		};
+		assert!(issue.is_failure());
+		assert_eq!(
+			issue.describe(10, "DOT"),
+			"The destination account does not exist and 0.5 DOT is less than the existential deposit of 1 DOT required to create it."
+		);
+	}
+}
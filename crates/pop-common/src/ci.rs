@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::Error;
+use duct::cmd;
+use std::{
+	fmt::Write as _,
+	fs,
+	path::{Path, PathBuf},
+};
+use strum_macros::{AsRefStr, EnumMessage, EnumString, VariantArray};
+
+/// The version of `srtool` used for deterministic runtime builds.
+pub const SRTOOL_VERSION: &str = "1.84.1";
+
+/// A CI provider, for which a pipeline can be generated.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumMessage, EnumString, Eq, PartialEq, VariantArray)]
+pub enum Provider {
+	/// GitHub Actions.
+	#[strum(serialize = "github", message = "GitHub Actions")]
+	GitHub,
+	/// GitLab CI/CD.
+	#[strum(serialize = "gitlab", message = "GitLab CI/CD")]
+	GitLab,
+}
+
+impl Provider {
+	/// The path of the pipeline file, relative to the root of the project.
+	pub fn path(&self) -> &'static str {
+		match self {
+			Self::GitHub => ".github/workflows/ci.yml",
+			Self::GitLab => ".gitlab-ci.yml",
+		}
+	}
+}
+
+/// A runtime of a project, which is built deterministically.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Runtime {
+	/// The name of the runtime package.
+	pub package: String,
+	/// The directory of the runtime, relative to the root of the project.
+	pub path: PathBuf,
+}
+
+impl Runtime {
+	/// The name of the chain of the runtime, as expected by `srtool`.
+	fn chain(&self) -> &str {
+		self.package.strip_suffix("-runtime").unwrap_or(&self.package)
+	}
+}
+
+/// The layout of a project, from which its pipeline is generated.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Pipeline {
+	/// Whether the project is a smart contract.
+	pub contract: bool,
+	/// The runtimes of the project.
+	pub runtimes: Vec<Runtime>,
+	/// Whether the project declares weight limits, as asserted by `pop test weights`.
+	pub weights: bool,
+}
+
+impl Pipeline {
+	/// Detects the layout of a project.
+	///
+	/// # Arguments
+	/// * `path` - The path to the project.
+	pub fn detect(path: &Path) -> Result<Self, Error> {
+		let output = cmd("cargo", ["metadata", "--no-deps", "--format-version", "1"])
+			.dir(path)
+			.stderr_null()
+			.read()
+			.map_err(|_| Error::ManifestPath(path.display().to_string()))?;
+		let metadata: serde_json::Value = serde_json::from_str(&output)?;
+		let root = PathBuf::from(metadata["workspace_root"].as_str().unwrap_or_default());
+		let mut pipeline = Self { weights: path.join("weights.toml").exists(), ..Self::default() };
+		for package in metadata["packages"].as_array().into_iter().flatten() {
+			let dependencies = package["dependencies"].as_array().into_iter().flatten();
+			let depends_on = |name: &str, kind: Option<&str>| {
+				dependencies.clone().any(|d| d["name"] == name && d["kind"].as_str() == kind)
+			};
+			if depends_on("ink", None) {
+				pipeline.contract = true;
+			}
+			// A runtime is built to Wasm via its build script.
+			if depends_on("substrate-wasm-builder", Some("build")) {
+				let manifest = Path::new(package["manifest_path"].as_str().unwrap_or_default());
+				let dir = manifest.parent().unwrap_or(manifest);
+				pipeline.runtimes.push(Runtime {
+					package: package["name"].as_str().unwrap_or_default().to_string(),
+					path: dir.strip_prefix(&root).unwrap_or(dir).to_path_buf(),
+				});
+			}
+		}
+		Ok(pipeline)
+	}
+
+	/// The commands which test the project.
+	fn tests(&self) -> Vec<&'static str> {
+		if self.contract {
+			return vec!["pop test contract"];
+		}
+		vec!["pop test parachain"]
+	}
+
+	/// The commands which check the benchmarks of the project.
+	fn benchmarks(&self) -> Vec<&'static str> {
+		if self.contract || self.runtimes.is_empty() {
+			return vec![];
+		}
+		let mut commands = vec!["pop test --matrix --features runtime-benchmarks"];
+		if self.weights {
+			commands.push("pop test weights -y");
+		}
+		commands
+	}
+
+	/// Renders the pipeline for the specified provider.
+	///
+	/// # Arguments
+	/// * `provider` - The CI provider.
+	pub fn render(&self, provider: Provider) -> String {
+		match provider {
+			Provider::GitHub => self.github(),
+			Provider::GitLab => self.gitlab(),
+		}
+	}
+
+	fn github(&self) -> String {
+		let mut yaml = String::from(
+			"name: ci\n\non:\n  push:\n    branches: [main]\n  pull_request:\n\nenv:\n  CARGO_TERM_COLOR: always\n\njobs:\n",
+		);
+		let job = |yaml: &mut String, name: &str, needs: Option<&str>, commands: &[&str]| {
+			let _ = writeln!(yaml, "  {name}:\n    runs-on: ubuntu-latest");
+			if let Some(needs) = needs {
+				let _ = writeln!(yaml, "    needs: {needs}");
+			}
+			yaml.push_str("    steps:\n      - uses: actions/checkout@v4\n");
+			yaml.push_str("      - name: Install pop\n        run: |\n          cargo install --locked pop-cli\n          pop install -y\n");
+			for command in commands {
+				let _ = writeln!(yaml, "      - run: {command}");
+			}
+		};
+		job(&mut yaml, "build", None, &["pop build --release"]);
+		job(&mut yaml, "test", Some("build"), &self.tests());
+		let benchmarks = self.benchmarks();
+		if !benchmarks.is_empty() {
+			job(&mut yaml, "benchmarks", Some("build"), &benchmarks);
+		}
+		if !self.runtimes.is_empty() {
+			yaml.push_str("  srtool:\n    runs-on: ubuntu-latest\n    needs: build\n    strategy:\n      matrix:\n        include:\n");
+			for runtime in &self.runtimes {
+				let _ = writeln!(
+					yaml,
+					"          - chain: {}\n            package: {}\n            runtime_dir: {}",
+					runtime.chain(),
+					runtime.package,
+					runtime.path.display()
+				);
+			}
+			let _ = write!(
+				yaml,
+				"    steps:\n      - uses: actions/checkout@v4\n      - name: Build deterministic runtime\n        id: srtool\n        uses: chevdor/srtool-actions@v0.9.2\n        with:\n          tag: {SRTOOL_VERSION}\n          chain: ${{{{ matrix.chain }}}}\n          package: ${{{{ matrix.package }}}}\n          runtime_dir: ${{{{ matrix.runtime_dir }}}}\n      - uses: actions/upload-artifact@v4\n        with:\n          name: ${{{{ matrix.chain }}}}-runtime\n          path: ${{{{ steps.srtool.outputs.wasm_compressed }}}}\n"
+			);
+		}
+		yaml
+	}
+
+	fn gitlab(&self) -> String {
+		let benchmarks = self.benchmarks();
+		let mut yaml = String::from("stages:\n  - build\n  - test\n");
+		if !benchmarks.is_empty() {
+			yaml.push_str("  - benchmark\n");
+		}
+		if !self.runtimes.is_empty() {
+			yaml.push_str("  - release\n");
+		}
+		yaml.push_str("\ndefault:\n  image: rust:latest\n  before_script:\n    - cargo install --locked pop-cli\n    - pop install -y\n");
+		let job = |yaml: &mut String, name: &str, stage: &str, commands: &[&str]| {
+			let _ = writeln!(yaml, "\n{name}:\n  stage: {stage}\n  script:");
+			for command in commands {
+				let _ = writeln!(yaml, "    - {command}");
+			}
+		};
+		job(&mut yaml, "build", "build", &["pop build --release"]);
+		job(&mut yaml, "test", "test", &self.tests());
+		if !benchmarks.is_empty() {
+			job(&mut yaml, "benchmarks", "benchmark", &benchmarks);
+		}
+		for runtime in &self.runtimes {
+			let _ = write!(
+				yaml,
+				"\nsrtool-{chain}:\n  stage: release\n  image: paritytech/srtool:{SRTOOL_VERSION}\n  before_script: []\n  variables:\n    PACKAGE: {package}\n    RUNTIME_DIR: {dir}\n  script:\n    - /srtool/build --app --json\n  artifacts:\n    paths:\n      - {dir}/target/srtool/release/wbuild/{package}/\n",
+				chain = runtime.chain(),
+				package = runtime.package,
+				dir = runtime.path.display(),
+			);
+		}
+		yaml
+	}
+}
+
+/// Generates a pipeline for a project, returning the path of the resulting file.
+///
+/// # Arguments
+/// * `path` - The path to the project.
+/// * `provider` - The CI provider.
+pub fn generate_pipeline(path: &Path, provider: Provider) -> Result<PathBuf, Error> {
+	let pipeline = Pipeline::detect(path)?;
+	let file = path.join(provider.path());
+	if let Some(parent) = file.parent() {
+		fs::create_dir_all(parent)?;
+	}
+	fs::write(&file, pipeline.render(provider))?;
+	Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parachain() -> Pipeline {
+		Pipeline {
+			contract: false,
+			runtimes: vec![Runtime {
+				package: "parachain-template-runtime".into(),
+				path: PathBuf::from("runtime"),
+			}],
+			weights: true,
+		}
+	}
+
+	#[test]
+	fn provider_works() {
+		assert_eq!("github".parse::<Provider>().unwrap(), Provider::GitHub);
+		assert_eq!("gitlab".parse::<Provider>().unwrap(), Provider::GitLab);
+		assert_eq!(Provider::GitHub.path(), ".github/workflows/ci.yml");
+		assert_eq!(Provider::GitLab.path(), ".gitlab-ci.yml");
+	}
+
+	#[test]
+	fn render_github_works() {
+		let yaml = parachain().render(Provider::GitHub);
+		assert!(yaml.contains("      - run: pop build --release\n"));
+		assert!(yaml.contains("      - run: pop test parachain\n"));
+		assert!(yaml.contains("      - run: pop test --matrix --features runtime-benchmarks\n"));
+		assert!(yaml.contains("      - run: pop test weights -y\n"));
+		assert!(yaml.contains(
+			"          - chain: parachain-template\n            package: parachain-template-runtime\n            runtime_dir: runtime\n"
+		));
+		assert!(yaml.contains("chain: ${{ matrix.chain }}"));
+
+		let yaml = Pipeline { contract: true, ..Default::default() }.render(Provider::GitHub);
+		assert!(yaml.contains("      - run: pop test contract\n"));
+		assert!(!yaml.contains("benchmarks:"));
+		assert!(!yaml.contains("srtool"));
+	}
+
+	#[test]
+	fn render_gitlab_works() {
+		let yaml = parachain().render(Provider::GitLab);
+		assert!(yaml.starts_with("stages:\n  - build\n  - test\n  - benchmark\n  - release\n"));
+		assert!(yaml.contains("\ntest:\n  stage: test\n  script:\n    - pop test parachain\n"));
+		assert!(yaml.contains("\nsrtool-parachain-template:\n  stage: release\n"));
+		assert!(
+			yaml.contains("    PACKAGE: parachain-template-runtime\n    RUNTIME_DIR: runtime\n")
+		);
+
+		let yaml = Pipeline { contract: true, ..Default::default() }.render(Provider::GitLab);
+		assert!(yaml.starts_with("stages:\n  - build\n  - test\n\n"));
+		assert!(!yaml.contains("srtool"));
+	}
+
+	#[test]
+	fn detect_works() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		fs::write(
+			temp_dir.path().join("Cargo.toml"),
+			"[workspace]\nmembers = [\"runtime\"]\nresolver = \"2\"\n",
+		)?;
+		let runtime = temp_dir.path().join("runtime");
+		fs::create_dir_all(runtime.join("src"))?;
+		fs::write(runtime.join("src/lib.rs"), "")?;
+		fs::write(
+			runtime.join("Cargo.toml"),
+			"[package]\nname = \"my-runtime\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[build-dependencies]\nsubstrate-wasm-builder = \"24\"\n",
+		)?;
+		fs::write(temp_dir.path().join("weights.toml"), "")?;
+		assert_eq!(
+			Pipeline::detect(temp_dir.path())?,
+			Pipeline {
+				contract: false,
+				runtimes: vec![Runtime {
+					package: "my-runtime".into(),
+					path: PathBuf::from("runtime")
+				}],
+				weights: true,
+			}
+		);
+		assert_eq!(Pipeline::detect(temp_dir.path())?.runtimes[0].chain(), "my");
+		Ok(())
+	}
+
+	#[test]
+	fn generate_pipeline_fails_without_manifest() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		assert!(matches!(
+			generate_pipeline(temp_dir.path(), Provider::GitHub),
+			Err(Error::ManifestPath(..))
+		));
+		Ok(())
+	}
+}
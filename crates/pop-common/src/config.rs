@@ -0,0 +1,306 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{deploy::Deployment, Error};
+use serde::Deserialize;
+use std::{
+	collections::BTreeMap,
+	fs::read_to_string,
+	path::{Path, PathBuf},
+};
+
+/// The name of the user configuration file, located within the pop configuration directory.
+const CONFIG_FILE_NAME: &str = "config.toml";
+/// The name of the project configuration file, located within the project directory.
+pub const PROJECT_CONFIG_FILE_NAME: &str = "pop.toml";
+
+/// User configuration for pop, loaded from `config.toml` within the pop configuration directory.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct Config {
+	/// User-defined command aliases, keyed by alias name.
+	pub alias: BTreeMap<String, Alias>,
+	/// Named chain endpoints (e.g. `pop`), keyed by name, which may be used instead of URLs.
+	pub chains: BTreeMap<String, ChainProfile>,
+	/// The contract verification service to which deployed contracts are uploaded.
+	pub verifier: Verifier,
+}
+
+impl Config {
+	/// Loads the configuration from the specified file, returning the default configuration if the
+	/// file does not exist.
+	///
+	/// # Arguments
+	/// * `path` - The path to the configuration file.
+	pub fn load(path: &Path) -> Result<Self, Error> {
+		if !path.exists() {
+			return Ok(Self::default());
+		}
+		let contents = read_to_string(path)?;
+		toml::from_str(&contents)
+			.map_err(|e| Error::Config(format!("unable to parse {}: {e}", path.display())))
+	}
+}
+
+/// A user-defined command alias, expanding to one or more command line arguments.
+///
+/// An alias can either be specified as a single string, which is split on whitespace, or as a
+/// list of arguments (e.g. to preserve arguments containing spaces).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum Alias {
+	/// The arguments, separated by whitespace.
+	Command(String),
+	/// The arguments as a list.
+	Args(Vec<String>),
+}
+
+impl Alias {
+	/// The command line arguments the alias expands to.
+	pub fn args(&self) -> Vec<String> {
+		match self {
+			Alias::Command(command) => command.split_whitespace().map(String::from).collect(),
+			Alias::Args(args) => args.clone(),
+		}
+	}
+}
+
+/// A named chain endpoint, along with any properties of the chain which are to be used rather
+/// than those resolved from the chain itself.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ChainProfile {
+	/// The endpoint of a node of the chain (e.g. `wss://rpc1.paseo.popnetwork.xyz`).
+	pub url: String,
+	/// The symbol of the native token of the chain.
+	pub token_symbol: Option<String>,
+	/// The number of decimals of the native token of the chain.
+	pub token_decimals: Option<u8>,
+	/// The prefix of the SS58 addresses of the chain.
+	pub ss58_prefix: Option<u16>,
+}
+
+/// A contract verification service (e.g. an ink! verifier API), to which the verification
+/// packages of deployed contracts are uploaded.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Verifier {
+	/// The base URL of the API of the service.
+	pub url: Option<String>,
+	/// The key used to authenticate with the API of the service.
+	pub api_key: Option<String>,
+}
+
+/// Project configuration for pop, loaded from `pop.toml` within the project directory.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct ProjectConfig {
+	/// The environments the project is used with (e.g. `dev`, `staging` and `production`), keyed
+	/// by name.
+	pub env: BTreeMap<String, Environment>,
+	/// The deployments of the project, keyed by name.
+	pub deployments: BTreeMap<String, Deployment>,
+}
+
+impl ProjectConfig {
+	/// Loads the configuration from the specified file, returning the default configuration if the
+	/// file does not exist.
+	///
+	/// # Arguments
+	/// * `path` - The path to the configuration file.
+	pub fn load(path: &Path) -> Result<Self, Error> {
+		if !path.exists() {
+			return Ok(Self::default());
+		}
+		let contents = read_to_string(path)?;
+		toml::from_str(&contents)
+			.map_err(|e| Error::Config(format!("unable to parse {}: {e}", path.display())))
+	}
+
+	/// Returns the environment with the specified name.
+	///
+	/// # Arguments
+	/// * `name` - The name of the environment.
+	pub fn environment(&self, name: &str) -> Result<&Environment, Error> {
+		self.env.get(name).ok_or_else(|| {
+			Error::Config(format!(
+				"the environment `{name}` is not defined within {PROJECT_CONFIG_FILE_NAME}"
+			))
+		})
+	}
+}
+
+/// An environment the project is used with, such as a local development network or a live
+/// network, against which commands are resolved.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Environment {
+	/// The endpoint of the chain (e.g. `ws://localhost:9944`).
+	pub url: Option<String>,
+	/// The secret key URI of the signer used by default.
+	pub suri: Option<String>,
+	/// The identifier of the parachain.
+	pub para_id: Option<u32>,
+	/// Named signers, keyed by alias and resolving to their secret key URI.
+	pub signers: BTreeMap<String, String>,
+	/// Named contracts, keyed by alias and resolving to their address.
+	pub contracts: BTreeMap<String, String>,
+}
+
+/// Returns the path to the pop configuration directory, based on the OS's default configuration
+/// directory.
+pub fn config_dir() -> Result<PathBuf, Error> {
+	dirs::config_dir()
+		.map(|path| path.join("pop"))
+		.ok_or(Error::Config("the configuration directory could not be determined".into()))
+}
+
+/// Returns the path to the user configuration file.
+pub fn config_file_path() -> Result<PathBuf, Error> {
+	Ok(config_dir()?.join(CONFIG_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use std::fs::write;
+
+	#[test]
+	fn load_returns_default_when_missing() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		assert_eq!(Config::load(&temp_dir.path().join(CONFIG_FILE_NAME))?, Config::default());
+		Ok(())
+	}
+
+	#[test]
+	fn load_parses_aliases() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join(CONFIG_FILE_NAME);
+		write(
+			&path,
+			r#"
+[alias]
+b = "build --release"
+deploy-dev = ["up", "--url", "ws://localhost:9944", "-y"]
+"#,
+		)?;
+		let config = Config::load(&path)?;
+		assert_eq!(config.alias.len(), 2);
+		assert_eq!(config.alias["b"].args(), vec!["build", "--release"]);
+		assert_eq!(
+			config.alias["deploy-dev"].args(),
+			vec!["up", "--url", "ws://localhost:9944", "-y"]
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn load_parses_verifier() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join(CONFIG_FILE_NAME);
+		write(&path, "[verifier]\nurl = \"https://verify.example.com\"\napi-key = \"key\"")?;
+		assert_eq!(
+			Config::load(&path)?.verifier,
+			Verifier {
+				url: Some("https://verify.example.com".into()),
+				api_key: Some("key".into())
+			}
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn load_parses_chains() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join(CONFIG_FILE_NAME);
+		write(
+			&path,
+			r#"
+[chains.pop]
+url = "wss://rpc1.paseo.popnetwork.xyz"
+token-symbol = "PAS"
+token-decimals = 10
+ss58-prefix = 0
+
+[chains.local]
+url = "ws://localhost:9944"
+"#,
+		)?;
+		let config = Config::load(&path)?;
+		assert_eq!(
+			config.chains["pop"],
+			ChainProfile {
+				url: "wss://rpc1.paseo.popnetwork.xyz".into(),
+				token_symbol: Some("PAS".into()),
+				token_decimals: Some(10),
+				ss58_prefix: Some(0),
+			}
+		);
+		assert_eq!(
+			config.chains["local"],
+			ChainProfile { url: "ws://localhost:9944".into(), ..Default::default() }
+		);
+		// The endpoint of a chain is required.
+		write(&path, "[chains.pop]\ntoken-symbol = \"PAS\"")?;
+		assert!(matches!(Config::load(&path), Err(Error::Config(..))));
+		Ok(())
+	}
+
+	#[test]
+	fn load_fails_with_invalid_config() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join(CONFIG_FILE_NAME);
+		write(&path, "[alias]\nb = 1")?;
+		assert!(matches!(Config::load(&path), Err(Error::Config(..))));
+		Ok(())
+	}
+
+	#[test]
+	fn project_config_parses_environments() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join(PROJECT_CONFIG_FILE_NAME);
+		assert_eq!(ProjectConfig::load(&path)?, ProjectConfig::default());
+		write(
+			&path,
+			r#"
+[env.dev]
+url = "ws://localhost:9944"
+suri = "//Alice"
+para-id = 2000
+
+[env.dev.signers]
+deployer = "//Bob"
+
+[env.dev.contracts]
+flipper = "5CLPm1CeUvJhZ8GCDZCR7nWZ2m3XXe4X5MtAQK69zEjut36A"
+
+[env.production]
+url = "wss://rpc.example.com"
+"#,
+		)?;
+		let config = ProjectConfig::load(&path)?;
+		let dev = config.environment("dev")?;
+		assert_eq!(dev.url.as_deref(), Some("ws://localhost:9944"));
+		assert_eq!(dev.suri.as_deref(), Some("//Alice"));
+		assert_eq!(dev.para_id, Some(2000));
+		assert_eq!(dev.signers["deployer"], "//Bob");
+		assert_eq!(dev.contracts["flipper"], "5CLPm1CeUvJhZ8GCDZCR7nWZ2m3XXe4X5MtAQK69zEjut36A");
+		assert_eq!(
+			config.environment("production")?,
+			&Environment { url: Some("wss://rpc.example.com".into()), ..Default::default() }
+		);
+		assert!(matches!(
+			config.environment("staging"),
+			Err(Error::Config(message)) if message.contains("`staging`")
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn config_file_path_works() -> Result<()> {
+		let path = config_file_path()?;
+		assert_eq!(path.file_name().unwrap(), CONFIG_FILE_NAME);
+		assert_eq!(path.parent().unwrap().file_name().unwrap(), "pop");
+		Ok(())
+	}
+}
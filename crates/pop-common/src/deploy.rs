@@ -0,0 +1,477 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{Error, APP_USER_AGENT};
+use duct::cmd;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+use strum_macros::{Display, EnumString};
+use toml_edit::{value, DocumentMut, Item, Table};
+use url::Url;
+
+/// The port on which the RPC server of a node deployed via Docker listens within its container.
+const CONTAINER_RPC_PORT: u16 = 9944;
+/// The directory within a container to which artifacts are mounted.
+const CONTAINER_ARTIFACTS: &str = "/pop";
+
+/// A target to which the artifacts of a project (e.g. a chain spec, runtime or contract bundle)
+/// are deployed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+	/// A host reachable via SSH, to which the artifacts are copied before an optional command is
+	/// run on the host.
+	Ssh {
+		/// The host, e.g. `user@example.com`.
+		host: String,
+		/// The directory on the host to which the artifacts are copied.
+		path: String,
+		/// A command run on the host once the artifacts are copied, e.g. to restart a node.
+		command: Option<String>,
+		/// The endpoint of the deployment, if known.
+		endpoint: Option<String>,
+	},
+	/// A Docker engine, running a node image with the artifacts mounted.
+	Docker {
+		/// The node image, e.g. `parity/polkadot-parachain`.
+		image: String,
+		/// The port on which the RPC server of the node is published.
+		port: u16,
+	},
+	/// A hosted infrastructure provider, via its HTTP API.
+	Provider {
+		/// The base URL of the API of the provider.
+		api: Url,
+		/// The key used to authenticate with the API, if required.
+		api_key: Option<String>,
+	},
+}
+
+impl Target {
+	/// A description of the target, as recorded with a deployment.
+	fn description(&self) -> String {
+		match self {
+			Target::Ssh { host, path, .. } => format!("ssh://{host}:{path}"),
+			Target::Docker { image, .. } => format!("docker://{image}"),
+			Target::Provider { api, .. } => api.to_string(),
+		}
+	}
+}
+
+/// The status of a deployment.
+#[derive(
+	Clone, Copy, Debug, Default, Deserialize, Display, EnumString, Eq, PartialEq, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum DeploymentStatus {
+	/// The deployment has been handed off but is not yet running.
+	#[default]
+	Pending,
+	/// The deployment is running.
+	Running,
+	/// The deployment has stopped.
+	Stopped,
+	/// The deployment failed.
+	Failed,
+}
+
+/// A deployment of a project, as recorded within the `[deployments]` section of the project
+/// configuration file.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Deployment {
+	/// A description of the target of the deployment.
+	pub target: String,
+	/// The identifier of the deployment with its target (e.g. a container name), if any.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub id: Option<String>,
+	/// The status of the deployment, when last checked.
+	pub status: DeploymentStatus,
+	/// The endpoint of the deployment, once known.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub endpoint: Option<String>,
+}
+
+// A deployment, as described by the API of a provider.
+#[derive(Deserialize)]
+struct ProviderDeployment {
+	id: String,
+	status: DeploymentStatus,
+	endpoint: Option<String>,
+}
+
+/// Deploys artifacts to a target.
+///
+/// # Arguments
+/// * `name` - The name of the deployment (e.g. the environment).
+/// * `target` - The target of the deployment.
+/// * `artifacts` - The artifacts to be deployed.
+pub async fn deploy(
+	name: &str,
+	target: &Target,
+	artifacts: &[PathBuf],
+) -> Result<Deployment, Error> {
+	if artifacts.is_empty() {
+		return Err(Error::Deployment("no artifacts were specified".into()));
+	}
+	if let Some(missing) = artifacts.iter().find(|a| !a.is_file()) {
+		return Err(Error::Deployment(format!("the artifact {} was not found", missing.display())));
+	}
+	let description = target.description();
+	match target {
+		Target::Ssh { host, path, command, endpoint } => {
+			let mut args: Vec<String> = artifacts.iter().map(|a| a.display().to_string()).collect();
+			args.push(format!("{host}:{path}"));
+			cmd("scp", args).stdout_null().run()?;
+			if let Some(command) = command {
+				cmd("ssh", [host, command]).stdout_null().run()?;
+			}
+			Ok(Deployment {
+				target: description,
+				id: None,
+				status: DeploymentStatus::Running,
+				endpoint: endpoint.clone(),
+			})
+		},
+		Target::Docker { image, port } => {
+			let container = container_name(name);
+			cmd("docker", docker_args(&container, image, *port, artifacts)?)
+				.stdout_null()
+				.run()?;
+			Ok(Deployment {
+				target: description,
+				status: docker_status(&container)?,
+				id: Some(container),
+				endpoint: Some(format!("ws://localhost:{port}")),
+			})
+		},
+		Target::Provider { api, api_key } => {
+			let artifacts = artifacts
+				.iter()
+				.map(|a| {
+					Ok(json!({
+						"name": a.file_name().and_then(|n| n.to_str()).unwrap_or_default(),
+						"content": to_hex(&fs::read(a)?),
+					}))
+				})
+				.collect::<Result<Vec<_>, Error>>()?;
+			let request = client()?
+				.post(api.join("deployments")?)
+				.json(&json!({ "name": name, "artifacts": artifacts }));
+			let deployment = send(request, api_key.as_deref()).await?;
+			Ok(Deployment {
+				target: description,
+				id: Some(deployment.id),
+				status: deployment.status,
+				endpoint: deployment.endpoint,
+			})
+		},
+	}
+}
+
+/// Checks the current status of a deployment, returning the updated deployment. The status of a
+/// deployment via SSH cannot be checked, so it is returned as recorded.
+///
+/// # Arguments
+/// * `deployment` - The deployment, as recorded.
+/// * `api_key` - The key used to authenticate with the API of a provider, if required.
+pub async fn check_deployment(
+	deployment: &Deployment,
+	api_key: Option<&str>,
+) -> Result<Deployment, Error> {
+	let mut deployment = deployment.clone();
+	let Some(id) = deployment.id.clone() else {
+		return Ok(deployment);
+	};
+	if deployment.target.starts_with("docker://") {
+		deployment.status = docker_status(&id)?;
+	} else if deployment.target.starts_with("http") {
+		let api: Url = deployment.target.parse()?;
+		let checked = send(client()?.get(api.join(&format!("deployments/{id}"))?), api_key).await?;
+		deployment.status = checked.status;
+		deployment.endpoint = checked.endpoint.or(deployment.endpoint);
+	}
+	Ok(deployment)
+}
+
+/// Records a deployment within the `[deployments]` section of the project configuration file,
+/// along with its endpoint as the `url` of the environment of the same name, so that subsequent
+/// commands can be resolved against it.
+///
+/// # Arguments
+/// * `path` - The path to the project configuration file, which is created if missing.
+/// * `name` - The name of the deployment.
+/// * `deployment` - The deployment.
+pub fn record_deployment(path: &Path, name: &str, deployment: &Deployment) -> Result<(), Error> {
+	let mut config = match path.exists() {
+		true => fs::read_to_string(path)?
+			.parse::<DocumentMut>()
+			.map_err(|e| Error::Config(format!("unable to parse {}: {e}", path.display())))?,
+		false => DocumentMut::new(),
+	};
+	let table = toml_edit::ser::to_document(deployment)
+		.map_err(|e| Error::Config(e.to_string()))?
+		.as_table()
+		.clone();
+	section(&mut config, "deployments").insert(name, Item::Table(table));
+	if let Some(endpoint) = &deployment.endpoint {
+		let env = section(&mut config, "env");
+		let env = env
+			.entry(name)
+			.or_insert(Item::Table(Table::new()))
+			.as_table_mut()
+			.ok_or_else(|| Error::Config(format!("expected `env.{name}` to be a table")))?;
+		env.insert("url", value(endpoint));
+	}
+	fs::write(path, config.to_string())?;
+	Ok(())
+}
+
+// Returns the table of a section of the configuration, created if missing.
+fn section<'a>(config: &'a mut DocumentMut, name: &str) -> &'a mut Table {
+	let item = config.entry(name).or_insert_with(|| {
+		let mut table = Table::new();
+		table.set_implicit(true);
+		Item::Table(table)
+	});
+	if !item.is_table() {
+		*item = Item::Table(Table::new());
+	}
+	item.as_table_mut().expect("section is a table")
+}
+
+// The name of the container of a deployment via Docker.
+fn container_name(name: &str) -> String {
+	format!("pop-{name}")
+}
+
+// The arguments to `docker` to run a node with the artifacts mounted, using the first chain spec
+// amongst the artifacts.
+fn docker_args(
+	container: &str,
+	image: &str,
+	port: u16,
+	artifacts: &[PathBuf],
+) -> Result<Vec<String>, Error> {
+	let mut args = vec![
+		"run".to_string(),
+		"--detach".into(),
+		"--name".into(),
+		container.into(),
+		"--publish".into(),
+		format!("{port}:{CONTAINER_RPC_PORT}"),
+	];
+	let mut chain_spec = None;
+	for artifact in artifacts {
+		let file_name = artifact
+			.file_name()
+			.and_then(|n| n.to_str())
+			.ok_or_else(|| Error::Deployment(format!("invalid artifact {}", artifact.display())))?;
+		let mounted = format!("{CONTAINER_ARTIFACTS}/{file_name}");
+		args.push("--volume".into());
+		args.push(format!("{}:{mounted}:ro", fs::canonicalize(artifact)?.display()));
+		if chain_spec.is_none() && file_name.ends_with(".json") {
+			chain_spec = Some(mounted);
+		}
+	}
+	args.push(image.into());
+	if let Some(chain_spec) = chain_spec {
+		args.extend(["--chain".into(), chain_spec]);
+	}
+	args.extend([
+		"--rpc-external".into(),
+		"--rpc-cors=all".into(),
+		format!("--rpc-port={CONTAINER_RPC_PORT}"),
+	]);
+	Ok(args)
+}
+
+// The status of a container.
+fn docker_status(container: &str) -> Result<DeploymentStatus, Error> {
+	let status = cmd("docker", ["inspect", "--format", "{{.State.Status}}", container])
+		.stderr_null()
+		.read()?;
+	Ok(match status.trim() {
+		"running" => DeploymentStatus::Running,
+		"created" | "restarting" => DeploymentStatus::Pending,
+		"dead" => DeploymentStatus::Failed,
+		_ => DeploymentStatus::Stopped,
+	})
+}
+
+fn client() -> Result<reqwest::Client, Error> {
+	reqwest::ClientBuilder::new()
+		.user_agent(APP_USER_AGENT)
+		.build()
+		.map_err(|e| Error::Deployment(e.to_string()))
+}
+
+// Sends a request to the API of a provider, authenticated by the key if specified.
+async fn send(
+	request: reqwest::RequestBuilder,
+	api_key: Option<&str>,
+) -> Result<ProviderDeployment, Error> {
+	let request = match api_key {
+		Some(key) => request.bearer_auth(key),
+		None => request,
+	};
+	let response = request
+		.send()
+		.await
+		.and_then(|r| r.error_for_status())
+		.map_err(|e| Error::Deployment(e.to_string()))?;
+	response.json().await.map_err(|e| Error::Deployment(e.to_string()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	format!("0x{}", bytes.iter().map(|b| format!("{b:02x}")).collect::<String>())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::ProjectConfig;
+	use anyhow::Result;
+	use tempfile::tempdir;
+
+	#[test]
+	fn docker_args_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let wasm = temp_dir.path().join("runtime.wasm");
+		let chain_spec = temp_dir.path().join("chain-spec-raw.json");
+		fs::write(&wasm, [0])?;
+		fs::write(&chain_spec, "{}")?;
+		let args = docker_args(
+			"pop-staging",
+			"parity/polkadot-parachain",
+			9955,
+			&[wasm.clone(), chain_spec.clone()],
+		)?;
+		assert_eq!(
+			args,
+			vec![
+				"run".to_string(),
+				"--detach".into(),
+				"--name".into(),
+				"pop-staging".into(),
+				"--publish".into(),
+				"9955:9944".into(),
+				"--volume".into(),
+				format!("{}:/pop/runtime.wasm:ro", fs::canonicalize(&wasm)?.display()),
+				"--volume".into(),
+				format!("{}:/pop/chain-spec-raw.json:ro", fs::canonicalize(&chain_spec)?.display()),
+				"parity/polkadot-parachain".into(),
+				"--chain".into(),
+				"/pop/chain-spec-raw.json".into(),
+				"--rpc-external".into(),
+				"--rpc-cors=all".into(),
+				"--rpc-port=9944".into(),
+			]
+		);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn deploy_fails_with_missing_artifacts() {
+		let target = Target::Docker { image: "node".into(), port: 9944 };
+		assert!(matches!(
+			deploy("staging", &target, &[]).await,
+			Err(Error::Deployment(message)) if message == "no artifacts were specified"
+		));
+		assert!(matches!(
+			deploy("staging", &target, &[PathBuf::from("./missing.json")]).await,
+			Err(Error::Deployment(message)) if message.contains("missing.json was not found")
+		));
+	}
+
+	#[tokio::test]
+	async fn deploy_to_provider_works() -> Result<()> {
+		let mut server = mockito::Server::new_async().await;
+		let created = server
+			.mock("POST", "/deployments")
+			.match_header("authorization", "Bearer key")
+			.match_body(mockito::Matcher::PartialJson(json!({
+				"name": "staging",
+				"artifacts": [{ "name": "flipper.contract", "content": "0x7b7d" }]
+			})))
+			.with_body(r#"{"id":"42","status":"pending"}"#)
+			.create_async()
+			.await;
+		let checked = server
+			.mock("GET", "/deployments/42")
+			.with_body(r#"{"id":"42","status":"running","endpoint":"wss://staging.example.com"}"#)
+			.create_async()
+			.await;
+		let temp_dir = tempdir()?;
+		let artifact = temp_dir.path().join("flipper.contract");
+		fs::write(&artifact, "{}")?;
+		let api: Url = format!("{}/", server.url()).parse()?;
+		let target = Target::Provider { api: api.clone(), api_key: Some("key".into()) };
+
+		let deployment = deploy("staging", &target, &[artifact]).await?;
+		assert_eq!(
+			deployment,
+			Deployment {
+				target: api.to_string(),
+				id: Some("42".into()),
+				status: DeploymentStatus::Pending,
+				endpoint: None,
+			}
+		);
+		let deployment = check_deployment(&deployment, Some("key")).await?;
+		assert_eq!(deployment.status, DeploymentStatus::Running);
+		assert_eq!(deployment.endpoint.as_deref(), Some("wss://staging.example.com"));
+		created.assert_async().await;
+		checked.assert_async().await;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn check_deployment_via_ssh_returns_recorded_status() -> Result<()> {
+		let deployment = Deployment {
+			target: "ssh://user@example.com:/srv".into(),
+			status: DeploymentStatus::Running,
+			..Default::default()
+		};
+		assert_eq!(check_deployment(&deployment, None).await?, deployment);
+		Ok(())
+	}
+
+	#[test]
+	fn record_deployment_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let path = temp_dir.path().join("pop.toml");
+		fs::write(&path, "# Environments\n[env.staging]\nsuri = \"//Alice\"\n")?;
+		let deployment = Deployment {
+			target: "docker://parity/polkadot-parachain".into(),
+			id: Some("pop-staging".into()),
+			status: DeploymentStatus::Running,
+			endpoint: Some("ws://localhost:9955".into()),
+		};
+		record_deployment(&path, "staging", &deployment)?;
+		let contents = fs::read_to_string(&path)?;
+		// Existing configuration is retained.
+		assert!(contents.starts_with("# Environments\n"));
+		let config = ProjectConfig::load(&path)?;
+		assert_eq!(config.deployments["staging"], deployment);
+		let staging = config.environment("staging")?;
+		assert_eq!(staging.url.as_deref(), Some("ws://localhost:9955"));
+		assert_eq!(staging.suri.as_deref(), Some("//Alice"));
+		// A configuration file is created when missing.
+		let path = temp_dir.path().join("new.toml");
+		record_deployment(&path, "production", &Deployment::default())?;
+		let config = ProjectConfig::load(&path)?;
+		assert_eq!(config.deployments["production"], Deployment::default());
+		assert!(config.env.is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn deployment_status_works() {
+		assert_eq!(DeploymentStatus::Running.to_string(), "running");
+		assert_eq!("failed".parse::<DeploymentStatus>(), Ok(DeploymentStatus::Failed));
+	}
+}
@@ -6,17 +6,31 @@ use thiserror::Error;
 /// Represents the various errors that can occur in the crate.
 #[derive(Error, Debug)]
 pub enum Error {
+	/// An error occurred while loading or updating the address book.
+	#[error("Address book error: {0}")]
+	AddressBook(String),
 	#[error("Anyhow error: {0}")]
 	AnyhowError(#[from] anyhow::Error),
 	#[error("Configuration error: {0}")]
 	Config(String),
+	/// An error occurred while deploying a project, or checking the status of a deployment.
+	#[error("Deployment error: {0}")]
+	Deployment(String),
 	#[error("a git error occurred: {0}")]
 	Git(String),
+	/// An amount could not be parsed as a number of tokens.
+	#[error("Invalid amount: {0}")]
+	InvalidAmount(String),
 	#[error("IO error: {0}")]
 	IO(#[from] std::io::Error),
+	#[error("JSON error: {0}")]
+	JsonError(#[from] serde_json::Error),
 	/// An error occurred while attempting to create a keypair from the provided URI.
 	#[error("Failed to create keypair from URI: {0}")]
 	KeyPairCreation(String),
+	/// An error occurred while importing or unlocking an account within the keystore.
+	#[error("Keystore error: {0}")]
+	Keystore(String),
 	#[error("Manifest error: {0}")]
 	ManifestError(#[from] cargo_toml::Error),
 	/// An error occurred while attempting to retrieve the manifest path.
@@ -27,12 +41,27 @@ pub enum Error {
 	/// An error occurred while parsing the provided secret URI.
 	#[error("Failed to parse secret URI: {0}")]
 	ParseSecretURI(String),
+	/// An error occurred while storing or unlocking the encrypted secrets of signers.
+	#[error("Secrets error: {0}")]
+	Secrets(String),
 	#[error("SourceError error: {0}")]
 	SourceError(#[from] sourcing::Error),
+	/// An error occurred whilst interacting with a chain using `subxt`, boxed due to its size.
+	#[error("Subxt error: {0}")]
+	SubXtError(Box<subxt::Error>),
 	#[error("TemplateError error: {0}")]
 	TemplateError(#[from] templates::Error),
 	#[error("Unsupported command: {0}")]
 	UnsupportedCommand(String),
 	#[error("Unsupported platform: {arch} {os}")]
 	UnsupportedPlatform { arch: &'static str, os: &'static str },
+	/// An error occurred while upgrading the dependencies of a project.
+	#[error("Upgrade error: {0}")]
+	Upgrade(String),
+}
+
+impl From<subxt::Error> for Error {
+	fn from(error: subxt::Error) -> Self {
+		Self::SubXtError(Box::new(error))
+	}
 }
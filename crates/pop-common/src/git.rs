@@ -1,13 +1,16 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::{errors::Error, polkadot_sdk::parse_latest_tag, APP_USER_AGENT};
+use crate::{errors::Error, polkadot_sdk::parse_latest_tag, Status, APP_USER_AGENT};
 use anyhow::Result;
 use git2::{
-	build::RepoBuilder, FetchOptions, IndexAddOption, RemoteCallbacks, Repository as GitRepository,
-	ResetType,
+	build::{CheckoutBuilder, RepoBuilder},
+	FetchOptions, IndexAddOption, RemoteCallbacks, Repository as GitRepository, ResetType,
 };
 use git2_credentials::CredentialHandler;
-use std::{fs, path::Path};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
 use url::Url;
 
 /// A helper for handling Git operations.
@@ -46,60 +49,166 @@ impl Git {
 		Ok(repo.clone(&ssh_url, working_dir)?)
 	}
 
-	/// Clone a Git repository and degit it.
+	/// Clone a Git repository and degit it, fetching only the required reference rather than its
+	/// full history. Falls back to downloading the source archive of the reference should fetching
+	/// it via Git fail, although such archives exclude the contents of any submodules.
 	///
 	/// # Arguments
 	///
 	/// * `url` - the URL of the repository to clone.
 	/// * `target` - location where the repository will be cloned.
 	/// * `tag_version` - the specific tag or version of the repository to use
+	/// * `status` - used to observe status updates.
 	pub fn clone_and_degit(
 		url: &str,
 		target: &Path,
 		tag_version: Option<String>,
+		status: &impl Status,
 	) -> Result<Option<String>> {
-		let repo = match GitRepository::clone(url, target) {
-			Ok(repo) => repo,
-			Err(_e) =>
-				Self::ssh_clone_and_degit(url::Url::parse(url).map_err(Error::from)?, target)?,
+		let url = Url::parse(url).map_err(Error::from)?;
+		// Resolve the latest release up front, so that only it needs to be fetched.
+		let tag = match tag_version {
+			Some(tag_version) => Some(tag_version),
+			None => {
+				status.update("Resolving the latest release...");
+				Self::fetch_latest_remote_tag(&url)
+			},
 		};
-
-		if let Some(tag_version) = tag_version {
-			let (object, reference) = repo.revparse_ext(&tag_version).expect("Object not found");
-			repo.checkout_tree(&object, None).expect("Failed to checkout");
-			match reference {
-				// gref is an actual reference like branches or tags
-				Some(gref) => repo.set_head(gref.name().unwrap()),
-				// this is a commit, not a reference
-				None => repo.set_head_detached(object.id()),
+		if let Err(e) = Self::shallow_clone_and_degit(&url, target, tag.as_deref(), status) {
+			let Some(tag) = tag.as_deref() else { return Err(e) };
+			status.update("Fetching via git failed, downloading the source archive instead...");
+			if target.exists() {
+				fs::remove_dir_all(target)?;
 			}
-			.expect("Failed to set HEAD");
-
-			let git_dir = repo.path();
-			fs::remove_dir_all(git_dir)?;
-			return Ok(Some(tag_version));
+			Self::download_source_archive(&url, tag, target).map_err(|_| e)?;
 		}
+		Ok(tag)
+	}
 
-		// fetch tags from remote
-		let release = Self::fetch_latest_tag(&repo);
-
-		let git_dir = repo.path();
-		fs::remove_dir_all(git_dir)?;
-		// Or by default the last one
-		Ok(release)
+	/// Fetches a single reference of a repository (or its default branch) without its history,
+	/// along with any submodules, into the target before degitting it.
+	fn shallow_clone_and_degit(
+		url: &Url,
+		target: &Path,
+		reference: Option<&str>,
+		status: &impl Status,
+	) -> Result<()> {
+		let repo = GitRepository::init(target)?;
+		let object = match Self::shallow_fetch(&repo, url.as_str(), reference, false, status) {
+			Ok(object) => object,
+			Err(e) => {
+				let ssh_url = GitHub::convert_to_ssh_url(url);
+				Self::shallow_fetch(&repo, &ssh_url, reference, true, status).map_err(|_| e)?
+			},
+		};
+		repo.checkout_tree(&object, Some(CheckoutBuilder::new().force()))?;
+		repo.set_head_detached(object.id())?;
+		Self::update_submodules(&repo, status)?;
+		fs::remove_dir_all(repo.path())?;
+		Ok(())
 	}
 
-	/// For users that have ssh configuration for cloning repositories.
-	fn ssh_clone_and_degit(url: Url, target: &Path) -> Result<GitRepository> {
-		let ssh_url = GitHub::convert_to_ssh_url(&url);
-		// Prepare callback and fetch options.
+	/// Fetches a single reference from a remote with a depth of one, returning the fetched commit.
+	fn shallow_fetch<'a>(
+		repo: &'a GitRepository,
+		url: &str,
+		reference: Option<&str>,
+		ssh: bool,
+		status: &impl Status,
+	) -> Result<git2::Object<'a>> {
 		let mut fo = FetchOptions::new();
-		Self::set_up_ssh_fetch_options(&mut fo)?;
-		// Prepare builder and clone.
-		let mut builder = RepoBuilder::new();
-		builder.fetch_options(fo);
-		let repo = builder.clone(&ssh_url, target)?;
-		Ok(repo)
+		// Shallow fetches are not supported by the local transport.
+		if !url.starts_with("file://") {
+			fo.depth(1);
+		}
+		let mut callbacks = RemoteCallbacks::new();
+		if ssh {
+			let git_config = git2::Config::open_default()
+				.map_err(|e| Error::Config(format!("Cannot open git configuration: {}", e)))?;
+			let mut ch = CredentialHandler::new(git_config);
+			callbacks.credentials(move |url, username, allowed| {
+				ch.try_next_credential(url, username, allowed)
+			});
+		}
+		callbacks.transfer_progress(|progress| {
+			if progress.total_objects() > 0 {
+				let pct = progress.received_objects() * 100 / progress.total_objects();
+				status.progress("fetch", pct as u8);
+			}
+			true
+		});
+		fo.remote_callbacks(callbacks);
+		let mut remote = repo.remote_anonymous(url)?;
+		remote.fetch(&[reference.unwrap_or("HEAD")], Some(&mut fo), None)?;
+		Ok(repo.revparse_single("FETCH_HEAD")?.peel(git2::ObjectType::Commit)?)
+	}
+
+	/// Updates any submodules of a repository recursively, removing their git links once done.
+	fn update_submodules(repo: &GitRepository, status: &impl Status) -> Result<()> {
+		let workdir = repo.workdir().ok_or(Error::Git("the repository is bare".into()))?;
+		for mut submodule in repo.submodules()? {
+			status
+				.update(&format!("Fetching submodule {}...", submodule.name().unwrap_or_default()));
+			submodule.update(true, None)?;
+			Self::update_submodules(&submodule.open()?, status)?;
+			fs::remove_file(workdir.join(submodule.path()).join(".git"))?;
+		}
+		Ok(())
+	}
+
+	/// Fetches the latest release of a remote repository from its tags, without cloning it.
+	fn fetch_latest_remote_tag(url: &Url) -> Option<String> {
+		let mut remote = git2::Remote::create_detached(url.as_str()).ok()?;
+		remote.connect(git2::Direction::Fetch).ok()?;
+		let refs = remote.list().ok()?;
+		let tags = refs
+			.iter()
+			.filter_map(|r| r.name().strip_prefix("refs/tags/"))
+			// Skip the peeled commits of annotated tags.
+			.filter(|t| !t.ends_with("^{}"))
+			.collect();
+		parse_latest_tag(tags)
+	}
+
+	/// Downloads the source archive of a reference of a GitHub repository into the target.
+	fn download_source_archive(url: &Url, reference: &str, target: &Path) -> Result<()> {
+		let repo = GitHub::parse(url.as_str())?;
+		let archive =
+			format!("https://github.com/{}/{}/archive/{reference}.tar.gz", repo.org, repo.name);
+		// Git operations are blocking, so the archive is downloaded using a separate runtime on
+		// its own thread, allowing it to also be used within an asynchronous context.
+		let bytes = std::thread::scope(|s| {
+			s.spawn(|| -> Result<Vec<u8>> {
+				let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+				runtime.block_on(async {
+					let client =
+						reqwest::ClientBuilder::new().user_agent(APP_USER_AGENT).build()?;
+					let response = client.get(&archive).send().await?.error_for_status()?;
+					Ok(response.bytes().await?.to_vec())
+				})
+			})
+			.join()
+			.map_err(|_| Error::Git("failed to download the source archive".into()))?
+		})?;
+		Self::unpack_source_archive(&bytes, target)
+	}
+
+	/// Unpacks a source archive into the target, excluding the top-level directory of the archive.
+	fn unpack_source_archive(archive: &[u8], target: &Path) -> Result<()> {
+		let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+		for entry in archive.entries()? {
+			let mut entry = entry?;
+			let path: PathBuf = entry.path()?.components().skip(1).collect();
+			if path.as_os_str().is_empty() {
+				continue;
+			}
+			let dest = target.join(path);
+			if let Some(parent) = dest.parent() {
+				fs::create_dir_all(parent)?;
+			}
+			entry.unpack(dest)?;
+		}
+		Ok(())
 	}
 
 	fn set_up_ssh_fetch_options(fo: &mut FetchOptions) -> Result<()> {
@@ -115,12 +224,6 @@ impl Git {
 		Ok(())
 	}
 
-	/// Fetch the latest release from a repository
-	fn fetch_latest_tag(repo: &GitRepository) -> Option<String> {
-		let tags = repo.tag_names(None).ok()?;
-		parse_latest_tag(tags.iter().flatten().collect::<Vec<_>>())
-	}
-
 	/// Init a new git repository.
 	///
 	/// # Arguments
@@ -517,4 +620,81 @@ mod tests {
 			));
 		}
 	}
+	// Creates a local repository with a committed file per entry, tagging each commit as
+	// specified.
+	fn local_repository(path: &Path, commits: &[(&str, Option<&str>)]) -> Result<()> {
+		let repo = GitRepository::init(path)?;
+		let signature = git2::Signature::now("pop", "pop@r0gue.io")?;
+		for (file, tag) in commits {
+			fs::write(path.join(file), file)?;
+			let mut index = repo.index()?;
+			index.add_path(Path::new(file))?;
+			let tree = repo.find_tree(index.write_tree()?)?;
+			let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+			let parents: Vec<_> = parent.iter().collect();
+			let commit =
+				repo.commit(Some("HEAD"), &signature, &signature, file, &tree, &parents)?;
+			if let Some(tag) = tag {
+				repo.tag_lightweight(tag, &repo.find_object(commit, None)?, false)?;
+			}
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn clone_and_degit_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let source = temp_dir.path().join("source");
+		local_repository(&source, &[("a", Some("v1.0.0")), ("b", Some("v1.1.0")), ("c", None)])?;
+		let url = Url::from_file_path(&source).expect("valid path");
+
+		// The latest release is used by default.
+		let target = temp_dir.path().join("latest");
+		assert_eq!(Git::clone_and_degit(url.as_str(), &target, None, &())?, Some("v1.1.0".into()));
+		assert!(target.join("a").exists() && target.join("b").exists());
+		assert!(!target.join("c").exists());
+		assert!(!target.join(".git").exists());
+
+		let target = temp_dir.path().join("tagged");
+		assert_eq!(
+			Git::clone_and_degit(url.as_str(), &target, Some("v1.0.0".into()), &())?,
+			Some("v1.0.0".into())
+		);
+		assert!(target.join("a").exists() && !target.join("b").exists());
+		Ok(())
+	}
+
+	#[test]
+	fn clone_and_degit_without_releases_uses_default_branch() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let source = temp_dir.path().join("source");
+		local_repository(&source, &[("a", None), ("b", None)])?;
+		let url = Url::from_file_path(&source).expect("valid path");
+		let target = temp_dir.path().join("target");
+		assert_eq!(Git::clone_and_degit(url.as_str(), &target, None, &())?, None);
+		assert!(target.join("a").exists() && target.join("b").exists());
+		assert!(!target.join(".git").exists());
+		Ok(())
+	}
+
+	#[test]
+	fn unpack_source_archive_works() -> Result<()> {
+		let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+			Vec::new(),
+			flate2::Compression::default(),
+		));
+		let contents = b"// lib";
+		let mut header = tar::Header::new_gnu();
+		header.set_size(contents.len() as u64);
+		header.set_cksum();
+		builder.append_data(&mut header, "template-v1.0.0/src/lib.rs", &contents[..])?;
+		let archive = builder.into_inner()?.finish()?;
+
+		let temp_dir = tempfile::tempdir()?;
+		let target = temp_dir.path().join("target");
+		Git::unpack_source_archive(&archive, &target)?;
+		assert_eq!(fs::read_to_string(target.join("src/lib.rs"))?, "// lib");
+		assert!(!target.join("template-v1.0.0").exists());
+		Ok(())
+	}
 }
@@ -0,0 +1,353 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{config::config_dir, Error};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use crypto_secretbox::{
+	aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+	Key, Nonce, XSalsa20Poly1305,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+	str::FromStr,
+	sync::{Mutex, OnceLock},
+};
+use subxt::utils::AccountId32;
+use subxt_signer::{polkadot_js_compat::decrypt_json, sr25519::Keypair};
+
+/// The prefix of a secret URI referring to an account within the keystore, e.g.
+/// `keystore:savings`.
+pub const KEYSTORE_PREFIX: &str = "keystore:";
+/// The version of the encoding of accounts exported by polkadot.js.
+const ENCODING_VERSION: &str = "3";
+/// The scrypt parameters used by polkadot.js, being the only ones supported: N = 2^15, p = 1 and
+/// r = 8.
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_P: u32 = 1;
+const SCRYPT_R: u32 = 8;
+/// The length of the salt, which precedes the scrypt parameters and the nonce.
+const SALT_LENGTH: usize = 32;
+/// The length of the salt and scrypt parameters, followed by the nonce and then the ciphertext.
+const PARAMS_LENGTH: usize = SALT_LENGTH + 12;
+const NONCE_LENGTH: usize = 24;
+
+/// The accounts unlocked for signing during the current process, by name.
+static UNLOCKED: OnceLock<Mutex<HashMap<String, Keypair>>> = OnceLock::new();
+
+/// An account within the keystore.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Account {
+	/// The name of the account, by which it is referred to via `keystore:<name>`.
+	pub name: String,
+	/// The address of the account.
+	pub address: AccountId32,
+}
+
+/// A keystore of sr25519 accounts, each stored as a JSON file in the format exported by
+/// polkadot.js and compatible wallets such as Talisman, encrypted with a password.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keystore {
+	path: PathBuf,
+}
+
+impl Keystore {
+	/// A keystore within the specified directory.
+	///
+	/// # Arguments
+	/// * `path` - The directory containing the accounts.
+	pub fn new(path: &Path) -> Self {
+		Self { path: path.to_path_buf() }
+	}
+
+	/// The keystore within the configuration directory of the user.
+	pub fn open() -> Result<Self, Error> {
+		Ok(Self::new(&config_dir()?.join("keystore")))
+	}
+
+	/// Imports an account exported by polkadot.js or a compatible wallet, re-encrypting it with
+	/// the password of the keystore.
+	///
+	/// # Arguments
+	/// * `json` - The exported account.
+	/// * `password` - The password with which the account was exported.
+	/// * `keystore_password` - The password with which the account is stored.
+	/// * `name` - The name of the account, otherwise the name with which it was exported.
+	pub fn import(
+		&self,
+		json: &str,
+		password: &str,
+		keystore_password: &str,
+		name: Option<&str>,
+	) -> Result<Account, Error> {
+		let mut pair: KeyPair = serde_json::from_str(json)
+			.map_err(|e| Error::Keystore(format!("invalid account JSON: {e}")))?;
+		let name = name
+			.map(String::from)
+			.or_else(|| pair.meta.get("name").and_then(|n| n.as_str()).map(String::from))
+			.ok_or_else(|| Error::Keystore("the account has no name".into()))?;
+		if !is_valid_name(&name) {
+			return Err(Error::Keystore(format!(
+				"invalid account name `{name}`: only letters, numbers, `-` and `_` are supported"
+			)));
+		}
+		if self.contains(&name) {
+			return Err(Error::Keystore(format!("an account named `{name}` already exists")));
+		}
+		let keys = decrypt(&pair, password)?;
+		pair.encoded = encrypt(&keys, keystore_password)?;
+		pair.meta.insert("name".into(), Value::String(name.clone()));
+		let json = serde_json::to_string_pretty(&pair)?;
+		// Ensure the account can be decrypted before it is stored.
+		let keypair = decrypt_json(&json, keystore_password)
+			.map_err(|e| Error::Keystore(format!("invalid account: {e}")))?;
+		fs::create_dir_all(&self.path)?;
+		fs::write(self.account_path(&name), json)?;
+		Ok(Account { name, address: keypair.public_key().to_account_id() })
+	}
+
+	/// The accounts within the keystore, ordered by name.
+	pub fn accounts(&self) -> Result<Vec<Account>, Error> {
+		if !self.path.exists() {
+			return Ok(Vec::new());
+		}
+		let mut accounts = Vec::new();
+		for entry in fs::read_dir(&self.path)? {
+			let path = entry?.path();
+			let Some(name) = path
+				.file_stem()
+				.filter(|_| path.extension().is_some_and(|e| e == "json"))
+				.and_then(|n| n.to_str())
+			else {
+				continue;
+			};
+			accounts.push(Account { name: name.to_string(), address: read(&path)?.address()? });
+		}
+		accounts.sort_by(|a, b| a.name.cmp(&b.name));
+		Ok(accounts)
+	}
+
+	/// Whether the keystore contains an account with the specified name.
+	///
+	/// # Arguments
+	/// * `name` - The name of the account.
+	pub fn contains(&self, name: &str) -> bool {
+		self.account_path(name).exists()
+	}
+
+	/// Unlocks an account for signing, for the remainder of the current process, via the secret
+	/// URI `keystore:<name>`.
+	///
+	/// # Arguments
+	/// * `name` - The name of the account.
+	/// * `password` - The password of the keystore.
+	pub fn unlock(&self, name: &str, password: &str) -> Result<Account, Error> {
+		let path = self.account_path(name);
+		if !path.exists() {
+			return Err(Error::Keystore(format!("the account `{name}` was not found")));
+		}
+		let keypair = decrypt_json(&fs::read_to_string(path)?, password)
+			.map_err(|e| Error::Keystore(format!("unable to unlock `{name}`: {e}")))?;
+		let address = keypair.public_key().to_account_id();
+		unlocked().insert(name.to_string(), keypair);
+		Ok(Account { name: name.to_string(), address })
+	}
+
+	fn account_path(&self, name: &str) -> PathBuf {
+		self.path.join(format!("{name}.json"))
+	}
+}
+
+/// The name of the keystore account referred to by a secret URI, if any.
+///
+/// # Arguments
+/// * `suri` - The secret URI, e.g. `keystore:savings`.
+pub fn keystore_account(suri: &str) -> Option<&str> {
+	suri.strip_prefix(KEYSTORE_PREFIX)
+}
+
+/// Whether a keystore account is unlocked for signing.
+///
+/// # Arguments
+/// * `name` - The name of the account.
+pub fn is_unlocked(name: &str) -> bool {
+	unlocked().contains_key(name)
+}
+
+/// The keypair of a keystore account, if unlocked.
+///
+/// # Arguments
+/// * `name` - The name of the account.
+pub(crate) fn unlocked_keypair(name: &str) -> Option<Keypair> {
+	unlocked().get(name).cloned()
+}
+
+fn unlocked() -> std::sync::MutexGuard<'static, HashMap<String, Keypair>> {
+	UNLOCKED.get_or_init(Default::default).lock().expect("lock is not poisoned")
+}
+
+// An account in the format exported by polkadot.js.
+#[derive(Deserialize, Serialize)]
+struct KeyPair {
+	encoded: String,
+	encoding: Encoding,
+	address: String,
+	#[serde(default)]
+	meta: Map<String, Value>,
+}
+
+impl KeyPair {
+	fn address(&self) -> Result<AccountId32, Error> {
+		AccountId32::from_str(&self.address)
+			.map_err(|e| Error::Keystore(format!("invalid address {}: {e:?}", self.address)))
+	}
+}
+
+#[derive(Deserialize, Serialize)]
+struct Encoding {
+	content: Vec<String>,
+	#[serde(rename = "type")]
+	kind: Vec<String>,
+	version: String,
+}
+
+fn read(path: &Path) -> Result<KeyPair, Error> {
+	serde_json::from_str(&fs::read_to_string(path)?)
+		.map_err(|e| Error::Keystore(format!("invalid account {}: {e}", path.display())))
+}
+
+fn is_valid_name(name: &str) -> bool {
+	!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// Derives the key with which the keys of an account are encrypted from the password.
+fn derive_key(password: &str, salt: &[u8]) -> Key {
+	let params =
+		scrypt::Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P, 32).expect("parameters are valid");
+	let mut key = Key::default();
+	scrypt::scrypt(password.as_bytes(), salt, &params, &mut key).expect("key is 32 bytes");
+	key
+}
+
+// Decrypts the PKCS#8 encoded keys of an account.
+fn decrypt(pair: &KeyPair, password: &str) -> Result<Vec<u8>, Error> {
+	let Encoding { content, kind, version } = &pair.encoding;
+	let contains = |values: &[String], value: &str| values.iter().any(|v| v == value);
+	if version != ENCODING_VERSION ||
+		!contains(content, "pkcs8") ||
+		!contains(content, "sr25519") ||
+		!contains(kind, "scrypt") ||
+		!contains(kind, "xsalsa20-poly1305")
+	{
+		return Err(Error::Keystore(
+			"unsupported encoding: only sr25519 accounts encrypted via scrypt are supported".into(),
+		));
+	}
+	let encoded = STANDARD
+		.decode(&pair.encoded)
+		.map_err(|e| Error::Keystore(format!("invalid encoded account: {e}")))?;
+	if encoded.len() < PARAMS_LENGTH + NONCE_LENGTH {
+		return Err(Error::Keystore("invalid encoded account: too short".into()));
+	}
+	let (params, encrypted) = encoded.split_at(PARAMS_LENGTH);
+	let param = |i: usize| {
+		let start = SALT_LENGTH + i * 4;
+		u32::from_le_bytes(params[start..start + 4].try_into().expect("4 bytes"))
+	};
+	if (param(0), param(1), param(2)) != (1 << SCRYPT_LOG_N, SCRYPT_P, SCRYPT_R) {
+		return Err(Error::Keystore("unsupported scrypt parameters".into()));
+	}
+	let (nonce, ciphertext) = encrypted.split_at(NONCE_LENGTH);
+	XSalsa20Poly1305::new(&derive_key(password, &params[..SALT_LENGTH]))
+		.decrypt(Nonce::from_slice(nonce), ciphertext)
+		.map_err(|_| Error::Keystore("invalid password".into()))
+}
+
+// Encrypts the PKCS#8 encoded keys of an account, with a new salt and nonce.
+fn encrypt(keys: &[u8], password: &str) -> Result<String, Error> {
+	let mut salt = [0u8; SALT_LENGTH];
+	OsRng.fill_bytes(&mut salt);
+	let nonce = XSalsa20Poly1305::generate_nonce(&mut OsRng);
+	let ciphertext = XSalsa20Poly1305::new(&derive_key(password, &salt))
+		.encrypt(&nonce, keys)
+		.map_err(|e| Error::Keystore(format!("unable to encrypt the account: {e}")))?;
+	let params = [1u32 << SCRYPT_LOG_N, SCRYPT_P, SCRYPT_R].map(u32::to_le_bytes).concat();
+	Ok(STANDARD.encode([salt.as_slice(), &params, &nonce, &ciphertext].concat()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::create_signer;
+	use anyhow::Result;
+	use tempfile::tempdir;
+
+	// The account of Alice, as exported by polkadot.js with the password `whoisalice`.
+	const ALICE: &str = r#"{
+		"encoded": "DumgApKCTqoCty1OZW/8WS+sgo6RdpHhCwAkA2IoDBMAgAAAAQAAAAgAAAB6IG/q24EeVf0JqWqcBd5m2tKq5BlyY84IQ8oamLn9DZe9Ouhgunr7i36J1XxUnTI801axqL/ym1gil0U8440Qvj0lFVKwGuxq38zuifgoj0B3Yru0CI6QKEvQPU5xxj4MpyxdSxP+2PnTzYao0HDH0fulaGvlAYXfqtU89xrx2/z9z7IjSwS3oDFPXRQ9kAdDebtyCVreZ9Otw9v3",
+		"encoding": { "content": ["pkcs8", "sr25519"], "type": ["scrypt", "xsalsa20-poly1305"], "version": "3" },
+		"address": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+		"meta": { "genesisHash": "", "name": "Alice", "whenCreated": 1718265838755 }
+	}"#;
+
+	#[test]
+	fn import_and_unlock_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let keystore = Keystore::new(&temp_dir.path().join("keystore"));
+		assert!(keystore.accounts()?.is_empty());
+		let alice = create_signer("//Alice")?.public_key().to_account_id();
+
+		let account = keystore.import(ALICE, "whoisalice", "secret", None)?;
+		assert_eq!(account, Account { name: "Alice".into(), address: alice.clone() });
+		assert!(keystore.contains("Alice"));
+		assert_eq!(keystore.accounts()?, vec![account]);
+		assert!(matches!(
+			keystore.import(ALICE, "whoisalice", "secret", None),
+			Err(Error::Keystore(message)) if message == "an account named `Alice` already exists"
+		));
+		// The account is re-encrypted with the password of the keystore.
+		assert!(matches!(
+			keystore.unlock("Alice", "whoisalice"),
+			Err(Error::Keystore(message)) if message.starts_with("unable to unlock `Alice`")
+		));
+		assert!(matches!(create_signer("keystore:Alice"), Err(Error::KeyPairCreation(..))));
+		assert!(!is_unlocked("Alice"));
+		keystore.unlock("Alice", "secret")?;
+		assert!(is_unlocked("Alice"));
+		assert_eq!(create_signer("keystore:Alice")?.public_key().to_account_id(), alice);
+		Ok(())
+	}
+
+	#[test]
+	fn import_fails_with_invalid_account() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let keystore = Keystore::new(temp_dir.path());
+		assert!(matches!(
+			keystore.import(ALICE, "password", "secret", None),
+			Err(Error::Keystore(message)) if message == "invalid password"
+		));
+		assert!(matches!(
+			keystore.import(ALICE, "whoisalice", "secret", Some("my alice")),
+			Err(Error::Keystore(message)) if message.starts_with("invalid account name `my alice`")
+		));
+		let ethereum = ALICE.replace(r#""sr25519""#, r#""ethereum""#);
+		assert!(matches!(
+			keystore.import(&ethereum, "whoisalice", "secret", None),
+			Err(Error::Keystore(message)) if message.starts_with("unsupported encoding")
+		));
+		assert!(matches!(
+			keystore.import("{}", "whoisalice", "secret", None),
+			Err(Error::Keystore(message)) if message.starts_with("invalid account JSON")
+		));
+		assert!(keystore.accounts()?.is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn keystore_account_works() {
+		assert_eq!(keystore_account("keystore:savings"), Some("savings"));
+		assert_eq!(keystore_account("//Alice"), None);
+	}
+}
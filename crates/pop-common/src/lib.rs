@@ -13,19 +13,50 @@ pub use sourcing::set_executable_permission;
 pub use subxt::{Config, PolkadotConfig as DefaultConfig};
 pub use subxt_signer::sr25519::Keypair;
 pub use templates::extractor::extract_template_files;
+pub use test_report::TestReport;
 
+/// Provides functionality for storing named accounts, which are offered whenever an account is to
+/// be entered.
+pub mod address_book;
+/// Provides functionality for checking the balances involved in operations transferring value.
+pub mod balances;
 pub mod build;
+/// Provides functionality for generating CI pipelines.
+pub mod ci;
+/// Provides functionality for loading the user and project configuration.
+pub mod config;
+/// Provides functionality for deploying a project to hosted infrastructure and tracking the status
+/// of its deployments.
+pub mod deploy;
 pub mod errors;
 pub mod git;
 pub mod helpers;
+/// Provides functionality for importing accounts exported by polkadot.js into a local keystore,
+/// from which they are unlocked for signing.
+pub mod keystore;
 pub mod manifest;
 /// Provides functionality for formatting and resolving metadata types.
 pub mod metadata;
 pub mod polkadot_sdk;
+/// Provides functionality for rolling back incomplete operations when cancelled, such as removing
+/// partial downloads and terminating spawned processes.
+pub mod rollback;
+/// Provides functionality for fuzzy searching, such as for pallets and extrinsics by name.
+pub mod search;
+/// Provides functionality for storing the secret URIs of signers, encrypted with a master
+/// password, and unlocking them once per shell session.
+pub mod secrets;
 /// Provides functionality for creating a signer from a secret URI.
 pub mod signer;
 pub mod sourcing;
 pub mod templates;
+/// Provides functionality for running tests and reporting their results, e.g. as JUnit XML.
+pub mod test_report;
+/// Provides functionality for converting amounts between whole tokens and their smallest unit.
+pub mod units;
+/// Provides functionality for upgrading the polkadot-sdk and ink! dependencies of a project, and
+/// detecting the API breakages which result.
+pub mod upgrade;
 
 static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
@@ -33,6 +64,9 @@ static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_P
 pub trait Status {
 	/// Update the observer with the provided `status`.
 	fn update(&self, status: &str);
+	/// Update the observer with the progress of the current `phase` (e.g. "download"), as a
+	/// percentage.
+	fn progress(&self, _phase: &str, _pct: u8) {}
 }
 
 impl Status for () {
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use std::{
+	fmt::{Display, Formatter},
+	fs::{remove_dir_all, remove_file},
+	path::PathBuf,
+	process::Command,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Mutex,
+	},
+};
+
+/// The incomplete operations of the current process.
+static PENDING: Registry = Registry::new();
+
+/// An incomplete operation, which is rolled back by [`roll_back`] if the process is cancelled
+/// before it is dropped (e.g. upon completion of the operation).
+#[must_use = "the operation is no longer rolled back once dropped"]
+pub struct Rollback {
+	id: u64,
+	registry: &'static Registry,
+}
+
+impl Rollback {
+	/// Removes the file or directory at the specified path if rolled back, such as a partial
+	/// download or a temporary working directory.
+	///
+	/// # Arguments
+	/// * `path` - The path of the file or directory.
+	pub fn remove(path: impl Into<PathBuf>) -> Self {
+		PENDING.register(Resource::Path(path.into()))
+	}
+
+	/// Terminates the process with the specified identifier if rolled back, such as a spawned
+	/// node or benchmarking process.
+	///
+	/// # Arguments
+	/// * `pid` - The identifier of the process.
+	pub fn terminate(pid: u32) -> Self {
+		PENDING.register(Resource::Process(pid))
+	}
+}
+
+impl Drop for Rollback {
+	fn drop(&mut self) {
+		self.registry.deregister(self.id);
+	}
+}
+
+/// Rolls back all incomplete operations, most recent first, returning a description of each
+/// operation rolled back.
+pub fn roll_back() -> Vec<String> {
+	PENDING.roll_back()
+}
+
+// A resource of an incomplete operation.
+enum Resource {
+	// A file or directory.
+	Path(PathBuf),
+	// A process, by identifier.
+	Process(u32),
+}
+
+impl Resource {
+	// Rolls back the resource, returning whether anything needed to be rolled back.
+	fn roll_back(&self) -> bool {
+		match self {
+			Resource::Path(path) if path.is_dir() => remove_dir_all(path).is_ok(),
+			Resource::Path(path) => remove_file(path).is_ok(),
+			Resource::Process(pid) => Command::new("kill")
+				.args(["-s", "TERM", &pid.to_string()])
+				.output()
+				.is_ok_and(|o| o.status.success()),
+		}
+	}
+}
+
+impl Display for Resource {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Resource::Path(path) => write!(f, "removed {}", path.display()),
+			Resource::Process(pid) => write!(f, "terminated process {pid}"),
+		}
+	}
+}
+
+// The resources of incomplete operations, by identifier.
+struct Registry {
+	next: AtomicU64,
+	resources: Mutex<Vec<(u64, Resource)>>,
+}
+
+impl Registry {
+	const fn new() -> Self {
+		Self { next: AtomicU64::new(0), resources: Mutex::new(Vec::new()) }
+	}
+
+	fn register(&'static self, resource: Resource) -> Rollback {
+		let id = self.next.fetch_add(1, Ordering::Relaxed);
+		self.resources.lock().expect("lock is not poisoned").push((id, resource));
+		Rollback { id, registry: self }
+	}
+
+	fn deregister(&self, id: u64) {
+		self.resources.lock().expect("lock is not poisoned").retain(|(i, _)| *i != id);
+	}
+
+	fn roll_back(&self) -> Vec<String> {
+		let resources = std::mem::take(&mut *self.resources.lock().expect("lock is not poisoned"));
+		resources
+			.into_iter()
+			.rev()
+			.filter(|(_, resource)| resource.roll_back())
+			.map(|(_, resource)| resource.to_string())
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs::{create_dir, File};
+
+	#[test]
+	fn roll_back_works() -> anyhow::Result<()> {
+		let registry = Box::leak(Box::new(Registry::new()));
+		let temp_dir = tempfile::tempdir()?;
+		let (file, dir) = (temp_dir.path().join("partial"), temp_dir.path().join("working"));
+		File::create(&file)?;
+		create_dir(&dir)?;
+		let _file = registry.register(Resource::Path(file.clone()));
+		let _dir = registry.register(Resource::Path(dir.clone()));
+		// Completed operations are not rolled back.
+		let complete = temp_dir.path().join("complete");
+		File::create(&complete)?;
+		drop(registry.register(Resource::Path(complete.clone())));
+		// Nothing is rolled back for resources which no longer exist.
+		let _missing = registry.register(Resource::Path(temp_dir.path().join("missing")));
+
+		assert_eq!(
+			registry.roll_back(),
+			[format!("removed {}", dir.display()), format!("removed {}", file.display())]
+		);
+		assert!(!file.exists() && !dir.exists() && complete.exists());
+		// Operations are only rolled back once.
+		assert!(registry.roll_back().is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn terminating_process_works() -> anyhow::Result<()> {
+		let registry = Box::leak(Box::new(Registry::new()));
+		let mut process = Command::new("sleep").arg("60").spawn()?;
+		let _process = registry.register(Resource::Process(process.id()));
+		assert_eq!(registry.roll_back(), [format!("terminated process {}", process.id())]);
+		assert!(!process.wait()?.success());
+		Ok(())
+	}
+}
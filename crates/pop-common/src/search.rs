@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: GPL-3.0
+
+/// The score of each character of the query matched.
+const MATCH: u32 = 1;
+/// The bonus for a match immediately following the previous match.
+const CONSECUTIVE: u32 = 4;
+/// The bonus for a match at the start of a word, such as `B` of `setBalance` or `b` of
+/// `set_balance`.
+const WORD_START: u32 = 8;
+/// The bonus for a match at the start of the candidate.
+const PREFIX: u32 = 16;
+/// The bonus for a candidate matching the query exactly, ignoring case and separators.
+const EXACT: u32 = 64;
+
+/// Scores how well a candidate (e.g. the name of a pallet or extrinsic) matches a query, where a
+/// higher score is a better match, or `None` if the characters of the query do not all occur in
+/// order within the candidate.
+///
+/// Matching ignores case and word separators, favouring matches at the start of the candidate and
+/// of words within it (whether camel or snake case), as well as consecutive matches. An empty
+/// query matches every candidate equally.
+///
+/// # Arguments
+/// * `query` - The query, such as the input of a user.
+/// * `candidate` - The candidate to be scored.
+pub fn score(query: &str, candidate: &str) -> Option<u32> {
+	let query: Vec<char> = query
+		.chars()
+		.filter(|c| !is_separator(*c))
+		.flat_map(char::to_lowercase)
+		.collect();
+	let candidate = characters(candidate);
+	if query.is_empty() {
+		return Some(0);
+	}
+	// The best score of matching the query up to each character, with the last such character
+	// matched at each position of the candidate.
+	let mut best: Vec<Option<u32>> = vec![None; candidate.len()];
+	for (i, q) in query.iter().enumerate() {
+		let mut next = vec![None; candidate.len()];
+		// The best score of any previous match before the current position.
+		let mut preceding: Option<u32> = None;
+		for (j, (c, word_start)) in candidate.iter().enumerate() {
+			let previous = match i {
+				0 => Some(0),
+				_ => {
+					let consecutive =
+						j.checked_sub(1).and_then(|k| best[k]).map(|s| s + CONSECUTIVE);
+					preceding.max(consecutive)
+				},
+			};
+			if c == q {
+				if let Some(previous) = previous {
+					let bonus = match (j, word_start) {
+						(0, _) => PREFIX + WORD_START,
+						(_, true) => WORD_START,
+						_ => 0,
+					};
+					next[j] = Some(previous + MATCH + bonus);
+				}
+			}
+			// Only matches strictly before the next position can precede it.
+			preceding = preceding.max(best[j]);
+		}
+		best = next;
+	}
+	let score = best.into_iter().flatten().max()?;
+	Some(if query.len() == candidate.len() { score + EXACT } else { score })
+}
+
+/// Searches for the items best matching a query, ordered from best to worst match, with shorter
+/// keys preferred for equal scores and the original order retained otherwise. Items which do not
+/// match are excluded, whilst an empty query matches all items in their original order.
+///
+/// # Arguments
+/// * `query` - The query, such as the input of a user.
+/// * `items` - The items to be searched.
+/// * `key` - The key of each item against which the query is matched, such as its name.
+pub fn search<'a, T>(query: &str, items: &'a [T], key: impl Fn(&T) -> &str) -> Vec<&'a T> {
+	if query.chars().all(is_separator) {
+		return items.iter().collect();
+	}
+	let mut matches: Vec<_> = items
+		.iter()
+		.enumerate()
+		.filter_map(|(index, item)| {
+			let key = key(item);
+			score(query, key).map(|score| (score, key.chars().count(), index, item))
+		})
+		.collect();
+	matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+	matches.into_iter().map(|(.., item)| item).collect()
+}
+
+// The lowercase characters of a candidate, excluding separators, along with whether each starts a
+// word.
+fn characters(candidate: &str) -> Vec<(char, bool)> {
+	let mut characters = Vec::new();
+	let mut previous: Option<char> = None;
+	for c in candidate.chars() {
+		if !is_separator(c) {
+			let word_start = match previous {
+				None => true,
+				Some(p) =>
+					is_separator(p) ||
+						(p.is_lowercase() && c.is_uppercase()) ||
+						(p.is_alphabetic() && c.is_numeric()),
+			};
+			characters.extend(c.to_lowercase().map(|c| (c, word_start)));
+		}
+		previous = Some(c);
+	}
+	characters
+}
+
+fn is_separator(c: char) -> bool {
+	c == '_' || c == '-' || c.is_whitespace()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn score_works() {
+		// Characters must occur in order.
+		assert_eq!(score("xyz", "Balances"), None);
+		assert_eq!(score("sb", "Balances"), None);
+		assert_eq!(score("", "Balances"), Some(0));
+		// Case and separators are ignored.
+		assert_eq!(score("BALANCES", "Balances"), score("balances", "Balances"));
+		assert_eq!(
+			score("keep alive", "transfer_keep_alive"),
+			score("keepalive", "transfer_keep_alive")
+		);
+		// Exact matches are favoured.
+		assert!(score("balances", "Balances") > score("balances", "BalancesExtra"));
+		// Prefixes are favoured over matches elsewhere.
+		assert!(score("bal", "Balances") > score("bal", "SetBalance"));
+		// Word starts are favoured, whether camel or snake case.
+		assert!(score("tka", "transfer_keep_alive") > score("tka", "token_stakes"));
+		assert!(score("ps", "ParachainSystem") > score("ps", "Proxies"));
+		// Consecutive matches are favoured.
+		assert!(score("ock", "unlock") > score("ock", "unbond_stack"));
+	}
+
+	#[test]
+	fn score_finds_best_alignment() {
+		// A greedy match of `b` within `su[b]` would miss the word start of `Balance`.
+		assert_eq!(
+			score("sb", "subBalance"),
+			Some(MATCH + PREFIX + WORD_START + MATCH + WORD_START)
+		);
+	}
+
+	#[test]
+	fn search_works() {
+		let pallets = ["System", "Balances", "ParachainSystem", "Sudo", "AssetRate", "Assets"];
+		assert_eq!(search("sys", &pallets, |p| p), [&"System", &"ParachainSystem"]);
+		// Shorter keys are favoured for equal scores.
+		assert_eq!(search("asset", &pallets, |p| p), [&"Assets", &"AssetRate"]);
+		// The original order is retained otherwise.
+		assert_eq!(search("", &pallets, |p| p), pallets.iter().collect::<Vec<_>>());
+		assert!(search("xyz", &pallets, |p| p).is_empty());
+	}
+}
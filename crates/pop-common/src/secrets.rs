@@ -0,0 +1,447 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{config::config_dir, Error};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{
+	engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+	Engine,
+};
+use crypto_secretbox::{
+	aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+	Key, Nonce, XSalsa20Poly1305,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::{BTreeMap, HashMap},
+	fs,
+	io::Write,
+	path::{Path, PathBuf},
+	sync::{Mutex, OnceLock},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The prefix of a secret URI referring to a stored secret, e.g. `secret:savings`.
+pub const SECRET_PREFIX: &str = "secret:";
+/// The environment variable holding the session with which stored secrets are unlocked, as set
+/// via `eval "$(pop keystore unlock)"`.
+pub const SESSION_VARIABLE: &str = "POP_SESSION";
+/// How long a session remains unlocked.
+pub const SESSION_DURATION: Duration = Duration::from_secs(12 * 60 * 60);
+/// The name of the file containing the stored secrets, within the pop configuration directory.
+const SECRETS_FILE_NAME: &str = "secrets.json";
+/// A known value, encrypted to verify the master password.
+const CHECK: &[u8] = b"pop";
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 24;
+
+/// The secrets unlocked for signing during the current process, by name.
+static LOADED: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+/// The key with which the secrets are encrypted, derived from the master password.
+#[derive(Clone)]
+pub struct SessionKey(Key);
+
+/// A file of secret URIs, each encrypted with a key derived from a master password via
+/// Argon2id.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Secrets {
+	path: PathBuf,
+}
+
+impl Secrets {
+	/// The secrets within the specified file.
+	///
+	/// # Arguments
+	/// * `path` - The path to the file.
+	pub fn new(path: &Path) -> Self {
+		Self { path: path.to_path_buf() }
+	}
+
+	/// The secrets within the pop configuration directory.
+	pub fn open() -> Result<Self, Error> {
+		Ok(Self::new(&config_dir()?.join(SECRETS_FILE_NAME)))
+	}
+
+	/// Whether a master password has been set, i.e. whether any secrets have been stored.
+	pub fn exists(&self) -> bool {
+		self.path.exists()
+	}
+
+	/// Sets the master password, returning the key with which secrets are then stored.
+	///
+	/// # Arguments
+	/// * `password` - The master password.
+	pub fn create(&self, password: &str) -> Result<SessionKey, Error> {
+		if self.exists() {
+			return Err(Error::Secrets("a master password has already been set".into()));
+		}
+		let mut salt = [0u8; SALT_LENGTH];
+		OsRng.fill_bytes(&mut salt);
+		let key = derive_key(password, &salt)?;
+		let file = SecretsFile {
+			salt: STANDARD.encode(salt),
+			check: encrypt(&key, CHECK)?,
+			session: None,
+			secrets: BTreeMap::new(),
+		};
+		self.write(&file)?;
+		Ok(key)
+	}
+
+	/// Unlocks the secrets with the master password, returning the key with which they are
+	/// encrypted.
+	///
+	/// # Arguments
+	/// * `password` - The master password.
+	pub fn unlock(&self, password: &str) -> Result<SessionKey, Error> {
+		let file = self.read()?;
+		let salt = STANDARD
+			.decode(&file.salt)
+			.map_err(|e| Error::Secrets(format!("invalid salt: {e}")))?;
+		let key = derive_key(password, &salt)?;
+		decrypt(&key, &file.check).map_err(|_| Error::Secrets("invalid master password".into()))?;
+		Ok(key)
+	}
+
+	/// Starts a session lasting [`SESSION_DURATION`], replacing any previous session, and
+	/// returns the token with which it is resumed, e.g. via the environment variable
+	/// [`SESSION_VARIABLE`].
+	///
+	/// The token is a new random key, with which the key of the secrets is stored along with the
+	/// expiry of the session. The key of the secrets is therefore never exported, and the token is
+	/// of no use without the stored secrets or once the session has expired.
+	///
+	/// # Arguments
+	/// * `key` - The key of the secrets.
+	pub fn start_session(&self, key: &SessionKey) -> Result<String, Error> {
+		self.start_session_until(key, now()? + SESSION_DURATION.as_secs())
+	}
+
+	// Starts a session expiring at the specified time, in seconds since the Unix epoch.
+	fn start_session_until(&self, key: &SessionKey, expires: u64) -> Result<String, Error> {
+		let mut file = self.read()?;
+		decrypt(key, &file.check).map_err(|_| Error::Secrets("invalid session".into()))?;
+		let mut token = [0u8; 32];
+		OsRng.fill_bytes(&mut token);
+		let session = [&expires.to_le_bytes()[..], key.0.as_slice()].concat();
+		file.session = Some(encrypt(&SessionKey(*Key::from_slice(&token)), &session)?);
+		self.write(&file)?;
+		Ok(STANDARD.encode(token))
+	}
+
+	/// Resumes a session, returning the key of the secrets unless the session has expired.
+	///
+	/// # Arguments
+	/// * `token` - The session token, as returned by [`Secrets::start_session`].
+	pub fn resume(&self, token: &str) -> Result<SessionKey, Error> {
+		let invalid = || Error::Secrets("invalid session".into());
+		let token = STANDARD
+			.decode(token.trim())
+			.ok()
+			.filter(|t| t.len() == 32)
+			.map(|t| SessionKey(*Key::from_slice(&t)))
+			.ok_or_else(invalid)?;
+		let mut file = self.read()?;
+		let session = file
+			.session
+			.as_deref()
+			.ok_or_else(|| Error::Secrets("no session has been unlocked".into()))?;
+		let session = decrypt(&token, session)
+			.ok()
+			.filter(|s| s.len() == 8 + 32)
+			.ok_or_else(invalid)?;
+		let (expires, key) = session.split_at(8);
+		let expires = u64::from_le_bytes(expires.try_into().expect("length checked above"));
+		if expires <= now()? {
+			// An expired session can no longer be resumed, even with the stored secrets.
+			file.session = None;
+			self.write(&file)?;
+			return Err(Error::Secrets("the session has expired".into()));
+		}
+		let key = SessionKey(*Key::from_slice(key));
+		decrypt(&key, &file.check).map_err(|_| invalid())?;
+		Ok(key)
+	}
+
+	/// The names of the stored secrets.
+	pub fn names(&self) -> Result<Vec<String>, Error> {
+		if !self.exists() {
+			return Ok(Vec::new());
+		}
+		Ok(self.read()?.secrets.into_keys().collect())
+	}
+
+	/// Stores a secret URI, to be referred to via `secret:<name>`.
+	///
+	/// # Arguments
+	/// * `key` - The key of the session.
+	/// * `name` - The name of the secret.
+	/// * `suri` - The secret URI.
+	pub fn store(&self, key: &SessionKey, name: &str, suri: &str) -> Result<(), Error> {
+		if name.is_empty() ||
+			!name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+		{
+			return Err(Error::Secrets(format!(
+				"invalid name `{name}`: only letters, numbers, `-` and `_` are supported"
+			)));
+		}
+		let mut file = self.read()?;
+		if file.secrets.contains_key(name) {
+			return Err(Error::Secrets(format!("a secret named `{name}` already exists")));
+		}
+		decrypt(key, &file.check).map_err(|_| Error::Secrets("invalid session".into()))?;
+		file.secrets.insert(name.to_string(), encrypt(key, suri.as_bytes())?);
+		self.write(&file)
+	}
+
+	/// Decrypts a stored secret, to be used for signing via `secret:<name>` for the remainder of
+	/// the current process.
+	///
+	/// # Arguments
+	/// * `key` - The key of the session.
+	/// * `name` - The name of the secret.
+	pub fn load(&self, key: &SessionKey, name: &str) -> Result<(), Error> {
+		let file = self.read()?;
+		let secret = file
+			.secrets
+			.get(name)
+			.ok_or_else(|| Error::Secrets(format!("the secret `{name}` was not found")))?;
+		let suri = String::from_utf8(decrypt(key, secret)?)
+			.map_err(|_| Error::Secrets(format!("the secret `{name}` is invalid")))?;
+		loaded().insert(name.to_string(), suri);
+		Ok(())
+	}
+
+	fn read(&self) -> Result<SecretsFile, Error> {
+		if !self.exists() {
+			return Err(Error::Secrets("no secrets have been stored".into()));
+		}
+		serde_json::from_str(&fs::read_to_string(&self.path)?)
+			.map_err(|e| Error::Secrets(format!("invalid secrets {}: {e}", self.path.display())))
+	}
+
+	fn write(&self, file: &SecretsFile) -> Result<(), Error> {
+		if let Some(parent) = self.path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		let mut options = fs::OpenOptions::new();
+		options.write(true).create(true).truncate(true);
+		// Only readable by the user from creation, with any existing file restricted before being
+		// written.
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+			options.mode(0o600);
+			if self.path.exists() {
+				fs::set_permissions(&self.path, fs::Permissions::from_mode(0o600))?;
+			}
+		}
+		options
+			.open(&self.path)?
+			.write_all(serde_json::to_string_pretty(file)?.as_bytes())?;
+		Ok(())
+	}
+}
+
+/// The name of the stored secret referred to by a secret URI, if any.
+///
+/// # Arguments
+/// * `suri` - The secret URI, e.g. `secret:savings`.
+pub fn secret_name(suri: &str) -> Option<&str> {
+	suri.strip_prefix(SECRET_PREFIX)
+}
+
+/// Generates a random token, such as that with which requests to a local server are authorised.
+pub fn random_token() -> String {
+	let mut token = [0u8; 32];
+	OsRng.fill_bytes(&mut token);
+	URL_SAFE_NO_PAD.encode(token)
+}
+
+/// Whether a stored secret has been loaded for signing.
+///
+/// # Arguments
+/// * `name` - The name of the secret.
+pub fn is_loaded(name: &str) -> bool {
+	loaded().contains_key(name)
+}
+
+/// The secret URI of a stored secret, if loaded.
+///
+/// # Arguments
+/// * `name` - The name of the secret.
+pub(crate) fn loaded_secret(name: &str) -> Option<String> {
+	loaded().get(name).cloned()
+}
+
+fn loaded() -> std::sync::MutexGuard<'static, HashMap<String, String>> {
+	LOADED.get_or_init(Default::default).lock().expect("lock is not poisoned")
+}
+
+#[derive(Deserialize, Serialize)]
+struct SecretsFile {
+	salt: String,
+	check: String,
+	// The key of the secrets and the expiry of the current session, encrypted with the session
+	// token.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	session: Option<String>,
+	secrets: BTreeMap<String, String>,
+}
+
+// The current time, in seconds since the Unix epoch.
+fn now() -> Result<u64, Error> {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_secs())
+		.map_err(|e| Error::Secrets(format!("invalid system time: {e}")))
+}
+
+// Derives the key with which the secrets are encrypted from the master password.
+fn derive_key(password: &str, salt: &[u8]) -> Result<SessionKey, Error> {
+	let mut key = Key::default();
+	Argon2::new(Algorithm::Argon2id, Version::V0x13, Params::default())
+		.hash_password_into(password.as_bytes(), salt, &mut key)
+		.map_err(|e| Error::Secrets(format!("unable to derive the key: {e}")))?;
+	Ok(SessionKey(key))
+}
+
+// Encrypts a value, prefixed by a new nonce.
+fn encrypt(key: &SessionKey, value: &[u8]) -> Result<String, Error> {
+	let nonce = XSalsa20Poly1305::generate_nonce(&mut OsRng);
+	let ciphertext = XSalsa20Poly1305::new(&key.0)
+		.encrypt(&nonce, value)
+		.map_err(|e| Error::Secrets(format!("unable to encrypt the secret: {e}")))?;
+	Ok(STANDARD.encode([nonce.as_slice(), &ciphertext].concat()))
+}
+
+// Decrypts a value, prefixed by its nonce.
+fn decrypt(key: &SessionKey, value: &str) -> Result<Vec<u8>, Error> {
+	let value = STANDARD
+		.decode(value)
+		.ok()
+		.filter(|v| v.len() > NONCE_LENGTH)
+		.ok_or_else(|| Error::Secrets("invalid encrypted secret".into()))?;
+	let (nonce, ciphertext) = value.split_at(NONCE_LENGTH);
+	XSalsa20Poly1305::new(&key.0)
+		.decrypt(Nonce::from_slice(nonce), ciphertext)
+		.map_err(|_| Error::Secrets("unable to decrypt the secret".into()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::create_signer;
+	use anyhow::Result;
+	use tempfile::tempdir;
+
+	#[test]
+	fn store_and_load_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let secrets = Secrets::new(&temp_dir.path().join(SECRETS_FILE_NAME));
+		assert!(!secrets.exists());
+		assert!(secrets.names()?.is_empty());
+
+		let key = secrets.create("master")?;
+		assert!(matches!(secrets.create("master"), Err(Error::Secrets(..))));
+		secrets.store(&key, "stored", "//Bob")?;
+		assert_eq!(secrets.names()?, vec!["stored"]);
+		assert!(matches!(
+			secrets.store(&key, "stored", "//Bob"),
+			Err(Error::Secrets(message)) if message == "a secret named `stored` already exists"
+		));
+		assert!(matches!(
+			secrets.store(&key, "invalid name", "//Bob"),
+			Err(Error::Secrets(message)) if message.starts_with("invalid name")
+		));
+		// The secret URI is not stored in plain text.
+		assert!(!fs::read_to_string(temp_dir.path().join(SECRETS_FILE_NAME))?.contains("//Bob"));
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::PermissionsExt;
+			let metadata = fs::metadata(temp_dir.path().join(SECRETS_FILE_NAME))?;
+			assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+		}
+
+		assert!(matches!(
+			secrets.unlock("wrong"),
+			Err(Error::Secrets(message)) if message == "invalid master password"
+		));
+		let key = secrets.unlock("master")?;
+		assert!(matches!(create_signer("secret:stored"), Err(Error::KeyPairCreation(..))));
+		assert!(!is_loaded("stored"));
+		secrets.load(&key, "stored")?;
+		assert!(is_loaded("stored"));
+		assert_eq!(
+			create_signer("secret:stored")?.public_key().to_account_id(),
+			create_signer("//Bob")?.public_key().to_account_id()
+		);
+		assert!(matches!(secrets.load(&key, "missing"), Err(Error::Secrets(..))));
+		Ok(())
+	}
+
+	#[test]
+	fn resume_works() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let secrets = Secrets::new(&temp_dir.path().join(SECRETS_FILE_NAME));
+		let key = secrets.create("master")?;
+		assert!(matches!(
+			secrets.resume(&STANDARD.encode([0u8; 32])),
+			Err(Error::Secrets(message)) if message == "no session has been unlocked"
+		));
+		let token = secrets.start_session(&key)?;
+		// The token is not the key of the secrets.
+		assert_ne!(STANDARD.decode(&token)?, key.0.to_vec());
+		let key = secrets.resume(&token)?;
+		secrets.store(&key, "resumed", "//Charlie")?;
+		secrets.load(&key, "resumed")?;
+		// A session for other secrets cannot be resumed.
+		let other = Secrets::new(&temp_dir.path().join("other.json"));
+		let other_token = other.start_session(&other.create("master")?)?;
+		assert!(matches!(
+			secrets.resume(&other_token),
+			Err(Error::Secrets(message)) if message == "invalid session"
+		));
+		assert!(matches!(secrets.resume("invalid"), Err(Error::Secrets(..))));
+		// Starting a session replaces any previous session.
+		let replaced = secrets.start_session(&key)?;
+		assert!(matches!(
+			secrets.resume(&token),
+			Err(Error::Secrets(message)) if message == "invalid session"
+		));
+		assert!(secrets.resume(&replaced).is_ok());
+		Ok(())
+	}
+
+	#[test]
+	fn expired_sessions_cannot_be_resumed() -> Result<()> {
+		let temp_dir = tempdir()?;
+		let secrets = Secrets::new(&temp_dir.path().join(SECRETS_FILE_NAME));
+		let key = secrets.create("master")?;
+		let token = secrets.start_session_until(&key, now()? - 1)?;
+		assert!(matches!(
+			secrets.resume(&token),
+			Err(Error::Secrets(message)) if message == "the session has expired"
+		));
+		// The expired session is removed.
+		assert!(secrets.read()?.session.is_none());
+		assert!(matches!(
+			secrets.resume(&token),
+			Err(Error::Secrets(message)) if message == "no session has been unlocked"
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn secret_name_works() {
+		assert_eq!(secret_name("secret:savings"), Some("savings"));
+		assert_eq!(secret_name("//Alice"), None);
+	}
+
+	#[test]
+	fn random_token_works() {
+		let token = random_token();
+		assert_eq!(URL_SAFE_NO_PAD.decode(&token).unwrap().len(), 32);
+		assert_ne!(token, random_token());
+	}
+}
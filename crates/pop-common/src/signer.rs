@@ -1,13 +1,28 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::errors::Error;
+use crate::{
+	errors::Error,
+	keystore::{keystore_account, unlocked_keypair},
+	secrets::{loaded_secret, secret_name},
+};
 use subxt_signer::{sr25519::Keypair, SecretUri};
 
-/// Create a keypair from a secret URI.
+/// Create a keypair from a secret URI, from an unlocked keystore account referred to via
+/// `keystore:<name>`, or from a loaded secret referred to via `secret:<name>`.
 ///
 /// # Arguments
 /// `suri` - Secret URI string used to generate the `Keypair`.
 pub fn create_signer(suri: &str) -> Result<Keypair, Error> {
+	if let Some(name) = keystore_account(suri) {
+		return unlocked_keypair(name).ok_or_else(|| {
+			Error::KeyPairCreation(format!("the keystore account `{name}` is locked"))
+		});
+	}
+	if let Some(name) = secret_name(suri) {
+		let suri = loaded_secret(name)
+			.ok_or_else(|| Error::KeyPairCreation(format!("the secret `{name}` is locked")))?;
+		return create_signer(&suri);
+	}
 	let uri = <SecretUri as std::str::FromStr>::from_str(suri)
 		.map_err(|e| Error::ParseSecretURI(format!("{}", e)))?;
 	let keypair = Keypair::from_uri(&uri).map_err(|e| Error::KeyPairCreation(format!("{}", e)))?;
@@ -3,7 +3,7 @@
 mod binary;
 pub use binary::*;
 
-use crate::{Git, Status, APP_USER_AGENT};
+use crate::{rollback::Rollback, Git, Status, APP_USER_AGENT};
 use duct::cmd;
 use flate2::read::GzDecoder;
 use reqwest::StatusCode;
@@ -247,13 +247,14 @@ async fn from_archive(
 	status.update(&format!("Downloading from {url}..."));
 	let response = reqwest::get(url).await?.error_for_status()?;
 	let mut file = tempfile()?;
-	file.write_all(&response.bytes().await?)?;
+	write_response(response, &mut file, status).await?;
 	file.seek(SeekFrom::Start(0))?;
 	// Extract contents
 	status.update("Extracting from archive...");
 	let tar = GzDecoder::new(file);
 	let mut archive = Archive::new(tar);
 	let temp_dir = tempdir()?;
+	let _rollback = Rollback::remove(temp_dir.path());
 	let working_dir = temp_dir.path();
 	archive.unpack(working_dir)?;
 	for (name, dest) in contents {
@@ -299,6 +300,7 @@ async fn from_git(
 ) -> Result<(), Error> {
 	// Clone repository into working directory
 	let temp_dir = tempdir()?;
+	let _rollback = Rollback::remove(temp_dir.path());
 	let working_dir = temp_dir.path();
 	status.update(&format!("Cloning {url}..."));
 	Git::clone(&Url::parse(url)?, working_dir, reference)?;
@@ -368,13 +370,14 @@ async fn from_github_archive(
 			},
 		};
 	let mut file = tempfile()?;
-	file.write_all(&response.bytes().await?)?;
+	write_response(response, &mut file, status).await?;
 	file.seek(SeekFrom::Start(0))?;
 	// Extract contents
 	status.update("Extracting from archive...");
 	let tar = GzDecoder::new(file);
 	let mut archive = Archive::new(tar);
 	let temp_dir = tempdir()?;
+	let _rollback = Rollback::remove(temp_dir.path());
 	let mut working_dir = temp_dir.path().into();
 	archive.unpack(&working_dir)?;
 	// Prepare archive contents for build
@@ -430,7 +433,7 @@ pub(crate) async fn from_local_package(
 async fn from_url(url: &str, path: &Path, status: &impl Status) -> Result<(), Error> {
 	// Download required version of binaries
 	status.update(&format!("Downloading from {url}..."));
-	download(url, path).await?;
+	download(url, path, status).await?;
 	status.update("Sourcing complete.");
 	Ok(())
 }
@@ -489,16 +492,47 @@ async fn build(
 /// # Arguments
 /// * `url` - The url of the file.
 /// * `path` - The (local) destination path.
-async fn download(url: &str, dest: &Path) -> Result<(), Error> {
+/// * `status` - Used to observe status updates.
+async fn download(url: &str, dest: &Path, status: &impl Status) -> Result<(), Error> {
 	// Download to destination path
 	let response = reqwest::get(url).await?.error_for_status()?;
+	// Any partial download is removed if cancelled.
+	let _rollback = Rollback::remove(dest);
 	let mut file = File::create(dest)?;
-	file.write_all(&response.bytes().await?)?;
+	write_response(response, &mut file, status).await?;
 	// Make executable
 	set_executable_permission(dest)?;
 	Ok(())
 }
 
+/// Writes the body of a response to the destination, reporting the progress of the download
+/// when the length of the content is known.
+///
+/// # Arguments
+/// * `response` - The response to be written.
+/// * `dest` - The destination to write to.
+/// * `status` - Used to observe the progress of the download.
+async fn write_response(
+	mut response: reqwest::Response,
+	dest: &mut impl Write,
+	status: &impl Status,
+) -> Result<(), Error> {
+	let length = response.content_length().filter(|l| *l > 0);
+	let (mut downloaded, mut reported) = (0u64, None);
+	while let Some(chunk) = response.chunk().await? {
+		dest.write_all(&chunk)?;
+		downloaded += chunk.len() as u64;
+		if let Some(length) = length {
+			let pct = (downloaded * 100 / length).min(100) as u8;
+			if reported != Some(pct) {
+				status.progress("download", pct);
+				reported = Some(pct);
+			}
+		}
+	}
+	Ok(())
+}
+
 /// Sets the executable permission for a given file.
 ///
 /// # Arguments
@@ -829,6 +863,33 @@ pub(super) mod tests {
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn write_response_reports_download_progress() -> anyhow::Result<()> {
+		struct Progress(std::sync::Mutex<Vec<u8>>);
+		impl Status for Progress {
+			fn update(&self, _status: &str) {}
+			fn progress(&self, phase: &str, pct: u8) {
+				assert_eq!(phase, "download");
+				self.0.lock().unwrap().push(pct);
+			}
+		}
+
+		let mut server = mockito::Server::new_async().await;
+		let body = vec![1u8; 1024];
+		let mock = server.mock("GET", "/binary").with_body(&body).create_async().await;
+		let response = reqwest::get(format!("{}/binary", server.url())).await?;
+		let mut dest = Vec::new();
+		let progress = Progress(Default::default());
+
+		write_response(response, &mut dest, &progress).await?;
+		mock.assert_async().await;
+		assert_eq!(dest, body);
+		let progress = progress.0.into_inner().unwrap();
+		assert!(progress.windows(2).all(|w| w[0] < w[1]));
+		assert_eq!(progress.last(), Some(&100));
+		Ok(())
+	}
+
 	pub(crate) struct Output;
 	impl Status for Output {
 		fn update(&self, status: &str) {
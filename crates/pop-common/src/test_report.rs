@@ -0,0 +1,689 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::Error;
+use duct::cmd;
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::HashSet,
+	fmt::Write as _,
+	fs,
+	io::{BufRead, BufReader},
+	path::{Path, PathBuf},
+	time::{SystemTime, UNIX_EPOCH},
+};
+use strum_macros::{Display, EnumString};
+
+/// The environment variable via which the seed of a test run is provided to the tests.
+pub const SEED_ENV: &str = "POP_TEST_SEED";
+
+/// The outcome of a test case.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TestOutcome {
+	/// The test passed.
+	Passed,
+	/// The test failed, with any output captured for the test.
+	Failed(Option<String>),
+	/// The test was ignored.
+	Ignored,
+}
+
+/// A test case, as reported by the test harness.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestCase {
+	/// The full name of the test, e.g. `tests::it_works`.
+	pub name: String,
+	/// The outcome of the test.
+	pub outcome: TestOutcome,
+}
+
+/// A report of the tests run via `cargo test`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TestReport {
+	/// The test cases run.
+	pub cases: Vec<TestCase>,
+	/// Whether the tests completed successfully.
+	pub success: bool,
+}
+
+impl TestReport {
+	/// Parses a report from the output of the default test harness.
+	///
+	/// # Arguments
+	/// * `output` - The standard output of `cargo test`.
+	/// * `success` - Whether the tests completed successfully.
+	pub fn parse(output: &str, success: bool) -> Self {
+		let mut cases: Vec<TestCase> = vec![];
+		// The name of a test whose outcome is reported on a subsequent line, due to test output.
+		let mut pending: Option<String> = None;
+		// The name of a failed test whose captured output is being read, along with the output.
+		let mut failure: Option<(String, Vec<&str>)> = None;
+		for line in output.lines() {
+			if let Some(name) =
+				line.strip_prefix("---- ").and_then(|l| l.strip_suffix(" stdout ----"))
+			{
+				record_failure(&mut failure, &mut cases);
+				failure = Some((name.to_string(), vec![]));
+				continue;
+			}
+			if failure.is_some() {
+				if line == "failures:" || line.starts_with("test result:") {
+					record_failure(&mut failure, &mut cases);
+				} else if let Some((_, output)) = failure.as_mut() {
+					output.push(line);
+				}
+				continue;
+			}
+			if let Some((name, result)) =
+				line.strip_prefix("test ").and_then(|l| l.split_once(" ... "))
+			{
+				match outcome(result) {
+					Some(outcome) => cases.push(TestCase { name: name.to_string(), outcome }),
+					None => pending = Some(name.to_string()),
+				}
+				continue;
+			}
+			if let Some(outcome) = outcome(line) {
+				if let Some(name) = pending.take() {
+					cases.push(TestCase { name, outcome });
+				}
+			}
+		}
+		record_failure(&mut failure, &mut cases);
+		Self { cases, success }
+	}
+
+	/// The number of failed test cases.
+	pub fn failures(&self) -> usize {
+		self.cases
+			.iter()
+			.filter(|c| matches!(c.outcome, TestOutcome::Failed(_)))
+			.count()
+	}
+
+	/// The number of ignored test cases.
+	pub fn ignored(&self) -> usize {
+		self.cases.iter().filter(|c| c.outcome == TestOutcome::Ignored).count()
+	}
+
+	/// Formats the report as JUnit XML, allowing failed test cases to be displayed natively by CI
+	/// systems.
+	///
+	/// # Arguments
+	/// * `suite` - The name of the test suite, e.g. "unit".
+	pub fn to_junit(&self, suite: &str) -> String {
+		let (tests, failures, skipped) = (self.cases.len(), self.failures(), self.ignored());
+		let suite = escape(suite);
+		let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+		let _ = writeln!(
+			xml,
+			"<testsuites name=\"{suite}\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\">"
+		);
+		let _ = writeln!(
+			xml,
+			"  <testsuite name=\"{suite}\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\">"
+		);
+		for case in &self.cases {
+			let (class, name) = case.name.rsplit_once("::").unwrap_or((&suite, &case.name));
+			let _ = write!(
+				xml,
+				"    <testcase name=\"{}\" classname=\"{}\"",
+				escape(name),
+				escape(class)
+			);
+			match &case.outcome {
+				TestOutcome::Passed => xml.push_str("/>\n"),
+				TestOutcome::Ignored => xml.push_str(">\n      <skipped/>\n    </testcase>\n"),
+				TestOutcome::Failed(output) => {
+					xml.push_str(">\n      <failure message=\"test failed\"");
+					match output {
+						Some(output) => {
+							let _ = writeln!(xml, ">{}</failure>", escape(output));
+						},
+						None => xml.push_str("/>\n"),
+					}
+					xml.push_str("    </testcase>\n");
+				},
+			}
+		}
+		xml.push_str("  </testsuite>\n</testsuites>\n");
+		xml
+	}
+
+	/// Writes the report as JUnit XML to the specified file.
+	///
+	/// # Arguments
+	/// * `path` - The path of the file.
+	/// * `suite` - The name of the test suite, e.g. "unit".
+	pub fn write_junit(&self, path: &Path, suite: &str) -> Result<(), Error> {
+		fs::write(path, self.to_junit(suite))?;
+		Ok(())
+	}
+
+	/// Aggregates the reports of several runs into a single report, prefixing the name of each
+	/// test case with the label of the run to which it belongs.
+	///
+	/// # Arguments
+	/// * `reports` - The reports, keyed by the label of their run.
+	pub fn merge(reports: &[(String, TestReport)]) -> Self {
+		let cases = reports
+			.iter()
+			.flat_map(|(label, report)| {
+				report.cases.iter().map(move |c| TestCase {
+					name: format!("{label}::{}", c.name),
+					outcome: c.outcome.clone(),
+				})
+			})
+			.collect();
+		Self { cases, success: reports.iter().all(|(_, r)| r.success) }
+	}
+}
+
+/// Records the output captured for a failed test against its test case.
+fn record_failure(failure: &mut Option<(String, Vec<&str>)>, cases: &mut [TestCase]) {
+	if let Some((name, output)) = failure.take() {
+		let output = output.join("\n").trim().to_string();
+		if let Some(case) = cases.iter_mut().rev().find(|c| c.name == name) {
+			case.outcome = TestOutcome::Failed((!output.is_empty()).then_some(output));
+		}
+	}
+}
+
+/// Parses the outcome of a test, as reported by the default test harness.
+fn outcome(result: &str) -> Option<TestOutcome> {
+	match result.trim() {
+		"ok" => Some(TestOutcome::Passed),
+		"FAILED" => Some(TestOutcome::Failed(None)),
+		r if r == "ignored" || r.starts_with("ignored,") => Some(TestOutcome::Ignored),
+		_ => None,
+	}
+}
+
+/// Escapes text for use within XML.
+fn escape(text: &str) -> String {
+	text.chars().filter(|c| !c.is_control() || matches!(c, '\n' | '\t')).fold(
+		String::with_capacity(text.len()),
+		|mut escaped, c| {
+			match c {
+				'&' => escaped.push_str("&amp;"),
+				'<' => escaped.push_str("&lt;"),
+				'>' => escaped.push_str("&gt;"),
+				'"' => escaped.push_str("&quot;"),
+				'\'' => escaped.push_str("&apos;"),
+				c => escaped.push(c),
+			}
+			escaped
+		},
+	)
+}
+
+/// The configuration of a test run, which is recorded should the run fail so that it can be
+/// replayed.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct TestRun {
+	/// The seed provided to the tests (via `POP_TEST_SEED`), from which any random data should
+	/// be derived.
+	pub seed: u64,
+	/// The tests to be run, otherwise all tests.
+	pub tests: Vec<String>,
+}
+
+impl TestRun {
+	/// A run of all tests, using the specified seed, otherwise one derived from the current time.
+	///
+	/// # Arguments
+	/// * `seed` - The seed to be used, if any.
+	pub fn new(seed: Option<u64>) -> Self {
+		let seed = seed.unwrap_or_else(|| {
+			SystemTime::now()
+				.duration_since(UNIX_EPOCH)
+				.map(|d| d.as_nanos() as u64)
+				.unwrap_or_default()
+		});
+		Self { seed, tests: vec![] }
+	}
+
+	/// The arguments to `cargo test`, including any specified arguments, restricting the run to
+	/// the specified tests where applicable.
+	///
+	/// # Arguments
+	/// * `args` - Any additional arguments for `cargo test`.
+	pub fn args(&self, args: &[String]) -> Vec<String> {
+		let mut args = args.to_vec();
+		if !self.tests.is_empty() {
+			if !args.iter().any(|a| a == "--") {
+				args.push("--".into());
+			}
+			args.push("--exact".into());
+			args.extend(self.tests.iter().cloned());
+		}
+		args
+	}
+
+	/// The environment variables to be provided to the tests.
+	pub fn envs(&self) -> Vec<(String, String)> {
+		vec![(SEED_ENV.to_string(), self.seed.to_string())]
+	}
+
+	/// The run replaying the failures of this run, if any.
+	///
+	/// # Arguments
+	/// * `report` - The report of this run.
+	pub fn failed(&self, report: &TestReport) -> Option<Self> {
+		let tests: Vec<_> = report
+			.cases
+			.iter()
+			.filter(|c| matches!(c.outcome, TestOutcome::Failed(_)))
+			.map(|c| c.name.clone())
+			.collect();
+		(!tests.is_empty()).then_some(Self { seed: self.seed, tests })
+	}
+
+	/// Loads the last failing run of a test suite of a project, if any.
+	///
+	/// # Arguments
+	/// * `project` - The path of the project.
+	/// * `suite` - The name of the test suite.
+	pub fn load(project: &Path, suite: &str) -> Result<Option<Self>, Error> {
+		let path = Self::path(project, suite);
+		if !path.exists() {
+			return Ok(None);
+		}
+		Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+	}
+
+	/// Records the outcome of a run of a test suite of a project: the failures are recorded to
+	/// be replayed, otherwise any previously recorded failures are cleared.
+	///
+	/// # Arguments
+	/// * `project` - The path of the project.
+	/// * `suite` - The name of the test suite.
+	/// * `report` - The report of the run.
+	pub fn record(&self, project: &Path, suite: &str, report: &TestReport) -> Result<(), Error> {
+		let path = Self::path(project, suite);
+		match self.failed(report) {
+			Some(failed) => {
+				if let Some(parent) = path.parent() {
+					fs::create_dir_all(parent)?;
+				}
+				fs::write(path, serde_json::to_string_pretty(&failed)?)?;
+			},
+			None if path.exists() => fs::remove_file(path)?,
+			None => {},
+		}
+		Ok(())
+	}
+
+	// The file in which the last failing run of a test suite is recorded.
+	fn path(project: &Path, suite: &str) -> PathBuf {
+		project.join("target/pop").join(format!("{suite}-failed.json"))
+	}
+}
+
+/// The format of a coverage report.
+#[derive(Clone, Copy, Debug, Display, EnumString, PartialEq)]
+#[strum(serialize_all = "lowercase")]
+pub enum CoverageFormat {
+	/// An lcov tracefile, as consumed by most coverage services and editors.
+	Lcov,
+	/// A browsable HTML report.
+	Html,
+}
+
+/// The collection of coverage whilst running tests, via `cargo llvm-cov`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coverage {
+	/// The format of the report.
+	pub format: CoverageFormat,
+	/// The location of the report, relative to the project, otherwise within
+	/// `target/coverage`.
+	pub output: Option<PathBuf>,
+}
+
+impl Coverage {
+	/// The location of the report: the lcov file or the directory containing the HTML report.
+	pub fn output(&self) -> PathBuf {
+		self.output.clone().unwrap_or_else(|| {
+			let target = PathBuf::from("target/coverage");
+			match self.format {
+				CoverageFormat::Lcov => target.join("lcov.info"),
+				CoverageFormat::Html => target,
+			}
+		})
+	}
+
+	/// The file to be opened to view the report.
+	pub fn report(&self) -> PathBuf {
+		match self.format {
+			CoverageFormat::Lcov => self.output(),
+			CoverageFormat::Html => self.output().join("html/index.html"),
+		}
+	}
+
+	// The arguments to `cargo llvm-cov`, preceding those of `cargo test`.
+	fn args(&self) -> Vec<String> {
+		let output = self.output().display().to_string();
+		match self.format {
+			CoverageFormat::Lcov => vec!["--lcov".into(), "--output-path".into(), output],
+			CoverageFormat::Html => vec!["--html".into(), "--output-dir".into(), output],
+		}
+	}
+}
+
+/// Whether `cargo llvm-cov`, required to collect coverage, is installed.
+pub fn llvm_cov_installed() -> bool {
+	cmd("cargo", ["llvm-cov", "--version"])
+		.stdout_null()
+		.stderr_null()
+		.unchecked()
+		.run()
+		.is_ok_and(|o| o.status.success())
+}
+
+/// Runs `cargo test`, streaming its output whilst capturing it to produce a report.
+///
+/// # Arguments
+/// * `path` - The path of the project.
+/// * `args` - Any additional arguments for `cargo test`.
+/// * `envs` - Any environment variables to be provided to the tests.
+/// * `coverage` - Whether coverage should be collected, and how it should be reported.
+pub fn cargo_test(
+	path: &Path,
+	args: &[String],
+	envs: &[(String, String)],
+	coverage: Option<&Coverage>,
+) -> Result<TestReport, Error> {
+	let subcommand = match coverage {
+		Some(coverage) => ["llvm-cov".to_string()].into_iter().chain(coverage.args()).collect(),
+		None => vec!["test".to_string()],
+	};
+	let mut command = cmd("cargo", subcommand.iter().chain(args)).dir(path).stderr_to_stdout();
+	for (key, value) in envs {
+		command = command.env(key, value);
+	}
+	let reader = command.unchecked().reader()?;
+	let mut output = String::new();
+	let mut lines = BufReader::new(&reader);
+	let mut line = String::new();
+	while lines.read_line(&mut line)? > 0 {
+		print!("{line}");
+		output.push_str(&line);
+		line.clear();
+	}
+	let success = reader.try_wait()?.map(|o| o.status.success()).unwrap_or_default();
+	Ok(TestReport::parse(&output, success))
+}
+
+/// The feature set denoting the default features of a project.
+pub const DEFAULT_FEATURES: &str = "default";
+
+/// A combination of a feature set and a toolchain, against which tests are run as part of a
+/// matrix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatrixEntry {
+	/// The feature set enabled, in addition to the default features.
+	pub features: String,
+	/// The toolchain used, e.g. "stable" or "nightly".
+	pub toolchain: String,
+}
+
+impl MatrixEntry {
+	/// The label of the entry, e.g. `try-runtime@stable`.
+	pub fn label(&self) -> String {
+		format!("{}@{}", self.features, self.toolchain)
+	}
+
+	/// The arguments to `cargo test`, including any specified arguments, enabling the feature set.
+	///
+	/// # Arguments
+	/// * `args` - Any additional arguments for `cargo test`.
+	pub fn args(&self, args: &[String]) -> Vec<String> {
+		let mut features = vec![];
+		if self.features != DEFAULT_FEATURES {
+			features = vec!["--features".into(), self.features.clone()];
+		}
+		features.into_iter().chain(args.iter().cloned()).collect()
+	}
+
+	/// The environment variables selecting the toolchain, via rustup.
+	pub fn envs(&self) -> Vec<(String, String)> {
+		vec![("RUSTUP_TOOLCHAIN".to_string(), self.toolchain.clone())]
+	}
+}
+
+/// The matrix of every combination of the specified feature sets and toolchains.
+///
+/// # Arguments
+/// * `features` - The feature sets.
+/// * `toolchains` - The toolchains.
+pub fn matrix(features: &[String], toolchains: &[String]) -> Vec<MatrixEntry> {
+	toolchains
+		.iter()
+		.flat_map(|toolchain| {
+			features.iter().map(|features| MatrixEntry {
+				features: features.clone(),
+				toolchain: toolchain.clone(),
+			})
+		})
+		.collect()
+}
+
+/// The features declared by any of the packages of a project (or workspace), as reported by
+/// `cargo metadata`.
+///
+/// # Arguments
+/// * `path` - The path of the project.
+pub fn declared_features(path: &Path) -> Result<HashSet<String>, Error> {
+	let output = cmd("cargo", ["metadata", "--no-deps", "--format-version", "1"])
+		.dir(path)
+		.stderr_null()
+		.read()?;
+	let metadata: serde_json::Value = serde_json::from_str(&output)?;
+	Ok(metadata["packages"]
+		.as_array()
+		.into_iter()
+		.flatten()
+		.filter_map(|p| p["features"].as_object())
+		.flat_map(|f| f.keys().cloned())
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const OUTPUT: &str = r#"
+running 4 tests
+test tests::it_works ... ok
+test tests::slow ... ignored, requires a node
+test tests::noisy ... printed by the test
+ok
+test tests::fails ... FAILED
+
+failures:
+
+---- tests::fails stdout ----
+thread 'tests::fails' panicked at src/lib.rs:10:5:
+assertion `left == right` failed
+  left: 1
+ right: 2
+
+
+failures:
+    tests::fails
+
+test result: FAILED. 2 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+"#;
+
+	#[test]
+	fn parse_works() {
+		let report = TestReport::parse(OUTPUT, false);
+		assert_eq!(
+			report,
+			TestReport {
+				cases: vec![
+					TestCase { name: "tests::it_works".into(), outcome: TestOutcome::Passed },
+					TestCase { name: "tests::slow".into(), outcome: TestOutcome::Ignored },
+					TestCase { name: "tests::noisy".into(), outcome: TestOutcome::Passed },
+					TestCase {
+						name: "tests::fails".into(),
+						outcome: TestOutcome::Failed(Some(
+							"thread 'tests::fails' panicked at src/lib.rs:10:5:\nassertion `left == right` failed\n  left: 1\n right: 2".into()
+						)),
+					},
+				],
+				success: false,
+			}
+		);
+		assert_eq!((report.failures(), report.ignored()), (1, 1));
+	}
+
+	#[test]
+	fn to_junit_works() {
+		let report = TestReport::parse(OUTPUT, false);
+		assert_eq!(
+			report.to_junit("unit"),
+			r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="unit" tests="4" failures="1" skipped="1">
+  <testsuite name="unit" tests="4" failures="1" skipped="1">
+    <testcase name="it_works" classname="tests"/>
+    <testcase name="slow" classname="tests">
+      <skipped/>
+    </testcase>
+    <testcase name="noisy" classname="tests"/>
+    <testcase name="fails" classname="tests">
+      <failure message="test failed">thread &apos;tests::fails&apos; panicked at src/lib.rs:10:5:
+assertion `left == right` failed
+  left: 1
+ right: 2</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+		);
+	}
+
+	#[test]
+	fn escape_works() {
+		assert_eq!(
+			escape("<a href=\"x\">'&'</a>\u{1b}"),
+			"&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;"
+		);
+	}
+
+	#[test]
+	fn test_run_args_works() {
+		let run = TestRun::new(Some(42));
+		assert_eq!(run.args(&["--offline".into()]), vec!["--offline"]);
+		assert_eq!(run.envs(), vec![(SEED_ENV.to_string(), "42".to_string())]);
+		let run = TestRun { seed: 42, tests: vec!["tests::fails".into()] };
+		assert_eq!(run.args(&[]), vec!["--", "--exact", "tests::fails"]);
+		assert_eq!(
+			run.args(&["--".into(), "--nocapture".into()]),
+			vec!["--", "--nocapture", "--exact", "tests::fails"]
+		);
+	}
+
+	#[test]
+	fn test_run_record_works() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		let run = TestRun::new(Some(42));
+		assert_eq!(TestRun::load(temp_dir.path(), "unit")?, None);
+		let report = TestReport::parse(OUTPUT, false);
+		run.record(temp_dir.path(), "unit", &report)?;
+		let failed = TestRun { seed: 42, tests: vec!["tests::fails".into()] };
+		assert_eq!(run.failed(&report), Some(failed.clone()));
+		assert_eq!(TestRun::load(temp_dir.path(), "unit")?, Some(failed.clone()));
+		assert_eq!(TestRun::load(temp_dir.path(), "e2e")?, None);
+		// A successful run clears the recorded failures.
+		let report = TestReport { cases: vec![], success: true };
+		failed.record(temp_dir.path(), "unit", &report)?;
+		assert_eq!(TestRun::load(temp_dir.path(), "unit")?, None);
+		Ok(())
+	}
+
+	#[test]
+	fn coverage_works() {
+		assert_eq!("lcov".parse(), Ok(CoverageFormat::Lcov));
+		assert_eq!("html".parse(), Ok(CoverageFormat::Html));
+		assert!("xml".parse::<CoverageFormat>().is_err());
+		let lcov = Coverage { format: CoverageFormat::Lcov, output: None };
+		assert_eq!(lcov.report(), PathBuf::from("target/coverage/lcov.info"));
+		assert_eq!(lcov.args(), vec!["--lcov", "--output-path", "target/coverage/lcov.info"]);
+		let html = Coverage { format: CoverageFormat::Html, output: Some("coverage".into()) };
+		assert_eq!(html.report(), PathBuf::from("coverage/html/index.html"));
+		assert_eq!(html.args(), vec!["--html", "--output-dir", "coverage"]);
+	}
+
+	#[test]
+	fn cargo_test_works() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		cmd("cargo", ["init", "--lib", "--vcs", "none", "--name", "report"])
+			.dir(temp_dir.path())
+			.stderr_null()
+			.run()?;
+		fs::write(
+			temp_dir.path().join("src/lib.rs"),
+			"#[test]\nfn passes() {}\n#[test]\nfn fails() { assert!(std::env::var(\"FAIL\").is_err()) }\n",
+		)?;
+		let report = cargo_test(temp_dir.path(), &["--offline".into()], &[], None)?;
+		assert!(report.success);
+		assert_eq!(report.cases.len(), 2);
+		let envs = [("FAIL".to_string(), "1".to_string())];
+		let report = cargo_test(temp_dir.path(), &["--offline".into()], &envs, None)?;
+		assert!(!report.success);
+		assert_eq!(report.failures(), 1);
+		Ok(())
+	}
+
+	#[test]
+	fn merge_works() {
+		let report = TestReport::parse(OUTPUT, false);
+		let passed = TestReport {
+			cases: vec![TestCase { name: "tests::it_works".into(), outcome: TestOutcome::Passed }],
+			success: true,
+		};
+		let merged = TestReport::merge(&[
+			("default@stable".into(), passed.clone()),
+			("try-runtime@stable".into(), report),
+		]);
+		assert!(!merged.success);
+		assert_eq!(merged.cases.len(), 5);
+		assert_eq!(merged.cases[0].name, "default@stable::tests::it_works");
+		assert_eq!(merged.cases[4].name, "try-runtime@stable::tests::fails");
+		assert_eq!(merged.failures(), 1);
+		assert!(TestReport::merge(&[("default@stable".into(), passed)]).success);
+	}
+
+	#[test]
+	fn matrix_works() {
+		let entries =
+			matrix(&["default".into(), "try-runtime".into()], &["stable".into(), "nightly".into()]);
+		assert_eq!(
+			entries.iter().map(|e| e.label()).collect::<Vec<_>>(),
+			vec!["default@stable", "try-runtime@stable", "default@nightly", "try-runtime@nightly"]
+		);
+		assert_eq!(entries[0].args(&["--offline".into()]), vec!["--offline"]);
+		assert_eq!(
+			entries[1].args(&["--offline".into()]),
+			vec!["--features", "try-runtime", "--offline"]
+		);
+		assert_eq!(
+			entries[2].envs(),
+			vec![("RUSTUP_TOOLCHAIN".to_string(), "nightly".to_string())]
+		);
+	}
+
+	#[test]
+	fn declared_features_works() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		cmd("cargo", ["init", "--lib", "--vcs", "none", "--name", "features"])
+			.dir(temp_dir.path())
+			.stderr_null()
+			.run()?;
+		let manifest = temp_dir.path().join("Cargo.toml");
+		let contents = fs::read_to_string(&manifest)?;
+		fs::write(&manifest, format!("{contents}\n[features]\ntry-runtime = []\n"))?;
+		assert_eq!(declared_features(temp_dir.path())?, HashSet::from(["try-runtime".to_string()]));
+		Ok(())
+	}
+}
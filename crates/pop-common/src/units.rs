@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::Error;
+
+/// Parses an amount of whole tokens, such as `1.5`, into the smallest unit of the token.
+///
+/// # Arguments
+/// * `amount` - The amount of whole tokens.
+/// * `decimals` - The number of decimals of the token.
+pub fn parse_units(amount: &str, decimals: u8) -> Result<u128, Error> {
+	let invalid = || Error::InvalidAmount(amount.to_string());
+	let (whole, fraction) = amount.trim().split_once('.').unwrap_or((amount.trim(), ""));
+	if (whole.is_empty() && fraction.is_empty()) ||
+		!whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+	{
+		return Err(invalid());
+	}
+	if fraction.len() > decimals as usize {
+		return Err(Error::InvalidAmount(format!(
+			"{amount} has more than {decimals} decimal places"
+		)));
+	}
+	let unit = 10u128.checked_pow(decimals as u32).ok_or_else(invalid)?;
+	let whole = match whole {
+		"" => 0,
+		whole => whole.parse::<u128>().map_err(|_| invalid())?,
+	};
+	let fraction = match fraction {
+		"" => 0,
+		fraction => {
+			let padding = 10u128.pow((decimals as usize - fraction.len()) as u32);
+			fraction.parse::<u128>().map_err(|_| invalid())? * padding
+		},
+	};
+	whole
+		.checked_mul(unit)
+		.and_then(|w| w.checked_add(fraction))
+		.ok_or_else(invalid)
+}
+
+/// Parses a balance, either as an amount of whole tokens followed by the symbol of the token, such
+/// as `1.5 DOT`, or otherwise as an amount in the smallest unit of the token, such as
+/// `15000000000`.
+///
+/// # Arguments
+/// * `balance` - The balance.
+/// * `symbol` - The symbol of the token.
+/// * `decimals` - The number of decimals of the token.
+pub fn parse_balance(balance: &str, symbol: &str, decimals: u8) -> Result<u128, Error> {
+	let balance = balance.trim();
+	let denominated = balance
+		.len()
+		.checked_sub(symbol.len())
+		.filter(|i| balance.is_char_boundary(*i))
+		.map(|i| balance.split_at(i))
+		.filter(|(_, s)| !symbol.is_empty() && s.eq_ignore_ascii_case(symbol));
+	match denominated {
+		Some((amount, _)) => parse_units(amount, decimals),
+		None => balance.parse().map_err(|_| Error::InvalidAmount(balance.to_string())),
+	}
+}
+
+/// Formats an amount in the smallest unit of a token as whole tokens, such as `1.5`.
+///
+/// # Arguments
+/// * `amount` - The amount in the smallest unit of the token.
+/// * `decimals` - The number of decimals of the token.
+pub fn format_units(amount: u128, decimals: u8) -> String {
+	let unit = 10u128.pow(decimals as u32);
+	let fraction = format!("{:0width$}", amount % unit, width = decimals as usize);
+	match fraction.trim_end_matches('0') {
+		"" => (amount / unit).to_string(),
+		fraction => format!("{}.{fraction}", amount / unit),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_units_works() -> Result<(), Error> {
+		assert_eq!(parse_units("1", 10)?, 10_000_000_000);
+		assert_eq!(parse_units("1.5", 12)?, 1_500_000_000_000);
+		assert_eq!(parse_units(".25", 2)?, 25);
+		assert_eq!(parse_units("0.0000000001", 10)?, 1);
+		assert_eq!(parse_units("42", 0)?, 42);
+		assert_eq!(parse_units("42.", 0)?, 42);
+		for invalid in ["", ".", "1.2.3", "-1", "1e3", "abc"] {
+			assert!(matches!(parse_units(invalid, 10), Err(Error::InvalidAmount(_))), "{invalid}");
+		}
+		assert!(matches!(
+			parse_units("1.123", 2),
+			Err(Error::InvalidAmount(m)) if m == "1.123 has more than 2 decimal places"
+		));
+		// Amounts which overflow are rejected.
+		assert!(parse_units(&u128::MAX.to_string(), 1).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn parse_balance_works() -> Result<(), Error> {
+		assert_eq!(parse_balance("1.5 DOT", "DOT", 10)?, 15_000_000_000);
+		assert_eq!(parse_balance("0.01pas", "PAS", 10)?, 100_000_000);
+		assert_eq!(parse_balance(" 2 DOT ", "DOT", 10)?, 20_000_000_000);
+		// Balances without the symbol are in the smallest unit of the token.
+		assert_eq!(parse_balance("15000000000", "DOT", 10)?, 15_000_000_000);
+		for invalid in ["1.5", "1.5 KSM", "DOT", "-1 DOT", "1.00000000001 DOT"] {
+			assert!(matches!(parse_balance(invalid, "DOT", 10), Err(Error::InvalidAmount(_))));
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn format_units_works() {
+		assert_eq!(format_units(10_000_000_000, 10), "1");
+		assert_eq!(format_units(1_500_000_000_000, 12), "1.5");
+		assert_eq!(format_units(1, 10), "0.0000000001");
+		assert_eq!(format_units(42, 0), "42");
+	}
+}
@@ -0,0 +1,565 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{Error, APP_USER_AGENT};
+use duct::cmd;
+use regex::Regex;
+use serde::Deserialize;
+use std::{
+	collections::HashMap,
+	fmt::{Display, Formatter},
+	fs::{read_dir, read_to_string, write},
+	path::{Path, PathBuf},
+};
+use toml_edit::{DocumentMut, Item, TableLike};
+
+/// The repository of polkadot-sdk, whose crates are published as part of each stable release.
+pub const POLKADOT_SDK: &str = "https://github.com/paritytech/polkadot-sdk";
+/// The base URL from which the files of polkadot-sdk are fetched at a release tag.
+const POLKADOT_SDK_RAW: &str = "https://raw.githubusercontent.com/paritytech/polkadot-sdk";
+/// The crates of ink!, which are all published with the same version.
+pub const INK_CRATES: [&str; 13] = [
+	"ink",
+	"ink_allocator",
+	"ink_codegen",
+	"ink_e2e",
+	"ink_e2e_macro",
+	"ink_engine",
+	"ink_env",
+	"ink_ir",
+	"ink_macro",
+	"ink_metadata",
+	"ink_prelude",
+	"ink_primitives",
+	"ink_storage",
+];
+/// The tables of a manifest in which dependencies are declared.
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Normalizes a stable release of polkadot-sdk to its tag, e.g. `stable2412` to
+/// `polkadot-stable2412`.
+///
+/// # Arguments
+/// * `release` - The release, with or without the `polkadot-` prefix and with an optional patch
+///   number, e.g. `stable2412-1`.
+pub fn release_tag(release: &str) -> Option<String> {
+	let release = release.strip_prefix("polkadot-").unwrap_or(release);
+	Regex::new(r"^stable\d{4}(-\d+)?$")
+		.expect("valid regex")
+		.is_match(release)
+		.then(|| format!("polkadot-{release}"))
+}
+
+/// Fetches the versions of the crates published as part of a stable release of polkadot-sdk.
+///
+/// # Arguments
+/// * `tag` - The tag of the release, e.g. `polkadot-stable2412`.
+pub async fn sdk_versions(tag: &str) -> Result<HashMap<String, String>, Error> {
+	fetch_sdk_versions(POLKADOT_SDK_RAW, tag).await
+}
+
+async fn fetch_sdk_versions(base: &str, tag: &str) -> Result<HashMap<String, String>, Error> {
+	let client = reqwest::ClientBuilder::new()
+		.user_agent(APP_USER_AGENT)
+		.build()
+		.map_err(|e| Error::Upgrade(e.to_string()))?;
+	let lockfile = client
+		.get(format!("{base}/{tag}/Cargo.lock"))
+		.send()
+		.await
+		.and_then(|r| r.error_for_status())
+		.map_err(|e| Error::Upgrade(format!("unable to fetch the crates of {tag}: {e}")))?
+		.text()
+		.await
+		.map_err(|e| Error::Upgrade(e.to_string()))?;
+	parse_lockfile(&lockfile)
+}
+
+/// Parses the versions of the crates of a workspace from its `Cargo.lock`, which are those without
+/// a source.
+///
+/// # Arguments
+/// * `lockfile` - The contents of the `Cargo.lock` file.
+pub fn parse_lockfile(lockfile: &str) -> Result<HashMap<String, String>, Error> {
+	#[derive(Deserialize)]
+	struct Lockfile {
+		#[serde(default)]
+		package: Vec<Package>,
+	}
+	#[derive(Deserialize)]
+	struct Package {
+		name: String,
+		version: String,
+		source: Option<String>,
+	}
+	let lockfile: Lockfile =
+		toml::from_str(lockfile).map_err(|e| Error::Upgrade(format!("invalid lockfile: {e}")))?;
+	Ok(lockfile
+		.package
+		.into_iter()
+		.filter(|p| p.source.is_none())
+		.map(|p| (p.name, p.version))
+		.collect())
+}
+
+/// The versions of the crates of ink!, for the specified version.
+///
+/// # Arguments
+/// * `version` - The version of ink!, e.g. `5.1.1`.
+pub fn ink_versions(version: &str) -> HashMap<String, String> {
+	INK_CRATES.iter().map(|c| (c.to_string(), version.to_string())).collect()
+}
+
+/// A dependency which is changed by an upgrade.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpgradedDependency {
+	/// The manifest declaring the dependency.
+	pub manifest: PathBuf,
+	/// The name of the dependency.
+	pub name: String,
+	/// The version, or git reference, prior to the upgrade.
+	pub from: String,
+	/// The version, or git reference, after the upgrade.
+	pub to: String,
+}
+
+impl Display for UpgradedDependency {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}: {} -> {}", self.name, self.from, self.to)
+	}
+}
+
+/// Finds the manifests of a project, skipping build directories and hidden directories.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+pub fn manifests(project: &Path) -> Vec<PathBuf> {
+	let mut manifests = Vec::new();
+	if project.join("Cargo.toml").is_file() {
+		manifests.push(project.join("Cargo.toml"));
+	}
+	let mut dirs: Vec<_> = read_dir(project)
+		.into_iter()
+		.flatten()
+		.filter_map(|e| e.ok().map(|e| e.path()))
+		.filter(|p| p.is_dir())
+		.filter(|p| {
+			p.file_name()
+				.and_then(|n| n.to_str())
+				.is_some_and(|n| n != "target" && !n.starts_with('.'))
+		})
+		.collect();
+	dirs.sort();
+	for dir in dirs {
+		manifests.extend(self::manifests(&dir));
+	}
+	manifests
+}
+
+/// The versions to which dependencies are upgraded.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Upgrade {
+	/// The version of each crate, by name.
+	pub versions: HashMap<String, String>,
+	/// The tag to which dependencies on the specified git repository are pinned, e.g.
+	/// `(POLKADOT_SDK, "polkadot-stable2412")`.
+	pub git: Option<(String, String)>,
+}
+
+impl Upgrade {
+	/// Upgrades the dependencies declared within a manifest, returning those changed.
+	///
+	/// Dependencies inherited from the workspace are left untouched, as they are upgraded along
+	/// with the manifest of the workspace.
+	///
+	/// # Arguments
+	/// * `manifest` - The path to the manifest.
+	pub fn apply(&self, manifest: &Path) -> Result<Vec<UpgradedDependency>, Error> {
+		let mut doc = read_to_string(manifest)?
+			.parse::<DocumentMut>()
+			.map_err(|e| Error::Upgrade(format!("invalid manifest {}: {e}", manifest.display())))?;
+		let mut upgraded = Vec::new();
+		let mut tables: Vec<&mut dyn TableLike> = Vec::new();
+		for (key, item) in doc.iter_mut() {
+			match key.get() {
+				"workspace" => tables.extend(
+					item.as_table_like_mut()
+						.and_then(|w| w.get_mut("dependencies"))
+						.and_then(Item::as_table_like_mut),
+				),
+				"target" =>
+					for (_, target) in
+						item.as_table_like_mut().into_iter().flat_map(|t| t.iter_mut())
+					{
+						for (key, item) in
+							target.as_table_like_mut().into_iter().flat_map(|t| t.iter_mut())
+						{
+							if DEPENDENCY_TABLES.contains(&key.get()) {
+								tables.extend(item.as_table_like_mut());
+							}
+						}
+					},
+				key if DEPENDENCY_TABLES.contains(&key) => tables.extend(item.as_table_like_mut()),
+				_ => {},
+			}
+		}
+		for table in tables {
+			for (key, item) in table.iter_mut() {
+				if let Some((from, to)) = self.upgrade(key.get(), item) {
+					upgraded.push(UpgradedDependency {
+						manifest: manifest.to_path_buf(),
+						name: key.get().to_string(),
+						from,
+						to,
+					});
+				}
+			}
+		}
+		if !upgraded.is_empty() {
+			write(manifest, doc.to_string())?;
+		}
+		Ok(upgraded)
+	}
+
+	// Upgrades a dependency, returning its prior and new version or git reference if changed.
+	fn upgrade(&self, name: &str, item: &mut Item) -> Option<(String, String)> {
+		// A version requirement, e.g. `ink = "5.0.0"`.
+		if let Some(version) = item.as_str() {
+			let to = requirement(version, self.versions.get(name)?);
+			return set(item, version.to_string(), to);
+		}
+		let dependency = item.as_table_like_mut()?;
+		if dependency.get("workspace").and_then(Item::as_bool) == Some(true) {
+			return None;
+		}
+		// The crate may be renamed, e.g. `codec = { package = "parity-scale-codec", ... }`.
+		let package = dependency.get("package").and_then(Item::as_str).unwrap_or(name).to_string();
+		if let Some((repository, tag)) = &self.git {
+			let git = dependency.get("git").and_then(Item::as_str).map(normalize);
+			if git.as_deref() == Some(normalize(repository).as_str()) {
+				let from = ["tag", "branch", "rev"]
+					.into_iter()
+					.find_map(|r| dependency.remove(r).and_then(|r| r.as_str().map(String::from)))
+					.unwrap_or_default();
+				dependency.insert("tag", toml_edit::value(tag));
+				return (from != *tag).then(|| (from, tag.clone()));
+			}
+		}
+		let version = dependency.get_mut("version")?;
+		let from = version.as_str()?.to_string();
+		let to = requirement(&from, self.versions.get(&package)?);
+		set(version, from, to)
+	}
+}
+
+// Sets the version of a dependency, retaining its formatting, should it change.
+fn set(item: &mut Item, from: String, to: String) -> Option<(String, String)> {
+	if from == to {
+		return None;
+	}
+	let value = item.as_value_mut()?;
+	let decor = value.decor().clone();
+	*value = to.as_str().into();
+	*value.decor_mut() = decor;
+	Some((from, to))
+}
+
+// The requirement for a version, retaining any operator of the prior requirement, e.g. `=`.
+fn requirement(current: &str, version: &str) -> String {
+	let operator: String = current.chars().take_while(|c| "=^~<>".contains(*c)).collect();
+	format!("{operator}{version}")
+}
+
+fn normalize(repository: &str) -> String {
+	repository.trim_end_matches('/').trim_end_matches(".git").to_lowercase()
+}
+
+/// The kind of API breakage indicated by a compiler error.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum BreakageKind {
+	/// An item was removed, renamed or moved.
+	MissingItem,
+	/// The signature of a function or type changed.
+	ChangedSignature,
+	/// The items of a trait changed, e.g. a pallet `Config`.
+	ChangedTrait,
+	/// Any other error.
+	Other,
+}
+
+impl BreakageKind {
+	fn from_code(code: Option<&str>) -> Self {
+		match code {
+			Some(
+				"E0405" | "E0412" | "E0422" | "E0425" | "E0432" | "E0433" | "E0531" | "E0599" |
+				"E0609",
+			) => Self::MissingItem,
+			Some("E0061" | "E0063" | "E0107" | "E0308" | "E0560") => Self::ChangedSignature,
+			Some("E0046" | "E0049" | "E0050" | "E0053" | "E0195" | "E0220" | "E0407" | "E0437") =>
+				Self::ChangedTrait,
+			_ => Self::Other,
+		}
+	}
+}
+
+impl Display for BreakageKind {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let kind = match self {
+			Self::MissingItem => "removed or renamed item",
+			Self::ChangedSignature => "changed signature",
+			Self::ChangedTrait => "changed trait",
+			Self::Other => "other error",
+		};
+		write!(f, "{kind}")
+	}
+}
+
+/// A compiler error detected when checking a project, which indicates a breaking change of an
+/// upgraded dependency.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Breakage {
+	/// The kind of breakage.
+	pub kind: BreakageKind,
+	/// The error code, e.g. `E0412`.
+	pub code: Option<String>,
+	/// The error message.
+	pub message: String,
+	/// The location of the error, as `file:line`.
+	pub location: Option<String>,
+}
+
+impl Display for Breakage {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		if let Some(code) = &self.code {
+			write!(f, "[{code}] ")?;
+		}
+		write!(f, "{}", self.message)?;
+		if let Some(location) = &self.location {
+			write!(f, " ({location})")?;
+		}
+		Ok(())
+	}
+}
+
+/// Checks that a project builds, returning the errors detected.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+pub fn check(project: &Path) -> Result<Vec<Breakage>, Error> {
+	let output = cmd("cargo", ["check", "--workspace", "--message-format=json"])
+		.dir(project)
+		.stdout_capture()
+		.stderr_null()
+		.unchecked()
+		.run()?;
+	Ok(parse_breakages(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parses the errors reported by the compiler, as JSON messages emitted by cargo.
+///
+/// # Arguments
+/// * `output` - The output of cargo, via `--message-format=json`.
+pub fn parse_breakages(output: &str) -> Vec<Breakage> {
+	#[derive(Deserialize)]
+	struct CargoMessage {
+		reason: String,
+		message: Option<CompilerMessage>,
+	}
+	#[derive(Deserialize)]
+	struct CompilerMessage {
+		level: String,
+		message: String,
+		code: Option<Code>,
+		#[serde(default)]
+		spans: Vec<Span>,
+	}
+	#[derive(Deserialize)]
+	struct Code {
+		code: String,
+	}
+	#[derive(Deserialize)]
+	struct Span {
+		file_name: String,
+		line_start: u32,
+		is_primary: bool,
+	}
+	let mut breakages: Vec<Breakage> = output
+		.lines()
+		.filter_map(|l| serde_json::from_str::<CargoMessage>(l).ok())
+		.filter(|m| m.reason == "compiler-message")
+		.filter_map(|m| m.message)
+		.filter(|m| m.level == "error" && m.code.is_some())
+		.map(|m| {
+			let code = m.code.map(|c| c.code);
+			Breakage {
+				kind: BreakageKind::from_code(code.as_deref()),
+				code,
+				message: m.message,
+				location: m
+					.spans
+					.iter()
+					.find(|s| s.is_primary)
+					.map(|s| format!("{}:{}", s.file_name, s.line_start)),
+			}
+		})
+		.collect();
+	breakages.dedup();
+	breakages
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs::create_dir_all;
+	use tempfile::tempdir;
+
+	#[test]
+	fn release_tag_works() {
+		assert_eq!(release_tag("stable2412"), Some("polkadot-stable2412".into()));
+		assert_eq!(release_tag("polkadot-stable2412-1"), Some("polkadot-stable2412-1".into()));
+		assert_eq!(release_tag("v1.14.0"), None);
+		assert_eq!(release_tag("stable2412-rc1"), None);
+	}
+
+	#[tokio::test]
+	async fn fetch_sdk_versions_works() -> Result<(), Error> {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("GET", "/polkadot-stable2412/Cargo.lock")
+			.with_status(200)
+			.with_body(
+				r#"version = 3
+
+[[package]]
+name = "frame-support"
+version = "38.0.0"
+
+[[package]]
+name = "serde"
+version = "1.0.214"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#,
+			)
+			.create_async()
+			.await;
+		let versions = fetch_sdk_versions(&server.url(), "polkadot-stable2412").await?;
+		assert_eq!(versions, HashMap::from([("frame-support".into(), "38.0.0".into())]));
+		mock.assert_async().await;
+		assert!(matches!(
+			fetch_sdk_versions(&server.url(), "polkadot-stable2401").await,
+			Err(Error::Upgrade(..))
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn manifests_works() -> Result<(), Error> {
+		let temp_dir = tempdir()?;
+		let project = temp_dir.path();
+		for dir in ["node", "runtime", "pallets/template", "target/release/build", ".git"] {
+			create_dir_all(project.join(dir))?;
+			write(project.join(dir).join("Cargo.toml"), "")?;
+		}
+		write(project.join("Cargo.toml"), "")?;
+		assert_eq!(
+			manifests(project),
+			["", "node", "pallets/template", "runtime"]
+				.map(|d| project.join(d).join("Cargo.toml"))
+				.to_vec()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn apply_works() -> Result<(), Error> {
+		let temp_dir = tempdir()?;
+		let manifest = temp_dir.path().join("Cargo.toml");
+		write(
+			&manifest,
+			r#"[workspace.dependencies]
+frame-support = { version = "37.0.0", default-features = false }
+sp-runtime = "=38.0.0"
+serde = "1.0"
+polkadot-sdk = { git = "https://github.com/paritytech/polkadot-sdk.git", branch = "stable2409" }
+
+[dependencies]
+codec = { package = "parity-scale-codec", version = "3.6.12" }
+frame-system = { workspace = true }
+
+[target.'cfg(unix)'.dev-dependencies.pallet-balances]
+version = "38.0.0"
+"#,
+		)?;
+		let upgrade = Upgrade {
+			versions: HashMap::from([
+				("frame-support".into(), "38.0.0".into()),
+				("sp-runtime".into(), "39.0.2".into()),
+				("frame-system".into(), "38.0.0".into()),
+				("parity-scale-codec".into(), "3.7.0".into()),
+				("pallet-balances".into(), "39.0.0".into()),
+			]),
+			git: Some((POLKADOT_SDK.into(), "polkadot-stable2412".into())),
+		};
+		let upgraded = upgrade.apply(&manifest)?;
+		assert_eq!(
+			upgraded.iter().map(|d| d.to_string()).collect::<Vec<_>>(),
+			[
+				"frame-support: 37.0.0 -> 38.0.0",
+				"sp-runtime: =38.0.0 -> =39.0.2",
+				"polkadot-sdk: stable2409 -> polkadot-stable2412",
+				"codec: 3.6.12 -> 3.7.0",
+				"pallet-balances: 38.0.0 -> 39.0.0",
+			]
+		);
+		assert_eq!(
+			read_to_string(&manifest)?,
+			r#"[workspace.dependencies]
+frame-support = { version = "38.0.0", default-features = false }
+sp-runtime = "=39.0.2"
+serde = "1.0"
+polkadot-sdk = { git = "https://github.com/paritytech/polkadot-sdk.git", tag = "polkadot-stable2412" }
+
+[dependencies]
+codec = { package = "parity-scale-codec", version = "3.7.0" }
+frame-system = { workspace = true }
+
+[target.'cfg(unix)'.dev-dependencies.pallet-balances]
+version = "39.0.0"
+"#
+		);
+		// Nothing is changed once upgraded.
+		assert!(upgrade.apply(&manifest)?.is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn ink_versions_works() {
+		let versions = ink_versions("5.1.1");
+		assert_eq!(versions.len(), INK_CRATES.len());
+		assert_eq!(versions["ink_e2e"], "5.1.1");
+	}
+
+	#[test]
+	fn parse_breakages_works() {
+		let output = [
+			r#"{"reason":"compiler-artifact","package_id":"serde"}"#,
+			r#"{"reason":"compiler-message","message":{"level":"error","message":"cannot find type `BlockNumber` in this scope","code":{"code":"E0412","explanation":""},"spans":[{"file_name":"runtime/src/lib.rs","line_start":42,"is_primary":true}]}}"#,
+			r#"{"reason":"compiler-message","message":{"level":"error","message":"not all trait items implemented, missing: `RuntimeTask`","code":{"code":"E0046","explanation":""},"spans":[{"file_name":"runtime/src/lib.rs","line_start":80,"is_primary":false},{"file_name":"runtime/src/lib.rs","line_start":81,"is_primary":true}]}}"#,
+			r#"{"reason":"compiler-message","message":{"level":"warning","message":"unused import","code":{"code":"unused_imports","explanation":null},"spans":[]}}"#,
+			r#"{"reason":"compiler-message","message":{"level":"error","message":"aborting due to 2 previous errors","code":null,"spans":[]}}"#,
+			r#"{"reason":"build-finished","success":false}"#,
+		]
+		.join("\n");
+		let breakages = parse_breakages(&output);
+		assert_eq!(
+			breakages.iter().map(|b| b.to_string()).collect::<Vec<_>>(),
+			[
+				"[E0412] cannot find type `BlockNumber` in this scope (runtime/src/lib.rs:42)",
+				"[E0046] not all trait items implemented, missing: `RuntimeTask` (runtime/src/lib.rs:81)",
+			]
+		);
+		assert_eq!(breakages[0].kind, BreakageKind::MissingItem);
+		assert_eq!(breakages[1].kind, BreakageKind::ChangedTrait);
+		assert_eq!(BreakageKind::from_code(Some("E0061")), BreakageKind::ChangedSignature);
+		assert_eq!(BreakageKind::from_code(None).to_string(), "other error");
+	}
+}
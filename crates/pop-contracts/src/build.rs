@@ -3,7 +3,15 @@
 use crate::{errors::Error, utils::get_manifest_path};
 pub use contract_build::Verbosity;
 use contract_build::{execute, BuildMode, BuildResult, ExecuteArgs};
-use std::path::Path;
+use duct::cmd;
+use sp_core::{blake2_256, bytes::to_hex};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+/// The file within the artifacts directory recording the fingerprint of the last build.
+const FINGERPRINT: &str = ".fingerprint";
 
 /// Build the smart contract located at the specified `path` in `build_release` mode.
 ///
@@ -24,11 +32,125 @@ pub fn build_smart_contract(
 		false => BuildMode::Debug,
 	};
 
+	// Fingerprint the sources before building, so that any changes made during the build are
+	// picked up by the next one.
+	let project = manifest_path.directory().map(Path::to_path_buf).unwrap_or_default();
+	let fingerprint = Fingerprint::new(&project, release);
+
 	// Default values
 	let args = ExecuteArgs { manifest_path, build_mode, verbosity, ..Default::default() };
 
 	// Execute the build and log the output of the build
-	execute(args)
+	let result = execute(args)?;
+	// A missing fingerprint only results in the contract being rebuilt next time.
+	if let Ok(fingerprint) = fingerprint {
+		let _ = fs::write(result.target_directory.join(FINGERPRINT), fingerprint.to_string());
+	}
+	Ok(result)
+}
+
+/// Determines whether the artifacts of a smart contract are up to date, by comparing the
+/// fingerprint of its sources and toolchain with that of the last build.
+///
+/// # Arguments
+/// * `path` - The optional path to the smart contract project, defaulting to the current directory
+///   if not specified.
+/// * `release` - The build mode the artifacts must have been built in, or `None` for either.
+pub fn is_up_to_date(path: Option<&Path>, release: Option<bool>) -> bool {
+	let project = path.unwrap_or_else(|| Path::new("./"));
+	last_build(project).is_some_and(|last| {
+		(release.is_none() || release == Some(last.release)) &&
+			Fingerprint::new(project, last.release).is_ok_and(|current| current == last)
+	})
+}
+
+/// Whether the smart contract has a fingerprint of its last build, which is not the case for
+/// artifacts built by other tools.
+///
+/// # Arguments
+/// * `path` - The optional path to the smart contract project, defaulting to the current directory
+///   if not specified.
+pub fn has_fingerprint(path: Option<&Path>) -> bool {
+	last_build(path.unwrap_or_else(|| Path::new("./"))).is_some()
+}
+
+// The fingerprint of the last build of a project.
+fn last_build(project: &Path) -> Option<Fingerprint> {
+	fs::read_to_string(project.join("target/ink").join(FINGERPRINT))
+		.ok()?
+		.parse()
+		.ok()
+}
+
+// A fingerprint of the sources and toolchain from which a smart contract is built.
+#[derive(Debug, PartialEq)]
+struct Fingerprint {
+	// A hash of the source files of the project.
+	sources: String,
+	// The version of the toolchain used by the project.
+	toolchain: String,
+	// Whether the contract is built without any debugging functionality.
+	release: bool,
+}
+
+impl Fingerprint {
+	fn new(project: &Path, release: bool) -> Result<Self, Error> {
+		let mut files = Vec::new();
+		source_files(project, &mut files)?;
+		files.sort();
+		let mut contents = Vec::new();
+		for file in files {
+			contents.extend(file.strip_prefix(project).unwrap_or(&file).to_string_lossy().bytes());
+			contents.extend(blake2_256(&fs::read(&file)?));
+		}
+		// The toolchain is resolved from within the project, respecting any toolchain file.
+		let toolchain = cmd("rustc", ["--version"]).dir(project).read()?;
+		Ok(Self { sources: to_hex(&blake2_256(&contents), false), toolchain, release })
+	}
+}
+
+impl std::fmt::Display for Fingerprint {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		writeln!(f, "sources: {}", self.sources)?;
+		writeln!(f, "toolchain: {}", self.toolchain)?;
+		writeln!(f, "release: {}", self.release)
+	}
+}
+
+impl std::str::FromStr for Fingerprint {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let field = |name: &str| {
+			s.lines()
+				.find_map(|line| line.strip_prefix(name)?.strip_prefix(": "))
+				.ok_or_else(|| Error::ParseFingerprint(format!("missing {name}")))
+		};
+		Ok(Self {
+			sources: field("sources")?.to_string(),
+			toolchain: field("toolchain")?.to_string(),
+			release: field("release")?
+				.parse()
+				.map_err(|_| Error::ParseFingerprint("invalid release".into()))?,
+		})
+	}
+}
+
+// Collects the source files of a project, excluding build outputs and hidden files.
+fn source_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+	for entry in fs::read_dir(dir)? {
+		let path = entry?.path();
+		let name = path.file_name().unwrap_or_default().to_string_lossy();
+		if name.starts_with('.') || name == "target" {
+			continue;
+		}
+		if path.is_dir() {
+			source_files(&path, files)?;
+		} else {
+			files.push(path);
+		}
+	}
+	Ok(())
 }
 
 /// Determines whether the manifest at the supplied path is a supported smart contract project.
@@ -44,7 +166,6 @@ pub fn is_supported(path: Option<&Path>) -> Result<bool, Error> {
 mod tests {
 	use super::*;
 	use contract_build::new_contract_project;
-	use duct::cmd;
 
 	#[test]
 	fn is_supported_works() -> anyhow::Result<()> {
@@ -62,4 +183,39 @@ mod tests {
 		assert!(is_supported(Some(&path.join(name)))?);
 		Ok(())
 	}
+
+	#[test]
+	fn is_up_to_date_works() -> anyhow::Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let project = temp_dir.path();
+		fs::create_dir_all(project.join("target/ink"))?;
+		fs::write(project.join("lib.rs"), "// contract")?;
+		assert!(!is_up_to_date(Some(project), None));
+		assert!(!has_fingerprint(Some(project)));
+
+		let fingerprint = Fingerprint::new(project, true)?;
+		fs::write(project.join("target/ink").join(FINGERPRINT), fingerprint.to_string())?;
+		assert!(has_fingerprint(Some(project)));
+		assert!(is_up_to_date(Some(project), None));
+		assert!(is_up_to_date(Some(project), Some(true)));
+		assert!(!is_up_to_date(Some(project), Some(false)));
+		// Build outputs don't affect the fingerprint, whereas sources do.
+		fs::write(project.join("target/ink/flipper.contract"), "{}")?;
+		assert!(is_up_to_date(Some(project), Some(true)));
+		fs::write(project.join("lib.rs"), "// changed contract")?;
+		assert!(!is_up_to_date(Some(project), Some(true)));
+		Ok(())
+	}
+
+	#[test]
+	fn fingerprint_parsing_works() -> anyhow::Result<()> {
+		let fingerprint = Fingerprint {
+			sources: "0x01".into(),
+			toolchain: "rustc 1.81.0 (eeb90cda1 2024-09-04)".into(),
+			release: false,
+		};
+		assert_eq!(fingerprint.to_string().parse::<Fingerprint>()?, fingerprint);
+		assert!(matches!("sources: 0x01".parse::<Fingerprint>(), Err(Error::ParseFingerprint(_))));
+		Ok(())
+	}
 }
@@ -15,10 +15,15 @@ use contract_extrinsics::{
 	extrinsic_calls::Call, BalanceVariant, CallCommandBuilder, CallExec, ContractArtifacts,
 	DisplayEvents, ErrorVariant, ExtrinsicOptsBuilder, TokenMetadata,
 };
+use contract_transcode::{ContractMessageTranscoder, Value};
 use ink_env::{DefaultEnvironment, Environment};
 use pop_common::{create_signer, Config, DefaultConfig, Keypair};
+use serde_json::{json, Map};
 use sp_weights::Weight;
-use std::path::PathBuf;
+use std::{
+	fmt::{Display, Formatter},
+	path::PathBuf,
+};
 use subxt::{tx::Payload, SubstrateConfig};
 use url::Url;
 
@@ -47,6 +52,88 @@ pub struct CallOpts {
 	pub execute: bool,
 }
 
+/// The value returned by a contract message, decoded via the return type of the message within the
+/// contract metadata.
+///
+/// ink! wraps the value returned by each message within a `Result`, whose `Err` variant signals
+/// that the message could not be dispatched.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReturnValue(Value);
+
+impl ReturnValue {
+	/// The value returned by the message itself.
+	pub fn value(&self) -> &Value {
+		match &self.0 {
+			Value::Tuple(result) if result.ident().as_deref() == Some("Ok") =>
+				result.values().next().unwrap_or(&self.0),
+			value => value,
+		}
+	}
+
+	/// The error returned by the message, should either the message return the `Err` variant of
+	/// a `Result` or ink! be unable to dispatch it.
+	pub fn error(&self) -> Option<&Value> {
+		[&self.0, self.value()].into_iter().find_map(|value| match value {
+			Value::Tuple(result) if result.ident().as_deref() == Some("Err") =>
+				result.values().next(),
+			_ => None,
+		})
+	}
+
+	/// The value as JSON, in which enum variants and structs are keyed by their name, e.g.
+	/// `{"Ok": {"Err": "InsufficientBalance"}}`.
+	pub fn to_json(&self) -> serde_json::Value {
+		to_json(&self.0)
+	}
+}
+
+impl Display for ReturnValue {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+// Decodes the value returned by a message via its return type within the contract metadata.
+fn decode_return_value(
+	transcoder: &ContractMessageTranscoder,
+	message: &str,
+	data: &[u8],
+) -> anyhow::Result<ReturnValue> {
+	transcoder.decode_message_return(message, &mut &data[..]).map(ReturnValue)
+}
+
+// Converts a decoded value to JSON, with integers which do not fit within 64 bits as strings.
+fn to_json(value: &Value) -> serde_json::Value {
+	let named = |ident: Option<String>, value: serde_json::Value| match ident {
+		Some(ident) => json!({ ident: value }),
+		None => value,
+	};
+	match value {
+		Value::Bool(value) => json!(value),
+		Value::Char(value) => json!(value),
+		Value::UInt(value) =>
+			u64::try_from(*value).map_or_else(|_| json!(value.to_string()), |v| json!(v)),
+		Value::Int(value) =>
+			i64::try_from(*value).map_or_else(|_| json!(value.to_string()), |v| json!(v)),
+		Value::String(value) | Value::Literal(value) => json!(value),
+		Value::Hex(value) => json!(format!("0x{}", value.as_str())),
+		Value::Unit => serde_json::Value::Null,
+		Value::Seq(seq) => seq.elems().iter().map(to_json).collect(),
+		Value::Map(map) => {
+			let fields: Map<_, _> = map.iter().map(|(k, v)| (k.to_string(), to_json(v))).collect();
+			named(map.ident(), fields.into())
+		},
+		Value::Tuple(tuple) => {
+			let mut values: Vec<_> = tuple.values().map(to_json).collect();
+			match (tuple.ident(), values.len()) {
+				(Some(ident), 0) => json!(ident),
+				(ident, 1) => named(ident, values.remove(0)),
+				(ident, _) => named(ident, values.into()),
+			}
+		},
+	}
+}
+
 /// Prepare the preprocessed data for a contract `call`.
 ///
 /// # Arguments
@@ -99,22 +186,22 @@ pub async fn set_up_call(
 	Ok(call_exec)
 }
 
-/// Simulate a smart contract call without modifying the state of the blockchain.
+/// Simulate a smart contract call without modifying the state of the blockchain, returning the
+/// decoded value returned by the message.
 ///
 /// # Arguments
 ///
 /// * `call_exec` - struct with the call to be executed.
 pub async fn dry_run_call(
 	call_exec: &CallExec<DefaultConfig, DefaultEnvironment, Keypair>,
-) -> Result<String, Error> {
+) -> Result<ReturnValue, Error> {
 	let call_result = call_exec.call_dry_run().await?;
 	match call_result.result {
 		Ok(ref ret_val) => {
-			let value = call_exec
-				.transcoder()
-				.decode_message_return(call_exec.message(), &mut &ret_val.data[..])
-				.context(format!("Failed to decode return value {:?}", &ret_val))?;
-			Ok(value.to_string())
+			let value =
+				decode_return_value(call_exec.transcoder(), call_exec.message(), &ret_val.data)
+					.context(format!("Failed to decode return value {:?}", ret_val))?;
+			Ok(value)
 		},
 		Err(ref err) => {
 			let error_variant =
@@ -232,6 +319,7 @@ mod tests {
 		set_up_deployment, UpOpts,
 	};
 	use anyhow::Result;
+	use contract_transcode::Tuple;
 	use pop_common::{find_free_port, set_executable_permission};
 	use sp_core::Bytes;
 	use std::{env, process::Command, time::Duration};
@@ -326,6 +414,64 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn decode_return_value_works() -> Result<()> {
+		let current_dir = env::current_dir().expect("Failed to get current directory");
+		let artifacts = ContractArtifacts::from_manifest_or_file(
+			None,
+			Some(&current_dir.join("./tests/files/testing.json")),
+		)?;
+		let transcoder = artifacts.contract_transcoder()?;
+		let value = decode_return_value(&transcoder, "get", &[0, 1])?;
+		assert_eq!(value.to_string(), "Ok(true)");
+		assert_eq!(value.value(), &Value::Bool(true));
+		assert_eq!(value.error(), None);
+		assert_eq!(value.to_json(), serde_json::json!({ "Ok": true }));
+		// The language error raised should ink! be unable to dispatch the message.
+		let value = decode_return_value(&transcoder, "get", &[1, 1])?;
+		assert_eq!(
+			value.error(),
+			Some(&Value::Tuple(Tuple::new(Some("CouldNotReadInput"), vec![])))
+		);
+		assert_eq!(value.to_json(), serde_json::json!({ "Err": "CouldNotReadInput" }));
+		assert!(decode_return_value(&transcoder, "flip_all", &[0]).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn return_value_works() {
+		let result = |ident: &str, value: Value| Value::Tuple(Tuple::new(Some(ident), vec![value]));
+		// A message returning an error of its own.
+		let value = ReturnValue(result(
+			"Ok",
+			result("Err", Value::Tuple(Tuple::new(Some("InsufficientBalance"), vec![]))),
+		));
+		assert_eq!(
+			value.error(),
+			Some(&Value::Tuple(Tuple::new(Some("InsufficientBalance"), vec![])))
+		);
+		assert_eq!(value.to_json(), serde_json::json!({ "Ok": { "Err": "InsufficientBalance" } }));
+		// Integers which do not fit within 64 bits are represented as strings.
+		let fields = [
+			(Value::String("free".into()), Value::UInt(u128::MAX)),
+			(Value::String("reserved".into()), Value::UInt(10)),
+			(Value::String("delta".into()), Value::Int(-5)),
+		];
+		let value = ReturnValue(result(
+			"Ok",
+			Value::Seq(vec![Value::Map(fields.into_iter().collect()), Value::Unit].into()),
+		));
+		assert!(matches!(value.value(), Value::Seq(..)));
+		assert_eq!(value.error(), None);
+		assert_eq!(
+			value.to_json(),
+			serde_json::json!({ "Ok": [
+				{ "free": u128::MAX.to_string(), "reserved": 10, "delta": -5 },
+				null
+			] })
+		);
+	}
+
 	#[tokio::test]
 	async fn test_dry_run_call_error_contract_not_deployed() -> Result<()> {
 		let temp_dir = new_environment("testing")?;
@@ -433,7 +579,8 @@ mod tests {
 		})
 		.await?;
 		let mut query = dry_run_call(&query_exec).await?;
-		assert_eq!(query, "Ok(false)");
+		assert_eq!(query.to_string(), "Ok(false)");
+		assert_eq!(query.to_json(), serde_json::json!({ "Ok": false }));
 		// Test extrinsic execution by flipping the value.
 		let call_exec = set_up_call(CallOpts {
 			path: Some(temp_dir.path().join("testing")),
@@ -454,7 +601,7 @@ mod tests {
 		call_smart_contract(call_exec, weight, &Url::parse(&localhost_url)?).await?;
 		// Assert that the value has been flipped.
 		query = dry_run_call(&query_exec).await?;
-		assert_eq!(query, "Ok(true)");
+		assert_eq!(query.to_string(), "Ok(true)");
 		// Stop the process contracts-node
 		Command::new("kill")
 			.args(["-s", "TERM", &process.id().to_string()])
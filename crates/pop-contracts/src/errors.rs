@@ -45,6 +45,8 @@ pub enum Error {
 	MissingArgument(String),
 	#[error("Failed to create new contract project: {0}")]
 	NewContract(String),
+	#[error("Failed to parse the fingerprint of the build: {0}")]
+	ParseFingerprint(String),
 	#[error("ParseError error: {0}")]
 	ParseError(#[from] url::ParseError),
 	#[error("The `Repository` property is missing from the template variant")]
@@ -57,4 +59,6 @@ pub enum Error {
 	UnsupportedPlatform { os: &'static str },
 	#[error("{0}")]
 	UploadContractError(String),
+	#[error("Failed to verify the contract: {0}")]
+	VerificationError(String),
 }
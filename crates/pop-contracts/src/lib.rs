@@ -11,16 +11,20 @@ mod test;
 mod testing;
 mod up;
 mod utils;
+mod verify;
 
-pub use build::{build_smart_contract, is_supported, Verbosity};
+pub use build::{build_smart_contract, has_fingerprint, is_supported, is_up_to_date, Verbosity};
 pub use call::{
 	call_smart_contract, call_smart_contract_from_signed_payload, dry_run_call,
-	dry_run_gas_estimate_call, get_call_payload, set_up_call, CallOpts,
+	dry_run_gas_estimate_call, get_call_payload, set_up_call, CallOpts, ReturnValue,
 };
+pub use errors::Error;
 pub use new::{create_smart_contract, is_valid_contract_name};
 pub use node::{contracts_node_generator, is_chain_alive, run_contracts_node};
 pub use templates::{Contract, ContractType};
-pub use test::{test_e2e_smart_contract, test_smart_contract};
+pub use test::{
+	has_sandbox_tests, test_e2e_smart_contract, test_sandbox_smart_contract, test_smart_contract,
+};
 pub use testing::{mock_build_process, new_environment};
 pub use up::{
 	dry_run_gas_estimate_instantiate, dry_run_upload, get_code_hash_from_event, get_contract_code,
@@ -32,6 +36,7 @@ pub use utils::{
 	metadata::{get_message, get_messages, ContractFunction},
 	parse_account, parse_hex_bytes,
 };
+pub use verify::{get_contract_bundle, upload_verification_package, Verification};
 // External exports
 pub use contract_extrinsics::CallExec;
 pub use ink_env::DefaultEnvironment;
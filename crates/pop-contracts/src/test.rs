@@ -1,45 +1,109 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use crate::errors::Error;
-use duct::cmd;
+use pop_common::{
+	manifest::from_path,
+	test_report::{cargo_test, Coverage, TestReport, TestRun},
+};
 use std::{env, path::Path};
 
-/// Run unit tests of a smart contract.
+/// Run unit tests of a smart contract, returning a report of the results.
 ///
 /// # Arguments
 ///
 /// * `path` - location of the smart contract.
-pub fn test_smart_contract(path: Option<&Path>) -> Result<(), Error> {
+/// * `test_run` - the seed and tests of the run.
+/// * `coverage` - whether coverage should be collected. As unit tests are run natively within the
+///   off-chain environment, the contract itself is instrumented.
+pub fn test_smart_contract(
+	path: Option<&Path>,
+	test_run: &TestRun,
+	coverage: Option<&Coverage>,
+) -> Result<TestReport, Error> {
 	// Execute `cargo test` command in the specified directory.
-	cmd("cargo", vec!["test"])
-		.dir(path.unwrap_or_else(|| Path::new("./")))
-		.run()
-		.map_err(|e| Error::TestCommand(format!("Cargo test command failed: {}", e)))?;
-	Ok(())
+	run(path, &test_run.args(&[]), &test_run.envs(), coverage)
 }
 
-/// Run e2e tests of a smart contract.
+/// Run e2e tests of a smart contract, returning a report of the results.
 ///
 /// # Arguments
 ///
 /// * `path` - location of the smart contract.
 /// * `node` - location of the contracts node binary.
-pub fn test_e2e_smart_contract(path: Option<&Path>, node: Option<&Path>) -> Result<(), Error> {
+/// * `test_run` - the seed and tests of the run.
+pub fn test_e2e_smart_contract(
+	path: Option<&Path>,
+	node: Option<&Path>,
+	test_run: &TestRun,
+) -> Result<TestReport, Error> {
 	// Set the environment variable `CONTRACTS_NODE` to the path of the contracts node.
 	if let Some(node) = node {
 		env::set_var("CONTRACTS_NODE", node);
 	}
 	// Execute `cargo test --features=e2e-tests` command in the specified directory.
-	cmd("cargo", vec!["test", "--features=e2e-tests"])
-		.dir(path.unwrap_or_else(|| Path::new("./")))
-		.run()
+	// Coverage is not collected, as the contract is compiled to Wasm and executed by the node.
+	let args = test_run.args(&["--features=e2e-tests".into()]);
+	run(path, &args, &test_run.envs(), None)
+}
+
+/// The dependencies providing a sandboxed runtime for testing a smart contract.
+const SANDBOX_DEPENDENCIES: [&str; 2] = ["drink", "ink_sandbox"];
+/// The feature gating the sandboxed tests of a smart contract, if declared.
+const SANDBOX_FEATURE: &str = "sandbox-tests";
+
+/// Determines whether a smart contract is tested within a sandboxed runtime, i.e. whether it
+/// depends on drink! (or the ink! sandbox) for testing.
+///
+/// # Arguments
+///
+/// * `path` - location of the smart contract.
+pub fn has_sandbox_tests(path: Option<&Path>) -> Result<bool, Error> {
+	let manifest = from_path(path)?;
+	Ok(SANDBOX_DEPENDENCIES.iter().any(|d| manifest.dev_dependencies.contains_key(*d)))
+}
+
+/// Run the sandboxed tests of a smart contract, returning a report of the results. The contract is
+/// executed by a runtime embedded within the tests (via drink!), so chain state can be
+/// manipulated and several contracts can interact without a node. The tests are gated behind the
+/// `sandbox-tests` feature where declared.
+///
+/// # Arguments
+///
+/// * `path` - location of the smart contract.
+/// * `test_run` - the seed and tests of the run.
+/// * `coverage` - whether coverage should be collected.
+pub fn test_sandbox_smart_contract(
+	path: Option<&Path>,
+	test_run: &TestRun,
+	coverage: Option<&Coverage>,
+) -> Result<TestReport, Error> {
+	let mut args = vec![];
+	if from_path(path)?.features.contains_key(SANDBOX_FEATURE) {
+		args.push(format!("--features={SANDBOX_FEATURE}"));
+	}
+	run(path, &test_run.args(&args), &test_run.envs(), coverage)
+}
+
+/// Runs `cargo test` in the specified directory, failing if the tests could not be run at all
+/// (e.g. due to a build failure). Test failures are reported via the resulting report.
+fn run(
+	path: Option<&Path>,
+	args: &[String],
+	envs: &[(String, String)],
+	coverage: Option<&Coverage>,
+) -> Result<TestReport, Error> {
+	let report = cargo_test(path.unwrap_or_else(|| Path::new("./")), args, envs, coverage)
 		.map_err(|e| Error::TestCommand(format!("Cargo test command failed: {}", e)))?;
-	Ok(())
+	if !report.success && report.cases.is_empty() {
+		return Err(Error::TestCommand("Cargo test command failed".into()));
+	}
+	Ok(report)
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use duct::cmd;
 	use tempfile;
 
 	#[test]
@@ -47,7 +111,12 @@ mod tests {
 		let temp_dir = tempfile::tempdir()?;
 		cmd("cargo", ["new", "test_contract", "--bin"]).dir(temp_dir.path()).run()?;
 		// Run unit tests for the smart contract in the temporary contract directory.
-		test_smart_contract(Some(&temp_dir.path().join("test_contract")))?;
+		let report = test_smart_contract(
+			Some(&temp_dir.path().join("test_contract")),
+			&TestRun::default(),
+			None,
+		)?;
+		assert!(report.success);
 		Ok(())
 	}
 
@@ -55,25 +124,45 @@ mod tests {
 	fn test_smart_contract_wrong_directory() -> Result<(), Error> {
 		let temp_dir = tempfile::tempdir()?;
 		assert!(matches!(
-			test_smart_contract(Some(&temp_dir.path().join(""))),
+			test_smart_contract(Some(&temp_dir.path().join("")), &TestRun::default(), None),
 			Err(Error::TestCommand(..))
 		));
 		Ok(())
 	}
 
+	#[test]
+	fn test_smart_contract_reports_failures() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		cmd("cargo", ["new", "test_contract", "--lib"]).dir(temp_dir.path()).run()?;
+		let path = temp_dir.path().join("test_contract");
+		std::fs::write(
+			path.join("src/lib.rs"),
+			"#[test]\nfn passes() {}\n#[test]\nfn fails() { panic!(\"failed\") }\n",
+		)?;
+		let report = test_smart_contract(Some(&path), &TestRun::default(), None)?;
+		assert!(!report.success);
+		assert_eq!((report.cases.len(), report.failures()), (2, 1));
+		Ok(())
+	}
+
 	#[test]
 	fn test_e2e_smart_contract_set_env_variable() -> Result<(), Error> {
 		let temp_dir = tempfile::tempdir()?;
 		cmd("cargo", ["new", "test_contract", "--bin"]).dir(temp_dir.path()).run()?;
 		// Ignore 2e2 testing in this scenario, will fail. Only test if the environment variable
 		// CONTRACTS_NODE is set.
-		let err = test_e2e_smart_contract(Some(&temp_dir.path().join("test_contract")), None);
+		let err = test_e2e_smart_contract(
+			Some(&temp_dir.path().join("test_contract")),
+			None,
+			&TestRun::default(),
+		);
 		assert!(err.is_err());
 		// The environment variable `CONTRACTS_NODE` should not be set.
 		assert!(env::var("CONTRACTS_NODE").is_err());
 		let err = test_e2e_smart_contract(
 			Some(&temp_dir.path().join("test_contract")),
 			Some(&Path::new("/path/to/contracts-node")),
+			&TestRun::default(),
 		);
 		assert!(err.is_err());
 		// The environment variable `CONTRACTS_NODE` should has been set.
@@ -89,9 +178,51 @@ mod tests {
 		let temp_dir = tempfile::tempdir()?;
 		cmd("cargo", ["new", "test_contract", "--bin"]).dir(temp_dir.path()).run()?;
 		assert!(matches!(
-			test_e2e_smart_contract(Some(&temp_dir.path().join("test_contract")), None),
+			test_e2e_smart_contract(
+				Some(&temp_dir.path().join("test_contract")),
+				None,
+				&TestRun::default(),
+			),
 			Err(Error::TestCommand(..))
 		));
 		Ok(())
 	}
+
+	#[test]
+	fn has_sandbox_tests_works() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		cmd("cargo", ["new", "test_contract", "--lib"]).dir(temp_dir.path()).run()?;
+		let path = temp_dir.path().join("test_contract");
+		assert!(!has_sandbox_tests(Some(&path))?);
+		let manifest = std::fs::read_to_string(path.join("Cargo.toml"))?;
+		std::fs::write(
+			path.join("Cargo.toml"),
+			format!("{manifest}\n[dev-dependencies]\ndrink = \"0.18\"\n"),
+		)?;
+		assert!(has_sandbox_tests(Some(&path))?);
+		Ok(())
+	}
+
+	#[test]
+	fn test_sandbox_smart_contract_enables_feature() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		cmd("cargo", ["new", "test_contract", "--lib"]).dir(temp_dir.path()).run()?;
+		let path = temp_dir.path().join("test_contract");
+		std::fs::write(
+			path.join("src/lib.rs"),
+			"#[cfg(feature = \"sandbox-tests\")]\n#[test]\nfn sandboxed() {}\n#[test]\nfn unit() {}\n",
+		)?;
+		// Where the feature is not declared, the tests are run without it.
+		let report = test_sandbox_smart_contract(Some(&path), &TestRun::default(), None)?;
+		assert_eq!(report.cases.len(), 1);
+		let manifest = std::fs::read_to_string(path.join("Cargo.toml"))?;
+		std::fs::write(
+			path.join("Cargo.toml"),
+			format!("{manifest}\n[features]\nsandbox-tests = []\n"),
+		)?;
+		let report = test_sandbox_smart_contract(Some(&path), &TestRun::default(), None)?;
+		assert!(report.success);
+		assert_eq!(report.cases.len(), 2);
+		Ok(())
+	}
 }
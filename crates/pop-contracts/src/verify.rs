@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::errors::Error;
+use pop_common::manifest::from_path;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// The response of a contract verification service to an uploaded verification package.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Verification {
+	/// The status of the verification, e.g. `pending` or `verified`.
+	pub status: Option<String>,
+	/// A link to the verified contract, e.g. within an explorer.
+	pub url: Option<String>,
+}
+
+/// Returns the path to the contract bundle (`.contract`) of a built contract, which contains
+/// both the metadata and the code of the contract along with the information required to
+/// reproduce the build.
+///
+/// # Arguments
+/// * `path` - The path to the contract project.
+pub fn get_contract_bundle(path: Option<&Path>) -> Result<PathBuf, Error> {
+	let project = path.unwrap_or_else(|| Path::new("./"));
+	let manifest = from_path(Some(project))?;
+	let name = manifest
+		.package
+		.map(|p| p.name)
+		.ok_or_else(|| Error::ManifestPath("the manifest has no package".into()))?;
+	let bundle = project.join(format!("target/ink/{name}.contract"));
+	if !bundle.exists() {
+		return Err(Error::VerificationError(format!(
+			"the contract bundle {} was not found",
+			bundle.display()
+		)));
+	}
+	Ok(bundle)
+}
+
+/// Uploads the verification package of a deployed contract to a contract verification service,
+/// so that the contract is shown as verified within explorers.
+///
+/// # Arguments
+/// * `api` - The base URL of the API of the verification service.
+/// * `api_key` - The key used to authenticate with the API, if required.
+/// * `network` - The endpoint of the chain the contract is deployed to.
+/// * `code_hash` - The code hash of the deployed contract.
+/// * `bundle` - The path to the contract bundle.
+pub async fn upload_verification_package(
+	api: &Url,
+	api_key: Option<&str>,
+	network: &str,
+	code_hash: &str,
+	bundle: &Path,
+) -> Result<Verification, Error> {
+	let contents = std::fs::read_to_string(bundle)?;
+	let bundle: Value = serde_json::from_str(&contents)
+		.map_err(|e| Error::VerificationError(format!("invalid contract bundle: {e}")))?;
+	let request = reqwest::Client::new()
+		.post(api.join("verify")?)
+		.json(&json!({ "network": network, "code_hash": code_hash, "bundle": bundle }));
+	let request = match api_key {
+		Some(key) => request.bearer_auth(key),
+		None => request,
+	};
+	let response = request
+		.send()
+		.await
+		.and_then(|r| r.error_for_status())
+		.map_err(|e| Error::VerificationError(e.to_string()))?;
+	response.json().await.map_err(|e| Error::VerificationError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mock_build_process, new_environment};
+	use anyhow::Result;
+	use std::env;
+
+	#[test]
+	fn get_contract_bundle_works() -> Result<()> {
+		let temp_dir = new_environment("testing")?;
+		let project = temp_dir.path().join("testing");
+		assert!(matches!(
+			get_contract_bundle(Some(&project)),
+			Err(Error::VerificationError(message)) if message.contains("testing.contract")
+		));
+		let current_dir = env::current_dir()?;
+		mock_build_process(
+			project.clone(),
+			current_dir.join("./tests/files/testing.contract"),
+			current_dir.join("./tests/files/testing.json"),
+		)?;
+		assert_eq!(
+			get_contract_bundle(Some(&project))?,
+			project.join("target/ink/testing.contract")
+		);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn upload_verification_package_works() -> Result<()> {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("POST", "/verify")
+			.match_header("authorization", "Bearer key")
+			.match_body(mockito::Matcher::PartialJson(json!({
+				"network": "wss://rpc.example.com",
+				"code_hash": "0x1234",
+				"bundle": { "source": { "hash": "0x1234" } }
+			})))
+			.with_body(r#"{"status":"pending","url":"https://explorer.example.com/0x1234"}"#)
+			.create_async()
+			.await;
+		let temp_dir = tempfile::tempdir()?;
+		let bundle = temp_dir.path().join("flipper.contract");
+		std::fs::write(&bundle, r#"{"source":{"hash":"0x1234"}}"#)?;
+		let api: Url = format!("{}/", server.url()).parse()?;
+
+		let verification = upload_verification_package(
+			&api,
+			Some("key"),
+			"wss://rpc.example.com",
+			"0x1234",
+			&bundle,
+		)
+		.await?;
+		assert_eq!(
+			verification,
+			Verification {
+				status: Some("pending".into()),
+				url: Some("https://explorer.example.com/0x1234".into())
+			}
+		);
+		mock.assert_async().await;
+
+		// An invalid bundle is not uploaded.
+		std::fs::write(&bundle, "")?;
+		assert!(matches!(
+			upload_verification_package(&api, None, "wss://rpc.example.com", "0x1234", &bundle)
+				.await,
+			Err(Error::VerificationError(message)) if message.contains("invalid contract bundle")
+		));
+		Ok(())
+	}
+}
@@ -0,0 +1,346 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	errors::Error,
+	xcm::{location, x2},
+};
+use scale_value::{Composite, ValueDef};
+use std::fmt::{Display, Formatter};
+use subxt::{
+	dynamic::{self, Value},
+	tx::DynamicPayload,
+	utils::AccountId32,
+	OnlineClient, SubstrateConfig,
+};
+
+/// The pallets in which assets are registered, along with whether they are keyed by location.
+const ASSET_PALLETS: [(&str, bool); 2] = [("Assets", false), ("ForeignAssets", true)];
+
+/// The identifier of an asset registered on a chain.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AssetId {
+	/// An asset registered by its index.
+	Index(u128),
+	/// A foreign asset, registered by its location.
+	Location(Value),
+}
+
+impl Display for AssetId {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Index(index) => write!(f, "{index}"),
+			Self::Location(location) => write!(f, "{location}"),
+		}
+	}
+}
+
+/// An asset registered on a chain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegisteredAsset {
+	/// The identifier of the asset.
+	pub id: AssetId,
+	/// The name of the asset.
+	pub name: String,
+	/// The symbol of the asset.
+	pub symbol: String,
+	/// The number of decimals of the asset.
+	pub decimals: u8,
+	/// The minimum balance of an account holding the asset.
+	pub min_balance: u128,
+	/// The (unversioned) location of the asset, relative to the chain on which it is registered.
+	pub location: Value,
+}
+
+/// Queries the assets registered on a chain, via its `Assets` and `ForeignAssets` pallets.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+pub async fn registered_assets(
+	client: &OnlineClient<SubstrateConfig>,
+) -> Result<Vec<RegisteredAsset>, Error> {
+	let metadata = client.metadata();
+	let storage = client.storage().at_latest().await?;
+	let mut assets = Vec::new();
+	for (pallet, foreign) in ASSET_PALLETS {
+		let Some(index) = metadata.pallet_by_name(pallet).map(|p| p.index()) else { continue };
+		let mut entries =
+			storage.iter(dynamic::storage(pallet, "Asset", Vec::<Value>::new())).await?;
+		while let Some(entry) = entries.next().await {
+			let entry = entry?;
+			let Some(key) = entry.keys.into_iter().next() else { continue };
+			let details = entry
+				.value
+				.to_value()
+				.map_err(|e| Error::MetadataParsingError(format!("asset details: {e}")))?;
+			let metadata = storage
+				.fetch(&dynamic::storage(pallet, "Metadata", vec![key.clone()]))
+				.await?
+				.map(|m| m.to_value())
+				.transpose()
+				.map_err(|e| Error::MetadataParsingError(format!("asset metadata: {e}")))?;
+			assets.push(parse_asset(index, foreign, key, &details, metadata.as_ref())?);
+		}
+	}
+	Ok(assets)
+}
+
+/// Queries an asset registered by its index in the `Assets` pallet of a chain, if any.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `id` - The index of the asset.
+pub async fn registered_asset(
+	client: &OnlineClient<SubstrateConfig>,
+	id: u128,
+) -> Result<Option<RegisteredAsset>, Error> {
+	let (pallet, foreign) = ASSET_PALLETS[0];
+	let Some(index) = client.metadata().pallet_by_name(pallet).map(|p| p.index()) else {
+		return Ok(None);
+	};
+	let storage = client.storage().at_latest().await?;
+	let key = Value::u128(id);
+	let Some(details) =
+		storage.fetch(&dynamic::storage(pallet, "Asset", vec![key.clone()])).await?
+	else {
+		return Ok(None);
+	};
+	let details = details
+		.to_value()
+		.map_err(|e| Error::MetadataParsingError(format!("asset details: {e}")))?;
+	let metadata = storage
+		.fetch(&dynamic::storage(pallet, "Metadata", vec![key.clone()]))
+		.await?
+		.map(|m| m.to_value())
+		.transpose()
+		.map_err(|e| Error::MetadataParsingError(format!("asset metadata: {e}")))?;
+	parse_asset(index, foreign, key, &details, metadata.as_ref()).map(Some)
+}
+
+/// Constructs a call to create an asset in the `Assets` pallet, administered by the specified
+/// account.
+///
+/// # Arguments
+/// * `id` - The index of the asset.
+/// * `admin` - The account administering the asset.
+/// * `min_balance` - The minimum balance of an account holding the asset.
+pub fn create_asset(id: u128, admin: &AccountId32, min_balance: u128) -> DynamicPayload {
+	dynamic::tx("Assets", "create", vec![Value::u128(id), address(admin), Value::u128(min_balance)])
+}
+
+/// Constructs a call to set the metadata of an asset in the `Assets` pallet.
+///
+/// # Arguments
+/// * `id` - The index of the asset.
+/// * `name` - The name of the asset.
+/// * `symbol` - The symbol of the asset.
+/// * `decimals` - The number of decimals of the asset.
+pub fn set_asset_metadata(id: u128, name: &str, symbol: &str, decimals: u8) -> DynamicPayload {
+	dynamic::tx(
+		"Assets",
+		"set_metadata",
+		vec![
+			Value::u128(id),
+			Value::from_bytes(name),
+			Value::from_bytes(symbol),
+			Value::u128(decimals.into()),
+		],
+	)
+}
+
+/// Constructs a call to mint an amount of an asset in the `Assets` pallet to an account.
+///
+/// # Arguments
+/// * `id` - The index of the asset.
+/// * `beneficiary` - The account receiving the minted amount.
+/// * `amount` - The amount to be minted, in the smallest unit of the asset.
+pub fn mint_asset(id: u128, beneficiary: &AccountId32, amount: u128) -> DynamicPayload {
+	dynamic::tx("Assets", "mint", vec![Value::u128(id), address(beneficiary), Value::u128(amount)])
+}
+
+/// Constructs a call to transfer an amount of an asset in the `Assets` pallet, keeping the sender
+/// alive.
+///
+/// # Arguments
+/// * `id` - The index of the asset.
+/// * `target` - The account receiving the amount.
+/// * `amount` - The amount to be transferred, in the smallest unit of the asset.
+pub fn transfer_asset(id: u128, target: &AccountId32, amount: u128) -> DynamicPayload {
+	dynamic::tx(
+		"Assets",
+		"transfer_keep_alive",
+		vec![Value::u128(id), address(target), Value::u128(amount)],
+	)
+}
+
+/// Constructs a call to freeze an asset in the `Assets` pallet, either for the specified account
+/// or, if none specified, for all accounts.
+///
+/// # Arguments
+/// * `id` - The index of the asset.
+/// * `who` - The account for which the asset is frozen, if any.
+pub fn freeze_asset(id: u128, who: Option<&AccountId32>) -> DynamicPayload {
+	match who {
+		Some(who) => dynamic::tx("Assets", "freeze", vec![Value::u128(id), address(who)]),
+		None => dynamic::tx("Assets", "freeze_asset", vec![Value::u128(id)]),
+	}
+}
+
+// The address of an account, as expected by the calls of the `Assets` pallet.
+fn address(account: &AccountId32) -> Value {
+	Value::unnamed_variant("Id", [Value::from_bytes(account.0)])
+}
+
+// Parses a registered asset from its key, details and metadata.
+fn parse_asset(
+	pallet: u8,
+	foreign: bool,
+	key: Value,
+	details: &Value<u32>,
+	metadata: Option<&Value<u32>>,
+) -> Result<RegisteredAsset, Error> {
+	let parse_error = || Error::MetadataParsingError("asset".into());
+	let (id, location) = if foreign {
+		(AssetId::Location(key.clone()), key)
+	} else {
+		let index = key.as_u128().ok_or_else(parse_error)?;
+		let location = location(
+			0,
+			x2(
+				Value::unnamed_variant("PalletInstance", [Value::u128(pallet.into())]),
+				Value::unnamed_variant("GeneralIndex", [Value::u128(index)]),
+			),
+		);
+		(AssetId::Index(index), location)
+	};
+	let ValueDef::Composite(details) = &details.value else { return Err(parse_error()) };
+	let min_balance = field(details, "min_balance")
+		.and_then(|v| v.as_u128())
+		.ok_or_else(parse_error)?;
+	let (name, symbol, decimals) = match metadata.map(|m| &m.value) {
+		Some(ValueDef::Composite(metadata)) => (
+			field(metadata, "name").map(text).unwrap_or_default(),
+			field(metadata, "symbol").map(text).unwrap_or_default(),
+			field(metadata, "decimals").and_then(|d| d.as_u128()).unwrap_or_default() as u8,
+		),
+		_ => Default::default(),
+	};
+	Ok(RegisteredAsset { id, name, symbol, decimals, min_balance, location })
+}
+
+fn field<'a, T>(composite: &'a Composite<T>, name: &str) -> Option<&'a Value<T>> {
+	match composite {
+		Composite::Named(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+		Composite::Unnamed(_) => None,
+	}
+}
+
+// Decodes text stored as bytes, which may be wrapped in a bounded vector.
+fn text<T>(value: &Value<T>) -> String {
+	let ValueDef::Composite(bytes) = &value.value else { return String::new() };
+	let bytes = match bytes.values().next() {
+		Some(Value { value: ValueDef::Composite(inner), .. }) if bytes.len() == 1 => inner,
+		_ => bytes,
+	};
+	let bytes: Vec<u8> = bytes.values().filter_map(|b| b.as_u128()).map(|b| b as u8).collect();
+	String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn details(min_balance: u128) -> Value<u32> {
+		Value::named_composite([
+			("supply", Value::u128(1_000)),
+			("min_balance", Value::u128(min_balance)),
+			("is_sufficient", Value::bool(true)),
+		])
+		.map_context(|_| 0)
+	}
+
+	fn metadata(name: &str, symbol: &str, decimals: u8) -> Value<u32> {
+		Value::named_composite([
+			("deposit", Value::u128(0)),
+			("name", Value::unnamed_composite([Value::from_bytes(name)])),
+			("symbol", Value::from_bytes(symbol)),
+			("decimals", Value::u128(decimals.into())),
+		])
+		.map_context(|_| 0)
+	}
+
+	#[test]
+	fn parse_local_asset_works() -> Result<(), Error> {
+		let asset = parse_asset(
+			50,
+			false,
+			Value::u128(1984),
+			&details(70_000),
+			Some(&metadata("Tether USD", "USDt", 6)),
+		)?;
+		assert_eq!(
+			asset,
+			RegisteredAsset {
+				id: AssetId::Index(1984),
+				name: "Tether USD".into(),
+				symbol: "USDt".into(),
+				decimals: 6,
+				min_balance: 70_000,
+				location: location(
+					0,
+					x2(
+						Value::unnamed_variant("PalletInstance", [Value::u128(50)]),
+						Value::unnamed_variant("GeneralIndex", [Value::u128(1984)]),
+					)
+				),
+			}
+		);
+		assert_eq!(asset.id.to_string(), "1984");
+		Ok(())
+	}
+
+	#[test]
+	fn parse_foreign_asset_works() -> Result<(), Error> {
+		let key = location(1, Value::unnamed_variant("Here", []));
+		let asset = parse_asset(53, true, key.clone(), &details(1), None)?;
+		assert_eq!(asset.id, AssetId::Location(key.clone()));
+		assert_eq!(asset.location, key);
+		assert_eq!(asset.symbol, "");
+		assert_eq!(asset.decimals, 0);
+		Ok(())
+	}
+
+	#[test]
+	fn calls_are_constructed() {
+		let alice = AccountId32([1; 32]);
+		let calls = [
+			(create_asset(1, &alice, 1_000), "create"),
+			(set_asset_metadata(1, "Pop", "POP", 10), "set_metadata"),
+			(mint_asset(1, &alice, 1_000), "mint"),
+			(transfer_asset(1, &alice, 1_000), "transfer_keep_alive"),
+			(freeze_asset(1, Some(&alice)), "freeze"),
+			(freeze_asset(1, None), "freeze_asset"),
+		];
+		for (call, name) in calls {
+			assert_eq!((call.pallet_name(), call.call_name()), ("Assets", name));
+		}
+		let Composite::Unnamed(args) = create_asset(1, &alice, 1_000).call_data().clone() else {
+			panic!("expected unnamed arguments")
+		};
+		assert_eq!(
+			args,
+			vec![
+				Value::u128(1),
+				Value::unnamed_variant("Id", [Value::from_bytes([1; 32])]),
+				Value::u128(1_000)
+			]
+		);
+	}
+
+	#[test]
+	fn parse_asset_fails_without_details() {
+		assert!(matches!(
+			parse_asset(50, false, Value::u128(1), &Value::u128(0).map_context(|_| 0), None),
+			Err(Error::MetadataParsingError(..))
+		));
+	}
+}
@@ -0,0 +1,471 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{errors::Error, Connection};
+use scale_value::{
+	stringify::{custom_formatters::format_hex, to_writer_custom},
+	Composite, Value,
+};
+use std::{
+	fmt::{Display, Formatter},
+	ops::RangeInclusive,
+	str::FromStr,
+};
+use subxt::{
+	blocks::{Block, ExtrinsicDetails, ExtrinsicEvents},
+	events::{EventDetails, Phase},
+	utils::{AccountId32, H256},
+	OnlineClient, SubstrateConfig,
+};
+
+/// Identifies a block, by either its number or its hash.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockId {
+	/// The number of the block.
+	Number(u32),
+	/// The hash of the block.
+	Hash(H256),
+}
+
+impl FromStr for BlockId {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Ok(number) = s.parse() {
+			return Ok(Self::Number(number));
+		}
+		H256::from_str(s).map(Self::Hash).map_err(|_| Error::ParamProcessingError)
+	}
+}
+
+impl Display for BlockId {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Number(number) => write!(f, "#{number}"),
+			Self::Hash(hash) => write!(f, "{hash:?}"),
+		}
+	}
+}
+
+/// A decoded event.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedEvent {
+	/// The name of the pallet which emitted the event.
+	pub pallet: String,
+	/// The name of the event.
+	pub name: String,
+	/// The fields of the event, by name or position.
+	pub fields: Vec<(String, String)>,
+}
+
+impl DecodedEvent {
+	/// Decodes an event via the metadata of the chain.
+	///
+	/// # Arguments
+	/// * `event` - The event to be decoded.
+	pub fn decode(event: &EventDetails<SubstrateConfig>) -> Result<Self, Error> {
+		Ok(Self {
+			pallet: event.pallet_name().to_string(),
+			name: event.variant_name().to_string(),
+			fields: fields(&event.field_values().map_err(subxt::Error::from)?),
+		})
+	}
+}
+
+impl Display for DecodedEvent {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}.{}", self.pallet, self.name)?;
+		if !self.fields.is_empty() {
+			let fields: Vec<_> = self.fields.iter().map(|(n, v)| format!("{n}: {v}")).collect();
+			write!(f, " {{ {} }}", fields.join(", "))?;
+		}
+		Ok(())
+	}
+}
+
+/// Selects events by pallet and name, ignoring case.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventFilter {
+	/// The pallet which emitted the event, or any pallet if not specified.
+	pub pallet: Option<String>,
+	/// The name of the event, or any event if not specified.
+	pub event: Option<String>,
+}
+
+impl EventFilter {
+	/// Whether an event is selected by the filter.
+	///
+	/// # Arguments
+	/// * `pallet` - The name of the pallet which emitted the event.
+	/// * `event` - The name of the event.
+	pub fn matches(&self, pallet: &str, event: &str) -> bool {
+		let matches = |filter: &Option<String>, name: &str| match filter {
+			Some(filter) => filter.eq_ignore_ascii_case(name),
+			None => true,
+		};
+		matches(&self.pallet, pallet) && matches(&self.event, event)
+	}
+}
+
+/// An event emitted within a block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockEvent {
+	/// The number of the block.
+	pub number: u32,
+	/// The hash of the block.
+	pub hash: H256,
+	/// The index of the extrinsic which emitted the event, if any.
+	pub extrinsic: Option<u32>,
+	/// The event.
+	pub event: DecodedEvent,
+}
+
+/// Decodes the events emitted within a block which are selected by a filter.
+///
+/// # Arguments
+/// * `block` - The block.
+/// * `filter` - Selects the events to be decoded.
+pub async fn block_events(
+	block: &Block<SubstrateConfig, OnlineClient<SubstrateConfig>>,
+	filter: &EventFilter,
+) -> Result<Vec<BlockEvent>, Error> {
+	let mut events = Vec::new();
+	for event in block.events().await?.iter() {
+		let event = event.map_err(subxt::Error::from)?;
+		if !filter.matches(event.pallet_name(), event.variant_name()) {
+			continue;
+		}
+		let extrinsic = match event.phase() {
+			Phase::ApplyExtrinsic(index) => Some(index),
+			_ => None,
+		};
+		events.push(BlockEvent {
+			number: block.number(),
+			hash: block.hash(),
+			extrinsic,
+			event: DecodedEvent::decode(&event)?,
+		});
+	}
+	Ok(events)
+}
+
+/// A decoded extrinsic, along with the events it emitted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedExtrinsic {
+	/// The index of the extrinsic within its block.
+	pub index: u32,
+	/// The hash of the extrinsic.
+	pub hash: H256,
+	/// The name of the pallet of the call.
+	pub pallet: String,
+	/// The name of the call.
+	pub call: String,
+	/// The arguments of the call, by name or position.
+	pub args: Vec<(String, String)>,
+	/// The account which signed the extrinsic, if signed.
+	pub signer: Option<String>,
+	/// The fee paid for the extrinsic, if any.
+	pub fee: Option<u128>,
+	/// The events emitted by the extrinsic.
+	pub events: Vec<DecodedEvent>,
+}
+
+/// A block, along with its decoded extrinsics and events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockDetails {
+	/// The number of the block.
+	pub number: u32,
+	/// The hash of the block.
+	pub hash: H256,
+	/// The hash of the parent of the block.
+	pub parent_hash: H256,
+	/// The root of the state after the block.
+	pub state_root: H256,
+	/// The root of the extrinsics of the block.
+	pub extrinsics_root: H256,
+	/// The extrinsics of the block.
+	pub extrinsics: Vec<DecodedExtrinsic>,
+	/// The events emitted during the initialization and finalization of the block.
+	pub events: Vec<DecodedEvent>,
+}
+
+/// The hash of the block with the specified number, if any.
+///
+/// # Arguments
+/// * `connection` - The connection to a node of the chain.
+/// * `number` - The number of the block.
+pub async fn block_hash(connection: &Connection, number: u32) -> Result<Option<H256>, Error> {
+	Ok(connection.methods().chain_get_block_hash(Some(number.into())).await?)
+}
+
+/// Inspects a block, decoding its extrinsics and events via the metadata of the chain.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `hash` - The hash of the block.
+pub async fn inspect_block(
+	client: &OnlineClient<SubstrateConfig>,
+	hash: H256,
+) -> Result<BlockDetails, Error> {
+	let block = client.blocks().at(hash).await?;
+	let header = block.header();
+	let mut extrinsics = Vec::new();
+	for extrinsic in block.extrinsics().await?.iter() {
+		extrinsics.push(decode_extrinsic(&extrinsic).await?);
+	}
+	let mut events = Vec::new();
+	for event in block.events().await?.iter() {
+		let event = event.map_err(subxt::Error::from)?;
+		if !matches!(event.phase(), Phase::ApplyExtrinsic(_)) {
+			events.push(DecodedEvent::decode(&event)?);
+		}
+	}
+	Ok(BlockDetails {
+		number: header.number,
+		hash: block.hash(),
+		parent_hash: header.parent_hash,
+		state_root: header.state_root,
+		extrinsics_root: header.extrinsics_root,
+		extrinsics,
+		events,
+	})
+}
+
+/// The activity of an account within a block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountActivity {
+	/// The number of the block.
+	pub number: u32,
+	/// The hash of the block.
+	pub hash: H256,
+	/// The extrinsics signed by the account.
+	pub extrinsics: Vec<DecodedExtrinsic>,
+	/// The events referring to the account.
+	pub events: Vec<BlockEvent>,
+}
+
+/// Scans a range of blocks for the extrinsics signed by an account and the events referring to
+/// it, stopping at the last block of the chain.
+///
+/// # Arguments
+/// * `connection` - The connection to a node of the chain.
+/// * `account` - The encoded account.
+/// * `blocks` - The numbers of the blocks to be scanned.
+pub async fn account_history(
+	connection: &Connection,
+	account: &[u8],
+	blocks: RangeInclusive<u32>,
+) -> Result<Vec<AccountActivity>, Error> {
+	let (client, methods) = (connection.client(), connection.methods());
+	let mut history = Vec::new();
+	for number in blocks {
+		let Some(hash) = methods.chain_get_block_hash(Some(number.into())).await? else { break };
+		let block = client.blocks().at(hash).await?;
+		let mut extrinsics = Vec::new();
+		for extrinsic in block.extrinsics().await?.iter() {
+			if extrinsic.address_bytes().is_some_and(|a| signed_by(a, account)) {
+				extrinsics.push(decode_extrinsic(&extrinsic).await?);
+			}
+		}
+		let mut events = Vec::new();
+		for event in block.events().await?.iter() {
+			let event = event.map_err(subxt::Error::from)?;
+			if refers_to(event.field_bytes(), account) {
+				let extrinsic = match event.phase() {
+					Phase::ApplyExtrinsic(index) => Some(index),
+					_ => None,
+				};
+				events.push(BlockEvent {
+					number,
+					hash,
+					extrinsic,
+					event: DecodedEvent::decode(&event)?,
+				});
+			}
+		}
+		if !extrinsics.is_empty() || !events.is_empty() {
+			history.push(AccountActivity { number, hash, extrinsics, events });
+		}
+	}
+	Ok(history)
+}
+
+// Decodes an extrinsic, along with the events it emitted.
+async fn decode_extrinsic(
+	extrinsic: &ExtrinsicDetails<SubstrateConfig, OnlineClient<SubstrateConfig>>,
+) -> Result<DecodedExtrinsic, Error> {
+	let events = extrinsic.events().await?;
+	Ok(DecodedExtrinsic {
+		index: extrinsic.index(),
+		hash: extrinsic.hash(),
+		pallet: extrinsic.pallet_name()?.to_string(),
+		call: extrinsic.variant_name()?.to_string(),
+		args: fields(&extrinsic.field_values()?),
+		signer: extrinsic.address_bytes().and_then(signer),
+		fee: fee(&events)?,
+		events: events.iter().map(|e| DecodedEvent::decode(&e?)).collect::<Result<_, _>>()?,
+	})
+}
+
+// Whether an encoded address, possibly a multi-address, is that of the account.
+fn signed_by(address: &[u8], account: &[u8]) -> bool {
+	address == account || address.strip_prefix(&[0]) == Some(account)
+}
+
+// Whether encoded fields contain the encoded account.
+fn refers_to(fields: &[u8], account: &[u8]) -> bool {
+	!account.is_empty() && fields.windows(account.len()).any(|w| w == account)
+}
+
+// The fee paid for an extrinsic, as reported by the transaction payment pallet.
+fn fee(events: &ExtrinsicEvents<SubstrateConfig>) -> Result<Option<u128>, Error> {
+	for event in events.iter() {
+		let event = event?;
+		if event.pallet_name() == "TransactionPayment" &&
+			event.variant_name() == "TransactionFeePaid"
+		{
+			let fields = event.field_values().map_err(subxt::Error::from)?;
+			return Ok(field(&fields, "actual_fee").and_then(|f| f.as_u128()));
+		}
+	}
+	Ok(None)
+}
+
+// Formats the signer of an extrinsic from its encoded address.
+fn signer(address: &[u8]) -> Option<String> {
+	match address {
+		// An account identifier, possibly as the `Id` variant of a multi-address.
+		[0, id @ ..] | id if id.len() == 32 => Some(AccountId32(id.try_into().ok()?).to_string()),
+		// An Ethereum-style account.
+		id if id.len() == 20 => Some(format!("0x{}", hex(id))),
+		_ => Some(format!("0x{}", hex(address))),
+	}
+}
+
+// Formats the fields of a composite by name, or position when unnamed.
+pub(crate) fn fields<T>(composite: &Composite<T>) -> Vec<(String, String)> {
+	match composite {
+		Composite::Named(fields) => fields.iter().map(|(n, v)| (n.clone(), display(v))).collect(),
+		Composite::Unnamed(values) =>
+			values.iter().enumerate().map(|(i, v)| (i.to_string(), display(v))).collect(),
+	}
+}
+
+// Formats a value, with any bytes as hex.
+pub(crate) fn display<T>(value: &Value<T>) -> String {
+	let mut output = String::new();
+	let result = to_writer_custom()
+		.add_custom_formatter(|v, w| format_hex(v, w))
+		.write(value, &mut output);
+	match result {
+		Ok(()) => output,
+		Err(_) => value.to_string(),
+	}
+}
+
+fn field<'a, T>(composite: &'a Composite<T>, name: &str) -> Option<&'a Value<T>> {
+	match composite {
+		Composite::Named(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+		Composite::Unnamed(_) => None,
+	}
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn block_id_from_str_works() {
+		assert_eq!("42".parse::<BlockId>().unwrap(), BlockId::Number(42));
+		let hash = format!("0x{}", "ab".repeat(32));
+		assert_eq!(hash.parse::<BlockId>().unwrap(), BlockId::Hash(H256::repeat_byte(0xab)));
+		assert!("latest".parse::<BlockId>().is_err());
+		assert!("0x1234".parse::<BlockId>().is_err());
+	}
+
+	#[test]
+	fn display_block_id_works() {
+		assert_eq!(BlockId::Number(42).to_string(), "#42");
+		assert_eq!(
+			BlockId::Hash(H256::repeat_byte(0xab)).to_string(),
+			format!("0x{}", "ab".repeat(32))
+		);
+	}
+
+	#[test]
+	fn event_filter_works() {
+		let filter = |pallet: Option<&str>, event: Option<&str>| EventFilter {
+			pallet: pallet.map(String::from),
+			event: event.map(String::from),
+		};
+		assert!(filter(None, None).matches("Balances", "Transfer"));
+		assert!(filter(Some("balances"), None).matches("Balances", "Transfer"));
+		assert!(filter(Some("Balances"), Some("TRANSFER")).matches("Balances", "Transfer"));
+		assert!(!filter(Some("Balances"), Some("Deposit")).matches("Balances", "Transfer"));
+		assert!(!filter(Some("System"), None).matches("Balances", "Transfer"));
+		assert!(filter(None, Some("Transfer")).matches("Assets", "Transfer"));
+	}
+
+	#[test]
+	fn display_event_works() {
+		let event = |fields: Vec<(String, String)>| DecodedEvent {
+			pallet: "Balances".into(),
+			name: "Transfer".into(),
+			fields,
+		};
+		assert_eq!(event(vec![]).to_string(), "Balances.Transfer");
+		assert_eq!(
+			event(vec![("from".into(), "0x01".into()), ("amount".into(), "100".into())])
+				.to_string(),
+			"Balances.Transfer { from: 0x01, amount: 100 }"
+		);
+	}
+
+	#[test]
+	fn signer_works() {
+		let alice = AccountId32([1; 32]);
+		let mut address = vec![0];
+		address.extend([1; 32]);
+		assert_eq!(signer(&address), Some(alice.to_string()));
+		assert_eq!(signer(&[1; 32]), Some(alice.to_string()));
+		assert_eq!(signer(&[0xab; 20]), Some(format!("0x{}", "ab".repeat(20))));
+	}
+
+	#[test]
+	fn signed_by_works() {
+		let mut address = vec![0];
+		address.extend([1; 32]);
+		assert!(signed_by(&address, &[1; 32]));
+		assert!(signed_by(&[1; 32], &[1; 32]));
+		assert!(signed_by(&[0xab; 20], &[0xab; 20]));
+		assert!(!signed_by(&address, &[2; 32]));
+	}
+
+	#[test]
+	fn refers_to_works() {
+		let mut fields = vec![0, 3, 5];
+		fields.extend([1; 32]);
+		fields.extend([100, 0]);
+		assert!(refers_to(&fields, &[1; 32]));
+		assert!(!refers_to(&fields, &[2; 32]));
+		assert!(!refers_to(&[1; 8], &[1; 32]));
+		assert!(!refers_to(&fields, &[]));
+	}
+
+	#[test]
+	fn fields_works() {
+		let named = Composite::Named(vec![
+			("dest".into(), Value::unnamed_variant("Id", [Value::from_bytes([1, 2])])),
+			("value".into(), Value::u128(100)),
+		]);
+		assert_eq!(
+			fields(&named),
+			[("dest".into(), "Id (0x0102)".into()), ("value".into(), "100".into())]
+		);
+		let unnamed = Composite::Unnamed(vec![Value::bool(true)]);
+		assert_eq!(fields(&unnamed), [("0".to_string(), "true".to_string())]);
+	}
+}
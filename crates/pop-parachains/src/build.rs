@@ -5,12 +5,17 @@ use anyhow::{anyhow, Result};
 use duct::cmd;
 use pop_common::{manifest::from_path, Profile};
 use serde_json::{json, Value};
+use sp_core::bytes::from_hex;
 use std::{
 	fs,
 	path::{Path, PathBuf},
 	str::FromStr,
 };
 
+/// The storage key under which the code of the runtime is stored, within a raw chain
+/// specification.
+const CODE_KEY: &str = "0x3a636f6465";
+
 /// Build the parachain and returns the path to the binary.
 ///
 /// # Arguments
@@ -52,7 +57,7 @@ pub fn is_supported(path: Option<&Path>) -> Result<bool, Error> {
 		["cumulus-client-collator", "cumulus-primitives-core", "parachains-common", "polkadot-sdk"];
 	Ok(DEPENDENCIES.into_iter().any(|d| {
 		manifest.dependencies.contains_key(d) ||
-			manifest.workspace.as_ref().map_or(false, |w| w.dependencies.contains_key(d))
+			manifest.workspace.as_ref().is_some_and(|w| w.dependencies.contains_key(d))
 	}))
 }
 
@@ -200,8 +205,44 @@ pub fn generate_genesis_state_file(
 	Ok(genesis_file)
 }
 
+/// Builds the runtime of a project with the specified features enabled, returning the path to
+/// the resulting WebAssembly blob.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+/// * `runtime` - The path to the runtime crate.
+/// * `features` - The features to be enabled, if any, e.g. `try-runtime`.
+pub(crate) fn build_runtime(
+	project: &Path,
+	runtime: &Path,
+	features: Option<&str>,
+) -> Result<PathBuf, Error> {
+	let package = from_path(Some(runtime))?
+		.package
+		.map(|p| p.name)
+		.ok_or_else(|| Config("the runtime manifest does not define a package".into()))?;
+	let mut args = vec!["build", "--release", "--package", &package];
+	if let Some(features) = features {
+		args.extend(["--features", features]);
+	}
+	cmd("cargo", args).dir(project).run()?;
+	let wasm = runtime_wasm_path(&Profile::Release.target_directory(project), &package);
+	if !wasm.exists() {
+		return Err(MissingBinary(wasm.display().to_string()));
+	}
+	Ok(wasm)
+}
+
+/// The path to the compressed WebAssembly blob of a runtime, within the target directory.
+pub(crate) fn runtime_wasm_path(target: &Path, package: &str) -> PathBuf {
+	target
+		.join("wbuild")
+		.join(package)
+		.join(format!("{}.compact.compressed.wasm", package.replace('-', "_")))
+}
+
 /// Checks if a given command exists and can be executed by running it with the "--help" argument.
-fn check_command_exists(binary_path: &Path, command: &str) -> Result<(), Error> {
+pub(crate) fn check_command_exists(binary_path: &Path, command: &str) -> Result<(), Error> {
 	cmd(binary_path, vec![command, "--help"]).stdout_null().run().map_err(|_err| {
 		Error::MissingCommand {
 			command: command.to_string(),
@@ -213,6 +254,15 @@ fn check_command_exists(binary_path: &Path, command: &str) -> Result<(), Error>
 
 /// A chain specification.
 pub struct ChainSpec(Value);
+
+impl FromStr for ChainSpec {
+	type Err = serde_json::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(ChainSpec(Value::from_str(s)?))
+	}
+}
+
 impl ChainSpec {
 	/// Parses a chain specification from a path.
 	///
@@ -242,6 +292,17 @@ impl ChainSpec {
 		self.0.get("relay_chain").and_then(|v| v.as_str())
 	}
 
+	/// Get the code of the runtime from the chain specification, whether plain or raw.
+	pub fn get_code(&self) -> Option<Vec<u8>> {
+		let genesis = self.0.get("genesis")?;
+		let code = genesis
+			.get("runtimeGenesis")
+			.and_then(|g| g.get("code"))
+			.or_else(|| genesis.get("runtime").and_then(|r| r.get("system")?.get("code")))
+			.or_else(|| genesis.get("raw").and_then(|r| r.get("top")?.get(CODE_KEY)))?;
+		from_hex(code.as_str()?).ok()
+	}
+
 	/// Replaces the parachain id with the provided `para_id`.
 	///
 	/// # Arguments
@@ -348,7 +409,7 @@ mod tests {
 			decimals: 18,
 			initial_endowment: "1000000".to_string(),
 		};
-		instantiate_standard_template(&Parachain::Standard, temp_dir.path(), config, None)?;
+		instantiate_standard_template(&Parachain::Standard, temp_dir.path(), config, None, &())?;
 		Ok(temp_dir)
 	}
 
@@ -602,6 +663,21 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn get_code_works() -> Result<()> {
+		let chain_spec = ChainSpec(json!({
+			"genesis": { "runtimeGenesis": { "code": "0x0102" } },
+		}));
+		assert_eq!(chain_spec.get_code(), Some(vec![1, 2]));
+		let chain_spec = ChainSpec(json!({
+			"genesis": { "raw": { "top": { CODE_KEY: "0x0304" } } },
+		}));
+		assert_eq!(chain_spec.get_code(), Some(vec![3, 4]));
+		let chain_spec: ChainSpec = r#"{"genesis": {"runtimeGenesis": {"patch": {}}}}"#.parse()?;
+		assert_eq!(chain_spec.get_code(), None);
+		Ok(())
+	}
+
 	#[test]
 	fn replace_para_id_works() -> Result<()> {
 		let mut chain_spec = ChainSpec(json!({
@@ -811,4 +887,12 @@ mod tests {
 		assert!(is_supported(Some(&path.join(name)))?);
 		Ok(())
 	}
+
+	#[test]
+	fn runtime_wasm_path_works() {
+		assert_eq!(
+			runtime_wasm_path(Path::new("target/release"), "my-runtime"),
+			PathBuf::from("target/release/wbuild/my-runtime/my_runtime.compact.compressed.wasm")
+		);
+	}
 }
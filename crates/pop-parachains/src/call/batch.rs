@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{construct_extrinsic, metadata::resolve_function};
+use crate::{errors::Error, Function};
+use serde::Deserialize;
+use std::{fs, path::Path};
+use subxt::{
+	dynamic::{self, Value},
+	tx::DynamicPayload,
+	OnlineClient, SubstrateConfig,
+};
+
+/// The calls declared within a manifest, e.g. within a `calls.toml` file:
+///
+/// ```toml
+/// [[call]]
+/// pallet = "System"
+/// function = "remark"
+/// args = ["0x11"]
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct CallManifest {
+	/// The calls, in the order in which they are dispatched.
+	#[serde(default, rename = "call")]
+	pub calls: Vec<ManifestCall>,
+}
+
+impl CallManifest {
+	/// Loads the calls declared within a manifest.
+	///
+	/// # Arguments
+	/// * `path` - The path to the manifest.
+	pub fn load(path: &Path) -> Result<Self, Error> {
+		let manifest: Self = toml_edit::de::from_str(&fs::read_to_string(path)?)?;
+		if manifest.calls.is_empty() {
+			return Err(Error::Config(format!("no calls are declared in {}", path.display())));
+		}
+		Ok(manifest)
+	}
+
+	/// Validates the calls against the metadata of a chain, returning each dispatchable function
+	/// along with its call.
+	///
+	/// # Arguments
+	/// * `client` - The client used to interact with the chain.
+	pub fn resolve(
+		&self,
+		client: &OnlineClient<SubstrateConfig>,
+	) -> Result<Vec<(Function, DynamicPayload)>, Error> {
+		self.calls
+			.iter()
+			.enumerate()
+			.map(|(index, call)| {
+				call.resolve(client).map_err(|e| {
+					Error::Config(format!(
+						"call {} ({}.{}) is invalid: {e}",
+						index + 1,
+						call.pallet,
+						call.function
+					))
+				})
+			})
+			.collect()
+	}
+}
+
+/// A call declared within a manifest.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct ManifestCall {
+	/// The pallet containing the dispatchable function.
+	pub pallet: String,
+	/// The name of the dispatchable function.
+	pub function: String,
+	/// The arguments of the function, encoded as strings.
+	#[serde(default)]
+	pub args: Vec<String>,
+}
+
+impl ManifestCall {
+	// Resolves the dispatchable function and constructs the call with its arguments.
+	fn resolve(
+		&self,
+		client: &OnlineClient<SubstrateConfig>,
+	) -> Result<(Function, DynamicPayload), Error> {
+		let function = Function {
+			pallet: self.pallet.clone(),
+			name: self.function.clone(),
+			..Default::default()
+		};
+		let function = resolve_function(client, &function)?;
+		if !function.is_supported {
+			return Err(Error::FunctionNotSupported);
+		}
+		if self.args.len() != function.params.len() {
+			return Err(Error::ParamProcessingError);
+		}
+		let xt = construct_extrinsic(&function, self.args.clone())?;
+		Ok((function, xt))
+	}
+}
+
+/// Constructs a call dispatching several calls atomically via `Utility.batch_all`, such that
+/// none are dispatched should any of them fail.
+///
+/// # Arguments
+/// * `xts` - The calls, in the order in which they are dispatched.
+pub fn batch_all(xts: Vec<DynamicPayload>) -> DynamicPayload {
+	let calls = Value::unnamed_composite(xts.into_iter().map(|xt| xt.into_value()));
+	dynamic::tx("Utility", "batch_all", vec![calls])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use scale_value::Composite;
+
+	#[test]
+	fn load_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("calls.toml");
+		fs::write(
+			&path,
+			r#"
+				[[call]]
+				pallet = "System"
+				function = "remark"
+				args = ["0x11"]
+
+				[[call]]
+				pallet = "Balances"
+				function = "transfer_keep_alive"
+				args = ["Id(5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty)", "100"]
+
+				[[call]]
+				pallet = "Utility"
+				function = "dispatch_noop"
+			"#,
+		)?;
+		let manifest = CallManifest::load(&path)?;
+		assert_eq!(manifest.calls.len(), 3);
+		assert_eq!(
+			manifest.calls[0],
+			ManifestCall {
+				pallet: "System".into(),
+				function: "remark".into(),
+				args: vec!["0x11".into()]
+			}
+		);
+		assert_eq!(manifest.calls[1].args.len(), 2);
+		assert!(manifest.calls[2].args.is_empty());
+
+		fs::write(&path, "")?;
+		assert!(matches!(CallManifest::load(&path), Err(Error::Config(_))));
+		fs::write(&path, "[[call]]\npallet = \"System\"")?;
+		assert!(CallManifest::load(&path).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn batch_all_works() {
+		let remark = || dynamic::tx("System", "remark", vec![Value::from_bytes([0x11])]);
+		let xt = batch_all(vec![remark(), remark()]);
+		assert_eq!((xt.pallet_name(), xt.call_name()), ("Utility", "batch_all"));
+		assert_eq!(
+			xt.call_data(),
+			&Composite::unnamed([Value::unnamed_composite([
+				remark().into_value(),
+				remark().into_value()
+			])])
+		);
+	}
+}
@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{call::ExtrinsicOptions, errors::Error};
+use libsecp256k1::{Message, PublicKey, SecretKey};
+use sp_core::{ecdsa, keccak_256, Pair};
+use subxt::{
+	config::{
+		substrate::{BlakeTwo256, SubstrateHeader},
+		DefaultExtrinsicParamsBuilder, SubstrateExtrinsicParams,
+	},
+	dynamic::{self, Value},
+	ext::{
+		codec::{Decode, Encode, Input, Output},
+		scale_value::At,
+		subxt_core::utils::AccountId20,
+	},
+	tx::{Payload, Signer},
+	utils::H256,
+	Metadata, OfflineClient, OnlineClient, SubstrateConfig,
+};
+
+/// The private key of Alith, the development account endowed by Frontier-based templates.
+pub const ALITH_PRIVATE_KEY: &str =
+	"0x5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133";
+
+/// The format of the accounts of a chain, which determines how they are specified and how
+/// extrinsics are signed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AccountFormat {
+	/// 32-byte accounts specified as SS58 addresses, signing via sr25519 (e.g. Polkadot).
+	#[default]
+	Substrate,
+	/// 20-byte accounts specified as H160 addresses, signing via ECDSA (e.g. Frontier-based
+	/// chains).
+	Ethereum,
+}
+
+impl AccountFormat {
+	/// Detects the format of the accounts of a chain from the type of the address of its
+	/// extrinsics.
+	///
+	/// # Arguments
+	/// * `metadata` - The metadata of the chain.
+	pub fn detect(metadata: &Metadata) -> Self {
+		let address = metadata.types().resolve(metadata.extrinsic().address_ty());
+		match address.and_then(|ty| ty.path.ident()).as_deref() {
+			Some("AccountId20") => AccountFormat::Ethereum,
+			_ => AccountFormat::Substrate,
+		}
+	}
+}
+
+/// The configuration of a chain with Ethereum-style accounts, which only differs from
+/// [`SubstrateConfig`] by its accounts and signatures.
+pub enum EthereumConfig {}
+
+impl subxt::Config for EthereumConfig {
+	type Hash = H256;
+	type AccountId = AccountId20;
+	type Address = AccountId20;
+	type Signature = EthereumSignature;
+	type Hasher = BlakeTwo256;
+	type Header = SubstrateHeader<u32, BlakeTwo256>;
+	type ExtrinsicParams = SubstrateExtrinsicParams<Self>;
+	type AssetId = u32;
+}
+
+/// A recoverable ECDSA signature of the Keccak-256 hash of a payload, as expected by chains with
+/// Ethereum-style accounts.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EthereumSignature(pub [u8; 65]);
+
+impl Encode for EthereumSignature {
+	fn size_hint(&self) -> usize {
+		self.0.len()
+	}
+
+	fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+		dest.write(&self.0)
+	}
+}
+
+impl Decode for EthereumSignature {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, subxt::ext::codec::Error> {
+		Ok(Self(<[u8; 65]>::decode(input)?))
+	}
+}
+
+/// Signs extrinsics on behalf of an Ethereum-style account.
+pub struct EthereumSigner {
+	secret: SecretKey,
+	account: AccountId20,
+}
+
+impl EthereumSigner {
+	/// Creates a signer from a secret URI, such as the hex-encoded private key of the account.
+	///
+	/// # Arguments
+	/// * `suri` - The secret URI of the account.
+	pub fn from_uri(suri: &str) -> Result<Self, Error> {
+		let key_pair_error = pop_common::Error::KeyPairCreation;
+		let pair =
+			ecdsa::Pair::from_string(suri, None).map_err(|e| key_pair_error(format!("{e:?}")))?;
+		let secret =
+			SecretKey::parse(&pair.seed()).map_err(|e| key_pair_error(format!("{e:?}")))?;
+		// The account is the last 20 bytes of the hash of the uncompressed public key, without
+		// its prefix.
+		let public = PublicKey::from_secret_key(&secret).serialize();
+		let mut account = [0u8; 20];
+		account.copy_from_slice(&keccak_256(&public[1..])[12..]);
+		Ok(Self { secret, account: AccountId20(account) })
+	}
+
+	/// The account of the signer.
+	pub fn account(&self) -> AccountId20 {
+		self.account
+	}
+}
+
+impl Signer<EthereumConfig> for EthereumSigner {
+	fn account_id(&self) -> AccountId20 {
+		self.account
+	}
+
+	fn address(&self) -> AccountId20 {
+		self.account
+	}
+
+	fn sign(&self, signer_payload: &[u8]) -> EthereumSignature {
+		let message = Message::parse(&keccak_256(signer_payload));
+		let (signature, recovery_id) = libsecp256k1::sign(&message, &self.secret);
+		let mut bytes = [0u8; 65];
+		bytes[..64].copy_from_slice(&signature.serialize());
+		bytes[64] = recovery_id.serialize();
+		EthereumSignature(bytes)
+	}
+}
+
+/// Signs an extrinsic on behalf of an Ethereum-style account, returning the encoded extrinsic so
+/// that it can be submitted via the client of the chain.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `xt` - The extrinsic to be signed.
+/// * `suri` - The secret URI of the account, such as its hex-encoded private key.
+/// * `options` - The nonce, tip and mortality of the extrinsic.
+pub async fn sign_ethereum_extrinsic<Xt: Payload>(
+	client: &OnlineClient<SubstrateConfig>,
+	xt: &Xt,
+	suri: &str,
+	options: &ExtrinsicOptions,
+) -> Result<Vec<u8>, Error> {
+	let signer = EthereumSigner::from_uri(suri)?;
+	let nonce = match options.nonce {
+		Some(nonce) => nonce,
+		None => account_nonce(client, &signer.account).await?,
+	};
+	let mut params = DefaultExtrinsicParamsBuilder::<EthereumConfig>::new()
+		.tip(options.tip)
+		.nonce(nonce);
+	let period = options.era.unwrap_or(super::DEFAULT_ERA);
+	if period > 0 {
+		params = params.mortal(client.blocks().at_latest().await?.header(), period);
+	}
+	// The extrinsic is signed offline, as the chain is only connected to via the client.
+	let offline = OfflineClient::<EthereumConfig>::new(
+		client.genesis_hash(),
+		client.runtime_version(),
+		client.metadata(),
+	);
+	let partial = offline
+		.tx()
+		.create_partial_signed_offline(xt, params.build())
+		.map_err(|e| Error::ExtrinsicSubmissionError(e.to_string()))?;
+	Ok(partial.sign(&signer).encoded().to_vec())
+}
+
+// The next nonce of an account, which is zero if the account does not exist.
+async fn account_nonce(
+	client: &OnlineClient<SubstrateConfig>,
+	account: &AccountId20,
+) -> Result<u64, Error> {
+	let query = dynamic::storage("System", "Account", vec![Value::from_bytes(account.0)]);
+	let Some(info) = client.storage().at_latest().await?.fetch(&query).await? else {
+		return Ok(0);
+	};
+	let info = info.to_value().map_err(subxt::Error::from)?;
+	Ok(info.at("nonce").and_then(|n| n.as_u128()).unwrap_or_default() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{call::tests::POP_NETWORK_TESTNET_URL, set_up_client};
+	use anyhow::Result;
+	use libsecp256k1::{recover, RecoveryId, Signature};
+	use sp_core::bytes::to_hex;
+
+	const ALITH: &str = "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac";
+
+	#[test]
+	fn ethereum_signer_works() -> Result<()> {
+		let signer = EthereumSigner::from_uri(ALITH_PRIVATE_KEY)?;
+		assert_eq!(to_hex(&signer.account().0, false), ALITH.to_lowercase());
+		// The signature recovers to the public key of the account.
+		let signature = signer.sign(b"payload");
+		let message = Message::parse(&keccak_256(b"payload"));
+		let recovered = recover(
+			&message,
+			&Signature::parse_standard_slice(&signature.0[..64])?,
+			&RecoveryId::parse(signature.0[64])?,
+		)?;
+		assert_eq!(recovered, PublicKey::from_secret_key(&signer.secret));
+		assert_eq!(EthereumSignature::decode(&mut &signature.encode()[..])?, signature);
+		assert!(matches!(
+			EthereumSigner::from_uri("wrong"),
+			Err(Error::CommonError(pop_common::Error::KeyPairCreation(..)))
+		));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn detect_account_format_works() -> Result<()> {
+		let client = set_up_client(POP_NETWORK_TESTNET_URL).await?;
+		assert_eq!(AccountFormat::detect(&client.metadata()), AccountFormat::Substrate);
+		Ok(())
+	}
+}
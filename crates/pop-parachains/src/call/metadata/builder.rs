@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{list_pallets, parse_dispatchable_arguments, resolve_function_with, Function, Pallet};
+use crate::{errors::Error, NativeToken};
+use pop_common::address_book::AddressBook;
+use subxt::{dynamic::Value, Metadata, OnlineClient, SubstrateConfig};
+
+/// Builds the calls of a runtime, such that they can be composed as the arguments of other
+/// calls, e.g. those of `Utility.batch`, `Sudo.sudo` or `Proxy.proxy`.
+#[derive(Clone)]
+pub struct CallBuilder {
+	metadata: Metadata,
+	pallets: Vec<Pallet>,
+	token: NativeToken,
+	address_book: AddressBook,
+}
+
+impl CallBuilder {
+	/// Creates a builder for the calls of a chain.
+	///
+	/// # Arguments
+	/// * `client`: The client to interact with the chain.
+	pub fn new(client: &OnlineClient<SubstrateConfig>) -> Self {
+		let pallets =
+			list_pallets(client).into_iter().filter(|p| !p.functions.is_empty()).collect();
+		Self {
+			metadata: client.metadata(),
+			pallets,
+			token: NativeToken::default(),
+			address_book: AddressBook::default(),
+		}
+	}
+
+	/// Specifies the native token of the chain, such that balances may be entered as amounts of
+	/// the token.
+	///
+	/// # Arguments
+	/// * `token`: The native token of the chain.
+	pub fn with_token(mut self, token: NativeToken) -> Self {
+		self.token = token;
+		self
+	}
+
+	/// The native token of the chain, if specified.
+	pub fn token(&self) -> &NativeToken {
+		&self.token
+	}
+
+	/// Specifies the address book, whose accounts are offered whenever an account is to be
+	/// entered.
+	///
+	/// # Arguments
+	/// * `address_book`: The address book of the user.
+	pub fn with_address_book(mut self, address_book: AddressBook) -> Self {
+		self.address_book = address_book;
+		self
+	}
+
+	/// The address book, which is empty unless specified.
+	pub fn address_book(&self) -> &AddressBook {
+		&self.address_book
+	}
+
+	/// The pallets with dispatchable functions, whose parameters are resolved via
+	/// [`CallBuilder::resolve`].
+	pub fn pallets(&self) -> &[Pallet] {
+		&self.pallets
+	}
+
+	/// Resolves a dispatchable function, including its parameters and whether it is supported.
+	///
+	/// # Arguments
+	/// * `function`: The dispatchable function, such as one of [`CallBuilder::pallets`].
+	pub fn resolve(&self, function: &Function) -> Result<Function, Error> {
+		resolve_function_with(&self.metadata, function)
+	}
+
+	/// Builds a call of a dispatchable function.
+	///
+	/// # Arguments
+	/// * `function`: The resolved dispatchable function.
+	/// * `args`: The arguments of the function, which may themselves be calls.
+	pub fn build(&self, function: &Function, args: Vec<String>) -> Result<Value, Error> {
+		call_value(function, args)
+	}
+}
+
+/// Encodes a call of a dispatchable function as a value, whose textual form can be provided as
+/// the argument of a parameter of type `RuntimeCall`.
+///
+/// # Arguments
+/// * `function`: The resolved dispatchable function.
+/// * `args`: The arguments of the function, which may themselves be calls.
+pub fn call_value(function: &Function, args: Vec<String>) -> Result<Value, Error> {
+	if args.len() != function.params.len() {
+		return Err(Error::ParamProcessingError);
+	}
+	let values = parse_dispatchable_arguments(&function.params, args)?;
+	let fields = function.params.iter().map(|p| p.name.clone()).zip(values);
+	Ok(Value::unnamed_variant(&function.pallet, [Value::named_variant(&function.name, fields)]))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Param;
+	use anyhow::Result;
+
+	fn remark() -> Function {
+		Function {
+			pallet: "System".into(),
+			name: "remark".into(),
+			params: vec![Param {
+				name: "remark".into(),
+				type_name: "[u8]".into(),
+				is_sequence: true,
+				..Default::default()
+			}],
+			is_supported: true,
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn call_value_works() -> Result<()> {
+		let bytes = |b: &[u8]| Value::unnamed_composite(b.iter().map(|b| Value::u128(*b as u128)));
+		let call = call_value(&remark(), vec!["hi".into()])?;
+		assert_eq!(
+			call,
+			Value::unnamed_variant(
+				"System",
+				[Value::named_variant("remark", [("remark", bytes(b"hi"))])]
+			)
+		);
+		assert_eq!(call.to_string(), "System (remark { remark: (104, 105) })");
+		assert!(matches!(call_value(&remark(), vec![]), Err(Error::ParamProcessingError)));
+
+		// Calls are nested via their textual form, e.g. within a batch.
+		let batch = Function {
+			pallet: "Utility".into(),
+			name: "batch".into(),
+			params: vec![Param {
+				name: "calls".into(),
+				type_name: "Vec<RuntimeCall>".into(),
+				is_sequence: true,
+				is_call: true,
+				..Default::default()
+			}],
+			is_supported: true,
+			..Default::default()
+		};
+		let calls = format!("({call}, {call})");
+		assert_eq!(
+			call_value(&batch, vec![calls])?,
+			Value::unnamed_variant(
+				"Utility",
+				[Value::named_variant(
+					"batch",
+					[("calls", Value::unnamed_composite([call.clone(), call]))]
+				)]
+			)
+		);
+		Ok(())
+	}
+}
@@ -2,15 +2,29 @@
 
 use crate::errors::Error;
 use params::Param;
+use pop_common::format_type;
+use scale_info::{form::PortableForm, Field, PortableRegistry, Variant};
 use scale_value::stringify::custom_parsers;
-use std::fmt::{Display, Formatter};
-use subxt::{dynamic::Value, utils::to_hex, Metadata, OnlineClient, SubstrateConfig};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::{
+	fmt::{Display, Formatter},
+	thread,
+};
+use subxt::{
+	dynamic::Value, metadata::types::PalletMetadata, utils::to_hex, Metadata, OnlineClient,
+	SubstrateConfig,
+};
+use v16::ViewFunction;
 
 pub mod action;
+pub mod builder;
 pub mod params;
+pub mod storage;
+pub mod v16;
 
 /// Represents a pallet in the blockchain, including its dispatchable functions.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct Pallet {
 	/// The name of the pallet.
 	pub name: String,
@@ -20,6 +34,16 @@ pub struct Pallet {
 	pub docs: String,
 	/// The dispatchable functions of the pallet.
 	pub functions: Vec<Function>,
+	/// The storage items of the pallet.
+	pub storage: Vec<PalletItem>,
+	/// The constants of the pallet.
+	pub constants: Vec<PalletItem>,
+	/// The events of the pallet.
+	pub events: Vec<PalletItem>,
+	/// The view functions of the pallet, as declared by metadata V16.
+	pub view_functions: Vec<ViewFunction>,
+	/// The associated types of the pallet, as declared by metadata V16.
+	pub associated_types: Vec<PalletItem>,
 }
 
 impl Display for Pallet {
@@ -29,7 +53,7 @@ impl Display for Pallet {
 }
 
 /// Represents a dispatchable function.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct Function {
 	/// The pallet containing the dispatchable function.
 	pub pallet: String,
@@ -43,6 +67,9 @@ pub struct Function {
 	pub params: Vec<Param>,
 	/// Whether this function is supported (no recursive or unsupported types like `RuntimeCall`).
 	pub is_supported: bool,
+	/// A description of the deprecation of the function, if deprecated, as declared by metadata
+	/// V16.
+	pub deprecation: Option<String>,
 }
 
 impl Display for Function {
@@ -51,80 +78,245 @@ impl Display for Function {
 	}
 }
 
+/// Represents a storage item, constant or event of a pallet.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct PalletItem {
+	/// The name of the item.
+	pub name: String,
+	/// The documentation of the item.
+	pub docs: String,
+	/// The type of the item, or the fields of an event.
+	pub type_name: String,
+}
+
+impl Display for PalletItem {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name)
+	}
+}
+
 /// Parses the chain metadata to extract information about pallets and their dispatchable functions.
 ///
+/// The pallets are parsed in parallel. Use [`list_pallets`] where only the names of functions are
+/// required upfront.
+///
 /// # Arguments
 /// * `client`: The client to interact with the chain.
 ///
 /// NOTE: pallets are ordered by their index within the runtime by default.
 pub fn parse_chain_metadata(client: &OnlineClient<SubstrateConfig>) -> Result<Vec<Pallet>, Error> {
 	let metadata: Metadata = client.metadata();
+	let pallets: Vec<_> = metadata.pallets().collect();
+	let workers = thread::available_parallelism().map_or(1, |n| n.get());
+	let chunk_size = pallets.len().div_ceil(workers).max(1);
+	let metadata = &metadata;
+	let chunks = thread::scope(|s| {
+		let handles: Vec<_> = pallets
+			.chunks(chunk_size)
+			.map(|chunk| {
+				s.spawn(move || chunk.iter().map(|p| parse_pallet(metadata, p)).collect::<Vec<_>>())
+			})
+			.collect();
+		handles
+			.into_iter()
+			.map(|h| h.join().expect("parsing the metadata of a pallet does not panic"))
+			.collect::<Vec<_>>()
+	});
+	Ok(chunks.into_iter().flatten().collect())
+}
 
-	let pallets = metadata
+/// Lists the pallets of the chain metadata along with their dispatchable functions, without
+/// parsing the parameters of the functions, which are resolved on demand via
+/// [`resolve_function`]. Storage items, constants and events are not listed.
+///
+/// # Arguments
+/// * `client`: The client to interact with the chain.
+///
+/// NOTE: pallets are ordered by their index within the runtime by default.
+pub fn list_pallets(client: &OnlineClient<SubstrateConfig>) -> Vec<Pallet> {
+	client
+		.metadata()
 		.pallets()
-		.map(|pallet| {
-			let functions = pallet
+		.map(|pallet| Pallet {
+			name: pallet.name().to_string(),
+			index: pallet.index(),
+			docs: pallet.docs().join(" "),
+			functions: pallet
 				.call_variants()
 				.map(|variants| {
 					variants
 						.iter()
-						.map(|variant| {
-							let mut is_supported = true;
-
-							// Parse parameters for the dispatchable function.
-							let params = {
-								let mut parsed_params = Vec::new();
-								for field in &variant.fields {
-									match params::field_to_param(&metadata, field) {
-										Ok(param) => parsed_params.push(param),
-										Err(_) => {
-											// If an error occurs while parsing the values, mark the
-											// dispatchable function as unsupported rather than
-											// error.
-											is_supported = false;
-											parsed_params.clear();
-											break;
-										},
-									}
-								}
-								parsed_params
-							};
-
-							Ok(Function {
-								pallet: pallet.name().to_string(),
-								name: variant.name.clone(),
-								index: variant.index,
-								docs: if is_supported {
-									// Filter out blank lines and then flatten into a single value.
-									variant
-										.docs
-										.iter()
-										.filter(|l| !l.is_empty())
-										.cloned()
-										.collect::<Vec<_>>()
-										.join(" ")
-								} else {
-									// To display the message in the UI
-									"Function Not Supported".to_string()
-								},
-								params,
-								is_supported,
-							})
+						.map(|variant| Function {
+							pallet: pallet.name().to_string(),
+							name: variant.name.clone(),
+							index: variant.index,
+							docs: docs(&variant.docs),
+							// Whether the function is supported is only known once resolved.
+							is_supported: true,
+							..Default::default()
 						})
-						.collect::<Result<Vec<Function>, Error>>()
+						.collect()
 				})
-				.unwrap_or_else(|| Ok(vec![]))?;
+				.unwrap_or_default(),
+			..Default::default()
+		})
+		.collect()
+}
 
-			Ok(Pallet {
-				name: pallet.name().to_string(),
-				index: pallet.index(),
-				docs: pallet.docs().join(" "),
-				functions,
-			})
+/// Resolves a dispatchable function, including its parameters and whether it is supported.
+///
+/// # Arguments
+/// * `client`: The client to interact with the chain.
+/// * `function`: The dispatchable function, such as one listed by [`list_pallets`].
+pub fn resolve_function(
+	client: &OnlineClient<SubstrateConfig>,
+	function: &Function,
+) -> Result<Function, Error> {
+	resolve_function_with(&client.metadata(), function)
+}
+
+// Resolves a dispatchable function using the metadata of a chain.
+fn resolve_function_with(metadata: &Metadata, function: &Function) -> Result<Function, Error> {
+	let pallet = metadata
+		.pallet_by_name(&function.pallet)
+		.ok_or_else(|| Error::PalletNotFound(function.pallet.clone()))?;
+	let variant = pallet.call_variant_by_name(&function.name).ok_or(Error::FunctionNotSupported)?;
+	Ok(Function {
+		deprecation: function.deprecation.clone(),
+		..parse_function(metadata, pallet.name(), variant)
+	})
+}
+
+// Parses a pallet, including its dispatchable functions, storage items, constants and events.
+fn parse_pallet(metadata: &Metadata, pallet: &PalletMetadata) -> Pallet {
+	let functions = pallet
+		.call_variants()
+		.map(|variants| {
+			variants
+				.iter()
+				.map(|variant| parse_function(metadata, pallet.name(), variant))
+				.collect()
+		})
+		.unwrap_or_default();
+
+	let registry = metadata.types();
+	let storage = pallet
+		.storage()
+		.map(|storage| {
+			storage
+				.entries()
+				.iter()
+				.map(|entry| {
+					let value = type_name(registry, entry.entry_type().value_ty());
+					PalletItem {
+						name: entry.name().to_string(),
+						docs: docs(entry.docs()),
+						type_name: match entry.entry_type().key_ty() {
+							Some(key) => format!("Map<{}, {value}>", type_name(registry, key)),
+							None => value,
+						},
+					}
+				})
+				.collect()
+		})
+		.unwrap_or_default();
+	let constants = pallet
+		.constants()
+		.map(|constant| PalletItem {
+			name: constant.name().to_string(),
+			docs: docs(constant.docs()),
+			type_name: type_name(registry, constant.ty()),
+		})
+		.collect();
+	let events = pallet
+		.event_variants()
+		.map(|variants| {
+			variants
+				.iter()
+				.map(|variant| PalletItem {
+					name: variant.name.clone(),
+					docs: docs(&variant.docs),
+					type_name: event_fields(registry, &variant.fields),
+				})
+				.collect()
 		})
-		.collect::<Result<Vec<Pallet>, Error>>()?;
+		.unwrap_or_default();
+
+	Pallet {
+		name: pallet.name().to_string(),
+		index: pallet.index(),
+		docs: pallet.docs().join(" "),
+		functions,
+		storage,
+		constants,
+		events,
+		..Default::default()
+	}
+}
+
+// Parses a dispatchable function, including its parameters.
+fn parse_function(metadata: &Metadata, pallet: &str, variant: &Variant<PortableForm>) -> Function {
+	let mut is_supported = true;
+
+	// Parse parameters for the dispatchable function.
+	let params = {
+		let mut parsed_params = Vec::new();
+		for field in &variant.fields {
+			match params::field_to_param(metadata, field) {
+				Ok(param) => parsed_params.push(param),
+				Err(_) => {
+					// If an error occurs while parsing the values, mark the dispatchable function
+					// as unsupported rather than error.
+					is_supported = false;
+					parsed_params.clear();
+					break;
+				},
+			}
+		}
+		parsed_params
+	};
+
+	Function {
+		pallet: pallet.to_string(),
+		name: variant.name.clone(),
+		index: variant.index,
+		docs: if is_supported {
+			docs(&variant.docs)
+		} else {
+			// To display the message in the UI
+			"Function Not Supported".to_string()
+		},
+		params,
+		is_supported,
+		..Default::default()
+	}
+}
+
+// Filters out blank lines of documentation and then flattens into a single value.
+fn docs(lines: &[String]) -> String {
+	lines.iter().filter(|l| !l.is_empty()).cloned().collect::<Vec<_>>().join(" ")
+}
 
-	Ok(pallets)
+// Formats a type of the metadata by its identifier.
+fn type_name(registry: &PortableRegistry, id: u32) -> String {
+	registry.resolve(id).map(|ty| format_type(ty, registry)).unwrap_or_default()
+}
+
+// Formats the fields of an event, preferring the type names declared by the pallet.
+fn event_fields(registry: &PortableRegistry, fields: &[Field<PortableForm>]) -> String {
+	let field = |f: &Field<PortableForm>| {
+		let ty = f.type_name.clone().unwrap_or_else(|| type_name(registry, f.ty.id));
+		match &f.name {
+			Some(name) => format!("{name}: {ty}"),
+			None => ty,
+		}
+	};
+	match fields.first() {
+		None => String::new(),
+		Some(first) if first.name.is_some() =>
+			format!("{{ {} }}", fields.iter().map(field).collect::<Vec<_>>().join(", ")),
+		Some(_) => format!("({})", fields.iter().map(field).collect::<Vec<_>>().join(", ")),
+	}
 }
 
 /// Finds a specific pallet by name and retrieves its details from metadata.
@@ -177,8 +369,13 @@ pub fn parse_dispatchable_arguments(
 		.iter()
 		.zip(raw_params)
 		.map(|(param, raw_param)| {
-			// Convert sequence parameters to hex if is_sequence
-			let processed_param = if param.is_sequence && !raw_param.starts_with("0x") {
+			// Convert sequences entered as a whole (e.g. bytes) to hex, other than sequences of
+			// calls.
+			let is_bytes = param.is_sequence &&
+				!param.is_call &&
+				!param.is_optional &&
+				param.sub_params.is_empty();
+			let processed_param = if is_bytes && !raw_param.starts_with("0x") {
 				to_hex(&raw_param)
 			} else {
 				raw_param
@@ -193,6 +390,121 @@ pub fn parse_dispatchable_arguments(
 		.collect()
 }
 
+/// Parses the arguments of a dispatchable function from a JSON object keyed by the names of its
+/// parameters, into the raw string values expected by [`parse_dispatchable_arguments`]. Objects
+/// and arrays map to the composites, variants (e.g. `{"Id": "5Grw..."}`), tuples and sequences
+/// described by the parameters, whilst strings are used as entered.
+///
+/// # Arguments
+/// * `params`: The metadata definition for each parameter of the dispatchable function.
+/// * `json`: The JSON object containing the arguments.
+pub fn parse_json_arguments(params: &[Param], json: &str) -> Result<Vec<String>, Error> {
+	let JsonValue::Object(mut args) = serde_json::from_str(json)? else {
+		return Err(Error::InvalidArguments(
+			"expected an object keyed by the names of the parameters".into(),
+		));
+	};
+	let values = params
+		.iter()
+		.map(|param| match args.remove(&param.name) {
+			Some(value) => json_to_raw(param, &value),
+			None if param.is_optional => Ok("None()".to_string()),
+			None => Err(Error::InvalidArguments(format!("missing a value for `{}`", param.name))),
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	if let Some(name) = args.keys().next() {
+		return Err(Error::InvalidArguments(format!("unknown parameter `{name}`")));
+	}
+	Ok(values)
+}
+
+// Converts a JSON value into the raw string value of a parameter.
+fn json_to_raw(param: &Param, value: &JsonValue) -> Result<String, Error> {
+	let invalid =
+		|| Error::InvalidArguments(format!("invalid value for `{}`: {value}", param.name));
+	let each = |params: &[Param], values: &[JsonValue]| -> Result<Vec<String>, Error> {
+		if params.len() != values.len() {
+			return Err(invalid());
+		}
+		params
+			.iter()
+			.zip(values)
+			.map(|(param, value)| json_to_raw(param, value))
+			.collect()
+	};
+	if param.is_optional {
+		let param = Param { is_optional: false, ..param.clone() };
+		return match value {
+			JsonValue::Null => Ok("None()".to_string()),
+			value => Ok(format!("Some({})", json_to_raw(&param, value)?)),
+		};
+	}
+	match value {
+		// Accounts are used as the `Id` variant of an address.
+		JsonValue::String(account) if param.is_address && !account.contains('(') =>
+			Ok(format!("Id({account})")),
+		JsonValue::String(name)
+			if param.is_variant &&
+				param.sub_params.iter().any(|v| v.name == *name && v.sub_params.is_empty()) =>
+			Ok(format!("{name}()")),
+		JsonValue::String(value) => Ok(value.clone()),
+		JsonValue::Number(_) | JsonValue::Bool(_) => Ok(value.to_string()),
+		JsonValue::Null => Err(invalid()),
+		JsonValue::Array(items) if param.is_sequence => match param.sub_params.first() {
+			Some(item) => {
+				let items = items
+					.iter()
+					.map(|value| json_to_raw(item, value))
+					.collect::<Result<Vec<_>, _>>()?;
+				Ok(format!("({})", items.join(", ")))
+			},
+			// Sequences entered as a whole are expected as bytes.
+			None => items
+				.iter()
+				.map(|byte| byte.as_u64().and_then(|b| u8::try_from(b).ok()))
+				.collect::<Option<Vec<u8>>>()
+				.map(to_hex)
+				.ok_or_else(invalid),
+		},
+		JsonValue::Array(values) if !param.is_variant && !param.sub_params.is_empty() =>
+			Ok(format!("({})", each(&param.sub_params, values)?.join(", "))),
+		JsonValue::Object(fields) if param.is_variant => {
+			let (name, value) =
+				fields.iter().next().filter(|_| fields.len() == 1).ok_or_else(invalid)?;
+			let variant = param.sub_params.iter().find(|v| v.name == *name).ok_or_else(invalid)?;
+			let values = match value {
+				JsonValue::Null => Vec::new(),
+				// The fields of variants are entered in order.
+				JsonValue::Object(values) if variant.sub_params.len() > 1 => variant
+					.sub_params
+					.iter()
+					.map(|field| json_to_raw(field, values.get(&field.name).ok_or_else(invalid)?))
+					.collect::<Result<Vec<_>, _>>()?,
+				JsonValue::Array(values) if variant.sub_params.len() != 1 =>
+					each(&variant.sub_params, values)?,
+				value => each(&variant.sub_params, std::slice::from_ref(value))?,
+			};
+			Ok(format!("{name}({})", values.join(", ")))
+		},
+		JsonValue::Object(fields) if !param.sub_params.is_empty() => {
+			let values = param
+				.sub_params
+				.iter()
+				.map(|field| {
+					let value = match fields.get(&field.name) {
+						Some(value) => json_to_raw(field, value)?,
+						None if field.is_optional => "None()".to_string(),
+						None => return Err(invalid()),
+					};
+					Ok(format!("{}: {value}", field.name))
+				})
+				.collect::<Result<Vec<_>, Error>>()?;
+			Ok(format!("{{{}}}", values.join(", ")))
+		},
+		_ => Err(invalid()),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -212,6 +524,11 @@ mod tests {
 		assert_eq!(first_pallet.index, 0);
 		assert_eq!(first_pallet.docs, "");
 		assert_eq!(first_pallet.functions.len(), 11);
+		let account = first_pallet.storage.iter().find(|s| s.name == "Account").unwrap();
+		assert!(account.type_name.starts_with("Map<AccountId32, AccountInfo<u32,"));
+		assert!(first_pallet.constants.iter().any(|c| c.name == "SS58Prefix"));
+		let remarked = first_pallet.events.iter().find(|e| e.name == "Remarked").unwrap();
+		assert_eq!(remarked.type_name, "{ sender: T::AccountId, hash: T::Hash }");
 		let first_function = first_pallet.functions.first().unwrap();
 		assert_eq!(first_function.name, "remark");
 		assert_eq!(first_function.index, 0);
@@ -230,6 +547,25 @@ mod tests {
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn list_pallets_works() -> Result<()> {
+		let client = set_up_client(POP_NETWORK_TESTNET_URL).await?;
+		let pallets = list_pallets(&client);
+		let parsed = parse_chain_metadata(&client)?;
+		assert_eq!(
+			pallets.iter().map(|p| (&p.name, p.functions.len())).collect::<Vec<_>>(),
+			parsed.iter().map(|p| (&p.name, p.functions.len())).collect::<Vec<_>>()
+		);
+		// Parameters are only resolved on demand.
+		let function = find_dispatchable_by_name(&pallets, "Balances", "force_transfer")?;
+		assert!(function.params.is_empty());
+		let resolved = resolve_function(&client, function)?;
+		assert_eq!(&resolved, find_dispatchable_by_name(&parsed, "Balances", "force_transfer")?);
+		let function = Function { pallet: "WrongName".into(), ..Default::default() };
+		assert!(matches!(resolve_function(&client, &function), Err(Error::PalletNotFound(_))));
+		Ok(())
+	}
+
 	#[tokio::test]
 	async fn find_pallet_by_name_works() -> Result<()> {
 		let client = set_up_client(POP_NETWORK_TESTNET_URL).await?;
@@ -330,6 +666,129 @@ mod tests {
 				])
 			]
 		);
+		// Sequences are converted to bytes, unless their items are entered individually.
+		let bytes = Param { is_sequence: true, ..Default::default() };
+		let items = Param {
+			is_sequence: true,
+			sub_params: vec![Param { type_name: "u32".to_string(), ..Default::default() }],
+			..Default::default()
+		};
+		assert_eq!(
+			parse_dispatchable_arguments(&[bytes, items], vec!["(1, 2)".into(), "(1, 2)".into()])?,
+			[
+				Value::from_bytes("(1, 2)"),
+				Value::unnamed_composite(vec![Value::u128(1), Value::u128(2)])
+			]
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn parse_json_arguments_works() -> Result<()> {
+		let param = |name: &str, type_name: &str| Param {
+			name: name.into(),
+			type_name: type_name.into(),
+			..Default::default()
+		};
+		let variant = |name: &str, sub_params: Vec<Param>| Param {
+			name: name.into(),
+			sub_params,
+			is_variant: true,
+			..Default::default()
+		};
+		let params = vec![
+			Param {
+				name: "dest".into(),
+				sub_params: vec![
+					variant("Id", vec![param("Id", "AccountId32")]),
+					variant("Index", vec![param("Index", "Compact<()>")]),
+				],
+				is_variant: true,
+				is_address: true,
+				..Default::default()
+			},
+			param("value", "u128"),
+			Param {
+				name: "witness".into(),
+				sub_params: vec![
+					param("owned_item", "u32"),
+					Param { is_optional: true, ..param("mint_price", "u128") },
+				],
+				..Default::default()
+			},
+			Param {
+				name: "origin".into(),
+				sub_params: vec![
+					variant("Root", vec![]),
+					variant("Signed", vec![param("Signed", "AccountId32")]),
+					variant("Pair", vec![param("first", "u8"), param("second", "u8")]),
+				],
+				is_variant: true,
+				..Default::default()
+			},
+			Param {
+				name: "items".into(),
+				sub_params: vec![param("items", "u32")],
+				is_sequence: true,
+				..Default::default()
+			},
+			Param { name: "remark".into(), is_sequence: true, ..Default::default() },
+			Param { is_optional: true, ..param("tip", "u128") },
+		];
+		let json = r#"{
+			"dest": "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty",
+			"value": "1000",
+			"witness": {"owned_item": 1},
+			"origin": {"Pair": {"second": 2, "first": 1}},
+			"items": [1, 2],
+			"remark": [104, 105]
+		}"#;
+		assert_eq!(
+			parse_json_arguments(&params, json)?,
+			[
+				"Id(5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty)",
+				"1000",
+				"{owned_item: 1, mint_price: None()}",
+				"Pair(1, 2)",
+				"(1, 2)",
+				"0x6869",
+				"None()",
+			]
+		);
+		// Variants may be specified by name alone, or alongside their fields.
+		let json = r#"{
+			"dest": {"Index": 1},
+			"value": 1000,
+			"witness": {"owned_item": 1, "mint_price": 10},
+			"origin": "Root",
+			"items": [],
+			"remark": "0x6869",
+			"tip": 10
+		}"#;
+		assert_eq!(
+			parse_json_arguments(&params, json)?,
+			[
+				"Index(1)",
+				"1000",
+				"{owned_item: 1, mint_price: Some(10)}",
+				"Root()",
+				"()",
+				"0x6869",
+				"Some(10)",
+			]
+		);
+		// Check the arguments are specified as an object with the names of the parameters.
+		for json in [
+			"[1, 2]",
+			r#"{"value": 1000}"#,
+			r#"{"dest": "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty", "value": 1000, "witness": {"owned_item": 1}, "origin": "Root", "items": [], "remark": "", "unknown": 1}"#,
+			r#"{"dest": {"Unknown": 1}, "value": 1000, "witness": {"owned_item": 1}, "origin": "Root", "items": [], "remark": ""}"#,
+		] {
+			assert!(matches!(
+				parse_json_arguments(&params, json),
+				Err(Error::InvalidArguments(..))
+			));
+		}
 		Ok(())
 	}
 }
@@ -2,11 +2,14 @@
 
 use crate::errors::Error;
 use pop_common::format_type;
-use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef};
-use subxt::Metadata;
+use scale_info::{form::PortableForm, Field, PortableRegistry, Type, TypeDef, TypeDefPrimitive};
+use serde::Serialize;
+use sp_core::bytes::from_hex;
+use std::str::FromStr;
+use subxt::{utils::AccountId32, Metadata};
 
 /// Describes a parameter of a dispatchable function.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct Param {
 	/// The name of the parameter.
 	pub name: String,
@@ -22,6 +25,110 @@ pub struct Param {
 	pub is_variant: bool,
 	/// Indicates if the parameter is a Sequence.
 	pub is_sequence: bool,
+	/// Indicates if the parameter is a call of the runtime (`RuntimeCall`), or a sequence of them.
+	pub is_call: bool,
+	/// Indicates if the parameter is a balance (e.g. `T::Balance`), which may be entered as an
+	/// amount of the native token.
+	pub is_balance: bool,
+	/// Indicates if the parameter is an address (`MultiAddress`), which may be entered as the
+	/// account of its `Id` variant.
+	pub is_address: bool,
+}
+
+/// The kinds of values which can be validated as they are entered, as identified by the name of
+/// their type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValueKind {
+	/// A boolean.
+	Bool,
+	/// An unsigned integer of the specified number of bits.
+	Unsigned(u32),
+	/// A signed integer of the specified number of bits.
+	Signed(u32),
+	/// A 32-byte account, specified by its SS58 address or as hex.
+	AccountId32,
+	/// A 20-byte Ethereum-style account, specified as hex.
+	AccountId20,
+	/// A 32-byte hash, specified as hex.
+	H256,
+}
+
+impl ValueKind {
+	/// The kind of the values of a type, if they can be validated.
+	///
+	/// # Arguments
+	/// * `type_name`: The name of the type, as described by a [`Param`].
+	pub fn of(type_name: &str) -> Option<Self> {
+		// Compact integers are entered as integers.
+		let type_name = type_name
+			.strip_prefix("Compact<")
+			.and_then(|t| t.strip_suffix('>'))
+			.unwrap_or(type_name);
+		let bits = |t: &str| t[1..].parse::<u32>().ok().filter(|b| *b <= 128);
+		match type_name {
+			"bool" => Some(ValueKind::Bool),
+			t if t.starts_with('u') => bits(t).map(ValueKind::Unsigned),
+			t if t.starts_with('i') => bits(t).map(ValueKind::Signed),
+			t if t.starts_with("AccountId32") => Some(ValueKind::AccountId32),
+			t if t.starts_with("AccountId20") => Some(ValueKind::AccountId20),
+			t if t.starts_with("H256") => Some(ValueKind::H256),
+			_ => None,
+		}
+	}
+
+	/// Validates a value of this kind.
+	///
+	/// # Arguments
+	/// * `value`: The value, as entered.
+	pub fn validate(&self, value: &str) -> Result<(), Error> {
+		let valid = match self {
+			ValueKind::Bool => value.parse::<bool>().is_ok(),
+			ValueKind::Unsigned(bits) =>
+				value.parse::<u128>().is_ok_and(|v| *bits == 128 || v < 1 << bits),
+			ValueKind::Signed(bits) => value
+				.parse::<i128>()
+				.is_ok_and(|v| *bits == 128 || (-(1 << (bits - 1))..1 << (bits - 1)).contains(&v)),
+			ValueKind::AccountId32 => AccountId32::from_str(value).is_ok() || is_hex(value, 32),
+			ValueKind::AccountId20 => is_hex(value, 20),
+			ValueKind::H256 => is_hex(value, 32),
+		};
+		match valid {
+			true => Ok(()),
+			false => Err(Error::ParamProcessingError),
+		}
+	}
+
+	/// Describes the values expected of this kind, e.g. when a value is invalid.
+	pub fn expected(&self) -> &'static str {
+		match self {
+			ValueKind::Bool => "Expected `true` or `false`.",
+			ValueKind::Unsigned(_) =>
+				"Expected a non-negative integer within the range of the type.",
+			ValueKind::Signed(_) => "Expected an integer within the range of the type.",
+			ValueKind::AccountId32 =>
+				"Expected an SS58 address, e.g. 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY.",
+			ValueKind::AccountId20 =>
+				"Expected a 20-byte hex address, e.g. 0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac.",
+			ValueKind::H256 => "Expected 32 bytes of hex, starting with `0x`.",
+		}
+	}
+
+	/// The range of the values of this kind, if they are integers.
+	pub fn range(&self) -> Option<String> {
+		match self {
+			ValueKind::Unsigned(128) => Some(format!("0 to {}", u128::MAX)),
+			ValueKind::Unsigned(bits) => Some(format!("0 to {}", (1u128 << bits) - 1)),
+			ValueKind::Signed(128) => Some(format!("{} to {}", i128::MIN, i128::MAX)),
+			ValueKind::Signed(bits) =>
+				Some(format!("{} to {}", -(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1)),
+			_ => None,
+		}
+	}
+}
+
+// Whether a value is the hex encoding of the specified number of bytes, prefixed by `0x`.
+fn is_hex(value: &str, len: usize) -> bool {
+	value.starts_with("0x") && from_hex(value).is_ok_and(|bytes| bytes.len() == len)
 }
 
 /// Transforms a metadata field into its `Param` representation.
@@ -31,13 +138,22 @@ pub struct Param {
 /// * `field`: A parameter of a dispatchable function (as [Field]).
 pub fn field_to_param(metadata: &Metadata, field: &Field<PortableForm>) -> Result<Param, Error> {
 	let registry = metadata.types();
-	if let Some(name) = field.type_name.as_deref() {
-		if name.contains("RuntimeCall") {
-			return Err(Error::FunctionNotSupported);
-		}
-	}
 	let name = field.name.as_deref().unwrap_or("Unnamed"); //It can be unnamed field
-	type_to_param(name, registry, field.ty.id)
+	field_param(name, registry, field, &mut Vec::new())
+}
+
+// Converts a field into its `Param` representation, noting whether it is a balance as per the
+// name of its type (e.g. `T::Balance` or `BalanceOf<T>`).
+fn field_param(
+	name: &str,
+	registry: &PortableRegistry,
+	field: &Field<PortableForm>,
+	sequences: &mut Vec<u32>,
+) -> Result<Param, Error> {
+	let mut param = to_param(name, registry, field.ty.id, sequences)?;
+	param.is_balance = field.type_name.as_deref().is_some_and(|t| t.contains("Balance")) &&
+		matches!(ValueKind::of(&param.type_name), Some(ValueKind::Unsigned(_)));
+	Ok(param)
 }
 
 /// Converts a type's metadata into a `Param` representation.
@@ -46,14 +162,46 @@ pub fn field_to_param(metadata: &Metadata, field: &Field<PortableForm>) -> Resul
 /// * `name`: The name of the parameter.
 /// * `registry`: Type registry containing all types used in the metadata.
 /// * `type_id`: The ID of the type to be converted.
-fn type_to_param(name: &str, registry: &PortableRegistry, type_id: u32) -> Result<Param, Error> {
+pub(super) fn type_to_param(
+	name: &str,
+	registry: &PortableRegistry,
+	type_id: u32,
+) -> Result<Param, Error> {
+	to_param(name, registry, type_id, &mut Vec::new())
+}
+
+// Converts a type into its `Param` representation, tracking the sequences whose items are being
+// converted so that the items of recursive types are not.
+fn to_param(
+	name: &str,
+	registry: &PortableRegistry,
+	type_id: u32,
+	sequences: &mut Vec<u32>,
+) -> Result<Param, Error> {
 	let type_info = registry
 		.resolve(type_id)
 		.ok_or_else(|| Error::MetadataParsingError(name.to_string()))?;
-	// Check for unsupported `RuntimeCall` type
-	if type_info.path.segments.contains(&"RuntimeCall".to_string()) {
-		return Err(Error::FunctionNotSupported);
+	// Calls are composed on demand, as they are recursive.
+	if is_call(type_info) {
+		return Ok(Param {
+			name: name.to_string(),
+			type_name: "RuntimeCall".to_string(),
+			is_call: true,
+			..Default::default()
+		});
 	}
+	if let TypeDef::Sequence(sequence) = &type_info.type_def {
+		if registry.resolve(sequence.type_param.id).is_some_and(is_call) {
+			return Ok(Param {
+				name: name.to_string(),
+				type_name: "Vec<RuntimeCall>".to_string(),
+				is_sequence: true,
+				is_call: true,
+				..Default::default()
+			});
+		}
+	}
+	// Check for unsupported types which are generic over calls, e.g. `Xcm<RuntimeCall>`.
 	for param in &type_info.type_params {
 		if param.name.contains("RuntimeCall") {
 			return Err(Error::FunctionNotSupported);
@@ -62,12 +210,15 @@ fn type_to_param(name: &str, registry: &PortableRegistry, type_id: u32) -> Resul
 	if type_info.path.segments == ["Option"] {
 		if let Some(sub_type_id) = type_info.type_params.first().and_then(|param| param.ty) {
 			// Recursive for the sub parameters
-			let sub_param = type_to_param(name, registry, sub_type_id.id)?;
+			let sub_param = to_param(name, registry, sub_type_id.id, sequences)?;
 			Ok(Param {
 				name: name.to_string(),
 				type_name: sub_param.type_name,
 				sub_params: sub_param.sub_params,
 				is_optional: true,
+				is_sequence: sub_param.is_sequence,
+				is_call: sub_param.is_call,
+				is_address: sub_param.is_address,
 				..Default::default()
 			})
 		} else {
@@ -85,7 +236,12 @@ fn type_to_param(name: &str, registry: &PortableRegistry, type_id: u32) -> Resul
 					.iter()
 					.map(|field| {
 						// Recursive for the sub parameters of composite type.
-						type_to_param(field.name.as_deref().unwrap_or(name), registry, field.ty.id)
+						field_param(
+							field.name.as_deref().unwrap_or(name),
+							registry,
+							field,
+							sequences,
+						)
 					})
 					.collect::<Result<Vec<Param>, Error>>()?;
 
@@ -101,10 +257,11 @@ fn type_to_param(name: &str, registry: &PortableRegistry, type_id: u32) -> Resul
 							.iter()
 							.map(|field| {
 								// Recursive for the sub parameters of variant type.
-								type_to_param(
+								field_param(
 									field.name.as_deref().unwrap_or(&variant_param.name),
 									registry,
-									field.ty.id,
+									field,
+									sequences,
 								)
 							})
 							.collect::<Result<Vec<Param>, Error>>()?;
@@ -123,25 +280,44 @@ fn type_to_param(name: &str, registry: &PortableRegistry, type_id: u32) -> Resul
 					type_name,
 					sub_params: variant_params,
 					is_variant: true,
+					is_address: type_info.path.ident().as_deref() == Some("MultiAddress"),
+					..Default::default()
+				})
+			},
+			TypeDef::Sequence(sequence) => {
+				// The items of a sequence are entered individually, other than bytes or the items
+				// of recursive types, which are entered as a whole.
+				let item = sequence.type_param.id;
+				let sub_params = match registry.resolve(item).map(|ty| &ty.type_def) {
+					Some(TypeDef::Primitive(TypeDefPrimitive::U8)) | None => Vec::new(),
+					_ if sequences.contains(&type_id) => Vec::new(),
+					_ => {
+						sequences.push(type_id);
+						let item = to_param(name, registry, item, sequences);
+						sequences.pop();
+						// Items which cannot be described are entered as a whole.
+						item.ok().into_iter().collect()
+					},
+				};
+				Ok(Param {
+					name: name.to_string(),
+					type_name,
+					sub_params,
+					is_sequence: true,
 					..Default::default()
 				})
 			},
-			TypeDef::Sequence(_) => Ok(Param {
-				name: name.to_string(),
-				type_name,
-				is_sequence: true,
-				..Default::default()
-			}),
 			TypeDef::Tuple(tuple) => {
 				let sub_params = tuple
 					.fields
 					.iter()
 					.enumerate()
 					.map(|(index, field_id)| {
-						type_to_param(
+						to_param(
 							&format!("Index {index} of the tuple {name}"),
 							registry,
 							field_id.id,
+							sequences,
 						)
 					})
 					.collect::<Result<Vec<Param>, Error>>()?;
@@ -159,11 +335,132 @@ fn type_to_param(name: &str, registry: &PortableRegistry, type_id: u32) -> Resul
 	}
 }
 
+// Whether a type is the call of a runtime.
+fn is_call(ty: &Type<PortableForm>) -> bool {
+	ty.path.segments.last().is_some_and(|s| s == "RuntimeCall")
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::{call::tests::POP_NETWORK_TESTNET_URL, set_up_client};
 	use anyhow::Result;
+	use scale_info::{MetaType, Registry, TypeInfo};
+
+	#[allow(dead_code)]
+	#[derive(TypeInfo)]
+	enum RuntimeCall {
+		System(u8),
+		Utility(Vec<RuntimeCall>),
+	}
+
+	#[allow(dead_code)]
+	#[derive(TypeInfo)]
+	struct Xcm<RuntimeCall>(Vec<RuntimeCall>);
+
+	#[allow(dead_code)]
+	#[derive(TypeInfo)]
+	struct Tree {
+		children: Vec<Tree>,
+	}
+
+	#[test]
+	fn type_to_param_supports_calls() -> Result<()> {
+		let mut registry = Registry::new();
+		let call = registry.register_type(&MetaType::new::<Box<RuntimeCall>>()).id;
+		let calls = registry.register_type(&MetaType::new::<Vec<RuntimeCall>>()).id;
+		let optional = registry.register_type(&MetaType::new::<Option<RuntimeCall>>()).id;
+		let xcm = registry.register_type(&MetaType::new::<Xcm<RuntimeCall>>()).id;
+		let registry: PortableRegistry = registry.into();
+
+		assert_eq!(
+			type_to_param("call", &registry, call)?,
+			Param {
+				name: "call".into(),
+				type_name: "RuntimeCall".into(),
+				is_call: true,
+				..Default::default()
+			}
+		);
+		assert_eq!(
+			type_to_param("calls", &registry, calls)?,
+			Param {
+				name: "calls".into(),
+				type_name: "Vec<RuntimeCall>".into(),
+				is_sequence: true,
+				is_call: true,
+				..Default::default()
+			}
+		);
+		let param = type_to_param("call", &registry, optional)?;
+		assert!(param.is_optional && param.is_call);
+		assert!(matches!(
+			type_to_param("message", &registry, xcm),
+			Err(Error::FunctionNotSupported)
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn value_kind_works() {
+		use ValueKind::{AccountId20, AccountId32, Bool, Signed, Unsigned, H256};
+		for (type_name, kind) in [
+			("bool", Some(Bool)),
+			("u8", Some(Unsigned(8))),
+			("Compact<u128>", Some(Unsigned(128))),
+			("i32", Some(Signed(32))),
+			("AccountId32 ([u8;32])", Some(AccountId32)),
+			("AccountId20 ([u8;20])", Some(AccountId20)),
+			("H256 ([u8;32])", Some(H256)),
+			("u256", None),
+			("Compact<()>", None),
+			("[u8]", None),
+		] {
+			assert_eq!(ValueKind::of(type_name), kind, "{type_name}");
+		}
+		let valid = |kind: ValueKind, value: &str| kind.validate(value).is_ok();
+		assert!(valid(Bool, "true") && !valid(Bool, "yes"));
+		assert!(valid(Unsigned(8), "255") && !valid(Unsigned(8), "256"));
+		assert!(!valid(Unsigned(8), "-1") && valid(Unsigned(128), &u128::MAX.to_string()));
+		assert!(valid(Signed(8), "-128") && !valid(Signed(8), "128"));
+		assert!(valid(AccountId32, "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"));
+		assert!(valid(AccountId32, &format!("0x{}", "01".repeat(32))));
+		assert!(!valid(AccountId32, "5Grw"));
+		assert!(valid(AccountId20, "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac"));
+		assert!(!valid(H256, "01".repeat(32).as_str()) && !valid(H256, "0x01"));
+		assert_eq!(Unsigned(8).range().as_deref(), Some("0 to 255"));
+		assert_eq!(Signed(16).range().as_deref(), Some("-32768 to 32767"));
+		assert_eq!(Bool.range(), None);
+	}
+
+	#[test]
+	fn type_to_param_supports_sequences() -> Result<()> {
+		let mut registry = Registry::new();
+		let bytes = registry.register_type(&MetaType::new::<Vec<u8>>()).id;
+		let numbers = registry.register_type(&MetaType::new::<Vec<u32>>()).id;
+		let optional = registry.register_type(&MetaType::new::<Option<Vec<u32>>>()).id;
+		let tree = registry.register_type(&MetaType::new::<Tree>()).id;
+		let registry: PortableRegistry = registry.into();
+
+		// Bytes are entered as a whole.
+		let param = type_to_param("remark", &registry, bytes)?;
+		assert!(param.is_sequence && param.sub_params.is_empty());
+		// The items of other sequences are entered individually.
+		let param = type_to_param("numbers", &registry, numbers)?;
+		assert!(param.is_sequence);
+		assert_eq!(
+			param.sub_params,
+			vec![Param { name: "numbers".into(), type_name: "u32".into(), ..Default::default() }]
+		);
+		let param = type_to_param("numbers", &registry, optional)?;
+		assert!(param.is_optional && param.is_sequence && param.sub_params.len() == 1);
+		// The items of recursive types are entered as a whole once nested.
+		let children = &type_to_param("tree", &registry, tree)?.sub_params[0];
+		assert!(children.is_sequence);
+		let nested = &children.sub_params[0].sub_params[0];
+		assert!(nested.is_sequence && nested.sub_params.is_empty());
+		Ok(())
+	}
 
 	#[tokio::test]
 	async fn field_to_param_works() -> Result<()> {
@@ -211,22 +508,22 @@ mod tests {
 				.type_name,
 			"AccountId32 ([u8;32])"
 		);
-		// Test some dispatchable functions that are not supported.
+		// The amount transferred is a balance, unlike the accounts.
+		assert!(params[2].is_balance && !params[0].is_balance);
+		assert!(params[0].is_address && !params[2].is_address);
+		// Test some dispatchable functions with calls as parameters.
 		let function =
 			metadata.pallet_by_name("Sudo").unwrap().call_variant_by_name("sudo").unwrap();
-		assert!(matches!(
-			field_to_param(&metadata, &function.fields.first().unwrap()),
-			Err(Error::FunctionNotSupported)
-		));
+		let param = field_to_param(&metadata, function.fields.first().unwrap())?;
+		assert!(param.is_call && !param.is_sequence);
 		let function = metadata
 			.pallet_by_name("Utility")
 			.unwrap()
 			.call_variant_by_name("batch")
 			.unwrap();
-		assert!(matches!(
-			field_to_param(&metadata, &function.fields.first().unwrap()),
-			Err(Error::FunctionNotSupported)
-		));
+		let param = field_to_param(&metadata, function.fields.first().unwrap())?;
+		assert!(param.is_call && param.is_sequence);
+		// Test some dispatchable functions that are not supported.
 		let function = metadata
 			.pallet_by_name("PolkadotXcm")
 			.unwrap()
@@ -245,10 +542,7 @@ mod tests {
 			.unwrap()
 			.call_variant_by_name("execute")
 			.unwrap();
-		assert!(matches!(
-			field_to_param(&metadata, &function.fields.first().unwrap()),
-			Err(Error::FunctionNotSupported)
-		));
+		assert!(field_to_param(&metadata, function.fields.first().unwrap())?.is_call);
 
 		Ok(())
 	}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{docs, params::type_to_param, parse_dispatchable_arguments, type_name, Param};
+use crate::{errors::Error, storage::decode};
+use scale_info::{PortableRegistry, TypeDef};
+use std::fmt::{Display, Formatter};
+use subxt::{
+	dynamic,
+	metadata::types::{StorageEntryModifier, StorageEntryType},
+	OnlineClient, SubstrateConfig,
+};
+
+/// Represents a storage item of a pallet, which is queried by its keys.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StorageItem {
+	/// The pallet containing the storage item.
+	pub pallet: String,
+	/// The name of the storage item.
+	pub name: String,
+	/// The documentation of the storage item.
+	pub docs: String,
+	/// The type of the value of the storage item.
+	pub type_name: String,
+	/// The keys of a map, one per hasher, which are empty for plain storage values.
+	pub keys: Vec<Param>,
+	/// Whether the default value is returned for keys which are not present.
+	pub has_default: bool,
+	/// Whether this storage item is supported (no unsupported types as its keys).
+	pub is_supported: bool,
+}
+
+impl Display for StorageItem {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name)
+	}
+}
+
+/// Parses the storage items of a pallet, including the parameters of their keys.
+///
+/// # Arguments
+/// * `client`: The client to interact with the chain.
+/// * `pallet`: The name of the pallet.
+pub fn parse_storage_items(
+	client: &OnlineClient<SubstrateConfig>,
+	pallet: &str,
+) -> Result<Vec<StorageItem>, Error> {
+	let metadata = client.metadata();
+	let pallet = metadata
+		.pallet_by_name(pallet)
+		.ok_or_else(|| Error::PalletNotFound(pallet.to_string()))?;
+	let registry = metadata.types();
+	let Some(storage) = pallet.storage() else { return Ok(Vec::new()) };
+	Ok(storage
+		.entries()
+		.iter()
+		.map(|entry| {
+			let keys = match entry.entry_type() {
+				StorageEntryType::Plain(_) => Ok(Vec::new()),
+				StorageEntryType::Map { hashers, key_ty, .. } =>
+					key_params(registry, hashers.len(), *key_ty),
+			};
+			StorageItem {
+				pallet: pallet.name().to_string(),
+				name: entry.name().to_string(),
+				docs: docs(entry.docs()),
+				type_name: type_name(registry, entry.entry_type().value_ty()),
+				is_supported: keys.is_ok(),
+				keys: keys.unwrap_or_default(),
+				has_default: matches!(entry.modifier(), StorageEntryModifier::Default),
+			}
+		})
+		.collect())
+}
+
+/// Queries the value of a storage item at the latest block, decoded via the metadata of the
+/// chain. Returns `None` if no value is present for the keys.
+///
+/// # Arguments
+/// * `client`: The client to interact with the chain.
+/// * `item`: The storage item.
+/// * `keys`: The keys of the entry, encoded as strings, which are empty for plain storage values.
+pub async fn query_storage(
+	client: &OnlineClient<SubstrateConfig>,
+	item: &StorageItem,
+	keys: Vec<String>,
+) -> Result<Option<String>, Error> {
+	if !item.is_supported {
+		return Err(Error::FunctionNotSupported);
+	}
+	if keys.len() != item.keys.len() {
+		return Err(Error::ParamProcessingError);
+	}
+	let keys = parse_dispatchable_arguments(&item.keys, keys)?;
+	let address = dynamic::storage(&item.pallet, &item.name, keys);
+	let storage = client.storage().at_latest().await?;
+	let value = match item.has_default {
+		true => Some(storage.fetch_or_default(&address).await?),
+		false => storage.fetch(&address).await?,
+	};
+	Ok(value.map(decode))
+}
+
+// The parameters of the keys of a map, whose key type is a tuple of the keys of each hasher when
+// there are multiple.
+fn key_params(
+	registry: &PortableRegistry,
+	hashers: usize,
+	key_ty: u32,
+) -> Result<Vec<Param>, Error> {
+	if hashers <= 1 {
+		return Ok(vec![type_to_param("key", registry, key_ty)?]);
+	}
+	let ty = registry
+		.resolve(key_ty)
+		.ok_or_else(|| Error::MetadataParsingError("key".to_string()))?;
+	let TypeDef::Tuple(tuple) = &ty.type_def else {
+		return Err(Error::MetadataParsingError("key".to_string()));
+	};
+	tuple
+		.fields
+		.iter()
+		.enumerate()
+		.map(|(index, field)| type_to_param(&format!("key {}", index + 1), registry, field.id))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+	use scale_info::{MetaType, Registry};
+
+	#[test]
+	fn key_params_works() -> Result<()> {
+		let mut registry = Registry::new();
+		let account = registry.register_type(&MetaType::new::<[u8; 32]>()).id;
+		let double = registry.register_type(&MetaType::new::<(u32, [u8; 32])>()).id;
+		let registry: PortableRegistry = registry.into();
+
+		let keys = key_params(&registry, 1, account)?;
+		assert_eq!(keys.len(), 1);
+		assert_eq!((keys[0].name.as_str(), keys[0].type_name.as_str()), ("key", "[u8;32]"));
+		let keys = key_params(&registry, 2, double)?;
+		assert_eq!(
+			keys.iter().map(|k| (k.name.as_str(), k.type_name.as_str())).collect::<Vec<_>>(),
+			[("key 1", "u32"), ("key 2", "[u8;32]")]
+		);
+		// Multiple hashers require the keys to be a tuple.
+		assert!(matches!(key_params(&registry, 2, account), Err(Error::MetadataParsingError(_))));
+		Ok(())
+	}
+}
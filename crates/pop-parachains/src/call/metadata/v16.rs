@@ -0,0 +1,371 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use super::{docs, params, parse_dispatchable_arguments, type_name, Pallet, PalletItem, Param};
+use crate::errors::Error;
+use frame_metadata::{
+	v16::{
+		ItemDeprecationInfo, PalletViewFunctionMetadata, RuntimeMetadataV16, VariantDeprecationInfo,
+	},
+	RuntimeMetadata, RuntimeMetadataPrefixed,
+};
+use scale_info::form::PortableForm;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use subxt::{
+	ext::codec::{Decode, Encode},
+	OnlineClient, SubstrateConfig,
+};
+
+/// The version of the metadata, which introduced view functions, associated types and
+/// deprecation information.
+const VERSION: u32 = 16;
+
+/// Represents a view function of a pallet, which queries the state of the chain without
+/// submitting an extrinsic.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct ViewFunction {
+	/// The pallet containing the view function.
+	pub pallet: String,
+	/// The name of the view function.
+	pub name: String,
+	/// The documentation of the view function.
+	pub docs: String,
+	/// The parameters of the view function.
+	pub params: Vec<Param>,
+	/// The type returned by the view function.
+	pub output: String,
+	/// A description of the deprecation of the view function, if deprecated.
+	pub deprecation: Option<String>,
+	/// Whether the view function is supported, i.e. whether its parameters can be entered.
+	pub is_supported: bool,
+}
+
+impl Display for ViewFunction {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.name)
+	}
+}
+
+/// Metadata V16 of a chain, providing the view functions, associated types and deprecation
+/// information which are not part of the metadata used to construct calls.
+#[derive(Debug)]
+pub struct MetadataV16(RuntimeMetadataV16);
+
+impl MetadataV16 {
+	/// Fetches metadata V16 from the chain, if provided by its runtime.
+	///
+	/// # Arguments
+	/// * `client`: The client to interact with the chain.
+	pub async fn fetch(client: &OnlineClient<SubstrateConfig>) -> Result<Option<Self>, Error> {
+		let api = client.runtime_api().at_latest().await?;
+		let versions: Vec<u32> = api.call_raw("Metadata_metadata_versions", None).await?;
+		if !versions.contains(&VERSION) {
+			return Ok(None);
+		}
+		let metadata: Option<Vec<u8>> =
+			api.call_raw("Metadata_metadata_at_version", Some(&VERSION.encode())).await?;
+		metadata.map(|metadata| Self::decode(&metadata)).transpose()
+	}
+
+	/// Decodes metadata V16 from its encoding, as returned by the runtime.
+	///
+	/// # Arguments
+	/// * `bytes`: The encoded metadata.
+	pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+		let RuntimeMetadataPrefixed(_, metadata) = RuntimeMetadataPrefixed::decode(&mut &bytes[..])
+			.map_err(|e| Error::DecodingError(format!("invalid metadata: {e}")))?;
+		match metadata {
+			RuntimeMetadata::V16(metadata) => Ok(Self(metadata)),
+			metadata => Err(Error::DecodingError(format!(
+				"expected metadata V{VERSION}, found V{}",
+				metadata.version()
+			))),
+		}
+	}
+
+	/// Adds the view functions and associated types of the pallets, along with the deprecation of
+	/// their dispatchable functions.
+	///
+	/// # Arguments
+	/// * `pallets`: The pallets, as parsed from the metadata used to construct calls.
+	pub fn apply(&self, pallets: &mut [Pallet]) {
+		let registry = &self.0.types;
+		for pallet in pallets {
+			let Some(metadata) = self.0.pallets.iter().find(|p| p.name == pallet.name) else {
+				continue;
+			};
+			pallet.associated_types = metadata
+				.associated_types
+				.iter()
+				.map(|ty| PalletItem {
+					name: ty.name.clone(),
+					docs: docs(&ty.docs),
+					type_name: type_name(registry, ty.ty.id),
+				})
+				.collect();
+			pallet.view_functions = metadata
+				.view_functions
+				.iter()
+				.map(|function| self.view_function(&pallet.name, function))
+				.collect();
+			if let Some(calls) = &metadata.calls {
+				for function in &mut pallet.functions {
+					function.deprecation =
+						calls.deprecation_info.0.get(&function.index).map(|deprecation| {
+							match deprecation {
+								VariantDeprecationInfo::DeprecatedWithoutNote =>
+									describe_deprecation(None, None),
+								VariantDeprecationInfo::Deprecated { note, since } =>
+									describe_deprecation(Some(note), since.as_deref()),
+							}
+						});
+				}
+			}
+		}
+	}
+
+	/// Whether the chain accepts extrinsics of the specified version.
+	///
+	/// # Arguments
+	/// * `version`: The version of the extrinsic format.
+	pub fn supports_extrinsic_version(&self, version: u8) -> bool {
+		self.0.extrinsic.versions.contains(&version)
+	}
+
+	/// Executes a view function, returning its output.
+	///
+	/// # Arguments
+	/// * `client`: The client to interact with the chain.
+	/// * `function`: The view function.
+	/// * `args`: The arguments of the view function.
+	pub async fn execute_view_function(
+		&self,
+		client: &OnlineClient<SubstrateConfig>,
+		function: &ViewFunction,
+		args: Vec<String>,
+	) -> Result<String, Error> {
+		let metadata = self
+			.0
+			.pallets
+			.iter()
+			.find(|p| p.name == function.pallet)
+			.and_then(|p| p.view_functions.iter().find(|f| f.name == function.name))
+			.ok_or_else(|| {
+				Error::ViewFunctionError(format!(
+					"{}.{} was not found",
+					function.pallet, function.name
+				))
+			})?;
+		if args.len() != metadata.inputs.len() {
+			return Err(Error::ViewFunctionError(format!(
+				"expected {} arguments, found {}",
+				metadata.inputs.len(),
+				args.len()
+			)));
+		}
+		let registry = &self.0.types;
+		let mut input = Vec::new();
+		for (value, param) in parse_dispatchable_arguments(&function.params, args)?
+			.iter()
+			.zip(&metadata.inputs)
+		{
+			scale_value::scale::encode_as_type(value, param.ty.id, registry, &mut input).map_err(
+				|e| Error::ViewFunctionError(format!("invalid argument `{}`: {e}", param.name)),
+			)?;
+		}
+		let api = client.runtime_api().at_latest().await?;
+		// The error is identified by its index: not implemented, not found or undecodable input.
+		let output: Result<Vec<u8>, u8> = api
+			.call_raw(
+				"RuntimeViewFunction_execute_view_function",
+				Some(&(metadata.id, input).encode()),
+			)
+			.await?;
+		let output = output.map_err(|e| {
+			Error::ViewFunctionError(
+				match e {
+					0 => "the view functions of the pallet are not implemented",
+					1 => "the view function was not found",
+					_ => "the arguments could not be decoded",
+				}
+				.into(),
+			)
+		})?;
+		let value =
+			scale_value::scale::decode_as_type(&mut &output[..], metadata.output.id, registry)
+				.map_err(|e| Error::DecodingError(e.to_string()))?;
+		Ok(value.to_string())
+	}
+
+	// Parses a view function, including its parameters.
+	fn view_function(
+		&self,
+		pallet: &str,
+		function: &PalletViewFunctionMetadata<PortableForm>,
+	) -> ViewFunction {
+		let registry = &self.0.types;
+		// The view function is unsupported if any of its parameters cannot be parsed.
+		let params: Result<Vec<_>, _> = function
+			.inputs
+			.iter()
+			.map(|input| params::type_to_param(&input.name, registry, input.ty.id))
+			.collect();
+		ViewFunction {
+			pallet: pallet.to_string(),
+			name: function.name.clone(),
+			docs: docs(&function.docs),
+			is_supported: params.is_ok(),
+			params: params.unwrap_or_default(),
+			output: type_name(registry, function.output.id),
+			deprecation: match &function.deprecation_info {
+				ItemDeprecationInfo::NotDeprecated => None,
+				ItemDeprecationInfo::DeprecatedWithoutNote =>
+					Some(describe_deprecation(None, None)),
+				ItemDeprecationInfo::Deprecated { note, since } =>
+					Some(describe_deprecation(Some(note), since.as_deref())),
+			},
+		}
+	}
+}
+
+// Describes the deprecation of an item, e.g. `Deprecated since 1.2.0: Use another call`.
+fn describe_deprecation(note: Option<&str>, since: Option<&str>) -> String {
+	let mut description = "Deprecated".to_string();
+	if let Some(since) = since {
+		description.push_str(&format!(" since {since}"));
+	}
+	if let Some(note) = note {
+		description.push_str(&format!(": {note}"));
+	}
+	description
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Function;
+	use frame_metadata::v16::{
+		CustomMetadata, EnumDeprecationInfo, ExtrinsicMetadata, FunctionParamMetadata, OuterEnums,
+		PalletAssociatedTypeMetadata, PalletCallMetadata, PalletMetadata,
+	};
+	use scale_info::meta_type;
+	use std::collections::BTreeMap;
+
+	// Encodes metadata V16 declaring a pallet with a view function, an associated type and a
+	// deprecated dispatchable function.
+	fn metadata() -> Vec<u8> {
+		let pallet = PalletMetadata {
+			name: "Example",
+			storage: None,
+			calls: Some(PalletCallMetadata {
+				ty: meta_type::<()>(),
+				deprecation_info: EnumDeprecationInfo(BTreeMap::from([(
+					1,
+					VariantDeprecationInfo::Deprecated { note: "Use `new`", since: Some("1.2.0") },
+				)])),
+			}),
+			event: None,
+			constants: vec![],
+			error: None,
+			associated_types: vec![PalletAssociatedTypeMetadata {
+				name: "Balance",
+				ty: meta_type::<u128>(),
+				docs: vec!["The balance of an account."],
+			}],
+			view_functions: vec![PalletViewFunctionMetadata {
+				id: [1; 32],
+				name: "value",
+				inputs: vec![FunctionParamMetadata { name: "key", ty: meta_type::<u32>() }],
+				output: meta_type::<Option<u64>>(),
+				docs: vec!["The value stored under a key."],
+				deprecation_info: ItemDeprecationInfo::DeprecatedWithoutNote,
+			}],
+			index: 0,
+			docs: vec![],
+			deprecation_info: ItemDeprecationInfo::NotDeprecated,
+		};
+		let extrinsic = ExtrinsicMetadata {
+			versions: vec![4, 5],
+			address_ty: meta_type::<()>(),
+			call_ty: meta_type::<()>(),
+			signature_ty: meta_type::<()>(),
+			transaction_extensions_by_version: BTreeMap::new(),
+			transaction_extensions: vec![],
+		};
+		let outer_enums = OuterEnums {
+			call_enum_ty: meta_type::<()>(),
+			event_enum_ty: meta_type::<()>(),
+			error_enum_ty: meta_type::<()>(),
+		};
+		let metadata = RuntimeMetadataV16::new(
+			vec![pallet],
+			extrinsic,
+			vec![],
+			outer_enums,
+			CustomMetadata { map: BTreeMap::new() },
+		);
+		RuntimeMetadataPrefixed::from(metadata).encode()
+	}
+
+	#[test]
+	fn apply_works() -> Result<(), Error> {
+		let metadata = MetadataV16::decode(&metadata())?;
+		let function = |name: &str, index| Function {
+			pallet: "Example".into(),
+			name: name.into(),
+			index,
+			is_supported: true,
+			..Default::default()
+		};
+		let mut pallets = vec![
+			Pallet {
+				name: "Example".into(),
+				functions: vec![function("new", 0), function("old", 1)],
+				..Default::default()
+			},
+			Pallet { name: "System".into(), ..Default::default() },
+		];
+		metadata.apply(&mut pallets);
+		assert_eq!(pallets[0].functions[0].deprecation, None);
+		assert_eq!(
+			pallets[0].functions[1].deprecation.as_deref(),
+			Some("Deprecated since 1.2.0: Use `new`")
+		);
+		assert_eq!(
+			pallets[0].associated_types,
+			vec![PalletItem {
+				name: "Balance".into(),
+				docs: "The balance of an account.".into(),
+				type_name: "u128".into(),
+			}]
+		);
+		let view_function = &pallets[0].view_functions[0];
+		assert_eq!(view_function.name, "value");
+		assert_eq!(view_function.docs, "The value stored under a key.");
+		assert_eq!(view_function.params[0].name, "key");
+		assert_eq!(view_function.params[0].type_name, "u32");
+		assert_eq!(view_function.output, "Option<u64>: None, Some(u64)");
+		assert_eq!(view_function.deprecation.as_deref(), Some("Deprecated"));
+		assert!(view_function.is_supported);
+		// Pallets not declared by the metadata are unchanged.
+		assert_eq!(pallets[1], Pallet { name: "System".into(), ..Default::default() });
+		assert!(metadata.supports_extrinsic_version(5));
+		assert!(!metadata.supports_extrinsic_version(6));
+		Ok(())
+	}
+
+	#[test]
+	fn decode_fails_with_invalid_metadata() {
+		assert!(matches!(MetadataV16::decode(&[1, 2, 3]), Err(Error::DecodingError(..))));
+	}
+
+	#[test]
+	fn describe_deprecation_works() {
+		assert_eq!(describe_deprecation(None, None), "Deprecated");
+		assert_eq!(describe_deprecation(Some("Use `new`"), None), "Deprecated: Use `new`");
+		assert_eq!(
+			describe_deprecation(Some("Use `new`"), Some("1.2.0")),
+			"Deprecated since 1.2.0: Use `new`"
+		);
+	}
+}
@@ -6,12 +6,103 @@ use pop_common::{
 	create_signer,
 };
 use sp_core::bytes::{from_hex, to_hex};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+use strum_macros::{AsRefStr, EnumString};
 use subxt::{
+	backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+	blocks::ExtrinsicEvents,
+	client::RuntimeVersion,
+	config::{DefaultExtrinsicParams, DefaultExtrinsicParamsBuilder, ExtrinsicParams},
 	dynamic::Value,
-	tx::{DynamicPayload, Payload, SubmittableExtrinsic},
-	OnlineClient, SubstrateConfig,
+	error::DispatchError,
+	ext::codec::{Decode, Encode},
+	tx::{DynamicPayload, Payload, SubmittableExtrinsic, TxProgress, TxStatus},
+	utils::H256,
+	Metadata, OnlineClient, SubstrateConfig,
 };
+mod batch;
+mod ethereum;
 pub mod metadata;
+mod offline;
+mod template;
+
+pub use batch::{batch_all, CallManifest, ManifestCall};
+pub use ethereum::{
+	sign_ethereum_extrinsic, AccountFormat, EthereumConfig, EthereumSignature, EthereumSigner,
+	ALITH_PRIVATE_KEY,
+};
+pub use offline::{create_payload, sign_payload, signed_extrinsic, SignedPayload, UnsignedPayload};
+pub use template::{CallTemplate, CallTemplates};
+
+/// The version of the metadata requested from a runtime, which includes its runtime APIs.
+const METADATA_VERSION: u32 = 15;
+/// The number of blocks for which an extrinsic is valid by default, as with subxt.
+const DEFAULT_ERA: u64 = 32;
+
+/// Options of the signed extensions of an extrinsic and of how far it is followed once submitted,
+/// which otherwise use their defaults.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ExtrinsicOptions {
+	/// The nonce of the signer, e.g. to submit several extrinsics concurrently [default: the next
+	/// nonce of the account].
+	pub nonce: Option<u64>,
+	/// The tip paid to the block author, in the smallest unit of the native token.
+	pub tip: u128,
+	/// The number of blocks from the latest one for which the extrinsic is valid, rounded to a
+	/// power of two, or zero for an immortal extrinsic [default: 32].
+	pub era: Option<u64>,
+	/// How far the extrinsic is followed once submitted [default: finalized].
+	pub wait: WaitFor,
+}
+
+impl ExtrinsicOptions {
+	/// The parameters of the signed extensions of an extrinsic.
+	///
+	/// # Arguments
+	/// * `client` - The client used to interact with the chain.
+	pub async fn params(
+		&self,
+		client: &OnlineClient<SubstrateConfig>,
+	) -> Result<
+		<DefaultExtrinsicParams<SubstrateConfig> as ExtrinsicParams<SubstrateConfig>>::Params,
+		Error,
+	> {
+		let mut params = DefaultExtrinsicParamsBuilder::<SubstrateConfig>::new().tip(self.tip);
+		if let Some(nonce) = self.nonce {
+			params = params.nonce(nonce);
+		}
+		let period = self.era.unwrap_or(DEFAULT_ERA);
+		if period > 0 {
+			params = params.mortal(client.blocks().at_latest().await?.header(), period);
+		}
+		Ok(params.build())
+	}
+}
+
+/// How far a submitted extrinsic is followed before its submission completes.
+#[derive(AsRefStr, Clone, Copy, Debug, Default, EnumString, Eq, PartialEq)]
+#[strum(serialize_all = "lowercase")]
+pub enum WaitFor {
+	/// Until the extrinsic is accepted into the transaction pool of the node.
+	None,
+	/// Until the extrinsic is included in the best block.
+	Best,
+	/// Until the extrinsic is included in a finalized block.
+	#[default]
+	Finalized,
+}
+
+/// A submitted extrinsic, as far as it was followed.
+#[derive(Debug)]
+pub enum Submitted {
+	/// The extrinsic was accepted into the transaction pool, but not followed any further.
+	Accepted(H256),
+	/// The extrinsic was included in a block, along with its events.
+	Included(ExtrinsicEvents<SubstrateConfig>),
+}
 
 /// Sets up an [OnlineClient] instance for connecting to a blockchain.
 ///
@@ -23,6 +114,89 @@ pub async fn set_up_client(url: &str) -> Result<OnlineClient<SubstrateConfig>, E
 		.map_err(|e| Error::ConnectionFailure(e.to_string()))
 }
 
+/// Sets up an [OnlineClient] instance for connecting to a blockchain, reusing the metadata of the
+/// runtime cached by a previous connection to the same chain, as identified by its genesis hash
+/// and spec version. Any metadata which is downloaded is cached for subsequent connections.
+///
+/// # Arguments
+/// * `url` - Endpoint of the node.
+/// * `cache` - The location of the cache.
+pub async fn set_up_cached_client(
+	url: &str,
+	cache: &Path,
+) -> Result<OnlineClient<SubstrateConfig>, Error> {
+	let rpc = RpcClient::from_url(url)
+		.await
+		.map_err(|e| Error::ConnectionFailure(e.to_string()))?;
+	cached_client(rpc, cache).await
+}
+
+// Sets up a client using an existing RPC client, reusing any cached metadata of the runtime.
+pub(crate) async fn cached_client(
+	rpc: RpcClient,
+	cache: &Path,
+) -> Result<OnlineClient<SubstrateConfig>, Error> {
+	let methods = LegacyRpcMethods::<SubstrateConfig>::new(rpc.clone());
+	let genesis_hash = methods.genesis_hash().await?;
+	let version = methods.state_get_runtime_version(None).await?;
+	let path = metadata_path(cache, &genesis_hash, version.spec_version);
+	let cached = fs::read(&path).ok().and_then(|bytes| Metadata::decode(&mut &bytes[..]).ok());
+	let metadata = match cached {
+		Some(metadata) => metadata,
+		None => {
+			let bytes = fetch_metadata(&methods).await?;
+			let metadata = Metadata::decode(&mut &bytes[..])
+				.map_err(|e| Error::MetadataParsingError(e.to_string()))?;
+			// Caching is best effort, with the metadata fetched again next time on failure.
+			if let Some(parent) = path.parent() {
+				let _ = fs::create_dir_all(parent).and_then(|_| fs::write(&path, &bytes));
+			}
+			metadata
+		},
+	};
+	let version = RuntimeVersion {
+		spec_version: version.spec_version,
+		transaction_version: version.transaction_version,
+	};
+	OnlineClient::from_rpc_client_with(genesis_hash, version, metadata, rpc)
+		.map_err(|e| Error::ConnectionFailure(e.to_string()))
+}
+
+// Fetches the encoded metadata of the latest runtime, preferring the version which includes its
+// runtime APIs.
+async fn fetch_metadata(methods: &LegacyRpcMethods<SubstrateConfig>) -> Result<Vec<u8>, Error> {
+	let versioned = methods
+		.state_call("Metadata_metadata_at_version", Some(&METADATA_VERSION.encode()), None)
+		.await
+		.ok()
+		.and_then(|bytes| Option::<Vec<u8>>::decode(&mut &bytes[..]).ok().flatten());
+	match versioned {
+		Some(bytes) => Ok(bytes),
+		None => {
+			let bytes = methods.state_call("Metadata_metadata", None, None).await?;
+			Ok(Vec::<u8>::decode(&mut &bytes[..]).map_err(|e| subxt::Error::Decode(e.into()))?)
+		},
+	}
+}
+
+/// Removes the metadata of all runtimes cached by previous connections, so that it is fetched
+/// again by the next connection.
+///
+/// # Arguments
+/// * `cache` - The location of the cache.
+pub fn clear_cached_metadata(cache: &Path) -> Result<(), Error> {
+	let path = cache.join("metadata");
+	if path.exists() {
+		fs::remove_dir_all(path)?;
+	}
+	Ok(())
+}
+
+// The path at which the metadata of a runtime is cached.
+fn metadata_path(cache: &Path, genesis_hash: &H256, spec_version: u32) -> PathBuf {
+	cache.join("metadata").join(format!("{genesis_hash:?}-{spec_version}.scale"))
+}
+
 /// Constructs a dynamic extrinsic payload for a specified dispatchable function.
 ///
 /// # Arguments
@@ -45,6 +219,22 @@ pub fn construct_sudo_extrinsic(xt: DynamicPayload) -> DynamicPayload {
 	subxt::dynamic::tx("Sudo", "sudo", [xt.into_value()].to_vec())
 }
 
+/// Wraps the encoded call data of a dispatchable function call in a Sudo call, such that it is
+/// dispatched with `Root` privileges.
+///
+/// # Arguments
+/// * `metadata`: The metadata of the chain.
+/// * `call_data`: The hex-encoded call data of the call.
+pub fn construct_sudo_call_data(metadata: &Metadata, call_data: &str) -> Result<String, Error> {
+	let call = decode_call_data(call_data)?;
+	let pallet = metadata
+		.pallet_by_name("Sudo")
+		.ok_or(Error::PalletNotFound("Sudo".to_string()))?;
+	let sudo = pallet.call_variant_by_name("sudo").ok_or(Error::FunctionNotSupported)?;
+	let call_data: Vec<u8> = [pallet.index(), sudo.index].into_iter().chain(call).collect();
+	Ok(to_hex(&call_data, false))
+}
+
 /// Signs and submits a given extrinsic.
 ///
 /// # Arguments
@@ -58,50 +248,215 @@ pub async fn sign_and_submit_extrinsic<Xt: Payload>(
 	xt: Xt,
 	suri: &str,
 ) -> Result<String, Error> {
-	let signer = create_signer(suri)?;
-	let result = client
-		.tx()
-		.sign_and_submit_then_watch_default(&xt, &signer)
-		.await
-		.map_err(|e| Error::ExtrinsicSubmissionError(format!("{:?}", e)))?
-		.wait_for_finalized_success()
-		.await
-		.map_err(|e| Error::ExtrinsicSubmissionError(format!("{:?}", e)))?;
+	let token_metadata = TokenMetadata::query::<SubstrateConfig>(url).await?;
+	sign_and_submit_extrinsic_with(client, &token_metadata, xt, suri).await
+}
 
+/// Signs and submits a given extrinsic, displaying its events using the specified token metadata.
+/// Useful when the token metadata is not queried via an endpoint, such as when connected via a
+/// light client.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `token_metadata` - The metadata of the native token of the chain.
+/// * `xt` - The (encoded) extrinsic to be signed and submitted.
+/// * `suri` - The secret URI (e.g., mnemonic or private key) for signing the extrinsic.
+pub async fn sign_and_submit_extrinsic_with<Xt: Payload>(
+	client: &OnlineClient<SubstrateConfig>,
+	token_metadata: &TokenMetadata,
+	xt: Xt,
+	suri: &str,
+) -> Result<String, Error> {
+	let result = sign_and_submit_extrinsic_events(client, xt, suri).await?;
+	display_extrinsic_events(client, token_metadata, &result)
+}
+
+/// Describes a finalized extrinsic by its hash and events, using the specified token metadata.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `token_metadata` - The metadata of the native token of the chain.
+/// * `result` - The events of the extrinsic.
+pub fn display_extrinsic_events(
+	client: &OnlineClient<SubstrateConfig>,
+	token_metadata: &TokenMetadata,
+	result: &ExtrinsicEvents<SubstrateConfig>,
+) -> Result<String, Error> {
 	// Obtain required metadata and parse events. The following is using existing logic from
 	// `cargo-contract`, also used in calling contracts, due to simplicity and can be refactored in
 	// the future.
 	let metadata = client.metadata();
-	let token_metadata = TokenMetadata::query::<SubstrateConfig>(url).await?;
-	let events = DisplayEvents::from_events::<SubstrateConfig, DefaultEnvironment>(
-		&result, None, &metadata,
-	)?;
 	let events =
-		events.display_events::<DefaultEnvironment>(Verbosity::Default, &token_metadata)?;
+		DisplayEvents::from_events::<SubstrateConfig, DefaultEnvironment>(result, None, &metadata)?;
+	let events = events.display_events::<DefaultEnvironment>(Verbosity::Default, token_metadata)?;
 
 	Ok(format!("Extrinsic Submitted with hash: {:?}\n\n{}", result.extrinsic_hash(), events))
 }
 
-/// Submits a signed extrinsic.
+/// Signs and submits a given extrinsic, returning its events once finalized, such as for reading
+/// the outcome of the extrinsic from them.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `xt` - The (encoded) extrinsic to be signed and submitted.
+/// * `suri` - The secret URI (e.g., mnemonic or private key) for signing the extrinsic.
+pub async fn sign_and_submit_extrinsic_events<Xt: Payload>(
+	client: &OnlineClient<SubstrateConfig>,
+	xt: Xt,
+	suri: &str,
+) -> Result<ExtrinsicEvents<SubstrateConfig>, Error> {
+	sign_and_submit_extrinsic_progress(client, xt, suri, &ExtrinsicOptions::default(), |_| {}).await
+}
+
+/// Signs and submits a given extrinsic, reporting its progress until included as per
+/// `options.wait` and then returning its events. As the events are only known once the extrinsic
+/// is included, an extrinsic which is not to be followed is followed until finalized. Should the
+/// extrinsic fail, the error of the pallet is resolved to its name and documentation. Extrinsics
+/// of chains with Ethereum-style accounts are signed via ECDSA.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `xt` - The (encoded) extrinsic to be signed and submitted.
+/// * `suri` - The secret URI (e.g., mnemonic or private key) for signing the extrinsic.
+/// * `options` - The nonce, tip and mortality of the extrinsic, and how far it is followed.
+/// * `report` - Called with a description of each change in the status of the extrinsic.
+pub async fn sign_and_submit_extrinsic_progress<Xt: Payload>(
+	client: &OnlineClient<SubstrateConfig>,
+	xt: Xt,
+	suri: &str,
+	options: &ExtrinsicOptions,
+	report: impl FnMut(String),
+) -> Result<ExtrinsicEvents<SubstrateConfig>, Error> {
+	let progress = match AccountFormat::detect(&client.metadata()) {
+		AccountFormat::Substrate => {
+			let signer = create_signer(suri)?;
+			let params = options.params(client).await?;
+			client.tx().sign_and_submit_then_watch(&xt, &signer, params).await
+		},
+		AccountFormat::Ethereum => {
+			let extrinsic = sign_ethereum_extrinsic(client, &xt, suri, options).await?;
+			SubmittableExtrinsic::from_bytes(client.clone(), extrinsic)
+				.submit_and_watch()
+				.await
+		},
+	}
+	.map_err(submission_error)?;
+	wait_for_success(progress, options.wait, report).await
+}
+
+/// Signs and submits a given extrinsic, following it only as far as specified by `options.wait`:
+/// returning its hash once accepted into the transaction pool, or otherwise its events once
+/// included.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `xt` - The (encoded) extrinsic to be signed and submitted.
+/// * `suri` - The secret URI (e.g., mnemonic or private key) for signing the extrinsic.
+/// * `options` - The nonce, tip and mortality of the extrinsic, and how far it is followed.
+/// * `report` - Called with a description of each change in the status of the extrinsic.
+pub async fn sign_and_submit_extrinsic_until<Xt: Payload>(
+	client: &OnlineClient<SubstrateConfig>,
+	xt: Xt,
+	suri: &str,
+	options: &ExtrinsicOptions,
+	report: impl FnMut(String),
+) -> Result<Submitted, Error> {
+	if options.wait != WaitFor::None {
+		return sign_and_submit_extrinsic_progress(client, xt, suri, options, report)
+			.await
+			.map(Submitted::Included);
+	}
+	let hash = match AccountFormat::detect(&client.metadata()) {
+		AccountFormat::Substrate => {
+			let signer = create_signer(suri)?;
+			let params = options.params(client).await?;
+			client.tx().sign_and_submit(&xt, &signer, params).await
+		},
+		AccountFormat::Ethereum => {
+			let extrinsic = sign_ethereum_extrinsic(client, &xt, suri, options).await?;
+			SubmittableExtrinsic::from_bytes(client.clone(), extrinsic).submit().await
+		},
+	}
+	.map_err(submission_error)?;
+	Ok(Submitted::Accepted(hash))
+}
+
+/// Submits a signed extrinsic, returning its events once finalized.
 ///
 /// # Arguments
 /// * `client` - The client used to interact with the chain.
 /// * `payload` - The signed payload string to be submitted.
+/// * `report` - Called with a description of each change in the status of the extrinsic.
 pub async fn submit_signed_extrinsic(
 	client: OnlineClient<SubstrateConfig>,
 	payload: String,
-) -> Result<String, Error> {
+	report: impl FnMut(String),
+) -> Result<ExtrinsicEvents<SubstrateConfig>, Error> {
 	let hex_encoded =
 		from_hex(&payload).map_err(|e| Error::CallDataDecodingError(e.to_string()))?;
 	let extrinsic = SubmittableExtrinsic::from_bytes(client, hex_encoded);
-	let result = extrinsic
-		.submit_and_watch()
-		.await
-		.map_err(|e| Error::ExtrinsicSubmissionError(format!("{:?}", e)))?
-		.wait_for_finalized_success()
-		.await
-		.map_err(|e| Error::ExtrinsicSubmissionError(format!("{:?}", e)))?;
-	Ok(format!("{:?}", result.extrinsic_hash()))
+	let progress = extrinsic.submit_and_watch().await.map_err(submission_error)?;
+	wait_for_success(progress, WaitFor::Finalized, report).await
+}
+
+/// Describes the error of a failed extrinsic, resolving any error of a pallet to its name and
+/// documentation.
+///
+/// # Arguments
+/// * `error` - The error dispatching the extrinsic.
+pub fn describe_dispatch_error(error: &DispatchError) -> String {
+	let DispatchError::Module(error) = error else { return error.to_string() };
+	match error.details() {
+		Ok(details) => {
+			let docs = details.variant.docs.iter().filter(|l| !l.is_empty()).cloned();
+			let docs = docs.collect::<Vec<_>>().join(" ");
+			match docs.is_empty() {
+				true => format!("{}::{}", details.pallet.name(), details.variant.name),
+				false => format!("{}::{}: {docs}", details.pallet.name(), details.variant.name),
+			}
+		},
+		Err(_) => error.to_string(),
+	}
+}
+
+// Waits for a submitted extrinsic to be included in the best block, or otherwise finalized,
+// reporting each change in its status, and then returns its events if it succeeded.
+async fn wait_for_success(
+	mut progress: TxProgress<SubstrateConfig, OnlineClient<SubstrateConfig>>,
+	wait: WaitFor,
+	mut report: impl FnMut(String),
+) -> Result<ExtrinsicEvents<SubstrateConfig>, Error> {
+	while let Some(status) = progress.next().await {
+		match status.map_err(submission_error)? {
+			TxStatus::Validated => report("Validated, waiting to be broadcast...".to_string()),
+			TxStatus::Broadcasted { num_peers } =>
+				report(format!("Broadcast to {num_peers} peers, waiting for inclusion...")),
+			TxStatus::NoLongerInBestBlock =>
+				report("No longer in the best block, waiting for inclusion...".to_string()),
+			TxStatus::InBestBlock(block) if wait == WaitFor::Best =>
+				return block.wait_for_success().await.map_err(submission_error),
+			TxStatus::InBestBlock(block) => report(format!(
+				"Included in block {:?}, waiting for finalization...",
+				block.block_hash()
+			)),
+			TxStatus::InFinalizedBlock(block) =>
+				return block.wait_for_success().await.map_err(submission_error),
+			TxStatus::Error { message } |
+			TxStatus::Invalid { message } |
+			TxStatus::Dropped { message } => return Err(Error::ExtrinsicSubmissionError(message)),
+		}
+	}
+	Err(Error::ExtrinsicSubmissionError(
+		"the progress of the extrinsic is no longer reported".to_string(),
+	))
+}
+
+// Maps an error submitting an extrinsic, describing why it failed when dispatched.
+fn submission_error(error: subxt::Error) -> Error {
+	match error {
+		subxt::Error::Runtime(error) => Error::ExtrinsicFailed(describe_dispatch_error(&error)),
+		error => Error::ExtrinsicSubmissionError(format!("{:?}", error)),
+	}
 }
 
 /// Encodes the call data for a given extrinsic into a hexadecimal string.
@@ -150,7 +505,7 @@ impl Payload for CallData {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
 	use super::*;
 	use crate::{find_dispatchable_by_name, parse_chain_metadata, set_up_client};
 	use anyhow::Result;
@@ -169,6 +524,66 @@ mod tests {
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn set_up_cached_client_works() -> Result<()> {
+		let cache = tempfile::tempdir()?;
+		let client = set_up_cached_client(POP_NETWORK_TESTNET_URL, cache.path()).await?;
+		let path = metadata_path(
+			cache.path(),
+			&client.genesis_hash(),
+			client.runtime_version().spec_version,
+		);
+		assert!(path.exists());
+		// The cached metadata is used by subsequent connections.
+		let cached = set_up_cached_client(POP_NETWORK_TESTNET_URL, cache.path()).await?;
+		assert_eq!(cached.metadata().hasher().hash(), client.metadata().hasher().hash());
+		Ok(())
+	}
+
+	#[test]
+	fn clear_cached_metadata_works() -> Result<()> {
+		let cache = tempfile::tempdir()?;
+		let path = metadata_path(cache.path(), &H256::zero(), 1);
+		fs::create_dir_all(path.parent().unwrap())?;
+		fs::write(&path, [0])?;
+		clear_cached_metadata(cache.path())?;
+		assert!(!path.exists());
+		// Clearing an empty cache is a no-op.
+		clear_cached_metadata(cache.path())?;
+		assert!(cache.path().exists());
+		Ok(())
+	}
+
+	#[test]
+	fn submission_error_works() {
+		assert!(matches!(
+			submission_error(subxt::Error::Runtime(DispatchError::BadOrigin)),
+			Error::ExtrinsicFailed(e) if e == DispatchError::BadOrigin.to_string()
+		));
+		assert!(matches!(
+			submission_error(subxt::Error::Other("timeout".into())),
+			Error::ExtrinsicSubmissionError(_)
+		));
+	}
+
+	#[test]
+	fn wait_for_works() {
+		assert_eq!("none".parse::<WaitFor>(), Ok(WaitFor::None));
+		assert_eq!("best".parse::<WaitFor>(), Ok(WaitFor::Best));
+		assert_eq!("finalized".parse::<WaitFor>(), Ok(WaitFor::Finalized));
+		assert!("included".parse::<WaitFor>().is_err());
+		assert_eq!(WaitFor::default().as_ref(), "finalized");
+	}
+
+	#[test]
+	fn metadata_path_works() {
+		let hash = H256::repeat_byte(1);
+		assert_eq!(
+			metadata_path(Path::new("/cache"), &hash, 1_004_001),
+			PathBuf::from(format!("/cache/metadata/0x{}-1004001.scale", "01".repeat(32)))
+		);
+	}
+
 	#[tokio::test]
 	async fn construct_extrinsic_works() -> Result<()> {
 		let client = set_up_client(POP_NETWORK_TESTNET_URL).await?;
@@ -260,4 +675,22 @@ mod tests {
 		assert_eq!(xt.pallet_name(), "Sudo");
 		Ok(())
 	}
+
+	#[tokio::test]
+	async fn construct_sudo_call_data_works() -> Result<()> {
+		let client = set_up_client(POP_NETWORK_TESTNET_URL).await?;
+		let pallets = parse_chain_metadata(&client)?;
+		let remark = find_dispatchable_by_name(&pallets, "System", "remark")?;
+		let xt = construct_extrinsic(remark, vec!["0x11".to_string()])?;
+		let call_data = encode_call_data(&client, &xt)?;
+		assert_eq!(
+			construct_sudo_call_data(&client.metadata(), &call_data)?,
+			encode_call_data(&client, &construct_sudo_extrinsic(xt))?
+		);
+		assert!(matches!(
+			construct_sudo_call_data(&client.metadata(), "wrongcalldata"),
+			Err(Error::CallDataDecodingError(..))
+		));
+		Ok(())
+	}
 }
@@ -0,0 +1,312 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{call::CallData, errors::Error};
+use pop_common::create_signer;
+use serde::{Deserialize, Serialize};
+use sp_core::{
+	blake2_256,
+	bytes::{from_hex, to_hex},
+};
+use std::str::FromStr;
+use subxt::{
+	client::OfflineClientT,
+	config::{Config, DefaultExtrinsicParamsBuilder, ExtrinsicParams, ExtrinsicParamsEncoder},
+	tx::PartialExtrinsic,
+	utils::{AccountId32, MultiAddress, MultiSignature},
+	OnlineClient, SubstrateConfig,
+};
+use subxt_signer::sr25519::{verify, PublicKey, Signature};
+
+/// An extrinsic awaiting a signature, such as from an account whose keys are only available on
+/// an offline machine.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedPayload {
+	/// The genesis hash of the chain on which the extrinsic is to be submitted.
+	pub genesis_hash: String,
+	/// The spec version of the runtime for which the extrinsic was created.
+	pub spec_version: u32,
+	/// The transaction version of the runtime for which the extrinsic was created.
+	pub transaction_version: u32,
+	/// The address of the account required to sign the extrinsic.
+	pub signer: String,
+	/// The nonce of the signer.
+	pub nonce: u64,
+	/// The SCALE-encoded call data of the extrinsic, as hex.
+	pub call_data: String,
+	/// The SCALE-encoded parameters included within the extrinsic (e.g. the nonce), as hex.
+	pub extra: String,
+	/// The SCALE-encoded parameters which are signed but not included within the extrinsic (e.g.
+	/// the genesis hash), as hex.
+	pub additional: String,
+	/// The bytes to be signed, as hex, which are derived from the call data and parameters.
+	pub signer_payload: String,
+}
+
+impl UnsignedPayload {
+	// The bytes to be signed, derived from the call data and parameters, failing should they not
+	// match the signer payload so that only the call data displayed is ever signed.
+	fn verified_signer_payload(&self) -> Result<Vec<u8>, Error> {
+		let signer_payload = signer_payload(
+			&decode_hex(&self.call_data)?,
+			&decode_hex(&self.extra)?,
+			&decode_hex(&self.additional)?,
+		);
+		if signer_payload != decode_hex(&self.signer_payload)? {
+			return Err(Error::OfflineSigningError(
+				"the signer payload does not match the call data and parameters".into(),
+			));
+		}
+		Ok(signer_payload)
+	}
+}
+
+/// An extrinsic signed offline, ready to be submitted.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedPayload {
+	/// The extrinsic which was signed.
+	#[serde(flatten)]
+	pub payload: UnsignedPayload,
+	/// The sr25519 signature of the signer payload, as hex.
+	pub signature: String,
+}
+
+/// Creates the payload of an extrinsic to be signed offline by the specified account, using its
+/// current nonce.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `call_data` - The SCALE-encoded call data of the extrinsic.
+/// * `signer` - The address of the account which is to sign the extrinsic.
+pub async fn create_payload(
+	client: &OnlineClient<SubstrateConfig>,
+	call_data: Vec<u8>,
+	signer: &str,
+) -> Result<UnsignedPayload, Error> {
+	let account = parse_account(signer)?;
+	let nonce = client.tx().account_nonce(&account).await?;
+	let partial = partial_extrinsic(client, &call_data, nonce)?;
+	let (extra, additional) = encoded_params(client, nonce)?;
+	let version = client.runtime_version();
+	Ok(UnsignedPayload {
+		genesis_hash: format!("{:?}", client.genesis_hash()),
+		spec_version: version.spec_version,
+		transaction_version: version.transaction_version,
+		signer: account.to_string(),
+		nonce,
+		call_data: to_hex(&call_data, false),
+		extra: to_hex(&extra, false),
+		additional: to_hex(&additional, false),
+		signer_payload: to_hex(&partial.signer_payload(), false),
+	})
+}
+
+/// Signs the payload of an extrinsic, which requires no connection to the chain. The signer
+/// payload is derived from the call data and parameters beforehand, so that a payload which has
+/// been tampered with cannot authorise a call other than that displayed.
+///
+/// # Arguments
+/// * `payload` - The payload of the extrinsic.
+/// * `suri` - The secret URI (e.g., mnemonic or private key) of the signer of the payload.
+pub fn sign_payload(payload: UnsignedPayload, suri: &str) -> Result<SignedPayload, Error> {
+	let keypair = create_signer(suri)?;
+	let account = keypair.public_key().to_account_id();
+	if account != parse_account(&payload.signer)? {
+		return Err(Error::OfflineSigningError(format!(
+			"the payload must be signed by {}, rather than {account}",
+			payload.signer
+		)));
+	}
+	let signature = keypair.sign(&payload.verified_signer_payload()?);
+	Ok(SignedPayload { payload, signature: to_hex(&signature.0, false) })
+}
+
+/// Assembles an extrinsic signed offline, returning it hex-encoded so that it can be submitted
+/// via [`submit_signed_extrinsic`](super::submit_signed_extrinsic). The payload is checked
+/// against the current state of the chain beforehand, as it may have changed since the payload
+/// was created.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `signed` - The signed payload.
+pub fn signed_extrinsic(
+	client: &OnlineClient<SubstrateConfig>,
+	signed: &SignedPayload,
+) -> Result<String, Error> {
+	let SignedPayload { payload, signature } = signed;
+	if payload.genesis_hash != format!("{:?}", client.genesis_hash()) {
+		return Err(Error::OfflineSigningError(
+			"the payload was created for a different chain".into(),
+		));
+	}
+	let version = client.runtime_version();
+	if (payload.spec_version, payload.transaction_version) !=
+		(version.spec_version, version.transaction_version)
+	{
+		return Err(Error::OfflineSigningError(format!(
+			"the runtime has been upgraded since the payload was created (spec version {} rather than {}), so it must be created and signed again",
+			version.spec_version, payload.spec_version
+		)));
+	}
+	let partial = partial_extrinsic(client, &decode_hex(&payload.call_data)?, payload.nonce)?;
+	let signer_payload = partial.signer_payload();
+	if to_hex(&signer_payload, false) != payload.signer_payload {
+		return Err(Error::OfflineSigningError(
+			"the signer payload does not match the call data and nonce".into(),
+		));
+	}
+	let signature: [u8; 64] = decode_hex(signature)?
+		.try_into()
+		.map_err(|_| Error::OfflineSigningError("the signature must be 64 bytes".into()))?;
+	let account = parse_account(&payload.signer)?;
+	if !verify(&Signature(signature), &signer_payload, &PublicKey(account.0)) {
+		return Err(Error::OfflineSigningError(format!(
+			"the signature is not valid for {}",
+			payload.signer
+		)));
+	}
+	let extrinsic = partial.sign_with_address_and_signature(
+		&MultiAddress::Id(account),
+		&MultiSignature::Sr25519(signature),
+	);
+	Ok(to_hex(extrinsic.encoded(), false))
+}
+
+// Creates an immortal extrinsic without a tip, awaiting its signature.
+fn partial_extrinsic(
+	client: &OnlineClient<SubstrateConfig>,
+	call_data: &[u8],
+	nonce: u64,
+) -> Result<PartialExtrinsic<SubstrateConfig, OnlineClient<SubstrateConfig>>, Error> {
+	let params = DefaultExtrinsicParamsBuilder::<SubstrateConfig>::new().nonce(nonce).build();
+	client
+		.tx()
+		.create_partial_signed_offline(&CallData::new(call_data.to_vec()), params)
+		.map_err(|e| Error::OfflineSigningError(e.to_string()))
+}
+
+// The encoded extra and additional parameters of an immortal extrinsic without a tip.
+fn encoded_params(
+	client: &OnlineClient<SubstrateConfig>,
+	nonce: u64,
+) -> Result<(Vec<u8>, Vec<u8>), Error> {
+	let params = DefaultExtrinsicParamsBuilder::<SubstrateConfig>::new().nonce(nonce).build();
+	let params = <<SubstrateConfig as Config>::ExtrinsicParams as ExtrinsicParams<_>>::new(
+		&client.client_state(),
+		params,
+	)
+	.map_err(|e| Error::OfflineSigningError(e.to_string()))?;
+	let (mut extra, mut additional) = (Vec::new(), Vec::new());
+	params.encode_extra_to(&mut extra);
+	params.encode_additional_to(&mut additional);
+	Ok((extra, additional))
+}
+
+// The bytes to be signed for an extrinsic, which are hashed when longer than 256 bytes.
+fn signer_payload(call_data: &[u8], extra: &[u8], additional: &[u8]) -> Vec<u8> {
+	let bytes = [call_data, extra, additional].concat();
+	match bytes.len() > 256 {
+		true => blake2_256(&bytes).to_vec(),
+		false => bytes,
+	}
+}
+
+fn parse_account(address: &str) -> Result<AccountId32, Error> {
+	AccountId32::from_str(address)
+		.map_err(|e| Error::OfflineSigningError(format!("invalid signer {address}: {e}")))
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, Error> {
+	from_hex(value).map_err(|e| Error::OfflineSigningError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+
+	const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+	fn payload() -> UnsignedPayload {
+		UnsignedPayload {
+			genesis_hash: format!("0x{}", "01".repeat(32)),
+			spec_version: 1_000,
+			transaction_version: 1,
+			signer: ALICE.into(),
+			nonce: 2,
+			call_data: "0x00000411".into(),
+			extra: "0x0008".into(),
+			additional: "0x0000".into(),
+			signer_payload: "0x0000041100080000".into(),
+		}
+	}
+
+	#[test]
+	fn signer_payload_works() {
+		assert_eq!(signer_payload(&[0, 0, 4, 17], &[0, 8], &[0, 0]), vec![0, 0, 4, 17, 0, 8, 0, 0]);
+		// Longer payloads are hashed.
+		let call_data = [1u8; 256];
+		assert_eq!(
+			signer_payload(&call_data, &[0, 8], &[0, 0]),
+			blake2_256(&[&call_data[..], &[0, 8, 0, 0]].concat()).to_vec()
+		);
+	}
+
+	#[test]
+	fn sign_payload_fails_with_mismatched_signer_payload() {
+		// A signer payload of another call, which does not match the call data displayed.
+		for payload in [
+			UnsignedPayload { signer_payload: "0x0000041200080000".into(), ..payload() },
+			UnsignedPayload { call_data: "0x00000412".into(), ..payload() },
+			UnsignedPayload { extra: "0x0004".into(), ..payload() },
+		] {
+			assert!(matches!(
+				sign_payload(payload, "//Alice"),
+				Err(Error::OfflineSigningError(message))
+					if message == "the signer payload does not match the call data and parameters"
+			));
+		}
+	}
+
+	#[test]
+	fn sign_payload_works() -> Result<()> {
+		let signed = sign_payload(payload(), "//Alice")?;
+		assert_eq!(signed.payload, payload());
+		let signature: [u8; 64] = from_hex(&signed.signature)?.try_into().unwrap();
+		assert!(verify(
+			&Signature(signature),
+			from_hex(&payload().signer_payload)?,
+			&PublicKey(parse_account(ALICE)?.0)
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn sign_payload_fails_with_wrong_signer() {
+		assert!(matches!(
+			sign_payload(payload(), "//Bob"),
+			Err(Error::OfflineSigningError(message)) if message.contains(ALICE)
+		));
+		assert!(matches!(
+			sign_payload(UnsignedPayload { signer: "wrong".into(), ..payload() }, "//Alice"),
+			Err(Error::OfflineSigningError(message)) if message.contains("invalid signer wrong")
+		));
+	}
+
+	#[test]
+	fn payloads_are_serialized_as_camel_case() -> Result<()> {
+		let signed = SignedPayload { payload: payload(), signature: "0x00".into() };
+		let json = serde_json::to_value(&signed)?;
+		assert_eq!(json["genesisHash"], payload().genesis_hash);
+		assert_eq!(json["signerPayload"], payload().signer_payload);
+		assert_eq!(json["signature"], "0x00");
+		assert_eq!(serde_json::from_value::<SignedPayload>(json)?, signed);
+		// An unsigned payload is read from a signed payload.
+		assert_eq!(
+			serde_json::from_value::<UnsignedPayload>(serde_json::to_value(&signed)?)?,
+			payload()
+		);
+		Ok(())
+	}
+}
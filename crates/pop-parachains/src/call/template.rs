@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::errors::Error;
+use pop_common::config::{config_dir, PROJECT_CONFIG_FILE_NAME};
+use serde::{Deserialize, Serialize};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+/// A call saved under a name (e.g. `transfer-to-bob`), such that it can be re-run later, e.g.
+/// within `.pop/calls/transfer-to-bob.toml`:
+///
+/// ```toml
+/// pallet = "Balances"
+/// function = "transfer_keep_alive"
+/// args = ["Id(5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty)", "1000"]
+/// url = "ws://localhost:9944/"
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CallTemplate {
+	/// The pallet containing the dispatchable function.
+	pub pallet: String,
+	/// The name of the dispatchable function.
+	pub function: String,
+	/// The arguments of the function, encoded as strings.
+	#[serde(default)]
+	pub args: Vec<String>,
+	/// The endpoint of the chain, if not to be specified when the call is re-run.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub url: Option<String>,
+	/// Whether the call is dispatched with `Root` origin, via sudo.
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub sudo: bool,
+}
+
+/// The call templates available, each stored as a TOML file named after the template.
+///
+/// Templates are saved to the first of the directories, whilst loaded from the first directory
+/// containing the template.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallTemplates {
+	dirs: Vec<PathBuf>,
+}
+
+impl CallTemplates {
+	/// The templates within the specified directories.
+	///
+	/// # Arguments
+	/// * `dirs` - The directories containing the templates, in order of precedence.
+	pub fn new(dirs: Vec<PathBuf>) -> Self {
+		Self { dirs }
+	}
+
+	/// The templates of a project, stored within its `.pop/calls` directory should it contain a
+	/// `pop.toml` file, followed by those of the user within their configuration directory.
+	///
+	/// # Arguments
+	/// * `dir` - The directory of the project, such as the current directory.
+	pub fn open(dir: &Path) -> Result<Self, Error> {
+		let mut dirs = Vec::new();
+		if dir.join(PROJECT_CONFIG_FILE_NAME).exists() {
+			dirs.push(dir.join(".pop").join("calls"));
+		}
+		dirs.push(config_dir()?.join("calls"));
+		Ok(Self::new(dirs))
+	}
+
+	/// Saves a template, replacing any template of the same name, returning the path of the file
+	/// to which it was saved.
+	///
+	/// # Arguments
+	/// * `name` - The name of the template.
+	/// * `template` - The template.
+	pub fn save(&self, name: &str, template: &CallTemplate) -> Result<PathBuf, Error> {
+		let dir = self
+			.dirs
+			.first()
+			.ok_or_else(|| Error::CallTemplateError("no directory to save to".into()))?;
+		if !is_valid_name(name) {
+			return Err(Error::CallTemplateError(format!(
+				"invalid name `{name}`: only letters, numbers, `-` and `_` are supported"
+			)));
+		}
+		let contents = toml_edit::ser::to_string(template)
+			.map_err(|e| Error::CallTemplateError(e.to_string()))?;
+		fs::create_dir_all(dir)?;
+		let path = dir.join(format!("{name}.toml"));
+		fs::write(&path, contents)?;
+		Ok(path)
+	}
+
+	/// Loads the template with the specified name.
+	///
+	/// # Arguments
+	/// * `name` - The name of the template.
+	pub fn load(&self, name: &str) -> Result<CallTemplate, Error> {
+		let path = is_valid_name(name)
+			.then(|| {
+				self.dirs
+					.iter()
+					.map(|dir| dir.join(format!("{name}.toml")))
+					.find(|p| p.exists())
+			})
+			.flatten()
+			.ok_or_else(|| Error::CallTemplateError(format!("no template named `{name}`")))?;
+		toml_edit::de::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+			Error::CallTemplateError(format!("unable to parse {}: {e}", path.display()))
+		})
+	}
+}
+
+// Whether a name is valid for a template, as it determines the name of its file.
+fn is_valid_name(name: &str) -> bool {
+	!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+
+	fn template() -> CallTemplate {
+		CallTemplate {
+			pallet: "System".into(),
+			function: "remark".into(),
+			args: vec!["0x11".into()],
+			url: Some("ws://localhost:9944/".into()),
+			sudo: false,
+		}
+	}
+
+	#[test]
+	fn call_templates_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let (project, user) = (temp_dir.path().join("project"), temp_dir.path().join("user"));
+		let templates = CallTemplates::new(vec![project.clone(), user.clone()]);
+		assert!(matches!(templates.load("remark"), Err(Error::CallTemplateError(..))));
+		let path = templates.save("remark", &template())?;
+		assert_eq!(path, project.join("remark.toml"));
+		assert_eq!(
+			fs::read_to_string(&path)?,
+			"pallet = \"System\"\nfunction = \"remark\"\nargs = [\"0x11\"]\nurl = \"ws://localhost:9944/\"\n"
+		);
+		assert_eq!(templates.load("remark")?, template());
+		// Templates are loaded from the first directory containing them.
+		let sudo = CallTemplate { sudo: true, ..template() };
+		CallTemplates::new(vec![user]).save("sudo-remark", &sudo)?;
+		assert_eq!(templates.load("sudo-remark")?, sudo);
+		for name in ["", "../remark", "remark.toml"] {
+			assert!(matches!(templates.save(name, &template()), Err(Error::CallTemplateError(..))));
+			assert!(matches!(templates.load(name), Err(Error::CallTemplateError(..))));
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn open_works() -> Result<()> {
+		let temp_dir = tempfile::tempdir()?;
+		let user = config_dir()?.join("calls");
+		assert_eq!(CallTemplates::open(temp_dir.path())?.dirs, std::slice::from_ref(&user));
+		fs::write(temp_dir.path().join(PROJECT_CONFIG_FILE_NAME), "")?;
+		assert_eq!(
+			CallTemplates::open(temp_dir.path())?.dirs,
+			[temp_dir.path().join(".pop").join("calls"), user]
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,460 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{call::cached_client, errors::Error};
+use pop_common::call::TokenMetadata;
+use std::{
+	collections::HashMap,
+	path::{Path, PathBuf},
+	sync::{Arc, RwLock},
+	time::Duration,
+};
+use subxt::{
+	backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+	lightclient::LightClient,
+	OnlineClient, SubstrateConfig,
+};
+use tokio::{sync::Mutex, task::JoinHandle};
+
+/// The number of decimals of the native token, if not specified by the chain.
+const DEFAULT_TOKEN_DECIMALS: u64 = 12;
+/// The symbol of the native token, if not specified by the chain.
+const DEFAULT_TOKEN_SYMBOL: &str = "UNIT";
+
+/// A connection to a node, sharing a single client across operations such as fetching metadata,
+/// dry-running, estimating fees and submitting calls. Clones share the same connection.
+///
+/// A connection may fail over between the endpoints of several nodes of the same chain, with the
+/// next endpoint used whenever the node currently connected to is unreachable.
+#[derive(Clone)]
+pub struct Connection {
+	source: Source,
+	inner: Arc<RwLock<Inner>>,
+	// The native token of the chain, overriding any specified by the chain.
+	token: NativeToken,
+}
+
+/// The native token of a chain, overriding the properties specified by the chain itself.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NativeToken {
+	/// The symbol of the token.
+	pub symbol: Option<String>,
+	/// The number of decimals of the token.
+	pub decimals: Option<u8>,
+}
+
+// How a connection to a chain is established.
+#[derive(Clone)]
+enum Source {
+	// Via the RPC endpoints of nodes, in order of preference, along with any cache of the
+	// metadata of runtimes.
+	Rpc { urls: Vec<String>, cache: Option<PathBuf> },
+	// Via an embedded light client, along with the chain spec of the relay chain when connecting
+	// to a parachain.
+	Light { chain_spec: String, relay_chain_spec: Option<String> },
+}
+
+struct Inner {
+	client: OnlineClient<SubstrateConfig>,
+	methods: LegacyRpcMethods<SubstrateConfig>,
+	// The index of the endpoint connected to, if connected via RPC.
+	endpoint: usize,
+	// The light client, which must be kept alive for as long as the connection is used.
+	_light_client: Option<LightClient>,
+}
+
+impl Connection {
+	/// Connects to a node.
+	///
+	/// # Arguments
+	/// * `url` - Endpoint of the node.
+	/// * `cache` - The location of any cache of the metadata of runtimes, which is then reused by
+	///   connections to the same runtime.
+	pub async fn connect(url: &str, cache: Option<&Path>) -> Result<Self, Error> {
+		Self::connect_any(&[url], cache).await
+	}
+
+	/// Connects to the first reachable node of a chain, failing over to the next node whenever
+	/// the node connected to becomes unreachable.
+	///
+	/// # Arguments
+	/// * `urls` - Endpoints of nodes of the same chain, in order of preference.
+	/// * `cache` - The location of any cache of the metadata of runtimes, which is then reused by
+	///   connections to the same runtime.
+	pub async fn connect_any(urls: &[&str], cache: Option<&Path>) -> Result<Self, Error> {
+		if urls.is_empty() {
+			return Err(Error::ConnectionFailure("no endpoints specified".into()));
+		}
+		let urls = urls.iter().map(|url| url.to_string()).collect();
+		Self::establish(Source::Rpc { urls, cache: cache.map(Path::to_path_buf) }).await
+	}
+
+	/// Connects to a chain via an embedded light client, which syncs with the network using the
+	/// bootnodes of the chain spec rather than relying on a trusted endpoint.
+	///
+	/// # Arguments
+	/// * `chain_spec` - The chain spec of the chain.
+	/// * `relay_chain_spec` - The chain spec of the relay chain, if the chain is a parachain.
+	pub async fn light(
+		chain_spec: String,
+		relay_chain_spec: Option<String>,
+	) -> Result<Self, Error> {
+		Self::establish(Source::Light { chain_spec, relay_chain_spec }).await
+	}
+
+	async fn establish(source: Source) -> Result<Self, Error> {
+		let inner = Inner::connect(&source, 0).await?;
+		Ok(Self { source, inner: Arc::new(RwLock::new(inner)), token: NativeToken::default() })
+	}
+
+	/// Uses the specified native token rather than that specified by the properties of the
+	/// chain, e.g. as configured for a named chain.
+	///
+	/// # Arguments
+	/// * `token` - The native token of the chain.
+	pub fn with_token(self, token: NativeToken) -> Self {
+		Self { token, ..self }
+	}
+
+	/// Endpoint of the node currently connected to, unless connected via a light client.
+	pub fn url(&self) -> Option<String> {
+		match &self.source {
+			Source::Rpc { urls, .. } => {
+				let endpoint = self.inner.read().expect("lock is not poisoned").endpoint;
+				Some(urls[endpoint].clone())
+			},
+			Source::Light { .. } => None,
+		}
+	}
+
+	/// The client used to interact with the chain.
+	pub fn client(&self) -> OnlineClient<SubstrateConfig> {
+		self.inner.read().expect("lock is not poisoned").client.clone()
+	}
+
+	/// The legacy RPC methods of the node, for queries not supported by the client.
+	pub fn methods(&self) -> LegacyRpcMethods<SubstrateConfig> {
+		self.inner.read().expect("lock is not poisoned").methods.clone()
+	}
+
+	/// Whether the node is still reachable via the connection.
+	pub async fn is_alive(&self) -> bool {
+		self.methods().system_health().await.is_ok()
+	}
+
+	/// The metadata of the native token of the chain.
+	pub async fn token_metadata(&self) -> Result<TokenMetadata, Error> {
+		if let NativeToken { symbol: Some(symbol), decimals: Some(decimals) } = &self.token {
+			return Ok(TokenMetadata { token_decimals: *decimals as usize, symbol: symbol.clone() });
+		}
+		let properties = self.methods().system_properties().await?;
+		Ok(TokenMetadata {
+			token_decimals: self.token.decimals.map(u64::from).unwrap_or_else(|| {
+				properties
+					.get("tokenDecimals")
+					.and_then(|d| d.as_u64())
+					.unwrap_or(DEFAULT_TOKEN_DECIMALS)
+			}) as usize,
+			symbol: self.token.symbol.clone().unwrap_or_else(|| {
+				properties
+					.get("tokenSymbol")
+					.and_then(|s| s.as_str())
+					.unwrap_or(DEFAULT_TOKEN_SYMBOL)
+					.to_string()
+			}),
+		})
+	}
+
+	/// The prefix of the SS58 addresses of the chain, as specified by the `SS58Prefix` constant of
+	/// the runtime, if any.
+	pub fn ss58_prefix(&self) -> Option<u16> {
+		self.client()
+			.constants()
+			.at(&subxt::dynamic::constant("System", "SS58Prefix"))
+			.ok()
+			.and_then(|c| c.to_value().ok())
+			.and_then(|v| v.as_u128())
+			.map(|p| p as u16)
+	}
+
+	/// Re-establishes the connection, which is then used by all clones. When several endpoints
+	/// were specified, the next endpoint is tried first, so that an unreachable node is failed
+	/// over from.
+	pub async fn reconnect(&self) -> Result<(), Error> {
+		let next = self.inner.read().expect("lock is not poisoned").endpoint + 1;
+		let inner = Inner::connect(&self.source, next).await?;
+		*self.inner.write().expect("lock is not poisoned") = inner;
+		Ok(())
+	}
+
+	/// Re-establishes the connection if the node is no longer reachable via it.
+	pub async fn ensure_alive(&self) -> Result<(), Error> {
+		match self.is_alive().await {
+			true => Ok(()),
+			false => self.reconnect().await,
+		}
+	}
+
+	/// Keeps the connection alive in the background, by checking it at the specified interval and
+	/// reconnecting whenever it is lost, until the returned handle is dropped.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between checks.
+	pub fn keep_alive(&self, interval: Duration) -> KeepAlive {
+		let connection = self.clone();
+		KeepAlive(tokio::spawn(async move {
+			loop {
+				tokio::time::sleep(interval).await;
+				// A failure to reconnect is retried at the next interval.
+				let _ = connection.ensure_alive().await;
+			}
+		}))
+	}
+}
+
+impl Inner {
+	// Connects via the source, starting from the specified endpoint when several were specified
+	// and trying each in turn until one is reachable.
+	async fn connect(source: &Source, start: usize) -> Result<Self, Error> {
+		match source {
+			Source::Rpc { urls, cache } => {
+				let mut error = None;
+				for endpoint in (0..urls.len()).map(|i| (start + i) % urls.len()) {
+					match Self::connect_rpc(&urls[endpoint], cache.as_deref()).await {
+						Ok(inner) => return Ok(Self { endpoint, ..inner }),
+						Err(e) => error = Some(e),
+					}
+				}
+				Err(error.expect("at least one endpoint is specified"))
+			},
+			Source::Light { chain_spec, relay_chain_spec } =>
+				Self::connect_light(chain_spec, relay_chain_spec.as_deref()).await,
+		}
+	}
+
+	async fn connect_rpc(url: &str, cache: Option<&Path>) -> Result<Self, Error> {
+		let rpc = RpcClient::from_url(url)
+			.await
+			.map_err(|e| Error::ConnectionFailure(e.to_string()))?;
+		Self::with_rpc(rpc, cache, None).await
+	}
+
+	async fn connect_light(
+		chain_spec: &str,
+		relay_chain_spec: Option<&str>,
+	) -> Result<Self, Error> {
+		let light_error =
+			|e: subxt::lightclient::LightClientError| Error::LightClientError(e.to_string());
+		let (light_client, rpc) = match relay_chain_spec {
+			Some(relay_chain_spec) => {
+				let (light_client, _) =
+					LightClient::relay_chain(relay_chain_spec).map_err(light_error)?;
+				let rpc = light_client.parachain(chain_spec).map_err(light_error)?;
+				(light_client, rpc)
+			},
+			None => LightClient::relay_chain(chain_spec).map_err(light_error)?,
+		};
+		Self::with_rpc(RpcClient::new(rpc), None, Some(light_client)).await
+	}
+
+	async fn with_rpc(
+		rpc: RpcClient,
+		cache: Option<&Path>,
+		light_client: Option<LightClient>,
+	) -> Result<Self, Error> {
+		let client = match cache {
+			Some(cache) => cached_client(rpc.clone(), cache).await?,
+			None => OnlineClient::<SubstrateConfig>::from_rpc_client(rpc.clone())
+				.await
+				.map_err(|e| Error::ConnectionFailure(e.to_string()))?,
+		};
+		Ok(Self {
+			client,
+			methods: LegacyRpcMethods::new(rpc),
+			endpoint: 0,
+			_light_client: light_client,
+		})
+	}
+}
+
+/// Keeps a connection alive until dropped.
+pub struct KeepAlive(JoinHandle<()>);
+
+impl Drop for KeepAlive {
+	fn drop(&mut self) {
+		self.0.abort();
+	}
+}
+
+/// Connections to nodes by endpoint, so that each node is only connected to once, e.g. when
+/// following a cross-chain message across chains. Clones share the same connections.
+#[derive(Clone, Default)]
+pub struct Connections(Arc<Mutex<HashMap<String, Connection>>>);
+
+impl Connections {
+	/// The connection to a node, connecting to it on first use.
+	///
+	/// # Arguments
+	/// * `url` - Endpoint of the node.
+	pub async fn get(&self, url: &str) -> Result<Connection, Error> {
+		let mut connections = self.0.lock().await;
+		if let Some(connection) = connections.get(url) {
+			return Ok(connection.clone());
+		}
+		let connection = Connection::connect(url, None).await?;
+		connections.insert(url.to_string(), connection.clone());
+		Ok(connection)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::call::tests::POP_NETWORK_TESTNET_URL;
+	use anyhow::Result;
+	use serde_json::value::RawValue;
+	use subxt::{
+		backend::rpc::{RawRpcFuture, RawRpcSubscription, RpcClientT},
+		error::RpcError,
+	};
+
+	// An RPC client whose connection to the node has been dropped.
+	struct Dropped;
+
+	impl RpcClientT for Dropped {
+		fn request_raw<'a>(
+			&'a self,
+			_method: &'a str,
+			_params: Option<Box<RawValue>>,
+		) -> RawRpcFuture<'a, Box<RawValue>> {
+			Box::pin(async { Err(RpcError::SubscriptionDropped) })
+		}
+
+		fn subscribe_raw<'a>(
+			&'a self,
+			_sub: &'a str,
+			_params: Option<Box<RawValue>>,
+			_unsub: &'a str,
+		) -> RawRpcFuture<'a, RawRpcSubscription> {
+			Box::pin(async { Err(RpcError::SubscriptionDropped) })
+		}
+	}
+
+	// Drops the connection, as though the node connected to became unreachable.
+	fn drop_connection(connection: &Connection) {
+		connection.inner.write().expect("lock is not poisoned").methods =
+			LegacyRpcMethods::new(RpcClient::new(Dropped));
+	}
+
+	#[tokio::test]
+	async fn connect_fails_with_invalid_url() {
+		assert!(matches!(
+			Connection::connect("wss://wronguri.xyz", None).await,
+			Err(Error::ConnectionFailure(_))
+		));
+		assert!(matches!(
+			Connections::default().get("wss://wronguri.xyz").await,
+			Err(Error::ConnectionFailure(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn connect_any_fails_over() -> Result<()> {
+		assert!(matches!(
+			Connection::connect_any(&[], None).await,
+			Err(Error::ConnectionFailure(message)) if message == "no endpoints specified"
+		));
+		assert!(matches!(
+			Connection::connect_any(&["wss://wronguri.xyz", "wss://wronguri.abc"], None).await,
+			Err(Error::ConnectionFailure(_))
+		));
+		// Unreachable endpoints are skipped, including when reconnecting.
+		let connection =
+			Connection::connect_any(&["wss://wronguri.xyz", POP_NETWORK_TESTNET_URL], None).await?;
+		assert_eq!(connection.url().as_deref(), Some(POP_NETWORK_TESTNET_URL));
+		connection.reconnect().await?;
+		assert_eq!(connection.url().as_deref(), Some(POP_NETWORK_TESTNET_URL));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn token_can_be_overridden() -> Result<()> {
+		let connection = Connection::connect(POP_NETWORK_TESTNET_URL, None).await?;
+		assert_eq!(connection.ss58_prefix(), Some(0));
+		let token = NativeToken { symbol: Some("POP".into()), decimals: None };
+		let token_metadata = connection.with_token(token).token_metadata().await?;
+		assert_eq!(token_metadata.symbol, "POP");
+		assert_eq!(token_metadata.token_decimals, 10);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn light_fails_with_invalid_chain_spec() {
+		assert!(matches!(
+			Connection::light("{}".into(), None).await,
+			Err(Error::LightClientError(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn connections_are_reused() -> Result<()> {
+		let connections = Connections::default();
+		let connection = connections.get(POP_NETWORK_TESTNET_URL).await?;
+		assert_eq!(connection.url().as_deref(), Some(POP_NETWORK_TESTNET_URL));
+		assert!(connection.is_alive().await);
+		// Clones share the connection, including once reconnected.
+		let shared = connections.get(POP_NETWORK_TESTNET_URL).await?;
+		assert!(Arc::ptr_eq(&connection.inner, &shared.inner));
+		shared.reconnect().await?;
+		connection.ensure_alive().await?;
+		assert_eq!(connection.client().genesis_hash(), shared.client().genesis_hash());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn ensure_alive_reconnects_dropped_connection() -> Result<()> {
+		let connection = Connection::connect(POP_NETWORK_TESTNET_URL, None).await?;
+		let shared = connection.clone();
+		drop_connection(&connection);
+		assert!(!shared.is_alive().await);
+		connection.ensure_alive().await?;
+		// Clones use the re-established connection.
+		assert!(shared.is_alive().await);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn keep_alive_reconnects_until_dropped() -> Result<()> {
+		let connection = Connection::connect(POP_NETWORK_TESTNET_URL, None).await?;
+		let keep_alive = connection.keep_alive(Duration::from_millis(100));
+		drop_connection(&connection);
+		tokio::time::timeout(Duration::from_secs(60), async {
+			while !connection.is_alive().await {
+				tokio::time::sleep(Duration::from_millis(100)).await;
+			}
+		})
+		.await?;
+		// The connection is no longer checked once the handle is dropped.
+		drop(keep_alive);
+		drop_connection(&connection);
+		tokio::time::sleep(Duration::from_millis(500)).await;
+		assert!(!connection.is_alive().await);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn cached_metadata_is_reused() -> Result<()> {
+		let cache = tempfile::tempdir()?;
+		let connection = Connection::connect(POP_NETWORK_TESTNET_URL, Some(cache.path())).await?;
+		let cached: Vec<_> = std::fs::read_dir(cache.path().join("metadata"))?.collect();
+		assert_eq!(cached.len(), 1);
+		// Reconnecting uses the cached metadata of the runtime rather than fetching it again.
+		connection.reconnect().await?;
+		assert_eq!(std::fs::read_dir(cache.path().join("metadata"))?.count(), 1);
+		let fetched = Connection::connect(POP_NETWORK_TESTNET_URL, None).await?;
+		assert_eq!(
+			connection.client().metadata().hasher().hash(),
+			fetched.client().metadata().hasher().hash()
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,373 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{errors::Error, DynamicPayload};
+use scale_value::{Composite, ValueDef};
+use strum_macros::{AsRefStr, EnumString};
+use subxt::{
+	dynamic::{self, Value},
+	OnlineClient, SubstrateConfig,
+};
+
+/// The mask of a complete core, i.e. all 80 of its parts.
+pub const COMPLETE_MASK: [u8; 10] = [0xFF; 10];
+
+/// A region of bulk coretime, i.e. the parts of a core from when the region begins.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RegionId {
+	/// The timeslice from which the region begins.
+	pub begin: u32,
+	/// The index of the core.
+	pub core: u16,
+	/// The parts of the core within the region.
+	pub mask: [u8; 10],
+}
+
+impl RegionId {
+	fn value(&self) -> Value {
+		Value::named_composite([
+			("begin", Value::u128(self.begin.into())),
+			("core", Value::u128(self.core.into())),
+			("mask", Value::unnamed_composite([Value::from_bytes(self.mask)])),
+		])
+	}
+}
+
+/// Whether the assignment of a region is final, or may still be changed.
+#[derive(AsRefStr, Clone, Copy, Debug, Default, EnumString, Eq, PartialEq)]
+pub enum Finality {
+	/// The region may be reassigned, but is not eligible for renewal.
+	Provisional,
+	/// The region may not be reassigned, but is eligible for renewal.
+	#[default]
+	Final,
+}
+
+/// The current sale of bulk coretime.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Sale {
+	/// The relay chain block from which the sale starts.
+	pub sale_start: u32,
+	/// The length of the leadin period, in relay chain blocks, during which the price decreases.
+	pub leadin_length: u32,
+	/// The price of a core at the end of the leadin period.
+	pub end_price: u128,
+	/// The timeslice from which the regions being sold begin.
+	pub region_begin: u32,
+	/// The timeslice at which the regions being sold end.
+	pub region_end: u32,
+	/// The number of cores offered.
+	pub cores_offered: u16,
+	/// The number of cores sold.
+	pub cores_sold: u16,
+}
+
+/// A renewal of a core which is available to a parachain.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Renewal {
+	/// The index of the core.
+	pub core: u16,
+	/// The timeslice from which the renewed region begins, before which it must be renewed.
+	pub when: u32,
+	/// The price of the renewal.
+	pub price: u128,
+}
+
+/// The state of the bulk coretime of a parachain, as known by the coretime chain.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CoretimeStatus {
+	/// The identifier of the parachain.
+	pub para_id: u32,
+	/// The length of a timeslice, in relay chain blocks.
+	pub timeslice_period: u32,
+	/// The current sale, if any.
+	pub sale: Option<Sale>,
+	/// The current price of a core in the sale, if known.
+	pub price: Option<u128>,
+	/// The cores with workload currently assigned to the parachain.
+	pub cores: Vec<u16>,
+	/// The renewals available to the parachain.
+	pub renewals: Vec<Renewal>,
+}
+
+/// Constructs a call to purchase a region of bulk coretime in the current sale.
+///
+/// # Arguments
+/// * `price_limit` - The maximum price to be paid for the region.
+pub fn purchase(price_limit: u128) -> DynamicPayload {
+	dynamic::tx("Broker", "purchase", vec![Value::u128(price_limit)])
+}
+
+/// Constructs a call to renew the bulk coretime of a core, for the regions of the next sale.
+///
+/// # Arguments
+/// * `core` - The index of the core to be renewed.
+pub fn renew(core: u16) -> DynamicPayload {
+	dynamic::tx("Broker", "renew", vec![Value::u128(core.into())])
+}
+
+/// Constructs a call to assign a region of bulk coretime to a task, e.g. a parachain.
+///
+/// # Arguments
+/// * `region` - The region to be assigned.
+/// * `task` - The task to which the region is assigned.
+/// * `finality` - Whether the assignment is final.
+pub fn assign(region: &RegionId, task: u32, finality: Finality) -> DynamicPayload {
+	dynamic::tx(
+		"Broker",
+		"assign",
+		vec![
+			region.value(),
+			Value::u128(task.into()),
+			Value::unnamed_variant(finality.as_ref(), []),
+		],
+	)
+}
+
+/// Queries the state of the bulk coretime of a parachain from the `Broker` pallet of a coretime
+/// chain.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the coretime chain.
+/// * `para_id` - The identifier of the parachain.
+pub async fn coretime_status(
+	client: &OnlineClient<SubstrateConfig>,
+	para_id: u32,
+) -> Result<CoretimeStatus, Error> {
+	let parse_error = |e: &dyn std::fmt::Display| Error::MetadataParsingError(e.to_string());
+	let timeslice_period = client
+		.constants()
+		.at(&dynamic::constant("Broker", "TimeslicePeriod"))?
+		.to_value()
+		.map_err(|e| parse_error(&e))?
+		.as_u128()
+		.unwrap_or_default() as u32;
+	let storage = client.storage().at_latest().await?;
+	let sale = storage
+		.fetch(&dynamic::storage("Broker", "SaleInfo", Vec::<Value>::new()))
+		.await?
+		.map(|s| s.to_value())
+		.transpose()
+		.map_err(|e| parse_error(&e))?
+		.and_then(|s| parse_sale(&s));
+	let price = sale_price(client).await?;
+
+	let mut cores = Vec::new();
+	let mut entries = storage
+		.iter(dynamic::storage("Broker", "Workload", Vec::<Value>::new()))
+		.await?;
+	while let Some(entry) = entries.next().await {
+		let entry = entry?;
+		let Some(core) = entry.keys.first().and_then(|k| k.as_u128()) else { continue };
+		let schedule = entry.value.to_value().map_err(|e| parse_error(&e))?;
+		if tasks(&schedule).contains(&para_id) {
+			cores.push(core as u16);
+		}
+	}
+	cores.sort();
+
+	// Renewals were previously referred to as allowed, rather than potential, renewals.
+	let metadata = client.metadata();
+	let renewals_entry = match metadata
+		.pallet_by_name("Broker")
+		.and_then(|p| p.storage())
+		.is_some_and(|s| s.entry_by_name("PotentialRenewals").is_some())
+	{
+		true => "PotentialRenewals",
+		false => "AllowedRenewals",
+	};
+	let mut renewals = Vec::new();
+	let mut entries = storage
+		.iter(dynamic::storage("Broker", renewals_entry, Vec::<Value>::new()))
+		.await?;
+	while let Some(entry) = entries.next().await {
+		let entry = entry?;
+		let Some(key) = entry.keys.first() else { continue };
+		let record = entry.value.to_value().map_err(|e| parse_error(&e))?;
+		if let Some(renewal) = parse_renewal(key, &record, para_id) {
+			renewals.push(renewal);
+		}
+	}
+	renewals.sort_by_key(|r| (r.when, r.core));
+	Ok(CoretimeStatus { para_id, timeslice_period, sale, price, cores, renewals })
+}
+
+/// The current price of a core in the sale of bulk coretime, if known.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the coretime chain.
+pub async fn sale_price(client: &OnlineClient<SubstrateConfig>) -> Result<Option<u128>, Error> {
+	// The sale price is only available from more recent runtimes.
+	let price = client
+		.runtime_api()
+		.at_latest()
+		.await?
+		.call(dynamic::runtime_api_call("BrokerApi", "sale_price", Vec::<Value>::new()))
+		.await;
+	Ok(price.ok().and_then(|p| p.to_value().ok()).and_then(|p| match &p.value {
+		ValueDef::Variant(v) if v.name == "Ok" => v.values.values().next()?.as_u128(),
+		_ => None,
+	}))
+}
+
+// Parses the current sale.
+fn parse_sale<T>(sale: &Value<T>) -> Option<Sale> {
+	let ValueDef::Composite(sale) = &sale.value else { return None };
+	let number = |name: &str| field(sale, name).and_then(|v| v.as_u128());
+	Some(Sale {
+		sale_start: number("sale_start")? as u32,
+		leadin_length: number("leadin_length")? as u32,
+		// The end price was previously referred to as the price.
+		end_price: number("end_price").or_else(|| number("price"))?,
+		region_begin: number("region_begin")? as u32,
+		region_end: number("region_end")? as u32,
+		cores_offered: number("cores_offered")? as u16,
+		cores_sold: number("cores_sold")? as u16,
+	})
+}
+
+// Parses a renewal from its identifier and record, if it renews the workload of the parachain.
+fn parse_renewal<K, T>(id: &Value<K>, record: &Value<T>, para_id: u32) -> Option<Renewal> {
+	let (ValueDef::Composite(id), ValueDef::Composite(record)) = (&id.value, &record.value) else {
+		return None;
+	};
+	// Only a complete renewal specifies the workload which is renewed.
+	let ValueDef::Variant(completion) = &field(record, "completion")?.value else { return None };
+	if completion.name != "Complete" ||
+		!tasks(completion.values.values().next()?).contains(&para_id)
+	{
+		return None;
+	}
+	Some(Renewal {
+		core: field(id, "core")?.as_u128()? as u16,
+		when: field(id, "when")?.as_u128()? as u32,
+		price: field(record, "price")?.as_u128()?,
+	})
+}
+
+// The tasks to which the items of a schedule are assigned.
+fn tasks<T>(schedule: &Value<T>) -> Vec<u32> {
+	let ValueDef::Composite(items) = &schedule.value else { return vec![] };
+	// A schedule may be wrapped in a bounded vector.
+	let items = match items.values().next() {
+		Some(Value { value: ValueDef::Composite(inner), .. })
+			if items.len() == 1 && field(inner, "assignment").is_none() =>
+			inner,
+		_ => items,
+	};
+	items
+		.values()
+		.filter_map(|item| match &item.value {
+			ValueDef::Composite(item) => field(item, "assignment"),
+			_ => None,
+		})
+		.filter_map(|assignment| match &assignment.value {
+			ValueDef::Variant(v) if v.name == "Task" => v.values.values().next()?.as_u128(),
+			_ => None,
+		})
+		.map(|task| task as u32)
+		.collect()
+}
+
+fn field<'a, T>(composite: &'a Composite<T>, name: &str) -> Option<&'a Value<T>> {
+	match composite {
+		Composite::Named(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+		Composite::Unnamed(_) => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn schedule(assignments: impl IntoIterator<Item = Value>) -> Value {
+		Value::unnamed_composite(assignments.into_iter().map(|assignment| {
+			Value::named_composite([
+				("mask", Value::unnamed_composite([Value::from_bytes(COMPLETE_MASK)])),
+				("assignment", assignment),
+			])
+		}))
+	}
+
+	fn task(id: u128) -> Value {
+		Value::unnamed_variant("Task", [Value::u128(id)])
+	}
+
+	#[test]
+	fn construct_calls_works() {
+		let region = RegionId { begin: 1_000, core: 3, mask: COMPLETE_MASK };
+		assert_eq!(
+			region.value(),
+			Value::named_composite([
+				("begin", Value::u128(1_000)),
+				("core", Value::u128(3)),
+				("mask", Value::unnamed_composite([Value::from_bytes([0xFF; 10])])),
+			])
+		);
+		let call = assign(&region, 4001, Finality::Provisional);
+		assert_eq!((call.pallet_name(), call.call_name()), ("Broker", "assign"));
+		assert_eq!(purchase(100).call_name(), "purchase");
+		assert_eq!(renew(3).call_name(), "renew");
+		assert_eq!("Final".parse::<Finality>(), Ok(Finality::Final));
+	}
+
+	#[test]
+	fn parse_sale_works() {
+		let sale = |price: &'static str| {
+			Value::named_composite([
+				("sale_start", Value::u128(100)),
+				("leadin_length", Value::u128(50)),
+				(price, Value::u128(1_000)),
+				("region_begin", Value::u128(20)),
+				("region_end", Value::u128(30)),
+				("ideal_cores_sold", Value::u128(4)),
+				("cores_offered", Value::u128(5)),
+				("first_core", Value::u128(40)),
+				("sellout_price", Value::unnamed_variant("None", [])),
+				("cores_sold", Value::u128(2)),
+			])
+		};
+		let expected = Sale {
+			sale_start: 100,
+			leadin_length: 50,
+			end_price: 1_000,
+			region_begin: 20,
+			region_end: 30,
+			cores_offered: 5,
+			cores_sold: 2,
+		};
+		assert_eq!(parse_sale(&sale("end_price")), Some(expected.clone()));
+		// Sales of previous versions of the pallet are supported too.
+		assert_eq!(parse_sale(&sale("price")), Some(expected));
+		assert_eq!(parse_sale(&Value::u128(0)), None);
+	}
+
+	#[test]
+	fn parse_renewal_works() {
+		let id = Value::named_composite([("core", Value::u128(3)), ("when", Value::u128(20))]);
+		let record = |completion| {
+			Value::named_composite([("price", Value::u128(1_000)), ("completion", completion)])
+		};
+		let complete = record(Value::unnamed_variant(
+			"Complete",
+			[schedule([Value::unnamed_variant("Pool", []), task(4001)])],
+		));
+		assert_eq!(
+			parse_renewal(&id, &complete, 4001),
+			Some(Renewal { core: 3, when: 20, price: 1_000 })
+		);
+		assert_eq!(parse_renewal(&id, &complete, 2000), None);
+		let partial = record(Value::unnamed_variant(
+			"Partial",
+			[Value::unnamed_composite([Value::from_bytes(COMPLETE_MASK)])],
+		));
+		assert_eq!(parse_renewal(&id, &partial, 4001), None);
+	}
+
+	#[test]
+	fn tasks_works() {
+		assert_eq!(tasks(&schedule([task(1000), Value::unnamed_variant("Idle", [])])), [1000]);
+		// Schedules may be wrapped in a bounded vector.
+		assert_eq!(tasks(&Value::unnamed_composite([schedule([task(4001)])])), [4001]);
+		assert!(tasks(&schedule([])).is_empty());
+	}
+}
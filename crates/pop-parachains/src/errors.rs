@@ -8,14 +8,26 @@ use zombienet_sdk::OrchestratorError;
 pub enum Error {
 	#[error("User aborted due to existing target directory.")]
 	Aborted,
+	/// The specified type matches multiple types of the metadata.
+	#[error("The type is ambiguous: {0}")]
+	AmbiguousType(String),
 	#[error("Anyhow error: {0}")]
 	AnyhowError(#[from] anyhow::Error),
+	/// A benchmark could not be run.
+	#[error("Benchmark error: {0}")]
+	BenchmarkError(String),
+	/// The specified block could not be found.
+	#[error("Failed to find the block {0}")]
+	BlockNotFound(String),
 	/// An error occurred while decoding the call data.
 	#[error("Failed to decode call data. {0}")]
 	CallDataDecodingError(String),
 	/// An error occurred while encoding the call data.
 	#[error("Failed to encode call data. {0}")]
 	CallDataEncodingError(String),
+	/// A call template could not be saved or loaded.
+	#[error("Call template error: {0}")]
+	CallTemplateError(String),
 	#[error("{0}")]
 	CommonError(#[from] pop_common::Error),
 	/// An error occurred while attempting to establish a connection to the endpoint.
@@ -25,18 +37,35 @@ pub enum Error {
 	Config(String),
 	#[error("Failed to access the current directory")]
 	CurrentDirAccess,
+	/// A value could not be decoded as the specified type.
+	#[error("Failed to decode the value: {0}")]
+	DecodingError(String),
 	#[error("Failed to parse the endowment value")]
 	EndowmentError,
 	/// An error occurred during the submission of an extrinsic.
+	#[error("The extrinsic failed: {0}")]
+	ExtrinsicFailed(String),
 	#[error("Extrinsic submission error: {0}")]
 	ExtrinsicSubmissionError(String),
+	/// The fee for executing a message could not be estimated.
+	#[error("Failed to estimate the fee: {0}")]
+	FeeEstimationError(String),
 	/// The dispatchable function is not supported.
 	#[error("The dispatchable function is not supported")]
 	FunctionNotSupported,
+	/// The runtime exhausted its heap while being executed.
+	#[error("The runtime exhausted its heap: {0}")]
+	HeapExhausted(String),
+	/// The arguments of a dispatchable function, specified as JSON, are invalid.
+	#[error("Invalid arguments: {0}")]
+	InvalidArguments(String),
 	#[error("IO error: {0}")]
 	IO(#[from] std::io::Error),
 	#[error("JSON error: {0}")]
 	JsonError(#[from] serde_json::Error),
+	/// The embedded light client could not connect to the chain.
+	#[error("Failed to connect via the light client: {0}")]
+	LightClientError(String),
 	/// An error occurred while parsing metadata of a parameter.
 	#[error("Error parsing metadata for parameter {0}")]
 	MetadataParsingError(String),
@@ -46,6 +75,18 @@ pub enum Error {
 	MissingChainSpec(String),
 	#[error("Command {command} doesn't exist in binary {binary}")]
 	MissingCommand { command: String, binary: String },
+	/// The pallet does not have a fuzzing harness.
+	#[error("Missing fuzzing harness at: {0}")]
+	MissingFuzzHarness(String),
+	/// The monitoring stack of a network could not be run.
+	#[error("Monitoring error: {0}")]
+	MonitoringError(String),
+	/// A call could not be approved by a signatory of a multisig account.
+	#[error("Multisig error: {0}")]
+	MultisigError(String),
+	/// An extrinsic could not be signed offline or assembled from its signature.
+	#[error("Offline signing error: {0}")]
+	OfflineSigningError(String),
 	#[error("Orchestrator error: {0}")]
 	OrchestratorError(#[from] OrchestratorError),
 	#[error("Failed to create pallet directory")]
@@ -58,17 +99,52 @@ pub enum Error {
 	ParamProcessingError,
 	#[error("Invalid path")]
 	PathError,
+	/// The code or version of a runtime is invalid.
+	#[error("Release error: {0}")]
+	ReleaseError(String),
+	#[error("Runtime error: {0}")]
+	RuntimeError(String),
+	/// A call could not be scheduled or its scheduled task identified.
+	#[error("Scheduling error: {0}")]
+	SchedulingError(String),
 	#[error("Failed to execute rustfmt")]
 	RustfmtError(std::io::Error),
+	/// A snapshot of the state of a chain could not be created.
+	#[error("Snapshot error: {0}")]
+	SnapshotError(String),
 	#[error("Template error: {0}")]
 	SourcingError(#[from] pop_common::sourcing::Error),
-	/// An error occurred whilst interacting with a chain using `subxt`.
+	/// The specified storage item could not be found.
+	#[error("Failed to find the storage item {0}")]
+	StorageItemNotFound(String),
+	/// The subscription to the blocks of a chain ended unexpectedly.
+	#[error("The subscription to the chain ended unexpectedly")]
+	SubscriptionEnded,
+	/// An error occurred whilst interacting with a chain using `subxt`, boxed due to its size.
 	#[error("Subxt error: {0}")]
-	SubXtError(#[from] subxt::Error),
+	SubXtError(Box<subxt::Error>),
+	/// The telemetry server of a network could not be run.
+	#[error("Telemetry error: {0}")]
+	TelemetryError(String),
 	#[error("Toml error: {0}")]
 	TomlError(#[from] toml_edit::de::Error),
+	/// The specified type could not be found within the metadata.
+	#[error("Failed to find the type {0}")]
+	TypeNotFound(String),
 	#[error("Unsupported command: {0}")]
 	UnsupportedCommand(String),
+	/// The transfer of assets between chains is not supported.
+	#[error("Unsupported transfer: {0}")]
+	UnsupportedTransfer(String),
+	/// A view function could not be executed.
+	#[error("Failed to execute the view function: {0}")]
+	ViewFunctionError(String),
 	#[error("Failed to locate the workspace")]
 	WorkspaceLocate,
 }
+
+impl From<subxt::Error> for Error {
+	fn from(error: subxt::Error) -> Self {
+		Self::SubXtError(Box::new(error))
+	}
+}
@@ -0,0 +1,298 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::errors::Error;
+use scale_value::{Composite, Value, ValueDef};
+use subxt::{
+	dynamic,
+	tx::{DynamicPayload, Payload},
+	utils::AccountId32,
+	OnlineClient, SubstrateConfig,
+};
+
+/// The calls of the `Balances` pallet which transfer an amount of the native token to an account.
+const TRANSFER_CALLS: [&str; 2] = ["transfer_allow_death", "transfer_keep_alive"];
+
+/// The fee charged for including a call within a block, excluding any tip.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FeeDetails {
+	/// The minimum fee of a transaction.
+	pub base_fee: u128,
+	/// The fee for the length of the transaction.
+	pub len_fee: u128,
+	/// The fee for the weight of the call, adjusted by the fee multiplier.
+	pub adjusted_weight_fee: u128,
+}
+
+impl FeeDetails {
+	/// The total fee.
+	pub fn total(&self) -> u128 {
+		self.base_fee
+			.saturating_add(self.len_fee)
+			.saturating_add(self.adjusted_weight_fee)
+	}
+}
+
+/// Encodes a call transferring an amount of the native token while keeping the sender alive,
+/// as a standard call whose fee is estimated.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `dest` - The account to which the amount is transferred.
+/// * `amount` - The amount transferred.
+pub fn transfer_call_data(
+	client: &OnlineClient<SubstrateConfig>,
+	dest: &AccountId32,
+	amount: u128,
+) -> Result<Vec<u8>, Error> {
+	transfer_call(dest, amount, true)
+		.encode_call_data(&client.metadata())
+		.map_err(|e| Error::CallDataEncodingError(e.to_string()))
+}
+
+/// Constructs a call transferring an amount of the native token to an account.
+///
+/// # Arguments
+/// * `dest` - The account to which the amount is transferred.
+/// * `amount` - The amount transferred.
+/// * `keep_alive` - Whether the call fails rather than reaping the account of the sender.
+pub fn transfer_call(dest: &AccountId32, amount: u128, keep_alive: bool) -> DynamicPayload {
+	let dest = Value::unnamed_variant("Id", [Value::from_bytes(dest.0)]);
+	let call = match keep_alive {
+		true => "transfer_keep_alive",
+		false => "transfer_allow_death",
+	};
+	dynamic::tx("Balances", call, vec![dest, Value::u128(amount)])
+}
+
+/// The destination and amount of a call transferring the native token, if the call is such a
+/// transfer.
+///
+/// # Arguments
+/// * `xt` - The call.
+pub fn native_transfer(xt: &DynamicPayload) -> Option<(AccountId32, u128)> {
+	if xt.pallet_name() != "Balances" || !TRANSFER_CALLS.contains(&xt.call_name()) {
+		return None;
+	}
+	let mut args = xt.call_data().values();
+	let ValueDef::Variant(dest) = &args.next()?.value else { return None };
+	if dest.name != "Id" {
+		return None;
+	}
+	let dest = bytes(unwrap(dest.values.values().next()?))?;
+	Some((AccountId32(dest.try_into().ok()?), args.next()?.as_u128()?))
+}
+
+/// Estimates the fee for a call at the latest block via the `TransactionPaymentCallApi` of the
+/// chain.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `call_data` - The encoded call.
+pub async fn call_fee(
+	client: &OnlineClient<SubstrateConfig>,
+	call_data: &[u8],
+) -> Result<FeeDetails, Error> {
+	let details = query_call(client, "query_call_fee_details", call_data)
+		.await?
+		.to_value()
+		.map_err(|e| Error::MetadataParsingError(format!("fee details: {e}")))?;
+	parse_fee_details(&details).ok_or_else(|| Error::MetadataParsingError("fee details".into()))
+}
+
+/// Estimates the weight of a call at the latest block via the `TransactionPaymentCallApi` of the
+/// chain, as its reference time and proof size.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `call_data` - The encoded call.
+pub(crate) async fn call_weight(
+	client: &OnlineClient<SubstrateConfig>,
+	call_data: &[u8],
+) -> Result<(u64, u64), Error> {
+	let info = query_call(client, "query_call_info", call_data)
+		.await?
+		.to_value()
+		.map_err(|e| Error::MetadataParsingError(format!("call info: {e}")))?;
+	parse_weight(&info).ok_or_else(|| Error::MetadataParsingError("call info".into()))
+}
+
+// Calls a function of the `TransactionPaymentCallApi` with a call and its encoded length.
+async fn query_call(
+	client: &OnlineClient<SubstrateConfig>,
+	function: &str,
+	call_data: &[u8],
+) -> Result<dynamic::DecodedValueThunk, Error> {
+	let metadata = client.metadata();
+	let mut input = call_data;
+	let call: Value<u32> = scale_value::scale::decode_as_type(
+		&mut input,
+		metadata.outer_enums().call_enum_ty(),
+		metadata.types(),
+	)
+	.map_err(|e| Error::CallDataDecodingError(e.to_string()))?;
+	let payload = dynamic::runtime_api_call(
+		"TransactionPaymentCallApi",
+		function,
+		vec![call.remove_context(), Value::u128(call_data.len() as u128)],
+	);
+	Ok(client.runtime_api().at_latest().await?.call(payload).await?)
+}
+
+/// The fee multipliers of the latest blocks, by block number and starting with the most recent,
+/// as fixed-point numbers with 18 decimals.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `count` - The number of blocks.
+pub async fn fee_multipliers(
+	client: &OnlineClient<SubstrateConfig>,
+	count: usize,
+) -> Result<Vec<(u32, u128)>, Error> {
+	let mut block = client.blocks().at_latest().await?;
+	let mut multipliers = Vec::new();
+	while multipliers.len() < count {
+		let multiplier = client
+			.storage()
+			.at(block.hash())
+			.fetch(&dynamic::storage(
+				"TransactionPayment",
+				"NextFeeMultiplier",
+				Vec::<Value>::new(),
+			))
+			.await?
+			.map(|multiplier| multiplier.to_value())
+			.transpose()
+			.map_err(|e| Error::MetadataParsingError(format!("fee multiplier: {e}")))?
+			.and_then(|multiplier| unwrap(&multiplier).as_u128());
+		let Some(multiplier) = multiplier else { break };
+		multipliers.push((block.number(), multiplier));
+		if block.number() == 0 {
+			break;
+		}
+		block = client.blocks().at(block.header().parent_hash).await?;
+	}
+	Ok(multipliers)
+}
+
+// Parses the fee details returned by the runtime, where a call without an inclusion fee is free.
+fn parse_fee_details<T>(details: &Value<T>) -> Option<FeeDetails> {
+	let ValueDef::Composite(details) = &details.value else { return None };
+	let ValueDef::Variant(inclusion_fee) = &field(details, "inclusion_fee")?.value else {
+		return None;
+	};
+	let Some(fee) = inclusion_fee.values.values().next() else {
+		return Some(FeeDetails::default());
+	};
+	let ValueDef::Composite(fee) = &fee.value else { return None };
+	let amount = |name: &str| field(fee, name).and_then(|v| v.as_u128());
+	Some(FeeDetails {
+		base_fee: amount("base_fee")?,
+		len_fee: amount("len_fee")?,
+		adjusted_weight_fee: amount("adjusted_weight_fee")?,
+	})
+}
+
+// Parses the weight of the dispatch info returned by the runtime.
+fn parse_weight<T>(info: &Value<T>) -> Option<(u64, u64)> {
+	let ValueDef::Composite(info) = &info.value else { return None };
+	let ValueDef::Composite(weight) = &field(info, "weight")?.value else { return None };
+	let component = |name: &str| field(weight, name).and_then(|v| unwrap(v).as_u128());
+	Some((component("ref_time")? as u64, component("proof_size")? as u64))
+}
+
+// Unwraps a value from any newtypes, such as `FixedU128`.
+fn unwrap<T>(value: &Value<T>) -> &Value<T> {
+	match &value.value {
+		ValueDef::Composite(c) if c.len() == 1 =>
+			unwrap(c.values().next().expect("length checked")),
+		_ => value,
+	}
+}
+
+// The bytes of a value encoded as a sequence of bytes, such as an account identifier.
+fn bytes<T>(value: &Value<T>) -> Option<Vec<u8>> {
+	let ValueDef::Composite(Composite::Unnamed(values)) = &value.value else { return None };
+	values.iter().map(|v| v.as_u128().and_then(|b| u8::try_from(b).ok())).collect()
+}
+
+fn field<'a, T>(composite: &'a Composite<T>, name: &str) -> Option<&'a Value<T>> {
+	match composite {
+		Composite::Named(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+		Composite::Unnamed(_) => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_fee_details_works() {
+		let details = |inclusion_fee: Value| {
+			Value::named_composite([("inclusion_fee", inclusion_fee), ("tip", Value::u128(0))])
+		};
+		let fee = Value::named_composite([
+			("base_fee", Value::u128(100)),
+			("len_fee", Value::u128(20)),
+			("adjusted_weight_fee", Value::u128(3)),
+		]);
+		let fee = parse_fee_details(&details(Value::unnamed_variant("Some", [fee]))).unwrap();
+		assert_eq!(fee, FeeDetails { base_fee: 100, len_fee: 20, adjusted_weight_fee: 3 });
+		assert_eq!(fee.total(), 123);
+		assert_eq!(
+			parse_fee_details(&details(Value::unnamed_variant("None", []))),
+			Some(FeeDetails::default())
+		);
+		assert_eq!(parse_fee_details(&Value::u128(0)), None);
+	}
+
+	#[test]
+	fn parse_weight_works() {
+		let weight = Value::named_composite([
+			("ref_time", Value::u128(1_000)),
+			("proof_size", Value::u128(64)),
+		]);
+		let info = Value::named_composite([
+			("weight", weight),
+			("class", Value::unnamed_variant("Normal", [])),
+			("partial_fee", Value::u128(10)),
+		]);
+		assert_eq!(parse_weight(&info), Some((1_000, 64)));
+		assert_eq!(parse_weight(&Value::u128(0)), None);
+	}
+
+	#[test]
+	fn native_transfer_works() {
+		let dest = AccountId32([1; 32]);
+		let id = || Value::unnamed_variant("Id", [Value::from_bytes(dest.0)]);
+		for call in TRANSFER_CALLS {
+			let xt = dynamic::tx("Balances", call, vec![id(), Value::u128(100)]);
+			assert_eq!(native_transfer(&xt), Some((dest.clone(), 100)));
+		}
+		let xt = dynamic::tx("Balances", "force_transfer", vec![id(), id(), Value::u128(100)]);
+		assert_eq!(native_transfer(&xt), None);
+		let index = Value::unnamed_variant("Index", [Value::u128(1)]);
+		let xt = dynamic::tx("Balances", "transfer_keep_alive", vec![index, Value::u128(100)]);
+		assert_eq!(native_transfer(&xt), None);
+		let xt = dynamic::tx("Assets", "transfer_keep_alive", vec![id(), Value::u128(100)]);
+		assert_eq!(native_transfer(&xt), None);
+	}
+
+	#[test]
+	fn transfer_call_works() {
+		let dest = AccountId32([1; 32]);
+		let xt = transfer_call(&dest, 100, true);
+		assert_eq!(xt.call_name(), "transfer_keep_alive");
+		assert_eq!(native_transfer(&xt), Some((dest.clone(), 100)));
+		let xt = transfer_call(&dest, 100, false);
+		assert_eq!(xt.call_name(), "transfer_allow_death");
+		assert_eq!(native_transfer(&xt), Some((dest, 100)));
+	}
+
+	#[test]
+	fn unwrap_works() {
+		let multiplier = Value::unnamed_composite([Value::u128(1_000_000_000_000_000_000)]);
+		assert_eq!(unwrap(&multiplier).as_u128(), Some(1_000_000_000_000_000_000));
+		assert_eq!(unwrap(&Value::u128(1)).as_u128(), Some(1));
+	}
+}
@@ -0,0 +1,290 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	errors::Error,
+	generator::fuzz::{FuzzCargoToml, FuzzGitignore, FuzzMock, FuzzTarget},
+	utils::helpers::render_to_file,
+};
+use duct::cmd;
+use pop_common::manifest::from_path;
+use std::{
+	fs::{create_dir_all, read_dir},
+	path::{Path, PathBuf},
+};
+
+/// The name of the fuzz target generated for the extrinsics of a pallet.
+pub const FUZZ_TARGET: &str = "extrinsics";
+/// The prefixes used by libFuzzer for the inputs causing a failure.
+const FAILURE_PREFIXES: [&str; 4] = ["crash-", "leak-", "oom-", "timeout-"];
+
+/// Options for a fuzzing run.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FuzzOptions {
+	/// The maximum duration of the run, in seconds.
+	pub max_total_time: Option<u64>,
+	/// The maximum number of individual runs.
+	pub runs: Option<u64>,
+	/// The number of fuzzing jobs to run in parallel.
+	pub jobs: Option<u16>,
+	/// The seed of the random number generator, allowing a run to be repeated.
+	pub seed: Option<u64>,
+}
+
+/// A cargo-fuzz harness for a pallet, located within the `fuzz` directory of the pallet.
+#[derive(Debug, PartialEq)]
+pub struct FuzzHarness {
+	pallet: PathBuf,
+}
+
+impl FuzzHarness {
+	/// Generates a harness which fuzzes the extrinsics of a pallet against a mock runtime.
+	///
+	/// # Arguments
+	/// * `pallet` - The path to the pallet.
+	pub fn create(pallet: &Path) -> Result<Self, Error> {
+		let manifest = from_path(Some(pallet))?;
+		let package = manifest
+			.package
+			.map(|p| p.name)
+			.ok_or_else(|| Error::PalletNotFound(pallet.display().to_string()))?;
+		let krate = package.replace('-', "_");
+		let name = krate.strip_prefix("pallet_").unwrap_or(&krate).to_string();
+
+		let harness = Self { pallet: pallet.to_path_buf() };
+		let path = harness.path();
+		create_dir_all(path.join("fuzz_targets"))?;
+		render_to_file(
+			&path.join("Cargo.toml"),
+			FuzzCargoToml { package, target: FUZZ_TARGET.into() },
+		)?;
+		render_to_file(&path.join(".gitignore"), FuzzGitignore {})?;
+		render_to_file(
+			&path.join("fuzz_targets/mock.rs"),
+			FuzzMock { name, krate: krate.clone() },
+		)?;
+		render_to_file(&path.join(format!("fuzz_targets/{FUZZ_TARGET}.rs")), FuzzTarget { krate })?;
+		create_dir_all(harness.corpus(FUZZ_TARGET))?;
+		Ok(harness)
+	}
+
+	/// Loads the existing harness of a pallet.
+	///
+	/// # Arguments
+	/// * `pallet` - The path to the pallet.
+	pub fn new(pallet: &Path) -> Result<Self, Error> {
+		let harness = Self { pallet: pallet.to_path_buf() };
+		let manifest = harness.path().join("Cargo.toml");
+		if !manifest.exists() {
+			return Err(Error::MissingFuzzHarness(harness.path().display().to_string()));
+		}
+		Ok(harness)
+	}
+
+	/// The path to the harness.
+	pub fn path(&self) -> PathBuf {
+		self.pallet.join("fuzz")
+	}
+
+	/// The fuzz targets defined by the harness.
+	pub fn targets(&self) -> Result<Vec<String>, Error> {
+		let manifest = from_path(Some(&self.path()))?;
+		Ok(manifest.bin.into_iter().filter_map(|b| b.name).collect())
+	}
+
+	/// The corpus of a fuzz target: the inputs found to be interesting, which seed future runs.
+	///
+	/// # Arguments
+	/// * `target` - The fuzz target.
+	pub fn corpus(&self, target: &str) -> PathBuf {
+		self.path().join("corpus").join(target)
+	}
+
+	/// The artifacts of a fuzz target, containing the inputs which caused failures.
+	///
+	/// # Arguments
+	/// * `target` - The fuzz target.
+	pub fn artifacts(&self, target: &str) -> PathBuf {
+		self.path().join("artifacts").join(target)
+	}
+
+	/// The inputs found to cause a fuzz target to fail (e.g. crash or timeout), sorted by name.
+	///
+	/// # Arguments
+	/// * `target` - The fuzz target.
+	pub fn crashes(&self, target: &str) -> Result<Vec<PathBuf>, Error> {
+		let artifacts = self.artifacts(target);
+		if !artifacts.exists() {
+			return Ok(vec![]);
+		}
+		let mut crashes = vec![];
+		for entry in read_dir(artifacts)? {
+			let path = entry?.path();
+			let failure = path
+				.file_name()
+				.and_then(|n| n.to_str())
+				.is_some_and(|name| FAILURE_PREFIXES.iter().any(|prefix| name.starts_with(prefix)));
+			if failure {
+				crashes.push(path);
+			}
+		}
+		crashes.sort();
+		Ok(crashes)
+	}
+
+	/// Fuzzes a target, returning whether the run completed without any failures.
+	///
+	/// # Arguments
+	/// * `target` - The fuzz target.
+	/// * `options` - The options for the run.
+	pub fn run(&self, target: &str, options: &FuzzOptions) -> Result<bool, Error> {
+		self.cargo_fuzz(run_args(target, options))
+	}
+
+	/// Runs a fuzz target with a single input, returning whether the input no longer causes a
+	/// failure.
+	///
+	/// # Arguments
+	/// * `target` - The fuzz target.
+	/// * `input` - The input to be reproduced, typically a crash.
+	pub fn reproduce(&self, target: &str, input: &Path) -> Result<bool, Error> {
+		let input = input.canonicalize()?.display().to_string();
+		self.cargo_fuzz(vec!["run".into(), target.into(), input])
+	}
+
+	/// Minimizes the corpus of a fuzz target, removing inputs which do not increase coverage.
+	///
+	/// # Arguments
+	/// * `target` - The fuzz target.
+	pub fn minimize(&self, target: &str) -> Result<bool, Error> {
+		self.cargo_fuzz(vec!["cmin".into(), target.into()])
+	}
+
+	// Runs cargo-fuzz from the pallet, which requires a nightly toolchain for the sanitizers.
+	fn cargo_fuzz(&self, args: Vec<String>) -> Result<bool, Error> {
+		let args = ["+nightly".to_string(), "fuzz".to_string()].into_iter().chain(args);
+		let output = cmd("cargo", args).dir(&self.pallet).unchecked().run()?;
+		Ok(output.status.success())
+	}
+}
+
+/// The arguments to `cargo fuzz` for a fuzzing run.
+fn run_args(target: &str, options: &FuzzOptions) -> Vec<String> {
+	let mut args = vec!["run".to_string(), target.to_string()];
+	if let Some(jobs) = options.jobs {
+		args.push(format!("--jobs={jobs}"));
+	}
+	let libfuzzer: Vec<_> = [
+		options.max_total_time.map(|t| format!("-max_total_time={t}")),
+		options.runs.map(|r| format!("-runs={r}")),
+		options.seed.map(|s| format!("-seed={s}")),
+	]
+	.into_iter()
+	.flatten()
+	.collect();
+	if !libfuzzer.is_empty() {
+		args.push("--".into());
+		args.extend(libfuzzer);
+	}
+	args
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs::{read_to_string, write};
+
+	fn pallet(dir: &Path) -> Result<PathBuf, Error> {
+		let path = dir.join("my-pallet");
+		create_dir_all(&path)?;
+		write(
+			path.join("Cargo.toml"),
+			"[package]\nname = \"pallet-my-pallet\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+		)?;
+		Ok(path)
+	}
+
+	#[test]
+	fn create_works() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		let pallet = pallet(temp_dir.path())?;
+		let harness = FuzzHarness::create(&pallet)?;
+		let path = pallet.join("fuzz");
+		assert_eq!(harness.path(), path);
+		let manifest = read_to_string(path.join("Cargo.toml"))?;
+		assert!(manifest.contains("name = \"pallet-my-pallet-fuzz\""));
+		assert!(manifest.contains("pallet-my-pallet = { path = \"..\" }"));
+		assert!(manifest.contains("path = \"fuzz_targets/extrinsics.rs\""));
+		let mock = read_to_string(path.join("fuzz_targets/mock.rs"))?;
+		assert!(mock.contains("pub type My_pallet = pallet_my_pallet;"));
+		assert!(mock.contains("impl pallet_my_pallet::Config for Test"));
+		let target = read_to_string(path.join("fuzz_targets/extrinsics.rs"))?;
+		assert!(target.contains("pallet_my_pallet::Call::<Test>::decode"));
+		assert!(path.join(".gitignore").exists());
+		assert!(harness.corpus(FUZZ_TARGET).exists());
+		assert_eq!(harness.targets()?, vec![FUZZ_TARGET]);
+		assert_eq!(FuzzHarness::new(&pallet)?, harness);
+		Ok(())
+	}
+
+	#[test]
+	fn create_fails_without_manifest() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		assert!(matches!(
+			FuzzHarness::create(temp_dir.path()),
+			Err(Error::CommonError(pop_common::Error::ManifestPath(..)))
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn new_fails_without_harness() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		assert!(matches!(
+			FuzzHarness::new(temp_dir.path()),
+			Err(Error::MissingFuzzHarness(path)) if path == temp_dir.path().join("fuzz").display().to_string()
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn crashes_works() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		let harness = FuzzHarness::create(&pallet(temp_dir.path())?)?;
+		assert!(harness.crashes(FUZZ_TARGET)?.is_empty());
+		let artifacts = harness.artifacts(FUZZ_TARGET);
+		create_dir_all(&artifacts)?;
+		for file in ["timeout-2", "crash-1", "minimized-from-1", "oom-3"] {
+			write(artifacts.join(file), [0u8])?;
+		}
+		assert_eq!(
+			harness.crashes(FUZZ_TARGET)?,
+			vec![artifacts.join("crash-1"), artifacts.join("oom-3"), artifacts.join("timeout-2")]
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn run_args_works() {
+		assert_eq!(run_args(FUZZ_TARGET, &FuzzOptions::default()), vec!["run", "extrinsics"]);
+		assert_eq!(
+			run_args(
+				FUZZ_TARGET,
+				&FuzzOptions {
+					max_total_time: Some(60),
+					runs: Some(1000),
+					jobs: Some(4),
+					seed: Some(42)
+				}
+			),
+			vec![
+				"run",
+				"extrinsics",
+				"--jobs=4",
+				"--",
+				"-max_total_time=60",
+				"-runs=1000",
+				"-seed=42"
+			]
+		);
+	}
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "fuzz/Cargo.templ", escape = "none")]
+pub(crate) struct FuzzCargoToml {
+	pub(crate) package: String,
+	pub(crate) target: String,
+}
+
+#[derive(Template)]
+#[template(path = "fuzz/gitignore.templ", escape = "none")]
+pub(crate) struct FuzzGitignore {}
+
+#[derive(Template)]
+#[template(path = "fuzz/fuzz_targets/mock.rs.templ", escape = "none")]
+pub(crate) struct FuzzMock {
+	pub(crate) name: String,
+	pub(crate) krate: String,
+}
+
+#[derive(Template)]
+#[template(path = "fuzz/fuzz_targets/target.rs.templ", escape = "none")]
+pub(crate) struct FuzzTarget {
+	pub(crate) krate: String,
+}
@@ -1,4 +1,10 @@
 // SPDX-License-Identifier: GPL-3.0
 
+pub mod fuzz;
+pub mod monitoring;
+pub mod network_tests;
 pub mod pallet;
 pub mod parachain;
+pub mod runtime_tests;
+pub mod telemetry;
+pub mod xcm_simulator;
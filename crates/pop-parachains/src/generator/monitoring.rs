@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::monitoring::ScrapeTarget;
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "monitoring/docker-compose.templ", escape = "none")]
+pub(crate) struct DockerCompose {
+	pub(crate) prometheus_port: u16,
+	pub(crate) grafana_port: u16,
+}
+
+#[derive(Template)]
+#[template(path = "monitoring/prometheus.templ", escape = "none")]
+pub(crate) struct Prometheus<'a> {
+	pub(crate) targets: &'a [ScrapeTarget],
+}
+
+#[derive(Template)]
+#[template(path = "monitoring/grafana/datasource.templ", escape = "none")]
+pub(crate) struct GrafanaDatasource {}
+
+#[derive(Template)]
+#[template(path = "monitoring/grafana/dashboards.templ", escape = "none")]
+pub(crate) struct GrafanaDashboards {}
+
+#[derive(Template)]
+#[template(path = "monitoring/grafana/substrate.json.templ", escape = "none")]
+pub(crate) struct SubstrateDashboard {}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "network_tests/Cargo.templ", escape = "none")]
+pub(crate) struct NetworkTestsCargoToml {
+	pub(crate) name: String,
+	pub(crate) network_config: String,
+	pub(crate) in_workspace: bool,
+}
+
+#[derive(Template)]
+#[template(path = "network_tests/src/lib.rs.templ", escape = "none")]
+pub(crate) struct NetworkTestsLib {
+	pub(crate) network_config: String,
+	// The path of the network configuration, relative to the tests.
+	pub(crate) network_config_path: String,
+}
+
+#[derive(Template)]
+#[template(path = "network_tests/tests/network.rs.templ", escape = "none")]
+pub(crate) struct NetworkTests {
+	pub(crate) krate: String,
+	pub(crate) para_ids: Vec<u32>,
+	pub(crate) hrmp_channels: Vec<(u32, u32)>,
+}
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "runtime_tests/Cargo.templ", escape = "none")]
+pub(crate) struct RuntimeTestsCargoToml {
+	pub(crate) name: String,
+	pub(crate) runtime: String,
+	pub(crate) runtime_path: String,
+	pub(crate) in_workspace: bool,
+	// The dependencies of the tests, each rendered as a manifest entry.
+	pub(crate) dependencies: Vec<String>,
+}
+
+#[derive(Template)]
+#[template(path = "runtime_tests/src/lib.rs.templ", escape = "none")]
+pub(crate) struct RuntimeTestsLib {
+	pub(crate) runtime: String,
+	pub(crate) para_id: u32,
+	pub(crate) slot_duration: u64,
+}
+
+#[derive(Template)]
+#[template(path = "runtime_tests/src/mock_relay.rs.templ", escape = "none")]
+pub(crate) struct RuntimeTestsMockRelay {}
+
+#[derive(Template)]
+#[template(path = "runtime_tests/src/tests.rs.templ", escape = "none")]
+pub(crate) struct RuntimeTestsTests {}
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "telemetry/docker-compose.templ", escape = "none")]
+pub(crate) struct DockerCompose {
+	pub(crate) feed_port: u16,
+	pub(crate) shard_port: u16,
+	pub(crate) ui_port: u16,
+}
+
+#[derive(Template)]
+#[template(path = "telemetry/command.templ", escape = "none")]
+pub(crate) struct Command<'a> {
+	pub(crate) command: &'a str,
+	pub(crate) url: &'a str,
+}
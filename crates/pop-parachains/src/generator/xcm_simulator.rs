@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "xcm_simulator/Cargo.templ", escape = "none")]
+pub(crate) struct XcmSimulatorCargoToml {
+	pub(crate) name: String,
+	pub(crate) runtime: String,
+	pub(crate) runtime_path: String,
+	pub(crate) in_workspace: bool,
+	// The dependencies of the tests, each rendered as a manifest entry.
+	pub(crate) dependencies: Vec<String>,
+}
+
+#[derive(Template)]
+#[template(path = "xcm_simulator/src/lib.rs.templ", escape = "none")]
+pub(crate) struct XcmSimulatorLib {
+	pub(crate) runtime: String,
+	pub(crate) xcm_config: String,
+	pub(crate) para_a: u32,
+	pub(crate) para_b: u32,
+}
+
+#[derive(Template)]
+#[template(path = "xcm_simulator/src/relay_chain.rs.templ", escape = "none")]
+pub(crate) struct XcmSimulatorRelayChain {}
+
+#[derive(Template)]
+#[template(path = "xcm_simulator/src/tests.rs.templ", escape = "none")]
+pub(crate) struct XcmSimulatorTests {}
@@ -0,0 +1,355 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{errors::Error, DynamicPayload};
+use scale_value::{Composite, Primitive, ValueDef};
+use sp_core::blake2_256;
+use strum::VariantArray as _;
+use strum_macros::{AsRefStr, EnumString, VariantArray};
+use subxt::{
+	blocks::ExtrinsicEvents,
+	dynamic::{self, Value},
+	utils::H256,
+	OnlineClient, SubstrateConfig,
+};
+
+/// A track of the referenda pallet, via which referenda with a given origin are decided.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Track {
+	/// The identifier of the track.
+	pub id: u16,
+	/// The name of the track, e.g. `root` or `small_tipper`.
+	pub name: String,
+	/// The deposit required for a referendum on the track to start being decided.
+	pub decision_deposit: u128,
+}
+
+impl Track {
+	// The origin with which the proposal of a referendum on the track is dispatched, being the
+	// `Root` origin for the root track and otherwise the custom origin named after the track.
+	fn origin(&self) -> Value {
+		match self.name.as_str() {
+			"root" => Value::unnamed_variant("system", [Value::unnamed_variant("Root", [])]),
+			name =>
+				Value::unnamed_variant("Origins", [Value::unnamed_variant(camel_case(name), [])]),
+		}
+	}
+}
+
+/// The conviction of a vote, i.e. how many times the voting balance is multiplied by in exchange
+/// for locking it for longer.
+#[derive(AsRefStr, Clone, Copy, Debug, Default, EnumString, Eq, PartialEq, VariantArray)]
+#[strum(serialize_all = "lowercase")]
+pub enum Conviction {
+	/// A tenth of the balance is counted, without it being locked beyond the referendum.
+	#[default]
+	None,
+	/// The balance is counted once and locked for one enactment period.
+	Locked1x,
+	/// The balance is counted twice and locked for two enactment periods.
+	Locked2x,
+	/// The balance is counted three times and locked for four enactment periods.
+	Locked3x,
+	/// The balance is counted four times and locked for eight enactment periods.
+	Locked4x,
+	/// The balance is counted five times and locked for sixteen enactment periods.
+	Locked5x,
+	/// The balance is counted six times and locked for thirty-two enactment periods.
+	Locked6x,
+}
+
+/// A standard vote on a referendum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Vote {
+	/// Whether the vote is in favour of the referendum.
+	pub aye: bool,
+	/// The conviction of the vote.
+	pub conviction: Conviction,
+	/// The balance voted with, in the smallest unit of the token.
+	pub balance: u128,
+}
+
+impl Vote {
+	// The vote, encoded as a single byte of which the most significant bit indicates an aye.
+	fn byte(&self) -> u8 {
+		let conviction = Conviction::VARIANTS
+			.iter()
+			.position(|c| *c == self.conviction)
+			.expect("conviction is a variant") as u8;
+		match self.aye {
+			true => 0x80 | conviction,
+			false => conviction,
+		}
+	}
+}
+
+/// The hash of a preimage, via which a referendum refers to its proposal.
+///
+/// # Arguments
+/// * `call_data` - The SCALE-encoded call data of the proposal.
+pub fn preimage_hash(call_data: &[u8]) -> H256 {
+	H256(blake2_256(call_data))
+}
+
+/// Constructs a call to note the preimage of a proposal, so that a referendum can refer to it by
+/// its hash.
+///
+/// # Arguments
+/// * `call_data` - The SCALE-encoded call data of the proposal.
+pub fn note_preimage(call_data: &[u8]) -> DynamicPayload {
+	dynamic::tx("Preimage", "note_preimage", vec![Value::from_bytes(call_data)])
+}
+
+/// Whether the preimage with the specified hash has already been noted.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `hash` - The hash of the preimage.
+pub async fn is_preimage_noted(
+	client: &OnlineClient<SubstrateConfig>,
+	hash: H256,
+) -> Result<bool, Error> {
+	let storage = client.storage().at_latest().await?;
+	// The status of a preimage was previously stored as `StatusFor`.
+	for entry in ["RequestStatusFor", "StatusFor"] {
+		let key = vec![Value::from_bytes(hash.0)];
+		if let Ok(Some(_)) = storage.fetch(&dynamic::storage("Preimage", entry, key)).await {
+			return Ok(true);
+		}
+	}
+	Ok(false)
+}
+
+/// The tracks of the referenda pallet of a chain, ordered by identifier.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+pub fn tracks(client: &OnlineClient<SubstrateConfig>) -> Result<Vec<Track>, Error> {
+	let tracks = client
+		.constants()
+		.at(&dynamic::constant("Referenda", "Tracks"))?
+		.to_value()
+		.map_err(|e| Error::MetadataParsingError(e.to_string()))?;
+	let ValueDef::Composite(tracks) = &tracks.value else { return Ok(vec![]) };
+	let mut tracks: Vec<_> = tracks.values().filter_map(parse_track).collect();
+	tracks.sort_by_key(|t| t.id);
+	Ok(tracks)
+}
+
+/// Constructs a call to submit a referendum on the specified track, for a proposal whose
+/// preimage has been noted.
+///
+/// # Arguments
+/// * `track` - The track on which the referendum is decided.
+/// * `hash` - The hash of the preimage of the proposal.
+/// * `len` - The length of the preimage of the proposal.
+/// * `enactment_after` - The number of blocks after approval at which the proposal is enacted.
+pub fn submit_referendum(
+	track: &Track,
+	hash: H256,
+	len: u32,
+	enactment_after: u32,
+) -> DynamicPayload {
+	dynamic::tx(
+		"Referenda",
+		"submit",
+		vec![
+			track.origin(),
+			Value::named_variant(
+				"Lookup",
+				[("hash", Value::from_bytes(hash.0)), ("len", Value::u128(len.into()))],
+			),
+			Value::unnamed_variant("After", [Value::u128(enactment_after.into())]),
+		],
+	)
+}
+
+/// Constructs a call to place the decision deposit of a referendum, so that it can start being
+/// decided.
+///
+/// # Arguments
+/// * `index` - The index of the referendum.
+pub fn place_decision_deposit(index: u32) -> DynamicPayload {
+	dynamic::tx("Referenda", "place_decision_deposit", vec![Value::u128(index.into())])
+}
+
+/// Constructs a call to vote on a referendum.
+///
+/// # Arguments
+/// * `index` - The index of the referendum.
+/// * `vote` - The vote.
+pub fn vote(index: u32, vote: &Vote) -> DynamicPayload {
+	dynamic::tx(
+		"ConvictionVoting",
+		"vote",
+		vec![
+			Value::u128(index.into()),
+			Value::named_variant(
+				"Standard",
+				[
+					("vote", Value::unnamed_composite([Value::u128(vote.byte().into())])),
+					("balance", Value::u128(vote.balance)),
+				],
+			),
+		],
+	)
+}
+
+/// The index of the referendum submitted by an extrinsic, from its events.
+///
+/// # Arguments
+/// * `events` - The events of the extrinsic.
+pub fn submitted_referendum(
+	events: &ExtrinsicEvents<SubstrateConfig>,
+) -> Result<Option<u32>, Error> {
+	for event in events.iter() {
+		let event = event?;
+		if event.pallet_name() == "Referenda" && event.variant_name() == "Submitted" {
+			let fields = event.field_values().map_err(subxt::Error::from)?;
+			return Ok(field(&fields, "index").and_then(|i| i.as_u128()).map(|i| i as u32));
+		}
+	}
+	Ok(None)
+}
+
+// Parses a track from its identifier and information.
+fn parse_track<T>(track: &Value<T>) -> Option<Track> {
+	let ValueDef::Composite(Composite::Unnamed(track)) = &track.value else { return None };
+	let [id, info] = &track[..] else { return None };
+	let ValueDef::Composite(info) = &info.value else { return None };
+	Some(Track {
+		id: id.as_u128()? as u16,
+		name: name(field(info, "name")?)?,
+		decision_deposit: field(info, "decision_deposit")?.as_u128()?,
+	})
+}
+
+// The name of a track, either as a string or, for more recent runtimes, as bytes padded with
+// zeros.
+fn name<T>(name: &Value<T>) -> Option<String> {
+	match &name.value {
+		ValueDef::Primitive(Primitive::String(name)) => Some(name.clone()),
+		ValueDef::Composite(bytes) => {
+			let bytes = bytes
+				.values()
+				.map(|b| b.as_u128().map(|b| b as u8))
+				.collect::<Option<Vec<_>>>()?;
+			let name = String::from_utf8(bytes).ok()?;
+			Some(name.trim_end_matches('\0').to_string())
+		},
+		_ => None,
+	}
+}
+
+// Converts the snake case name of a track to the camel case name of its origin, e.g.
+// `small_tipper` to `SmallTipper`.
+fn camel_case(name: &str) -> String {
+	name.split('_')
+		.map(|word| {
+			let mut chars = word.chars();
+			chars
+				.next()
+				.map(|c| c.to_uppercase().chain(chars).collect::<String>())
+				.unwrap_or_default()
+		})
+		.collect()
+}
+
+fn field<'a, T>(composite: &'a Composite<T>, name: &str) -> Option<&'a Value<T>> {
+	match composite {
+		Composite::Named(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+		Composite::Unnamed(_) => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	fn track(name: &str) -> Track {
+		Track { id: 0, name: name.into(), decision_deposit: 0 }
+	}
+
+	#[test]
+	fn track_origin_works() {
+		assert_eq!(
+			track("root").origin(),
+			Value::unnamed_variant("system", [Value::unnamed_variant("Root", [])])
+		);
+		assert_eq!(
+			track("small_tipper").origin(),
+			Value::unnamed_variant("Origins", [Value::unnamed_variant("SmallTipper", [])])
+		);
+	}
+
+	#[test]
+	fn vote_byte_works() {
+		let vote = |aye, conviction| Vote { aye, conviction, balance: 1 }.byte();
+		assert_eq!(vote(true, Conviction::None), 0x80);
+		assert_eq!(vote(true, Conviction::Locked1x), 0x81);
+		assert_eq!(vote(false, Conviction::Locked3x), 0x03);
+		assert_eq!(vote(false, Conviction::Locked6x), 0x06);
+		assert_eq!(Conviction::from_str("locked2x"), Ok(Conviction::Locked2x));
+		assert_eq!(Conviction::None.as_ref(), "none");
+	}
+
+	#[test]
+	fn calls_are_constructed() {
+		let hash = preimage_hash(&[0, 0]);
+		assert_eq!(hash, H256(blake2_256(&[0, 0])));
+		let calls = [
+			(note_preimage(&[0, 0]), "Preimage", "note_preimage"),
+			(submit_referendum(&track("root"), hash, 2, 10), "Referenda", "submit"),
+			(place_decision_deposit(1), "Referenda", "place_decision_deposit"),
+			(
+				vote(1, &Vote { aye: true, conviction: Conviction::None, balance: 1 }),
+				"ConvictionVoting",
+				"vote",
+			),
+		];
+		for (call, pallet, name) in calls {
+			assert_eq!((call.pallet_name(), call.call_name()), (pallet, name));
+		}
+		let submit = submit_referendum(&track("root"), hash, 2, 10);
+		let Composite::Unnamed(args) = submit.call_data() else {
+			panic!("expected unnamed arguments")
+		};
+		assert_eq!(
+			args[1],
+			Value::named_variant(
+				"Lookup",
+				[("hash", Value::from_bytes(hash.0)), ("len", Value::u128(2))]
+			)
+		);
+		assert_eq!(args[2], Value::unnamed_variant("After", [Value::u128(10)]));
+	}
+
+	#[test]
+	fn parse_track_works() {
+		let info = |name: Value| {
+			Value::unnamed_composite([
+				Value::u128(11),
+				Value::named_composite([
+					("name", name),
+					("max_deciding", Value::u128(10)),
+					("decision_deposit", Value::u128(1_000)),
+				]),
+			])
+		};
+		let expected = Track { id: 11, name: "small_tipper".into(), decision_deposit: 1_000 };
+		assert_eq!(parse_track(&info(Value::string("small_tipper"))), Some(expected.clone()));
+		// More recent runtimes specify the name as bytes padded with zeros.
+		let mut name = b"small_tipper".to_vec();
+		name.resize(25, 0);
+		let bytes = Value::unnamed_composite(name.into_iter().map(|b| Value::u128(b.into())));
+		assert_eq!(parse_track(&info(bytes)), Some(expected));
+		assert_eq!(parse_track(&Value::u128(11)), None);
+	}
+
+	#[test]
+	fn camel_case_works() {
+		assert_eq!(camel_case("root"), "Root");
+		assert_eq!(camel_case("whitelisted_caller"), "WhitelistedCaller");
+		assert_eq!(camel_case("big_spender"), "BigSpender");
+	}
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{errors::Error, Connection};
+use scale_value::{Value, ValueDef};
+use subxt::{config::Header, dynamic};
+
+/// A sample of the health of a chain, as observed by a node.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChainHealth {
+	/// The number of the best block.
+	pub best: u32,
+	/// The number of the last finalized block.
+	pub finalized: u32,
+	/// The number of peers of the node.
+	pub peers: usize,
+	/// Whether the node is expected to have peers, which is not the case for local chains.
+	pub should_have_peers: bool,
+	/// Whether the node is syncing.
+	pub is_syncing: bool,
+	/// The number of blocks of a parachain which have yet to be included by the relay chain, if
+	/// the chain is a parachain.
+	pub unincluded_segment: Option<usize>,
+}
+
+impl ChainHealth {
+	/// The number of blocks by which finality lags behind the best block.
+	pub fn lag(&self) -> u32 {
+		self.best.saturating_sub(self.finalized)
+	}
+}
+
+/// Samples the health of a chain via a node.
+pub struct HealthMonitor {
+	connection: Connection,
+}
+
+impl HealthMonitor {
+	/// Connects to a node of a chain.
+	///
+	/// # Arguments
+	/// * `url` - Endpoint of the node.
+	pub async fn connect(url: &str) -> Result<Self, Error> {
+		Ok(Self { connection: Connection::connect(url, None).await? })
+	}
+
+	/// Samples the current health of the chain.
+	pub async fn sample(&self) -> Result<ChainHealth, Error> {
+		let (client, methods) = (self.connection.client(), self.connection.methods());
+		let header = |hash| {
+			let methods = &methods;
+			async move {
+				methods
+					.chain_get_header(hash)
+					.await?
+					.ok_or(Error::BlockNotFound(format!("{hash:?}")))
+			}
+		};
+		let best = header(None).await?;
+		let finalized = header(Some(methods.chain_get_finalized_head().await?)).await?;
+		let health = methods.system_health().await?;
+		let segment = client
+			.storage()
+			.at(best.hash())
+			.fetch(&dynamic::storage("ParachainSystem", "UnincludedSegment", Vec::<Value>::new()))
+			.await;
+		let unincluded_segment = match segment {
+			Ok(Some(segment)) => segment.to_value().ok().map(|v| len(&v)),
+			// An empty segment is not stored.
+			Ok(None) => Some(0),
+			// The chain is not a parachain, or does not track unincluded blocks.
+			Err(_) => None,
+		};
+		Ok(ChainHealth {
+			best: best.number(),
+			finalized: finalized.number(),
+			peers: health.peers,
+			should_have_peers: health.should_have_peers,
+			is_syncing: health.is_syncing,
+			unincluded_segment,
+		})
+	}
+}
+
+// The number of items of a sequence.
+fn len<T>(value: &Value<T>) -> usize {
+	match &value.value {
+		ValueDef::Composite(items) => items.values().count(),
+		_ => 0,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn lag_works() {
+		assert_eq!(ChainHealth { best: 10, finalized: 8, ..Default::default() }.lag(), 2);
+		// The finalized block may be observed ahead of the best block between queries.
+		assert_eq!(ChainHealth { best: 8, finalized: 10, ..Default::default() }.lag(), 0);
+	}
+
+	#[test]
+	fn len_works() {
+		assert_eq!(len(&Value::unnamed_composite([Value::u128(1), Value::u128(2)])), 2);
+		assert_eq!(len(&Value::unnamed_composite([])), 0);
+		assert_eq!(len(&Value::u128(1)), 0);
+	}
+}
@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{errors::Error, Connection};
+use serde_json::{Map, Value as JsonValue};
+use subxt::{config::Header, dynamic, utils::H256};
+
+/// A summary of a chain and its runtime.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChainInfo {
+	/// The name of the chain.
+	pub name: String,
+	/// The name of the runtime specification.
+	pub spec_name: Option<String>,
+	/// The version of the runtime specification.
+	pub spec_version: u32,
+	/// The name of the runtime implementation.
+	pub impl_name: Option<String>,
+	/// The version of the runtime implementation.
+	pub impl_version: Option<u64>,
+	/// The version of the transaction format.
+	pub transaction_version: u32,
+	/// The symbol of the native token.
+	pub token_symbol: Option<String>,
+	/// The number of decimals of the native token.
+	pub token_decimals: Option<u8>,
+	/// The prefix of the SS58 addresses of the chain.
+	pub ss58_prefix: Option<u16>,
+	/// The minimum balance required for an account to exist, in the smallest unit of the token.
+	pub existential_deposit: Option<u128>,
+	/// The number and hash of the last finalized block.
+	pub finalized: (u32, H256),
+	/// The runtime APIs supported by the chain, by name.
+	pub runtime_apis: Vec<String>,
+}
+
+/// Summarizes a chain and its runtime.
+///
+/// # Arguments
+/// * `connection` - The connection to a node of the chain.
+pub async fn chain_info(connection: &Connection) -> Result<ChainInfo, Error> {
+	let (client, methods) = (connection.client(), connection.methods());
+	let version = methods.state_get_runtime_version(None).await?;
+	let properties = methods.system_properties().await?;
+	let (token_symbol, token_decimals, ss58_format) = parse_properties(&properties);
+	let hash = methods.chain_get_finalized_head().await?;
+	let number = methods
+		.chain_get_header(Some(hash))
+		.await?
+		.map(|h| h.number())
+		.unwrap_or_default();
+	let constant = |pallet: &str, name: &str| {
+		client
+			.constants()
+			.at(&dynamic::constant(pallet, name))
+			.ok()
+			.and_then(|c| c.to_value().ok())
+			.and_then(|v| v.as_u128())
+	};
+	let metadata = client.metadata();
+	let mut runtime_apis: Vec<_> =
+		metadata.runtime_api_traits().map(|api| api.name().to_string()).collect();
+	runtime_apis.sort();
+	let text = |key: &str| version.other.get(key).and_then(|v| v.as_str()).map(String::from);
+	Ok(ChainInfo {
+		name: methods.system_chain().await?,
+		spec_name: text("specName"),
+		spec_version: version.spec_version,
+		impl_name: text("implName"),
+		impl_version: version.other.get("implVersion").and_then(|v| v.as_u64()),
+		transaction_version: version.transaction_version,
+		token_symbol,
+		token_decimals,
+		ss58_prefix: constant("System", "SS58Prefix").map(|p| p as u16).or(ss58_format),
+		existential_deposit: constant("Balances", "ExistentialDeposit"),
+		finalized: (number, hash),
+		runtime_apis,
+	})
+}
+
+// Parses the token symbol, token decimals and SS58 format from the properties of a chain, using the
+// first token of chains with multiple tokens.
+fn parse_properties(
+	properties: &Map<String, JsonValue>,
+) -> (Option<String>, Option<u8>, Option<u16>) {
+	let first = |key: &str| match properties.get(key) {
+		Some(JsonValue::Array(values)) => values.first().cloned(),
+		value => value.cloned(),
+	};
+	let symbol = first("tokenSymbol").and_then(|s| s.as_str().map(String::from));
+	let decimals = first("tokenDecimals").and_then(|d| d.as_u64()).map(|d| d as u8);
+	let ss58_format = properties.get("ss58Format").and_then(|f| f.as_u64()).map(|f| f as u16);
+	(symbol, decimals, ss58_format)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn parse_properties_works() {
+		let properties = |value: JsonValue| value.as_object().unwrap().clone();
+		assert_eq!(
+			parse_properties(&properties(
+				json!({ "tokenSymbol": "PAS", "tokenDecimals": 10, "ss58Format": 0 })
+			)),
+			(Some("PAS".into()), Some(10), Some(0))
+		);
+		assert_eq!(
+			parse_properties(&properties(
+				json!({ "tokenSymbol": ["ACA", "AUSD"], "tokenDecimals": [12, 12] })
+			)),
+			(Some("ACA".into()), Some(12), None)
+		);
+		assert_eq!(parse_properties(&Map::new()), (None, None, None));
+	}
+}
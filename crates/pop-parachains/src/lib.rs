@@ -1,44 +1,153 @@
 // SPDX-License-Identifier: GPL-3.0
 
 #![doc = include_str!("../README.md")]
+mod assets;
+mod blocks;
 mod build;
 /// Provides functionality to construct, encode, sign, and submit chain extrinsics.
 mod call;
+mod connection;
+mod coretime;
 mod errors;
+mod fees;
+mod fuzz;
 mod generator;
+mod governance;
+mod health;
+mod info;
+mod monitoring;
+mod multisig;
+mod new_network_tests;
 mod new_pallet;
 mod new_parachain;
+mod new_runtime_tests;
+mod new_xcm_simulator;
+mod nfts;
+mod omni_node;
+mod paras;
+mod registry;
 mod relay;
+mod release;
+mod runtime;
+mod scheduler;
+mod storage;
+mod telemetry;
 mod templates;
+mod try_runtime;
+mod types;
 mod up;
 mod utils;
+mod wait;
+#[cfg(feature = "benchmark")]
+mod weights;
+mod xcm;
 
+pub use assets::{
+	create_asset, freeze_asset, mint_asset, registered_asset, registered_assets,
+	set_asset_metadata, transfer_asset, AssetId, RegisteredAsset,
+};
+pub use blocks::{
+	account_history, block_events, block_hash, inspect_block, AccountActivity, BlockDetails,
+	BlockEvent, BlockId, DecodedEvent, DecodedExtrinsic, EventFilter,
+};
 pub use build::{
 	binary_path, build_parachain, export_wasm_file, generate_genesis_state_file,
 	generate_plain_chain_spec, generate_raw_chain_spec, is_supported, ChainSpec,
 };
 pub use call::{
-	construct_extrinsic, construct_sudo_extrinsic, decode_call_data, encode_call_data,
+	batch_all, clear_cached_metadata, construct_extrinsic, construct_sudo_call_data,
+	construct_sudo_extrinsic, create_payload, decode_call_data, describe_dispatch_error,
+	display_extrinsic_events, encode_call_data,
 	metadata::{
 		action::{supported_actions, Action},
-		find_dispatchable_by_name, find_pallet_by_name,
-		params::Param,
-		parse_chain_metadata, Function, Pallet,
+		builder::{call_value, CallBuilder},
+		find_dispatchable_by_name, find_pallet_by_name, list_pallets,
+		params::{Param, ValueKind},
+		parse_chain_metadata, parse_dispatchable_arguments, parse_json_arguments, resolve_function,
+		storage::{parse_storage_items, query_storage, StorageItem},
+		v16::{MetadataV16, ViewFunction},
+		Function, Pallet, PalletItem,
 	},
-	set_up_client, sign_and_submit_extrinsic, submit_signed_extrinsic, CallData,
+	set_up_cached_client, set_up_client, sign_and_submit_extrinsic,
+	sign_and_submit_extrinsic_events, sign_and_submit_extrinsic_progress,
+	sign_and_submit_extrinsic_until, sign_and_submit_extrinsic_with, sign_ethereum_extrinsic,
+	sign_payload, signed_extrinsic, submit_signed_extrinsic, AccountFormat, CallData, CallManifest,
+	CallTemplate, CallTemplates, EthereumConfig, EthereumSignature, EthereumSigner,
+	ExtrinsicOptions, ManifestCall, SignedPayload, Submitted, UnsignedPayload, WaitFor,
+	ALITH_PRIVATE_KEY,
+};
+pub use connection::{Connection, Connections, KeepAlive, NativeToken};
+pub use coretime::{
+	assign, coretime_status, purchase, renew, sale_price, CoretimeStatus, Finality, RegionId,
+	Renewal, Sale, COMPLETE_MASK,
 };
 pub use errors::Error;
+pub use fees::{
+	call_fee, fee_multipliers, native_transfer, transfer_call, transfer_call_data, FeeDetails,
+};
+pub use fuzz::{FuzzHarness, FuzzOptions, FUZZ_TARGET};
+pub use governance::{
+	is_preimage_noted, note_preimage, place_decision_deposit, preimage_hash, submit_referendum,
+	submitted_referendum, tracks, vote, Conviction, Track, Vote,
+};
+pub use health::{ChainHealth, HealthMonitor};
 pub use indexmap::IndexSet;
+pub use info::{chain_info, ChainInfo};
+pub use monitoring::{Monitoring, ScrapeTarget};
+pub use multisig::{multisig_call, pending_approval, Multisig, MultisigCall, PendingApproval};
+pub use new_network_tests::create_network_tests;
 pub use new_pallet::{create_pallet_template, new_pallet_options::*, TemplatePalletConfig};
 pub use new_parachain::instantiate_template_dir;
+pub use new_runtime_tests::{create_runtime_tests, RuntimeTestsConfig};
+pub use new_xcm_simulator::{create_xcm_simulator_tests, XcmSimulatorConfig};
+pub use nfts::{
+	create_collection, created_collection, mint_item, parse_attributes, set_nft_attribute,
+	set_nft_metadata, transfer_item,
+};
+pub use omni_node::{
+	build_omni_runtime, generate_omni_chain_spec, is_runtime_only, omni_node_generator,
+};
+pub use paras::{block_times, parachain_info, Core, CoreAssignment, ParachainInfo};
+pub use registry::{Bridge, Chain, ExplorerItem};
 pub use relay::{clear_dmpq, RelayChain};
+pub use release::{
+	build_release_runtime, bump_spec_version, migrations, previous_release, weight_changes,
+	RuntimeDigest, RuntimeRelease, WeightChange,
+};
+pub use runtime::RuntimeVersion;
+pub use scheduler::{
+	cancel_scheduled, resolve_schedule, schedule, scheduled_tasks, task_name, ScheduleAt,
+	ScheduledTask, ScheduledTaskId,
+};
+pub use storage::{diff_storage, dump_storage, state_diff, StorageChange, StorageEntry};
 // External export from subxt.
 pub use subxt::{
+	blocks::ExtrinsicEvents,
+	dynamic::Value,
+	ext::subxt_core::utils::AccountId20,
 	tx::{DynamicPayload, Payload},
-	OnlineClient, SubstrateConfig,
+	utils::{AccountId32, H256},
+	Metadata, OnlineClient, SubstrateConfig,
 };
+pub use telemetry::Telemetry;
 pub use templates::{Config, Parachain, Provider};
+pub use try_runtime::{
+	build_try_runtime, create_snapshot, create_snapshot_args, execute_block_args, finalized_block,
+	follow_chain_args, run_try_runtime, try_runtime_generator, State, TryStateSelect,
+};
+pub use types::{decode_as_type, decode_call, load_metadata, resolve_type, DecodedCall, TypeRef};
 pub use up::Zombienet;
 pub use utils::helpers::is_initial_endowment_valid;
+pub use wait::{wait_for, wait_for_client, Condition, ObservedBlock};
+#[cfg(feature = "benchmark")]
+pub use weights::{
+	assert_weight, build_benchmark_runtime, omni_bencher_generator, parse_benchmark_results,
+	ExecutionOptions, HeapPages, MeasuredWeight, WeightAssertion, WeightLimit, WeightLimits,
+};
+pub use xcm::{
+	dry_run_call, dry_run_xcm, estimate_fees, execution_fee, instructions, relative_location,
+	resolve_location, Asset, Beneficiary, DryRunEffects, Fee, ForwardedXcm, HopFees, RemoteCall,
+	Transfer, TransferType,
+};
 /// Information about the Node. External export from Zombienet-SDK.
 pub use zombienet_sdk::NetworkNode;
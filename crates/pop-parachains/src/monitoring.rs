@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	errors::Error,
+	generator::monitoring::{
+		DockerCompose, GrafanaDashboards, GrafanaDatasource, Prometheus, SubstrateDashboard,
+	},
+	utils::helpers::render_to_file,
+};
+use duct::cmd;
+use pop_common::find_free_port;
+use std::{
+	fs::create_dir_all,
+	path::{Path, PathBuf},
+};
+
+/// The port on which Prometheus is published, unless already in use.
+const PROMETHEUS_PORT: u16 = 9090;
+/// The port on which Grafana is published, unless already in use.
+const GRAFANA_PORT: u16 = 3000;
+/// The unique identifier of the provisioned Substrate dashboard.
+const DASHBOARD_UID: &str = "substrate";
+
+/// A node whose metrics are scraped via its Prometheus endpoint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScrapeTarget {
+	/// The name of the node.
+	pub node: String,
+	/// The chain the node belongs to.
+	pub chain: String,
+	/// The port of the Prometheus endpoint of the node.
+	pub port: u16,
+}
+
+impl ScrapeTarget {
+	/// The target for a node, based on the port of its Prometheus endpoint as specified via the
+	/// `--prometheus-port` argument of the node.
+	///
+	/// # Arguments
+	/// * `node` - The name of the node.
+	/// * `chain` - The chain the node belongs to.
+	/// * `args` - The arguments of the node.
+	pub fn from_args(node: &str, chain: &str, args: &[&str]) -> Option<Self> {
+		// Any arguments of an embedded relay chain node follow those of the node itself.
+		let port = args
+			.iter()
+			.position(|a| *a == "--prometheus-port")
+			.and_then(|i| args.get(i + 1))
+			.and_then(|p| p.parse().ok())?;
+		Some(Self { node: node.into(), chain: chain.into(), port })
+	}
+}
+
+/// A Prometheus and Grafana stack, run via Docker Compose, which monitors the nodes of a local
+/// network.
+#[derive(Debug, PartialEq)]
+pub struct Monitoring {
+	path: PathBuf,
+	prometheus_port: u16,
+	grafana_port: u16,
+}
+
+impl Monitoring {
+	/// Generates the configuration of the stack within the specified directory, with Prometheus
+	/// scraping the targets and Grafana provisioned with a Substrate dashboard.
+	///
+	/// # Arguments
+	/// * `path` - The directory in which the configuration is generated.
+	/// * `targets` - The nodes to be scraped.
+	pub fn create(path: &Path, targets: &[ScrapeTarget]) -> Result<Self, Error> {
+		let monitoring = Self {
+			path: path.to_path_buf(),
+			prometheus_port: find_free_port(Some(PROMETHEUS_PORT)),
+			grafana_port: find_free_port(Some(GRAFANA_PORT)),
+		};
+		let grafana = path.join("grafana");
+		create_dir_all(grafana.join("provisioning/datasources"))?;
+		create_dir_all(grafana.join("provisioning/dashboards"))?;
+		create_dir_all(grafana.join("dashboards"))?;
+		render_to_file(
+			&path.join("docker-compose.yml"),
+			DockerCompose {
+				prometheus_port: monitoring.prometheus_port,
+				grafana_port: monitoring.grafana_port,
+			},
+		)?;
+		render_to_file(&path.join("prometheus.yml"), Prometheus { targets })?;
+		render_to_file(
+			&grafana.join("provisioning/datasources/prometheus.yml"),
+			GrafanaDatasource {},
+		)?;
+		render_to_file(
+			&grafana.join("provisioning/dashboards/substrate.yml"),
+			GrafanaDashboards {},
+		)?;
+		render_to_file(&grafana.join("dashboards/substrate.json"), SubstrateDashboard {})?;
+		Ok(monitoring)
+	}
+
+	/// Starts the stack in the background.
+	pub fn start(&self) -> Result<(), Error> {
+		self.compose(&["up", "--detach"])
+	}
+
+	/// Stops the stack, removing its containers.
+	pub fn stop(&self) -> Result<(), Error> {
+		self.compose(&["down"])
+	}
+
+	/// The URL of the provisioned Substrate dashboard.
+	pub fn dashboard_url(&self) -> String {
+		format!("http://localhost:{}/d/{DASHBOARD_UID}", self.grafana_port)
+	}
+
+	/// The URL of Prometheus.
+	pub fn prometheus_url(&self) -> String {
+		format!("http://localhost:{}", self.prometheus_port)
+	}
+
+	fn compose(&self, args: &[&str]) -> Result<(), Error> {
+		let file = self.path.join("docker-compose.yml").display().to_string();
+		let mut compose_args = vec!["compose", "--file", &file, "--project-name", "pop-monitoring"];
+		compose_args.extend(args);
+		cmd("docker", compose_args)
+			.stdout_null()
+			.stderr_null()
+			.run()
+			.map_err(|e| Error::MonitoringError(format!("unable to run docker compose: {e}")))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs::read_to_string;
+	use tempfile::tempdir;
+
+	#[test]
+	fn scrape_target_from_args_works() {
+		let args = [
+			"--chain",
+			"spec.json",
+			"--prometheus-external",
+			"--prometheus-port",
+			"33445",
+			"--",
+			"--prometheus-port",
+			"33446",
+		];
+		assert_eq!(
+			ScrapeTarget::from_args("collator", "pop", &args),
+			Some(ScrapeTarget { node: "collator".into(), chain: "pop".into(), port: 33445 })
+		);
+		assert_eq!(ScrapeTarget::from_args("alice", "paseo-local", &["--chain", "paseo"]), None);
+	}
+
+	#[test]
+	fn create_works() -> Result<(), Error> {
+		let temp_dir = tempdir()?;
+		let targets = [
+			ScrapeTarget { node: "alice".into(), chain: "paseo-local".into(), port: 33445 },
+			ScrapeTarget { node: "collator".into(), chain: "pop".into(), port: 33446 },
+		];
+		let monitoring = Monitoring::create(temp_dir.path(), &targets)?;
+		let prometheus = read_to_string(temp_dir.path().join("prometheus.yml"))?;
+		assert!(prometheus.contains(
+			r#"      - targets: ["host.docker.internal:33445"]
+        labels:
+          chain: "paseo-local"
+          node: "alice""#
+		));
+		assert!(prometheus.contains(r#"["host.docker.internal:33446"]"#));
+		let compose = read_to_string(temp_dir.path().join("docker-compose.yml"))?;
+		assert!(compose.contains(&format!("\"{}:3000\"", monitoring.grafana_port)));
+		assert!(compose.contains(&format!("\"{}:9090\"", monitoring.prometheus_port)));
+		let dashboard = read_to_string(temp_dir.path().join("grafana/dashboards/substrate.json"))?;
+		let dashboard: serde_json::Value = serde_json::from_str(&dashboard)?;
+		assert_eq!(dashboard["uid"], DASHBOARD_UID);
+		assert!(temp_dir.path().join("grafana/provisioning/datasources/prometheus.yml").exists());
+		assert!(temp_dir.path().join("grafana/provisioning/dashboards/substrate.yml").exists());
+		assert_eq!(
+			monitoring.dashboard_url(),
+			format!("http://localhost:{}/d/substrate", monitoring.grafana_port)
+		);
+		Ok(())
+	}
+}
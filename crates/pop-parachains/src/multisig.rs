@@ -0,0 +1,326 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{errors::Error, fees::call_weight, DynamicPayload};
+use scale_value::{Composite, ValueDef};
+use sp_core::blake2_256;
+use std::{
+	fmt::{Display, Formatter},
+	str::FromStr,
+};
+use subxt::{
+	dynamic::{self, Value},
+	ext::codec::Encode,
+	tx::Payload,
+	utils::{AccountId32, H256},
+	OnlineClient, SubstrateConfig,
+};
+
+/// The prefix of the entropy from which the account of a multisig is derived by the `Multisig`
+/// pallet.
+const MULTISIG_PREFIX: &[u8; 16] = b"modlpy/utilisuba";
+
+/// The signatories of a multisig account and the number of them required to dispatch a call,
+/// e.g. `5Grw...,5FHn...,5FLS...;2`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Multisig {
+	/// The signatories of the account, sorted.
+	pub signatories: Vec<AccountId32>,
+	/// The number of signatories required to approve a call.
+	pub threshold: u16,
+}
+
+impl Multisig {
+	/// Creates a multisig account.
+	///
+	/// # Arguments
+	/// * `signatories` - The signatories of the account, in any order.
+	/// * `threshold` - The number of signatories required to approve a call.
+	pub fn new(mut signatories: Vec<AccountId32>, threshold: u16) -> Result<Self, Error> {
+		signatories.sort();
+		signatories.dedup();
+		if signatories.len() < 2 {
+			return Err(Error::MultisigError("at least two signatories are required".into()));
+		}
+		if threshold == 0 || threshold as usize > signatories.len() {
+			return Err(Error::MultisigError(format!(
+				"the threshold must be between 1 and {}",
+				signatories.len()
+			)));
+		}
+		Ok(Self { signatories, threshold })
+	}
+
+	/// The account of the multisig, as derived by the `Multisig` pallet.
+	pub fn account(&self) -> AccountId32 {
+		AccountId32((MULTISIG_PREFIX, &self.signatories, self.threshold).using_encoded(blake2_256))
+	}
+
+	/// The signatories other than the specified one, as expected by the `Multisig` pallet.
+	///
+	/// # Arguments
+	/// * `signatory` - The signatory approving a call.
+	pub fn other_signatories(&self, signatory: &AccountId32) -> Result<Vec<AccountId32>, Error> {
+		if !self.signatories.contains(signatory) {
+			return Err(Error::MultisigError(format!("{signatory} is not a signatory")));
+		}
+		Ok(self.signatories.iter().filter(|s| *s != signatory).cloned().collect())
+	}
+}
+
+impl FromStr for Multisig {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let invalid = || {
+			Error::MultisigError(format!(
+				"expected the signatories and threshold as `addr1,addr2,...;threshold`, not `{s}`"
+			))
+		};
+		let (signatories, threshold) = s.split_once(';').ok_or_else(invalid)?;
+		let signatories = signatories
+			.split(',')
+			.map(|s| AccountId32::from_str(s.trim()).map_err(|_| invalid()))
+			.collect::<Result<_, _>>()?;
+		Self::new(signatories, threshold.trim().parse().map_err(|_| invalid())?)
+	}
+}
+
+impl Display for Multisig {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let signatories: Vec<_> = self.signatories.iter().map(|s| s.to_string()).collect();
+		write!(f, "{};{}", signatories.join(","), self.threshold)
+	}
+}
+
+/// A call awaiting the approval of the signatories of a multisig account.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PendingApproval {
+	/// The block at which the call was first approved.
+	pub height: u32,
+	/// The index of the extrinsic which first approved the call within its block.
+	pub index: u32,
+	/// The signatories which have approved the call.
+	pub approvals: Vec<AccountId32>,
+}
+
+/// A call wrapped such that it is approved by a signatory of a multisig account.
+pub struct MultisigCall {
+	/// The call to be submitted by the signatory.
+	pub xt: DynamicPayload,
+	/// The account of the multisig.
+	pub account: AccountId32,
+	/// The hash of the call being approved.
+	pub call_hash: H256,
+	/// The number of approvals of the call, once submitted.
+	pub approvals: usize,
+	/// Whether the call is dispatched once submitted, as the threshold is reached.
+	pub executes: bool,
+}
+
+/// Queries the approvals of a call by the signatories of a multisig account, if pending.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `account` - The account of the multisig.
+/// * `call_hash` - The hash of the call.
+pub async fn pending_approval(
+	client: &OnlineClient<SubstrateConfig>,
+	account: &AccountId32,
+	call_hash: &H256,
+) -> Result<Option<PendingApproval>, Error> {
+	let address = dynamic::storage(
+		"Multisig",
+		"Multisigs",
+		vec![Value::from_bytes(account.0), Value::from_bytes(call_hash.0)],
+	);
+	let Some(value) = client.storage().at_latest().await?.fetch(&address).await? else {
+		return Ok(None);
+	};
+	let value = value
+		.to_value()
+		.map_err(|e| Error::MetadataParsingError(format!("multisig: {e}")))?;
+	parse_pending_approval(&value)
+		.map(Some)
+		.ok_or_else(|| Error::MetadataParsingError("multisig".into()))
+}
+
+/// Wraps a call such that it is approved by a signatory of a multisig account: via
+/// `approve_as_multi` while further approvals are required, otherwise via `as_multi` so that the
+/// call is dispatched.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `multisig` - The multisig account.
+/// * `signatory` - The signatory approving the call.
+/// * `xt` - The call to be dispatched by the multisig account.
+pub async fn multisig_call(
+	client: &OnlineClient<SubstrateConfig>,
+	multisig: &Multisig,
+	signatory: &AccountId32,
+	xt: DynamicPayload,
+) -> Result<MultisigCall, Error> {
+	let others = multisig.other_signatories(signatory)?;
+	let others = Value::unnamed_composite(others.iter().map(|s| Value::from_bytes(s.0)));
+	let account = multisig.account();
+	let call_data = xt
+		.encode_call_data(&client.metadata())
+		.map_err(|e| Error::CallDataEncodingError(e.to_string()))?;
+	let call_hash = H256(blake2_256(&call_data));
+	if multisig.threshold == 1 {
+		let xt = dynamic::tx("Multisig", "as_multi_threshold_1", vec![others, xt.into_value()]);
+		return Ok(MultisigCall { xt, account, call_hash, approvals: 1, executes: true });
+	}
+
+	let pending = pending_approval(client, &account, &call_hash).await?;
+	if pending.as_ref().is_some_and(|p| p.approvals.contains(signatory)) {
+		return Err(Error::MultisigError(format!("{signatory} has already approved the call")));
+	}
+	let approvals = pending.as_ref().map_or(0, |p| p.approvals.len()) + 1;
+	let timepoint = match &pending {
+		Some(pending) => Value::unnamed_variant(
+			"Some",
+			[Value::named_composite([
+				("height", Value::u128(pending.height.into())),
+				("index", Value::u128(pending.index.into())),
+			])],
+		),
+		None => Value::unnamed_variant("None", []),
+	};
+	let executes = approvals >= multisig.threshold as usize;
+	let (call, call_or_hash, (ref_time, proof_size)) = match executes {
+		true => ("as_multi", xt.into_value(), call_weight(client, &call_data).await?),
+		false => ("approve_as_multi", Value::from_bytes(call_hash.0), (0, 0)),
+	};
+	let max_weight = Value::named_composite([
+		("ref_time", Value::u128(ref_time.into())),
+		("proof_size", Value::u128(proof_size.into())),
+	]);
+	let threshold = Value::u128(multisig.threshold.into());
+	let xt =
+		dynamic::tx("Multisig", call, vec![threshold, others, timepoint, call_or_hash, max_weight]);
+	Ok(MultisigCall { xt, account, call_hash, approvals, executes })
+}
+
+// Parses a pending multisig operation, as stored by the `Multisig` pallet.
+fn parse_pending_approval<T>(value: &Value<T>) -> Option<PendingApproval> {
+	let ValueDef::Composite(multisig) = &value.value else { return None };
+	let ValueDef::Composite(when) = &field(multisig, "when")?.value else { return None };
+	let number = |name: &str| field(when, name).and_then(|v| v.as_u128()?.try_into().ok());
+	Some(PendingApproval {
+		height: number("height")?,
+		index: number("index")?,
+		approvals: accounts(field(multisig, "approvals")?)?,
+	})
+}
+
+fn field<'a, T>(composite: &'a Composite<T>, name: &str) -> Option<&'a Value<T>> {
+	match composite {
+		Composite::Named(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+		Composite::Unnamed(_) => None,
+	}
+}
+
+// The accounts within a value, such as a bounded sequence of account identifiers.
+fn accounts<T>(value: &Value<T>) -> Option<Vec<AccountId32>> {
+	if let Some(account) = bytes(value).and_then(|b| <[u8; 32]>::try_from(b).ok()) {
+		return Some(vec![AccountId32(account)]);
+	}
+	let ValueDef::Composite(composite) = &value.value else { return None };
+	Some(composite.values().map(accounts).collect::<Option<Vec<_>>>()?.concat())
+}
+
+// The bytes of a value encoded as a sequence of bytes, such as an account identifier.
+fn bytes<T>(value: &Value<T>) -> Option<Vec<u8>> {
+	let ValueDef::Composite(Composite::Unnamed(values)) = &value.value else { return None };
+	values.iter().map(|v| v.as_u128().and_then(|b| u8::try_from(b).ok())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use anyhow::Result;
+
+	const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+	const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+	const CHARLIE: &str = "5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y";
+
+	fn account(address: &str) -> AccountId32 {
+		AccountId32::from_str(address).unwrap()
+	}
+
+	#[test]
+	fn parse_multisig_works() -> Result<()> {
+		let multisig: Multisig = format!("{CHARLIE}, {ALICE},{BOB};2").parse()?;
+		let mut expected = vec![account(ALICE), account(BOB), account(CHARLIE)];
+		expected.sort();
+		assert_eq!(multisig, Multisig { signatories: expected, threshold: 2 });
+		assert_eq!(multisig.to_string().parse::<Multisig>()?, multisig);
+		for invalid in [
+			format!("{ALICE},{BOB}"),
+			format!("{ALICE},{BOB};x"),
+			format!("{ALICE},wrong;2"),
+			format!("{ALICE},{ALICE};1"),
+			format!("{ALICE},{BOB};0"),
+			format!("{ALICE},{BOB};3"),
+		] {
+			assert!(matches!(invalid.parse::<Multisig>(), Err(Error::MultisigError(_))));
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn account_works() -> Result<()> {
+		let multisig = Multisig::new(vec![account(BOB), account(ALICE)], 2)?;
+		let mut entropy = MULTISIG_PREFIX.to_vec();
+		entropy.push(2 << 2); // The compact length of the signatories.
+		multisig.signatories.iter().for_each(|s| entropy.extend(s.0));
+		entropy.extend(2u16.to_le_bytes());
+		assert_eq!(multisig.account(), AccountId32(blake2_256(&entropy)));
+		// The account is independent of the order of the signatories, but not of the threshold.
+		assert_eq!(
+			Multisig::new(vec![account(ALICE), account(BOB)], 2)?.account(),
+			multisig.account()
+		);
+		assert_ne!(
+			Multisig::new(vec![account(ALICE), account(BOB)], 1)?.account(),
+			multisig.account()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn other_signatories_works() -> Result<()> {
+		let multisig = Multisig::new(vec![account(ALICE), account(BOB), account(CHARLIE)], 2)?;
+		let others = multisig.other_signatories(&account(BOB))?;
+		assert_eq!(others.len(), 2);
+		assert!(!others.contains(&account(BOB)));
+		assert!(others.windows(2).all(|w| w[0] <= w[1]));
+		let multisig = Multisig::new(vec![account(ALICE), account(BOB)], 2)?;
+		assert!(matches!(
+			multisig.other_signatories(&account(CHARLIE)),
+			Err(Error::MultisigError(_))
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn parse_pending_approval_works() {
+		// A bounded sequence of a single account identifier.
+		let account = Value::unnamed_composite([Value::from_bytes([1; 32])]);
+		let approvals = Value::unnamed_composite([Value::unnamed_composite([account])]);
+		let value = Value::named_composite([
+			(
+				"when",
+				Value::named_composite([("height", Value::u128(10)), ("index", Value::u128(1))]),
+			),
+			("deposit", Value::u128(100)),
+			("depositor", Value::from_bytes([1; 32])),
+			("approvals", approvals),
+		]);
+		assert_eq!(
+			parse_pending_approval(&value),
+			Some(PendingApproval { height: 10, index: 1, approvals: vec![AccountId32([1; 32])] })
+		);
+		assert_eq!(parse_pending_approval(&Value::u128(0)), None);
+	}
+}
@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	errors::Error,
+	generator::network_tests::{NetworkTests, NetworkTestsCargoToml, NetworkTestsLib},
+	utils::helpers::{relative_path, render_to_file},
+};
+use pop_common::find_workspace_toml;
+use std::{
+	fs::{create_dir_all, read_to_string},
+	path::Path,
+};
+use toml_edit::DocumentMut;
+
+/// Creates a crate of zombienet-sdk tests for the network defined by a network configuration
+/// file, with tests for the block production of each chain, any HRMP channels defined by the
+/// configuration and runtime upgrades.
+///
+/// # Arguments
+///
+/// * `path` - location where the crate will be created.
+/// * `network_config` - the network configuration file.
+pub fn create_network_tests(path: &Path, network_config: &Path) -> Result<(), Error> {
+	if !network_config.exists() {
+		return Err(Error::Config(format!(
+			"The {network_config:?} configuration file was not found"
+		)));
+	}
+	let config = read_to_string(network_config)?
+		.parse::<DocumentMut>()
+		.map_err(|err| Error::TomlError(err.into()))?;
+	let ids = |key: &str, fields: &[&str]| -> Vec<Vec<u32>> {
+		config
+			.get(key)
+			.and_then(|i| i.as_array_of_tables())
+			.map(|tables| {
+				tables
+					.iter()
+					.filter_map(|t| {
+						fields
+							.iter()
+							.map(|f| t.get(f).and_then(|v| v.as_integer()).map(|v| v as u32))
+							.collect()
+					})
+					.collect()
+			})
+			.unwrap_or_default()
+	};
+	let para_ids = ids("parachains", &["id"]).into_iter().map(|id| id[0]).collect();
+	let hrmp_channels = ids("hrmp_channels", &["sender", "recipient"])
+		.into_iter()
+		.map(|c| (c[0], c[1]))
+		.collect();
+
+	let name = path
+		.file_name()
+		.and_then(|name| name.to_str())
+		.ok_or(Error::PathError)?
+		.to_string();
+	let file_name = network_config
+		.file_name()
+		.and_then(|name| name.to_str())
+		.ok_or(Error::PathError)?
+		.to_string();
+	create_dir_all(path.join("src"))?;
+	create_dir_all(path.join("tests"))?;
+	let network_config_path = relative_path(&path.canonicalize()?, &network_config.canonicalize()?);
+
+	render_to_file(
+		&path.join("Cargo.toml"),
+		NetworkTestsCargoToml {
+			name: name.clone(),
+			network_config: file_name.clone(),
+			in_workspace: find_workspace_toml(path).is_some(),
+		},
+	)?;
+	render_to_file(
+		&path.join("src/lib.rs"),
+		NetworkTestsLib {
+			network_config: file_name,
+			network_config_path: network_config_path.display().to_string(),
+		},
+	)?;
+	render_to_file(
+		&path.join("tests/network.rs"),
+		NetworkTests { krate: name.replace('-', "_"), para_ids, hrmp_channels },
+	)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs::write;
+
+	#[test]
+	fn create_network_tests_works() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		let network_config = temp_dir.path().join("network.toml");
+		write(
+			&network_config,
+			r#"[relaychain]
+chain = "paseo-local"
+
+[[relaychain.nodes]]
+name = "alice"
+
+[[parachains]]
+id = 2000
+
+[[parachains.collators]]
+name = "collator-01"
+
+[[parachains]]
+id = 4001
+
+[[hrmp_channels]]
+sender = 2000
+recipient = 4001
+max_capacity = 1000
+max_message_size = 8000
+"#,
+		)?;
+		let path = temp_dir.path().join("network-tests");
+		create_network_tests(&path, &network_config)?;
+
+		let manifest = read_to_string(path.join("Cargo.toml"))?;
+		assert!(manifest.contains("name = \"network-tests\""));
+		assert!(manifest.contains("edition = \"2021\""));
+		assert!(manifest.contains("zombienet-sdk = \"0.2.25\""));
+		let lib = read_to_string(path.join("src/lib.rs"))?;
+		assert!(lib.contains("concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/../network.toml\")"));
+		let tests = read_to_string(path.join("tests/network.rs"))?;
+		assert!(tests.contains("use network_tests::*;"));
+		assert!(tests.contains("assert_blocks(collator(&network, 2000)?, BLOCKS, DEFAULT_TIMEOUT)"));
+		assert!(tests.contains("assert_blocks(collator(&network, 4001)?, BLOCKS, DEFAULT_TIMEOUT)"));
+		assert!(tests.contains("assert_hrmp_channel(relay_chain, 2000, 4001, DEFAULT_TIMEOUT)"));
+		assert!(tests.contains("network.parachain(2000)"));
+		Ok(())
+	}
+
+	#[test]
+	fn create_network_tests_without_parachains_works() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		let network_config = temp_dir.path().join("network.toml");
+		write(&network_config, "[relaychain]\nchain = \"paseo-local\"\n")?;
+		let path = temp_dir.path().join("network-tests");
+		create_network_tests(&path, &network_config)?;
+		let tests = read_to_string(path.join("tests/network.rs"))?;
+		assert!(tests.contains("async fn produces_blocks()"));
+		assert!(!tests.contains("opens_hrmp_channels"));
+		assert!(!tests.contains("upgrades_runtime"));
+		Ok(())
+	}
+
+	#[test]
+	fn create_network_tests_fails_without_network_config() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		assert!(matches!(
+			create_network_tests(
+				&temp_dir.path().join("network-tests"),
+				&temp_dir.path().join("network.toml")
+			),
+			Err(Error::Config(..))
+		));
+		Ok(())
+	}
+}
@@ -9,6 +9,7 @@ use anyhow::Result;
 use pop_common::{
 	git::Git,
 	templates::{extractor::extract_template_files, Template, Type},
+	Status,
 };
 use std::{fs, path::Path};
 use walkdir::WalkDir;
@@ -21,21 +22,23 @@ use walkdir::WalkDir;
 /// * `target` - location where the parachain will be created.
 /// * `tag_version` - version to use (`None` to use latest).
 /// * `config` - customization values to include in the new parachain.
+/// * `status` - used to observe the progress of fetching the template.
 pub fn instantiate_template_dir(
 	template: &Parachain,
 	target: &Path,
 	tag_version: Option<String>,
 	config: Config,
+	status: &impl Status,
 ) -> Result<Option<String>> {
 	sanitize(target)?;
 
 	if Provider::Pop.provides(template) {
-		return instantiate_standard_template(template, target, config, tag_version);
+		return instantiate_standard_template(template, target, config, tag_version, status);
 	}
 	if Provider::OpenZeppelin.provides(template) {
-		return instantiate_openzeppelin_template(template, target, tag_version);
+		return instantiate_openzeppelin_template(template, target, tag_version, status);
 	}
-	let tag = Git::clone_and_degit(template.repository_url()?, target, tag_version)?;
+	let tag = Git::clone_and_degit(template.repository_url()?, target, tag_version, status)?;
 	Ok(tag)
 }
 
@@ -44,11 +47,12 @@ pub fn instantiate_standard_template(
 	target: &Path,
 	config: Config,
 	tag_version: Option<String>,
+	status: &impl Status,
 ) -> Result<Option<String>> {
 	let temp_dir = ::tempfile::TempDir::new_in(std::env::temp_dir())?;
 	let source = temp_dir.path();
 
-	let tag = Git::clone_and_degit(template.repository_url()?, source, tag_version)?;
+	let tag = Git::clone_and_degit(template.repository_url()?, source, tag_version, status)?;
 
 	for entry in WalkDir::new(source) {
 		let entry = entry?;
@@ -82,11 +86,12 @@ pub fn instantiate_openzeppelin_template(
 	template: &Parachain,
 	target: &Path,
 	tag_version: Option<String>,
+	status: &impl Status,
 ) -> Result<Option<String>> {
 	let temp_dir = ::tempfile::TempDir::new_in(std::env::temp_dir())?;
 	let source = temp_dir.path();
 
-	let tag = Git::clone_and_degit(template.repository_url()?, source, tag_version)?;
+	let tag = Git::clone_and_degit(template.repository_url()?, source, tag_version, status)?;
 	let template_name = template.template_name_without_provider();
 	extract_template_files(template_name, temp_dir.path(), target, None)?;
 	Ok(tag)
@@ -105,7 +110,7 @@ mod tests {
 			decimals: 18,
 			initial_endowment: "1000000".to_string(),
 		};
-		instantiate_standard_template(&Parachain::Standard, temp_dir.path(), config, None)?;
+		instantiate_standard_template(&Parachain::Standard, temp_dir.path(), config, None, &())?;
 		Ok(temp_dir)
 	}
 
@@ -141,7 +146,7 @@ mod tests {
 	#[test]
 	fn test_parachain_instantiate_openzeppelin_template() -> Result<()> {
 		let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
-		instantiate_openzeppelin_template(&Parachain::OpenZeppelinEVM, temp_dir.path(), None)?;
+		instantiate_openzeppelin_template(&Parachain::OpenZeppelinEVM, temp_dir.path(), None, &())?;
 
 		let node_manifest =
 			pop_common::manifest::from_path(Some(&temp_dir.path().join("node/Cargo.toml")))
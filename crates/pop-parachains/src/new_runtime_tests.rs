@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	errors::Error,
+	generator::runtime_tests::{
+		RuntimeTestsCargoToml, RuntimeTestsLib, RuntimeTestsMockRelay, RuntimeTestsTests,
+	},
+	utils::helpers::{manifest_dependencies, relative_path, render_to_file},
+};
+use pop_common::{find_workspace_toml, manifest::from_path};
+use std::{
+	fs::create_dir_all,
+	path::{Path, PathBuf},
+};
+
+/// The dependencies of the tests: the name via which each is referenced, its package and the
+/// version used when not already provided by the workspace.
+const DEPENDENCIES: [(&str, &str, &str); 12] = [
+	("codec", "parity-scale-codec", "3.6.12"),
+	("cumulus-pallet-parachain-system", "cumulus-pallet-parachain-system", "0.15.0"),
+	("cumulus-primitives-core", "cumulus-primitives-core", "0.14.0"),
+	("cumulus-primitives-parachain-inherent", "cumulus-primitives-parachain-inherent", "0.14.0"),
+	("cumulus-test-relay-sproof-builder", "cumulus-test-relay-sproof-builder", "0.14.0"),
+	("frame-support", "frame-support", "36.0.0"),
+	("frame-system", "frame-system", "36.0.0"),
+	("pallet-timestamp", "pallet-timestamp", "35.0.0"),
+	("parachain-info", "staging-parachain-info", "0.15.0"),
+	("sp-consensus-aura", "sp-consensus-aura", "0.38.0"),
+	("sp-io", "sp-io", "37.0.0"),
+	("sp-runtime", "sp-runtime", "38.0.0"),
+];
+
+/// Configuration for the runtime tests.
+#[derive(Debug)]
+pub struct RuntimeTestsConfig {
+	/// The path to the runtime of the project.
+	pub runtime: PathBuf,
+	/// The identifier of the parachain.
+	pub para_id: u32,
+	/// The duration of a slot of the parachain, in milliseconds.
+	pub slot_duration: u64,
+}
+
+/// Creates a crate of unit tests for the runtime of the project, which execute blocks against a
+/// mock relay chain providing the validation data (and any messages) of each block.
+///
+/// # Arguments
+///
+/// * `path` - location where the crate will be created.
+/// * `config` - the configuration of the tests.
+pub fn create_runtime_tests(path: &Path, config: RuntimeTestsConfig) -> Result<(), Error> {
+	let runtime = from_path(Some(&config.runtime))?
+		.package
+		.map(|p| p.name)
+		.ok_or_else(|| Error::Config("the runtime manifest does not define a package".into()))?;
+	let name = path
+		.file_name()
+		.and_then(|name| name.to_str())
+		.ok_or(Error::PathError)?
+		.to_string();
+	create_dir_all(path.join("src"))?;
+	let runtime_path = relative_path(&path.canonicalize()?, &config.runtime.canonicalize()?);
+	let workspace = find_workspace_toml(path);
+	let dependencies = manifest_dependencies(workspace.as_deref(), &DEPENDENCIES)?;
+
+	render_to_file(
+		&path.join("Cargo.toml"),
+		RuntimeTestsCargoToml {
+			name,
+			runtime: runtime.clone(),
+			runtime_path: runtime_path.display().to_string(),
+			in_workspace: workspace.is_some(),
+			dependencies,
+		},
+	)?;
+	// The modules are rendered before the crate root, which is formatted along with them.
+	render_to_file(&path.join("src/mock_relay.rs"), RuntimeTestsMockRelay {})?;
+	render_to_file(&path.join("src/tests.rs"), RuntimeTestsTests {})?;
+	render_to_file(
+		&path.join("src/lib.rs"),
+		RuntimeTestsLib {
+			runtime: runtime.replace('-', "_"),
+			para_id: config.para_id,
+			slot_duration: config.slot_duration,
+		},
+	)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs::{read_to_string, write};
+
+	fn config(project: &Path) -> Result<RuntimeTestsConfig, Error> {
+		let runtime = project.join("runtime");
+		create_dir_all(&runtime)?;
+		write(
+			runtime.join("Cargo.toml"),
+			"[package]\nname = \"my-runtime\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+		)?;
+		Ok(RuntimeTestsConfig { runtime, para_id: 2000, slot_duration: 6000 })
+	}
+
+	#[test]
+	fn create_runtime_tests_works() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("runtime-tests");
+		create_runtime_tests(&path, config(temp_dir.path())?)?;
+
+		let manifest = read_to_string(path.join("Cargo.toml"))?;
+		assert!(manifest.contains("name = \"runtime-tests\""));
+		assert!(manifest.contains("edition = \"2021\""));
+		assert!(manifest.contains("my-runtime = { path = \"../runtime\" }"));
+		assert!(manifest.contains("cumulus-test-relay-sproof-builder = \"0.14.0\""));
+		assert!(manifest.contains(
+			"parachain-info = { package = \"staging-parachain-info\", version = \"0.15.0\" }"
+		));
+		let lib = read_to_string(path.join("src/lib.rs"))?;
+		assert!(lib.contains("pub(crate) use my_runtime as runtime;"));
+		assert!(lib.contains("pub(crate) const PARA_ID: u32 = 2000;"));
+		assert!(lib.contains("pub(crate) const SLOT_DURATION: u64 = 6000;"));
+		assert!(path.join("src/mock_relay.rs").exists());
+		assert!(path.join("src/tests.rs").exists());
+		Ok(())
+	}
+
+	#[test]
+	fn create_runtime_tests_fails_without_runtime() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		let config = RuntimeTestsConfig {
+			runtime: temp_dir.path().join("runtime"),
+			para_id: 2000,
+			slot_duration: 6000,
+		};
+		assert!(matches!(
+			create_runtime_tests(&temp_dir.path().join("runtime-tests"), config),
+			Err(Error::CommonError(pop_common::Error::ManifestPath(..)))
+		));
+		Ok(())
+	}
+}
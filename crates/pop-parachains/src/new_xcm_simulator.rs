@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	errors::Error,
+	generator::xcm_simulator::{
+		XcmSimulatorCargoToml, XcmSimulatorLib, XcmSimulatorRelayChain, XcmSimulatorTests,
+	},
+	utils::helpers::{manifest_dependencies, relative_path, render_to_file},
+};
+use pop_common::{find_workspace_toml, manifest::from_path};
+use std::{
+	fs::create_dir_all,
+	path::{Path, PathBuf},
+};
+
+/// The dependencies of the tests: the name via which each is referenced, its package and the
+/// version used when not already provided by the workspace.
+const DEPENDENCIES: [(&str, &str, &str); 15] = [
+	("codec", "parity-scale-codec", "3.6.12"),
+	("frame-support", "frame-support", "36.0.0"),
+	("frame-system", "frame-system", "36.0.0"),
+	("pallet-balances", "pallet-balances", "37.0.0"),
+	("pallet-message-queue", "pallet-message-queue", "39.0.0"),
+	("pallet-xcm", "pallet-xcm", "15.0.0"),
+	("parachain-info", "staging-parachain-info", "0.15.0"),
+	("polkadot-parachain-primitives", "polkadot-parachain-primitives", "13.0.0"),
+	("polkadot-runtime-parachains", "polkadot-runtime-parachains", "15.0.0"),
+	("sp-io", "sp-io", "37.0.0"),
+	("sp-runtime", "sp-runtime", "38.0.0"),
+	("xcm", "staging-xcm", "14.0.0"),
+	("xcm-builder", "staging-xcm-builder", "15.0.0"),
+	("xcm-executor", "staging-xcm-executor", "15.0.0"),
+	("xcm-simulator", "xcm-simulator", "15.0.0"),
+];
+
+/// Configuration for the XCM simulator tests.
+#[derive(Debug)]
+pub struct XcmSimulatorConfig {
+	/// The path to the runtime of the project.
+	pub runtime: PathBuf,
+	/// The path to the XCM configuration within the runtime, e.g.
+	/// `configs::xcm_config::XcmConfig`.
+	pub xcm_config: String,
+	/// The identifiers of the two parachains.
+	pub para_ids: (u32, u32),
+}
+
+/// Creates a crate of XCM simulator tests, consisting of a mock relay chain and two parachains
+/// using the runtime of the project.
+///
+/// # Arguments
+///
+/// * `path` - location where the crate will be created.
+/// * `config` - the configuration of the tests.
+pub fn create_xcm_simulator_tests(path: &Path, config: XcmSimulatorConfig) -> Result<(), Error> {
+	let runtime = from_path(Some(&config.runtime))?
+		.package
+		.map(|p| p.name)
+		.ok_or_else(|| Error::Config("the runtime manifest does not define a package".into()))?;
+	let name = path
+		.file_name()
+		.and_then(|name| name.to_str())
+		.ok_or(Error::PathError)?
+		.to_string();
+	create_dir_all(path.join("src"))?;
+	let runtime_path = relative_path(&path.canonicalize()?, &config.runtime.canonicalize()?);
+
+	let workspace = find_workspace_toml(path);
+	let dependencies = manifest_dependencies(workspace.as_deref(), &DEPENDENCIES)?;
+
+	render_to_file(
+		&path.join("Cargo.toml"),
+		XcmSimulatorCargoToml {
+			name,
+			runtime: runtime.clone(),
+			runtime_path: runtime_path.display().to_string(),
+			in_workspace: workspace.is_some(),
+			dependencies,
+		},
+	)?;
+	// The modules are rendered before the crate root, which is formatted along with them.
+	render_to_file(&path.join("src/relay_chain.rs"), XcmSimulatorRelayChain {})?;
+	render_to_file(&path.join("src/tests.rs"), XcmSimulatorTests {})?;
+	render_to_file(
+		&path.join("src/lib.rs"),
+		XcmSimulatorLib {
+			runtime: runtime.replace('-', "_"),
+			xcm_config: config.xcm_config,
+			para_a: config.para_ids.0,
+			para_b: config.para_ids.1,
+		},
+	)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs::{read_to_string, write};
+
+	fn runtime(project: &Path) -> Result<PathBuf, Error> {
+		let path = project.join("runtime");
+		create_dir_all(&path)?;
+		write(
+			path.join("Cargo.toml"),
+			"[package]\nname = \"my-runtime\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+		)?;
+		Ok(path)
+	}
+
+	fn config(runtime: PathBuf) -> XcmSimulatorConfig {
+		XcmSimulatorConfig {
+			runtime,
+			xcm_config: "configs::xcm_config::XcmConfig".into(),
+			para_ids: (2000, 2001),
+		}
+	}
+
+	#[test]
+	fn create_xcm_simulator_tests_works() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		let runtime = runtime(temp_dir.path())?;
+		let path = temp_dir.path().join("xcm-tests");
+		create_xcm_simulator_tests(&path, config(runtime))?;
+
+		let manifest = read_to_string(path.join("Cargo.toml"))?;
+		assert!(manifest.contains("name = \"xcm-tests\""));
+		assert!(manifest.contains("edition = \"2021\""));
+		assert!(manifest.contains("my-runtime = { path = \"../runtime\" }"));
+		assert!(manifest.contains("xcm-simulator = \"15.0.0\""));
+		assert!(manifest.contains("xcm = { package = \"staging-xcm\", version = \"14.0.0\" }"));
+		let lib = read_to_string(path.join("src/lib.rs"))?;
+		assert!(lib.contains("pub(crate) use my_runtime as parachain;"));
+		assert!(lib.contains("type XcmConfig = parachain::configs::xcm_config::XcmConfig;"));
+		assert!(lib.contains("pub(crate) const PARA_A: u32 = 2000;"));
+		assert!(lib.contains("pub(crate) const PARA_B: u32 = 2001;"));
+		assert!(path.join("src/relay_chain.rs").exists());
+		assert!(path.join("src/tests.rs").exists());
+		Ok(())
+	}
+
+	#[test]
+	fn create_xcm_simulator_tests_inherits_workspace_dependencies() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		write(
+			temp_dir.path().join("Cargo.toml"),
+			r#"[workspace]
+members = ["runtime"]
+
+[workspace.package]
+edition = "2021"
+
+[workspace.dependencies]
+frame-support = { version = "38.0.0", default-features = false }
+xcm = { version = "14.2.0", package = "staging-xcm", default-features = false }
+xcm-builder = { version = "17.0.0", default-features = false }
+"#,
+		)?;
+		let runtime = runtime(temp_dir.path())?;
+		let path = temp_dir.path().join("integration-tests");
+		create_xcm_simulator_tests(&path, config(runtime))?;
+
+		let manifest = read_to_string(path.join("Cargo.toml"))?;
+		assert!(manifest.contains("edition.workspace = true"));
+		assert!(manifest.contains("frame-support = { workspace = true }"));
+		assert!(manifest.contains("xcm = { workspace = true }"));
+		// The workspace dependency refers to a different package, so is not inherited.
+		assert!(manifest
+			.contains("xcm-builder = { package = \"staging-xcm-builder\", version = \"15.0.0\" }"));
+		Ok(())
+	}
+
+	#[test]
+	fn create_xcm_simulator_tests_fails_without_runtime() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		assert!(matches!(
+			create_xcm_simulator_tests(
+				&temp_dir.path().join("xcm-tests"),
+				config(temp_dir.path().join("runtime"))
+			),
+			Err(Error::CommonError(pop_common::Error::ManifestPath(..)))
+		));
+		Ok(())
+	}
+}
@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{errors::Error, DynamicPayload};
+use scale_value::Composite;
+use subxt::{
+	blocks::ExtrinsicEvents,
+	dynamic::{self, Value},
+	utils::AccountId32,
+	SubstrateConfig,
+};
+
+/// Constructs a call to create a collection of the `Nfts` pallet, administered by the specified
+/// account and with items only minted by its issuer.
+///
+/// # Arguments
+/// * `admin` - The account administering the collection.
+/// * `max_supply` - The maximum number of items in the collection, if limited.
+pub fn create_collection(admin: &AccountId32, max_supply: Option<u32>) -> DynamicPayload {
+	let config = Value::named_composite([
+		("settings", settings()),
+		("max_supply", option(max_supply.map(|s| Value::u128(s.into())))),
+		(
+			"mint_settings",
+			Value::named_composite([
+				("mint_type", Value::unnamed_variant("Issuer", [])),
+				("price", option(None)),
+				("start_block", option(None)),
+				("end_block", option(None)),
+				("default_item_settings", settings()),
+			]),
+		),
+	]);
+	dynamic::tx("Nfts", "create", vec![address(admin), config])
+}
+
+/// Constructs a call to mint an item of a collection of the `Nfts` pallet to an account.
+///
+/// # Arguments
+/// * `collection` - The identifier of the collection.
+/// * `item` - The identifier of the item.
+/// * `to` - The account receiving the item.
+pub fn mint_item(collection: u32, item: u32, to: &AccountId32) -> DynamicPayload {
+	dynamic::tx(
+		"Nfts",
+		"mint",
+		vec![Value::u128(collection.into()), Value::u128(item.into()), address(to), option(None)],
+	)
+}
+
+/// Constructs a call to set the metadata of an item or, if none specified, of the collection
+/// itself.
+///
+/// # Arguments
+/// * `collection` - The identifier of the collection.
+/// * `item` - The identifier of the item, if any.
+/// * `data` - The metadata, e.g. a link to a JSON file describing the item.
+pub fn set_nft_metadata(collection: u32, item: Option<u32>, data: &[u8]) -> DynamicPayload {
+	let collection = Value::u128(collection.into());
+	match item {
+		Some(item) => dynamic::tx(
+			"Nfts",
+			"set_metadata",
+			vec![collection, Value::u128(item.into()), Value::from_bytes(data)],
+		),
+		None => dynamic::tx(
+			"Nfts",
+			"set_collection_metadata",
+			vec![collection, Value::from_bytes(data)],
+		),
+	}
+}
+
+/// Constructs a call to set an attribute of an item or, if none specified, of the collection
+/// itself, in the namespace of the owner of the collection.
+///
+/// # Arguments
+/// * `collection` - The identifier of the collection.
+/// * `item` - The identifier of the item, if any.
+/// * `key` - The key of the attribute.
+/// * `value` - The value of the attribute.
+pub fn set_nft_attribute(
+	collection: u32,
+	item: Option<u32>,
+	key: &str,
+	value: &str,
+) -> DynamicPayload {
+	dynamic::tx(
+		"Nfts",
+		"set_attribute",
+		vec![
+			Value::u128(collection.into()),
+			option(item.map(|i| Value::u128(i.into()))),
+			Value::unnamed_variant("CollectionOwner", []),
+			Value::from_bytes(key),
+			Value::from_bytes(value),
+		],
+	)
+}
+
+/// Constructs a call to transfer an item of a collection of the `Nfts` pallet to an account.
+///
+/// # Arguments
+/// * `collection` - The identifier of the collection.
+/// * `item` - The identifier of the item.
+/// * `dest` - The account receiving the item.
+pub fn transfer_item(collection: u32, item: u32, dest: &AccountId32) -> DynamicPayload {
+	dynamic::tx(
+		"Nfts",
+		"transfer",
+		vec![Value::u128(collection.into()), Value::u128(item.into()), address(dest)],
+	)
+}
+
+/// The identifier of the collection created by an extrinsic, from its events.
+///
+/// # Arguments
+/// * `events` - The events of the extrinsic.
+pub fn created_collection(events: &ExtrinsicEvents<SubstrateConfig>) -> Result<Option<u32>, Error> {
+	for event in events.iter() {
+		let event = event?;
+		if event.pallet_name() == "Nfts" && event.variant_name() == "Created" {
+			let fields = event.field_values().map_err(subxt::Error::from)?;
+			return Ok(field(&fields, "collection").and_then(|c| c.as_u128()).map(|c| c as u32));
+		}
+	}
+	Ok(None)
+}
+
+/// Parses the attributes of an item or collection from a JSON object, of which any values which
+/// are not strings are used as their JSON representation.
+///
+/// # Arguments
+/// * `json` - The JSON object, e.g. `{"color": "red", "size": 3}`.
+pub fn parse_attributes(json: &str) -> Result<Vec<(String, String)>, Error> {
+	let attributes: serde_json::Map<String, serde_json::Value> = serde_json::from_str(json)?;
+	Ok(attributes
+		.into_iter()
+		.map(|(key, value)| match value {
+			serde_json::Value::String(value) => (key, value),
+			value => (key, value.to_string()),
+		})
+		.collect())
+}
+
+// The address of an account, as expected by the calls of the `Nfts` pallet.
+fn address(account: &AccountId32) -> Value {
+	Value::unnamed_variant("Id", [Value::from_bytes(account.0)])
+}
+
+// The default settings of a collection or item, with none of the settings disabled.
+fn settings() -> Value {
+	Value::unnamed_composite([Value::u128(0)])
+}
+
+fn option(value: Option<Value>) -> Value {
+	match value {
+		Some(value) => Value::unnamed_variant("Some", [value]),
+		None => Value::unnamed_variant("None", []),
+	}
+}
+
+fn field<'a, T>(composite: &'a Composite<T>, name: &str) -> Option<&'a Value<T>> {
+	match composite {
+		Composite::Named(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+		Composite::Unnamed(_) => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use scale_value::ValueDef;
+
+	#[test]
+	fn calls_are_constructed() {
+		let alice = AccountId32([1; 32]);
+		let calls = [
+			(create_collection(&alice, Some(10)), "create"),
+			(mint_item(0, 1, &alice), "mint"),
+			(set_nft_metadata(0, Some(1), b"ipfs://item"), "set_metadata"),
+			(set_nft_metadata(0, None, b"ipfs://collection"), "set_collection_metadata"),
+			(set_nft_attribute(0, Some(1), "color", "red"), "set_attribute"),
+			(transfer_item(0, 1, &alice), "transfer"),
+		];
+		for (call, name) in calls {
+			assert_eq!((call.pallet_name(), call.call_name()), ("Nfts", name));
+		}
+		let Composite::Unnamed(args) =
+			set_nft_attribute(0, None, "color", "red").call_data().clone()
+		else {
+			panic!("expected unnamed arguments")
+		};
+		assert_eq!(args[1], Value::unnamed_variant("None", []));
+		assert_eq!(args[3], Value::from_bytes("color"));
+	}
+
+	#[test]
+	fn create_collection_limits_supply() {
+		let config = |max_supply| {
+			let Composite::Unnamed(args) =
+				create_collection(&AccountId32([1; 32]), max_supply).call_data().clone()
+			else {
+				panic!("expected unnamed arguments")
+			};
+			let ValueDef::Composite(config) = args[1].value.clone() else {
+				panic!("expected the config")
+			};
+			field(&config, "max_supply").cloned()
+		};
+		assert_eq!(config(Some(10)), Some(Value::unnamed_variant("Some", [Value::u128(10)])));
+		assert_eq!(config(None), Some(Value::unnamed_variant("None", [])));
+	}
+
+	#[test]
+	fn parse_attributes_works() -> Result<(), Error> {
+		assert_eq!(
+			parse_attributes(r#"{"color": "red", "size": 3, "rare": true}"#)?,
+			vec![
+				("color".to_string(), "red".to_string()),
+				("size".to_string(), "3".to_string()),
+				("rare".to_string(), "true".to_string()),
+			]
+		);
+		assert!(matches!(parse_attributes(r#"["red"]"#), Err(Error::JsonError(_))));
+		Ok(())
+	}
+}
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	build::{build_runtime, check_command_exists, is_supported},
+	errors::Error,
+};
+use duct::cmd;
+use pop_common::sourcing::{traits::Source as _, Binary, Source};
+use std::{
+	env::consts::{ARCH, OS},
+	path::{Path, PathBuf},
+};
+use strum::{EnumProperty, VariantArray};
+
+/// The `polkadot-omni-node` binary, used to run the runtime of a project which has no node of
+/// its own.
+#[derive(Debug, EnumProperty, PartialEq, VariantArray)]
+pub(crate) enum OmniNode {
+	#[strum(props(
+		Repository = "https://github.com/paritytech/polkadot-sdk",
+		Binary = "polkadot-omni-node",
+		Fallback = "polkadot-stable2412"
+	))]
+	OmniNode,
+}
+
+impl pop_common::sourcing::traits::Source for OmniNode {}
+
+/// Generates the `polkadot-omni-node` binary, which is either downloaded from the latest release
+/// where a pre-built binary is available for the platform or otherwise built from source.
+///
+/// # Arguments
+/// * `cache` - The cache directory path.
+pub fn omni_node_generator(cache: PathBuf) -> Binary {
+	let node = OmniNode::OmniNode;
+	let name = node.binary().to_string();
+	let repository = node.repository();
+	let source = match (OS, ARCH) {
+		("linux", "x86_64") => Source::Url {
+			url: format!("{repository}/releases/latest/download/{name}"),
+			name: name.clone(),
+		},
+		_ => Source::Git {
+			url: repository.parse().expect("repository url is valid"),
+			reference: Some(node.fallback().to_string()),
+			manifest: None,
+			package: name.clone(),
+			artifacts: vec![name.clone()],
+		},
+	};
+	Binary::Source { name, source, cache }
+}
+
+/// Determines whether the project at the supplied path is a chain project without a node crate,
+/// and so is to be run via `polkadot-omni-node`.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+pub fn is_runtime_only(project: &Path) -> Result<bool, Error> {
+	Ok(is_supported(Some(project))? && !project.join("node").join("Cargo.toml").exists())
+}
+
+/// Builds the runtime of a project to be run via `polkadot-omni-node`, returning the path to the
+/// resulting WebAssembly blob.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+/// * `runtime` - The path to the runtime crate.
+pub fn build_omni_runtime(project: &Path, runtime: &Path) -> Result<PathBuf, Error> {
+	build_runtime(project, runtime, None)
+}
+
+/// Generates the plain text chain specification for a parachain from its runtime, via the
+/// `chain-spec-builder` command of `polkadot-omni-node`.
+///
+/// # Arguments
+/// * `binary_path` - The path to the `polkadot-omni-node` binary.
+/// * `runtime` - The path to the WebAssembly blob of the runtime.
+/// * `plain_chain_spec` - Location of the plain chain specification file to be generated.
+/// * `para_id` - The parachain identifier.
+/// * `relay_chain` - The relay chain the parachain connects to.
+/// * `chain` - The chain specification (e.g. dev or local), used to select the genesis preset of
+///   the runtime.
+pub fn generate_omni_chain_spec(
+	binary_path: &Path,
+	runtime: &Path,
+	plain_chain_spec: &Path,
+	para_id: u32,
+	relay_chain: &str,
+	chain: &str,
+) -> Result<(), Error> {
+	check_command_exists(binary_path, "chain-spec-builder")?;
+	if !runtime.exists() {
+		return Err(Error::MissingBinary(runtime.display().to_string()));
+	}
+	cmd(
+		binary_path,
+		vec![
+			"chain-spec-builder",
+			"--chain-spec-path",
+			&plain_chain_spec.display().to_string(),
+			"create",
+			"--relay-chain",
+			relay_chain,
+			"--para-id",
+			&para_id.to_string(),
+			"--runtime",
+			&runtime.display().to_string(),
+			"named-preset",
+			preset(chain),
+		],
+	)
+	.stdout_null()
+	.stderr_null()
+	.run()?;
+	Ok(())
+}
+
+// The genesis preset of a runtime corresponding to a chain, as per the presets defined by the
+// templates of the SDK.
+fn preset(chain: &str) -> &str {
+	match chain {
+		"dev" => "development",
+		"local" => "local_testnet",
+		preset => preset,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempfile::tempdir;
+
+	#[test]
+	fn omni_node_generator_works() {
+		let cache = PathBuf::from("./cache");
+		let binary = omni_node_generator(cache.clone());
+		assert_eq!(binary.name(), "polkadot-omni-node");
+		assert_eq!(binary.path(), cache.join("polkadot-omni-node"));
+		let Binary::Source { source, .. } = binary else { panic!("expected a binary source") };
+		match (OS, ARCH) {
+			("linux", "x86_64") => assert_eq!(
+				source,
+				Source::Url {
+					url: "https://github.com/paritytech/polkadot-sdk/releases/latest/download/polkadot-omni-node".into(),
+					name: "polkadot-omni-node".into()
+				}
+			),
+			_ => assert!(
+				matches!(source, Source::Git { package, .. } if package == "polkadot-omni-node")
+			),
+		}
+	}
+
+	#[test]
+	fn is_runtime_only_works() -> Result<(), Error> {
+		let temp_dir = tempdir()?;
+		let project = temp_dir.path();
+		fs::write(
+			project.join("Cargo.toml"),
+			"[package]\nname = \"chain\"\nversion = \"0.1.0\"\n\n[dependencies]\npolkadot-sdk = \"0.7.0\"\n",
+		)?;
+		fs::create_dir(project.join("src"))?;
+		fs::write(project.join("src").join("lib.rs"), "")?;
+		assert!(is_runtime_only(project)?);
+		fs::create_dir(project.join("node"))?;
+		fs::write(project.join("node").join("Cargo.toml"), "")?;
+		assert!(!is_runtime_only(project)?);
+		Ok(())
+	}
+
+	#[test]
+	fn preset_works() {
+		assert_eq!(preset("dev"), "development");
+		assert_eq!(preset("local"), "local_testnet");
+		assert_eq!(preset("custom"), "custom");
+	}
+
+	#[test]
+	fn generate_omni_chain_spec_fails_without_command() -> Result<(), Error> {
+		let temp_dir = tempdir()?;
+		let binary = temp_dir.path().join("polkadot-omni-node");
+		assert!(matches!(
+			generate_omni_chain_spec(
+				&binary,
+				&temp_dir.path().join("runtime.wasm"),
+				&temp_dir.path().join("chain-spec.json"),
+				2000,
+				"paseo-local",
+				"dev"
+			),
+			Err(Error::MissingCommand { command, .. }) if command == "chain-spec-builder"
+		));
+		Ok(())
+	}
+}
@@ -0,0 +1,265 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::errors::Error;
+use scale_value::{Composite, ValueDef};
+use sp_core::blake2_256;
+use std::fmt::{Display, Formatter};
+use subxt::{
+	dynamic::{self, Value},
+	ext::codec::{Compact, Decode},
+	utils::H256,
+	OnlineClient, SubstrateConfig,
+};
+
+/// How a core is assigned to a parachain.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CoreAssignment {
+	/// The core is assigned via bulk coretime.
+	Bulk,
+	/// The core is assigned via on-demand coretime.
+	OnDemand,
+}
+
+impl Display for CoreAssignment {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Bulk => write!(f, "bulk"),
+			Self::OnDemand => write!(f, "on-demand"),
+		}
+	}
+}
+
+/// A core of a relay chain, as assigned to a parachain.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Core {
+	/// The index of the core.
+	pub index: u32,
+	/// How the core is assigned.
+	pub assignment: CoreAssignment,
+}
+
+/// The state of a parachain, as known by a relay chain.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParachainInfo {
+	/// The identifier of the parachain.
+	pub para_id: u32,
+	/// The lifecycle of the parachain, e.g. `Parachain`, if registered.
+	pub lifecycle: Option<String>,
+	/// The number and hash of the latest head of the parachain included by the relay chain.
+	pub head: Option<(u32, H256)>,
+	/// The hash of the current validation code of the parachain.
+	pub code_hash: Option<H256>,
+	/// The cores with upcoming claims by the parachain.
+	pub cores: Vec<Core>,
+}
+
+/// Queries the state of a parachain from the `Paras` and `ParaScheduler` pallets of a relay
+/// chain.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the relay chain.
+/// * `para_id` - The identifier of the parachain.
+pub async fn parachain_info(
+	client: &OnlineClient<SubstrateConfig>,
+	para_id: u32,
+) -> Result<ParachainInfo, Error> {
+	let storage = client.storage().at_latest().await?;
+	let key = || vec![Value::u128(para_id.into())];
+	let lifecycle = match storage.fetch(&dynamic::storage("Paras", "ParaLifecycles", key())).await?
+	{
+		Some(lifecycle) => {
+			let lifecycle = lifecycle
+				.to_value()
+				.map_err(|e| Error::MetadataParsingError(format!("lifecycle: {e}")))?;
+			match lifecycle.value {
+				ValueDef::Variant(v) => Some(v.name),
+				_ => None,
+			}
+		},
+		None => None,
+	};
+	let head = match storage.fetch(&dynamic::storage("Paras", "Heads", key())).await? {
+		Some(head) => {
+			let head = Vec::<u8>::decode(&mut head.encoded())
+				.map_err(|e| Error::MetadataParsingError(format!("head: {e}")))?;
+			parse_head(&head)
+		},
+		None => None,
+	};
+	let code_hash =
+		match storage.fetch(&dynamic::storage("Paras", "CurrentCodeHash", key())).await? {
+			Some(hash) => Some(
+				H256::decode(&mut hash.encoded())
+					.map_err(|e| Error::MetadataParsingError(format!("code hash: {e}")))?,
+			),
+			None => None,
+		};
+	// The claim queue is only available from more recent versions of the scheduler.
+	let cores = match storage
+		.fetch(&dynamic::storage("ParaScheduler", "ClaimQueue", Vec::<Value>::new()))
+		.await
+	{
+		Ok(Some(queue)) => {
+			let queue = queue
+				.to_value()
+				.map_err(|e| Error::MetadataParsingError(format!("claim queue: {e}")))?;
+			parse_claim_queue(&queue, para_id)
+		},
+		_ => vec![],
+	};
+	Ok(ParachainInfo { para_id, lifecycle, head, code_hash, cores })
+}
+
+/// The intervals between the timestamps of the latest blocks of a chain, in milliseconds and
+/// starting with the most recent.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `count` - The number of intervals.
+pub async fn block_times(
+	client: &OnlineClient<SubstrateConfig>,
+	count: usize,
+) -> Result<Vec<u64>, Error> {
+	let mut block = client.blocks().at_latest().await?;
+	let mut timestamps = Vec::new();
+	loop {
+		let now = client
+			.storage()
+			.at(block.hash())
+			.fetch(&dynamic::storage("Timestamp", "Now", Vec::<Value>::new()))
+			.await?
+			.map(|now| now.to_value())
+			.transpose()
+			.map_err(|e| Error::MetadataParsingError(format!("timestamp: {e}")))?
+			.and_then(|now| now.as_u128());
+		let Some(now) = now else { break };
+		timestamps.push(now);
+		if timestamps.len() > count || block.number() == 0 {
+			break;
+		}
+		block = client.blocks().at(block.header().parent_hash).await?;
+	}
+	Ok(timestamps.windows(2).map(|w| w[0].saturating_sub(w[1]) as u64).collect())
+}
+
+// Parses the number and hash of a parachain head, which is its encoded header.
+fn parse_head(head: &[u8]) -> Option<(u32, H256)> {
+	// A header starts with the hash of its parent, followed by its number.
+	let mut number = head.get(32..)?;
+	let Compact(number) = Compact::<u32>::decode(&mut number).ok()?;
+	Some((number, H256(blake2_256(head))))
+}
+
+// Parses the cores with claims by a parachain from the claim queue of a relay chain.
+fn parse_claim_queue<T>(queue: &Value<T>, para_id: u32) -> Vec<Core> {
+	let ValueDef::Composite(queue) = &queue.value else { return vec![] };
+	let mut cores = Vec::new();
+	for entry in queue.values() {
+		let ValueDef::Composite(entry) = &entry.value else { continue };
+		let mut entry = entry.values();
+		let (Some(index), Some(claims)) = (entry.next().and_then(number), entry.next()) else {
+			continue;
+		};
+		let ValueDef::Composite(claims) = &claims.value else { continue };
+		let assignment = claims
+			.values()
+			.filter_map(assignment)
+			.find_map(|(assignment, id)| (id == para_id).then_some(assignment));
+		if let Some(assignment) = assignment {
+			cores.push(Core { index: index as u32, assignment });
+		}
+	}
+	cores
+}
+
+// Parses a claim of a core, which depending on the version of the scheduler may be wrapped.
+fn assignment<T>(claim: &Value<T>) -> Option<(CoreAssignment, u32)> {
+	match &claim.value {
+		ValueDef::Variant(v) => match v.name.as_str() {
+			"Bulk" => Some((CoreAssignment::Bulk, number(v.values.values().next()?)? as u32)),
+			"Pool" => {
+				let para_id = match &v.values {
+					Composite::Named(fields) =>
+						fields.iter().find(|(n, _)| n == "para_id").map(|(_, v)| v),
+					Composite::Unnamed(_) => v.values.values().next(),
+				};
+				Some((CoreAssignment::OnDemand, number(para_id?)? as u32))
+			},
+			"Some" => assignment(v.values.values().next()?),
+			_ => None,
+		},
+		ValueDef::Composite(Composite::Named(fields)) =>
+			fields.iter().find(|(n, _)| n == "assignment").and_then(|(_, v)| assignment(v)),
+		_ => None,
+	}
+}
+
+// Parses a number, which may be wrapped in a newtype.
+fn number<T>(value: &Value<T>) -> Option<u128> {
+	match &value.value {
+		ValueDef::Composite(c) if c.len() == 1 => number(c.values().next()?),
+		_ => value.as_u128(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use subxt::ext::codec::Encode;
+
+	#[test]
+	fn parse_head_works() {
+		let mut head = [1u8; 32].to_vec();
+		Compact(1_234u32).encode_to(&mut head);
+		head.extend([0u8; 64]);
+		assert_eq!(parse_head(&head), Some((1_234, H256(blake2_256(&head)))));
+		assert_eq!(parse_head(&[0u8; 16]), None);
+	}
+
+	#[test]
+	fn parse_claim_queue_works() {
+		let para_id = |id: u128| Value::unnamed_composite([Value::u128(id)]);
+		let queue = Value::unnamed_composite([
+			Value::unnamed_composite([
+				Value::u128(0),
+				Value::unnamed_composite([Value::unnamed_variant("Bulk", [para_id(1000)])]),
+			]),
+			Value::unnamed_composite([
+				Value::u128(1),
+				Value::unnamed_composite([
+					Value::unnamed_variant("Bulk", [para_id(2000)]),
+					// Claims may be wrapped, depending on the version of the scheduler.
+					Value::named_composite([
+						(
+							"assignment",
+							Value::named_variant(
+								"Pool",
+								[("para_id", para_id(4001)), ("core_index", Value::u128(1))],
+							),
+						),
+						("availability_timeouts", Value::u128(0)),
+					]),
+				]),
+			]),
+			Value::unnamed_composite([
+				Value::u128(2),
+				Value::unnamed_composite([Value::unnamed_variant(
+					"Some",
+					[Value::unnamed_variant("Bulk", [para_id(4001)])],
+				)]),
+			]),
+		]);
+		assert_eq!(
+			parse_claim_queue(&queue, 4001),
+			vec![
+				Core { index: 1, assignment: CoreAssignment::OnDemand },
+				Core { index: 2, assignment: CoreAssignment::Bulk },
+			]
+		);
+		assert_eq!(
+			parse_claim_queue(&queue, 1000),
+			vec![Core { index: 0, assignment: CoreAssignment::Bulk }]
+		);
+		assert!(parse_claim_queue(&queue, 3000).is_empty());
+	}
+}
@@ -0,0 +1,539 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use strum::{EnumMessage, EnumProperty, VariantArray};
+use strum_macros::{AsRefStr, EnumString};
+
+/// The identifier of the asset hub of a relay chain, on which its assets are registered.
+const ASSET_HUB_PARA_ID: u32 = 1000;
+/// The identifier of the coretime chain of a relay chain, on which its coretime is sold.
+const CORETIME_PARA_ID: u32 = 1005;
+
+/// A well-known chain, along with a public endpoint via which it can be reached.
+#[derive(
+	AsRefStr, Clone, Copy, Debug, EnumMessage, EnumProperty, EnumString, Eq, PartialEq, VariantArray,
+)]
+pub enum Chain {
+	/// Polkadot.
+	#[strum(
+		serialize = "polkadot",
+		message = "Polkadot",
+		props(
+			Url = "wss://rpc.polkadot.io",
+			Symbol = "DOT",
+			Decimals = "10",
+			Explorer = "https://polkadot.subscan.io"
+		)
+	)]
+	Polkadot,
+	/// Kusama.
+	#[strum(
+		serialize = "kusama",
+		message = "Kusama",
+		props(
+			Url = "wss://kusama-rpc.polkadot.io",
+			Symbol = "KSM",
+			Decimals = "12",
+			Explorer = "https://kusama.subscan.io"
+		)
+	)]
+	Kusama,
+	/// Paseo.
+	#[strum(
+		serialize = "paseo",
+		message = "Paseo",
+		props(
+			Url = "wss://paseo.rpc.amforc.com",
+			Symbol = "PAS",
+			Decimals = "10",
+			Explorer = "https://paseo.subscan.io"
+		)
+	)]
+	Paseo,
+	/// Westend.
+	#[strum(
+		serialize = "westend",
+		message = "Westend",
+		props(
+			Url = "wss://westend-rpc.polkadot.io",
+			Symbol = "WND",
+			Decimals = "12",
+			Explorer = "https://westend.subscan.io"
+		)
+	)]
+	Westend,
+	/// Asset Hub on Polkadot.
+	#[strum(
+		serialize = "assethub-polkadot",
+		message = "Asset Hub (Polkadot)",
+		props(
+			Url = "wss://polkadot-asset-hub-rpc.polkadot.io",
+			Relay = "polkadot",
+			ParaId = "1000",
+			Symbol = "DOT",
+			Decimals = "10",
+			Bridged = "assethub-kusama,ethereum",
+			Explorer = "https://assethub-polkadot.subscan.io"
+		)
+	)]
+	AssetHubPolkadot,
+	/// Asset Hub on Kusama.
+	#[strum(
+		serialize = "assethub-kusama",
+		message = "Asset Hub (Kusama)",
+		props(
+			Url = "wss://kusama-asset-hub-rpc.polkadot.io",
+			Relay = "kusama",
+			ParaId = "1000",
+			Symbol = "KSM",
+			Decimals = "12",
+			Bridged = "assethub-polkadot",
+			Explorer = "https://assethub-kusama.subscan.io"
+		)
+	)]
+	AssetHubKusama,
+	/// Asset Hub on Paseo.
+	#[strum(
+		serialize = "assethub-paseo",
+		message = "Asset Hub (Paseo)",
+		props(
+			Url = "wss://asset-hub-paseo-rpc.dwellir.com",
+			Relay = "paseo",
+			ParaId = "1000",
+			Symbol = "PAS",
+			Decimals = "10",
+			Explorer = "https://assethub-paseo.subscan.io"
+		)
+	)]
+	AssetHubPaseo,
+	/// Asset Hub on Westend.
+	#[strum(
+		serialize = "assethub-westend",
+		message = "Asset Hub (Westend)",
+		props(
+			Url = "wss://westend-asset-hub-rpc.polkadot.io",
+			Relay = "westend",
+			ParaId = "1000",
+			Symbol = "WND",
+			Decimals = "12",
+			Bridged = "sepolia",
+			Explorer = "https://assethub-westend.subscan.io"
+		)
+	)]
+	AssetHubWestend,
+	/// Pop Network on Paseo.
+	#[strum(
+		serialize = "pop-paseo",
+		message = "Pop Network (Paseo)",
+		props(
+			Url = "wss://rpc1.paseo.popnetwork.xyz",
+			Relay = "paseo",
+			ParaId = "4001",
+			Symbol = "PAS",
+			Decimals = "10"
+		)
+	)]
+	PopPaseo,
+	/// Bridge Hub on Polkadot.
+	#[strum(
+		serialize = "bridgehub-polkadot",
+		message = "Bridge Hub (Polkadot)",
+		props(
+			Url = "wss://polkadot-bridge-hub-rpc.polkadot.io",
+			Relay = "polkadot",
+			ParaId = "1002",
+			Symbol = "DOT",
+			Decimals = "10",
+			Explorer = "https://bridgehub-polkadot.subscan.io"
+		)
+	)]
+	BridgeHubPolkadot,
+	/// Bridge Hub on Kusama.
+	#[strum(
+		serialize = "bridgehub-kusama",
+		message = "Bridge Hub (Kusama)",
+		props(
+			Url = "wss://kusama-bridge-hub-rpc.polkadot.io",
+			Relay = "kusama",
+			ParaId = "1002",
+			Symbol = "KSM",
+			Decimals = "12",
+			Explorer = "https://bridgehub-kusama.subscan.io"
+		)
+	)]
+	BridgeHubKusama,
+	/// Bridge Hub on Westend.
+	#[strum(
+		serialize = "bridgehub-westend",
+		message = "Bridge Hub (Westend)",
+		props(
+			Url = "wss://westend-bridge-hub-rpc.polkadot.io",
+			Relay = "westend",
+			ParaId = "1002",
+			Symbol = "WND",
+			Decimals = "12",
+			Explorer = "https://bridgehub-westend.subscan.io"
+		)
+	)]
+	BridgeHubWestend,
+	/// Coretime on Polkadot.
+	#[strum(
+		serialize = "coretime-polkadot",
+		message = "Coretime (Polkadot)",
+		props(
+			Url = "wss://polkadot-coretime-rpc.polkadot.io",
+			Relay = "polkadot",
+			ParaId = "1005",
+			Symbol = "DOT",
+			Decimals = "10",
+			Explorer = "https://coretime-polkadot.subscan.io"
+		)
+	)]
+	CoretimePolkadot,
+	/// Coretime on Kusama.
+	#[strum(
+		serialize = "coretime-kusama",
+		message = "Coretime (Kusama)",
+		props(
+			Url = "wss://kusama-coretime-rpc.polkadot.io",
+			Relay = "kusama",
+			ParaId = "1005",
+			Symbol = "KSM",
+			Decimals = "12",
+			Explorer = "https://coretime-kusama.subscan.io"
+		)
+	)]
+	CoretimeKusama,
+	/// Coretime on Paseo.
+	#[strum(
+		serialize = "coretime-paseo",
+		message = "Coretime (Paseo)",
+		props(
+			Url = "wss://sys.ibp.network/coretime-paseo",
+			Relay = "paseo",
+			ParaId = "1005",
+			Symbol = "PAS",
+			Decimals = "10"
+		)
+	)]
+	CoretimePaseo,
+	/// Coretime on Westend.
+	#[strum(
+		serialize = "coretime-westend",
+		message = "Coretime (Westend)",
+		props(
+			Url = "wss://westend-coretime-rpc.polkadot.io",
+			Relay = "westend",
+			ParaId = "1005",
+			Symbol = "WND",
+			Decimals = "12",
+			Explorer = "https://coretime-westend.subscan.io"
+		)
+	)]
+	CoretimeWestend,
+	/// Ethereum, reachable from Polkadot via Snowbridge.
+	#[strum(
+		serialize = "ethereum",
+		message = "Ethereum",
+		props(
+			Url = "wss://ethereum-rpc.publicnode.com",
+			ChainId = "1",
+			Symbol = "ETH",
+			Decimals = "18"
+		)
+	)]
+	Ethereum,
+	/// Sepolia, the Ethereum testnet reachable from Westend via Snowbridge.
+	#[strum(
+		serialize = "sepolia",
+		message = "Sepolia",
+		props(
+			Url = "wss://ethereum-sepolia-rpc.publicnode.com",
+			ChainId = "11155111",
+			Symbol = "ETH",
+			Decimals = "18"
+		)
+	)]
+	Sepolia,
+}
+
+/// An item of a chain which can be looked up within a block explorer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExplorerItem<'a> {
+	/// A block, by its hash.
+	Block(&'a str),
+	/// An extrinsic, by its hash.
+	Extrinsic(&'a str),
+	/// An account, such as a contract, by its address.
+	Account(&'a str),
+}
+
+/// A bridge between consensus systems, via which a chain reaches destinations beyond its own
+/// consensus.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumMessage, EnumProperty, Eq, PartialEq)]
+pub enum Bridge {
+	/// The bridge between Polkadot and Kusama, via their bridge hubs.
+	#[strum(message = "the Polkadot <> Kusama bridge", props(Delivery = "around 10 minutes"))]
+	PolkadotKusama,
+	/// Snowbridge, between Polkadot and Ethereum.
+	#[strum(message = "Snowbridge", props(Delivery = "around 30 minutes"))]
+	Snowbridge,
+}
+
+impl Bridge {
+	/// The name of the bridge.
+	pub fn name(&self) -> &'static str {
+		self.get_message().unwrap_or_default()
+	}
+
+	/// The expected time for a message to be delivered via the bridge.
+	pub fn delivery_time(&self) -> &'static str {
+		self.get_str("Delivery").unwrap_or_default()
+	}
+}
+
+impl Chain {
+	/// The known chain reachable at the specified endpoint, if any.
+	///
+	/// # Arguments
+	/// * `url` - The endpoint of the chain.
+	pub fn from_url(url: &str) -> Option<Chain> {
+		let url = url.trim_end_matches('/');
+		Chain::VARIANTS.iter().find(|c| c.url() == url).copied()
+	}
+
+	/// The name of the chain.
+	pub fn name(&self) -> &'static str {
+		self.get_message().unwrap_or_default()
+	}
+
+	/// A public endpoint of the chain.
+	pub fn url(&self) -> &'static str {
+		self.get_str("Url").unwrap_or_default()
+	}
+
+	/// The relay chain of the chain, if it is a parachain.
+	pub fn relay(&self) -> Option<Chain> {
+		self.get_str("Relay").and_then(|r| r.parse().ok())
+	}
+
+	/// The identifier of the chain, if it is a parachain.
+	pub fn para_id(&self) -> Option<u32> {
+		self.get_str("ParaId").and_then(|id| id.parse().ok())
+	}
+
+	/// Whether the chain is a system chain, i.e. trusted by its relay chain for teleports.
+	pub fn is_system(&self) -> bool {
+		self.para_id().is_some_and(|id| id < 2000)
+	}
+
+	/// The symbol of the native token of the chain.
+	pub fn symbol(&self) -> &'static str {
+		self.get_str("Symbol").unwrap_or_default()
+	}
+
+	/// The number of decimals of the native token of the chain.
+	pub fn decimals(&self) -> u8 {
+		self.get_str("Decimals").and_then(|d| d.parse().ok()).unwrap_or_default()
+	}
+
+	/// The relay chain which provides the consensus of the chain, which is the chain itself for a
+	/// relay chain.
+	pub fn consensus(&self) -> Chain {
+		self.relay().unwrap_or(*self)
+	}
+
+	/// The identifier of the chain, if it is an Ethereum network.
+	pub fn chain_id(&self) -> Option<u64> {
+		self.get_str("ChainId").and_then(|id| id.parse().ok())
+	}
+
+	/// Whether the chain is an Ethereum network, which can only be reached via a bridge.
+	pub fn is_ethereum(&self) -> bool {
+		self.chain_id().is_some()
+	}
+
+	/// The chains beyond the consensus of the chain which it reaches via a bridge.
+	pub fn bridged(&self) -> Vec<Chain> {
+		self.get_str("Bridged")
+			.map(|chains| chains.split(',').filter_map(|c| c.parse().ok()).collect())
+			.unwrap_or_default()
+	}
+
+	/// The bridge via which the chain reaches another chain, if any.
+	///
+	/// # Arguments
+	/// * `to` - The chain being reached.
+	pub fn bridge(&self, to: &Chain) -> Option<Bridge> {
+		if !self.bridged().contains(to) {
+			return None;
+		}
+		match to.is_ethereum() {
+			true => Some(Bridge::Snowbridge),
+			false => Some(Bridge::PolkadotKusama),
+		}
+	}
+
+	/// The asset hub of the consensus of the chain, on which its assets are registered, if known.
+	pub fn asset_hub(&self) -> Option<Chain> {
+		Chain::VARIANTS
+			.iter()
+			.find(|c| c.consensus() == self.consensus() && c.para_id() == Some(ASSET_HUB_PARA_ID))
+			.copied()
+	}
+
+	/// The coretime chain via which the coretime of the consensus of the chain is managed, if
+	/// known.
+	pub fn coretime(&self) -> Option<Chain> {
+		Chain::VARIANTS
+			.iter()
+			.find(|c| c.consensus() == self.consensus() && c.para_id() == Some(CORETIME_PARA_ID))
+			.copied()
+	}
+
+	/// The Subscan explorer of the chain, if the chain is indexed by Subscan.
+	pub fn explorer(&self) -> Option<&'static str> {
+		self.get_str("Explorer")
+	}
+
+	/// Links to an item within the block explorers of the chain: Subscan, where the chain is
+	/// indexed, and polkadot.js apps, connected via the public endpoint of the chain.
+	///
+	/// # Arguments
+	/// * `item` - The item to be looked up.
+	pub fn explorer_links(&self, item: ExplorerItem) -> Vec<String> {
+		let mut links = Vec::new();
+		if let Some(explorer) = self.explorer() {
+			links.push(match item {
+				ExplorerItem::Block(hash) => format!("{explorer}/block/{hash}"),
+				ExplorerItem::Extrinsic(hash) => format!("{explorer}/extrinsic/{hash}"),
+				ExplorerItem::Account(address) => format!("{explorer}/account/{address}"),
+			});
+		}
+		// Only blocks can be looked up directly via polkadot.js apps.
+		if let (ExplorerItem::Block(hash), false) = (item, self.is_ethereum()) {
+			links.push(format!(
+				"https://polkadot.js.org/apps/?rpc={}#/explorer/query/{hash}",
+				self.url()
+			));
+		}
+		links
+	}
+
+	/// The chains which share the consensus of the chain, excluding the chain itself.
+	pub fn siblings(&self) -> Vec<Chain> {
+		Chain::VARIANTS
+			.iter()
+			.filter(|c| *c != self && c.consensus() == self.consensus())
+			.copied()
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn chain_properties_work() {
+		for chain in Chain::VARIANTS {
+			assert!(!chain.name().is_empty());
+			assert!(chain.url().starts_with("wss://"));
+			assert!(!chain.symbol().is_empty());
+			assert!(chain.decimals() > 0);
+			assert_eq!(chain.as_ref().parse::<Chain>().unwrap(), *chain);
+			// A parachain has both a relay chain and an identifier, a relay chain neither.
+			assert_eq!(chain.relay().is_some(), chain.para_id().is_some());
+		}
+		assert_eq!(Chain::AssetHubPaseo.relay(), Some(Chain::Paseo));
+		assert_eq!(Chain::PopPaseo.para_id(), Some(4001));
+		assert!(Chain::AssetHubPaseo.is_system());
+		assert!(!Chain::PopPaseo.is_system());
+		assert!(!Chain::Paseo.is_system());
+	}
+
+	#[test]
+	fn from_url_works() {
+		assert_eq!(Chain::from_url("wss://rpc.polkadot.io/"), Some(Chain::Polkadot));
+		assert_eq!(Chain::from_url("wss://rpc1.paseo.popnetwork.xyz"), Some(Chain::PopPaseo));
+		assert_eq!(Chain::from_url("ws://localhost:9944/"), None);
+	}
+
+	#[test]
+	fn siblings_works() {
+		assert_eq!(
+			Chain::Paseo.siblings(),
+			vec![Chain::AssetHubPaseo, Chain::PopPaseo, Chain::CoretimePaseo]
+		);
+		assert_eq!(
+			Chain::PopPaseo.siblings(),
+			vec![Chain::Paseo, Chain::AssetHubPaseo, Chain::CoretimePaseo]
+		);
+		assert_eq!(
+			Chain::Polkadot.siblings(),
+			vec![Chain::AssetHubPolkadot, Chain::BridgeHubPolkadot, Chain::CoretimePolkadot]
+		);
+		assert!(Chain::Ethereum.siblings().is_empty());
+	}
+
+	#[test]
+	fn asset_hub_works() {
+		assert_eq!(Chain::Polkadot.asset_hub(), Some(Chain::AssetHubPolkadot));
+		assert_eq!(Chain::PopPaseo.asset_hub(), Some(Chain::AssetHubPaseo));
+		assert_eq!(Chain::AssetHubKusama.asset_hub(), Some(Chain::AssetHubKusama));
+		assert_eq!(Chain::Ethereum.asset_hub(), None);
+	}
+
+	#[test]
+	fn coretime_works() {
+		assert_eq!(Chain::Paseo.coretime(), Some(Chain::CoretimePaseo));
+		assert_eq!(Chain::PopPaseo.coretime(), Some(Chain::CoretimePaseo));
+		assert_eq!(Chain::CoretimeKusama.coretime(), Some(Chain::CoretimeKusama));
+		assert_eq!(Chain::Ethereum.coretime(), None);
+	}
+
+	#[test]
+	fn explorer_links_work() {
+		assert_eq!(Chain::AssetHubPaseo.explorer(), Some("https://assethub-paseo.subscan.io"));
+		assert_eq!(
+			Chain::Polkadot.explorer_links(ExplorerItem::Block("0x12")),
+			vec![
+				"https://polkadot.subscan.io/block/0x12",
+				"https://polkadot.js.org/apps/?rpc=wss://rpc.polkadot.io#/explorer/query/0x12"
+			]
+		);
+		assert_eq!(
+			Chain::Kusama.explorer_links(ExplorerItem::Extrinsic("0x34")),
+			vec!["https://kusama.subscan.io/extrinsic/0x34"]
+		);
+		assert_eq!(
+			Chain::Westend.explorer_links(ExplorerItem::Account("5Grw")),
+			vec!["https://westend.subscan.io/account/5Grw"]
+		);
+		// Chains not indexed by Subscan are still linked to via polkadot.js apps.
+		assert_eq!(
+			Chain::PopPaseo.explorer_links(ExplorerItem::Block("0x12")),
+			vec!["https://polkadot.js.org/apps/?rpc=wss://rpc1.paseo.popnetwork.xyz#/explorer/query/0x12"]
+		);
+		assert!(Chain::PopPaseo.explorer_links(ExplorerItem::Extrinsic("0x34")).is_empty());
+		assert!(Chain::Ethereum.explorer_links(ExplorerItem::Block("0x12")).is_empty());
+	}
+
+	#[test]
+	fn bridges_work() {
+		assert_eq!(Chain::AssetHubPolkadot.bridged(), vec![Chain::AssetHubKusama, Chain::Ethereum]);
+		assert_eq!(
+			Chain::AssetHubKusama.bridge(&Chain::AssetHubPolkadot),
+			Some(Bridge::PolkadotKusama)
+		);
+		assert_eq!(Chain::AssetHubWestend.bridge(&Chain::Sepolia), Some(Bridge::Snowbridge));
+		assert_eq!(Chain::AssetHubWestend.bridge(&Chain::Ethereum), None);
+		assert_eq!(Chain::PopPaseo.bridge(&Chain::Ethereum), None);
+		assert!(Chain::VARIANTS
+			.iter()
+			.flat_map(|c| c.bridged())
+			.all(|c| c.is_ethereum() || c.relay().is_some()));
+		assert_eq!(Chain::Sepolia.chain_id(), Some(11155111));
+		assert!(!Bridge::Snowbridge.delivery_time().is_empty());
+		assert_eq!(Bridge::PolkadotKusama.name(), "the Polkadot <> Kusama bridge");
+	}
+}
@@ -0,0 +1,612 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	build::{build_runtime, runtime_wasm_path},
+	errors::Error,
+	RuntimeVersion,
+};
+use duct::cmd;
+use pop_common::{ci::SRTOOL_VERSION, manifest::from_path};
+use serde_json::json;
+use sp_core::{
+	bytes::to_hex,
+	hashing::{blake2_256, sha2_256},
+};
+use std::{
+	collections::BTreeMap,
+	fmt::Write as _,
+	fs,
+	path::{Path, PathBuf},
+};
+
+/// The field of the runtime version declaring its specification version.
+const SPEC_VERSION: &str = "spec_version:";
+/// The names of the type aliases declaring the migrations of a runtime.
+const MIGRATIONS: [&str; 3] = ["Migrations", "SingleBlockMigrations", "Unreleased"];
+
+/// The digest of a runtime, from which its upgrade is authorized.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuntimeDigest {
+	/// The version embedded within the runtime.
+	pub version: RuntimeVersion,
+	/// The size of the compressed runtime, in bytes.
+	pub size: usize,
+	/// The Blake2-256 hash of the compressed runtime, which authorizes its upgrade.
+	pub blake2_256: [u8; 32],
+	/// The SHA-256 hash of the compressed runtime.
+	pub sha256: [u8; 32],
+}
+
+impl RuntimeDigest {
+	/// Computes the digest of a compressed runtime.
+	///
+	/// # Arguments
+	/// * `path` - The path to the compressed runtime.
+	pub fn from_file(path: &Path) -> Result<Self, Error> {
+		let code = fs::read(path)?;
+		Ok(Self {
+			version: RuntimeVersion::from_code(&code)?,
+			size: code.len(),
+			blake2_256: blake2_256(&code),
+			sha256: sha2_256(&code),
+		})
+	}
+
+	/// The hash authorizing the upgrade to the runtime, as hex.
+	pub fn code_hash(&self) -> String {
+		to_hex(&self.blake2_256, false)
+	}
+}
+
+/// The change in the base weight of a benchmarked function.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WeightChange {
+	/// The file of weights declaring the function, relative to the root of the project.
+	pub file: PathBuf,
+	/// The name of the function.
+	pub function: String,
+	/// The reference time and proof size of the base weight before the change, if benchmarked.
+	pub before: Option<(u64, u64)>,
+	/// The reference time and proof size of the base weight after the change, if benchmarked.
+	pub after: Option<(u64, u64)>,
+}
+
+impl WeightChange {
+	/// The change in the reference time, as a percentage, if benchmarked both before and after.
+	pub fn ref_time_change(&self) -> Option<f64> {
+		match (self.before, self.after) {
+			(Some((before, _)), Some((after, _))) if before > 0 =>
+				Some((after as f64 / before as f64 - 1.0) * 100.0),
+			_ => None,
+		}
+	}
+}
+
+/// A release of a runtime, from which its release notes and artifacts are produced.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuntimeRelease {
+	/// The digest of the runtime.
+	pub digest: RuntimeDigest,
+	/// The previous specification version of the runtime, if bumped.
+	pub previous_version: Option<u32>,
+	/// Whether the runtime was built deterministically via `srtool`.
+	pub deterministic: bool,
+	/// The git reference of the previous release, against which weights are compared.
+	pub since: Option<String>,
+	/// The changes in the weights of the runtime since the previous release.
+	pub weights: Vec<WeightChange>,
+	/// The migrations executed upon upgrade.
+	pub migrations: Vec<String>,
+}
+
+impl RuntimeRelease {
+	/// Drafts the release notes, as markdown.
+	pub fn notes(&self) -> String {
+		let RuntimeDigest { version, size, sha256, .. } = &self.digest;
+		let code_hash = self.digest.code_hash();
+		let mut notes = format!("# {version}\n\n");
+		let _ = match self.previous_version {
+			Some(previous) => writeln!(
+				notes,
+				"Upgrades `{}` from `spec_version` {previous} to {}.\n",
+				version.spec_name, version.spec_version
+			),
+			None => writeln!(
+				notes,
+				"Upgrades `{}` to `spec_version` {}.\n",
+				version.spec_name, version.spec_version
+			),
+		};
+		let _ = writeln!(notes, "## Runtime\n");
+		let build = match self.deterministic {
+			true => format!("deterministic, via srtool {SRTOOL_VERSION}"),
+			false => "local toolchain, not reproducible".to_string(),
+		};
+		let _ = writeln!(notes, "- Build: {build}");
+		let _ = writeln!(notes, "- Size: {:.2} KiB", *size as f64 / 1024.0);
+		let _ = writeln!(notes, "- Blake2-256: `{code_hash}`");
+		let _ = writeln!(notes, "- SHA-256: `{}`\n", to_hex(sha256, false));
+
+		let _ = writeln!(notes, "## Migrations\n");
+		match self.migrations.is_empty() {
+			true => notes.push_str("None.\n"),
+			false => self.migrations.iter().for_each(|m| {
+				let _ = writeln!(notes, "- `{m}`");
+			}),
+		}
+
+		let since = self.since.as_deref().unwrap_or("the previous release");
+		let _ = writeln!(notes, "\n## Weights\n");
+		match self.weights.is_empty() {
+			true => {
+				let _ = writeln!(notes, "No changes since {since}.");
+			},
+			false => {
+				let _ = writeln!(notes, "Changes to base weights since {since}:\n");
+				notes.push_str("| Function | Before | After | Change |\n|---|---|---|---|\n");
+				let weight = |w: Option<(u64, u64)>| {
+					w.map_or("-".to_string(), |(ref_time, proof)| format!("{ref_time} / {proof}"))
+				};
+				for change in &self.weights {
+					let pallet = change.file.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+					let _ = writeln!(
+						notes,
+						"| `{pallet}::{}` | {} | {} | {} |",
+						change.function,
+						weight(change.before),
+						weight(change.after),
+						change.ref_time_change().map_or("-".into(), |c| format!("{c:+.1}%"))
+					);
+				}
+			},
+		}
+
+		let _ = writeln!(notes, "\n## Upgrade\n");
+		let _ = writeln!(
+			notes,
+			"Authorize the upgrade with the code hash `{code_hash}`, e.g. via a referendum:\n\n```\npop governance submit --pallet System --function authorize_upgrade --args {code_hash}\n```\n\nOnce authorized, anyone can apply it with the runtime via `System.apply_authorized_upgrade`."
+		);
+		notes
+	}
+
+	/// Writes the artifacts of the release to a directory: the compressed runtime, its digest
+	/// and the release notes. Returns the paths to the artifacts.
+	///
+	/// # Arguments
+	/// * `runtime` - The path to the compressed runtime.
+	/// * `output` - The directory to which the artifacts are written.
+	pub fn write(&self, runtime: &Path, output: &Path) -> Result<Vec<PathBuf>, Error> {
+		fs::create_dir_all(output)?;
+		let RuntimeDigest { version, size, sha256, .. } = &self.digest;
+		let wasm = output.join(format!(
+			"{}-v{}.compact.compressed.wasm",
+			version.spec_name, version.spec_version
+		));
+		fs::copy(runtime, &wasm)?;
+		let digest = output.join("digest.json");
+		let contents = json!({
+			"spec_name": version.spec_name,
+			"spec_version": version.spec_version,
+			"previous_spec_version": self.previous_version,
+			"size": size,
+			"blake2_256": self.digest.code_hash(),
+			"sha256": to_hex(sha256, false),
+			"deterministic": self.deterministic,
+			"srtool": self.deterministic.then_some(SRTOOL_VERSION),
+			"migrations": self.migrations,
+		});
+		fs::write(&digest, serde_json::to_string_pretty(&contents)?)?;
+		let notes = output.join("RELEASE_NOTES.md");
+		fs::write(&notes, self.notes())?;
+		Ok(vec![wasm, digest, notes])
+	}
+}
+
+/// Bumps the `spec_version` declared by a runtime, returning its previous and new version.
+///
+/// # Arguments
+/// * `runtime` - The path to the runtime crate.
+/// * `version` - The new version [default: the next version].
+pub fn bump_spec_version(runtime: &Path, version: Option<u32>) -> Result<(u32, u32), Error> {
+	let path = runtime.join("src/lib.rs");
+	let source = fs::read_to_string(&path)?;
+	let not_found =
+		|| Error::ReleaseError(format!("no `spec_version` is declared by {}", path.display()));
+	let start = source.find(SPEC_VERSION).ok_or_else(not_found)? + SPEC_VERSION.len();
+	let declared = source[start..].split(',').next().ok_or_else(not_found)?;
+	let previous: u32 = declared.trim().replace('_', "").parse().map_err(|_| not_found())?;
+	let next = version.unwrap_or(previous + 1);
+	if next <= previous {
+		return Err(Error::ReleaseError(format!(
+			"the new `spec_version` {next} must be greater than {previous}"
+		)));
+	}
+	let formatted = match declared.contains('_') {
+		true => separate_thousands(next),
+		false => next.to_string(),
+	};
+	let updated = format!(
+		"{}{}{formatted}{}",
+		&source[..start],
+		&declared[..declared.len() - declared.trim_start().len()],
+		&source[start + declared.trim_end().len()..]
+	);
+	fs::write(&path, updated)?;
+	Ok((previous, next))
+}
+
+/// Builds a runtime, returning the path to the resulting compressed blob. The runtime is built
+/// deterministically via `srtool` within a container, unless built with the local toolchain.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+/// * `runtime` - The path to the runtime crate.
+/// * `deterministic` - Whether to build the runtime via `srtool`.
+pub fn build_release_runtime(
+	project: &Path,
+	runtime: &Path,
+	deterministic: bool,
+) -> Result<PathBuf, Error> {
+	if !deterministic {
+		return build_runtime(project, runtime, None);
+	}
+	let package = from_path(Some(runtime))?
+		.package
+		.map(|p| p.name)
+		.ok_or_else(|| Error::Config("the runtime manifest does not define a package".into()))?;
+	let project = project.canonicalize()?;
+	let runtime = runtime.canonicalize()?;
+	let dir = runtime.strip_prefix(&project).map_err(|_| Error::PathError)?;
+	cmd("docker", srtool_args(&project, &package, dir))
+		.run()
+		.map_err(|e| Error::ReleaseError(format!("srtool could not build the runtime: {e}")))?;
+	let wasm = runtime_wasm_path(&runtime.join("target/srtool/release"), &package);
+	if !wasm.exists() {
+		return Err(Error::MissingBinary(wasm.display().to_string()));
+	}
+	Ok(wasm)
+}
+
+/// The latest release tag of a project, if any.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+pub fn previous_release(project: &Path) -> Option<String> {
+	cmd("git", ["describe", "--tags", "--abbrev=0"])
+		.dir(project)
+		.stderr_null()
+		.read()
+		.ok()
+		.map(|tag| tag.trim().to_string())
+		.filter(|tag| !tag.is_empty())
+}
+
+/// The changes in the base weights of the benchmarked functions of a project since a git
+/// reference, e.g. the previous release.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+/// * `since` - The git reference against which the weights are compared.
+pub fn weight_changes(project: &Path, since: &str) -> Result<Vec<WeightChange>, Error> {
+	let git_error = |e: std::io::Error| Error::ReleaseError(format!("git: {e}"));
+	let files = cmd("git", ["diff", "--name-only", "--relative", since, "--"])
+		.dir(project)
+		.stderr_null()
+		.read()
+		.map_err(git_error)?;
+	let mut changes = Vec::new();
+	for file in files.lines().map(PathBuf::from).filter(|f| is_weights_file(f)) {
+		let before = cmd("git", ["show", &format!("{since}:./{}", file.display())])
+			.dir(project)
+			.stderr_null()
+			.read()
+			.map(|s| parse_weights(&s))
+			.unwrap_or_default();
+		let after = fs::read_to_string(project.join(&file))
+			.map(|s| parse_weights(&s))
+			.unwrap_or_default();
+		changes.extend(diff_weights(&file, &before, &after));
+	}
+	Ok(changes)
+}
+
+/// The migrations executed by a runtime upon upgrade, as declared by its `Migrations` type
+/// alias.
+///
+/// # Arguments
+/// * `runtime` - The path to the runtime crate.
+pub fn migrations(runtime: &Path) -> Result<Vec<String>, Error> {
+	let source = fs::read_to_string(runtime.join("src/lib.rs"))?;
+	// Comments are removed, as they may list migrations no longer executed.
+	let source: String = source
+		.lines()
+		.map(|line| line.split_once("//").map_or(line, |(code, _)| code))
+		.collect::<Vec<_>>()
+		.join("\n");
+	let mut migrations = Vec::new();
+	for name in MIGRATIONS {
+		let Some(start) = source.find(&format!("type {name}")) else { continue };
+		let declaration = &source[start..];
+		let Some(value) = declaration
+			.split_once('=')
+			.and_then(|(_, value)| value.split_once(';'))
+			.map(|(value, _)| value.trim())
+		else {
+			continue;
+		};
+		let value = value.strip_prefix('(').and_then(|v| v.strip_suffix(')')).unwrap_or(value);
+		migrations.extend(split_top_level(value));
+	}
+	Ok(migrations)
+}
+
+// The arguments running `srtool` within a container to build a runtime of a project.
+fn srtool_args(project: &Path, package: &str, runtime: &Path) -> Vec<String> {
+	vec![
+		"run".into(),
+		"--rm".into(),
+		"-e".into(),
+		format!("PACKAGE={package}"),
+		"-e".into(),
+		format!("RUNTIME_DIR={}", runtime.display()),
+		"-v".into(),
+		format!("{}:/build", project.display()),
+		format!("paritytech/srtool:{SRTOOL_VERSION}"),
+		"build".into(),
+		"--app".into(),
+	]
+}
+
+// Formats a number with underscores separating its thousands, e.g. `1_000_001`.
+fn separate_thousands(number: u32) -> String {
+	let digits: Vec<char> = number.to_string().chars().collect();
+	let groups: Vec<String> = digits.rchunks(3).rev().map(|group| group.iter().collect()).collect();
+	groups.join("_")
+}
+
+// Whether a file declares the weights of benchmarked functions.
+fn is_weights_file(file: &Path) -> bool {
+	file.extension().is_some_and(|e| e == "rs") &&
+		file.components().any(|c| c.as_os_str().to_string_lossy().contains("weights"))
+}
+
+// Parses the base weight of each function of a file of weights, being the first weight within
+// its body, e.g. `Weight::from_parts(25_394_000, 3593)`.
+fn parse_weights(source: &str) -> BTreeMap<String, (u64, u64)> {
+	let number = |n: &str| n.trim().trim_end_matches("_u64").replace('_', "").parse::<u64>().ok();
+	let mut weights = BTreeMap::new();
+	for function in source.split("fn ").skip(1) {
+		let Some((name, body)) = function.split_once('(') else { continue };
+		let Some((_, parts)) = body.split_once("Weight::from_parts(") else { continue };
+		let Some((parts, _)) = parts.split_once(')') else { continue };
+		let Some((ref_time, proof_size)) = parts.split_once(',') else { continue };
+		if let (Some(ref_time), Some(proof_size)) = (number(ref_time), number(proof_size)) {
+			// The weights of the implementation for the unit type are the same, so the first
+			// declaration is kept.
+			weights.entry(name.trim().to_string()).or_insert((ref_time, proof_size));
+		}
+	}
+	weights
+}
+
+// The changes between the base weights of the functions of a file of weights.
+fn diff_weights(
+	file: &Path,
+	before: &BTreeMap<String, (u64, u64)>,
+	after: &BTreeMap<String, (u64, u64)>,
+) -> Vec<WeightChange> {
+	let mut functions: Vec<_> = before.keys().chain(after.keys()).collect();
+	functions.sort();
+	functions.dedup();
+	functions
+		.into_iter()
+		.filter(|f| before.get(*f) != after.get(*f))
+		.map(|f| WeightChange {
+			file: file.to_path_buf(),
+			function: f.clone(),
+			before: before.get(f).copied(),
+			after: after.get(f).copied(),
+		})
+		.collect()
+}
+
+// Splits a list of types by their top-level commas, ignoring those within generic arguments or
+// tuples.
+fn split_top_level(list: &str) -> Vec<String> {
+	let mut items = Vec::new();
+	let (mut depth, mut item) = (0i32, String::new());
+	for c in list.chars() {
+		match c {
+			'<' | '(' | '[' => depth += 1,
+			'>' | ')' | ']' => depth -= 1,
+			',' if depth == 0 => {
+				items.push(std::mem::take(&mut item));
+				continue;
+			},
+			_ => {},
+		}
+		item.push(c);
+	}
+	items.push(item);
+	items
+		.into_iter()
+		.map(|i| i.split_whitespace().collect::<Vec<_>>().join(" "))
+		.filter(|i| !i.is_empty())
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::tempdir;
+
+	fn runtime(source: &str) -> Result<tempfile::TempDir, Error> {
+		let temp_dir = tempdir()?;
+		fs::create_dir_all(temp_dir.path().join("src"))?;
+		fs::write(temp_dir.path().join("src/lib.rs"), source)?;
+		Ok(temp_dir)
+	}
+
+	#[test]
+	fn bump_spec_version_works() -> Result<(), Error> {
+		let source = "pub const VERSION: RuntimeVersion = RuntimeVersion {\n\tspec_name: create_runtime_str!(\"pop\"),\n\tspec_version: 1_000_009,\n\timpl_version: 0,\n};\n";
+		let runtime = runtime(source)?;
+		assert_eq!(bump_spec_version(runtime.path(), None)?, (1_000_009, 1_000_010));
+		let bumped = fs::read_to_string(runtime.path().join("src/lib.rs"))?;
+		assert_eq!(bumped, source.replace("1_000_009", "1_000_010"));
+		assert_eq!(bump_spec_version(runtime.path(), Some(1_001_000))?, (1_000_010, 1_001_000));
+		assert!(matches!(
+			bump_spec_version(runtime.path(), Some(1_000_000)),
+			Err(Error::ReleaseError(..))
+		));
+
+		let runtime = self::runtime("\tspec_version: 100,\n")?;
+		assert_eq!(bump_spec_version(runtime.path(), None)?, (100, 101));
+		assert_eq!(
+			fs::read_to_string(runtime.path().join("src/lib.rs"))?,
+			"\tspec_version: 101,\n"
+		);
+		let runtime = self::runtime("fn main() {}")?;
+		assert!(matches!(bump_spec_version(runtime.path(), None), Err(Error::ReleaseError(..))));
+		Ok(())
+	}
+
+	#[test]
+	fn separate_thousands_works() {
+		assert_eq!(separate_thousands(1), "1");
+		assert_eq!(separate_thousands(1_000), "1_000");
+		assert_eq!(separate_thousands(1_000_010), "1_000_010");
+		assert_eq!(separate_thousands(100_000), "100_000");
+	}
+
+	#[test]
+	fn migrations_works() -> Result<(), Error> {
+		let runtime = runtime(
+			"pub mod migrations {\n\tpub type Unreleased = (\n\t\tpallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,\n\t\t// cumulus_pallet_xcmp_queue::migration::v4::MigrationToV4<Runtime>,\n\t\tInitStorageVersions<(Balances, Session)>,\n\t);\n}\npub type Migrations = migrations::Unreleased;\n",
+		)?;
+		assert_eq!(
+			migrations(runtime.path())?,
+			[
+				"migrations::Unreleased",
+				"pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>",
+				"InitStorageVersions<(Balances, Session)>",
+			]
+		);
+		let runtime = self::runtime("pub type Migrations = ();\n")?;
+		assert!(migrations(runtime.path())?.is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn parse_weights_works() {
+		let source = "impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {\n\tfn transfer_allow_death() -> Weight {\n\t\tWeight::from_parts(47_000_000, 3593)\n\t\t\t.saturating_add(T::DbWeight::get().reads(1_u64))\n\t}\n\tfn set_balance(u: u32, ) -> Weight {\n\t\tWeight::from_parts(15_000_000, 0)\n\t\t\t.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(u.into()))\n\t}\n}\nimpl WeightInfo for () {\n\tfn transfer_allow_death() -> Weight {\n\t\tWeight::from_parts(47_000_000, 3593)\n\t}\n\tfn upgrade() -> Weight { Weight::zero() }\n}\n";
+		let weights = parse_weights(source);
+		assert_eq!(
+			weights,
+			BTreeMap::from([
+				("set_balance".to_string(), (15_000_000, 0)),
+				("transfer_allow_death".to_string(), (47_000_000, 3593)),
+			])
+		);
+	}
+
+	#[test]
+	fn diff_weights_works() {
+		let file = Path::new("runtime/src/weights/pallet_balances.rs");
+		let before = BTreeMap::from([
+			("transfer".to_string(), (40_000_000, 3593)),
+			("unchanged".to_string(), (1_000, 0)),
+			("removed".to_string(), (1_000, 0)),
+		]);
+		let after = BTreeMap::from([
+			("transfer".to_string(), (50_000_000, 3593)),
+			("unchanged".to_string(), (1_000, 0)),
+			("added".to_string(), (2_000, 0)),
+		]);
+		let changes = diff_weights(file, &before, &after);
+		let functions: Vec<_> = changes.iter().map(|c| c.function.as_str()).collect();
+		assert_eq!(functions, ["added", "removed", "transfer"]);
+		assert_eq!(changes[0].ref_time_change(), None);
+		assert_eq!(changes[2].ref_time_change(), Some(25.0));
+		assert!(is_weights_file(file));
+		assert!(!is_weights_file(Path::new("runtime/src/lib.rs")));
+		assert!(!is_weights_file(Path::new("runtime/weights.toml")));
+	}
+
+	#[test]
+	fn srtool_args_works() {
+		assert_eq!(
+			srtool_args(Path::new("/project"), "pop-runtime", Path::new("runtime")),
+			[
+				"run",
+				"--rm",
+				"-e",
+				"PACKAGE=pop-runtime",
+				"-e",
+				"RUNTIME_DIR=runtime",
+				"-v",
+				"/project:/build",
+				&format!("paritytech/srtool:{SRTOOL_VERSION}"),
+				"build",
+				"--app",
+			]
+		);
+	}
+
+	#[test]
+	fn release_works() -> Result<(), Error> {
+		let release = RuntimeRelease {
+			digest: RuntimeDigest {
+				version: RuntimeVersion { spec_name: "pop".into(), spec_version: 1_001 },
+				size: 2048,
+				blake2_256: [1; 32],
+				sha256: [2; 32],
+			},
+			previous_version: Some(1_000),
+			deterministic: true,
+			since: Some("v1.0.0".into()),
+			weights: vec![WeightChange {
+				file: PathBuf::from("runtime/src/weights/pallet_balances.rs"),
+				function: "transfer".into(),
+				before: Some((40_000_000, 3593)),
+				after: Some((50_000_000, 3593)),
+			}],
+			migrations: vec!["pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>".into()],
+		};
+		let notes = release.notes();
+		let code_hash = format!("0x{}", "01".repeat(32));
+		assert!(
+			notes.starts_with("# pop v1001\n\nUpgrades `pop` from `spec_version` 1000 to 1001.\n")
+		);
+		assert!(notes.contains(&format!("- Build: deterministic, via srtool {SRTOOL_VERSION}\n")));
+		assert!(notes.contains("- Size: 2.00 KiB\n"));
+		assert!(notes.contains(&format!("- Blake2-256: `{code_hash}`\n")));
+		assert!(notes.contains("- `pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>`\n"));
+		assert!(notes.contains("Changes to base weights since v1.0.0:\n"));
+		assert!(notes.contains(
+			"| `pallet_balances::transfer` | 40000000 / 3593 | 50000000 / 3593 | +25.0% |\n"
+		));
+		assert!(notes.contains(&format!("--function authorize_upgrade --args {code_hash}\n")));
+
+		let temp_dir = tempdir()?;
+		let wasm = temp_dir.path().join("runtime.wasm");
+		fs::write(&wasm, [0; 8])?;
+		let output = temp_dir.path().join("release");
+		let artifacts = release.write(&wasm, &output)?;
+		assert_eq!(
+			artifacts,
+			[
+				output.join("pop-v1001.compact.compressed.wasm"),
+				output.join("digest.json"),
+				output.join("RELEASE_NOTES.md"),
+			]
+		);
+		let digest: serde_json::Value =
+			serde_json::from_str(&fs::read_to_string(output.join("digest.json"))?)?;
+		assert_eq!(digest["spec_version"], 1_001);
+		assert_eq!(digest["blake2_256"], code_hash);
+		assert_eq!(digest["srtool"], SRTOOL_VERSION);
+		assert_eq!(fs::read_to_string(output.join("RELEASE_NOTES.md"))?, notes);
+		Ok(())
+	}
+}
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::errors::Error;
+use std::{fmt, io::Read, path::Path};
+use subxt::ext::codec::Decode;
+use wasmparser::{Parser, Payload};
+
+/// The prefix of a compressed WebAssembly blob, e.g. `*.compact.compressed.wasm`.
+const ZSTD_PREFIX: [u8; 8] = [82, 188, 83, 118, 70, 219, 142, 5];
+/// The custom section of a runtime WebAssembly blob in which its version is embedded.
+const VERSION_SECTION: &str = "runtime_version";
+
+/// The version of a runtime.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuntimeVersion {
+	/// The name of the runtime, which identifies the chain it belongs to.
+	pub spec_name: String,
+	/// The version of the runtime specification.
+	pub spec_version: u32,
+}
+
+impl RuntimeVersion {
+	/// Reads the version embedded within a runtime WebAssembly blob.
+	///
+	/// # Arguments
+	/// * `path` - The path to the blob, which may be compressed.
+	pub fn from_file(path: &Path) -> Result<Self, Error> {
+		Self::from_code(&std::fs::read(path)?)
+	}
+
+	/// Reads the version embedded within the code of a runtime.
+	///
+	/// # Arguments
+	/// * `code` - The code of the runtime, which may be compressed.
+	pub fn from_code(code: &[u8]) -> Result<Self, Error> {
+		let code = decompress(code)?;
+		let mut section = None;
+		for payload in Parser::new(0).parse_all(&code) {
+			if let Payload::CustomSection(reader) =
+				payload.map_err(|e| Error::RuntimeError(format!("invalid runtime code: {e}")))?
+			{
+				if reader.name() == VERSION_SECTION {
+					section = Some(reader.data());
+					break;
+				}
+			}
+		}
+		let mut section = section.ok_or_else(|| {
+			Error::RuntimeError("the runtime code does not embed its version".into())
+		})?;
+		// The name of the runtime is followed by the name of its implementation and the
+		// authoring version.
+		let decode = |section: &mut &[u8]| -> Result<Self, subxt::ext::codec::Error> {
+			let spec_name = String::decode(section)?;
+			let _impl_name = String::decode(section)?;
+			let _authoring_version = u32::decode(section)?;
+			Ok(Self { spec_name, spec_version: u32::decode(section)? })
+		};
+		decode(&mut section)
+			.map_err(|e| Error::RuntimeError(format!("invalid runtime version: {e}")))
+	}
+
+	/// Whether a runtime of this version can replace one of the specified version, i.e. whether
+	/// both belong to the same chain.
+	///
+	/// # Arguments
+	/// * `current` - The version of the runtime to be replaced.
+	pub fn is_compatible_with(&self, current: &RuntimeVersion) -> bool {
+		self.spec_name == current.spec_name
+	}
+}
+
+impl fmt::Display for RuntimeVersion {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} v{}", self.spec_name, self.spec_version)
+	}
+}
+
+// Decompresses the code of a runtime, if compressed.
+fn decompress(code: &[u8]) -> Result<Vec<u8>, Error> {
+	let Some(mut compressed) = code.strip_prefix(&ZSTD_PREFIX) else {
+		return Ok(code.to_vec());
+	};
+	let mut decoder = ruzstd::StreamingDecoder::new(&mut compressed)
+		.map_err(|e| Error::RuntimeError(format!("invalid compressed runtime code: {e}")))?;
+	let mut code = Vec::new();
+	decoder
+		.read_to_end(&mut code)
+		.map_err(|e| Error::RuntimeError(format!("invalid compressed runtime code: {e}")))?;
+	Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use subxt::ext::codec::Encode;
+
+	// A minimal WebAssembly module, containing the specified custom section.
+	fn code(section: &str, data: &[u8]) -> Vec<u8> {
+		let contents = [&[section.len() as u8], section.as_bytes(), data].concat();
+		[b"\0asm\x01\0\0\0".as_slice(), &[0, contents.len() as u8], &contents].concat()
+	}
+
+	#[test]
+	fn from_code_works() -> Result<(), Error> {
+		let version = ("paseo".to_string(), "paseo".to_string(), 0u32, 1_004_001u32, 0u32).encode();
+		let code = code(VERSION_SECTION, &version);
+		assert_eq!(
+			RuntimeVersion::from_code(&code)?,
+			RuntimeVersion { spec_name: "paseo".into(), spec_version: 1_004_001 }
+		);
+		assert_eq!(RuntimeVersion::from_code(&code)?.to_string(), "paseo v1004001");
+		Ok(())
+	}
+
+	#[test]
+	fn from_code_fails_without_version() {
+		assert!(matches!(
+			RuntimeVersion::from_code(&code("name", &[])),
+			Err(Error::RuntimeError(message)) if message == "the runtime code does not embed its version"
+		));
+		assert!(matches!(
+			RuntimeVersion::from_code(&[1, 2, 3]),
+			Err(Error::RuntimeError(message)) if message.starts_with("invalid runtime code")
+		));
+		assert!(matches!(
+			RuntimeVersion::from_code(&[ZSTD_PREFIX.as_slice(), &[1, 2, 3]].concat()),
+			Err(Error::RuntimeError(message)) if message.starts_with("invalid compressed runtime code")
+		));
+	}
+
+	#[test]
+	fn is_compatible_with_works() {
+		let current = RuntimeVersion { spec_name: "paseo".into(), spec_version: 1_004_001 };
+		let patched = RuntimeVersion { spec_name: "paseo".into(), spec_version: 1_004_002 };
+		assert!(patched.is_compatible_with(&current));
+		let other = RuntimeVersion { spec_name: "westend".into(), spec_version: 1_017_000 };
+		assert!(!other.is_compatible_with(&current));
+	}
+}
@@ -0,0 +1,501 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{errors::Error, DynamicPayload};
+use scale_value::{Composite, ValueDef};
+use sp_core::bytes::to_hex;
+use std::{
+	fmt::{Display, Formatter},
+	str::FromStr,
+};
+use subxt::{
+	dynamic::{self, Value},
+	Metadata, OnlineClient, SubstrateConfig,
+};
+
+/// The priority with which scheduled calls are dispatched, halfway between the highest (0) and
+/// the lowest (255).
+const DEFAULT_PRIORITY: u8 = 127;
+/// The block time assumed when a chain does not define the minimum period between blocks, in
+/// milliseconds.
+const DEFAULT_BLOCK_TIME: u64 = 6_000;
+/// The length of the name of a scheduled task, in bytes.
+const TASK_NAME_LEN: usize = 32;
+
+/// When a call is scheduled to be dispatched.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScheduleAt {
+	/// At a block, e.g. `1200`.
+	Block(u32),
+	/// After a number of blocks from the current one, e.g. `+10`.
+	After(u32),
+	/// At the first block following a Unix timestamp in seconds, e.g. `@1767225600`.
+	Timestamp(u64),
+}
+
+impl FromStr for ScheduleAt {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parsed = if let Some(after) = s.strip_prefix('+') {
+			after.parse().map(Self::After)
+		} else if let Some(timestamp) = s.strip_prefix('@') {
+			timestamp.parse().map(Self::Timestamp)
+		} else {
+			s.strip_prefix('#').unwrap_or(s).parse().map(Self::Block)
+		};
+		parsed.map_err(|_| Error::ParamProcessingError)
+	}
+}
+
+impl Display for ScheduleAt {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Block(block) => write!(f, "{block}"),
+			Self::After(blocks) => write!(f, "+{blocks}"),
+			Self::Timestamp(timestamp) => write!(f, "@{timestamp}"),
+		}
+	}
+}
+
+/// A task of the scheduler, identified either by the block at which it is scheduled and its
+/// index within the agenda of that block, or by its name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScheduledTaskId {
+	/// An anonymous task, e.g. `1200:0`.
+	Index {
+		/// The block at which the task is scheduled.
+		block: u32,
+		/// The index of the task within the agenda of the block.
+		index: u32,
+	},
+	/// A named task.
+	Named(String),
+}
+
+impl FromStr for ScheduledTaskId {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some((block, index)) = s.split_once(':') {
+			if let (Ok(block), Ok(index)) = (block.parse(), index.parse()) {
+				return Ok(Self::Index { block, index });
+			}
+		}
+		task_name(s)?;
+		Ok(Self::Named(s.to_string()))
+	}
+}
+
+impl Display for ScheduledTaskId {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Index { block, index } => write!(f, "{block}:{index}"),
+			Self::Named(name) => write!(f, "{name}"),
+		}
+	}
+}
+
+/// A call scheduled to be dispatched by the scheduler.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ScheduledTask {
+	/// The block at which the call is dispatched.
+	pub block: u32,
+	/// The index of the task within the agenda of the block.
+	pub index: u32,
+	/// The name of the task, if any.
+	pub name: Option<String>,
+	/// The priority with which the call is dispatched, the lowest being the highest priority.
+	pub priority: u8,
+	/// The call, e.g. `System.remark`, or the hash of its preimage if it has not been noted.
+	pub call: String,
+	/// The interval in blocks and the number of times the call is dispatched, if periodic.
+	pub periodic: Option<(u32, u32)>,
+}
+
+impl ScheduledTask {
+	/// The identifier of the task, via which it can be cancelled.
+	pub fn id(&self) -> ScheduledTaskId {
+		match &self.name {
+			Some(name) => ScheduledTaskId::Named(name.clone()),
+			None => ScheduledTaskId::Index { block: self.block, index: self.index },
+		}
+	}
+}
+
+/// The name of a scheduled task, padded to the length expected by the scheduler.
+///
+/// # Arguments
+/// * `name` - The name of the task, of up to 32 bytes.
+pub fn task_name(name: &str) -> Result<[u8; TASK_NAME_LEN], Error> {
+	if name.is_empty() || name.len() > TASK_NAME_LEN {
+		return Err(Error::SchedulingError(format!(
+			"the name of a task must be between 1 and {TASK_NAME_LEN} bytes"
+		)));
+	}
+	let mut padded = [0; TASK_NAME_LEN];
+	padded[..name.len()].copy_from_slice(name.as_bytes());
+	Ok(padded)
+}
+
+/// Resolves when a call is scheduled to be dispatched into a block, estimating the block by
+/// which a timestamp is reached from the minimum period between blocks of the chain.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `at` - When the call is scheduled to be dispatched.
+pub async fn resolve_schedule(
+	client: &OnlineClient<SubstrateConfig>,
+	at: ScheduleAt,
+) -> Result<ScheduleAt, Error> {
+	let (block, timestamp) = match at {
+		ScheduleAt::After(_) => return Ok(at),
+		ScheduleAt::Block(block) => (Some(block), None),
+		ScheduleAt::Timestamp(timestamp) => (None, Some(timestamp)),
+	};
+	let latest = client.blocks().at_latest().await?;
+	let number = latest.number();
+	if let Some(block) = block {
+		return match block > number {
+			true => Ok(at),
+			false => Err(Error::SchedulingError(format!(
+				"block {block} is not after the latest block {number}"
+			))),
+		};
+	}
+	let now = client
+		.storage()
+		.at(latest.hash())
+		.fetch(&dynamic::storage("Timestamp", "Now", Vec::<Value>::new()))
+		.await?
+		.and_then(|v| v.to_value().ok())
+		.and_then(|v| v.as_u128())
+		.ok_or_else(|| Error::SchedulingError("the time of the latest block is unknown".into()))?;
+	// The block time is twice the minimum period between blocks.
+	let block_time = client
+		.constants()
+		.at(&dynamic::constant("Timestamp", "MinimumPeriod"))
+		.ok()
+		.and_then(|c| c.to_value().ok())
+		.and_then(|v| v.as_u128())
+		.map_or(DEFAULT_BLOCK_TIME, |p| p as u64 * 2);
+	let timestamp = timestamp.expect("either a block or a timestamp");
+	estimate_block(number, now as u64, block_time, timestamp.saturating_mul(1_000))
+		.map(ScheduleAt::Block)
+		.ok_or_else(|| {
+			Error::SchedulingError(format!("the timestamp {timestamp} has already passed"))
+		})
+}
+
+/// Constructs a call to schedule a call, optionally named so that it can be cancelled by its
+/// name.
+///
+/// # Arguments
+/// * `xt` - The call to be scheduled.
+/// * `at` - When the call is dispatched, resolved via [`resolve_schedule`].
+/// * `name` - The name of the task, if any.
+pub fn schedule(
+	xt: DynamicPayload,
+	at: ScheduleAt,
+	name: Option<&str>,
+) -> Result<DynamicPayload, Error> {
+	let (call, when) = match at {
+		ScheduleAt::Block(block) => ("schedule", block),
+		ScheduleAt::After(blocks) => ("schedule_after", blocks),
+		ScheduleAt::Timestamp(timestamp) =>
+			return Err(Error::SchedulingError(format!(
+				"the timestamp {timestamp} has not been resolved to a block"
+			))),
+	};
+	let mut args = vec![
+		Value::u128(when.into()),
+		Value::unnamed_variant("None", []),
+		Value::u128(DEFAULT_PRIORITY.into()),
+		xt.into_value(),
+	];
+	let call = match name {
+		Some(name) => {
+			args.insert(0, Value::from_bytes(task_name(name)?));
+			call.replacen("schedule", "schedule_named", 1)
+		},
+		None => call.to_string(),
+	};
+	Ok(dynamic::tx("Scheduler", call, args))
+}
+
+/// Constructs a call to cancel a scheduled task.
+///
+/// # Arguments
+/// * `id` - The identifier of the task.
+pub fn cancel_scheduled(id: &ScheduledTaskId) -> Result<DynamicPayload, Error> {
+	Ok(match id {
+		ScheduledTaskId::Index { block, index } => dynamic::tx(
+			"Scheduler",
+			"cancel",
+			vec![Value::u128((*block).into()), Value::u128((*index).into())],
+		),
+		ScheduledTaskId::Named(name) =>
+			dynamic::tx("Scheduler", "cancel_named", vec![Value::from_bytes(task_name(name)?)]),
+	})
+}
+
+/// Queries the tasks scheduled on a chain, ordered by the block at which they are dispatched.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+pub async fn scheduled_tasks(
+	client: &OnlineClient<SubstrateConfig>,
+) -> Result<Vec<ScheduledTask>, Error> {
+	let metadata = client.metadata();
+	let storage = client.storage().at_latest().await?;
+	let mut entries = storage
+		.iter(dynamic::storage("Scheduler", "Agenda", Vec::<Value>::new()))
+		.await?;
+	let mut tasks = Vec::new();
+	while let Some(entry) = entries.next().await {
+		let entry = entry?;
+		let Some(block) = entry.keys.first().and_then(|k| k.as_u128()) else { continue };
+		let agenda = entry
+			.value
+			.to_value()
+			.map_err(|e| Error::MetadataParsingError(format!("agenda: {e}")))?;
+		let ValueDef::Composite(agenda) = &agenda.value else { continue };
+		for (index, task) in agenda.values().enumerate() {
+			let decode = |data: &[u8]| decode_call_name(&metadata, data);
+			if let Some(task) = parse_task(block as u32, index as u32, task, decode) {
+				tasks.push(task);
+			}
+		}
+	}
+	tasks.sort_by_key(|t| (t.block, t.index));
+	Ok(tasks)
+}
+
+// Estimates the block by which a timestamp is reached, if it has not already passed.
+fn estimate_block(number: u32, now: u64, block_time: u64, timestamp: u64) -> Option<u32> {
+	let remaining = timestamp.checked_sub(now).filter(|r| *r > 0)?;
+	let blocks = remaining.div_ceil(block_time.max(1));
+	number.checked_add(u32::try_from(blocks).ok()?)
+}
+
+// Parses a task of the agenda of a block, which is `None` once dispatched or cancelled, using
+// the specified function to decode the name of an inlined call.
+fn parse_task<T>(
+	block: u32,
+	index: u32,
+	task: &Value<T>,
+	decode: impl Fn(&[u8]) -> Option<String>,
+) -> Option<ScheduledTask> {
+	let ValueDef::Variant(task) = &task.value else { return None };
+	let ValueDef::Composite(task) = &task.values.values().next()?.value else { return None };
+	let name = field(task, "maybe_id")
+		.and_then(some)
+		.and_then(bytes)
+		.map(|id| describe_name(&id));
+	let priority = field(task, "priority").and_then(|p| p.as_u128()).unwrap_or_default() as u8;
+	let call = field(task, "call").map(|c| describe_call(c, decode)).unwrap_or_default();
+	let periodic = field(task, "maybe_periodic").and_then(some).and_then(|p| {
+		let ValueDef::Composite(p) = &p.value else { return None };
+		let mut values = p.values().filter_map(|v| v.as_u128());
+		Some((values.next()? as u32, values.next()? as u32))
+	});
+	Some(ScheduledTask { block, index, name, priority, call, periodic })
+}
+
+// Describes a call bounded by the scheduler, decoding it if inlined or otherwise referring to the
+// hash of its preimage.
+fn describe_call<T>(call: &Value<T>, decode: impl Fn(&[u8]) -> Option<String>) -> String {
+	let ValueDef::Variant(call) = &call.value else { return String::new() };
+	let Some(data) = call.values.values().next().and_then(bytes) else { return String::new() };
+	match call.name.as_str() {
+		"Inline" => decode(&data).unwrap_or_else(|| "unknown call".into()),
+		_ => format!("preimage {}", to_hex(&data, false)),
+	}
+}
+
+// Decodes the name of a call from its call data, e.g. `System.remark`.
+fn decode_call_name(metadata: &Metadata, mut data: &[u8]) -> Option<String> {
+	let call: Value<u32> = scale_value::scale::decode_as_type(
+		&mut data,
+		metadata.outer_enums().call_enum_ty(),
+		metadata.types(),
+	)
+	.ok()?;
+	let ValueDef::Variant(pallet) = &call.value else { return None };
+	let ValueDef::Variant(function) = &pallet.values.values().next()?.value else { return None };
+	Some(format!("{}.{}", pallet.name, function.name))
+}
+
+// Describes the name of a task as text, unless it is not a padded name.
+fn describe_name(id: &[u8]) -> String {
+	let name = id.iter().rposition(|b| *b != 0).map_or(&id[..0], |end| &id[..=end]);
+	match std::str::from_utf8(name) {
+		Ok(name) if !name.is_empty() && !name.chars().any(char::is_control) => name.to_string(),
+		_ => to_hex(id, false),
+	}
+}
+
+// The value of an option, if it is `Some`.
+fn some<T>(value: &Value<T>) -> Option<&Value<T>> {
+	let ValueDef::Variant(option) = &value.value else { return None };
+	match option.name.as_str() {
+		"Some" => option.values.values().next(),
+		_ => None,
+	}
+}
+
+// The bytes of a value encoded as a sequence of bytes, which may be wrapped in a bounded vector.
+fn bytes<T>(value: &Value<T>) -> Option<Vec<u8>> {
+	let ValueDef::Composite(Composite::Unnamed(values)) = &value.value else { return None };
+	if let [inner] = values.as_slice() {
+		if let ValueDef::Composite(_) = inner.value {
+			return bytes(inner);
+		}
+	}
+	values.iter().map(|v| v.as_u128().and_then(|b| u8::try_from(b).ok())).collect()
+}
+
+fn field<'a, T>(composite: &'a Composite<T>, name: &str) -> Option<&'a Value<T>> {
+	match composite {
+		Composite::Named(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+		Composite::Unnamed(_) => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn schedule_at_works() -> Result<(), Error> {
+		for (input, expected) in [
+			("1200", ScheduleAt::Block(1200)),
+			("#1200", ScheduleAt::Block(1200)),
+			("+10", ScheduleAt::After(10)),
+			("@1767225600", ScheduleAt::Timestamp(1767225600)),
+		] {
+			assert_eq!(input.parse::<ScheduleAt>()?, expected);
+		}
+		assert_eq!(ScheduleAt::After(10).to_string(), "+10");
+		assert!(matches!("+".parse::<ScheduleAt>(), Err(Error::ParamProcessingError)));
+		assert!(matches!("tomorrow".parse::<ScheduleAt>(), Err(Error::ParamProcessingError)));
+		Ok(())
+	}
+
+	#[test]
+	fn scheduled_task_id_works() -> Result<(), Error> {
+		assert_eq!(
+			"1200:3".parse::<ScheduledTaskId>()?,
+			ScheduledTaskId::Index { block: 1200, index: 3 }
+		);
+		assert_eq!("upgrade".parse::<ScheduledTaskId>()?, ScheduledTaskId::Named("upgrade".into()));
+		assert!(matches!(
+			"a".repeat(33).parse::<ScheduledTaskId>(),
+			Err(Error::SchedulingError(..))
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn task_name_works() -> Result<(), Error> {
+		let name = task_name("upgrade")?;
+		assert_eq!(&name[..7], b"upgrade");
+		assert!(name[7..].iter().all(|b| *b == 0));
+		assert_eq!(describe_name(&name), "upgrade");
+		assert_eq!(describe_name(&[1; 32]), format!("0x{}", "01".repeat(32)));
+		assert!(task_name("").is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn estimate_block_works() {
+		// 30 seconds at 6 second blocks.
+		assert_eq!(estimate_block(100, 1_000_000, 6_000, 1_030_000), Some(105));
+		// Part of a block is rounded up to the next block.
+		assert_eq!(estimate_block(100, 1_000_000, 6_000, 1_001_000), Some(101));
+		assert_eq!(estimate_block(100, 1_000_000, 6_000, 1_000_000), None);
+		assert_eq!(estimate_block(100, 1_000_000, 6_000, 999_999), None);
+	}
+
+	#[test]
+	fn schedule_works() -> Result<(), Error> {
+		let remark = || dynamic::tx("System", "remark", vec![Value::from_bytes("pop")]);
+		for (at, name, call) in [
+			(ScheduleAt::Block(1200), None, "schedule"),
+			(ScheduleAt::After(10), None, "schedule_after"),
+			(ScheduleAt::Block(1200), Some("upgrade"), "schedule_named"),
+			(ScheduleAt::After(10), Some("upgrade"), "schedule_named_after"),
+		] {
+			let xt = schedule(remark(), at, name)?;
+			assert_eq!((xt.pallet_name(), xt.call_name()), ("Scheduler", call));
+			assert_eq!(xt.call_data().len(), 4 + name.is_some() as usize);
+		}
+		assert!(matches!(
+			schedule(remark(), ScheduleAt::Timestamp(1767225600), None),
+			Err(Error::SchedulingError(..))
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn cancel_scheduled_works() -> Result<(), Error> {
+		let xt = cancel_scheduled(&ScheduledTaskId::Index { block: 1200, index: 0 })?;
+		assert_eq!(xt.call_name(), "cancel");
+		let xt = cancel_scheduled(&ScheduledTaskId::Named("upgrade".into()))?;
+		assert_eq!(xt.call_name(), "cancel_named");
+		Ok(())
+	}
+
+	#[test]
+	fn parse_task_works() -> Result<(), Error> {
+		let task = |name: Option<[u8; 32]>, call: Value| {
+			let name = match name {
+				Some(name) => Value::unnamed_variant("Some", [Value::from_bytes(name)]),
+				None => Value::unnamed_variant("None", []),
+			};
+			let periodic = Value::unnamed_composite([Value::u128(10), Value::u128(3)]);
+			Value::unnamed_variant(
+				"Some",
+				[Value::named_composite([
+					("maybe_id", name),
+					("priority", Value::u128(127)),
+					("call", call),
+					("maybe_periodic", Value::unnamed_variant("Some", [periodic])),
+					(
+						"origin",
+						Value::unnamed_variant("system", [Value::unnamed_variant("Root", [])]),
+					),
+				])],
+			)
+		};
+		// Inlined calls are decoded, with their call data wrapped in a bounded vector.
+		let decode = |data: &[u8]| (data == [0, 1]).then(|| "System.remark".to_string());
+		let inline = Value::unnamed_variant(
+			"Inline",
+			[Value::unnamed_composite([Value::from_bytes([0, 1])])],
+		);
+		let parsed = parse_task(1200, 0, &task(Some(task_name("upgrade")?), inline), decode);
+		assert_eq!(
+			parsed,
+			Some(ScheduledTask {
+				block: 1200,
+				index: 0,
+				name: Some("upgrade".into()),
+				priority: 127,
+				call: "System.remark".into(),
+				periodic: Some((10, 3)),
+			})
+		);
+		assert_eq!(parsed.unwrap().id(), ScheduledTaskId::Named("upgrade".into()));
+
+		let lookup = Value::named_variant(
+			"Lookup",
+			[("hash", Value::from_bytes([1; 32])), ("len", Value::u128(10))],
+		);
+		let parsed = parse_task(1200, 1, &task(None, lookup), decode).unwrap();
+		assert_eq!(parsed.call, format!("preimage 0x{}", "01".repeat(32)));
+		assert_eq!(parsed.id(), ScheduledTaskId::Index { block: 1200, index: 1 });
+		// Dispatched or cancelled tasks are skipped.
+		assert_eq!(parse_task(1200, 2, &Value::unnamed_variant("None", []), decode), None);
+		Ok(())
+	}
+}
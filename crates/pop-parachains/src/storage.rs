@@ -0,0 +1,241 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	blocks::{display, hex},
+	errors::Error,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use subxt::{
+	dynamic::{self, DecodedValueThunk, Value},
+	metadata::types::StorageEntryType,
+	utils::H256,
+	OnlineClient, SubstrateConfig,
+};
+
+/// A storage entry, with its keys and value decoded via the metadata of the chain.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct StorageEntry {
+	/// The pallet of the storage item.
+	pub pallet: String,
+	/// The name of the storage item.
+	pub item: String,
+	/// The hex-encoded key of the entry.
+	pub key: String,
+	/// The decoded keys of a map entry, which are empty for plain items and `()` for keys which
+	/// are hashed opaquely.
+	pub keys: Vec<String>,
+	/// The decoded value of the entry, or its hex encoding if it could not be decoded.
+	pub value: String,
+}
+
+/// A change to a storage entry between two blocks.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageChange {
+	/// The entry was inserted.
+	Inserted(StorageEntry),
+	/// The value of the entry was updated.
+	Updated {
+		/// The entry before the update.
+		from: StorageEntry,
+		/// The entry after the update.
+		to: StorageEntry,
+	},
+	/// The entry was removed.
+	Removed(StorageEntry),
+}
+
+impl StorageChange {
+	/// The entry after the change, or before it if it was removed.
+	pub fn entry(&self) -> &StorageEntry {
+		match self {
+			Self::Inserted(entry) | Self::Updated { to: entry, .. } | Self::Removed(entry) => entry,
+		}
+	}
+}
+
+/// Dumps the storage of a pallet, or of one of its items, at a block.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `hash` - The hash of the block.
+/// * `pallet` - The name of the pallet.
+/// * `item` - The name of a storage item within the pallet, otherwise all items are dumped.
+pub async fn dump_storage(
+	client: &OnlineClient<SubstrateConfig>,
+	hash: H256,
+	pallet: &str,
+	item: Option<&str>,
+) -> Result<Vec<StorageEntry>, Error> {
+	let metadata = client.metadata();
+	let pallet_metadata = metadata
+		.pallet_by_name(pallet)
+		.ok_or(Error::PalletNotFound(pallet.to_string()))?;
+	let items: Vec<_> = pallet_metadata
+		.storage()
+		.map(|s| s.entries())
+		.unwrap_or_default()
+		.iter()
+		.filter(|e| item.is_none() || item == Some(e.name()))
+		.collect();
+	if let Some(item) = item {
+		if items.is_empty() {
+			return Err(Error::StorageItemNotFound(format!("{pallet}.{item}")));
+		}
+	}
+	let storage = client.storage().at(hash);
+	let mut entries = Vec::new();
+	for metadata in items {
+		let address = dynamic::storage(pallet, metadata.name(), Vec::<Value>::new());
+		match metadata.entry_type() {
+			StorageEntryType::Plain(_) => {
+				let Some(value) = storage.fetch(&address).await? else { continue };
+				let key = client.storage().address_bytes(&address)?;
+				entries.push(entry(pallet, metadata.name(), &key, &[], decode(value)));
+			},
+			StorageEntryType::Map { .. } => {
+				let mut pairs = storage.iter(address).await?;
+				while let Some(pair) = pairs.next().await {
+					let pair = pair?;
+					entries.push(entry(
+						pallet,
+						metadata.name(),
+						&pair.key_bytes,
+						&pair.keys,
+						decode(pair.value),
+					));
+				}
+			},
+		}
+	}
+	Ok(entries)
+}
+
+/// Compares the storage of pallets between two blocks.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `from` - The hash of the block from which changes are compared.
+/// * `to` - The hash of the block to which changes are compared.
+/// * `pallets` - The names of the pallets to be compared, otherwise all pallets are compared.
+pub async fn state_diff(
+	client: &OnlineClient<SubstrateConfig>,
+	from: H256,
+	to: H256,
+	pallets: &[String],
+) -> Result<Vec<StorageChange>, Error> {
+	let pallets: Vec<String> = match pallets.is_empty() {
+		true => client
+			.metadata()
+			.pallets()
+			.filter(|p| p.storage().is_some())
+			.map(|p| p.name().to_string())
+			.collect(),
+		false => pallets.to_vec(),
+	};
+	let mut changes = Vec::new();
+	for pallet in &pallets {
+		let before = dump_storage(client, from, pallet, None).await?;
+		let after = dump_storage(client, to, pallet, None).await?;
+		changes.extend(diff_storage(before, after));
+	}
+	Ok(changes)
+}
+
+/// Compares two sets of storage entries, returning the changes ordered by key.
+///
+/// # Arguments
+/// * `before` - The entries before the changes.
+/// * `after` - The entries after the changes.
+pub fn diff_storage(before: Vec<StorageEntry>, after: Vec<StorageEntry>) -> Vec<StorageChange> {
+	let mut before: BTreeMap<_, _> = before.into_iter().map(|e| (e.key.clone(), e)).collect();
+	let mut changes: BTreeMap<_, _> = BTreeMap::new();
+	for entry in after {
+		match before.remove(&entry.key) {
+			None => {
+				changes.insert(entry.key.clone(), StorageChange::Inserted(entry));
+			},
+			Some(from) if from.value != entry.value => {
+				changes.insert(entry.key.clone(), StorageChange::Updated { from, to: entry });
+			},
+			Some(_) => {},
+		}
+	}
+	changes.extend(before.into_iter().map(|(k, e)| (k, StorageChange::Removed(e))));
+	changes.into_values().collect()
+}
+
+// Decodes a storage value, falling back to its encoding when it cannot be decoded.
+pub(crate) fn decode(value: DecodedValueThunk) -> String {
+	match value.to_value() {
+		Ok(value) => display(&value),
+		Err(_) => format!("0x{}", hex(value.encoded())),
+	}
+}
+
+fn entry(pallet: &str, item: &str, key: &[u8], keys: &[Value], value: String) -> StorageEntry {
+	StorageEntry {
+		pallet: pallet.to_string(),
+		item: item.to_string(),
+		key: format!("0x{}", hex(key)),
+		keys: keys.iter().map(display).collect(),
+		value,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn storage_entry(key: &str, value: &str) -> StorageEntry {
+		StorageEntry {
+			pallet: "System".into(),
+			item: "Account".into(),
+			key: key.into(),
+			keys: vec![],
+			value: value.into(),
+		}
+	}
+
+	#[test]
+	fn diff_storage_works() {
+		let before = vec![
+			storage_entry("0x01", "1"),
+			storage_entry("0x02", "2"),
+			storage_entry("0x03", "3"),
+		];
+		let after = vec![
+			storage_entry("0x04", "4"),
+			storage_entry("0x02", "20"),
+			storage_entry("0x01", "1"),
+		];
+		assert_eq!(
+			diff_storage(before, after),
+			vec![
+				StorageChange::Updated {
+					from: storage_entry("0x02", "2"),
+					to: storage_entry("0x02", "20")
+				},
+				StorageChange::Removed(storage_entry("0x03", "3")),
+				StorageChange::Inserted(storage_entry("0x04", "4")),
+			]
+		);
+		assert!(diff_storage(vec![storage_entry("0x01", "1")], vec![storage_entry("0x01", "1")])
+			.is_empty());
+	}
+
+	#[test]
+	fn entry_works() {
+		let keys = [Value::u128(42), Value::unnamed_composite([])];
+		assert_eq!(
+			entry("Assets", "Account", &[0x12, 0xab], &keys, "{ balance: 100 }".into()),
+			StorageEntry {
+				pallet: "Assets".into(),
+				item: "Account".into(),
+				key: "0x12ab".into(),
+				keys: vec!["42".into(), "()".into()],
+				value: "{ balance: 100 }".into(),
+			}
+		);
+	}
+}
@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	errors::Error,
+	generator::telemetry::{Command, DockerCompose},
+	utils::helpers::render_to_file,
+};
+use duct::cmd;
+use pop_common::find_free_port;
+use std::{
+	fs::{create_dir_all, set_permissions, Permissions},
+	os::unix::fs::PermissionsExt,
+	path::{Path, PathBuf},
+};
+
+/// The port on which the feed of the telemetry server is published, unless already in use.
+const FEED_PORT: u16 = 8000;
+/// The port on which nodes submit telemetry, unless already in use.
+const SHARD_PORT: u16 = 8001;
+/// The port on which the telemetry UI is published, unless already in use.
+const UI_PORT: u16 = 3001;
+/// The verbosity of the telemetry submitted, unless specified as part of the endpoint.
+const DEFAULT_VERBOSITY: u8 = 0;
+
+/// A telemetry server along with its UI, run via Docker Compose, to which the nodes of a local
+/// network submit telemetry.
+#[derive(Debug, PartialEq)]
+pub struct Telemetry {
+	path: PathBuf,
+	feed_port: u16,
+	shard_port: u16,
+	ui_port: u16,
+}
+
+impl Telemetry {
+	/// Generates the configuration of the server within the specified directory.
+	///
+	/// # Arguments
+	/// * `path` - The directory in which the configuration is generated.
+	pub fn create(path: &Path) -> Result<Self, Error> {
+		let telemetry = Self {
+			path: path.to_path_buf(),
+			feed_port: find_free_port(Some(FEED_PORT)),
+			shard_port: find_free_port(Some(SHARD_PORT)),
+			ui_port: find_free_port(Some(UI_PORT)),
+		};
+		create_dir_all(path)?;
+		render_to_file(
+			&path.join("docker-compose.yml"),
+			DockerCompose {
+				feed_port: telemetry.feed_port,
+				shard_port: telemetry.shard_port,
+				ui_port: telemetry.ui_port,
+			},
+		)?;
+		Ok(telemetry)
+	}
+
+	/// Starts the server in the background.
+	pub fn start(&self) -> Result<(), Error> {
+		self.compose(&["up", "--detach"])
+	}
+
+	/// Stops the server, removing its containers.
+	pub fn stop(&self) -> Result<(), Error> {
+		self.compose(&["down"])
+	}
+
+	/// The endpoint to which nodes submit telemetry.
+	pub fn submit_url(&self) -> String {
+		format!("ws://127.0.0.1:{}/submit", self.shard_port)
+	}
+
+	/// The URL of the telemetry UI.
+	pub fn ui_url(&self) -> String {
+		format!("http://localhost:{}", self.ui_port)
+	}
+
+	fn compose(&self, args: &[&str]) -> Result<(), Error> {
+		let file = self.path.join("docker-compose.yml").display().to_string();
+		let mut compose_args = vec!["compose", "--file", &file, "--project-name", "pop-telemetry"];
+		compose_args.extend(args);
+		cmd("docker", compose_args)
+			.stdout_null()
+			.stderr_null()
+			.run()
+			.map_err(|e| Error::TelemetryError(format!("unable to run docker compose: {e}")))?;
+		Ok(())
+	}
+}
+
+/// Generates a script which launches a node with its telemetry submitted to the specified
+/// endpoint, returning the path to the script. Zombienet disables the telemetry of every node it
+/// spawns, so the script replaces the `--no-telemetry` argument of the node.
+///
+/// # Arguments
+/// * `command` - The path to the binary of the node.
+/// * `url` - The telemetry endpoint, optionally followed by the verbosity (e.g.
+///   `wss://telemetry.polkadot.io/submit/ 0`).
+pub(crate) fn command_with_telemetry(command: &str, url: &str) -> Result<String, Error> {
+	let binary = Path::new(command);
+	let name = binary
+		.file_name()
+		.ok_or_else(|| Error::Config(format!("the command {command} has no file name")))?;
+	let dir = binary.parent().unwrap_or_else(|| Path::new(".")).join(".telemetry");
+	create_dir_all(&dir)?;
+	let url = match url.trim().contains(char::is_whitespace) {
+		true => url.trim().to_string(),
+		false => format!("{} {DEFAULT_VERBOSITY}", url.trim()),
+	};
+	let script = dir.join(name);
+	render_to_file(&script, Command { command, url: &url })?;
+	set_permissions(&script, Permissions::from_mode(0o755))?;
+	Ok(script.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs::{metadata, read_to_string};
+	use tempfile::tempdir;
+
+	#[test]
+	fn create_works() -> Result<(), Error> {
+		let temp_dir = tempdir()?;
+		let telemetry = Telemetry::create(temp_dir.path())?;
+		let compose = read_to_string(temp_dir.path().join("docker-compose.yml"))?;
+		assert!(compose.contains(&format!("\"{}:8000\"", telemetry.feed_port)));
+		assert!(compose.contains(&format!("\"{}:8001\"", telemetry.shard_port)));
+		assert!(compose.contains(&format!("\"{}:8000\"", telemetry.ui_port)));
+		assert!(compose.contains(&format!(
+			"SUBSTRATE_TELEMETRY_URL=ws://localhost:{}/feed",
+			telemetry.feed_port
+		)));
+		assert_eq!(
+			telemetry.submit_url(),
+			format!("ws://127.0.0.1:{}/submit", telemetry.shard_port)
+		);
+		assert_eq!(telemetry.ui_url(), format!("http://localhost:{}", telemetry.ui_port));
+		Ok(())
+	}
+
+	#[test]
+	fn command_with_telemetry_works() -> Result<(), Error> {
+		let temp_dir = tempdir()?;
+		let command = temp_dir.path().join("polkadot").display().to_string();
+		let script = command_with_telemetry(&command, "ws://127.0.0.1:8001/submit")?;
+		assert_eq!(script, temp_dir.path().join(".telemetry/polkadot").display().to_string());
+		let contents = read_to_string(&script)?;
+		assert!(contents.contains(r#"--telemetry-url "ws://127.0.0.1:8001/submit 0""#));
+		assert!(contents.contains(&format!(r#"exec "{command}" "$@""#)));
+		assert_eq!(metadata(&script)?.permissions().mode() & 0o777, 0o755);
+		// The script replaces the argument disabling telemetry.
+		std::fs::write(&command, "#!/bin/sh\necho \"$@\"")?;
+		set_permissions(&command, Permissions::from_mode(0o755))?;
+		let output = cmd(&script, ["--name", "alice", "--no-telemetry", "--", "--chain"]).read()?;
+		assert_eq!(output, "--name alice --telemetry-url ws://127.0.0.1:8001/submit 0 -- --chain");
+		// Any verbosity specified is retained.
+		command_with_telemetry(&command, "wss://telemetry.polkadot.io/submit/ 1")?;
+		assert!(read_to_string(&script)?
+			.contains(r#"--telemetry-url "wss://telemetry.polkadot.io/submit/ 1""#));
+		Ok(())
+	}
+}
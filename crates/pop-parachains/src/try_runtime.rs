@@ -0,0 +1,382 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{build::build_runtime, errors::Error, set_up_client};
+use duct::cmd;
+use pop_common::sourcing::{traits::Source as _, Binary, Source};
+use std::{
+	env::consts::{ARCH, OS},
+	path::{Path, PathBuf},
+	process::Stdio,
+};
+use strum::{EnumProperty, VariantArray};
+use subxt::utils::H256;
+
+/// The `try-runtime` binary, used to test a runtime against real chain state.
+#[derive(Debug, EnumProperty, PartialEq, VariantArray)]
+pub(crate) enum TryRuntimeCli {
+	#[strum(props(
+		Repository = "https://github.com/paritytech/try-runtime-cli",
+		Binary = "try-runtime",
+		Fallback = "v0.8.0"
+	))]
+	TryRuntime,
+}
+
+impl pop_common::sourcing::traits::Source for TryRuntimeCli {}
+
+/// The checks to be performed on the state after executing each block.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum TryStateSelect {
+	/// Run all the checks of all pallets.
+	#[default]
+	All,
+	/// Run no checks.
+	None,
+	/// Run the checks of the specified pallets.
+	Only(Vec<String>),
+}
+
+impl std::fmt::Display for TryStateSelect {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::All => write!(f, "all"),
+			Self::None => write!(f, "none"),
+			Self::Only(pallets) => write!(f, "{}", pallets.join(",")),
+		}
+	}
+}
+
+impl std::str::FromStr for TryStateSelect {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim() {
+			"all" => Ok(Self::All),
+			"none" => Ok(Self::None),
+			"" => Err(Error::ParamProcessingError),
+			pallets => Ok(Self::Only(pallets.split(',').map(|p| p.trim().to_string()).collect())),
+		}
+	}
+}
+
+/// The source of the state against which a block is executed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum State {
+	/// The state of a live chain, as provided by a remote node.
+	Live {
+		/// The block to be executed, otherwise the latest finalized block.
+		at: Option<String>,
+	},
+	/// The state contained within a local snapshot.
+	Snapshot {
+		/// The path to the snapshot.
+		path: PathBuf,
+	},
+}
+
+/// Generates the `try-runtime` binary, which is either downloaded from the latest release where
+/// a pre-built binary is available for the platform or otherwise built from source.
+///
+/// # Arguments
+/// * `cache` - The cache directory path.
+pub fn try_runtime_generator(cache: PathBuf) -> Binary {
+	let cli = TryRuntimeCli::TryRuntime;
+	let name = cli.binary().to_string();
+	let repository = cli.repository();
+	let source = match (OS, ARCH) {
+		("linux", "x86_64") => Source::Url {
+			url: format!("{repository}/releases/latest/download/{name}-x86_64-unknown-linux-musl"),
+			name: name.clone(),
+		},
+		_ => Source::Git {
+			url: repository.parse().expect("repository url is valid"),
+			reference: None,
+			manifest: None,
+			package: "try-runtime-cli".into(),
+			artifacts: vec![name.clone()],
+		},
+	};
+	Binary::Source { name, source, cache }
+}
+
+/// Builds the runtime of a project with the `try-runtime` feature enabled, returning the path to
+/// the resulting WebAssembly blob.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+/// * `runtime` - The path to the runtime crate.
+pub fn build_try_runtime(project: &Path, runtime: &Path) -> Result<PathBuf, Error> {
+	build_runtime(project, runtime, Some("try-runtime"))
+}
+
+/// The arguments to `try-runtime` for executing a block against the specified state, checking
+/// that the resulting state root matches that of the block.
+///
+/// # Arguments
+/// * `runtime` - The path to the runtime.
+/// * `uri` - The endpoint of the node from which blocks (and any live state) are fetched.
+/// * `state` - The source of the state.
+/// * `try_state` - The checks to be performed after executing the block.
+pub fn execute_block_args(
+	runtime: &Path,
+	uri: &str,
+	state: &State,
+	try_state: &TryStateSelect,
+) -> Vec<String> {
+	let mut args = vec![
+		format!("--runtime={}", runtime.display()),
+		"execute-block".into(),
+		format!("--try-state={try_state}"),
+	];
+	match state {
+		State::Live { at } => {
+			args.extend(["live".into(), format!("--uri={uri}")]);
+			if let Some(at) = at {
+				args.push(format!("--at={at}"));
+			}
+		},
+		State::Snapshot { path } => {
+			args.push(format!("--block-ws-uri={uri}"));
+			args.extend(["snap".into(), format!("--path={}", path.display())]);
+		},
+	}
+	args
+}
+
+/// The arguments to `try-runtime` for following a chain, executing each newly finalized block.
+///
+/// # Arguments
+/// * `runtime` - The path to the runtime.
+/// * `uri` - The endpoint of the node to be followed.
+/// * `try_state` - The checks to be performed after executing each block.
+pub fn follow_chain_args(runtime: &Path, uri: &str, try_state: &TryStateSelect) -> Vec<String> {
+	vec![
+		format!("--runtime={}", runtime.display()),
+		"follow-chain".into(),
+		format!("--uri={uri}"),
+		format!("--try-state={try_state}"),
+	]
+}
+
+/// The arguments to `try-runtime` for creating a snapshot of the state of a live chain, which can
+/// subsequently be used as the state against which blocks are executed.
+///
+/// # Arguments
+/// * `uri` - The endpoint of the node from which the state is downloaded.
+/// * `at` - The hash of the block at which the state is downloaded.
+/// * `pallets` - The pallets whose storage is downloaded, otherwise all storage.
+/// * `path` - The path to which the snapshot is written.
+pub fn create_snapshot_args(uri: &str, at: &str, pallets: &[String], path: &Path) -> Vec<String> {
+	let mut args = vec!["create-snapshot".into(), format!("--uri={uri}"), format!("--at={at}")];
+	args.extend(pallets.iter().map(|p| format!("--pallet={p}")));
+	args.push(path.display().to_string());
+	args
+}
+
+/// The hash of the latest finalized block of a chain.
+///
+/// # Arguments
+/// * `uri` - The endpoint of the node.
+pub async fn finalized_block(uri: &str) -> Result<H256, Error> {
+	Ok(set_up_client(uri).await?.blocks().at_latest().await?.hash())
+}
+
+/// Creates a snapshot of the state of a live chain via `try-runtime`, returning the last line
+/// logged by `try-runtime` as the error should it fail. The process is killed should the returned
+/// future be dropped (e.g. upon timing out), so the snapshot is not being written in the
+/// background.
+///
+/// # Arguments
+/// * `binary` - The path to the `try-runtime` binary.
+/// * `args` - The arguments, as provided by [`create_snapshot_args`].
+pub async fn create_snapshot(binary: &Path, args: &[String]) -> Result<(), Error> {
+	let output = tokio::process::Command::new(binary)
+		.args(args)
+		.stdout(Stdio::null())
+		.stderr(Stdio::piped())
+		.kill_on_drop(true)
+		.output()
+		.await?;
+	if output.status.success() {
+		return Ok(());
+	}
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	let reason = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or_default();
+	Err(Error::SnapshotError(match reason.is_empty() {
+		true => format!("try-runtime exited with {}", output.status),
+		false => reason.trim().to_string(),
+	}))
+}
+
+/// Runs `try-runtime`, returning whether it completed successfully.
+///
+/// # Arguments
+/// * `binary` - The path to the `try-runtime` binary.
+/// * `args` - The arguments.
+pub fn run_try_runtime(binary: &Path, args: &[String]) -> Result<bool, Error> {
+	Ok(cmd(binary, args).unchecked().run()?.status.success())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+
+	#[test]
+	fn try_runtime_generator_works() {
+		let cache = PathBuf::from("./cache");
+		let binary = try_runtime_generator(cache.clone());
+		assert_eq!(binary.name(), "try-runtime");
+		assert_eq!(binary.path(), cache.join("try-runtime"));
+		let Binary::Source { source, .. } = binary else { panic!("expected a binary source") };
+		match (OS, ARCH) {
+			("linux", "x86_64") => assert_eq!(
+				source,
+				Source::Url {
+					url: "https://github.com/paritytech/try-runtime-cli/releases/latest/download/try-runtime-x86_64-unknown-linux-musl".into(),
+					name: "try-runtime".into()
+				}
+			),
+			_ => assert!(matches!(source, Source::Git { package, .. } if package == "try-runtime-cli")),
+		}
+	}
+
+	#[test]
+	fn try_state_select_works() -> Result<(), Error> {
+		for (input, expected) in [
+			("all", TryStateSelect::All),
+			("none", TryStateSelect::None),
+			("System, Balances", TryStateSelect::Only(vec!["System".into(), "Balances".into()])),
+		] {
+			assert_eq!(input.parse::<TryStateSelect>()?, expected);
+		}
+		assert_eq!(
+			TryStateSelect::Only(vec!["System".into(), "Balances".into()]).to_string(),
+			"System,Balances"
+		);
+		assert!(matches!("".parse::<TryStateSelect>(), Err(Error::ParamProcessingError)));
+		Ok(())
+	}
+
+	#[test]
+	fn execute_block_args_works() {
+		let runtime = Path::new("runtime.wasm");
+		let uri = "ws://127.0.0.1:9944";
+		assert_eq!(
+			execute_block_args(runtime, uri, &State::Live { at: None }, &TryStateSelect::All),
+			vec![
+				"--runtime=runtime.wasm",
+				"execute-block",
+				"--try-state=all",
+				"live",
+				"--uri=ws://127.0.0.1:9944"
+			]
+		);
+		assert_eq!(
+			execute_block_args(
+				runtime,
+				uri,
+				&State::Live { at: Some("0x1234".into()) },
+				&TryStateSelect::None
+			),
+			vec![
+				"--runtime=runtime.wasm",
+				"execute-block",
+				"--try-state=none",
+				"live",
+				"--uri=ws://127.0.0.1:9944",
+				"--at=0x1234"
+			]
+		);
+		assert_eq!(
+			execute_block_args(
+				runtime,
+				uri,
+				&State::Snapshot { path: PathBuf::from("chain.snap") },
+				&TryStateSelect::All
+			),
+			vec![
+				"--runtime=runtime.wasm",
+				"execute-block",
+				"--try-state=all",
+				"--block-ws-uri=ws://127.0.0.1:9944",
+				"snap",
+				"--path=chain.snap"
+			]
+		);
+	}
+
+	#[test]
+	fn follow_chain_args_works() {
+		assert_eq!(
+			follow_chain_args(
+				Path::new("runtime.wasm"),
+				"ws://127.0.0.1:9944",
+				&TryStateSelect::Only(vec!["System".into()])
+			),
+			vec![
+				"--runtime=runtime.wasm",
+				"follow-chain",
+				"--uri=ws://127.0.0.1:9944",
+				"--try-state=System"
+			]
+		);
+	}
+
+	#[test]
+	fn build_try_runtime_fails_without_runtime() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		assert!(matches!(
+			build_try_runtime(temp_dir.path(), &temp_dir.path().join("runtime")),
+			Err(Error::CommonError(pop_common::Error::ManifestPath(..)))
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn create_snapshot_args_works() {
+		assert_eq!(
+			create_snapshot_args(
+				"ws://127.0.0.1:9944",
+				"0x1234",
+				&["Balances".into(), "Assets".into()],
+				Path::new("out.snap")
+			),
+			vec![
+				"create-snapshot",
+				"--uri=ws://127.0.0.1:9944",
+				"--at=0x1234",
+				"--pallet=Balances",
+				"--pallet=Assets",
+				"out.snap"
+			]
+		);
+	}
+
+	#[tokio::test]
+	async fn create_snapshot_reports_failure() -> Result<(), Error> {
+		let args =
+			["-c".to_string(), "echo connecting >&2; echo 'no route to host' >&2; exit 1".into()];
+		assert!(matches!(
+			create_snapshot(Path::new("sh"), &args).await,
+			Err(Error::SnapshotError(reason)) if reason == "no route to host"
+		));
+		create_snapshot(Path::new("sh"), &["-c".to_string(), "exit 0".into()]).await
+	}
+
+	#[tokio::test]
+	async fn create_snapshot_is_killed_when_dropped() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("out.snap");
+		let args = ["-c".to_string(), format!("sleep 1; touch {}", path.display())];
+		let result = tokio::time::timeout(
+			Duration::from_millis(100),
+			create_snapshot(Path::new("sh"), &args),
+		)
+		.await;
+		assert!(result.is_err());
+		tokio::time::sleep(Duration::from_secs(2)).await;
+		assert!(!path.exists());
+		Ok(())
+	}
+}
@@ -0,0 +1,332 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{blocks::display, errors::Error};
+use scale_info::PortableRegistry;
+use scale_value::{
+	stringify::{custom_formatters::format_hex, to_writer_custom},
+	Composite, Value, ValueDef,
+};
+use std::{
+	fmt::{Display, Formatter},
+	fs,
+	path::Path,
+	str::FromStr,
+};
+use subxt::{ext::codec::Decode, Metadata};
+
+/// Identifies a type within a type registry, such as that of the metadata of a chain.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TypeRef {
+	/// The identifier of the type.
+	Id(u32),
+	/// The path of the type, e.g. `pallet_balances::types::AccountData`, where intermediate
+	/// modules may be omitted, e.g. `pallet_balances::AccountData`.
+	Path(String),
+}
+
+impl FromStr for TypeRef {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Ok(id) = s.parse() {
+			return Ok(Self::Id(id));
+		}
+		match s.split("::").all(|s| !s.is_empty()) {
+			true => Ok(Self::Path(s.to_string())),
+			false => Err(Error::ParamProcessingError),
+		}
+	}
+}
+
+/// Resolves the identifier of a type within a type registry.
+///
+/// # Arguments
+/// * `registry` - The type registry.
+/// * `ty` - The type to be resolved.
+pub fn resolve_type(registry: &PortableRegistry, ty: &TypeRef) -> Result<u32, Error> {
+	let path = match ty {
+		TypeRef::Id(id) =>
+			return registry.resolve(*id).map(|_| *id).ok_or(Error::TypeNotFound(id.to_string())),
+		TypeRef::Path(path) => path,
+	};
+	let query: Vec<_> = path.split("::").collect();
+	let matches: Vec<_> = registry
+		.types
+		.iter()
+		.filter(|t| {
+			let segments = &t.ty.path.segments;
+			// The name must match, with any other segments matching in order.
+			segments.last().map(String::as_str) == query.last().copied() &&
+				segments.first().map(String::as_str) == query.first().copied() &&
+				is_subsequence(&query, segments)
+		})
+		.collect();
+	match matches.as_slice() {
+		[] => Err(Error::TypeNotFound(path.clone())),
+		[ty] => Ok(ty.id),
+		matches => {
+			let candidates: Vec<_> =
+				matches.iter().map(|t| format!("{} ({})", t.id, t.ty.path)).collect();
+			Err(Error::AmbiguousType(format!("{path} matches {}", candidates.join(", "))))
+		},
+	}
+}
+
+/// Decodes SCALE-encoded bytes as a type of a type registry, with any bytes displayed as hex.
+///
+/// # Arguments
+/// * `registry` - The type registry.
+/// * `ty` - The identifier of the type.
+/// * `bytes` - The encoded value.
+pub fn decode_as_type(registry: &PortableRegistry, ty: u32, bytes: &[u8]) -> Result<String, Error> {
+	decode_value(registry, ty, bytes).map(|value| display(&value))
+}
+
+/// A call of a dispatchable function, decoded via the metadata of a chain.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecodedCall {
+	/// The pallet containing the dispatchable function.
+	pub pallet: String,
+	/// The name of the dispatchable function.
+	pub function: String,
+	/// The arguments of the function by name, with any nested values spread across lines.
+	pub args: Vec<(String, String)>,
+}
+
+impl Display for DecodedCall {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}.{}", self.pallet, self.function)?;
+		if self.args.is_empty() {
+			return Ok(());
+		}
+		let args: Vec<_> =
+			self.args.iter().map(|(name, value)| format!("  {name}: {value}")).collect();
+		write!(f, " {{\n{}\n}}", args.join(",\n"))
+	}
+}
+
+/// Decodes SCALE-encoded call data, such as that of a governance referendum, as a call of a
+/// dispatchable function of a runtime.
+///
+/// # Arguments
+/// * `metadata` - The metadata of the runtime.
+/// * `bytes` - The encoded call data.
+pub fn decode_call(metadata: &Metadata, bytes: &[u8]) -> Result<DecodedCall, Error> {
+	decode_call_as(metadata.types(), metadata.outer_enums().call_enum_ty(), bytes)
+}
+
+/// Loads the metadata of a runtime from a file, as SCALE-encoded bytes such as those output by
+/// `subxt metadata`.
+///
+/// # Arguments
+/// * `path` - The path to the file.
+pub fn load_metadata(path: &Path) -> Result<Metadata, Error> {
+	let bytes = fs::read(path)?;
+	Metadata::decode(&mut bytes.as_slice())
+		.map_err(|e| Error::MetadataParsingError(format!("{}: {e}", path.display())))
+}
+
+// Decodes a call as the outer call type of a runtime, whose variants are the calls of each pallet.
+fn decode_call_as(
+	registry: &PortableRegistry,
+	ty: u32,
+	bytes: &[u8],
+) -> Result<DecodedCall, Error> {
+	let value = decode_value(registry, ty, bytes)?;
+	let invalid = || Error::DecodingError(format!("{} is not a call", display(&value)));
+	let ValueDef::Variant(pallet) = &value.value else { return Err(invalid()) };
+	let Some(ValueDef::Variant(function)) = pallet.values.values().next().map(|v| &v.value) else {
+		return Err(invalid());
+	};
+	let args = match &function.values {
+		Composite::Named(fields) => fields
+			.iter()
+			.map(|(name, value)| (name.clone(), display_pretty(value)))
+			.collect(),
+		Composite::Unnamed(values) => values
+			.iter()
+			.enumerate()
+			.map(|(index, value)| (index.to_string(), display_pretty(value)))
+			.collect(),
+	};
+	Ok(DecodedCall { pallet: pallet.name.clone(), function: function.name.clone(), args })
+}
+
+fn decode_value(registry: &PortableRegistry, ty: u32, bytes: &[u8]) -> Result<Value<u32>, Error> {
+	let mut input = bytes;
+	let value: Value<u32> = scale_value::scale::decode_as_type(&mut input, ty, registry)
+		.map_err(|e| Error::DecodingError(e.to_string()))?;
+	if !input.is_empty() {
+		return Err(Error::DecodingError(format!(
+			"{} bytes remain after decoding {}",
+			input.len(),
+			display(&value)
+		)));
+	}
+	Ok(value)
+}
+
+// Displays a value across lines, indented to be nested within the arguments of a call.
+fn display_pretty<T>(value: &Value<T>) -> String {
+	let mut output = String::new();
+	let result = to_writer_custom()
+		.pretty()
+		.leading_indent("  ")
+		.add_custom_formatter(|v, w| format_hex(v, w))
+		.write(value, &mut output);
+	match result {
+		Ok(()) => output,
+		Err(_) => display(value),
+	}
+}
+
+// Whether the segments of a query occur in order within a path.
+fn is_subsequence(query: &[&str], path: &[String]) -> bool {
+	let mut path = path.iter();
+	query.iter().all(|q| path.any(|p| p == q))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use scale_info::{MetaType, Registry, TypeInfo};
+
+	#[allow(dead_code)]
+	#[derive(TypeInfo)]
+	struct AccountData {
+		free: u128,
+		reserved: u128,
+		id: [u8; 2],
+	}
+
+	#[allow(dead_code)]
+	#[derive(TypeInfo)]
+	struct Wrapper<T>(T);
+
+	#[allow(dead_code)]
+	#[derive(TypeInfo)]
+	enum RuntimeCall {
+		System(SystemCall),
+		Balances(BalancesCall),
+	}
+
+	#[allow(dead_code, non_camel_case_types)]
+	#[derive(TypeInfo)]
+	enum SystemCall {
+		remark { remark: Vec<u8> },
+	}
+
+	#[allow(dead_code, non_camel_case_types)]
+	#[derive(TypeInfo)]
+	enum BalancesCall {
+		transfer_keep_alive { dest: AccountData, value: u128 },
+		upgrade_accounts(Vec<[u8; 2]>),
+	}
+
+	fn registry() -> (PortableRegistry, u32) {
+		let mut registry = Registry::new();
+		let id = registry.register_type(&MetaType::new::<AccountData>()).id;
+		registry.register_type(&MetaType::new::<Wrapper<u8>>());
+		registry.register_type(&MetaType::new::<Wrapper<u16>>());
+		(registry.into(), id)
+	}
+
+	#[test]
+	fn type_ref_from_str_works() {
+		assert_eq!("42".parse::<TypeRef>().unwrap(), TypeRef::Id(42));
+		assert_eq!(
+			"pallet_balances::AccountData".parse::<TypeRef>().unwrap(),
+			TypeRef::Path("pallet_balances::AccountData".into())
+		);
+		assert!("pallet_balances::".parse::<TypeRef>().is_err());
+	}
+
+	#[test]
+	fn resolve_type_works() {
+		let (registry, id) = registry();
+		let path = |p: &str| TypeRef::Path(p.into());
+		assert_eq!(resolve_type(&registry, &TypeRef::Id(id)).unwrap(), id);
+		assert_eq!(
+			resolve_type(&registry, &path("pop_parachains::types::tests::AccountData")).unwrap(),
+			id
+		);
+		assert_eq!(resolve_type(&registry, &path("pop_parachains::AccountData")).unwrap(), id);
+		assert!(matches!(
+			resolve_type(&registry, &path("pallet_balances::AccountData")),
+			Err(Error::TypeNotFound(_))
+		));
+		assert!(matches!(
+			resolve_type(&registry, &path("pop_parachains::Wrapper")),
+			Err(Error::AmbiguousType(_))
+		));
+		assert!(matches!(resolve_type(&registry, &TypeRef::Id(999)), Err(Error::TypeNotFound(_))));
+	}
+
+	#[test]
+	fn load_metadata_fails_on_invalid_file() -> Result<(), Error> {
+		let temp_dir = tempfile::tempdir()?;
+		let path = temp_dir.path().join("metadata.scale");
+		fs::write(&path, [0, 1, 2])?;
+		assert!(matches!(load_metadata(&path), Err(Error::MetadataParsingError(_))));
+		assert!(matches!(load_metadata(&temp_dir.path().join("missing")), Err(Error::IO(_))));
+		Ok(())
+	}
+
+	#[test]
+	fn decode_as_type_works() {
+		let (registry, id) = registry();
+		let mut bytes = 100u128.to_le_bytes().to_vec();
+		bytes.extend(5u128.to_le_bytes());
+		bytes.extend([0xab, 0xcd]);
+		assert_eq!(
+			decode_as_type(&registry, id, &bytes).unwrap(),
+			"{ free: 100, reserved: 5, id: 0xABCD }"
+		);
+		bytes.push(0);
+		assert!(matches!(decode_as_type(&registry, id, &bytes), Err(Error::DecodingError(_))));
+		assert!(matches!(decode_as_type(&registry, id, &[0]), Err(Error::DecodingError(_))));
+	}
+
+	#[test]
+	fn decode_call_works() {
+		let mut registry = Registry::new();
+		let ty = registry.register_type(&MetaType::new::<RuntimeCall>()).id;
+		let registry: PortableRegistry = registry.into();
+
+		let call = decode_call_as(&registry, ty, &[0, 0, 8, 0x11, 0x22]).unwrap();
+		assert_eq!(
+			call,
+			DecodedCall {
+				pallet: "System".into(),
+				function: "remark".into(),
+				args: vec![("remark".into(), "0x1122".into())]
+			}
+		);
+		assert_eq!(call.to_string(), "System.remark {\n  remark: 0x1122\n}");
+
+		let mut bytes = vec![1, 0];
+		bytes.extend(1u128.to_le_bytes());
+		bytes.extend(2u128.to_le_bytes());
+		bytes.extend([0xab, 0xcd]);
+		bytes.extend(100u128.to_le_bytes());
+		let call = decode_call_as(&registry, ty, &bytes).unwrap();
+		assert_eq!(
+			(call.pallet.as_str(), call.function.as_str()),
+			("Balances", "transfer_keep_alive")
+		);
+		assert_eq!(
+			call.to_string(),
+			"Balances.transfer_keep_alive {\n  dest: {\n    free: 1,\n    reserved: 2,\n    \
+			 id: 0xABCD\n  },\n  value: 100\n}"
+		);
+		// Unnamed arguments are identified by their position.
+		let call = decode_call_as(&registry, ty, &[1, 1, 4, 0xab, 0xcd]).unwrap();
+		assert_eq!(call.args, vec![("0".into(), "(\n    0xABCD\n  )".into())]);
+
+		assert!(matches!(decode_call_as(&registry, ty, &[2]), Err(Error::DecodingError(_))));
+		assert!(matches!(
+			decode_call_as(&registry, ty, &[0, 0, 0, 0]),
+			Err(Error::DecodingError(_))
+		));
+	}
+}
@@ -1,6 +1,10 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::errors::Error;
+use crate::{
+	errors::Error, omni_node::omni_node_generator, runtime::RuntimeVersion,
+	telemetry::command_with_telemetry, ChainSpec,
+};
+use duct::cmd;
 use glob::glob;
 use indexmap::IndexMap;
 pub use pop_common::{
@@ -34,6 +38,8 @@ pub struct Zombienet {
 	parachains: IndexMap<u32, Parachain>,
 	/// Whether any HRMP channels are to be pre-opened.
 	hrmp_channels: bool,
+	/// The telemetry endpoint to which the nodes of the network submit telemetry, if any.
+	telemetry_url: Option<String>,
 }
 
 impl Zombienet {
@@ -90,7 +96,8 @@ impl Zombienet {
 		.await?;
 		let hrmp_channels =
 			network_config.hrmp_channels().map(|c| !c.is_empty()).unwrap_or_default();
-		Ok(Self { network_config, relay_chain, parachains, hrmp_channels })
+		let telemetry_url = network_config.telemetry_url().map(String::from);
+		Ok(Self { network_config, relay_chain, parachains, hrmp_channels, telemetry_url })
 	}
 
 	/// The binaries required to launch the network.
@@ -202,6 +209,22 @@ impl Zombienet {
 				continue;
 			}
 
+			// Check if the parachain is launched via the omni node, e.g. for a project without a
+			// node of its own
+			let omni_node = omni_node_generator(cache.to_path_buf());
+			if command == omni_node.name() {
+				paras.insert(
+					id,
+					Parachain {
+						id,
+						binary: omni_node,
+						chain: chain.map(|c| c.to_string()),
+						chain_spec_generator: None,
+					},
+				);
+				continue;
+			}
+
 			// Check if parachain binary source specified as an argument
 			if let Some(parachains) = parachains.as_ref() {
 				if let Some(repo) = parachains.iter().find(|r| command == r.package) {
@@ -312,8 +335,122 @@ impl Zombienet {
 		self.hrmp_channels
 	}
 
+	/// The telemetry endpoint to which the nodes of the network submit telemetry, if any.
+	pub fn telemetry_url(&self) -> Option<&str> {
+		self.telemetry_url.as_deref()
+	}
+
+	/// Sets the telemetry endpoint to which the nodes of the network submit telemetry,
+	/// overriding any `telemetry_url` specified within the `settings` of the network
+	/// configuration.
+	///
+	/// # Arguments
+	/// * `url` - The telemetry endpoint, optionally followed by the verbosity (e.g.
+	///   `wss://telemetry.polkadot.io/submit/ 0`).
+	pub fn set_telemetry_url(&mut self, url: &str) {
+		self.telemetry_url = Some(url.into());
+	}
+
+	/// The identifiers of the parachains launched via `polkadot-omni-node` without a chain
+	/// specification, which is therefore to be generated from a runtime.
+	pub fn omni_node_parachains(&self) -> Vec<u32> {
+		let Some(tables) = self.network_config.parachains() else {
+			return vec![];
+		};
+		tables
+			.iter()
+			.filter(|t| !t.contains_key("chain_spec_path"))
+			.filter_map(|t| t.get("id").and_then(|i| i.as_integer()).map(|i| i as u32))
+			.filter(|id| {
+				self.parachains
+					.get(id)
+					.is_some_and(|p| p.binary.name() == omni_node_generator(PathBuf::new()).name())
+			})
+			.collect()
+	}
+
+	/// Sets the chain specification used to launch a parachain.
+	///
+	/// # Arguments
+	/// * `id` - The parachain identifier on the local network.
+	/// * `path` - The path to the chain specification.
+	pub fn set_chain_spec(&mut self, id: u32, path: &Path) -> Result<(), Error> {
+		let path = NetworkConfiguration::resolve_path(path)?;
+		let table = self
+			.network_config
+			.parachain_mut(id)
+			.ok_or_else(|| Error::Config(format!("expected parachain {id} to be configured")))?;
+		table.insert("chain_spec_path", value(path));
+		Ok(())
+	}
+
+	/// The version of the runtime of the relay chain, or of a parachain, as per the chain
+	/// specification generated by the binaries of the network. `None` is returned if the chain
+	/// specification does not contain the code of the runtime.
+	///
+	/// # Arguments
+	/// * `id` - The parachain identifier on the local network, or `None` for the relay chain.
+	pub fn runtime_version(&self, id: Option<u32>) -> Result<Option<RuntimeVersion>, Error> {
+		let chain_spec = match id {
+			None => generate_chain_spec(
+				&self.relay_chain.binary,
+				self.relay_chain.chain_spec_generator.as_ref(),
+				Some(&self.relay_chain.chain),
+			)?,
+			Some(id) => {
+				let path = self
+					.network_config
+					.parachain(id)
+					.and_then(|t| t.get("chain_spec_path"))
+					.and_then(|p| p.as_str());
+				match (path, self.parachains.get(&id)) {
+					(Some(path), _) => std::fs::read_to_string(path)?,
+					(None, Some(para)) => generate_chain_spec(
+						&para.binary,
+						para.chain_spec_generator.as_ref(),
+						para.chain.as_deref(),
+					)?,
+					(None, None) =>
+						return Err(Error::Config(format!(
+							"expected parachain {id} to be configured"
+						))),
+				}
+			},
+		};
+		let chain_spec: ChainSpec = chain_spec.parse()?;
+		chain_spec.get_code().map(|code| RuntimeVersion::from_code(&code)).transpose()
+	}
+
+	/// Replaces the runtime of the relay chain, or of a parachain, within the chain specification
+	/// generated when the network is spawned.
+	///
+	/// # Arguments
+	/// * `id` - The parachain identifier on the local network, or `None` for the relay chain.
+	/// * `path` - The path to the runtime WebAssembly blob.
+	pub fn set_runtime(&mut self, id: Option<u32>, path: &Path) -> Result<(), Error> {
+		let path = NetworkConfiguration::resolve_path(path)?;
+		let table = match id {
+			None => self.network_config.relay_chain_mut()?,
+			Some(id) => self.network_config.parachain_mut(id).ok_or_else(|| {
+				Error::Config(format!("expected parachain {id} to be configured"))
+			})?,
+		};
+		table.insert("wasm_override", value(path));
+		Ok(())
+	}
+
 	/// Launches the local network.
 	pub async fn spawn(&mut self) -> Result<Network<LocalFileSystem>, Error> {
+		let config = self.prepare()?;
+		let path = config.path().to_str().expect("temp config file should have a path");
+		let network_config = NetworkConfig::load_from_toml(path)?;
+		Ok(network_config.spawn_native().await?)
+	}
+
+	/// Prepares the network for launching, returning a network configuration file with the paths
+	/// of the sourced binaries resolved, e.g. for launching the network via zombienet-sdk from
+	/// tests.
+	pub fn prepare(&mut self) -> Result<NamedTempFile, Error> {
 		// Symlink polkadot workers
 		let relay_chain_binary_path = self.relay_chain.binary.path();
 		if !relay_chain_binary_path.exists() {
@@ -336,11 +473,11 @@ impl Zombienet {
 			symlink_file(cache.join(format!("{worker}-{version}")), dest)?;
 		}
 
-		// Load from config and spawn network
-		let config = self.network_config.configure(&self.relay_chain, &self.parachains)?;
-		let path = config.path().to_str().expect("temp config file should have a path");
-		let network_config = NetworkConfig::load_from_toml(path)?;
-		Ok(network_config.spawn_native().await?)
+		self.network_config.configure(
+			&self.relay_chain,
+			&self.parachains,
+			self.telemetry_url.as_deref(),
+		)
 	}
 }
 
@@ -390,11 +527,33 @@ impl NetworkConfiguration {
 		self.0.get_mut("parachains").and_then(|p| p.as_array_of_tables_mut())
 	}
 
+	/// Returns the configuration of the parachain with the specified identifier.
+	fn parachain(&self, id: u32) -> Option<&Table> {
+		self.parachains()?
+			.iter()
+			.find(|t| t.get("id").and_then(|i| i.as_integer()) == Some(id.into()))
+	}
+
+	/// Returns the configuration of the parachain with the specified identifier.
+	fn parachain_mut(&mut self, id: u32) -> Option<&mut Table> {
+		self.parachains_mut()?
+			.iter_mut()
+			.find(|t| t.get("id").and_then(|i| i.as_integer()) == Some(id.into()))
+	}
+
 	/// Returns the `hrmp_channels` configuration.
 	fn hrmp_channels(&self) -> Option<&ArrayOfTables> {
 		self.0.get("hrmp_channels").and_then(|p| p.as_array_of_tables())
 	}
 
+	/// Returns the `telemetry_url` specified within the `settings` configuration.
+	fn telemetry_url(&self) -> Option<&str> {
+		self.0
+			.get("settings")
+			.and_then(|s| s.get("telemetry_url"))
+			.and_then(|u| u.as_str())
+	}
+
 	/// Returns the `command` configuration.
 	fn command(config: &Table) -> Option<&Item> {
 		config.get("command")
@@ -426,10 +585,12 @@ impl NetworkConfiguration {
 	/// # Arguments
 	/// * `relay_chain` - The configuration required to launch the relay chain.
 	/// * `parachains` - The configuration required to launch the parachain(s).
+	/// * `telemetry_url` - The telemetry endpoint to which the nodes submit telemetry, if any.
 	fn configure(
 		&mut self,
 		relay_chain: &RelayChain,
 		parachains: &IndexMap<u32, Parachain>,
+		telemetry_url: Option<&str>,
 	) -> Result<NamedTempFile, Error> {
 		// Add zombienet-sdk specific settings if missing
 		let settings = self
@@ -444,10 +605,13 @@ impl NetworkConfiguration {
 		settings
 			.entry("node_spawn_timeout")
 			.or_insert(Item::Value(Value::Integer(Formatted::new(300))));
+		// Remove pop specific settings, unknown to zombienet-sdk
+		settings.remove("telemetry_url");
 
 		// Update relay chain config
 		let relay_chain_config = self.relay_chain_mut()?;
-		let relay_chain_binary_path = Self::resolve_path(&relay_chain.binary.path())?;
+		let relay_chain_binary_path =
+			Self::resolve_command(&relay_chain.binary.path(), telemetry_url)?;
 		*relay_chain_config
 			.entry("default_command")
 			.or_insert(value(&relay_chain_binary_path)) = value(&relay_chain_binary_path);
@@ -477,7 +641,7 @@ impl NetworkConfiguration {
 					parachains.get(&id).expect("expected parachain existence due to preprocessing");
 
 				// Resolve default_command to binary
-				let path = Self::resolve_path(&para.binary.path())?;
+				let path = Self::resolve_command(&para.binary.path(), telemetry_url)?;
 				table.insert("default_command", value(&path));
 
 				// Configure chain spec generator
@@ -520,6 +684,20 @@ impl NetworkConfiguration {
 		Ok(network_config_file)
 	}
 
+	/// Resolves the command used to launch nodes, which submits telemetry to the telemetry
+	/// endpoint if specified.
+	///
+	/// # Arguments
+	/// * `path` - The path to the binary of the nodes.
+	/// * `telemetry_url` - The telemetry endpoint to which the nodes submit telemetry, if any.
+	fn resolve_command(path: &Path, telemetry_url: Option<&str>) -> Result<String, Error> {
+		let path = Self::resolve_path(path)?;
+		match telemetry_url {
+			Some(url) => command_with_telemetry(&path, url),
+			None => Ok(path),
+		}
+	}
+
 	/// Resolves the canonical path of a command specified within a network configuration file.
 	///
 	/// # Arguments
@@ -534,6 +712,31 @@ impl NetworkConfiguration {
 	}
 }
 
+/// Generates the chain specification of a chain, using its chain specification generator if
+/// available.
+///
+/// # Arguments
+/// * `binary` - The binary used to launch the nodes of the chain.
+/// * `chain_spec_generator` - The binary used to generate the chain specification, if any.
+/// * `chain` - The name of the chain, if specified.
+fn generate_chain_spec(
+	binary: &Binary,
+	chain_spec_generator: Option<&Binary>,
+	chain: Option<&str>,
+) -> Result<String, Error> {
+	let output = match chain_spec_generator {
+		Some(generator) => cmd(generator.path(), chain).stderr_null().read()?,
+		None => {
+			let mut args = vec!["build-spec"];
+			if let Some(chain) = chain {
+				args.extend(["--chain", chain]);
+			}
+			cmd(binary.path(), args).stderr_null().read()?
+		},
+	};
+	Ok(output)
+}
+
 /// The configuration required to launch the relay chain.
 struct RelayChain {
 	/// The binary used to launch a relay chain node.
@@ -541,7 +744,6 @@ struct RelayChain {
 	/// The additional workers required by the relay chain node.
 	workers: [&'static str; 2],
 	/// The name of the chain.
-	#[allow(dead_code)]
 	chain: String,
 	/// If applicable, the binary used to generate a chain specification.
 	chain_spec_generator: Option<Binary>,
@@ -716,6 +918,8 @@ mod tests {
 	mod zombienet {
 		use super::*;
 		use pop_common::Status;
+		use sp_core::bytes::to_hex;
+		use subxt::ext::codec::Encode;
 
 		pub(crate) struct Output;
 		impl Status for Output {
@@ -1155,6 +1359,115 @@ default_command = "./target/release/parachain-template-node"
 			Ok(())
 		}
 
+		#[tokio::test]
+		async fn new_with_omni_node_works() -> Result<()> {
+			let temp_dir = tempdir()?;
+			let cache = PathBuf::from(temp_dir.path());
+			let config = Builder::new().suffix(".toml").tempfile()?;
+			writeln!(
+				config.as_file(),
+				r#"
+[relaychain]
+chain = "paseo-local"
+
+[[parachains]]
+id = 2000
+default_command = "polkadot-omni-node"
+
+[[parachains]]
+id = 2001
+default_command = "polkadot-omni-node"
+chain_spec_path = "./chain-spec.json"
+"#
+			)?;
+
+			let mut zombienet = Zombienet::new(
+				&cache,
+				config.path().to_str().unwrap(),
+				None,
+				None,
+				None,
+				None,
+				None,
+			)
+			.await?;
+
+			assert_eq!(zombienet.parachains.len(), 2);
+			let omni_node = &zombienet.parachains.get(&2000).unwrap().binary;
+			assert_eq!(omni_node, &omni_node_generator(cache.clone()));
+			// Only parachains without a chain specification require one to be generated.
+			assert_eq!(zombienet.omni_node_parachains(), vec![2000]);
+			let chain_spec = temp_dir.path().join("chain-spec.json");
+			File::create(&chain_spec)?;
+			zombienet.set_chain_spec(2000, &chain_spec)?;
+			assert!(zombienet.omni_node_parachains().is_empty());
+			assert!(matches!(zombienet.set_chain_spec(2002, &chain_spec), Err(Error::Config(_))));
+			Ok(())
+		}
+
+		#[tokio::test]
+		async fn set_runtime_works() -> Result<()> {
+			let temp_dir = tempdir()?;
+			let cache = PathBuf::from(temp_dir.path());
+			// A chain specification containing a minimal runtime, embedding its version.
+			let version = ("pop".to_string(), "pop".to_string(), 0u32, 1_000u32).encode();
+			let section = [&[15], b"runtime_version".as_slice(), &version].concat();
+			let code =
+				[b"\0asm\x01\0\0\0".as_slice(), &[0, section.len() as u8], &section].concat();
+			let chain_spec = temp_dir.path().join("chain-spec.json");
+			std::fs::write(
+				&chain_spec,
+				format!(
+					r#"{{"genesis":{{"runtimeGenesis":{{"code":"{}"}}}}}}"#,
+					to_hex(&code, false)
+				),
+			)?;
+			let config = Builder::new().suffix(".toml").tempfile()?;
+			writeln!(
+				config.as_file(),
+				r#"
+[relaychain]
+chain = "paseo-local"
+
+[[parachains]]
+id = 2000
+default_command = "./target/release/parachain-template-node"
+chain_spec_path = "{}"
+"#,
+				chain_spec.display()
+			)?;
+
+			let mut zombienet = Zombienet::new(
+				&cache,
+				config.path().to_str().unwrap(),
+				None,
+				None,
+				None,
+				None,
+				None,
+			)
+			.await?;
+
+			assert_eq!(
+				zombienet.runtime_version(Some(2000))?,
+				Some(RuntimeVersion { spec_name: "pop".into(), spec_version: 1_000 })
+			);
+			let runtime = temp_dir.path().join("runtime.wasm");
+			std::fs::write(&runtime, &code)?;
+			zombienet.set_runtime(Some(2000), &runtime)?;
+			zombienet.set_runtime(None, &runtime)?;
+			let runtime = runtime.canonicalize()?.display().to_string();
+			let parachain = zombienet.network_config.parachain(2000).unwrap();
+			assert_eq!(parachain.get("wasm_override").and_then(|p| p.as_str()), Some(&*runtime));
+			let relay_chain = zombienet.network_config.relay_chain()?;
+			assert_eq!(relay_chain.get("wasm_override").and_then(|p| p.as_str()), Some(&*runtime));
+			assert!(matches!(
+				zombienet.set_runtime(Some(2001), Path::new(&runtime)),
+				Err(Error::Config(_))
+			));
+			Ok(())
+		}
+
 		#[tokio::test]
 		async fn new_with_local_parachain_without_path_works() -> Result<()> {
 			let temp_dir = tempdir()?;
@@ -1820,6 +2133,7 @@ command = "./target/release/parachain-template-node"
 					),
 				]
 				.into(),
+				None,
 			)?;
 			assert_eq!("toml", configured.path().extension().unwrap());
 
@@ -1956,6 +2270,7 @@ command = "polkadot-parachain"
 					},
 				)]
 				.into(),
+				None,
 			)?;
 			assert_eq!("toml", configured.path().extension().unwrap());
 
@@ -2001,6 +2316,79 @@ node_spawn_timeout = 300
 			Ok(())
 		}
 
+		#[test]
+		fn configure_with_telemetry_works() -> Result<(), Error> {
+			let config = Builder::new().suffix(".toml").tempfile()?;
+			writeln!(
+				config.as_file(),
+				r#"
+[settings]
+telemetry_url = "wss://telemetry.polkadot.io/submit/ 1"
+
+[relaychain]
+chain = "paseo-local"
+
+[[relaychain.nodes]]
+name = "alice"
+
+[[parachains]]
+id = 2000
+
+[[parachains.collators]]
+name = "pop"
+command = "pop-node"
+"#
+			)?;
+			let mut network_config = NetworkConfiguration::from(config.path())?;
+			let telemetry_url = network_config.telemetry_url().map(String::from);
+			assert_eq!(telemetry_url.as_deref(), Some("wss://telemetry.polkadot.io/submit/ 1"));
+
+			let temp_dir = tempdir()?;
+			let (relay_chain, pop) =
+				(temp_dir.path().join("polkadot"), temp_dir.path().join("pop-node"));
+			File::create(&relay_chain)?;
+			File::create(&pop)?;
+			let mut configured = network_config.configure(
+				&RelayChain {
+					binary: Binary::Local {
+						name: "polkadot".to_string(),
+						path: relay_chain.clone(),
+						manifest: None,
+					},
+					workers: ["polkadot-execute-worker", ""],
+					chain: "paseo-local".to_string(),
+					chain_spec_generator: None,
+				},
+				&[(
+					2000,
+					Parachain {
+						id: 2000,
+						binary: Binary::Local {
+							name: "pop-node".to_string(),
+							path: pop.clone(),
+							manifest: None,
+						},
+						chain: None,
+						chain_spec_generator: None,
+					},
+				)]
+				.into(),
+				telemetry_url.as_deref(),
+			)?;
+
+			let mut contents = String::new();
+			configured.read_to_string(&mut contents)?;
+			// The nodes are launched via scripts which submit telemetry.
+			let telemetry = temp_dir.path().canonicalize()?.join(".telemetry");
+			let (relay_chain, pop) = (telemetry.join("polkadot"), telemetry.join("pop-node"));
+			assert!(contents.contains(&format!("default_command = \"{}\"", relay_chain.display())));
+			assert!(contents.contains(&format!("command = \"{}\"", pop.display())));
+			assert!(!contents.contains("telemetry_url"));
+			assert!(std::fs::read_to_string(pop)?
+				.contains(r#"--telemetry-url "wss://telemetry.polkadot.io/submit/ 1""#));
+			Ok(())
+		}
+
 		#[test]
 		fn resolves_path() -> Result<(), Error> {
 			let working_dir = tempdir()?;
@@ -1,10 +1,11 @@
 // SPDX-License-Identifier: GPL-3.0
 
 use crate::errors::Error;
+use pop_common::manifest::from_path;
 use std::{
 	fs::{self, OpenOptions},
 	io::{self, stdin, stdout, Write},
-	path::Path,
+	path::{Component, Path, PathBuf},
 };
 
 pub(crate) fn sanitize(target: &Path) -> Result<(), Error> {
@@ -68,6 +69,7 @@ pub(crate) fn write_to_file(path: &Path, contents: &str) -> Result<(), Error> {
 
 	if path.extension().map_or(false, |ext| ext == "rs") {
 		let output = std::process::Command::new("rustfmt")
+			.args(["--edition", "2021"])
 			.arg(path.to_str().unwrap())
 			.output()
 			.map_err(Error::RustfmtError)?;
@@ -83,6 +85,55 @@ pub(crate) fn write_to_file(path: &Path, contents: &str) -> Result<(), Error> {
 	Ok(())
 }
 
+/// Renders a template and writes it to the specified file.
+pub(crate) fn render_to_file(path: &Path, template: impl askama::Template) -> Result<(), Error> {
+	let rendered = template.render().map_err(anyhow::Error::from)?;
+	write_to_file(path, &rendered)
+}
+
+/// Renders the entries of a manifest for the specified dependencies, inheriting any provided by
+/// the workspace.
+///
+/// # Arguments
+/// * `workspace` - The manifest of the workspace, if any.
+/// * `dependencies` - The name via which each dependency is referenced, its package and the version
+///   used when not already provided by the workspace.
+pub(crate) fn manifest_dependencies(
+	workspace: Option<&Path>,
+	dependencies: &[(&str, &str, &str)],
+) -> Result<Vec<String>, Error> {
+	let workspace_dependencies = match workspace {
+		Some(workspace) => from_path(Some(workspace))?
+			.workspace
+			.map(|w| w.dependencies)
+			.unwrap_or_default(),
+		None => Default::default(),
+	};
+	Ok(dependencies
+		.iter()
+		.map(|(name, package, version)| {
+			let inherited = workspace_dependencies
+				.get(*name)
+				.is_some_and(|d| d.package().unwrap_or(name) == *package);
+			match (inherited, name == package) {
+				(true, _) => format!("{name} = {{ workspace = true }}"),
+				(false, true) => format!("{name} = \"{version}\""),
+				(false, false) =>
+					format!("{name} = {{ package = \"{package}\", version = \"{version}\" }}"),
+			}
+		})
+		.collect())
+}
+
+/// The path to a directory, relative to another directory.
+pub(crate) fn relative_path(from: &Path, to: &Path) -> PathBuf {
+	let (from, to): (Vec<_>, Vec<_>) = (from.components().collect(), to.components().collect());
+	let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+	let mut path: PathBuf = from[common..].iter().map(|_| Component::ParentDir).collect();
+	path.extend(&to[common..]);
+	path
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -128,4 +179,20 @@ mod tests {
 		let result = is_valid_bitwise_left_shift("wrong");
 		assert!(result.is_err());
 	}
+
+	#[test]
+	fn relative_path_works() {
+		assert_eq!(
+			relative_path(Path::new("/project/xcm-tests"), Path::new("/project/runtime")),
+			PathBuf::from("../runtime")
+		);
+		assert_eq!(
+			relative_path(Path::new("/project/tests/xcm"), Path::new("/project/runtime")),
+			PathBuf::from("../../runtime")
+		);
+		assert_eq!(
+			relative_path(Path::new("/project"), Path::new("/project/runtime")),
+			PathBuf::from("runtime")
+		);
+	}
 }
@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{set_up_client, Error};
+use std::{fmt, time::Duration};
+use subxt::{
+	blocks::Block,
+	dynamic::{self, Value},
+	ext::codec::{Compact, Decode},
+	utils::H256,
+	OnlineClient, SubstrateConfig,
+};
+
+/// The number of the most recent finalized blocks searched for an extrinsic which may already have
+/// been finalized.
+const FINALIZED_LOOKBACK: u32 = 256;
+
+/// A condition of a chain which can be waited for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+	/// The node is reachable.
+	Reachable,
+	/// The chain has progressed to at least the specified block number.
+	Block(u64),
+	/// The extrinsic with the specified hash has been included within a finalized block.
+	Finalized(H256),
+	/// The parachain with the specified identifier is producing blocks, as observed by the number
+	/// of its head on the relay chain increasing.
+	Producing(u32),
+}
+
+impl fmt::Display for Condition {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Condition::Reachable => write!(f, "node reachable"),
+			Condition::Block(number) => write!(f, "chain at block #{number}"),
+			Condition::Finalized(hash) => write!(f, "extrinsic {hash:?} finalized"),
+			Condition::Producing(id) => write!(f, "parachain #{id} producing blocks"),
+		}
+	}
+}
+
+/// The block at which a condition was observed to hold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObservedBlock {
+	/// The number of the block.
+	pub number: u64,
+	/// The hash of the block.
+	pub hash: H256,
+}
+
+/// Waits until the node at the specified endpoint is reachable, retrying at the specified
+/// interval.
+///
+/// # Arguments
+/// * `url` - Endpoint of the node.
+/// * `interval` - The interval between connection attempts.
+pub async fn wait_for_client(url: &str, interval: Duration) -> OnlineClient<SubstrateConfig> {
+	loop {
+		if let Ok(client) = set_up_client(url).await {
+			return client;
+		}
+		tokio::time::sleep(interval).await;
+	}
+}
+
+/// Waits until the specified condition holds, returning the block at which it was observed to
+/// hold where applicable.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain. When waiting for a parachain to produce
+///   blocks, this must be a client of the relay chain.
+/// * `condition` - The condition to wait for.
+pub async fn wait_for(
+	client: &OnlineClient<SubstrateConfig>,
+	condition: &Condition,
+) -> Result<Option<ObservedBlock>, Error> {
+	match condition {
+		Condition::Reachable => Ok(None),
+		Condition::Block(number) => {
+			let latest = client.blocks().at_latest().await?;
+			if u64::from(latest.number()) >= *number {
+				return Ok(Some(ObservedBlock {
+					number: latest.number().into(),
+					hash: latest.hash(),
+				}));
+			}
+			let mut blocks = client.blocks().subscribe_best().await?;
+			while let Some(block) = blocks.next().await {
+				let block = block?;
+				if u64::from(block.number()) >= *number {
+					return Ok(Some(ObservedBlock {
+						number: block.number().into(),
+						hash: block.hash(),
+					}));
+				}
+			}
+			Err(Error::SubscriptionEnded)
+		},
+		Condition::Finalized(hash) => {
+			// Subscribe before searching the recent blocks, so that no block finalized meanwhile
+			// is missed.
+			let mut blocks = client.blocks().subscribe_finalized().await?;
+			if let Some(observed) = find_finalized(client, hash).await? {
+				return Ok(Some(observed));
+			}
+			while let Some(block) = blocks.next().await {
+				let block = block?;
+				if includes(&block, hash).await? {
+					return Ok(Some(ObservedBlock {
+						number: block.number().into(),
+						hash: block.hash(),
+					}));
+				}
+			}
+			Err(Error::SubscriptionEnded)
+		},
+		Condition::Producing(id) => {
+			let address = dynamic::storage("Paras", "Heads", vec![Value::u128((*id).into())]);
+			let mut blocks = client.blocks().subscribe_best().await?;
+			// The number of the head of the parachain when first observed, so that a parachain is
+			// only considered to be producing blocks once a subsequent block has been observed.
+			let mut initial = None;
+			while let Some(block) = blocks.next().await {
+				let block = block?;
+				let head = block.storage().fetch(&address).await?;
+				let number = head.and_then(|h| head_number(&h.into_encoded()));
+				match (initial, number) {
+					(None, number) => initial = number,
+					(Some(initial), Some(number)) if number > initial => {
+						return Ok(Some(ObservedBlock {
+							number: block.number().into(),
+							hash: block.hash(),
+						}));
+					},
+					_ => {},
+				}
+			}
+			Err(Error::SubscriptionEnded)
+		},
+	}
+}
+
+// Searches the most recent finalized blocks for the extrinsic with the specified hash.
+async fn find_finalized(
+	client: &OnlineClient<SubstrateConfig>,
+	hash: &H256,
+) -> Result<Option<ObservedBlock>, Error> {
+	let mut block = client.blocks().at_latest().await?;
+	for _ in 0..FINALIZED_LOOKBACK {
+		if includes(&block, hash).await? {
+			return Ok(Some(ObservedBlock { number: block.number().into(), hash: block.hash() }));
+		}
+		if block.number() == 0 {
+			break;
+		}
+		block = client.blocks().at(block.header().parent_hash).await?;
+	}
+	Ok(None)
+}
+
+// The number of the block of a parachain, decoded from its encoded head data (a header, whose
+// number follows the hash of its parent).
+fn head_number(encoded: &[u8]) -> Option<u32> {
+	let head = Vec::<u8>::decode(&mut &encoded[..]).ok()?;
+	Compact::<u32>::decode(&mut head.get(32..)?).ok().map(|number| number.0)
+}
+
+// Whether a block includes the extrinsic with the specified hash.
+async fn includes(
+	block: &Block<SubstrateConfig, OnlineClient<SubstrateConfig>>,
+	hash: &H256,
+) -> Result<bool, Error> {
+	Ok(block.extrinsics().await?.iter().any(|extrinsic| extrinsic.hash() == *hash))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::call::tests::POP_NETWORK_TESTNET_URL;
+	use anyhow::Result;
+	use std::net::TcpListener;
+	use tokio::time::timeout;
+
+	#[test]
+	fn condition_display_works() {
+		assert_eq!(Condition::Reachable.to_string(), "node reachable");
+		assert_eq!(Condition::Block(10).to_string(), "chain at block #10");
+		assert_eq!(
+			Condition::Finalized(H256::repeat_byte(1)).to_string(),
+			format!("extrinsic 0x{} finalized", "01".repeat(32))
+		);
+		assert_eq!(Condition::Producing(2000).to_string(), "parachain #2000 producing blocks");
+	}
+
+	#[test]
+	fn head_number_works() {
+		use subxt::ext::codec::Encode;
+		let header = [&[1u8; 32][..], &Compact(1_234u32).encode(), &[2u8; 64]].concat();
+		assert_eq!(head_number(&header.encode()), Some(1_234));
+		assert_eq!(head_number(&[1u8; 16].to_vec().encode()), None);
+		assert_eq!(head_number(&[]), None);
+	}
+
+	#[tokio::test]
+	async fn wait_for_client_waits_while_unreachable() {
+		// Reserve a local port with nothing listening on it.
+		let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+		let url = format!("ws://127.0.0.1:{port}");
+		let result =
+			timeout(Duration::from_millis(500), wait_for_client(&url, Duration::from_millis(50)))
+				.await;
+		assert!(result.is_err(), "an unreachable node should not be waited for indefinitely");
+	}
+
+	#[tokio::test]
+	async fn wait_for_already_finalized_extrinsic() -> Result<()> {
+		let client = set_up_client(POP_NETWORK_TESTNET_URL).await?;
+		let block = client.blocks().at_latest().await?;
+		let extrinsic = block.extrinsics().await?.iter().next().expect("block has an inherent");
+		// The extrinsic is found without waiting for another block to be finalized.
+		let observed = timeout(
+			Duration::from_secs(60),
+			wait_for(&client, &Condition::Finalized(extrinsic.hash())),
+		)
+		.await??;
+		assert_eq!(
+			observed,
+			Some(ObservedBlock { number: block.number().into(), hash: block.hash() })
+		);
+		Ok(())
+	}
+}
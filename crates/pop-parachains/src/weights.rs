@@ -0,0 +1,561 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{build::build_runtime, errors::Error};
+use duct::cmd;
+use pop_common::{
+	rollback::Rollback,
+	sourcing::{traits::Source as _, Binary, Source},
+};
+use serde::Deserialize;
+use std::{
+	env::consts::{ARCH, OS},
+	fmt::{Display, Formatter},
+	fs,
+	path::{Path, PathBuf},
+	str::FromStr,
+};
+use strum::{EnumProperty, VariantArray};
+
+/// The number of picoseconds in a nanosecond, the unit in which benchmarks measure time.
+const PICOS_PER_NANO: u128 = 1_000;
+/// The number of heap pages first probed when sizing the heap automatically, double the default
+/// of `frame-omni-bencher`.
+const MIN_HEAP_PAGES: u64 = 4_096;
+/// The maximum number of heap pages probed when sizing the heap automatically, i.e. 2 GiB.
+const MAX_HEAP_PAGES: u64 = 32_768;
+/// The errors reported by the runtime allocator when the heap is exhausted.
+const ALLOCATOR_ERRORS: [&str; 2] = ["Allocator ran out of space", "Failed to allocate memory"];
+
+/// The `frame-omni-bencher` binary, used to benchmark the extrinsics of a runtime.
+#[derive(Debug, EnumProperty, PartialEq, VariantArray)]
+pub(crate) enum OmniBencher {
+	#[strum(props(
+		Repository = "https://github.com/paritytech/polkadot-sdk",
+		Binary = "frame-omni-bencher",
+		Fallback = "polkadot-stable2412"
+	))]
+	OmniBencher,
+}
+
+impl pop_common::sourcing::traits::Source for OmniBencher {}
+
+/// Generates the `frame-omni-bencher` binary, which is either downloaded from the latest release
+/// where a pre-built binary is available for the platform or otherwise built from source.
+///
+/// # Arguments
+/// * `cache` - The cache directory path.
+pub fn omni_bencher_generator(cache: PathBuf) -> Binary {
+	let bencher = OmniBencher::OmniBencher;
+	let name = bencher.binary().to_string();
+	let repository = bencher.repository();
+	let source = match (OS, ARCH) {
+		("linux", "x86_64") => Source::Url {
+			url: format!("{repository}/releases/latest/download/{name}"),
+			name: name.clone(),
+		},
+		_ => Source::Git {
+			url: repository.parse().expect("repository url is valid"),
+			reference: Some(bencher.fallback().to_string()),
+			manifest: None,
+			package: name.clone(),
+			artifacts: vec![name.clone()],
+		},
+	};
+	Binary::Source { name, source, cache }
+}
+
+/// Builds the runtime of a project with the `runtime-benchmarks` feature enabled, returning the
+/// path to the resulting WebAssembly blob.
+///
+/// # Arguments
+/// * `project` - The path to the project.
+/// * `runtime` - The path to the runtime crate.
+pub fn build_benchmark_runtime(project: &Path, runtime: &Path) -> Result<PathBuf, Error> {
+	build_runtime(project, runtime, Some("runtime-benchmarks"))
+}
+
+/// The maximum weights declared for extrinsics, e.g. within a `weights.toml` file:
+///
+/// ```toml
+/// [[extrinsic]]
+/// pallet = "pallet_balances"
+/// extrinsic = "transfer_allow_death"
+/// ref_time = 60_000_000
+/// proof_size = 3_593
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct WeightLimits {
+	/// The maximum weights of each extrinsic.
+	#[serde(default, rename = "extrinsic")]
+	pub extrinsics: Vec<WeightLimit>,
+}
+
+impl WeightLimits {
+	/// Loads the maximum weights declared within a configuration file.
+	///
+	/// # Arguments
+	/// * `path` - The path to the configuration file.
+	pub fn load(path: &Path) -> Result<Self, Error> {
+		let limits: Self = toml_edit::de::from_str(&fs::read_to_string(path)?)?;
+		if limits.extrinsics.is_empty() {
+			return Err(Error::Config(format!("no extrinsics are declared in {}", path.display())));
+		}
+		Ok(limits)
+	}
+}
+
+/// The maximum weight of an extrinsic.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct WeightLimit {
+	/// The name of the pallet, as known to the runtime benchmarks (e.g. `pallet_balances`).
+	pub pallet: String,
+	/// The name of the extrinsic.
+	pub extrinsic: String,
+	/// The maximum computation time, in picoseconds.
+	pub ref_time: Option<u64>,
+	/// The maximum size of the storage proof, in bytes.
+	pub proof_size: Option<u64>,
+}
+
+/// The number of heap pages with which the runtime is executed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HeapPages {
+	/// Probes increasing heap sizes until the benchmark no longer exhausts the heap.
+	Auto,
+	/// A fixed number of heap pages.
+	Fixed(u64),
+}
+
+impl FromStr for HeapPages {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"auto" => Ok(Self::Auto),
+			_ => s.parse().map(Self::Fixed).map_err(|_| Error::ParamProcessingError),
+		}
+	}
+}
+
+/// Options tuning how the runtime is executed when benchmarking.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExecutionOptions {
+	/// The strategy with which wasmtime instantiates the runtime, e.g. `recreate-instance`
+	/// [default: that of `frame-omni-bencher`].
+	pub wasm_instantiation_strategy: Option<String>,
+	/// The number of heap pages with which the runtime is executed [default: that of
+	/// `frame-omni-bencher`].
+	pub heap_pages: Option<HeapPages>,
+}
+
+impl WeightLimit {
+	/// The arguments to `frame-omni-bencher` for a quick benchmark of the extrinsic, whose
+	/// results are written to the specified file as JSON.
+	///
+	/// # Arguments
+	/// * `runtime` - The path to the runtime, built with the `runtime-benchmarks` feature.
+	/// * `output` - The file to which the results are written.
+	/// * `options` - Options tuning how the runtime is executed.
+	/// * `heap_pages` - The number of heap pages with which the runtime is executed, if any.
+	pub fn benchmark_args(
+		&self,
+		runtime: &Path,
+		output: &Path,
+		options: &ExecutionOptions,
+		heap_pages: Option<u64>,
+	) -> Vec<String> {
+		let mut args = vec![
+			"v1".into(),
+			"benchmark".into(),
+			"pallet".into(),
+			format!("--runtime={}", runtime.display()),
+			format!("--pallet={}", self.pallet),
+			format!("--extrinsic={}", self.extrinsic),
+			"--steps=2".into(),
+			"--repeat=1".into(),
+			format!("--json-file={}", output.display()),
+		];
+		if let Some(strategy) = &options.wasm_instantiation_strategy {
+			args.push(format!("--wasm-instantiation-strategy={strategy}"));
+		}
+		if let Some(heap_pages) = heap_pages {
+			args.push(format!("--heap-pages={heap_pages}"));
+		}
+		args
+	}
+
+	/// Asserts that the measured weight of the extrinsic is within its declared limits.
+	///
+	/// # Arguments
+	/// * `measured` - The weight measured by the benchmark.
+	pub fn check(&self, measured: &MeasuredWeight) -> WeightAssertion {
+		let exceeds = |limit: Option<u64>, value: u128| limit.is_some_and(|l| value > l as u128);
+		WeightAssertion {
+			limit: self.clone(),
+			measured: measured.clone(),
+			heap_pages: None,
+			passed: !exceeds(self.ref_time, measured.ref_time) &&
+				!exceeds(self.proof_size, measured.proof_size),
+		}
+	}
+}
+
+/// The worst-case weight of an extrinsic, as measured across the components of its benchmark.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeasuredWeight {
+	/// The computation time, in picoseconds.
+	pub ref_time: u128,
+	/// The size of the storage proof, in bytes.
+	pub proof_size: u128,
+}
+
+/// The outcome of asserting that an extrinsic is within its declared weight.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightAssertion {
+	/// The declared limit.
+	pub limit: WeightLimit,
+	/// The measured weight.
+	pub measured: MeasuredWeight,
+	/// The number of heap pages found to fit the benchmark, if sized automatically.
+	pub heap_pages: Option<u64>,
+	/// Whether the measured weight is within the limit.
+	pub passed: bool,
+}
+
+impl Display for WeightAssertion {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let limit = |l: Option<u64>| l.map_or("-".to_string(), |l| l.to_string());
+		write!(
+			f,
+			"{}::{}: ref_time {} (max {}), proof_size {} (max {})",
+			self.limit.pallet,
+			self.limit.extrinsic,
+			self.measured.ref_time,
+			limit(self.limit.ref_time),
+			self.measured.proof_size,
+			limit(self.limit.proof_size)
+		)
+	}
+}
+
+// The results of a benchmark, as written by `frame-omni-bencher` via `--json-file`.
+#[derive(Deserialize)]
+struct BenchmarkResults {
+	benchmark: String,
+	time_results: Vec<BenchmarkResult>,
+}
+
+#[derive(Deserialize)]
+struct BenchmarkResult {
+	extrinsic_time: u128,
+	proof_size: u128,
+}
+
+/// Parses the worst-case weight of an extrinsic from the JSON results of its benchmark.
+///
+/// # Arguments
+/// * `results` - The results, as written by `frame-omni-bencher`.
+/// * `extrinsic` - The name of the extrinsic.
+pub fn parse_benchmark_results(results: &str, extrinsic: &str) -> Result<MeasuredWeight, Error> {
+	let results: Vec<BenchmarkResults> = serde_json::from_str(results)?;
+	let results: Vec<_> = results
+		.iter()
+		.filter(|r| r.benchmark == extrinsic)
+		.flat_map(|r| &r.time_results)
+		.collect();
+	if results.is_empty() {
+		return Err(Error::Config(format!("no benchmark results found for {extrinsic}")));
+	}
+	Ok(MeasuredWeight {
+		ref_time: results
+			.iter()
+			.map(|r| r.extrinsic_time * PICOS_PER_NANO)
+			.max()
+			.unwrap_or_default(),
+		proof_size: results.iter().map(|r| r.proof_size).max().unwrap_or_default(),
+	})
+}
+
+/// Benchmarks an extrinsic via `frame-omni-bencher`, asserting that its measured weight is within
+/// its declared limit.
+///
+/// When the heap is sized automatically, the benchmark is repeated with twice as many heap pages
+/// whenever it exhausts the heap, until it fits or the maximum is reached.
+///
+/// # Arguments
+/// * `binary` - The path to the `frame-omni-bencher` binary.
+/// * `runtime` - The path to the runtime, built with the `runtime-benchmarks` feature.
+/// * `limit` - The declared limit of the extrinsic.
+/// * `options` - Options tuning how the runtime is executed.
+pub fn assert_weight(
+	binary: &Path,
+	runtime: &Path,
+	limit: &WeightLimit,
+	options: &ExecutionOptions,
+) -> Result<WeightAssertion, Error> {
+	let mut heap_pages = match options.heap_pages {
+		Some(HeapPages::Fixed(pages)) => Some(pages),
+		Some(HeapPages::Auto) | None => None,
+	};
+	loop {
+		match benchmark(binary, runtime, limit, options, heap_pages) {
+			Err(Error::HeapExhausted(error)) if options.heap_pages == Some(HeapPages::Auto) => {
+				let next = heap_pages.map_or(MIN_HEAP_PAGES, |p| p * 2);
+				if next > MAX_HEAP_PAGES {
+					return Err(Error::HeapExhausted(format!(
+						"the benchmark does not fit within {MAX_HEAP_PAGES} heap pages: {error}"
+					)));
+				}
+				heap_pages = Some(next);
+			},
+			Ok(mut assertion) => {
+				if options.heap_pages == Some(HeapPages::Auto) {
+					assertion.heap_pages = heap_pages;
+				}
+				return Ok(assertion);
+			},
+			Err(e) => return Err(e),
+		}
+	}
+}
+
+// Runs a single benchmark of an extrinsic, with the specified number of heap pages.
+fn benchmark(
+	binary: &Path,
+	runtime: &Path,
+	limit: &WeightLimit,
+	options: &ExecutionOptions,
+	heap_pages: Option<u64>,
+) -> Result<WeightAssertion, Error> {
+	let output = tempfile::NamedTempFile::new()?;
+	let handle = cmd(binary, limit.benchmark_args(runtime, output.path(), options, heap_pages))
+		.stdout_null()
+		.stderr_capture()
+		.unchecked()
+		.start()?;
+	// The benchmark is terminated if cancelled.
+	let _rollback: Vec<_> = handle.pids().into_iter().map(Rollback::terminate).collect();
+	let result = handle.wait()?;
+	if !result.status.success() {
+		return Err(benchmark_error(&String::from_utf8_lossy(&result.stderr)));
+	}
+	let measured = parse_benchmark_results(&fs::read_to_string(output.path())?, &limit.extrinsic)?;
+	Ok(limit.check(&measured))
+}
+
+// The error of a failed benchmark, based on its output.
+fn benchmark_error(stderr: &str) -> Error {
+	let error = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or_default().trim();
+	if ALLOCATOR_ERRORS.iter().any(|e| stderr.contains(e)) {
+		return Error::HeapExhausted(error.to_string());
+	}
+	Error::BenchmarkError(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::tempdir;
+
+	const RESULTS: &str = r#"[
+  {
+    "pallet": "pallet_balances",
+    "instance": "Balances",
+    "benchmark": "transfer_allow_death",
+    "time_results": [
+      { "components": [], "extrinsic_time": 45000, "storage_root_time": 1000, "reads": 1, "repeat_reads": 0, "writes": 1, "repeat_writes": 0, "proof_size": 3593 },
+      { "components": [], "extrinsic_time": 47000, "storage_root_time": 1000, "reads": 1, "repeat_reads": 0, "writes": 1, "repeat_writes": 0, "proof_size": 3500 }
+    ],
+    "db_results": []
+  }
+]"#;
+
+	fn limit() -> WeightLimit {
+		WeightLimit {
+			pallet: "pallet_balances".into(),
+			extrinsic: "transfer_allow_death".into(),
+			ref_time: Some(50_000_000),
+			proof_size: Some(3_593),
+		}
+	}
+
+	#[test]
+	fn omni_bencher_generator_works() {
+		let cache = PathBuf::from("./cache");
+		let binary = omni_bencher_generator(cache.clone());
+		assert_eq!(binary.name(), "frame-omni-bencher");
+		assert_eq!(binary.path(), cache.join("frame-omni-bencher"));
+		let Binary::Source { source, .. } = binary else { panic!("expected a binary source") };
+		match (OS, ARCH) {
+			("linux", "x86_64") => assert_eq!(
+				source,
+				Source::Url {
+					url: "https://github.com/paritytech/polkadot-sdk/releases/latest/download/frame-omni-bencher".into(),
+					name: "frame-omni-bencher".into()
+				}
+			),
+			_ => assert!(
+				matches!(source, Source::Git { package, .. } if package == "frame-omni-bencher")
+			),
+		}
+	}
+
+	#[test]
+	fn load_works() -> Result<(), Error> {
+		let temp_dir = tempdir()?;
+		let path = temp_dir.path().join("weights.toml");
+		fs::write(
+			&path,
+			r#"[[extrinsic]]
+pallet = "pallet_balances"
+extrinsic = "transfer_allow_death"
+ref_time = 50_000_000
+proof_size = 3_593
+
+[[extrinsic]]
+pallet = "pallet_balances"
+extrinsic = "force_transfer"
+"#,
+		)?;
+		let limits = WeightLimits::load(&path)?;
+		assert_eq!(
+			limits.extrinsics,
+			vec![
+				limit(),
+				WeightLimit {
+					pallet: "pallet_balances".into(),
+					extrinsic: "force_transfer".into(),
+					..Default::default()
+				}
+			]
+		);
+		fs::write(&path, "")?;
+		assert!(matches!(WeightLimits::load(&path), Err(Error::Config(..))));
+		Ok(())
+	}
+
+	#[test]
+	fn benchmark_args_works() {
+		assert_eq!(
+			limit().benchmark_args(
+				Path::new("runtime.wasm"),
+				Path::new("results.json"),
+				&ExecutionOptions::default(),
+				None
+			),
+			vec![
+				"v1",
+				"benchmark",
+				"pallet",
+				"--runtime=runtime.wasm",
+				"--pallet=pallet_balances",
+				"--extrinsic=transfer_allow_death",
+				"--steps=2",
+				"--repeat=1",
+				"--json-file=results.json"
+			]
+		);
+		let options = ExecutionOptions {
+			wasm_instantiation_strategy: Some("recreate-instance".into()),
+			heap_pages: Some(HeapPages::Auto),
+		};
+		let args = limit().benchmark_args(
+			Path::new("runtime.wasm"),
+			Path::new("results.json"),
+			&options,
+			Some(4_096),
+		);
+		assert_eq!(
+			&args[args.len() - 2..],
+			["--wasm-instantiation-strategy=recreate-instance", "--heap-pages=4096"]
+		);
+	}
+
+	#[test]
+	fn heap_pages_from_str_works() {
+		assert_eq!("auto".parse::<HeapPages>().unwrap(), HeapPages::Auto);
+		assert_eq!("8192".parse::<HeapPages>().unwrap(), HeapPages::Fixed(8_192));
+		assert!(matches!("many".parse::<HeapPages>(), Err(Error::ParamProcessingError)));
+	}
+
+	#[test]
+	fn assert_weight_probes_heap_pages() -> Result<(), Error> {
+		use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+		let temp_dir = tempdir()?;
+		fs::write(temp_dir.path().join("results.json"), RESULTS)?;
+		// The benchmark exhausts the heap unless executed with 8192 heap pages.
+		let binary = temp_dir.path().join("frame-omni-bencher");
+		fs::write(
+			&binary,
+			r#"#!/bin/sh
+for arg in "$@"; do
+  case "$arg" in
+    --json-file=*) output="${arg#--json-file=}" ;;
+    --heap-pages=8192) fits=1 ;;
+  esac
+done
+if [ -z "$fits" ]; then
+  echo 'Error: Failed to allocate memory: "Allocator ran out of space"' >&2
+  exit 1
+fi
+cat "$(dirname "$0")/results.json" > "$output"
+"#,
+		)?;
+		fs::set_permissions(&binary, Permissions::from_mode(0o755))?;
+		let runtime = Path::new("runtime.wasm");
+		let options = ExecutionOptions { heap_pages: Some(HeapPages::Auto), ..Default::default() };
+		let assertion = assert_weight(&binary, runtime, &limit(), &options)?;
+		assert!(assertion.passed);
+		assert_eq!(assertion.heap_pages, Some(8_192));
+		let options = ExecutionOptions { heap_pages: Some(HeapPages::Fixed(4_096)), ..options };
+		assert!(matches!(
+			assert_weight(&binary, runtime, &limit(), &options),
+			Err(Error::HeapExhausted(..))
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn benchmark_error_works() {
+		let stderr = "Starting benchmark: pallet_balances::transfer_allow_death\n\
+			Error: Input(\"Failed to allocate memory: Allocator ran out of space\")\n";
+		assert!(matches!(
+			benchmark_error(stderr),
+			Error::HeapExhausted(error) if error.starts_with("Error: Input")
+		));
+		assert!(matches!(
+			benchmark_error("Error: Input(\"pallet not found\")\n\n"),
+			Error::BenchmarkError(error) if error == "Error: Input(\"pallet not found\")"
+		));
+	}
+
+	#[test]
+	fn parse_benchmark_results_works() -> Result<(), Error> {
+		assert_eq!(
+			parse_benchmark_results(RESULTS, "transfer_allow_death")?,
+			MeasuredWeight { ref_time: 47_000_000, proof_size: 3_593 }
+		);
+		assert!(matches!(
+			parse_benchmark_results(RESULTS, "force_transfer"),
+			Err(Error::Config(..))
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn check_works() {
+		let limit = limit();
+		let assertion = limit.check(&MeasuredWeight { ref_time: 47_000_000, proof_size: 3_593 });
+		assert!(assertion.passed);
+		assert_eq!(
+			assertion.to_string(),
+			"pallet_balances::transfer_allow_death: ref_time 47000000 (max 50000000), proof_size 3593 (max 3593)"
+		);
+		assert!(!limit.check(&MeasuredWeight { ref_time: 50_000_001, proof_size: 0 }).passed);
+		assert!(!limit.check(&MeasuredWeight { ref_time: 0, proof_size: 3_594 }).passed);
+		let unbounded = WeightLimit { ref_time: None, proof_size: None, ..limit };
+		let assertion = unbounded.check(&MeasuredWeight { ref_time: u128::MAX, proof_size: 1 });
+		assert!(assertion.passed);
+		assert!(assertion.to_string().ends_with("(max -), proof_size 1 (max -)"));
+	}
+}
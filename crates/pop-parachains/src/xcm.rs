@@ -0,0 +1,1292 @@
+// SPDX-License-Identifier: GPL-3.0
+
+use crate::{
+	errors::Error,
+	registry::{Bridge, Chain},
+	Connections, DynamicPayload,
+};
+use scale_value::{Composite, ValueDef};
+use sp_core::bytes::from_hex;
+use std::collections::VecDeque;
+use strum::VariantArray as _;
+use strum_macros::{AsRefStr, EnumMessage, EnumString, VariantArray};
+use subxt::{
+	dynamic::{self, Value},
+	tx::Payload as _,
+	utils::AccountId32,
+	OnlineClient, SubstrateConfig,
+};
+
+/// The version of XCM used for constructing locations and assets.
+const XCM_VERSION: u128 = 4;
+/// The maximum number of hops followed when estimating the fees of a cross-chain call.
+const MAX_HOPS: usize = 5;
+
+/// The mechanism by which assets are transferred between chains.
+#[derive(AsRefStr, Clone, Copy, Debug, EnumMessage, EnumString, Eq, PartialEq, VariantArray)]
+pub enum TransferType {
+	/// The assets are burnt on the source and minted on the destination.
+	#[strum(
+		serialize = "teleport",
+		message = "Teleport",
+		detailed_message = "Burn the assets on the source and mint them on the destination, as trusted between system chains and their relay chain."
+	)]
+	Teleport,
+	/// The assets are moved via their reserve, with derivatives minted on the destination.
+	#[strum(
+		serialize = "reserve",
+		message = "Reserve transfer",
+		detailed_message = "Move the assets via their reserve, with derivatives minted on the destination."
+	)]
+	Reserve,
+}
+
+impl TransferType {
+	/// The dispatchable function of the XCM pallet used for the transfer.
+	pub fn function(&self) -> &'static str {
+		match self {
+			Self::Teleport => "limited_teleport_assets",
+			Self::Reserve => "transfer_assets",
+		}
+	}
+
+	/// Checks that the transfer type is supported between two chains.
+	///
+	/// # Arguments
+	/// * `source` - The chain from which the assets are sent.
+	/// * `destination` - The chain to which the assets are sent.
+	pub fn check(&self, source: &Chain, destination: &Chain) -> Result<(), Error> {
+		if !Self::supported(source, destination).contains(self) {
+			return Err(Error::UnsupportedTransfer(format!(
+				"a {} is not supported from {} to {}",
+				self.as_ref(),
+				source.name(),
+				destination.name()
+			)));
+		}
+		Ok(())
+	}
+
+	/// The transfer types supported between two chains.
+	///
+	/// # Arguments
+	/// * `source` - The chain from which the assets are sent.
+	/// * `destination` - The chain to which the assets are sent.
+	pub fn supported(source: &Chain, destination: &Chain) -> Vec<TransferType> {
+		if source == destination {
+			return vec![];
+		}
+		// Beyond its consensus, a chain can only transfer assets via a bridge, via their reserve.
+		if source.consensus() != destination.consensus() {
+			return match source.bridge(destination) {
+				Some(_) => vec![Self::Reserve],
+				None => vec![],
+			};
+		}
+		// Teleports are only trusted between system chains and their relay chain.
+		let trusted = |chain: &Chain| chain.relay().is_none() || chain.is_system();
+		if trusted(source) && trusted(destination) {
+			vec![Self::Teleport, Self::Reserve]
+		} else {
+			vec![Self::Reserve]
+		}
+	}
+}
+
+/// An asset to be transferred.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+	/// The native token of the relay chain.
+	Relay,
+	/// The native token of the source chain.
+	Native,
+	/// An asset registered on the source chain, by its (unversioned) location relative to the
+	/// source.
+	Registered(Value),
+}
+
+impl std::str::FromStr for Asset {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"relay" => Ok(Self::Relay),
+			"native" => Ok(Self::Native),
+			_ => Err(Error::ParamProcessingError),
+		}
+	}
+}
+
+/// An account receiving transferred assets.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Beneficiary {
+	/// A 32-byte account, as used by Polkadot SDK chains.
+	AccountId32(AccountId32),
+	/// A 20-byte account, as used by Ethereum.
+	AccountKey20([u8; 20]),
+}
+
+impl std::str::FromStr for Beneficiary {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Ok(account) = AccountId32::from_str(s) {
+			return Ok(Self::AccountId32(account));
+		}
+		from_hex(s)
+			.ok()
+			.and_then(|key| <[u8; 20]>::try_from(key).ok())
+			.map(Self::AccountKey20)
+			.ok_or(Error::ParamProcessingError)
+	}
+}
+
+/// A transfer of assets from one chain to another via XCM.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transfer {
+	/// The chain from which the assets are sent.
+	pub source: Chain,
+	/// The chain to which the assets are sent.
+	pub destination: Chain,
+	/// The mechanism by which the assets are transferred.
+	pub transfer_type: TransferType,
+	/// The asset to be transferred.
+	pub asset: Asset,
+	/// The amount to be transferred, in the smallest unit of the asset.
+	pub amount: u128,
+	/// The account receiving the assets on the destination.
+	pub beneficiary: Beneficiary,
+}
+
+impl Transfer {
+	/// Checks that the transfer is supported between its chains.
+	pub fn validate(&self) -> Result<(), Error> {
+		self.transfer_type.check(&self.source, &self.destination)?;
+		if self.amount == 0 {
+			return Err(Error::UnsupportedTransfer("the amount must be greater than zero".into()));
+		}
+		// Ethereum networks only have 20-byte accounts.
+		if self.destination.is_ethereum() !=
+			matches!(self.beneficiary, Beneficiary::AccountKey20(_))
+		{
+			return Err(Error::UnsupportedTransfer(format!(
+				"the beneficiary is not a valid account on {}",
+				self.destination.name()
+			)));
+		}
+		Ok(())
+	}
+
+	/// The bridge via which the assets are transferred, if the destination is beyond the
+	/// consensus of the source.
+	pub fn bridge(&self) -> Option<Bridge> {
+		self.source.bridge(&self.destination)
+	}
+
+	/// The XCM pallet of the source chain.
+	pub fn pallet(&self) -> &'static str {
+		pallet(&self.source)
+	}
+
+	/// The location of the destination, relative to the source.
+	pub fn destination_location(&self) -> Value {
+		relative_location(&self.source, &self.destination)
+	}
+
+	/// The location of the beneficiary, relative to the destination.
+	pub fn beneficiary_location(&self) -> Value {
+		let account = match &self.beneficiary {
+			Beneficiary::AccountId32(account) => account_id32(account),
+			Beneficiary::AccountKey20(key) => Value::named_variant(
+				"AccountKey20",
+				[("network", Value::unnamed_variant("None", [])), ("key", Value::from_bytes(key))],
+			),
+		};
+		versioned(location(0, x1(account)))
+	}
+
+	/// The assets to be transferred, relative to the source.
+	pub fn assets(&self) -> Value {
+		let location = match &self.asset {
+			Asset::Relay => location(u8::from(self.source.relay().is_some()), here()),
+			Asset::Native => location(0, here()),
+			Asset::Registered(location) => location.clone(),
+		};
+		let asset = Value::named_composite([
+			("id", Value::unnamed_composite([location])),
+			("fun", Value::unnamed_variant("Fungible", [Value::u128(self.amount)])),
+		]);
+		versioned(Value::unnamed_composite([asset]))
+	}
+
+	/// The fees charged along the route after the source, if the transferred assets are the relay
+	/// chain token from which they are paid and their amount does not cover them.
+	///
+	/// # Arguments
+	/// * `hops` - The fees charged by each chain along the route, starting with the source.
+	pub fn uncovered_fees(&self, hops: &[HopFees]) -> Option<u128> {
+		if self.asset != Asset::Relay {
+			return None;
+		}
+		let symbol = self.source.consensus().symbol();
+		let fees: u128 = hops
+			.iter()
+			.skip(1)
+			.flat_map(|hop| {
+				hop.execution
+					.iter()
+					.chain(&hop.delivery)
+					.filter(|fee| fee.symbol(&hop.chain) == Some(symbol))
+					.map(|fee| fee.amount)
+			})
+			.sum();
+		(fees >= self.amount).then_some(fees)
+	}
+
+	/// Constructs the extrinsic for the transfer, with the fees paid from the transferred asset.
+	pub fn construct(&self) -> DynamicPayload {
+		dynamic::tx(
+			self.pallet(),
+			self.transfer_type.function(),
+			vec![
+				self.destination_location(),
+				self.beneficiary_location(),
+				self.assets(),
+				Value::u128(0),
+				Value::unnamed_variant("Unlimited", []),
+			],
+		)
+	}
+}
+
+/// A call executed on another chain via XCM, e.g. a call of a smart contract deployed there.
+///
+/// The call is dispatched on the destination from the account derived from the origin on the
+/// source, which pays for its execution in the relay chain token.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RemoteCall {
+	/// The chain from which the message is sent.
+	pub source: Chain,
+	/// The chain on which the call is executed.
+	pub destination: Chain,
+	/// The account on the source sending the message.
+	pub origin: AccountId32,
+	/// The call to be executed, encoded for the runtime of the destination.
+	pub call: Vec<u8>,
+	/// The maximum weight of the call, as its reference time and proof size.
+	pub weight: (u64, u64),
+	/// The amount of the relay chain token withdrawn on the destination to pay for execution.
+	pub fee: u128,
+}
+
+impl RemoteCall {
+	/// The XCM pallet of the source chain.
+	pub fn pallet(&self) -> &'static str {
+		pallet(&self.source)
+	}
+
+	/// The location of the destination, relative to the source.
+	pub fn destination_location(&self) -> Value {
+		relative_location(&self.source, &self.destination)
+	}
+
+	/// The location of the origin, relative to the destination, from which the account
+	/// dispatching the call is derived.
+	pub fn origin_location(&self) -> Value {
+		let parents = u8::from(self.destination.relay().is_some());
+		let account = account_id32(&self.origin);
+		let interior = match self.source.para_id() {
+			Some(id) => x2(Value::unnamed_variant("Parachain", [Value::u128(id.into())]), account),
+			None => x1(account),
+		};
+		location(parents, interior)
+	}
+
+	/// The location of the relay chain token paying for execution, relative to the destination.
+	pub fn fee_location(&self) -> Value {
+		location(u8::from(self.destination.relay().is_some()), here())
+	}
+
+	/// The asset paying for execution, relative to the destination.
+	pub fn fee_asset(&self) -> Value {
+		Value::named_composite([
+			("id", Value::unnamed_composite([self.fee_location()])),
+			("fun", Value::unnamed_variant("Fungible", [Value::u128(self.fee)])),
+		])
+	}
+
+	/// The message executed on the destination: the fee is withdrawn to buy execution of the
+	/// call, with any surplus deposited back to the account of the origin.
+	pub fn message(&self) -> Value {
+		let (ref_time, proof_size) = self.weight;
+		let fee = self.fee_asset();
+		versioned(Value::unnamed_composite([
+			Value::unnamed_variant("WithdrawAsset", [Value::unnamed_composite([fee.clone()])]),
+			Value::named_variant(
+				"BuyExecution",
+				[("fees", fee), ("weight_limit", Value::unnamed_variant("Unlimited", []))],
+			),
+			Value::named_variant(
+				"Transact",
+				[
+					("origin_kind", Value::unnamed_variant("SovereignAccount", [])),
+					(
+						"require_weight_at_most",
+						Value::named_composite([
+							("ref_time", Value::u128(ref_time.into())),
+							("proof_size", Value::u128(proof_size.into())),
+						]),
+					),
+					("call", Value::named_composite([("encoded", Value::from_bytes(&self.call))])),
+				],
+			),
+			Value::unnamed_variant("RefundSurplus", []),
+			Value::named_variant(
+				"DepositAsset",
+				[
+					(
+						"assets",
+						Value::unnamed_variant(
+							"Wild",
+							[Value::named_variant("AllCounted", [("count", Value::u128(1))])],
+						),
+					),
+					("beneficiary", self.origin_location()),
+				],
+			),
+		]))
+	}
+
+	/// Constructs the extrinsic sending the message to the destination.
+	pub fn construct(&self) -> DynamicPayload {
+		dynamic::tx(self.pallet(), "send", vec![self.destination_location(), self.message()])
+	}
+}
+
+/// Messages forwarded to another chain as the result of executing a call or message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForwardedXcm {
+	/// The destination of the messages, relative to the chain which forwarded them.
+	pub destination: Value,
+	/// The messages forwarded to the destination.
+	pub messages: Vec<Value>,
+}
+
+/// The effects of dry-running a call or message.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DryRunEffects {
+	/// The reason the execution failed, if any.
+	pub error: Option<String>,
+	/// The messages which would be forwarded to other chains.
+	pub forwarded: Vec<ForwardedXcm>,
+}
+
+impl DryRunEffects {
+	/// Whether the execution was successful.
+	pub fn success(&self) -> bool {
+		self.error.is_none()
+	}
+
+	// Parses the effects from the decoded result of a `DryRunApi` method.
+	fn from_value(value: &Value<u32>) -> Result<Self, Error> {
+		let parse_error = || Error::MetadataParsingError("dry run result".into());
+		let effects = match variant(value).ok_or_else(parse_error)? {
+			("Ok", fields) => fields.values().next().ok_or_else(parse_error)?,
+			(_, fields) =>
+				return Err(Error::ExtrinsicSubmissionError(format!(
+					"the call could not be dry-run: {}",
+					display(fields)
+				))),
+		};
+		let ValueDef::Composite(effects) = &effects.value else { return Err(parse_error()) };
+		let result = field(effects, "execution_result").ok_or_else(parse_error)?;
+		let (outcome, fields) = variant(result).ok_or_else(parse_error)?;
+		// A call results in a dispatch result, a message in an outcome.
+		let error = match outcome {
+			"Ok" | "Complete" => None,
+			_ => {
+				let error = field(fields, "error").or_else(|| {
+					fields.values().next().and_then(|e| match &e.value {
+						ValueDef::Composite(c) => field(c, "error"),
+						_ => None,
+					})
+				});
+				Some(error.map(|e| e.to_string()).unwrap_or_else(|| display(fields)))
+			},
+		};
+		let forwarded = match field(effects, "forwarded_xcms").map(|f| &f.value) {
+			Some(ValueDef::Composite(forwarded)) => forwarded
+				.values()
+				.filter_map(|entry| {
+					let ValueDef::Composite(entry) = &entry.value else { return None };
+					let mut values = entry.values();
+					let destination = values.next()?.clone().remove_context();
+					let ValueDef::Composite(messages) = &values.next()?.value else {
+						return None;
+					};
+					let messages = messages.values().map(|m| m.clone().remove_context()).collect();
+					Some(ForwardedXcm { destination, messages })
+				})
+				.collect(),
+			_ => vec![],
+		};
+		Ok(Self { error, forwarded })
+	}
+}
+
+/// A fee, charged in an asset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fee {
+	/// The (unversioned) location of the asset, relative to the chain charging the fee.
+	pub asset: Value,
+	/// The amount charged, in the smallest unit of the asset.
+	pub amount: u128,
+}
+
+impl Fee {
+	/// The symbol of the asset, if it is the native token of the chain charging the fee or its
+	/// relay chain.
+	///
+	/// # Arguments
+	/// * `chain` - The chain charging the fee.
+	pub fn symbol(&self, chain: &Chain) -> Option<&'static str> {
+		let ValueDef::Composite(location) = &self.asset.value else { return None };
+		if !matches!(variant(field(location, "interior")?), Some(("Here", _))) {
+			return None;
+		}
+		match (field(location, "parents")?.as_u128()?, chain.relay()) {
+			(0, _) => Some(chain.symbol()),
+			(1, Some(relay)) => Some(relay.symbol()),
+			_ => None,
+		}
+	}
+}
+
+/// The fees charged by a chain along the route of a cross-chain call or message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HopFees {
+	/// The chain charging the fees.
+	pub chain: Chain,
+	/// The fee for executing the call or message, if execution is paid for.
+	pub execution: Option<Fee>,
+	/// The fees for delivering any messages forwarded to the next hops.
+	pub delivery: Vec<Fee>,
+}
+
+/// Dry-runs a call via the `DryRunApi` of a chain, without submitting it.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `xt` - The call to be dry-run.
+/// * `origin` - The account from which the call is dispatched.
+pub async fn dry_run_call(
+	client: &OnlineClient<SubstrateConfig>,
+	xt: DynamicPayload,
+	origin: &AccountId32,
+) -> Result<DryRunEffects, Error> {
+	let inputs = dry_run_api(client, "dry_run_call")?;
+	let origin = Value::unnamed_variant(
+		"system",
+		[Value::unnamed_variant("Signed", [Value::from_bytes(origin.0)])],
+	);
+	let mut args = vec![origin, xt.into_value()];
+	// Later versions of the API also take the XCM version of the resulting messages.
+	if inputs > 2 {
+		args.push(Value::u128(XCM_VERSION));
+	}
+	dry_run(client, "dry_run_call", args).await
+}
+
+/// Dry-runs a message via the `DryRunApi` of a chain, as if it were received from the specified
+/// origin.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `origin` - The location from which the message is received, relative to the chain.
+/// * `message` - The versioned message to be dry-run.
+pub async fn dry_run_xcm(
+	client: &OnlineClient<SubstrateConfig>,
+	origin: Value,
+	message: Value,
+) -> Result<DryRunEffects, Error> {
+	dry_run_api(client, "dry_run_xcm")?;
+	dry_run(client, "dry_run_xcm", vec![origin, message]).await
+}
+
+/// The location of a chain, relative to another chain.
+///
+/// # Arguments
+/// * `from` - The chain to which the location is relative.
+/// * `to` - The chain being located.
+pub fn relative_location(from: &Chain, to: &Chain) -> Value {
+	let parents = u8::from(from.relay().is_some());
+	let parachain = to
+		.para_id()
+		.map(|id| Value::unnamed_variant("Parachain", [Value::u128(id.into())]));
+	// A chain beyond the consensus of the other is located via its global consensus.
+	if from.consensus() != to.consensus() {
+		let consensus = global_consensus(to);
+		let interior = match parachain {
+			Some(parachain) => x2(consensus, parachain),
+			None => x1(consensus),
+		};
+		return versioned(location(parents + 1, interior));
+	}
+	let interior = match parachain {
+		Some(parachain) => x1(parachain),
+		None => here(),
+	};
+	versioned(location(parents, interior))
+}
+
+/// Resolves a location, relative to a chain, into a known chain with which it shares consensus.
+///
+/// # Arguments
+/// * `from` - The chain to which the location is relative.
+/// * `location` - The versioned location.
+pub fn resolve_location(from: &Chain, location: &Value) -> Option<Chain> {
+	let (_, versioned) = variant(location)?;
+	let ValueDef::Composite(location) = &versioned.values().next()?.value else { return None };
+	if field(location, "parents")?.as_u128()? != u128::from(from.relay().is_some()) {
+		return None;
+	}
+	let para_id = match variant(field(location, "interior")?)? {
+		("Here", _) => None,
+		("X1", junctions) => {
+			// Junctions are wrapped in an array from version 4.
+			let junction = junctions.values().next()?;
+			let junction = match &junction.value {
+				ValueDef::Composite(c) => c.values().next()?,
+				_ => junction,
+			};
+			match variant(junction)? {
+				("Parachain", id) => Some(u32::try_from(id.values().next()?.as_u128()?).ok()?),
+				_ => return None,
+			}
+		},
+		_ => return None,
+	};
+	let consensus = from.consensus();
+	match para_id {
+		// The relay chain, when located from one of its parachains.
+		None => from.relay().is_some().then_some(consensus),
+		Some(id) => Chain::VARIANTS
+			.iter()
+			.find(|c| c.consensus() == consensus && c.para_id() == Some(id))
+			.copied(),
+	}
+}
+
+/// The names of the instructions of a versioned message.
+///
+/// # Arguments
+/// * `message` - The versioned message.
+pub fn instructions(message: &Value) -> Vec<String> {
+	let Some((_, versioned)) = variant(message) else { return vec![] };
+	let Some(xcm) = versioned.values().next() else { return vec![] };
+	// A message is a sequence of instructions, which may be wrapped in a newtype.
+	let instructions = match &xcm.value {
+		ValueDef::Composite(c)
+			if c.len() == 1 && c.values().all(|v| matches!(v.value, ValueDef::Composite(_))) =>
+			c.values().next().expect("length checked above"),
+		_ => xcm,
+	};
+	let ValueDef::Composite(instructions) = &instructions.value else { return vec![] };
+	instructions
+		.values()
+		.filter_map(|i| variant(i).map(|(name, _)| name.to_string()))
+		.collect()
+}
+
+/// Estimates the fee for executing a message on a chain via its `XcmPaymentApi`, in the
+/// specified asset.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the chain.
+/// * `message` - The versioned message to be executed.
+/// * `asset` - The (unversioned) location of the asset in which the fee is paid, relative to the
+///   chain.
+pub async fn execution_fee(
+	client: &OnlineClient<SubstrateConfig>,
+	message: Value,
+	asset: Value,
+) -> Result<u128, Error> {
+	let weight = payment_api(client, "query_xcm_weight", vec![message]).await?;
+	let asset = versioned(Value::unnamed_composite([asset]));
+	payment_api(client, "query_weight_to_asset_fee", vec![weight.remove_context(), asset])
+		.await?
+		.as_u128()
+		.ok_or_else(|| Error::MetadataParsingError("fee".into()))
+}
+
+/// Estimates the fees charged by each chain along the route of a cross-chain call, by
+/// dry-running the call on the source and any forwarded messages on each subsequent hop.
+///
+/// # Arguments
+/// * `client` - The client used to interact with the source.
+/// * `source` - The chain from which the call is dispatched.
+/// * `xt` - The call.
+/// * `origin` - The account from which the call is dispatched.
+pub async fn estimate_fees(
+	client: &OnlineClient<SubstrateConfig>,
+	source: Chain,
+	xt: DynamicPayload,
+	origin: &AccountId32,
+) -> Result<Vec<HopFees>, Error> {
+	// The call itself is paid for by the origin, via a transaction fee.
+	let len = xt
+		.encode_call_data(&client.metadata())
+		.map_err(|e| Error::CallDataEncodingError(e.to_string()))?
+		.len();
+	let payload = dynamic::runtime_api_call(
+		"TransactionPaymentCallApi",
+		"query_call_info",
+		vec![xt.clone().into_value(), Value::u128(len as u128)],
+	);
+	let info = client
+		.runtime_api()
+		.at_latest()
+		.await?
+		.call(payload)
+		.await?
+		.to_value()
+		.map_err(|e| Error::MetadataParsingError(format!("call info: {e}")))?;
+	let amount = match &info.value {
+		ValueDef::Composite(info) => field(info, "partial_fee").and_then(|f| f.as_u128()),
+		_ => None,
+	}
+	.ok_or_else(|| Error::MetadataParsingError("call info".into()))?;
+	let effects = dry_run_call(client, xt, origin).await?;
+	if let Some(error) = effects.error {
+		return Err(Error::FeeEstimationError(error));
+	}
+	let mut hops = vec![HopFees {
+		chain: source,
+		execution: Some(Fee { asset: location(0, here()), amount }),
+		delivery: delivery_fees(client, &effects.forwarded).await?,
+	}];
+
+	// Follow the forwarded messages, hop by hop, connecting to each chain once.
+	let connections = Connections::default();
+	let mut pending: VecDeque<_> = effects.forwarded.into_iter().map(|f| (source, f)).collect();
+	while let Some((from, forwarded)) = pending.pop_front() {
+		if hops.len() >= MAX_HOPS {
+			break;
+		}
+		let Some(chain) = resolve_location(&from, &forwarded.destination) else { continue };
+		let client = connections.get(chain.url()).await?.client();
+		let origin = relative_location(&chain, &from);
+		for message in forwarded.messages {
+			let execution = match buy_execution_asset(&message) {
+				Some(asset) => Some(Fee {
+					amount: execution_fee(&client, message.clone(), asset.clone()).await?,
+					asset,
+				}),
+				None => None,
+			};
+			let effects = dry_run_xcm(&client, origin.clone(), message).await?;
+			if let Some(error) = effects.error {
+				return Err(Error::FeeEstimationError(format!("{error} on {}", chain.name())));
+			}
+			hops.push(HopFees {
+				chain,
+				execution,
+				delivery: delivery_fees(&client, &effects.forwarded).await?,
+			});
+			pending.extend(effects.forwarded.into_iter().map(|f| (chain, f)));
+		}
+	}
+	Ok(hops)
+}
+
+// Queries the fees for delivering forwarded messages from a chain, via its `XcmPaymentApi`.
+async fn delivery_fees(
+	client: &OnlineClient<SubstrateConfig>,
+	forwarded: &[ForwardedXcm],
+) -> Result<Vec<Fee>, Error> {
+	let mut fees = Vec::new();
+	for ForwardedXcm { destination, messages } in forwarded {
+		for message in messages {
+			let assets = payment_api(
+				client,
+				"query_delivery_fees",
+				vec![destination.clone(), message.clone()],
+			)
+			.await?;
+			fees.extend(parse_assets(&assets));
+		}
+	}
+	Ok(fees)
+}
+
+// The asset with which execution of a message is bought, if any.
+fn buy_execution_asset(message: &Value) -> Option<Value> {
+	let (_, versioned) = variant(message)?;
+	let mut instructions = versioned.values().next()?;
+	// A message is a sequence of instructions, which may be wrapped in a newtype.
+	if let ValueDef::Composite(c) = &instructions.value {
+		if c.len() == 1 && c.values().all(|v| matches!(v.value, ValueDef::Composite(_))) {
+			instructions = c.values().next()?;
+		}
+	}
+	let ValueDef::Composite(instructions) = &instructions.value else { return None };
+	instructions.values().find_map(|i| match variant(i)? {
+		("BuyExecution", fields) => parse_asset(field(fields, "fees")?).map(|fee| fee.asset),
+		_ => None,
+	})
+}
+
+// Parses versioned assets into fees.
+fn parse_assets<T: Clone>(assets: &Value<T>) -> Vec<Fee> {
+	let Some((_, versioned)) = variant(assets) else { return vec![] };
+	let Some(mut assets) = versioned.values().next() else { return vec![] };
+	// Assets may be wrapped in a newtype.
+	if let ValueDef::Composite(c) = &assets.value {
+		if let (1, Some(inner @ Value { value: ValueDef::Composite(Composite::Unnamed(_)), .. })) =
+			(c.len(), c.values().next())
+		{
+			assets = inner;
+		}
+	}
+	let ValueDef::Composite(assets) = &assets.value else { return vec![] };
+	assets.values().filter_map(parse_asset).collect()
+}
+
+// Parses a fungible asset into a fee.
+fn parse_asset<T: Clone>(asset: &Value<T>) -> Option<Fee> {
+	let ValueDef::Composite(asset) = &asset.value else { return None };
+	// The location of an asset is wrapped in its identifier.
+	let id = field(asset, "id")?;
+	let location = match &id.value {
+		ValueDef::Composite(Composite::Unnamed(c)) if c.len() == 1 => &c[0],
+		_ => id,
+	};
+	let ("Fungible", amount) = variant(field(asset, "fun")?)? else { return None };
+	let amount = amount.values().next()?.as_u128()?;
+	Some(Fee { asset: location.clone().remove_context(), amount })
+}
+
+// Calls a method of the `XcmPaymentApi` of a chain, returning the successful result.
+async fn payment_api(
+	client: &OnlineClient<SubstrateConfig>,
+	method: &str,
+	args: Vec<Value>,
+) -> Result<Value<u32>, Error> {
+	if client
+		.metadata()
+		.runtime_api_trait_by_name("XcmPaymentApi")
+		.and_then(|api| api.method_by_name(method))
+		.is_none()
+	{
+		return Err(Error::UnsupportedCommand("the chain does not provide a XcmPaymentApi".into()));
+	}
+	let payload = dynamic::runtime_api_call("XcmPaymentApi", method, args);
+	let result = client.runtime_api().at_latest().await?.call(payload).await?;
+	let value = result
+		.to_value()
+		.map_err(|e| Error::MetadataParsingError(format!("{method} result: {e}")))?;
+	payment_result(value)
+}
+
+// Unwraps the result of a method of the `XcmPaymentApi`.
+fn payment_result(value: Value<u32>) -> Result<Value<u32>, Error> {
+	match value.value {
+		ValueDef::Variant(v) if v.name == "Ok" => v
+			.values
+			.into_values()
+			.next()
+			.ok_or_else(|| Error::MetadataParsingError("Ok".into())),
+		ValueDef::Variant(v) => {
+			let error =
+				v.values.values().next().and_then(variant).map(|(name, _)| name.to_string());
+			Err(Error::FeeEstimationError(error.unwrap_or_else(|| display(&v.values))))
+		},
+		_ => Err(Error::MetadataParsingError("payment result".into())),
+	}
+}
+
+// Checks that a chain provides the `DryRunApi` method, returning its number of inputs.
+fn dry_run_api(client: &OnlineClient<SubstrateConfig>, method: &str) -> Result<usize, Error> {
+	client
+		.metadata()
+		.runtime_api_trait_by_name("DryRunApi")
+		.and_then(|api| api.method_by_name(method).map(|m| m.inputs().len()))
+		.ok_or_else(|| Error::UnsupportedCommand("the chain does not provide a DryRunApi".into()))
+}
+
+async fn dry_run(
+	client: &OnlineClient<SubstrateConfig>,
+	method: &str,
+	args: Vec<Value>,
+) -> Result<DryRunEffects, Error> {
+	let payload = dynamic::runtime_api_call("DryRunApi", method, args);
+	let result = client.runtime_api().at_latest().await?.call(payload).await?;
+	let value = result
+		.to_value()
+		.map_err(|e| Error::MetadataParsingError(format!("dry run result: {e}")))?;
+	DryRunEffects::from_value(&value)
+}
+
+// The XCM pallet of a chain.
+fn pallet(chain: &Chain) -> &'static str {
+	match chain.relay() {
+		Some(_) => "PolkadotXcm",
+		None => "XcmPallet",
+	}
+}
+
+fn versioned(value: Value) -> Value {
+	Value::unnamed_variant(format!("V{XCM_VERSION}"), [value])
+}
+
+pub(crate) fn location(parents: u8, interior: Value) -> Value {
+	Value::named_composite([("parents", Value::u128(parents.into())), ("interior", interior)])
+}
+
+// The global consensus of a chain, as a junction.
+fn global_consensus(chain: &Chain) -> Value {
+	let network = match chain.chain_id() {
+		Some(id) => Value::named_variant("Ethereum", [("chain_id", Value::u128(id.into()))]),
+		// The names of relay chains match those of their networks.
+		None => Value::unnamed_variant(chain.consensus().name(), []),
+	};
+	Value::unnamed_variant("GlobalConsensus", [network])
+}
+
+fn here() -> Value {
+	Value::unnamed_variant("Here", [])
+}
+
+fn x1(junction: Value) -> Value {
+	Value::unnamed_variant("X1", [Value::unnamed_composite([junction])])
+}
+
+pub(crate) fn x2(first: Value, second: Value) -> Value {
+	Value::unnamed_variant("X2", [Value::unnamed_composite([first, second])])
+}
+
+fn account_id32(account: &AccountId32) -> Value {
+	Value::named_variant(
+		"AccountId32",
+		[("network", Value::unnamed_variant("None", [])), ("id", Value::from_bytes(account.0))],
+	)
+}
+
+fn variant<T>(value: &Value<T>) -> Option<(&str, &Composite<T>)> {
+	match &value.value {
+		ValueDef::Variant(v) => Some((v.name.as_str(), &v.values)),
+		_ => None,
+	}
+}
+
+fn field<'a, T>(composite: &'a Composite<T>, name: &str) -> Option<&'a Value<T>> {
+	match composite {
+		Composite::Named(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+		Composite::Unnamed(_) => None,
+	}
+}
+
+fn display<T>(composite: &Composite<T>) -> String {
+	composite.values().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+	fn transfer(source: Chain, destination: Chain, transfer_type: TransferType) -> Transfer {
+		Transfer {
+			source,
+			destination,
+			transfer_type,
+			asset: Asset::Relay,
+			amount: 10_000_000_000,
+			beneficiary: Beneficiary::AccountId32(AccountId32::from_str(ALICE).unwrap()),
+		}
+	}
+
+	#[test]
+	fn supported_transfer_types_works() {
+		use Chain::*;
+		use TransferType::*;
+		assert_eq!(TransferType::supported(&Paseo, &AssetHubPaseo), vec![Teleport, Reserve]);
+		assert_eq!(TransferType::supported(&AssetHubPaseo, &Paseo), vec![Teleport, Reserve]);
+		assert_eq!(TransferType::supported(&Paseo, &PopPaseo), vec![Reserve]);
+		assert_eq!(TransferType::supported(&AssetHubPaseo, &PopPaseo), vec![Reserve]);
+		assert!(TransferType::supported(&Paseo, &Paseo).is_empty());
+		assert!(TransferType::supported(&Polkadot, &AssetHubKusama).is_empty());
+		// Transfers beyond the consensus of a chain are via a bridge.
+		assert_eq!(TransferType::supported(&AssetHubPolkadot, &AssetHubKusama), vec![Reserve]);
+		assert_eq!(TransferType::supported(&AssetHubWestend, &Sepolia), vec![Reserve]);
+		assert!(TransferType::supported(&AssetHubPaseo, &Ethereum).is_empty());
+	}
+
+	#[test]
+	fn validate_works() {
+		assert!(transfer(Chain::Paseo, Chain::AssetHubPaseo, TransferType::Teleport)
+			.validate()
+			.is_ok());
+		assert!(matches!(
+			transfer(Chain::Paseo, Chain::PopPaseo, TransferType::Teleport).validate(),
+			Err(Error::UnsupportedTransfer(e)) if e == "a teleport is not supported from Paseo to Pop Network (Paseo)"
+		));
+		let mut empty = transfer(Chain::Paseo, Chain::PopPaseo, TransferType::Reserve);
+		empty.amount = 0;
+		assert!(matches!(empty.validate(), Err(Error::UnsupportedTransfer(..))));
+		// Accounts on Ethereum are 20 bytes.
+		let mut bridged = transfer(Chain::AssetHubPolkadot, Chain::Ethereum, TransferType::Reserve);
+		assert!(matches!(
+			bridged.validate(),
+			Err(Error::UnsupportedTransfer(e)) if e == "the beneficiary is not a valid account on Ethereum"
+		));
+		bridged.beneficiary = Beneficiary::AccountKey20([1; 20]);
+		assert!(bridged.validate().is_ok());
+		assert_eq!(bridged.bridge(), Some(Bridge::Snowbridge));
+		assert_eq!(transfer(Chain::Paseo, Chain::PopPaseo, TransferType::Reserve).bridge(), None);
+	}
+
+	#[test]
+	fn locations_work() {
+		let parachain = |id: u128| x1(Value::unnamed_variant("Parachain", [Value::u128(id)]));
+		// From a relay chain to a parachain.
+		let t = transfer(Chain::Paseo, Chain::AssetHubPaseo, TransferType::Teleport);
+		assert_eq!(t.pallet(), "XcmPallet");
+		assert_eq!(t.destination_location(), versioned(location(0, parachain(1000))));
+		// From a parachain to its relay chain.
+		let t = transfer(Chain::AssetHubPaseo, Chain::Paseo, TransferType::Teleport);
+		assert_eq!(t.pallet(), "PolkadotXcm");
+		assert_eq!(t.destination_location(), versioned(location(1, here())));
+		// From a parachain to a sibling.
+		let t = transfer(Chain::AssetHubPaseo, Chain::PopPaseo, TransferType::Reserve);
+		assert_eq!(t.destination_location(), versioned(location(1, parachain(4001))));
+		assert_eq!(
+			t.beneficiary_location(),
+			versioned(location(0, x1(account_id32(&AccountId32::from_str(ALICE).unwrap()))))
+		);
+	}
+
+	#[test]
+	fn beneficiary_works() {
+		let alice = AccountId32::from_str(ALICE).unwrap();
+		assert_eq!(Beneficiary::from_str(ALICE).unwrap(), Beneficiary::AccountId32(alice));
+		let key = "0x0101010101010101010101010101010101010101";
+		assert_eq!(Beneficiary::from_str(key).unwrap(), Beneficiary::AccountKey20([1; 20]));
+		assert!(matches!(Beneficiary::from_str("0x0101"), Err(Error::ParamProcessingError)));
+		let mut t = transfer(Chain::AssetHubPolkadot, Chain::Ethereum, TransferType::Reserve);
+		t.beneficiary = Beneficiary::AccountKey20([1; 20]);
+		assert_eq!(
+			t.beneficiary_location(),
+			versioned(location(
+				0,
+				x1(Value::named_variant(
+					"AccountKey20",
+					[
+						("network", Value::unnamed_variant("None", [])),
+						("key", Value::from_bytes([1; 20]))
+					],
+				))
+			))
+		);
+	}
+
+	#[test]
+	fn assets_work() {
+		let asset = |parents: u8| {
+			versioned(Value::unnamed_composite([Value::named_composite([
+				("id", Value::unnamed_composite([location(parents, here())])),
+				("fun", Value::unnamed_variant("Fungible", [Value::u128(10_000_000_000)])),
+			])]))
+		};
+		let mut t = transfer(Chain::Paseo, Chain::AssetHubPaseo, TransferType::Teleport);
+		assert_eq!(t.assets(), asset(0));
+		t = transfer(Chain::AssetHubPaseo, Chain::PopPaseo, TransferType::Reserve);
+		assert_eq!(t.assets(), asset(1));
+		t.asset = Asset::Native;
+		assert_eq!(t.assets(), asset(0));
+		let registered = location(0, x1(Value::unnamed_variant("GeneralIndex", [Value::u128(1)])));
+		t.asset = Asset::Registered(registered.clone());
+		assert_eq!(
+			t.assets(),
+			versioned(Value::unnamed_composite([Value::named_composite([
+				("id", Value::unnamed_composite([registered])),
+				("fun", Value::unnamed_variant("Fungible", [Value::u128(10_000_000_000)])),
+			])]))
+		);
+		assert_eq!("relay".parse::<Asset>().unwrap(), Asset::Relay);
+		assert_eq!("native".parse::<Asset>().unwrap(), Asset::Native);
+		assert!(matches!("usdt".parse::<Asset>(), Err(Error::ParamProcessingError)));
+	}
+
+	#[test]
+	fn construct_works() {
+		let xt = transfer(Chain::Paseo, Chain::AssetHubPaseo, TransferType::Teleport).construct();
+		assert_eq!(xt.pallet_name(), "XcmPallet");
+		assert_eq!(xt.call_name(), "limited_teleport_assets");
+		let xt = transfer(Chain::PopPaseo, Chain::AssetHubPaseo, TransferType::Reserve).construct();
+		assert_eq!(xt.pallet_name(), "PolkadotXcm");
+		assert_eq!(xt.call_name(), "transfer_assets");
+	}
+
+	#[test]
+	fn remote_call_works() {
+		let remote = RemoteCall {
+			source: Chain::AssetHubPaseo,
+			destination: Chain::PopPaseo,
+			origin: AccountId32::from_str(ALICE).unwrap(),
+			call: vec![40, 6],
+			weight: (1_000, 100),
+			fee: 500,
+		};
+		let account = account_id32(&remote.origin);
+		let parachain = Value::unnamed_variant("Parachain", [Value::u128(1000)]);
+		assert_eq!(remote.origin_location(), location(1, x2(parachain, account.clone())));
+		assert_eq!(
+			remote.fee_asset(),
+			Value::named_composite([
+				("id", Value::unnamed_composite([location(1, here())])),
+				("fun", Value::unnamed_variant("Fungible", [Value::u128(500)])),
+			])
+		);
+		assert_eq!(
+			instructions(&remote.message()),
+			["WithdrawAsset", "BuyExecution", "Transact", "RefundSurplus", "DepositAsset"]
+		);
+		let xt = remote.construct();
+		assert_eq!(xt.pallet_name(), "PolkadotXcm");
+		assert_eq!(xt.call_name(), "send");
+
+		// From a relay chain, the origin is the account itself.
+		let remote = RemoteCall { source: Chain::Paseo, ..remote };
+		assert_eq!(remote.pallet(), "XcmPallet");
+		assert_eq!(remote.origin_location(), location(1, x1(account)));
+	}
+
+	#[test]
+	fn fee_symbol_works() {
+		let fee = |parents| Fee { asset: location(parents, here()), amount: 1 };
+		assert_eq!(fee(0).symbol(&Chain::PopPaseo), Some("PAS"));
+		assert_eq!(fee(1).symbol(&Chain::AssetHubKusama), Some("KSM"));
+		assert_eq!(fee(0).symbol(&Chain::Westend), Some("WND"));
+		assert_eq!(fee(1).symbol(&Chain::Westend), None);
+		let registered = location(0, x1(Value::unnamed_variant("GeneralIndex", [Value::u128(1)])));
+		assert_eq!(Fee { asset: registered, amount: 1 }.symbol(&Chain::AssetHubPaseo), None);
+	}
+
+	#[test]
+	fn uncovered_fees_works() {
+		let fee = |amount| Fee { asset: location(1, here()), amount };
+		let hops = |amount| {
+			vec![
+				// Fees on the source are paid by the origin.
+				HopFees {
+					chain: Chain::PopPaseo,
+					execution: Some(fee(u128::MAX)),
+					delivery: vec![],
+				},
+				HopFees {
+					chain: Chain::AssetHubPaseo,
+					execution: Some(fee(amount)),
+					delivery: vec![fee(amount)],
+				},
+			]
+		};
+		let mut t = transfer(Chain::PopPaseo, Chain::AssetHubPaseo, TransferType::Reserve);
+		assert_eq!(t.uncovered_fees(&hops(1_000)), None);
+		assert_eq!(t.uncovered_fees(&hops(t.amount / 2)), Some(t.amount));
+		t.asset = Asset::Native;
+		assert_eq!(t.uncovered_fees(&hops(t.amount)), None);
+	}
+
+	#[test]
+	fn buy_execution_asset_works() {
+		let remote = RemoteCall {
+			source: Chain::AssetHubPaseo,
+			destination: Chain::PopPaseo,
+			origin: AccountId32::from_str(ALICE).unwrap(),
+			call: vec![],
+			weight: (0, 0),
+			fee: 1,
+		};
+		assert_eq!(buy_execution_asset(&remote.message()), Some(location(1, here())));
+		let unpaid =
+			versioned(Value::unnamed_composite([Value::unnamed_variant("UnpaidExecution", [])]));
+		assert_eq!(buy_execution_asset(&unpaid), None);
+	}
+
+	#[test]
+	fn parse_assets_works() {
+		let asset = |parents, amount| {
+			Value::named_composite([
+				("id", Value::unnamed_composite([location(parents, here())])),
+				("fun", Value::unnamed_variant("Fungible", [Value::u128(amount)])),
+			])
+		};
+		let expected = vec![
+			Fee { asset: location(1, here()), amount: 100 },
+			Fee { asset: location(0, here()), amount: 5 },
+		];
+		// Assets are a sequence, which may be wrapped in a newtype.
+		let assets = Value::unnamed_composite([asset(1, 100), asset(0, 5)]);
+		assert_eq!(parse_assets(&versioned(assets.clone()).map_context(|_| 0u32)), expected);
+		let wrapped = versioned(Value::unnamed_composite([assets]));
+		assert_eq!(parse_assets(&wrapped.map_context(|_| 0u32)), expected);
+		assert!(parse_assets(&Value::u128(0)).is_empty());
+	}
+
+	#[test]
+	fn payment_result_works() {
+		let ok = Value::unnamed_variant("Ok", [Value::u128(1_000)]).map_context(|_| 0u32);
+		assert_eq!(payment_result(ok).unwrap().as_u128(), Some(1_000));
+		let err = Value::unnamed_variant("Err", [Value::unnamed_variant("AssetNotFound", [])])
+			.map_context(|_| 0u32);
+		assert!(matches!(
+			payment_result(err),
+			Err(Error::FeeEstimationError(e)) if e == "AssetNotFound"
+		));
+	}
+
+	#[test]
+	fn dry_run_effects_from_value_works() -> Result<(), Error> {
+		let effects = |result: Value| {
+			Value::unnamed_variant(
+				"Ok",
+				[Value::named_composite([
+					("execution_result", result),
+					("emitted_events", Value::unnamed_composite([])),
+				])],
+			)
+			.map_context(|_| 0u32)
+		};
+		let ok = effects(Value::unnamed_variant("Ok", [Value::unnamed_composite([])]));
+		assert!(DryRunEffects::from_value(&ok)?.success());
+
+		let error = Value::unnamed_variant("Module", [Value::string("TooExpensive")]);
+		let failed = effects(Value::unnamed_variant(
+			"Err",
+			[Value::named_composite([
+				("post_info", Value::unnamed_composite([])),
+				("error", error.clone()),
+			])],
+		));
+		assert_eq!(
+			DryRunEffects::from_value(&failed)?,
+			DryRunEffects { error: Some(error.to_string()), forwarded: vec![] }
+		);
+
+		let unavailable =
+			Value::unnamed_variant("Err", [Value::unnamed_variant("Unimplemented", [])])
+				.map_context(|_| 0u32);
+		assert!(matches!(
+			DryRunEffects::from_value(&unavailable),
+			Err(Error::ExtrinsicSubmissionError(..))
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn dry_run_effects_of_message_works() -> Result<(), Error> {
+		let message = versioned(Value::unnamed_composite([Value::unnamed_composite([
+			Value::unnamed_variant("ClearOrigin", []),
+		])]));
+		let destination = versioned(location(1, here()));
+		let effects = |outcome: Value| {
+			Value::unnamed_variant(
+				"Ok",
+				[Value::named_composite([
+					("execution_result", outcome),
+					("emitted_events", Value::unnamed_composite([])),
+					(
+						"forwarded_xcms",
+						Value::unnamed_composite([Value::unnamed_composite([
+							destination.clone(),
+							Value::unnamed_composite([message.clone()]),
+						])]),
+					),
+				])],
+			)
+			.map_context(|_| 0u32)
+		};
+		let complete = effects(Value::named_variant("Complete", [("used", Value::u128(0))]));
+		assert_eq!(
+			DryRunEffects::from_value(&complete)?,
+			DryRunEffects {
+				error: None,
+				forwarded: vec![ForwardedXcm {
+					destination: destination.clone(),
+					messages: vec![message.clone()]
+				}],
+			}
+		);
+		let error = Value::unnamed_variant("Barrier", []);
+		let incomplete = effects(Value::named_variant(
+			"Incomplete",
+			[("used", Value::u128(0)), ("error", error.clone())],
+		));
+		assert_eq!(DryRunEffects::from_value(&incomplete)?.error, Some(error.to_string()));
+		Ok(())
+	}
+
+	#[test]
+	fn relative_location_works() {
+		let parachain = |id: u128| x1(Value::unnamed_variant("Parachain", [Value::u128(id)]));
+		for (from, to, expected) in [
+			(Chain::Paseo, Chain::PopPaseo, versioned(location(0, parachain(4001)))),
+			(Chain::PopPaseo, Chain::Paseo, versioned(location(1, here()))),
+			(Chain::PopPaseo, Chain::AssetHubPaseo, versioned(location(1, parachain(1000)))),
+		] {
+			assert_eq!(relative_location(&from, &to), expected);
+			assert_eq!(resolve_location(&from, &expected), Some(to));
+		}
+		// Chains beyond the consensus of the other are located via their global consensus.
+		let polkadot =
+			Value::unnamed_variant("GlobalConsensus", [Value::unnamed_variant("Polkadot", [])]);
+		assert_eq!(
+			relative_location(&Chain::AssetHubKusama, &Chain::AssetHubPolkadot),
+			versioned(location(
+				2,
+				x2(polkadot, Value::unnamed_variant("Parachain", [Value::u128(1000)]))
+			))
+		);
+		let ethereum = Value::unnamed_variant(
+			"GlobalConsensus",
+			[Value::named_variant("Ethereum", [("chain_id", Value::u128(1))])],
+		);
+		assert_eq!(
+			relative_location(&Chain::AssetHubPolkadot, &Chain::Ethereum),
+			versioned(location(2, x1(ethereum)))
+		);
+		// Locations which are not known chains.
+		assert_eq!(resolve_location(&Chain::Paseo, &versioned(location(0, here()))), None);
+		assert_eq!(resolve_location(&Chain::Paseo, &versioned(location(1, here()))), None);
+		assert_eq!(
+			resolve_location(
+				&Chain::Paseo,
+				&versioned(location(
+					0,
+					x1(Value::unnamed_variant("Parachain", [Value::u128(3000)]))
+				))
+			),
+			None
+		);
+	}
+
+	#[test]
+	fn instructions_works() {
+		let message = |instructions: Vec<Value>| versioned(Value::unnamed_composite(instructions));
+		let withdraw = Value::unnamed_variant("WithdrawAsset", [Value::unnamed_composite([])]);
+		let deposit = Value::named_variant("DepositAsset", [("assets", Value::u128(0))]);
+		assert_eq!(
+			instructions(&message(vec![withdraw.clone(), deposit])),
+			vec!["WithdrawAsset", "DepositAsset"]
+		);
+		// Instructions wrapped in a newtype.
+		assert_eq!(
+			instructions(&message(vec![Value::unnamed_composite([withdraw])])),
+			vec!["WithdrawAsset"]
+		);
+		assert!(instructions(&Value::u128(0)).is_empty());
+	}
+}